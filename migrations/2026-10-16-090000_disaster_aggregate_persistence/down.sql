@@ -0,0 +1,35 @@
+-- This file should undo anything in `up.sql`
+
+DROP INDEX IF EXISTS idx_disasters_primary_location;
+DROP INDEX IF EXISTS idx_disasters_status;
+DROP INDEX IF EXISTS idx_locations_geometry;
+
+ALTER TABLE disaster_analytics
+    DROP CONSTRAINT IF EXISTS disaster_analytics_disaster_id_key,
+    DROP COLUMN IF EXISTS damaged_buildings,
+    DROP COLUMN IF EXISTS displaced_people,
+    DROP COLUMN IF EXISTS injuries,
+    DROP COLUMN IF EXISTS casualties;
+
+ALTER TABLE disasters ALTER COLUMN status SET DEFAULT 'active';
+UPDATE disasters SET status = 'active' WHERE status IN ('reported', 'verified');
+UPDATE disasters SET status = 'contained' WHERE status = 'responded';
+
+ALTER TABLE disasters
+    DROP COLUMN IF EXISTS version,
+    DROP COLUMN IF EXISTS confidence_score,
+    DROP COLUMN IF EXISTS verification_method,
+    DROP COLUMN IF EXISTS verified_by,
+    DROP COLUMN IF EXISTS is_verified,
+    DROP COLUMN IF EXISTS closed_at,
+    DROP COLUMN IF EXISTS first_response_at,
+    DROP COLUMN IF EXISTS verified_at,
+    DROP COLUMN IF EXISTS reported_at,
+    DROP COLUMN IF EXISTS resource_needs,
+    DROP COLUMN IF EXISTS assigned_responders,
+    DROP COLUMN IF EXISTS priority,
+    DROP COLUMN IF EXISTS disaster_type_other;
+
+UPDATE disasters SET severity = 5 WHERE severity = 6;
+ALTER TABLE disasters DROP CONSTRAINT IF EXISTS disasters_severity_check;
+ALTER TABLE disasters ADD CONSTRAINT disasters_severity_check CHECK (severity BETWEEN 1 AND 5);
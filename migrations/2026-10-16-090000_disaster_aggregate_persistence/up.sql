@@ -0,0 +1,67 @@
+-- Persist the full Disaster aggregate (timeline, verification, responders,
+-- resource needs, estimated damage) and support optimistic concurrency.
+
+-- Canonical disaster types used by the domain DisasterType mapping
+INSERT INTO disaster_types (id, name, description, severity) VALUES
+(1, 'Banjir', 'Bencana akibat meluapnya air yang menggenangi daratan', 3),
+(2, 'Gempa Bumi', 'Getaran atau guncangan yang terjadi di permukaan bumi', 4),
+(3, 'Kebakaran Hutan', 'Kebakaran yang terjadi di kawasan hutan', 3),
+(4, 'Tanah Longsor', 'Perpindahan material pembentuk lereng berupa batuan, tanah, atau material campuran', 3),
+(5, 'Tsunami', 'Gelombang air laut yang sangat besar yang disebabkan oleh gangguan di dasar laut', 5),
+(6, 'Letusan Gunung Berapi', 'Pelepasan magma, gas, dan abu dari gunung berapi', 4),
+(7, 'Angin Topan', 'Angin kencang yang berputar dan bergerak dengan kecepatan tinggi', 3),
+(8, 'Kekeringan', 'Kondisi kekurangan air dalam jangka waktu yang lama', 2),
+(9, 'Wabah Penyakit', 'Penyebaran penyakit menular dalam skala luas', 3),
+(10, 'Kecelakaan Industri', 'Bencana yang terjadi di kawasan industri seperti kebocoran bahan kimia', 3)
+ON CONFLICT (id) DO NOTHING;
+SELECT setval('disaster_types_id_seq', GREATEST((SELECT MAX(id) FROM disaster_types), 1));
+
+-- Severity 6 = catastrophic
+ALTER TABLE disasters DROP CONSTRAINT IF EXISTS disasters_severity_check;
+ALTER TABLE disasters ADD CONSTRAINT disasters_severity_check CHECK (severity BETWEEN 1 AND 6);
+
+ALTER TABLE disasters
+    ADD COLUMN disaster_type_other TEXT,                                   -- label untuk DisasterType::Other
+    ADD COLUMN priority            INTEGER NOT NULL DEFAULT 2,             -- prioritas (1-5)
+    ADD COLUMN assigned_responders UUID[]  NOT NULL DEFAULT '{}',          -- responder yang ditugaskan
+    ADD COLUMN resource_needs      JSONB   NOT NULL DEFAULT '[]',          -- kebutuhan sumber daya
+    ADD COLUMN reported_at         TIMESTAMP,                              -- waktu dilaporkan
+    ADD COLUMN verified_at         TIMESTAMP,                              -- waktu diverifikasi
+    ADD COLUMN first_response_at   TIMESTAMP,                              -- waktu respon pertama
+    ADD COLUMN closed_at           TIMESTAMP,                              -- waktu ditutup
+    ADD COLUMN is_verified         BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN verified_by         UUID REFERENCES users (id) ON DELETE SET NULL,
+    ADD COLUMN verification_method TEXT,
+    ADD COLUMN confidence_score    REAL,                                   -- skor keyakinan (0.0-1.0)
+    ADD COLUMN version             BIGINT  NOT NULL DEFAULT 1;             -- versi untuk optimistic locking
+
+-- Align legacy lifecycle values with the domain DisasterStatus
+UPDATE disasters SET status = 'verified', is_verified = TRUE WHERE status = 'active';
+UPDATE disasters SET status = 'responded', is_verified = TRUE WHERE status = 'contained';
+UPDATE disasters SET reported_at = created_at WHERE reported_at IS NULL;
+ALTER TABLE disasters ALTER COLUMN status SET DEFAULT 'reported';
+
+-- One analytics row per disaster, with the full damage estimate; keep the most recently
+-- updated row (undated rows last, ties broken on id)
+DELETE FROM disaster_analytics
+    WHERE id IN (
+        SELECT id FROM (
+            SELECT id, row_number() OVER (
+                PARTITION BY disaster_id
+                ORDER BY updated_at DESC NULLS LAST, id DESC
+            ) AS rank
+            FROM disaster_analytics
+            WHERE disaster_id IS NOT NULL
+        ) ranked
+        WHERE ranked.rank > 1
+    );
+ALTER TABLE disaster_analytics
+    ADD COLUMN casualties        INTEGER,                                  -- korban jiwa
+    ADD COLUMN injuries          INTEGER,                                  -- korban luka
+    ADD COLUMN displaced_people  INTEGER,                                  -- pengungsi
+    ADD COLUMN damaged_buildings INTEGER,                                  -- bangunan rusak
+    ADD CONSTRAINT disaster_analytics_disaster_id_key UNIQUE (disaster_id);
+
+CREATE INDEX IF NOT EXISTS idx_locations_geometry ON locations USING GIST (geometry);
+CREATE INDEX IF NOT EXISTS idx_disasters_status ON disasters (status);
+CREATE INDEX IF NOT EXISTS idx_disasters_primary_location ON disasters (primary_location_id);
@@ -0,0 +1,7 @@
+DROP INDEX IF EXISTS idx_locations_location_type;
+DROP INDEX IF EXISTS idx_locations_province;
+DROP INDEX IF EXISTS idx_locations_region;
+DROP INDEX IF EXISTS idx_locations_name_trgm;
+
+ALTER TABLE locations
+    DROP COLUMN IF EXISTS details;
@@ -0,0 +1,13 @@
+-- Location details and name search
+-- Domain-only location attributes (type, administrative level, risk factors,
+-- emergency contacts, ...) are kept in a JSONB side column
+
+CREATE EXTENSION IF NOT EXISTS "pg_trgm";
+
+ALTER TABLE locations
+    ADD COLUMN details JSONB NOT NULL DEFAULT '{}';
+
+CREATE INDEX IF NOT EXISTS idx_locations_name_trgm ON locations USING GIN (name gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_locations_region ON locations (lower(region));
+CREATE INDEX IF NOT EXISTS idx_locations_province ON locations (lower(province));
+CREATE INDEX IF NOT EXISTS idx_locations_location_type ON locations ((details -> 'location_type'));
@@ -0,0 +1,21 @@
+-- Append-only event store
+-- `sequence` gives a global replay order for projections; `event_version`
+-- is the per-aggregate version used for optimistic concurrency
+
+CREATE TABLE event_store
+(
+    sequence       BIGSERIAL   NOT NULL UNIQUE,
+    event_id       UUID PRIMARY KEY,
+    aggregate_id   UUID        NOT NULL,
+    aggregate_type TEXT        NOT NULL,
+    event_type     TEXT        NOT NULL,
+    event_version  BIGINT      NOT NULL CHECK (event_version > 0),
+    event_data     JSONB       NOT NULL,
+    metadata       JSONB       NOT NULL DEFAULT '{}',
+    occurred_at    TIMESTAMPTZ NOT NULL,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT event_store_aggregate_version_key UNIQUE (aggregate_id, event_version)
+);
+
+CREATE INDEX idx_event_store_event_type ON event_store (event_type, occurred_at);
+CREATE INDEX idx_event_store_aggregate_type ON event_store (aggregate_type, sequence);
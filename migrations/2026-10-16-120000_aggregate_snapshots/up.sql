@@ -0,0 +1,14 @@
+-- Latest snapshot per event-sourced aggregate
+-- `version` is the stream version the state was taken at; replay continues
+-- from the events stored after it
+
+CREATE TABLE aggregate_snapshots
+(
+    aggregate_id   UUID PRIMARY KEY,
+    aggregate_type TEXT        NOT NULL,
+    version        BIGINT      NOT NULL CHECK (version > 0),
+    state          JSONB       NOT NULL,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_aggregate_snapshots_type ON aggregate_snapshots (aggregate_type);
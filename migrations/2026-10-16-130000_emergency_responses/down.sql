@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS emergency_response_status_history;
+DROP TABLE IF EXISTS emergency_responses;
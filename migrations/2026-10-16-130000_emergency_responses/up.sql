@@ -0,0 +1,51 @@
+-- Emergency responses dispatched to a disaster
+-- Lifecycle: dispatched -> en_route -> on_scene -> completed
+
+CREATE TABLE emergency_responses
+(
+    id                    UUID PRIMARY KEY,
+    disaster_id           UUID             NOT NULL REFERENCES disasters (id) ON DELETE CASCADE,
+    response_type         TEXT             NOT NULL,
+    priority              SMALLINT         NOT NULL CHECK (priority BETWEEN 1 AND 5),
+    status                TEXT             NOT NULL DEFAULT 'dispatched'
+        CHECK (status IN ('dispatched', 'en_route', 'on_scene', 'completed')),
+    dispatched_by         UUID             NOT NULL REFERENCES users (id),
+    assigned_teams        UUID[]           NOT NULL DEFAULT '{}',
+    personnel_count       INTEGER          NOT NULL DEFAULT 0,
+    equipment             TEXT[]           NOT NULL DEFAULT '{}',
+    destination_latitude  DOUBLE PRECISION NOT NULL,
+    destination_longitude DOUBLE PRECISION NOT NULL,
+    last_latitude         DOUBLE PRECISION,
+    last_longitude        DOUBLE PRECISION,
+    estimated_arrival     TIMESTAMPTZ,
+    estimated_completion  TIMESTAMPTZ,
+    notes                 TEXT,
+    dispatched_at         TIMESTAMPTZ      NOT NULL,
+    en_route_at           TIMESTAMPTZ,
+    on_scene_at           TIMESTAMPTZ,
+    completed_at          TIMESTAMPTZ,
+    created_at            TIMESTAMPTZ      NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at            TIMESTAMPTZ      NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    version               BIGINT           NOT NULL DEFAULT 1
+);
+
+CREATE INDEX idx_emergency_responses_disaster ON emergency_responses (disaster_id);
+CREATE INDEX idx_emergency_responses_active ON emergency_responses (status, dispatched_at)
+    WHERE status <> 'completed';
+
+-- Append-only record of every status change
+CREATE TABLE emergency_response_status_history
+(
+    id          BIGSERIAL PRIMARY KEY,
+    response_id UUID        NOT NULL REFERENCES emergency_responses (id) ON DELETE CASCADE,
+    from_status TEXT,
+    to_status   TEXT        NOT NULL,
+    changed_by  UUID        NOT NULL,
+    latitude    DOUBLE PRECISION,
+    longitude   DOUBLE PRECISION,
+    notes       TEXT,
+    changed_at  TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_emergency_response_history_response
+    ON emergency_response_status_history (response_id, changed_at);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS response_teams;
+DROP TABLE IF EXISTS emergency_stations;
@@ -0,0 +1,42 @@
+-- Registry of emergency stations and the response teams dispatched from them
+
+CREATE TABLE emergency_stations
+(
+    id             UUID PRIMARY KEY,
+    name           TEXT             NOT NULL,
+    station_type   TEXT             NOT NULL CHECK (station_type IN ('medical', 'fire', 'rescue', 'police')),
+    latitude       DOUBLE PRECISION NOT NULL,
+    longitude      DOUBLE PRECISION NOT NULL,
+    address        TEXT,
+    contact_phone  TEXT,
+    staff_count    INTEGER          NOT NULL DEFAULT 0 CHECK (staff_count >= 0),
+    vehicles       TEXT[]           NOT NULL DEFAULT '{}',
+    is_operational BOOLEAN          NOT NULL DEFAULT TRUE,
+    created_at     TIMESTAMPTZ      NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at     TIMESTAMPTZ      NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- current_response_id is claimed before the response row is written, so it carries no FK
+CREATE TABLE response_teams
+(
+    id                  UUID PRIMARY KEY,
+    station_id          UUID             REFERENCES emergency_stations (id) ON DELETE SET NULL,
+    name                TEXT             NOT NULL,
+    team_type           TEXT             NOT NULL CHECK (team_type IN ('medical', 'fire', 'rescue', 'police')),
+    latitude            DOUBLE PRECISION NOT NULL,
+    longitude           DOUBLE PRECISION NOT NULL,
+    location_updated_at TIMESTAMPTZ      NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    personnel           INTEGER          NOT NULL CHECK (personnel > 0),
+    vehicles            TEXT[]           NOT NULL DEFAULT '{}',
+    availability        TEXT             NOT NULL DEFAULT 'available'
+        CHECK (availability IN ('available', 'dispatched', 'off_duty', 'out_of_service')),
+    current_response_id UUID,
+    created_at          TIMESTAMPTZ      NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at          TIMESTAMPTZ      NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    version             BIGINT           NOT NULL DEFAULT 1
+);
+
+CREATE INDEX idx_response_teams_type_availability ON response_teams (team_type, availability);
+CREATE INDEX idx_response_teams_station ON response_teams (station_id);
+CREATE INDEX idx_response_teams_response ON response_teams (current_response_id)
+    WHERE current_response_id IS NOT NULL;
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_volunteer_locations_volunteer_recorded;
+DROP INDEX IF EXISTS idx_volunteer_locations_geometry;
+DROP TABLE IF EXISTS user_address_geocodes;
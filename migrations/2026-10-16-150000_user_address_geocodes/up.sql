@@ -0,0 +1,11 @@
+-- Geocoded home addresses, used with volunteer location fixes to target geofenced alerts
+CREATE TABLE user_address_geocodes (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    address TEXT NOT NULL,
+    geometry GEOGRAPHY(Point, 4326) NOT NULL,
+    geocoded_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_user_address_geocodes_geometry ON user_address_geocodes USING GIST (geometry);
+CREATE INDEX IF NOT EXISTS idx_volunteer_locations_geometry ON volunteer_locations USING GIST (geometry);
+CREATE INDEX IF NOT EXISTS idx_volunteer_locations_volunteer_recorded ON volunteer_locations (volunteer_id, recorded_at DESC);
@@ -0,0 +1,41 @@
+-- Durable outbox of per-channel notification deliveries, drained by background workers
+CREATE TABLE notification_outbox (
+    id UUID PRIMARY KEY,
+    notification_id UUID NOT NULL,
+    recipient_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    channel VARCHAR(20) NOT NULL,
+    notification_type VARCHAR(50) NOT NULL,
+    title TEXT NOT NULL,
+    message TEXT NOT NULL,
+    priority SMALLINT NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'processing', 'sent', 'dead_lettered')),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    max_attempts INTEGER NOT NULL,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    locked_by TEXT,
+    locked_until TIMESTAMPTZ,
+    last_error TEXT,
+    expires_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    sent_at TIMESTAMPTZ,
+    dead_lettered_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_notification_outbox_due ON notification_outbox (priority DESC, next_attempt_at)
+    WHERE status IN ('pending', 'processing');
+CREATE INDEX idx_notification_outbox_dead ON notification_outbox (dead_lettered_at DESC)
+    WHERE status = 'dead_lettered';
+
+CREATE TABLE notification_delivery_attempts (
+    id UUID PRIMARY KEY,
+    outbox_id UUID NOT NULL REFERENCES notification_outbox(id) ON DELETE CASCADE,
+    channel VARCHAR(20) NOT NULL,
+    attempted_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    success BOOLEAN NOT NULL,
+    error_message TEXT,
+    response_details TEXT
+);
+
+CREATE INDEX idx_notification_delivery_attempts_outbox ON notification_delivery_attempts (outbox_id, attempted_at);
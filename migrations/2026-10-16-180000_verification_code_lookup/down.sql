@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_verification_codes_user_issued;
+DROP INDEX IF EXISTS idx_verification_codes_lookup;
@@ -0,0 +1,5 @@
+-- Verification codes are now stored hashed and looked up by hash
+UPDATE verification_codes SET is_used = TRUE WHERE is_used IS NOT TRUE;
+
+CREATE INDEX idx_verification_codes_lookup ON verification_codes (type, code) WHERE is_used IS NOT TRUE;
+CREATE INDEX idx_verification_codes_user_issued ON verification_codes (user_id, type, created_at);
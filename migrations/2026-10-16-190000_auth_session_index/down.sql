@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS idx_auth_sessions_user_active;
+ALTER TABLE auth_sessions DROP COLUMN IF EXISTS revoked_at;
+ALTER TABLE auth_sessions DROP COLUMN IF EXISTS device_fingerprint;
+DELETE FROM auth_sessions WHERE token IS NULL;
+ALTER TABLE auth_sessions ALTER COLUMN token SET NOT NULL;
@@ -0,0 +1,10 @@
+-- Per-user session index so sessions can be listed and revoked without scanning the cache
+-- Rows are keyed by session id; bearer tokens stay with the client and are no longer stored
+ALTER TABLE auth_sessions ALTER COLUMN token DROP NOT NULL;
+ALTER TABLE auth_sessions ADD COLUMN device_fingerprint TEXT;
+ALTER TABLE auth_sessions ADD COLUMN revoked_at TIMESTAMP;
+
+-- Rows written before sessions were indexed do not correspond to live sessions
+UPDATE auth_sessions SET revoked_at = CURRENT_TIMESTAMP;
+
+CREATE INDEX idx_auth_sessions_user_active ON auth_sessions (user_id, created_at DESC) WHERE revoked_at IS NULL;
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_emergency_resources_organization;
+DROP INDEX IF EXISTS idx_organization_members_user;
+ALTER TABLE organization_members DROP CONSTRAINT IF EXISTS organization_members_role_check;
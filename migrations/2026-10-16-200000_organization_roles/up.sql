@@ -0,0 +1,12 @@
+-- Organization-scoped roles: memberships carry one of a fixed set of roles
+UPDATE organization_members SET role = LOWER(TRIM(role));
+UPDATE organization_members SET role = 'member'
+WHERE role NOT IN ('admin', 'coordinator', 'volunteer', 'member');
+
+ALTER TABLE organization_members
+    ADD CONSTRAINT organization_members_role_check
+    CHECK (role IN ('admin', 'coordinator', 'volunteer', 'member'));
+
+-- Membership lookups by user on every organization-scoped request
+CREATE INDEX idx_organization_members_user ON organization_members (user_id);
+CREATE INDEX idx_emergency_resources_organization ON emergency_resources (organization_id);
@@ -0,0 +1,9 @@
+-- Provinces a coordinator is assigned to; access policies compare them with a disaster's province
+CREATE TABLE user_province_assignments
+(
+    user_id     UUID        NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    province    TEXT        NOT NULL,
+    assigned_by UUID REFERENCES users (id) ON DELETE SET NULL,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, province)
+);
@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_user_roles_role;
+DROP TABLE IF EXISTS role_audit_log;
+DROP TABLE IF EXISTS role_permissions;
+ALTER TABLE roles DROP COLUMN IF EXISTS is_system;
@@ -0,0 +1,94 @@
+-- Database-backed roles: permissions per role, built-in roles that cannot be removed and an audit trail
+ALTER TABLE roles ADD COLUMN is_system BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE role_permissions
+(
+    role_id    INTEGER   NOT NULL REFERENCES roles (id) ON DELETE CASCADE,
+    permission TEXT      NOT NULL, -- e.g. reports:write, see Permission::as_str
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (role_id, permission)
+);
+
+-- Role changes are kept after the role itself is deleted, so the role is recorded by id and name only
+CREATE TABLE role_audit_log
+(
+    id             UUID PRIMARY KEY     DEFAULT uuid_generate_v4(),
+    actor_id       UUID REFERENCES users (id) ON DELETE SET NULL,
+    action         TEXT        NOT NULL CHECK (action IN ('created', 'updated', 'deleted', 'assigned', 'revoked')),
+    role_id        INTEGER     NOT NULL,
+    role_name      TEXT        NOT NULL,
+    target_user_id UUID REFERENCES users (id) ON DELETE SET NULL,
+    details        JSONB       NOT NULL DEFAULT '{}'::jsonb,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_role_audit_log_created ON role_audit_log (created_at DESC);
+CREATE INDEX idx_role_audit_log_role ON role_audit_log (role_id, created_at DESC);
+CREATE INDEX idx_user_roles_role ON user_roles (role_id);
+
+-- Built-in roles mirror UserRole; their permissions start from the former hardcoded defaults
+INSERT INTO roles (name, description, is_system) VALUES
+('reporter', 'Regular user who can submit disaster reports', TRUE),
+('volunteer', 'Volunteer who can validate reports and assist in disaster response', TRUE),
+('coordinator', 'Emergency coordinator who manages responses and volunteers', TRUE),
+('org_admin', 'Organization administrator with elevated permissions', TRUE),
+('admin', 'Administrator with full access', TRUE),
+('system_admin', 'System administrator with every permission', TRUE),
+('super_admin', 'Super administrator with every permission', TRUE),
+('citizen', 'Member of the public with read-only access', TRUE),
+('responder', 'Field responder handling emergencies', TRUE)
+ON CONFLICT (name) DO UPDATE SET is_system = TRUE;
+
+INSERT INTO role_permissions (role_id, permission)
+SELECT r.id, p.permission
+FROM roles r
+         JOIN (VALUES
+                   ('citizen', 'reports:read'), ('citizen', 'data:read_public'),
+
+                   ('reporter', 'reports:read'), ('reporter', 'reports:write'), ('reporter', 'alerts:read'),
+                   ('reporter', 'data:read_public'),
+
+                   ('volunteer', 'reports:read'), ('volunteer', 'reports:write'), ('volunteer', 'alerts:read'),
+                   ('volunteer', 'data:read_public'), ('volunteer', 'reports:update'), ('volunteer', 'volunteer:manage'),
+
+                   ('coordinator', 'reports:read'), ('coordinator', 'reports:write'), ('coordinator', 'alerts:read'),
+                   ('coordinator', 'data:read_public'), ('coordinator', 'reports:update'),
+                   ('coordinator', 'volunteer:manage'), ('coordinator', 'emergency:manage'), ('coordinator', 'area:write'),
+
+                   ('responder', 'reports:read'), ('responder', 'reports:write'), ('responder', 'alerts:read'),
+                   ('responder', 'data:read_public'), ('responder', 'reports:update'),
+                   ('responder', 'volunteer:manage'), ('responder', 'emergency:manage'), ('responder', 'area:write')
+              ) AS p (role_name, permission) ON p.role_name = r.name
+ON CONFLICT DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission)
+SELECT r.id, p.permission
+FROM roles r
+         CROSS JOIN (VALUES
+                         ('reports:read'), ('reports:write'), ('alerts:read'), ('data:read_public'),
+                         ('reports:update'), ('volunteer:manage'), ('emergency:manage'), ('alerts:write'),
+                         ('users:manage'), ('analytics:read'), ('organization:manage')
+                    ) AS p (permission)
+WHERE r.name IN ('admin', 'org_admin')
+ON CONFLICT DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission)
+SELECT r.id, p.permission
+FROM roles r
+         CROSS JOIN (VALUES
+                         ('reports:read'), ('reports:write'), ('reports:delete'), ('reports:read_own'), ('reports:update'),
+                         ('alerts:read'), ('alerts:write'), ('alerts:delete'), ('alerts:manage'),
+                         ('emergency:manage'), ('emergency:update_status'), ('emergency:assign'),
+                         ('volunteers:manage'), ('volunteers:respond'), ('volunteers:read'), ('volunteer:manage'),
+                         ('users:manage'), ('users:read_profiles'), ('users:update_profiles'), ('users:delete'),
+                         ('analytics:read'), ('analytics:write'), ('analytics:read_advanced'), ('area:write'),
+                         ('organization:manage'), ('organization:read'), ('organization:write'),
+                         ('system:manage'), ('system:read'), ('system:write'), ('system:config'),
+                         ('data:read_public'), ('data:read_coordinator'),
+                         ('profile:read_own'), ('profile:update_own'), ('profile:read_all'),
+                         ('locations:manage'), ('locations:read'), ('locations:update'),
+                         ('resources:manage'), ('resources:read'), ('resources:update'),
+                         ('notifications:send'), ('notifications:manage_templates'), ('notifications:read_history')
+                    ) AS p (permission)
+WHERE r.name IN ('system_admin', 'super_admin')
+ON CONFLICT DO NOTHING;
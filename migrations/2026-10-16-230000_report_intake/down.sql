@@ -0,0 +1,34 @@
+-- Revert report intake columns and restore the legacy report statuses
+DELETE FROM role_permissions WHERE permission = 'reports:validate';
+
+DROP INDEX IF EXISTS idx_disaster_reports_report;
+DROP INDEX IF EXISTS idx_report_history_report;
+DROP INDEX IF EXISTS idx_report_comments_report;
+DROP INDEX IF EXISTS idx_report_media_report;
+DROP INDEX IF EXISTS idx_reports_reporter;
+DROP INDEX IF EXISTS idx_reports_status_created;
+
+ALTER TABLE disaster_reports DROP COLUMN IF EXISTS linked_by;
+
+ALTER TABLE report_media
+    DROP COLUMN IF EXISTS uploaded_by,
+    DROP COLUMN IF EXISTS size_bytes,
+    DROP COLUMN IF EXISTS content_type,
+    DROP COLUMN IF EXISTS storage_key;
+
+ALTER TABLE reports
+    DROP CONSTRAINT IF EXISTS reports_status_check,
+    ALTER COLUMN status SET DEFAULT 'pending';
+
+UPDATE reports
+SET status = CASE status
+                 WHEN 'reported' THEN 'pending'
+                 WHEN 'under_review' THEN 'pending'
+                 WHEN 'verified' THEN 'valid'
+                 WHEN 'rejected' THEN 'invalid'
+                 ELSE status
+    END;
+
+UPDATE report_history
+SET status_from = CASE status_from WHEN 'reported' THEN 'pending' WHEN 'under_review' THEN 'pending' WHEN 'verified' THEN 'valid' WHEN 'rejected' THEN 'invalid' ELSE status_from END,
+    status_to   = CASE status_to WHEN 'reported' THEN 'pending' WHEN 'under_review' THEN 'pending' WHEN 'verified' THEN 'valid' WHEN 'rejected' THEN 'invalid' ELSE status_to END;
@@ -0,0 +1,41 @@
+-- Report intake: report lifecycle statuses, stored media metadata, link provenance and the reports:validate permission
+UPDATE reports
+SET status = CASE status
+                 WHEN 'pending' THEN 'reported'
+                 WHEN 'valid' THEN 'verified'
+                 WHEN 'invalid' THEN 'rejected'
+                 ELSE COALESCE(status, 'reported')
+    END;
+
+UPDATE report_history
+SET status_from = CASE status_from WHEN 'pending' THEN 'reported' WHEN 'valid' THEN 'verified' WHEN 'invalid' THEN 'rejected' ELSE status_from END,
+    status_to   = CASE status_to WHEN 'pending' THEN 'reported' WHEN 'valid' THEN 'verified' WHEN 'invalid' THEN 'rejected' ELSE status_to END;
+
+ALTER TABLE reports
+    ALTER COLUMN status SET DEFAULT 'reported',
+    ADD CONSTRAINT reports_status_check
+        CHECK (status IN ('reported', 'under_review', 'verified', 'rejected', 'resolved'));
+
+-- Where an uploaded file lives in the configured media storage
+ALTER TABLE report_media
+    ADD COLUMN storage_key  TEXT,
+    ADD COLUMN content_type TEXT,
+    ADD COLUMN size_bytes   BIGINT,
+    ADD COLUMN uploaded_by  UUID REFERENCES users (id) ON DELETE SET NULL;
+
+-- Validator who linked a report to a disaster
+ALTER TABLE disaster_reports
+    ADD COLUMN linked_by UUID REFERENCES users (id) ON DELETE SET NULL;
+
+CREATE INDEX idx_reports_status_created ON reports (status, created_at DESC);
+CREATE INDEX idx_reports_reporter ON reports (reporter_id);
+CREATE INDEX idx_report_media_report ON report_media (report_id);
+CREATE INDEX idx_report_comments_report ON report_comments (report_id, created_at);
+CREATE INDEX idx_report_history_report ON report_history (report_id, created_at);
+CREATE INDEX idx_disaster_reports_report ON disaster_reports (report_id);
+
+INSERT INTO role_permissions (role_id, permission)
+SELECT r.id, 'reports:validate'
+FROM roles r
+WHERE r.name IN ('volunteer', 'coordinator', 'responder', 'admin', 'org_admin', 'system_admin', 'super_admin')
+ON CONFLICT DO NOTHING;
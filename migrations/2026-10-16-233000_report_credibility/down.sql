@@ -0,0 +1,8 @@
+-- Revert report credibility factors and incident clusters
+DROP INDEX IF EXISTS idx_reports_triage;
+DROP INDEX IF EXISTS idx_reports_type_created;
+DROP INDEX IF EXISTS idx_reports_cluster;
+
+ALTER TABLE reports
+    DROP COLUMN IF EXISTS cluster_id,
+    DROP COLUMN IF EXISTS credibility_factors;
@@ -0,0 +1,12 @@
+-- Report credibility: the factors behind each score and the incident cluster a report belongs to
+ALTER TABLE reports
+    ADD COLUMN credibility_factors JSONB,
+    ADD COLUMN cluster_id UUID;
+
+-- Clusters are named after their earliest report
+UPDATE reports SET cluster_id = id WHERE cluster_id IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_reports_cluster ON reports (cluster_id);
+CREATE INDEX IF NOT EXISTS idx_reports_type_created ON reports (disaster_type_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_reports_triage ON reports (credibility_score DESC)
+    WHERE status IN ('reported', 'under_review');
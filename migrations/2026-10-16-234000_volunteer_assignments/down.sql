@@ -0,0 +1,20 @@
+-- Revert volunteer assignment tracking
+DELETE FROM role_permissions rp
+USING roles r
+WHERE rp.role_id = r.id
+  AND r.name IN ('volunteer', 'coordinator', 'responder', 'admin', 'org_admin')
+  AND rp.permission IN ('volunteers:respond', 'volunteers:read', 'volunteers:manage');
+
+DROP INDEX IF EXISTS idx_volunteer_tracking_report;
+DROP INDEX IF EXISTS idx_volunteer_tracking_volunteer;
+DROP INDEX IF EXISTS idx_volunteer_tracking_active;
+
+ALTER TABLE volunteer_tracking DROP CONSTRAINT IF EXISTS volunteer_tracking_status_check;
+UPDATE volunteer_tracking SET status = 'en_route' WHERE status = 'accepted';
+UPDATE volunteer_tracking SET status = 'on_site' WHERE status = 'arrived';
+
+ALTER TABLE volunteer_tracking
+    DROP COLUMN IF EXISTS responded_at,
+    DROP COLUMN IF EXISTS assigned_by;
+
+DROP INDEX IF EXISTS idx_volunteers_user;
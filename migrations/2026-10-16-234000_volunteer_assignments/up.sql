@@ -0,0 +1,35 @@
+-- Volunteer profiles (one per user) and assignment tracking with accept/decline responses
+DELETE FROM volunteers v
+USING volunteers newer
+WHERE v.user_id = newer.user_id AND v.created_at < newer.created_at;
+CREATE UNIQUE INDEX idx_volunteers_user ON volunteers (user_id);
+
+ALTER TABLE volunteer_tracking
+    ADD COLUMN assigned_by  UUID REFERENCES users (id) ON DELETE SET NULL,
+    ADD COLUMN responded_at TIMESTAMP;
+
+UPDATE volunteer_tracking SET status = 'accepted' WHERE status = 'en_route';
+UPDATE volunteer_tracking SET status = 'arrived' WHERE status = 'on_site';
+UPDATE volunteer_tracking SET status = 'assigned' WHERE status IS NULL;
+ALTER TABLE volunteer_tracking
+    ADD CONSTRAINT volunteer_tracking_status_check
+        CHECK (status IN ('assigned', 'accepted', 'declined', 'arrived', 'completed', 'cancelled'));
+
+-- A volunteer holds at most one open assignment per report
+CREATE UNIQUE INDEX idx_volunteer_tracking_active
+    ON volunteer_tracking (volunteer_id, report_id)
+    WHERE status IN ('assigned', 'accepted', 'arrived');
+CREATE INDEX idx_volunteer_tracking_volunteer ON volunteer_tracking (volunteer_id, assigned_at DESC);
+CREATE INDEX idx_volunteer_tracking_report ON volunteer_tracking (report_id);
+
+INSERT INTO role_permissions (role_id, permission)
+SELECT r.id, p.permission
+FROM roles r
+         JOIN (VALUES
+                   ('volunteer', 'volunteers:respond'),
+                   ('coordinator', 'volunteers:respond'), ('coordinator', 'volunteers:read'), ('coordinator', 'volunteers:manage'),
+                   ('responder', 'volunteers:respond'), ('responder', 'volunteers:read'), ('responder', 'volunteers:manage'),
+                   ('admin', 'volunteers:respond'), ('admin', 'volunteers:read'), ('admin', 'volunteers:manage'),
+                   ('org_admin', 'volunteers:respond'), ('org_admin', 'volunteers:read'), ('org_admin', 'volunteers:manage')
+              ) AS p (role_name, permission) ON p.role_name = r.name
+ON CONFLICT DO NOTHING;
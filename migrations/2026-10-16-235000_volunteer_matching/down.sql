@@ -0,0 +1,6 @@
+-- Disaster-wide assignments cannot be kept without a report
+DELETE FROM volunteer_tracking WHERE report_id IS NULL;
+DROP INDEX IF EXISTS idx_volunteer_tracking_disaster;
+DROP INDEX IF EXISTS idx_volunteer_tracking_active_disaster;
+ALTER TABLE volunteer_tracking DROP CONSTRAINT IF EXISTS volunteer_tracking_target_check;
+ALTER TABLE volunteer_tracking DROP COLUMN IF EXISTS disaster_id;
@@ -0,0 +1,12 @@
+-- Assignments straight to a disaster, made by volunteer matching before any report is picked
+ALTER TABLE volunteer_tracking
+    ADD COLUMN disaster_id UUID REFERENCES disasters (id) ON DELETE CASCADE;
+ALTER TABLE volunteer_tracking
+    ADD CONSTRAINT volunteer_tracking_target_check
+        CHECK (report_id IS NOT NULL OR disaster_id IS NOT NULL);
+
+-- A volunteer holds at most one open disaster-wide assignment per disaster
+CREATE UNIQUE INDEX idx_volunteer_tracking_active_disaster
+    ON volunteer_tracking (volunteer_id, disaster_id)
+    WHERE report_id IS NULL AND status IN ('assigned', 'accepted', 'arrived');
+CREATE INDEX idx_volunteer_tracking_disaster ON volunteer_tracking (disaster_id);
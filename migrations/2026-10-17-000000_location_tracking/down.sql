@@ -0,0 +1,12 @@
+-- Fixes keep their position and volunteer; device and assignment details are dropped
+DROP INDEX IF EXISTS idx_volunteer_locations_team;
+DROP INDEX IF EXISTS idx_volunteer_locations_assignment;
+DROP INDEX IF EXISTS idx_volunteer_locations_user_recorded;
+ALTER TABLE volunteer_locations
+    DROP COLUMN IF EXISTS received_at,
+    DROP COLUMN IF EXISTS heading_deg,
+    DROP COLUMN IF EXISTS speed_mps,
+    DROP COLUMN IF EXISTS accuracy_m,
+    DROP COLUMN IF EXISTS assignment_id,
+    DROP COLUMN IF EXISTS team_id,
+    DROP COLUMN IF EXISTS user_id;
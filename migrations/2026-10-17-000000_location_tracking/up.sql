@@ -0,0 +1,22 @@
+-- GPS fixes from responders' devices: who sent them, for which assignment or team, and how good they are
+ALTER TABLE volunteer_locations
+    ADD COLUMN user_id UUID REFERENCES users (id) ON DELETE CASCADE,
+    ADD COLUMN team_id UUID REFERENCES response_teams (id) ON DELETE SET NULL,
+    ADD COLUMN assignment_id UUID REFERENCES volunteer_tracking (id) ON DELETE SET NULL,
+    ADD COLUMN accuracy_m DOUBLE PRECISION,
+    ADD COLUMN speed_mps DOUBLE PRECISION,
+    ADD COLUMN heading_deg DOUBLE PRECISION,
+    ADD COLUMN received_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP;
+
+UPDATE volunteer_locations l
+SET user_id = v.user_id
+FROM volunteers v
+WHERE v.id = l.volunteer_id AND l.user_id IS NULL;
+
+-- Devices re-send batches they could not confirm while offline; a repeated fix is dropped
+CREATE UNIQUE INDEX idx_volunteer_locations_user_recorded
+    ON volunteer_locations (user_id, recorded_at);
+CREATE INDEX idx_volunteer_locations_assignment
+    ON volunteer_locations (assignment_id, recorded_at DESC) WHERE assignment_id IS NOT NULL;
+CREATE INDEX idx_volunteer_locations_team
+    ON volunteer_locations (team_id, recorded_at DESC) WHERE team_id IS NOT NULL;
@@ -0,0 +1,2 @@
+-- The read model is rebuilt from the event stream when the table is recreated
+DROP TABLE IF EXISTS analytics_disaster_facts;
@@ -0,0 +1,21 @@
+-- Analytics read model: one row per disaster, kept up to date from the disaster event stream
+CREATE TABLE analytics_disaster_facts (
+    disaster_id UUID PRIMARY KEY REFERENCES disasters (id) ON DELETE CASCADE,
+    disaster_type TEXT NOT NULL,
+    severity TEXT NOT NULL,
+    status TEXT NOT NULL,
+    position GEOGRAPHY(POINT, 4326) NOT NULL,
+    province TEXT,
+    city TEXT,
+    reported_at TIMESTAMP NOT NULL,
+    verified_at TIMESTAMP,
+    first_response_at TIMESTAMP,
+    resolved_at TIMESTAMP,
+    closed_at TIMESTAMP,
+    projected_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_analytics_disaster_facts_reported ON analytics_disaster_facts (reported_at);
+CREATE INDEX idx_analytics_disaster_facts_type ON analytics_disaster_facts (disaster_type, reported_at);
+CREATE INDEX idx_analytics_disaster_facts_province ON analytics_disaster_facts (lower(province), reported_at);
+CREATE INDEX idx_analytics_disaster_facts_position ON analytics_disaster_facts USING GIST (position);
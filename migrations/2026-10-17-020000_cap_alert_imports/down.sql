@@ -0,0 +1 @@
+DROP TABLE IF EXISTS cap_alert_imports;
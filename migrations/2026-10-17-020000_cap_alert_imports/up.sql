@@ -0,0 +1,14 @@
+-- CAP alerts already ingested, so a re-delivered alert is not imported twice and
+-- Update/Cancel messages can find the disaster their references point to
+CREATE TABLE cap_alert_imports (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    sender TEXT NOT NULL,
+    identifier TEXT NOT NULL,
+    msg_type TEXT NOT NULL,
+    disaster_id UUID REFERENCES disasters (id) ON DELETE SET NULL,
+    imported_by UUID REFERENCES users (id) ON DELETE SET NULL,
+    imported_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_cap_alert_imports_sender_identifier ON cap_alert_imports (sender, identifier);
+CREATE INDEX idx_cap_alert_imports_disaster ON cap_alert_imports (disaster_id);
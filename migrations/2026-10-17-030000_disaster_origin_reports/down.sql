@@ -0,0 +1 @@
+ALTER TABLE disaster_reports DROP COLUMN IF EXISTS is_origin;
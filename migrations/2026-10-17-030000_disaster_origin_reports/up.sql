@@ -0,0 +1,5 @@
+-- The report a disaster was opened from, removed together with the disaster.
+-- Links made before this column existed stay unmarked, so deleting an older
+-- disaster keeps its reports rather than guessing which one it created
+ALTER TABLE disaster_reports
+    ADD COLUMN is_origin BOOLEAN NOT NULL DEFAULT FALSE;
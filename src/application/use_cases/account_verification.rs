@@ -0,0 +1,632 @@
+/// Account verification use cases
+/// Email verification, password reset and password change backed by single-use verification codes
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+
+use crate::application::use_cases::{UseCase, ValidatedUseCase};
+use crate::domain::entities::notification::NotificationChannel;
+use crate::domain::entities::{User, VerificationCode, VerificationPurpose};
+use crate::domain::ports::repositories::{UserRepository, VerificationCodeRepository};
+use crate::domain::ports::services::{AuthService, NotificationService, SessionRevoker};
+use crate::domain::value_objects::{Email, UserStatus};
+use crate::shared::security::{generate_verification_token, hash_verification_token, validate_password_strength};
+use crate::shared::{AppError, AppResult, UserId};
+
+/// Lifetimes, rate limit and link base for issued codes
+#[derive(Debug, Clone)]
+pub struct VerificationPolicy {
+    pub email_verification_ttl: Duration,
+    pub password_reset_ttl: Duration,
+    pub max_codes_per_hour: u32,
+    pub public_base_url: String,
+}
+
+/// Issues hashed codes and delivers the plaintext token to the user
+pub struct VerificationCodeIssuer {
+    verification_repository: Arc<dyn VerificationCodeRepository>,
+    notification_service: Arc<dyn NotificationService>,
+    policy: VerificationPolicy,
+}
+
+impl VerificationCodeIssuer {
+    pub fn new(
+        verification_repository: Arc<dyn VerificationCodeRepository>,
+        notification_service: Arc<dyn NotificationService>,
+        policy: VerificationPolicy,
+    ) -> Self {
+        Self {
+            verification_repository,
+            notification_service,
+            policy,
+        }
+    }
+
+    fn ttl(&self, purpose: VerificationPurpose) -> Duration {
+        match purpose {
+            VerificationPurpose::PasswordReset => self.policy.password_reset_ttl,
+            VerificationPurpose::EmailVerification | VerificationPurpose::PhoneVerification => {
+                self.policy.email_verification_ttl
+            }
+        }
+    }
+
+    pub async fn issue(&self, user: &User, purpose: VerificationPurpose, channel: NotificationChannel) -> AppResult<()> {
+        let issued = self.verification_repository
+            .count_issued_since(user.id(), purpose, Utc::now() - Duration::hours(1))
+            .await?;
+        if issued >= self.policy.max_codes_per_hour as u64 {
+            return Err(AppError::RateLimitExceeded(
+                "Too many verification requests for this account; try again later".to_string()
+            ));
+        }
+
+        let token = generate_verification_token();
+        let ttl = self.ttl(purpose);
+        let code = VerificationCode::new(*user.id(), purpose, hash_verification_token(&token), ttl);
+        self.verification_repository.issue(&code).await?;
+
+        let (subject, body) = match purpose {
+            VerificationPurpose::PasswordReset => (
+                "Reset your Terra Siaga password",
+                format!(
+                    "Use this code to reset your Terra Siaga password: {}\n\nIt expires in {} minutes. \
+                     If you did not request a reset, you can ignore this message.",
+                    token, ttl.num_minutes()
+                ),
+            ),
+            VerificationPurpose::EmailVerification | VerificationPurpose::PhoneVerification => (
+                "Verify your Terra Siaga account",
+                format!(
+                    "Confirm your account by opening {}/api/v1/auth/verify-email/{}\n\nThis link expires in {} hours.",
+                    self.policy.public_base_url, token, ttl.num_hours()
+                ),
+            ),
+        };
+
+        match (channel, user.phone_number()) {
+            (NotificationChannel::SMS, Some(phone)) => self.notification_service.send_sms(phone.value(), &body).await,
+            _ => self.notification_service.send_email(user.email().value(), subject, &body).await,
+        }
+    }
+}
+
+fn ensure_strong_password(password: &str) -> AppResult<()> {
+    let validation = validate_password_strength(password);
+    if !validation.is_valid {
+        return Err(AppError::Validation(validation.issues.join("; ")));
+    }
+    Ok(())
+}
+
+/// Request a password reset code
+#[derive(Debug, Clone)]
+pub struct RequestPasswordResetRequest {
+    pub email: String,
+    /// Email, or SMS when the account has a phone number
+    pub channel: NotificationChannel,
+}
+
+/// Use case for requesting a password reset
+/// Always succeeds for unknown or throttled accounts so callers cannot probe which emails exist
+pub struct RequestPasswordResetUseCase {
+    user_repository: Arc<dyn UserRepository>,
+    issuer: Arc<VerificationCodeIssuer>,
+}
+
+impl RequestPasswordResetUseCase {
+    pub fn new(user_repository: Arc<dyn UserRepository>, issuer: Arc<VerificationCodeIssuer>) -> Self {
+        Self { user_repository, issuer }
+    }
+}
+
+#[async_trait]
+impl UseCase<RequestPasswordResetRequest, ()> for RequestPasswordResetUseCase {
+    async fn execute(&self, request: RequestPasswordResetRequest) -> AppResult<()> {
+        let email = Email::new(request.email)?;
+        let Some(user) = self.user_repository.find_by_email(&email).await? else {
+            return Ok(());
+        };
+        if !user.can_login() && *user.status() != UserStatus::Pending {
+            return Ok(());
+        }
+
+        match self.issuer.issue(&user, VerificationPurpose::PasswordReset, request.channel).await {
+            Err(AppError::RateLimitExceeded(_)) => {
+                tracing::warn!("Password reset requests throttled for user {}", user.id());
+                Ok(())
+            }
+            other => other,
+        }
+    }
+}
+
+/// Complete a password reset with the delivered code
+#[derive(Debug, Clone)]
+pub struct ResetPasswordRequest {
+    pub token: String,
+    pub new_password: String,
+}
+
+/// Use case for resetting a password; signs out all of the user's sessions
+pub struct ResetPasswordUseCase {
+    verification_repository: Arc<dyn VerificationCodeRepository>,
+    user_repository: Arc<dyn UserRepository>,
+    auth_service: Arc<dyn AuthService>,
+    notification_service: Arc<dyn NotificationService>,
+    sessions: Arc<dyn SessionRevoker>,
+}
+
+impl ResetPasswordUseCase {
+    pub fn new(
+        verification_repository: Arc<dyn VerificationCodeRepository>,
+        user_repository: Arc<dyn UserRepository>,
+        auth_service: Arc<dyn AuthService>,
+        notification_service: Arc<dyn NotificationService>,
+        sessions: Arc<dyn SessionRevoker>,
+    ) -> Self {
+        Self {
+            verification_repository,
+            user_repository,
+            auth_service,
+            notification_service,
+            sessions,
+        }
+    }
+}
+
+#[async_trait]
+impl ValidatedUseCase<ResetPasswordRequest, UserId> for ResetPasswordUseCase {
+    async fn validate(&self, request: &ResetPasswordRequest) -> AppResult<()> {
+        if request.token.trim().is_empty() {
+            return Err(AppError::Validation("Reset token is required".to_string()));
+        }
+        ensure_strong_password(&request.new_password)
+    }
+}
+
+#[async_trait]
+impl UseCase<ResetPasswordRequest, UserId> for ResetPasswordUseCase {
+    async fn execute(&self, request: ResetPasswordRequest) -> AppResult<UserId> {
+        let user_id = self.verification_repository
+            .consume(VerificationPurpose::PasswordReset, &hash_verification_token(&request.token))
+            .await?
+            .ok_or_else(|| AppError::Validation("Invalid or expired reset token".to_string()))?;
+        let user = self.user_repository.find_by_id(&user_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("User {} not found", user_id)))?;
+
+        let password_hash = self.auth_service.hash_password(&request.new_password).await?;
+        self.user_repository.update_password(&user_id, &password_hash).await?;
+        self.sessions.revoke_user_sessions(&user_id, None).await?;
+
+        let _ = self.notification_service
+            .send_email(
+                user.email().value(),
+                "Your Terra Siaga password was reset",
+                "Your password was reset and all sessions were signed out. \
+                 If this was not you, contact your coordinator immediately.",
+            )
+            .await;
+        Ok(user_id)
+    }
+}
+
+/// Use case for (re)sending the account verification link
+pub struct SendEmailVerificationUseCase {
+    user_repository: Arc<dyn UserRepository>,
+    issuer: Arc<VerificationCodeIssuer>,
+}
+
+impl SendEmailVerificationUseCase {
+    pub fn new(user_repository: Arc<dyn UserRepository>, issuer: Arc<VerificationCodeIssuer>) -> Self {
+        Self { user_repository, issuer }
+    }
+}
+
+#[async_trait]
+impl UseCase<UserId, ()> for SendEmailVerificationUseCase {
+    async fn execute(&self, user_id: UserId) -> AppResult<()> {
+        let user = self.user_repository.find_by_id(&user_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("User {} not found", user_id)))?;
+        if *user.status() != UserStatus::Pending {
+            return Err(AppError::Conflict("Account is already verified".to_string()));
+        }
+
+        self.issuer.issue(&user, VerificationPurpose::EmailVerification, NotificationChannel::Email).await
+    }
+}
+
+/// Use case for confirming an email address from the emailed link
+pub struct VerifyEmailUseCase {
+    verification_repository: Arc<dyn VerificationCodeRepository>,
+    user_repository: Arc<dyn UserRepository>,
+}
+
+impl VerifyEmailUseCase {
+    pub fn new(
+        verification_repository: Arc<dyn VerificationCodeRepository>,
+        user_repository: Arc<dyn UserRepository>,
+    ) -> Self {
+        Self { verification_repository, user_repository }
+    }
+}
+
+#[async_trait]
+impl UseCase<String, UserId> for VerifyEmailUseCase {
+    async fn execute(&self, token: String) -> AppResult<UserId> {
+        let user_id = self.verification_repository
+            .consume(VerificationPurpose::EmailVerification, &hash_verification_token(&token))
+            .await?
+            .ok_or_else(|| AppError::Validation("Invalid or expired verification link".to_string()))?;
+
+        if !self.user_repository.verify_email(&user_id).await? {
+            return Err(AppError::NotFound(format!("User {} not found", user_id)));
+        }
+        Ok(user_id)
+    }
+}
+
+/// Change the password of a signed-in user
+#[derive(Debug, Clone)]
+pub struct ChangePasswordRequest {
+    pub user_id: UserId,
+    /// Session making the change; it stays signed in
+    pub session_id: String,
+    pub current_password: String,
+    pub new_password: String,
+}
+
+/// Use case for changing a password; signs out the user's other sessions
+pub struct ChangePasswordUseCase {
+    user_repository: Arc<dyn UserRepository>,
+    auth_service: Arc<dyn AuthService>,
+    notification_service: Arc<dyn NotificationService>,
+    sessions: Arc<dyn SessionRevoker>,
+}
+
+impl ChangePasswordUseCase {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        auth_service: Arc<dyn AuthService>,
+        notification_service: Arc<dyn NotificationService>,
+        sessions: Arc<dyn SessionRevoker>,
+    ) -> Self {
+        Self {
+            user_repository,
+            auth_service,
+            notification_service,
+            sessions,
+        }
+    }
+}
+
+#[async_trait]
+impl ValidatedUseCase<ChangePasswordRequest, ()> for ChangePasswordUseCase {
+    async fn validate(&self, request: &ChangePasswordRequest) -> AppResult<()> {
+        if request.current_password == request.new_password {
+            return Err(AppError::Validation("New password must differ from the current password".to_string()));
+        }
+        ensure_strong_password(&request.new_password)
+    }
+}
+
+#[async_trait]
+impl UseCase<ChangePasswordRequest, ()> for ChangePasswordUseCase {
+    async fn execute(&self, request: ChangePasswordRequest) -> AppResult<()> {
+        let user = self.user_repository.find_by_id(&request.user_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("User {} not found", request.user_id)))?;
+        if !self.auth_service.verify_password(&request.current_password, user.password_hash()).await? {
+            return Err(AppError::Unauthorized("Current password is incorrect".to_string()));
+        }
+
+        let password_hash = self.auth_service.hash_password(&request.new_password).await?;
+        self.user_repository.update_password(&request.user_id, &password_hash).await?;
+        self.sessions.revoke_user_sessions(&request.user_id, Some(&request.session_id)).await?;
+
+        let _ = self.notification_service
+            .send_email(
+                user.email().value(),
+                "Your Terra Siaga password was changed",
+                "Your password was changed and your other sessions were signed out. \
+                 If this was not you, reset your password immediately.",
+            )
+            .await;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use crate::domain::ports::services::{EmergencyResponse, TokenPair};
+    use crate::domain::value_objects::{Coordinates, GeoArea, UserRole, Username};
+
+    const NEW_PASSWORD: &str = "Banjir#Rob2026!";
+
+    #[derive(Default)]
+    struct MemoryCodes {
+        codes: Mutex<Vec<VerificationCode>>,
+    }
+
+    #[async_trait]
+    impl VerificationCodeRepository for MemoryCodes {
+        async fn issue(&self, code: &VerificationCode) -> AppResult<()> {
+            let mut codes = self.codes.lock().unwrap();
+            for outstanding in codes.iter_mut().filter(|c| c.user_id == code.user_id && c.purpose == code.purpose) {
+                outstanding.is_used = true;
+            }
+            codes.push(code.clone());
+            Ok(())
+        }
+
+        async fn consume(&self, purpose: VerificationPurpose, code_hash: &str) -> AppResult<Option<UserId>> {
+            let mut codes = self.codes.lock().unwrap();
+            let usable = codes.iter_mut()
+                .find(|c| c.purpose == purpose && c.code_hash == code_hash && !c.is_used && c.expires_at > Utc::now());
+            Ok(usable.map(|code| {
+                code.is_used = true;
+                code.user_id
+            }))
+        }
+
+        async fn count_issued_since(&self, user_id: &UserId, purpose: VerificationPurpose, since: chrono::DateTime<Utc>) -> AppResult<u64> {
+            Ok(self.codes.lock().unwrap().iter()
+                .filter(|c| &c.user_id == user_id && c.purpose == purpose && c.created_at >= since)
+                .count() as u64)
+        }
+    }
+
+    /// Users kept in memory; password changes are recorded instead of applied
+    #[derive(Default)]
+    struct MemoryUsers {
+        users: Mutex<Vec<User>>,
+        password_updates: Mutex<Vec<(UserId, String)>>,
+    }
+
+    #[async_trait]
+    impl UserRepository for MemoryUsers {
+        async fn find_by_id(&self, uid: &UserId) -> AppResult<Option<User>> {
+            Ok(self.users.lock().unwrap().iter().find(|u| u.id() == uid).cloned())
+        }
+        async fn save(&self, _entity: &User) -> AppResult<User> { unimplemented!() }
+        async fn update(&self, _entity: &User) -> AppResult<User> { unimplemented!() }
+        async fn delete(&self, _uid: &UserId) -> AppResult<bool> { unimplemented!() }
+        async fn find_all(&self) -> AppResult<Vec<User>> { unimplemented!() }
+        async fn find_by_email(&self, email_val: &Email) -> AppResult<Option<User>> {
+            Ok(self.users.lock().unwrap().iter().find(|u| u.email() == email_val).cloned())
+        }
+        async fn find_by_username(&self, _username_val: &str) -> AppResult<Option<User>> { unimplemented!() }
+        async fn find_by_role(&self, _role: &UserRole) -> AppResult<Vec<User>> { unimplemented!() }
+        async fn find_active_responders(&self) -> AppResult<Vec<User>> { unimplemented!() }
+        async fn update_last_login(&self, _uid: &UserId) -> AppResult<bool> { unimplemented!() }
+        async fn verify_email(&self, uid: &UserId) -> AppResult<bool> {
+            let mut users = self.users.lock().unwrap();
+            Ok(users.iter_mut().find(|u| u.id() == uid).map(|user| user.status = UserStatus::Active).is_some())
+        }
+        async fn update_password(&self, uid: &UserId, password_hash: &str) -> AppResult<bool> {
+            self.password_updates.lock().unwrap().push((*uid, password_hash.to_string()));
+            Ok(true)
+        }
+        async fn count_by_role(&self, _role: &UserRole) -> AppResult<u64> { unimplemented!() }
+        async fn find_users_in_radius(&self, _center: &Coordinates, _radius_km: f64) -> AppResult<Vec<User>> { unimplemented!() }
+        async fn find_users_in_area(&self, _area: &GeoArea) -> AppResult<Vec<User>> { unimplemented!() }
+        async fn count_by_status(&self, _status: &str) -> AppResult<u64> { unimplemented!() }
+        async fn save_address_geocode(&self, _uid: &UserId, _address: &str, _position: &Coordinates) -> AppResult<()> { unimplemented!() }
+        async fn clear_address_geocode(&self, _uid: &UserId) -> AppResult<()> { unimplemented!() }
+    }
+
+    /// Reversible "hashing" so tests can tell which password was stored
+    struct PlainAuth;
+
+    #[async_trait]
+    impl AuthService for PlainAuth {
+        async fn hash_password(&self, password: &str) -> AppResult<String> {
+            Ok(format!("hashed:{}", password))
+        }
+        async fn verify_password(&self, password: &str, hash: &str) -> AppResult<bool> {
+            Ok(hash == format!("hashed:{}", password))
+        }
+        async fn generate_tokens(&self, _user_id: UserId) -> AppResult<TokenPair> { unimplemented!() }
+        async fn verify_token(&self, _token: &str) -> AppResult<UserId> { unimplemented!() }
+        async fn refresh_token(&self, _refresh_token: &str) -> AppResult<TokenPair> { unimplemented!() }
+    }
+
+    /// Collects outgoing messages as (recipient, body)
+    #[derive(Default)]
+    struct Outbox {
+        sent: Mutex<Vec<(String, String)>>,
+    }
+
+    #[async_trait]
+    impl NotificationService for Outbox {
+        async fn send_email(&self, to: &str, _subject: &str, body: &str) -> AppResult<()> {
+            self.sent.lock().unwrap().push((to.to_string(), body.to_string()));
+            Ok(())
+        }
+        async fn send_sms(&self, to: &str, message: &str) -> AppResult<()> {
+            self.sent.lock().unwrap().push((to.to_string(), message.to_string()));
+            Ok(())
+        }
+        async fn send_whatsapp(&self, _to: &str, _message: &str) -> AppResult<()> { unimplemented!() }
+        async fn send_push_notification(&self, _user_id: UserId, _title: &str, _body: &str) -> AppResult<()> { unimplemented!() }
+        async fn notify_emergency_dispatch(
+            &self,
+            _disaster: &crate::domain::entities::disaster::Disaster,
+            _response: &EmergencyResponse,
+        ) -> AppResult<()> { unimplemented!() }
+        async fn notify_disaster_update(&self, _disaster: &crate::domain::entities::disaster::Disaster) -> AppResult<()> { unimplemented!() }
+        async fn notify_volunteers(&self, _disaster: &crate::domain::entities::disaster::Disaster, _volunteers: &[UserId]) -> AppResult<()> { unimplemented!() }
+        async fn send_emergency_alert(&self, _message: &str, _recipients: &[UserId]) -> AppResult<()> { unimplemented!() }
+    }
+
+    impl Outbox {
+        fn count(&self) -> usize {
+            self.sent.lock().unwrap().len()
+        }
+
+        /// Token from the newest message, which ends the line after `marker`
+        fn last_token(&self, marker: &str) -> String {
+            let sent = self.sent.lock().unwrap();
+            let (_, body) = sent.last().expect("no message was sent");
+            let start = body.find(marker).expect("message carries no token") + marker.len();
+            body[start..].lines().next().unwrap().trim().to_string()
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingRevoker {
+        revoked: Mutex<Vec<(UserId, Option<String>)>>,
+    }
+
+    #[async_trait]
+    impl SessionRevoker for RecordingRevoker {
+        async fn revoke_user_sessions(&self, user_id: &UserId, keep_session_id: Option<&str>) -> AppResult<()> {
+            self.revoked.lock().unwrap().push((*user_id, keep_session_id.map(str::to_string)));
+            Ok(())
+        }
+    }
+
+    struct Fixture {
+        user: User,
+        codes: Arc<MemoryCodes>,
+        users: Arc<MemoryUsers>,
+        outbox: Arc<Outbox>,
+        sessions: Arc<RecordingRevoker>,
+        issuer: Arc<VerificationCodeIssuer>,
+    }
+
+    impl Fixture {
+        fn new(max_codes_per_hour: u32) -> Self {
+            let user = User::new(
+                Email::new("warga@example.id".to_string()).unwrap(),
+                Username::new("warga".to_string()).unwrap(),
+                "Warga Siaga".to_string(),
+                "hashed:Lama#Sandi2025".to_string(),
+                UserRole::Citizen,
+            ).unwrap();
+            let codes = Arc::new(MemoryCodes::default());
+            let users = Arc::new(MemoryUsers::default());
+            users.users.lock().unwrap().push(user.clone());
+            let outbox = Arc::new(Outbox::default());
+            let policy = VerificationPolicy {
+                email_verification_ttl: Duration::hours(24),
+                password_reset_ttl: Duration::minutes(30),
+                max_codes_per_hour,
+                public_base_url: "https://siaga.example.id".to_string(),
+            };
+            let issuer = Arc::new(VerificationCodeIssuer::new(codes.clone(), outbox.clone(), policy));
+            Self { user, codes, users, outbox, sessions: Arc::new(RecordingRevoker::default()), issuer }
+        }
+
+        fn request_reset(&self) -> RequestPasswordResetUseCase {
+            RequestPasswordResetUseCase::new(self.users.clone(), self.issuer.clone())
+        }
+
+        fn reset(&self) -> ResetPasswordUseCase {
+            ResetPasswordUseCase::new(
+                self.codes.clone(), self.users.clone(), Arc::new(PlainAuth), self.outbox.clone(), self.sessions.clone(),
+            )
+        }
+
+        fn change(&self) -> ChangePasswordUseCase {
+            ChangePasswordUseCase::new(self.users.clone(), Arc::new(PlainAuth), self.outbox.clone(), self.sessions.clone())
+        }
+
+        fn reset_request(&self) -> RequestPasswordResetRequest {
+            RequestPasswordResetRequest { email: self.user.email().value().to_string(), channel: NotificationChannel::Email }
+        }
+    }
+
+    #[tokio::test]
+    async fn used_or_expired_codes_are_rejected() {
+        let fixture = Fixture::new(5);
+        fixture.request_reset().execute(fixture.reset_request()).await.unwrap();
+        let token = fixture.outbox.last_token("password: ");
+
+        let reset = || ResetPasswordRequest { token: token.clone(), new_password: NEW_PASSWORD.to_string() };
+        assert_eq!(fixture.reset().execute_validated(reset()).await.unwrap(), *fixture.user.id());
+        assert!(matches!(fixture.reset().execute_validated(reset()).await, Err(AppError::Validation(_))));
+
+        let stale = VerificationCode::new(
+            *fixture.user.id(), VerificationPurpose::PasswordReset, hash_verification_token("stale"), Duration::minutes(-1),
+        );
+        fixture.codes.issue(&stale).await.unwrap();
+        let expired = ResetPasswordRequest { token: "stale".to_string(), new_password: NEW_PASSWORD.to_string() };
+        assert!(matches!(fixture.reset().execute_validated(expired).await, Err(AppError::Validation(_))));
+        assert_eq!(fixture.users.password_updates.lock().unwrap().len(), 1);
+
+        // Verification links are single-use too
+        SendEmailVerificationUseCase::new(fixture.users.clone(), fixture.issuer.clone())
+            .execute(*fixture.user.id()).await.unwrap();
+        let link = fixture.outbox.last_token("verify-email/");
+        let verify = VerifyEmailUseCase::new(fixture.codes.clone(), fixture.users.clone());
+        assert_eq!(verify.execute(link.clone()).await.unwrap(), *fixture.user.id());
+        assert!(matches!(verify.execute(link).await, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn codes_are_rate_limited_per_account_and_hour() {
+        let fixture = Fixture::new(2);
+        let resend = SendEmailVerificationUseCase::new(fixture.users.clone(), fixture.issuer.clone());
+        resend.execute(*fixture.user.id()).await.unwrap();
+        resend.execute(*fixture.user.id()).await.unwrap();
+        assert!(matches!(resend.execute(*fixture.user.id()).await, Err(AppError::RateLimitExceeded(_))));
+
+        // Reset requests share the limit mechanism but answer throttled callers like any other
+        for _ in 0..3 {
+            fixture.request_reset().execute(fixture.reset_request()).await.unwrap();
+        }
+        assert_eq!(fixture.outbox.count(), 4);
+    }
+
+    #[tokio::test]
+    async fn unknown_emails_get_the_same_silent_answer() {
+        let fixture = Fixture::new(5);
+        let unknown = RequestPasswordResetRequest { email: "siapa@example.id".to_string(), channel: NotificationChannel::Email };
+        assert!(fixture.request_reset().execute(unknown).await.is_ok());
+        assert_eq!(fixture.outbox.count(), 0);
+        assert!(fixture.codes.codes.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn wrong_current_password_is_refused() {
+        let fixture = Fixture::new(5);
+        let request = ChangePasswordRequest {
+            user_id: *fixture.user.id(),
+            session_id: "laptop".to_string(),
+            current_password: "Salah#Sandi2025".to_string(),
+            new_password: NEW_PASSWORD.to_string(),
+        };
+        assert!(matches!(fixture.change().execute_validated(request).await, Err(AppError::Unauthorized(_))));
+        assert!(fixture.users.password_updates.lock().unwrap().is_empty());
+        assert!(fixture.sessions.revoked.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn password_changes_sign_out_other_sessions() {
+        let fixture = Fixture::new(5);
+        let user_id = *fixture.user.id();
+        fixture.change()
+            .execute_validated(ChangePasswordRequest {
+                user_id,
+                session_id: "laptop".to_string(),
+                current_password: "Lama#Sandi2025".to_string(),
+                new_password: NEW_PASSWORD.to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(*fixture.users.password_updates.lock().unwrap(), vec![(user_id, format!("hashed:{}", NEW_PASSWORD))]);
+        assert_eq!(*fixture.sessions.revoked.lock().unwrap(), vec![(user_id, Some("laptop".to_string()))]);
+
+        // A reset signs out every session, including the one that requested it
+        fixture.request_reset().execute(fixture.reset_request()).await.unwrap();
+        let token = fixture.outbox.last_token("password: ");
+        fixture.reset()
+            .execute_validated(ResetPasswordRequest { token, new_password: "Longsor#Tebing2026".to_string() })
+            .await
+            .unwrap();
+        assert_eq!(fixture.sessions.revoked.lock().unwrap().last(), Some(&(user_id, None)));
+    }
+}
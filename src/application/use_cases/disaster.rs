@@ -4,15 +4,18 @@
 use async_trait::async_trait;
 use std::sync::Arc;
 use chrono::{DateTime, Utc};
+use serde::Serialize;
 use uuid::Uuid;
 
 use crate::application::use_cases::{UseCase, ValidatedUseCase};
 use crate::domain::entities::disaster::{Disaster, DisasterSeverity, DisasterStatus, DisasterType};
 use crate::domain::value_objects::*;
-use crate::domain::ports::repositories::DisasterRepository;
+use crate::domain::ports::repositories::{CapAlertImportRepository, DisasterRepository, ProvinceAssignmentRepository};
 use crate::domain::ports::services::NotificationService;
 use crate::domain::events::{DisasterReportedEvent, DisasterStatusUpdatedEvent, EventPublisher};
 use crate::shared::{AppResult, AppError};
+use crate::shared::policy::{Attributes, PolicyEngine, Subject};
+use crate::shared::cap::{CapAlert, CapMsgType, CapStatus};
 
 /// Request to report a new disaster
 #[derive(Debug, Clone)]
@@ -28,7 +31,7 @@ pub struct ReportDisasterRequest {
 }
 
 /// Response after reporting a disaster
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct DisasterResponse {
     pub id: DisasterId,
     pub title: String,
@@ -42,6 +45,23 @@ pub struct DisasterResponse {
     pub updated_at: DateTime<Utc>,
 }
 
+impl DisasterResponse {
+    pub fn from_disaster(disaster: &Disaster) -> Self {
+        Self {
+            id: *disaster.id(),
+            title: disaster.title().to_string(),
+            disaster_type: format!("{:?}", disaster.disaster_type()),
+            severity: disaster.severity().clone(),
+            status: format!("{:?}", disaster.status()),
+            location: disaster.location().clone(),
+            description: disaster.description().to_string(),
+            reported_by: *disaster.reporter_id(),
+            reported_at: disaster.created_at(),
+            updated_at: disaster.updated_at(),
+        }
+    }
+}
+
 /// Use case for reporting new disasters
 pub struct ReportDisasterUseCase {
     disaster_repository: Arc<dyn DisasterRepository>,
@@ -89,20 +109,7 @@ impl ValidatedUseCase<ReportDisasterRequest, DisasterResponse> for ReportDisaste
 #[async_trait]
 impl UseCase<ReportDisasterRequest, DisasterResponse> for ReportDisasterUseCase {
     async fn execute(&self, request: ReportDisasterRequest) -> AppResult<DisasterResponse> {
-        // Map type string to enum
-        let dtype = match request.disaster_type.to_lowercase().as_str() {
-            "earthquake" => DisasterType::Earthquake,
-            "flood" => DisasterType::Flood,
-            "tsunami" => DisasterType::Tsunami,
-            "landslide" => DisasterType::Landslide,
-            "volcanic_eruption" | "volcaniceruption" | "volcano" => DisasterType::VolcanicEruption,
-            "fire" => DisasterType::Fire,
-            "storm" => DisasterType::Storm,
-            "drought" => DisasterType::Drought,
-            "epidemic" => DisasterType::Epidemic,
-            "technological" | "technological_disaster" => DisasterType::TechnologicalDisaster,
-            other => DisasterType::Other(other.to_string()),
-        };
+        let dtype = DisasterType::parse(&request.disaster_type);
 
         // Create disaster entity
         let disaster = Disaster::new(
@@ -112,7 +119,7 @@ impl UseCase<ReportDisasterRequest, DisasterResponse> for ReportDisasterUseCase
             request.severity.clone(),
             request.location.clone(),
             request.reported_by.clone(),
-        );
+        )?;
 
         // Save to repository
         let saved_disaster = self.disaster_repository.save(&disaster).await?;
@@ -150,28 +157,247 @@ impl UseCase<ReportDisasterRequest, DisasterResponse> for ReportDisasterUseCase
     }
 }
 
+/// Request to ingest a CAP 1.2 alert from an external warning system
+#[derive(Debug, Clone)]
+pub struct ImportCapAlertRequest {
+    pub xml: String,
+    pub imported_by: UserId,
+}
+
+/// What an imported CAP alert did to the disaster list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CapImportOutcome {
+    /// A new hazard was reported
+    Created,
+    /// An Update escalated the disaster its references point to
+    Updated,
+    /// A Cancel closed the disaster its references point to
+    Closed,
+    Skipped,
+}
+
+impl CapImportOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CapImportOutcome::Created => "created",
+            CapImportOutcome::Updated => "updated",
+            CapImportOutcome::Closed => "closed",
+            CapImportOutcome::Skipped => "skipped",
+        }
+    }
+}
+
+/// Outcome of a CAP import; alerts that do not affect any disaster are skipped
+#[derive(Debug, Clone)]
+pub struct ImportCapAlertResponse {
+    pub identifier: String,
+    pub sender: String,
+    pub outcome: CapImportOutcome,
+    pub disaster: Option<DisasterResponse>,
+    pub skipped_reason: Option<String>,
+}
+
+/// Use case for turning incoming CAP alerts into disaster reports
+/// Each alert is imported once per sender and identifier. Updates and Cancels follow their
+/// `references` to the disaster an earlier alert created; an Update naming no known alert
+/// is treated as a new hazard.
+pub struct ImportCapAlertUseCase {
+    report_disaster_use_case: Arc<ReportDisasterUseCase>,
+    disaster_repository: Arc<dyn DisasterRepository>,
+    imports: Arc<dyn CapAlertImportRepository>,
+    event_publisher: Arc<dyn EventPublisher>,
+}
+
+type CapImportResult = (CapImportOutcome, Option<DisasterResponse>, Option<String>);
+
+impl ImportCapAlertUseCase {
+    pub fn new(
+        report_disaster_use_case: Arc<ReportDisasterUseCase>,
+        disaster_repository: Arc<dyn DisasterRepository>,
+        imports: Arc<dyn CapAlertImportRepository>,
+        event_publisher: Arc<dyn EventPublisher>,
+    ) -> Self {
+        Self { report_disaster_use_case, disaster_repository, imports, event_publisher }
+    }
+
+    /// Why an alert should not be imported at all, if it should not
+    fn unimportable(alert: &CapAlert) -> Option<String> {
+        if alert.status != CapStatus::Actual {
+            return Some(format!("status is {}", alert.status.as_str()));
+        }
+        if !matches!(alert.msg_type, CapMsgType::Alert | CapMsgType::Update | CapMsgType::Cancel) {
+            return Some(format!("message type is {}", alert.msg_type.as_str()));
+        }
+        None
+    }
+
+    /// Disaster report for an alert, or the reason it cannot create one
+    fn to_report(alert: &CapAlert, imported_by: UserId) -> Result<ReportDisasterRequest, String> {
+        let info = alert.info_for_language("id").ok_or("alert has no info block")?;
+        let location = info.location().ok_or("alert has no polygon or circle area")?;
+
+        let source = format!(
+            "Imported from CAP alert {} ({})",
+            alert.identifier,
+            info.sender_name.as_deref().unwrap_or(&alert.sender)
+        );
+        let description = [info.description.clone(), info.instruction.clone(), Some(source)]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        Ok(ReportDisasterRequest {
+            title: info.headline.clone().unwrap_or_else(|| info.event.clone()),
+            description,
+            disaster_type: info.disaster_type(),
+            severity: info.severity.to_disaster_severity(),
+            location,
+            reported_by: imported_by,
+            contact_info: None,
+            images: Vec::new(),
+        })
+    }
+
+    async fn create(&self, alert: &CapAlert, imported_by: UserId) -> AppResult<CapImportResult> {
+        match Self::to_report(alert, imported_by) {
+            Ok(report) => {
+                let disaster = self.report_disaster_use_case.execute_validated(report).await?;
+                Ok((CapImportOutcome::Created, Some(disaster), None))
+            }
+            Err(reason) => Ok((CapImportOutcome::Skipped, None, Some(reason))),
+        }
+    }
+
+    /// Apply an Update to the disaster it refers to; severity is only ever escalated
+    async fn update(&self, alert: &CapAlert, mut disaster: Disaster, imported_by: UserId) -> AppResult<CapImportResult> {
+        let severity = alert.info_for_language("id").map(|info| info.severity.to_disaster_severity());
+        if let Some(severity) = severity.filter(|severity| severity > disaster.severity()) {
+            disaster.update_severity(severity, imported_by)?;
+            disaster = self.disaster_repository.update(&disaster).await?;
+        }
+        Ok((CapImportOutcome::Updated, Some(DisasterResponse::from_disaster(&disaster)), None))
+    }
+
+    /// Close the disaster a Cancel refers to
+    async fn close(&self, mut disaster: Disaster, imported_by: UserId) -> AppResult<CapImportResult> {
+        let old_status = format!("{:?}", disaster.status());
+        disaster.close_withdrawn(imported_by);
+        let saved = self.disaster_repository.update(&disaster).await?;
+
+        self.event_publisher.publish(&DisasterStatusUpdatedEvent {
+            event_id: Uuid::new_v4(),
+            disaster_id: *saved.id(),
+            updated_by: imported_by,
+            old_status,
+            new_status: format!("{:?}", saved.status()),
+            occurred_at: Utc::now(),
+            version: saved.version() as u64,
+        }).await?;
+
+        Ok((CapImportOutcome::Closed, Some(DisasterResponse::from_disaster(&saved)), None))
+    }
+
+    async fn import(&self, alert: &CapAlert, imported_by: UserId) -> AppResult<CapImportResult> {
+        let referenced = match alert.msg_type {
+            CapMsgType::Update | CapMsgType::Cancel => match self.imports.find_disaster(&alert.referenced_alerts()).await? {
+                Some(id) => self.disaster_repository.find_by_id(&id).await?,
+                None => None,
+            },
+            _ => None,
+        };
+
+        match (alert.msg_type, referenced) {
+            (CapMsgType::Update, Some(disaster)) => self.update(alert, disaster, imported_by).await,
+            (CapMsgType::Cancel, Some(disaster)) => self.close(disaster, imported_by).await,
+            (CapMsgType::Cancel, None) => {
+                Ok((CapImportOutcome::Skipped, None, Some("cancels no imported alert".to_string())))
+            }
+            _ => self.create(alert, imported_by).await,
+        }
+    }
+}
+
+#[async_trait]
+impl UseCase<ImportCapAlertRequest, ImportCapAlertResponse> for ImportCapAlertUseCase {
+    async fn execute(&self, request: ImportCapAlertRequest) -> AppResult<ImportCapAlertResponse> {
+        let alert = CapAlert::from_xml(&request.xml)?;
+
+        let (outcome, disaster, skipped_reason) = if let Some(reason) = Self::unimportable(&alert) {
+            (CapImportOutcome::Skipped, None, Some(reason))
+        } else if !self.imports.claim(&alert.sender, &alert.identifier, alert.msg_type.as_str(), &request.imported_by).await? {
+            (CapImportOutcome::Skipped, None, Some("already imported".to_string()))
+        } else {
+            match self.import(&alert, request.imported_by).await {
+                Ok(imported) => imported,
+                Err(e) => {
+                    if let Err(release_error) = self.imports.release(&alert.sender, &alert.identifier).await {
+                        tracing::warn!("Failed to release CAP alert {} after a failed import: {}", alert.identifier, release_error);
+                    }
+                    return Err(e);
+                }
+            }
+        };
+
+        if let Some(disaster) = &disaster {
+            self.imports.attach_disaster(&alert.sender, &alert.identifier, &disaster.id).await?;
+        }
+        if let Some(reason) = &skipped_reason {
+            tracing::info!("Skipping CAP alert {} from {}: {}", alert.identifier, alert.sender, reason);
+        }
+
+        Ok(ImportCapAlertResponse {
+            identifier: alert.identifier,
+            sender: alert.sender,
+            outcome,
+            disaster,
+            skipped_reason,
+        })
+    }
+}
+
+/// Attributes of a disaster that access policies can test
+pub fn disaster_policy_attributes(disaster: &Disaster) -> Attributes {
+    let mut attributes = Attributes::new()
+        .with("id", disaster.id().to_string())
+        .with("reporter_id", disaster.reporter_id().to_string())
+        .with("status", disaster.status().as_str());
+    if let Some(province) = disaster.province() {
+        attributes.insert("province", province);
+    }
+    attributes
+}
+
 /// Request to update disaster status
 #[derive(Debug, Clone)]
 pub struct UpdateDisasterStatusRequest {
     pub disaster_id: DisasterId,
     pub new_status: String,
-    pub updated_by: UserId,
+    /// Caller whose policies decide whether the change is allowed
+    pub actor: Subject,
     pub update_notes: Option<String>,
 }
 
 /// Use case for updating disaster status
+/// Moving a disaster to `verified` is the `disasters:verify` action; any other change is `disasters:update_status`
 pub struct UpdateDisasterStatusUseCase {
     disaster_repository: Arc<dyn DisasterRepository>,
+    province_assignments: Arc<dyn ProvinceAssignmentRepository>,
+    policy: Arc<PolicyEngine>,
     event_publisher: Arc<dyn EventPublisher>,
 }
 
 impl UpdateDisasterStatusUseCase {
     pub fn new(
         disaster_repository: Arc<dyn DisasterRepository>,
+        province_assignments: Arc<dyn ProvinceAssignmentRepository>,
+        policy: Arc<PolicyEngine>,
         event_publisher: Arc<dyn EventPublisher>,
     ) -> Self {
         Self {
             disaster_repository,
+            province_assignments,
+            policy,
             event_publisher,
         }
     }
@@ -214,8 +440,16 @@ impl UseCase<UpdateDisasterStatusRequest, DisasterResponse> for UpdateDisasterSt
             _ => return Err(AppError::Validation("Invalid status".to_string())),
         };
 
+        let action = if new_status_enum == DisasterStatus::Verified { "disasters:verify" } else { "disasters:update_status" };
+        let mut actor = request.actor;
+        if !actor.attributes.contains("provinces") {
+            let provinces = self.province_assignments.find_by_user(&actor.user_id).await?;
+            actor.attributes.insert("provinces", provinces);
+        }
+        self.policy.authorize(&actor, action, &disaster_policy_attributes(&disaster))?;
+
         // Update status
-        disaster.update_status(new_status_enum, request.updated_by.clone())?;
+        disaster.update_status(new_status_enum, actor.user_id)?;
 
         // Save updated disaster
         let saved_disaster = self.disaster_repository.update(&disaster).await?;
@@ -224,7 +458,7 @@ impl UseCase<UpdateDisasterStatusRequest, DisasterResponse> for UpdateDisasterSt
         let event = DisasterStatusUpdatedEvent {
             event_id: Uuid::new_v4(),
             disaster_id: request.disaster_id.clone(),
-            updated_by: request.updated_by.clone(),
+            updated_by: actor.user_id,
             old_status,
             new_status: format!("{:?}", saved_disaster.status()),
             occurred_at: Utc::now(),
@@ -8,13 +8,18 @@ use uuid::Uuid;
 
 use crate::application::use_cases::{UseCase, ValidatedUseCase};
 use crate::domain::entities::disaster::DisasterSeverity;
+use crate::domain::entities::emergency_response::{EmergencyResponse, EmergencyResponseStatus};
+use crate::domain::entities::response_team::{ResponseTeam, TeamAvailability, TeamType};
 use crate::shared::types::Coordinates as SCoordinates;
 use crate::shared::Permission;
 use crate::domain::value_objects::*;
-use crate::domain::ports::repositories::{DisasterRepository, UserRepository};
-use crate::domain::ports::services::{NotificationService, GeolocationService};
+use crate::domain::ports::repositories::{DisasterRepository, EmergencyResponseRepository, ResponseTeamRepository, UserRepository};
+use crate::domain::ports::services::{NotificationService, GeolocationService, RoutingService};
 use crate::domain::events::{EmergencyResponseDispatchedEvent, EventPublisher};
-use crate::shared::{AppResult, AppError};
+use crate::shared::geo_utils::SpatialIndex;
+use crate::shared::policy::{Attributes, PolicyEngine, Subject};
+use crate::shared::routing::TravelMode;
+use crate::shared::{AppResult, AppError, EmergencyResponseId};
 
 /// Request to dispatch emergency response team
 #[derive(Debug, Clone)]
@@ -26,6 +31,9 @@ pub struct DispatchEmergencyResponseRequest {
     pub estimated_personnel_needed: u32,
     pub special_equipment_needed: Vec<String>,
     pub notes: Option<String>,
+    /// Where the team should go; defaults to the disaster location
+    pub destination: Option<Coordinates>,
+    pub estimated_duration_minutes: Option<u32>,
 }
 
 /// Response after dispatching emergency team
@@ -39,44 +47,87 @@ pub struct EmergencyResponseDispatchResponse {
     pub equipment_allocated: Vec<String>,
     pub status: String,
     pub dispatched_at: DateTime<Utc>,
+    pub assigned_team_id: Uuid,
+    pub assigned_team_name: String,
+}
+
+/// Normalize a requested team type, mapping aliases to the team that handles them
+fn normalize_team_type(raw: &str) -> String {
+    let normalized = raw.trim().to_lowercase().replace(['-', ' '], "_");
+    match normalized.as_str() {
+        "search_and_rescue" | "evacuation" => "rescue".to_string(),
+        "fire_suppression" | "firefighting" => "fire".to_string(),
+        "ambulance" => "medical".to_string(),
+        _ => normalized,
+    }
+}
+
+/// Rank teams that can take `personnel_needed` people by distance to `destination`, closest first.
+/// Returns each team with its distance in km.
+pub fn rank_teams_by_distance<'a>(
+    teams: &'a [ResponseTeam],
+    destination: &Coordinates,
+    personnel_needed: u32,
+    limit: usize,
+) -> Vec<(&'a ResponseTeam, f64)> {
+    let mut index = SpatialIndex::new();
+    let mut by_id = std::collections::HashMap::new();
+    for team in teams.iter().filter(|team| team.has_capacity(personnel_needed)) {
+        let id = team.id.to_string();
+        index.add_response_team(&id, &team.location);
+        by_id.insert(id, team);
+    }
+
+    index.find_nearest_response_teams(destination, limit, |_| true)
+        .into_iter()
+        .filter_map(|(id, distance)| by_id.get(id).map(|team| (*team, distance)))
+        .collect()
 }
 
 /// Use case for dispatching emergency response teams
 pub struct DispatchEmergencyResponseUseCase {
     disaster_repository: Arc<dyn DisasterRepository>,
+    emergency_response_repository: Arc<dyn EmergencyResponseRepository>,
+    response_team_repository: Arc<dyn ResponseTeamRepository>,
     user_repository: Arc<dyn UserRepository>,
     notification_service: Arc<dyn NotificationService>,
     geo_service: Arc<dyn GeolocationService>,
+    routing_service: Arc<dyn RoutingService>,
     event_publisher: Arc<dyn EventPublisher>,
 }
 
 impl DispatchEmergencyResponseUseCase {
     pub fn new(
         disaster_repository: Arc<dyn DisasterRepository>,
+        emergency_response_repository: Arc<dyn EmergencyResponseRepository>,
+        response_team_repository: Arc<dyn ResponseTeamRepository>,
         user_repository: Arc<dyn UserRepository>,
         notification_service: Arc<dyn NotificationService>,
         geo_service: Arc<dyn GeolocationService>,
+        routing_service: Arc<dyn RoutingService>,
         event_publisher: Arc<dyn EventPublisher>,
     ) -> Self {
         Self {
             disaster_repository,
+            emergency_response_repository,
+            response_team_repository,
             user_repository,
             notification_service,
             geo_service,
+            routing_service,
             event_publisher,
         }
     }
 
-    /// Calculate estimated arrival time based on distance and traffic
+    /// Calculate estimated arrival time from road travel time, falling back to
+    /// straight-line distance when no road network is loaded
     async fn calculate_arrival_time(
         &self,
-        from_location: &SCoordinates,
-        to_location: &SCoordinates,
+        from_location: &Coordinates,
+        to_location: &Coordinates,
         team_type: &str,
     ) -> AppResult<DateTime<Utc>> {
-        let distance_km = from_location.distance_to(to_location);
-
-        // Base speed in km/h based on team type
+        // Top speed in km/h based on team type
         let base_speed = match team_type {
             "medical" => 80.0, // Ambulance with emergency lights
             "fire" => 70.0,    // Fire truck
@@ -85,14 +136,21 @@ impl DispatchEmergencyResponseUseCase {
             _ => 50.0,         // Default emergency vehicle
         };
 
-        // Add traffic factor (simplified - in real implementation use traffic API)
-        let traffic_factor = 1.3; // 30% slower due to traffic
-        let effective_speed = base_speed / traffic_factor; // km/h
+        let route = self.routing_service
+            .route(from_location, to_location, TravelMode::Driving { max_speed_kmh: base_speed })
+            .await?;
+        let travel_time_minutes = match route {
+            Some(route) => route.duration_minutes.ceil() as i64,
+            None => {
+                if self.routing_service.is_available() {
+                    tracing::warn!("No road route to {:?}; using straight-line estimate", to_location);
+                }
+                // Without road data assume 30% slower than top speed for traffic
+                let effective_speed = base_speed / 1.3;
+                (from_location.distance_to(to_location) / effective_speed * 60.0) as i64
+            }
+        };
 
-        // distance_km is already in kilometers
-        let travel_time_hours = distance_km / effective_speed;
-        let travel_time_minutes = (travel_time_hours * 60.0) as i64;
-        
         // Add preparation time (getting ready, equipment check)
         let preparation_time = match team_type {
             "medical" => 5,  // 5 minutes
@@ -106,11 +164,33 @@ impl DispatchEmergencyResponseUseCase {
         Ok(Utc::now() + chrono::Duration::minutes(total_minutes))
     }
 
-    /// Find nearest available emergency station
-    async fn find_nearest_station(&self, _location: &SCoordinates, _team_type: &str) -> AppResult<SCoordinates> {
-        // This would query a database of emergency stations
-        // For now, returning a mock coordinate for Jakarta Emergency Center
-        SCoordinates::new(-6.2088, 106.8456).map_err(|e| AppError::Validation(e.to_string()))
+    /// Claim the nearest available team of the requested type that has enough personnel.
+    /// Teams claimed concurrently by another dispatcher are skipped.
+    async fn claim_nearest_team(
+        &self,
+        destination: &Coordinates,
+        team_type: TeamType,
+        personnel_needed: u32,
+        response_id: EmergencyResponseId,
+    ) -> AppResult<ResponseTeam> {
+        let teams = self.response_team_repository
+            .find_by_filter(Some(team_type), Some(TeamAvailability::Available))
+            .await?;
+
+        for (team, _) in rank_teams_by_distance(&teams, destination, personnel_needed, teams.len()) {
+            let mut team = team.clone();
+            team.dispatch(response_id)?;
+            match self.response_team_repository.update(&team).await {
+                Ok(claimed) => return Ok(claimed),
+                Err(AppError::Conflict(_)) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(AppError::ResourceAllocation(format!(
+            "No available {} team with at least {} personnel",
+            team_type, personnel_needed
+        )))
     }
 
     /// Allocate resources based on disaster severity and type
@@ -197,19 +277,15 @@ impl ValidatedUseCase<DispatchEmergencyResponseRequest, EmergencyResponseDispatc
             .await?
             .ok_or_else(|| AppError::NotFound("Disaster not found".to_string()))?;
 
-        // Validate dispatcher has authority
-        let dispatcher = self.user_repository
+        // Authority is checked by the caller against the policy engine; the dispatcher only has to exist
+        self.user_repository
             .find_by_id(&request.dispatched_by)
             .await?
             .ok_or_else(|| AppError::NotFound("Dispatcher not found".to_string()))?;
 
-        if !dispatcher.role().has_permission(&Permission::ManageEmergencyResponse) {
-            return Err(AppError::Forbidden("Insufficient permissions to dispatch emergency response".to_string()));
-        }
-
-        // Validate team type (case-insensitive, trim spaces)
-        let valid_team_types = ["medical", "fire", "rescue", "police", "search_and_rescue"];
-        let team_type_norm = request.response_team_type.trim().to_lowercase();
+        // Validate team type (case-insensitive, trim spaces, aliases allowed)
+        let valid_team_types = ["medical", "fire", "rescue", "police"];
+        let team_type_norm = normalize_team_type(&request.response_team_type);
         if !valid_team_types.contains(&team_type_norm.as_str()) {
             return Err(AppError::Validation(format!(
                 "Invalid team type. Must be one of: {}",
@@ -240,29 +316,61 @@ impl UseCase<DispatchEmergencyResponseRequest, EmergencyResponseDispatchResponse
             .await?
             .ok_or_else(|| AppError::NotFound("Disaster not found".to_string()))?;
 
-        // Normalize team type and map aliases
-        let mut team_type_norm = request.response_team_type.trim().to_lowercase();
-        if team_type_norm == "search_and_rescue" { team_type_norm = "rescue".to_string(); }
+        let team_type_norm = normalize_team_type(&request.response_team_type);
+        let team_type: TeamType = team_type_norm.parse()?;
+        let personnel = request.estimated_personnel_needed;
+        let destination = request.destination.clone().unwrap_or_else(|| disaster.location().clone());
 
-        // Find nearest station and estimate arrival
-        // Convert disaster location to shared Coordinates
-        let disaster_loc = {
-            let loc = disaster.location();
-            SCoordinates { latitude: loc.latitude, longitude: loc.longitude, altitude: loc.altitude }
-        };
-        let station_coords = self.find_nearest_station(&disaster_loc, &team_type_norm).await?;
+        // Allocate resources and build the response so the team can be claimed against its id
+        let resources = self.allocate_resources(disaster.severity(), &team_type_norm, &request.special_equipment_needed);
+        let mut response = EmergencyResponse::new(
+            request.disaster_id,
+            team_type_norm.clone(),
+            request.priority_level,
+            request.dispatched_by,
+            destination.clone(),
+            personnel,
+            resources.clone(),
+        )?;
+        response.notes = request.notes.clone();
+
+        let team = self.claim_nearest_team(&destination, team_type, personnel, response.id).await?;
+        response.assigned_teams.push(team.id);
+
+        // Estimate arrival from the team's current position
         let estimated_arrival = self
-            .calculate_arrival_time(&station_coords, &disaster_loc, &team_type_norm)
+            .calculate_arrival_time(&team.location, &destination, &team_type_norm)
             .await?;
-
-        // Resolve a human-readable station name (best-effort)
-        let assigned_station_name = match self.geo_service.reverse_geocode(&station_coords).await {
-            Ok(name) => Some(name),
-            Err(_) => None,
+        response.set_estimates(
+            Some(estimated_arrival),
+            request.estimated_duration_minutes
+                .map(|minutes| estimated_arrival + chrono::Duration::minutes(minutes as i64)),
+        );
+
+        // Persist the response; hand the team back if that fails
+        let response = match self.emergency_response_repository.save(&response).await {
+            Ok(saved) => saved,
+            Err(e) => {
+                let mut team = team.clone();
+                team.release();
+                if let Err(release_err) = self.response_team_repository.update(&team).await {
+                    tracing::error!("Failed to release team {} after dispatch failure: {}", team.id, release_err);
+                }
+                return Err(e);
+            }
         };
+        let response_id = response.id.0;
 
-        // Allocate resources
-        let resources = self.allocate_resources(disaster.severity(), &team_type_norm, &request.special_equipment_needed);
+        // Describe where the team is coming from (best-effort)
+        let team_loc = SCoordinates {
+            latitude: team.location.latitude,
+            longitude: team.location.longitude,
+            altitude: team.location.altitude,
+        };
+        let assigned_station_name = match self.geo_service.reverse_geocode(&team_loc).await {
+            Ok(place) => Some(format!("{} ({})", team.name, place)),
+            Err(_) => Some(team.name.clone()),
+        };
 
         // Update disaster status to Responded if currently Reported/Verified
         use crate::domain::entities::disaster::DisasterStatus;
@@ -277,10 +385,6 @@ impl UseCase<DispatchEmergencyResponseRequest, EmergencyResponseDispatchResponse
             let _ = self.disaster_repository.update(&disaster).await?;
         }
 
-        // Create response payload
-        let response_id = Uuid::new_v4();
-        let personnel = request.estimated_personnel_needed;
-
         // Publish domain event
         let event = EmergencyResponseDispatchedEvent {
             event_id: Uuid::new_v4(),
@@ -296,7 +400,7 @@ impl UseCase<DispatchEmergencyResponseRequest, EmergencyResponseDispatchResponse
 
         // Send notifications (best-effort)
         let notify_payload = crate::domain::ports::services::EmergencyResponse {
-            id: crate::shared::types::EmergencyResponseId::new(),
+            id: response.id,
             disaster_id: request.disaster_id.clone(),
             response_team_type: team_type_norm.clone(),
             assigned_station: assigned_station_name.clone(),
@@ -313,8 +417,82 @@ impl UseCase<DispatchEmergencyResponseRequest, EmergencyResponseDispatchResponse
             estimated_arrival,
             personnel_count: personnel,
             equipment_allocated: resources,
-            status: "Dispatched".to_string(),
-            dispatched_at: Utc::now(),
+            status: response.status.to_string(),
+            dispatched_at: response.dispatched_at,
+            assigned_team_id: team.id,
+            assigned_team_name: team.name,
         })
     }
 }
+
+/// Request to move an emergency response along its lifecycle
+#[derive(Debug, Clone)]
+pub struct UpdateEmergencyResponseStatusRequest {
+    pub response_id: EmergencyResponseId,
+    pub new_status: EmergencyResponseStatus,
+    pub actor: Subject,
+    pub location: Option<Coordinates>,
+    pub notes: Option<String>,
+    pub estimated_completion: Option<DateTime<Utc>>,
+}
+
+/// Use case for recording dispatched -> en_route -> on_scene -> completed progress
+pub struct UpdateEmergencyResponseStatusUseCase {
+    emergency_response_repository: Arc<dyn EmergencyResponseRepository>,
+    response_team_repository: Arc<dyn ResponseTeamRepository>,
+    policy: Arc<PolicyEngine>,
+}
+
+impl UpdateEmergencyResponseStatusUseCase {
+    pub fn new(
+        emergency_response_repository: Arc<dyn EmergencyResponseRepository>,
+        response_team_repository: Arc<dyn ResponseTeamRepository>,
+        policy: Arc<PolicyEngine>,
+    ) -> Self {
+        Self {
+            emergency_response_repository,
+            response_team_repository,
+            policy,
+        }
+    }
+}
+
+#[async_trait]
+impl ValidatedUseCase<UpdateEmergencyResponseStatusRequest, EmergencyResponse> for UpdateEmergencyResponseStatusUseCase {
+    async fn validate(&self, request: &UpdateEmergencyResponseStatusRequest) -> AppResult<()> {
+        self.policy.authorize(&request.actor, Permission::ManageEmergencyResponse.as_str(), &Attributes::new())?;
+
+        if let Some(notes) = &request.notes {
+            if notes.len() > 2000 {
+                return Err(AppError::Validation("Progress notes must be at most 2000 characters".to_string()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl UseCase<UpdateEmergencyResponseStatusRequest, EmergencyResponse> for UpdateEmergencyResponseStatusUseCase {
+    async fn execute(&self, request: UpdateEmergencyResponseStatusRequest) -> AppResult<EmergencyResponse> {
+        let mut response = self.emergency_response_repository
+            .find_by_id(&request.response_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Emergency response {} not found", request.response_id)))?;
+
+        response.transition_to(request.new_status, request.actor.user_id, request.location, request.notes)?;
+        response.set_estimates(None, request.estimated_completion);
+
+        let response = self.emergency_response_repository.update(&response).await?;
+
+        // Completed responses free their teams for the next dispatch
+        if !response.is_active() {
+            for mut team in self.response_team_repository.find_by_response(&response.id).await? {
+                team.release();
+                self.response_team_repository.update(&team).await?;
+            }
+        }
+
+        Ok(response)
+    }
+}
@@ -6,6 +6,10 @@ pub mod disaster;
 pub mod notification;
 pub mod user_management;
 pub mod emergency_response;
+pub mod account_verification;
+pub mod report;
+pub mod volunteer;
+pub mod tracking;
 
 // Re-export use cases
 pub use auth::*;
@@ -13,6 +17,10 @@ pub use disaster::*;
 pub use notification::*;
 pub use user_management::*;
 pub use emergency_response::*;
+pub use account_verification::*;
+pub use report::*;
+pub use volunteer::*;
+pub use tracking::*;
 
 // Common use case traits and types
 use async_trait::async_trait;
@@ -8,24 +8,38 @@ use uuid::Uuid;
 
 use crate::application::use_cases::{UseCase, ValidatedUseCase};
 use crate::domain::entities::Notification;
-use crate::domain::entities::disaster::DisasterSeverity;
+use crate::domain::entities::disaster::{DisasterSeverity, DisasterType};
 use crate::domain::value_objects::*;
-use crate::domain::ports::repositories::{NotificationRepository, UserRepository, DisasterRepository};
-use crate::domain::ports::services::{NotificationService, GeolocationService};
-use crate::domain::events::{NotificationSentEvent, MassNotificationTriggeredEvent, EventPublisher};
+use crate::domain::ports::repositories::{NotificationOutboxRepository, UserRepository, DisasterRepository, DisasterZoneRepository};
+use crate::domain::ports::services::GeolocationService;
+use crate::domain::events::{MassNotificationTriggeredEvent, EventPublisher};
 use crate::Permission;
 use crate::shared::{AppResult, AppError};
+use crate::shared::cap::{
+    category_for, CapAlert, CapArea, CapCertainty, CapInfo, CapMsgType, CapScope, CapSeverity, CapStatus,
+    CapUrgency, CAP_SENDER,
+};
 use crate::shared::types::Priority;
 use crate::domain::entities::notification::{NotificationType, NotificationChannel};
 
+/// Where an emergency alert is delivered
+#[derive(Debug, Clone)]
+pub enum AlertTarget {
+    /// Circle around a point, up to 100 km
+    Radius { center: Coordinates, radius_km: f64 },
+    /// Stored zone of the alerted disaster
+    Zone { zone_id: Uuid },
+    /// Ad-hoc polygon or multipolygon
+    Area(GeoArea),
+}
+
 /// Request to send emergency alert to users in affected area
 #[derive(Debug, Clone)]
 pub struct SendEmergencyAlertRequest {
     pub disaster_id: DisasterId,
     pub alert_type: String, // "evacuation", "shelter", "warning", "all_clear"
     pub severity: DisasterSeverity,
-    pub affected_area_center: Coordinates,
-    pub radius_km: f64,
+    pub target: AlertTarget,
     pub message: String,
     pub channels: Vec<String>, // "sms", "email", "push", "whatsapp"
     pub sent_by: UserId,
@@ -38,42 +52,105 @@ pub struct EmergencyAlertResponse {
     pub alert_id: Uuid,
     pub disaster_id: DisasterId,
     pub recipients_targeted: u32,
-    pub notifications_sent: u32,
-    pub failed_deliveries: u32,
+    /// Per-channel deliveries handed to the notification outbox
+    pub deliveries_queued: u32,
     pub channels_used: Vec<String>,
     pub sent_at: DateTime<Utc>,
     pub estimated_delivery_time: u32, // seconds
+    pub cap_alert: CapAlert,
 }
 
 /// Use case for sending emergency alerts
 pub struct SendEmergencyAlertUseCase {
-    notification_repository: Arc<dyn NotificationRepository>,
+    notification_outbox: Arc<dyn NotificationOutboxRepository>,
     user_repository: Arc<dyn UserRepository>,
     disaster_repository: Arc<dyn DisasterRepository>,
-    notification_service: Arc<dyn NotificationService>,
+    disaster_zone_repository: Arc<dyn DisasterZoneRepository>,
     geo_service: Arc<dyn GeolocationService>,
     event_publisher: Arc<dyn EventPublisher>,
 }
 
 impl SendEmergencyAlertUseCase {
     pub fn new(
-        notification_repository: Arc<dyn NotificationRepository>,
+        notification_outbox: Arc<dyn NotificationOutboxRepository>,
         user_repository: Arc<dyn UserRepository>,
         disaster_repository: Arc<dyn DisasterRepository>,
-        notification_service: Arc<dyn NotificationService>,
+        disaster_zone_repository: Arc<dyn DisasterZoneRepository>,
         geo_service: Arc<dyn GeolocationService>,
         event_publisher: Arc<dyn EventPublisher>,
     ) -> Self {
         Self {
-            notification_repository,
+            notification_outbox,
             user_repository,
             disaster_repository,
-            notification_service,
+            disaster_zone_repository,
             geo_service,
             event_publisher,
         }
     }
 
+    /// Polygon of a zone target, checked to belong to the alerted disaster
+    async fn zone_area(&self, disaster_id: &DisasterId, zone_id: &Uuid) -> AppResult<GeoArea> {
+        let zone = self.disaster_zone_repository
+            .find_by_id(zone_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Disaster zone {} not found", zone_id)))?;
+
+        if zone.disaster_id.as_ref() != Some(disaster_id) {
+            return Err(AppError::Validation(format!(
+                "Disaster zone {} does not belong to disaster {}", zone_id, disaster_id
+            )));
+        }
+        Ok(zone.area)
+    }
+
+    /// CAP 1.2 representation of the alert
+    fn build_cap_alert(
+        &self,
+        alert_id: Uuid,
+        request: &SendEmergencyAlertRequest,
+        disaster_type: &DisasterType,
+        area: CapArea,
+        message: &str,
+        sent_at: DateTime<Utc>,
+    ) -> CapAlert {
+        let (response_type, urgency, certainty) = match request.alert_type.as_str() {
+            "evacuation" => ("Evacuate", CapUrgency::Immediate, CapCertainty::Observed),
+            "shelter" => ("Shelter", CapUrgency::Immediate, CapCertainty::Observed),
+            "all_clear" => ("AllClear", CapUrgency::Past, CapCertainty::Observed),
+            _ => ("Monitor", CapUrgency::Expected, CapCertainty::Likely),
+        };
+
+        CapAlert {
+            identifier: alert_id.to_string(),
+            sender: CAP_SENDER.to_string(),
+            sent: sent_at,
+            status: CapStatus::Actual,
+            msg_type: CapMsgType::Alert,
+            scope: CapScope::Public,
+            note: None,
+            references: None,
+            info: vec![CapInfo {
+                language: "id-ID".to_string(),
+                categories: vec![category_for(disaster_type).to_string()],
+                event: format!("{:?}", disaster_type),
+                response_types: vec![response_type.to_string()],
+                urgency,
+                severity: CapSeverity::from(&request.severity),
+                certainty,
+                effective: Some(sent_at),
+                onset: None,
+                expires: request.expires_at,
+                sender_name: Some("Terra Siaga".to_string()),
+                headline: Some(self.get_alert_template(&request.alert_type, &request.severity)),
+                description: Some(message.to_string()),
+                instruction: None,
+                web: None,
+                areas: vec![area],
+            }],
+        }
+    }
+
     /// Get message template based on alert type and severity
     fn get_alert_template(&self, alert_type: &str, severity: &DisasterSeverity) -> String {
         let urgency = match severity {
@@ -167,9 +244,17 @@ impl ValidatedUseCase<SendEmergencyAlertRequest, EmergencyAlertResponse> for Sen
             )));
         }
 
-        // Validate radius
-        if request.radius_km <= 0.0 || request.radius_km > 100.0 {
-            return Err(AppError::Validation("Radius must be between 0.1 and 100 km".to_string()));
+        // Validate target area
+        match &request.target {
+            AlertTarget::Radius { radius_km, .. } => {
+                if *radius_km <= 0.0 || *radius_km > 100.0 {
+                    return Err(AppError::Validation("Radius must be between 0.1 and 100 km".to_string()));
+                }
+            }
+            AlertTarget::Zone { zone_id } => {
+                self.zone_area(&request.disaster_id, zone_id).await?;
+            }
+            AlertTarget::Area(_) => {}
         }
 
         // Validate channels
@@ -207,10 +292,30 @@ impl UseCase<SendEmergencyAlertRequest, EmergencyAlertResponse> for SendEmergenc
         let alert_id = Uuid::new_v4();
         let sent_at = Utc::now();
 
-        // Get users in affected area
-        let affected_users = self.user_repository
-            .find_users_in_radius(&request.affected_area_center, request.radius_km)
-            .await?;
+        let disaster = self.disaster_repository
+            .find_by_id(&request.disaster_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Disaster not found".to_string()))?;
+
+        // Get users whose last known location is in the affected area
+        let (affected_users, affected_zone_id, affected_area_radius_km, cap_area) = match &request.target {
+            AlertTarget::Radius { center, radius_km } => {
+                let users = self.user_repository.find_users_in_radius(center, *radius_km).await?;
+                let desc = format!("{} km around {:.5},{:.5}", radius_km, center.latitude, center.longitude);
+                (users, None, *radius_km, CapArea::circle(desc, center.clone(), *radius_km))
+            }
+            AlertTarget::Zone { zone_id } => {
+                let area = self.zone_area(&request.disaster_id, zone_id).await?;
+                let users = self.user_repository.find_users_in_area(&area).await?;
+                let desc = format!("{} zone {}", disaster.title(), zone_id);
+                (users, Some(*zone_id), area.equivalent_radius_km(), CapArea::from_geo_area(desc, &area))
+            }
+            AlertTarget::Area(area) => {
+                let users = self.user_repository.find_users_in_area(area).await?;
+                let desc = format!("{} alert area", disaster.title());
+                (users, None, area.equivalent_radius_km(), CapArea::from_geo_area(desc, area))
+            }
+        };
 
         let recipients_targeted = affected_users.len() as u32;
 
@@ -219,79 +324,35 @@ impl UseCase<SendEmergencyAlertRequest, EmergencyAlertResponse> for SendEmergenc
         let full_message = format!("{}\n\n{}", template_message, request.message);
         let notif_type = self.map_type_str(&request.alert_type);
         let priority = self.map_severity_to_priority(&request.severity);
+        let channels: Vec<NotificationChannel> = request.channels.iter()
+            .filter_map(|ch| self.map_channel_str(ch))
+            .collect();
 
-        // Send notifications through all specified channels
-        let mut notifications_sent = 0u32;
-        let mut failed_deliveries = 0u32;
-
+        // Queue one delivery per recipient and channel in one batch; the outbox workers send them
+        let mut notifications = Vec::with_capacity(affected_users.len());
         for user in &affected_users {
-            // Determine channels
-            let mut channels = Vec::new();
-            for ch in &request.channels {
-                if let Some(mapped) = self.map_channel_str(ch.as_str()) {
-                    channels.push(mapped);
-                }
-            }
-            if channels.is_empty() { continue; }
-
-            // Build domain notification (single entity with multiple channels)
             let mut notification = Notification::new(
-                user.id().clone(),
+                *user.id(),
                 "Emergency Alert".to_string(),
                 full_message.clone(),
                 notif_type.clone(),
                 priority.clone(),
                 channels.clone(),
             )?;
-
-            // Save notification
-            let saved = self.notification_repository.save(&notification).await?;
-
-            // Attempt to send per channel
-            for ch in &channels {
-                let send_res = match ch {
-                    NotificationChannel::SMS => {
-                        if let Some(phone) = user.phone_number() { self.notification_service.send_sms(phone.value(), &full_message).await }
-                        else { Err(AppError::Validation("User has no phone number".to_string())) }
-                    }
-                    NotificationChannel::Email => {
-                        self.notification_service.send_email(user.email().value(), "Emergency Alert", &full_message).await
-                    }
-                    NotificationChannel::WhatsApp => {
-                        if let Some(phone) = user.phone_number() { self.notification_service.send_whatsapp(phone.value(), &full_message).await }
-                        else { Err(AppError::Validation("User has no phone number".to_string())) }
-                    }
-                    NotificationChannel::Push | NotificationChannel::InApp => {
-                        self.notification_service.send_push_notification(user.id().clone(), "Emergency Alert", &full_message).await
-                    }
-                };
-
-                match send_res {
-                    Ok(_) => {
-                        notifications_sent += 1;
-                        let event = NotificationSentEvent {
-                            event_id: Uuid::new_v4(),
-                            notification_id: saved.id,
-                            recipient_id: *user.id(),
-                            notification_type: "emergency_alert".to_string(),
-                            channel: match ch { NotificationChannel::SMS => "sms", NotificationChannel::Email => "email", NotificationChannel::WhatsApp => "whatsapp", _ => "push" }.to_string(),
-                            content: full_message.clone(),
-                            occurred_at: sent_at,
-                            version: 1,
-                        };
-                        let _ = self.event_publisher.publish(&event).await;
-                    }
-                    Err(_) => { failed_deliveries += 1; }
-                }
-            }
+            notification.metadata.source_entity_type = Some("disaster".to_string());
+            notification.metadata.source_entity_id = Some(request.disaster_id.to_string());
+            notification.metadata.expires_at = request.expires_at;
+            notifications.push(notification);
         }
+        let deliveries_queued = self.notification_outbox.enqueue_batch(&notifications).await?.len() as u32;
 
         // Publish mass notification event
         let mass_event = MassNotificationTriggeredEvent {
             event_id: Uuid::new_v4(),
             disaster_id: request.disaster_id.clone(),
             triggered_by: request.sent_by.clone(),
-            affected_area_radius_km: request.radius_km,
+            affected_area_radius_km,
+            affected_zone_id,
             notification_type: request.alert_type.clone(),
             estimated_recipients: recipients_targeted,
             occurred_at: sent_at,
@@ -301,16 +362,19 @@ impl UseCase<SendEmergencyAlertRequest, EmergencyAlertResponse> for SendEmergenc
         self.event_publisher.publish(&mass_event).await?;
 
         let estimated_delivery_time = self.estimate_delivery_time(&request.channels, recipients_targeted);
+        let cap_alert = self.build_cap_alert(
+            alert_id, &request, disaster.disaster_type(), cap_area, &request.message, sent_at,
+        );
 
         Ok(EmergencyAlertResponse {
             alert_id,
             disaster_id: request.disaster_id,
             recipients_targeted,
-            notifications_sent,
-            failed_deliveries,
+            deliveries_queued,
             channels_used: request.channels,
             sent_at,
             estimated_delivery_time,
+            cap_alert,
         })
     }
 }
@@ -331,24 +395,18 @@ pub struct SendCustomNotificationRequest {
 
 /// Use case for sending custom notifications
 pub struct SendCustomNotificationUseCase {
-    notification_repository: Arc<dyn NotificationRepository>,
+    notification_outbox: Arc<dyn NotificationOutboxRepository>,
     user_repository: Arc<dyn UserRepository>,
-    notification_service: Arc<dyn NotificationService>,
-    event_publisher: Arc<dyn EventPublisher>,
 }
 
 impl SendCustomNotificationUseCase {
     pub fn new(
-        notification_repository: Arc<dyn NotificationRepository>,
+        notification_outbox: Arc<dyn NotificationOutboxRepository>,
         user_repository: Arc<dyn UserRepository>,
-        notification_service: Arc<dyn NotificationService>,
-        event_publisher: Arc<dyn EventPublisher>,
     ) -> Self {
         Self {
-            notification_repository,
+            notification_outbox,
             user_repository,
-            notification_service,
-            event_publisher,
         }
     }
 
@@ -416,8 +474,15 @@ impl ValidatedUseCase<SendCustomNotificationRequest, Vec<NotificationId>> for Se
 #[async_trait]
 impl UseCase<SendCustomNotificationRequest, Vec<NotificationId>> for SendCustomNotificationUseCase {
     async fn execute(&self, request: SendCustomNotificationRequest) -> AppResult<Vec<NotificationId>> {
-        let mut sent_notifications = Vec::new();
-        let sent_at = Utc::now();
+        let mut queued_notifications = Vec::new();
+
+        // Map channels
+        let channels: Vec<NotificationChannel> = request.channels.iter()
+            .filter_map(|ch| self.map_channel_str(ch))
+            .collect();
+        if channels.is_empty() {
+            return Ok(queued_notifications);
+        }
 
         for recipient_id in &request.recipient_ids {
             // Get recipient
@@ -426,60 +491,23 @@ impl UseCase<SendCustomNotificationRequest, Vec<NotificationId>> for SendCustomN
                 None => continue, // Skip if user not found
             };
 
-            // Map channels
-            let mut channels = Vec::new();
-            for ch in &request.channels { if let Some(c) = self.map_channel_str(ch.as_str()) { channels.push(c); } }
-            if channels.is_empty() { continue; }
-
             // Create notification
             let mut notification = Notification::new(
-                user.id().clone(),
+                *user.id(),
                 request.title.clone(),
                 request.message.clone(),
                 NotificationType::Other(request.notification_type.clone()),
                 match request.priority { 1 => Priority::Low, 2 => Priority::Normal, 3 => Priority::High, 4 => Priority::Critical, 5 => Priority::Emergency, _ => Priority::Normal},
                 channels.clone(),
             )?;
+            notification.metadata.action_url = request.action_url.clone();
+            notification.metadata.expires_at = request.expires_at;
 
-            // Save and send notification
-            let saved = self.notification_repository.save(&notification).await?;
-
-            for ch in &channels {
-                let send_res = match ch {
-                    NotificationChannel::SMS => {
-                        if let Some(phone) = user.phone_number() { self.notification_service.send_sms(phone.value(), &request.message).await }
-                        else { Err(AppError::Validation("User has no phone number".to_string())) }
-                    }
-                    NotificationChannel::Email => {
-                        self.notification_service.send_email(user.email().value(), &request.title, &request.message).await
-                    }
-                    NotificationChannel::WhatsApp => {
-                        if let Some(phone) = user.phone_number() { self.notification_service.send_whatsapp(phone.value(), &request.message).await }
-                        else { Err(AppError::Validation("User has no phone number".to_string())) }
-                    }
-                    NotificationChannel::Push | NotificationChannel::InApp => {
-                        self.notification_service.send_push_notification(user.id().clone(), &request.title, &request.message).await
-                    }
-                };
-
-                if send_res.is_ok() {
-                    sent_notifications.push(saved.id);
-                    let event = NotificationSentEvent {
-                        event_id: Uuid::new_v4(),
-                        notification_id: saved.id,
-                        recipient_id: user.id().clone(),
-                        notification_type: request.notification_type.clone(),
-                        channel: match ch { NotificationChannel::SMS => "sms", NotificationChannel::Email => "email", NotificationChannel::WhatsApp => "whatsapp", _ => "push" }.to_string(),
-                        content: request.message.clone(),
-                        occurred_at: sent_at,
-                        version: 1,
-                    };
-                    let _ = self.event_publisher.publish(&event).await;
-                }
-            }
+            // Queue for delivery by the outbox workers
+            self.notification_outbox.enqueue(&notification).await?;
+            queued_notifications.push(notification.id);
         }
 
-        Ok(sent_notifications)
+        Ok(queued_notifications)
     }
-
 }
@@ -0,0 +1,606 @@
+/// Report intake and review use cases
+/// Handles (anonymous) report submission with photo/video uploads, edits by the reporter,
+/// validator status changes and linking reports to the disaster they describe
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::application::use_cases::{UseCase, ValidatedUseCase};
+use crate::domain::entities::report::{
+    MediaKind, Report, ReportDetails, ReportMedia, ReportSource, ReportStatus, ReportValidation,
+    WeatherAgreement,
+};
+use crate::domain::ports::repositories::{DisasterRepository, ReportRepository};
+use crate::domain::ports::services::{MediaStorage, WeatherService};
+use crate::domain::services::credibility::{CredibilityPolicy, CredibilityService, ReporterHistory};
+use crate::shared::policy::{Attributes, PolicyEngine, Subject};
+use crate::shared::{AppError, AppResult, Coordinates, DisasterId, ReportId, UserId};
+
+/// Most files one report may carry
+pub const MAX_MEDIA_PER_REPORT: usize = 10;
+
+/// A photo or video received with a request
+#[derive(Debug, Clone)]
+pub struct MediaUpload {
+    pub content_type: String,
+    pub bytes: Vec<u8>,
+    pub caption: Option<String>,
+}
+
+/// A report as returned to API clients, with its media and linked disasters
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportResponse {
+    pub id: ReportId,
+    pub source: ReportSource,
+    #[serde(flatten)]
+    pub details: ReportDetails,
+    pub status: ReportStatus,
+    pub credibility_score: f64,
+    pub validation: Option<ReportValidation>,
+    pub media: Vec<ReportMedia>,
+    pub disaster_ids: Vec<DisasterId>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ReportResponse {
+    pub fn new(report: Report, media: Vec<ReportMedia>, disaster_ids: Vec<DisasterId>) -> Self {
+        Self {
+            id: report.id,
+            source: report.source,
+            details: report.details,
+            status: report.status,
+            credibility_score: report.credibility_score,
+            validation: report.validation,
+            media,
+            disaster_ids,
+            created_at: report.created_at,
+            updated_at: report.updated_at,
+        }
+    }
+
+    /// Hide an anonymous reporter's phone number from callers who do not validate reports
+    pub fn without_contact_details(mut self) -> Self {
+        self.source = self.source.redacted();
+        self
+    }
+}
+
+/// Attributes of a report that access policies can test
+pub fn report_policy_attributes(report: &Report) -> Attributes {
+    let mut attributes = Attributes::new()
+        .with("id", report.id.to_string())
+        .with("status", report.status.as_str());
+    if let Some(reporter_id) = report.reporter_id() {
+        attributes.insert("reporter_id", reporter_id.to_string());
+    }
+    attributes
+}
+
+/// Storage key and accepted kind for an upload
+fn media_key(report_id: &ReportId, media_id: &Uuid, content_type: &str) -> AppResult<(String, MediaKind)> {
+    let kind = MediaKind::from_content_type(content_type).ok_or_else(|| AppError::Validation(format!(
+        "Unsupported media type '{}'; upload JPEG, PNG, WebP or HEIC photos or MP4, MOV, WebM or 3GP videos",
+        content_type
+    )))?;
+    let extension = match content_type.trim().to_lowercase().as_str() {
+        "image/jpeg" => "jpg",
+        "image/png" => "png",
+        "image/webp" => "webp",
+        "image/heic" => "heic",
+        "image/heif" => "heif",
+        "video/mp4" => "mp4",
+        "video/quicktime" => "mov",
+        "video/webm" => "webm",
+        _ => "3gp",
+    };
+    Ok((format!("reports/{}/{}.{}", report_id, media_id, extension), kind))
+}
+
+fn validate_uploads(uploads: &[MediaUpload], max_upload_bytes: usize) -> AppResult<()> {
+    for upload in uploads {
+        if MediaKind::from_content_type(&upload.content_type).is_none() {
+            return Err(AppError::Validation(format!("Unsupported media type '{}'", upload.content_type)));
+        }
+        if upload.bytes.is_empty() {
+            return Err(AppError::Validation("Uploaded file is empty".to_string()));
+        }
+        if upload.bytes.len() > max_upload_bytes {
+            return Err(AppError::Validation(format!("Uploaded file exceeds {} bytes", max_upload_bytes)));
+        }
+        ReportMedia::caption(upload.caption.clone())?;
+    }
+    Ok(())
+}
+
+/// Writes uploads to the media storage and describes them for the repository
+struct MediaStore {
+    storage: Arc<dyn MediaStorage>,
+}
+
+impl MediaStore {
+    async fn store(&self, report_id: ReportId, upload: MediaUpload, is_primary: bool, uploaded_by: Option<UserId>) -> AppResult<ReportMedia> {
+        let id = Uuid::new_v4();
+        let (key, kind) = media_key(&report_id, &id, &upload.content_type)?;
+        let size_bytes = upload.bytes.len() as i64;
+        let url = self.storage.put(&key, &upload.content_type, upload.bytes).await?;
+        Ok(ReportMedia {
+            id,
+            report_id,
+            kind,
+            url,
+            storage_key: Some(key),
+            content_type: Some(upload.content_type),
+            size_bytes: Some(size_bytes),
+            caption: ReportMedia::caption(upload.caption)?,
+            is_primary,
+            uploaded_by,
+            created_at: Utc::now(),
+        })
+    }
+
+    /// Best-effort removal of files whose database rows were never written
+    async fn discard(&self, media: &[ReportMedia]) {
+        for key in media.iter().filter_map(|m| m.storage_key.as_deref()) {
+            if let Err(e) = self.storage.delete(key).await {
+                tracing::warn!("Failed to remove orphaned {} media {}: {}", self.storage.backend(), key, e);
+            }
+        }
+    }
+}
+
+/// Request to submit a report, from a signed-in user or anonymously
+#[derive(Debug, Clone)]
+pub struct SubmitReportRequest {
+    pub source: ReportSource,
+    pub details: ReportDetails,
+    /// The first file becomes the report's primary media
+    pub media: Vec<MediaUpload>,
+}
+
+/// Use case for report intake
+pub struct SubmitReportUseCase {
+    report_repository: Arc<dyn ReportRepository>,
+    media: MediaStore,
+    credibility: Arc<AssessReportCredibilityUseCase>,
+    max_upload_bytes: usize,
+}
+
+impl SubmitReportUseCase {
+    pub fn new(
+        report_repository: Arc<dyn ReportRepository>,
+        media_storage: Arc<dyn MediaStorage>,
+        credibility: Arc<AssessReportCredibilityUseCase>,
+        max_upload_bytes: usize,
+    ) -> Self {
+        Self {
+            report_repository,
+            media: MediaStore { storage: media_storage },
+            credibility,
+            max_upload_bytes,
+        }
+    }
+}
+
+#[async_trait]
+impl ValidatedUseCase<SubmitReportRequest, ReportResponse> for SubmitReportUseCase {
+    async fn validate(&self, request: &SubmitReportRequest) -> AppResult<()> {
+        if request.media.len() > MAX_MEDIA_PER_REPORT {
+            return Err(AppError::Validation(format!("A report can carry at most {} files", MAX_MEDIA_PER_REPORT)));
+        }
+        validate_uploads(&request.media, self.max_upload_bytes)
+    }
+}
+
+#[async_trait]
+impl UseCase<SubmitReportRequest, ReportResponse> for SubmitReportUseCase {
+    async fn execute(&self, request: SubmitReportRequest) -> AppResult<ReportResponse> {
+        let (report, submitted) = Report::submit(request.source, request.details)?;
+        let uploaded_by = report.reporter_id();
+
+        // Files go to storage first so a failed upload leaves no half-submitted report behind
+        let mut media = Vec::with_capacity(request.media.len());
+        for (index, upload) in request.media.into_iter().enumerate() {
+            match self.media.store(report.id, upload, index == 0, uploaded_by).await {
+                Ok(stored) => media.push(stored),
+                Err(e) => {
+                    self.media.discard(&media).await;
+                    return Err(e);
+                }
+            }
+        }
+
+        let saved = match self.report_repository.create(&report, &submitted).await {
+            Ok(saved) => saved,
+            Err(e) => {
+                self.media.discard(&media).await;
+                return Err(e);
+            }
+        };
+        for item in &media {
+            self.report_repository.add_media(item).await?;
+        }
+
+        // Scoring is best effort; an unscored report still reaches the triage queue
+        let saved = match self.credibility.score(&saved.id, true).await {
+            Ok(scored) => scored,
+            Err(e) => {
+                tracing::warn!("Could not score report {}: {}", saved.id, e);
+                saved
+            }
+        };
+
+        tracing::info!(
+            "Report {} submitted {} with {} file(s)",
+            saved.id,
+            if saved.is_anonymous() { "anonymously" } else { "by a registered user" },
+            media.len()
+        );
+        Ok(ReportResponse::new(saved, media, Vec::new()))
+    }
+}
+
+/// Request to attach a photo or video to an existing report
+#[derive(Debug, Clone)]
+pub struct UploadReportMediaRequest {
+    pub report_id: ReportId,
+    pub actor: Subject,
+    pub upload: MediaUpload,
+    pub is_primary: bool,
+}
+
+/// Use case for adding media after submission; the same `reports:update` policy as editing applies
+pub struct UploadReportMediaUseCase {
+    report_repository: Arc<dyn ReportRepository>,
+    media: MediaStore,
+    policy: Arc<PolicyEngine>,
+    credibility: Arc<AssessReportCredibilityUseCase>,
+    max_upload_bytes: usize,
+}
+
+impl UploadReportMediaUseCase {
+    pub fn new(
+        report_repository: Arc<dyn ReportRepository>,
+        media_storage: Arc<dyn MediaStorage>,
+        policy: Arc<PolicyEngine>,
+        credibility: Arc<AssessReportCredibilityUseCase>,
+        max_upload_bytes: usize,
+    ) -> Self {
+        Self {
+            report_repository,
+            media: MediaStore { storage: media_storage },
+            policy,
+            credibility,
+            max_upload_bytes,
+        }
+    }
+}
+
+#[async_trait]
+impl ValidatedUseCase<UploadReportMediaRequest, ReportMedia> for UploadReportMediaUseCase {
+    async fn validate(&self, request: &UploadReportMediaRequest) -> AppResult<()> {
+        validate_uploads(std::slice::from_ref(&request.upload), self.max_upload_bytes)
+    }
+}
+
+#[async_trait]
+impl UseCase<UploadReportMediaRequest, ReportMedia> for UploadReportMediaUseCase {
+    async fn execute(&self, request: UploadReportMediaRequest) -> AppResult<ReportMedia> {
+        let report = self.report_repository.find_by_id(&request.report_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Report {} not found", request.report_id)))?;
+        self.policy.authorize(&request.actor, "reports:update", &report_policy_attributes(&report))?;
+
+        let existing = self.report_repository.find_media(&report.id).await?;
+        if existing.len() >= MAX_MEDIA_PER_REPORT {
+            return Err(AppError::BusinessRuleViolation(format!(
+                "Report {} already carries {} files",
+                report.id, MAX_MEDIA_PER_REPORT
+            )));
+        }
+
+        let is_primary = request.is_primary || existing.is_empty();
+        let media = self.media.store(report.id, request.upload, is_primary, Some(request.actor.user_id)).await?;
+        if let Err(e) = self.report_repository.add_media(&media).await {
+            self.media.discard(std::slice::from_ref(&media)).await;
+            return Err(e);
+        }
+        if let Err(e) = self.credibility.score(&report.id, false).await {
+            tracing::warn!("Could not rescore report {} after a media upload: {}", report.id, e);
+        }
+        Ok(media)
+    }
+}
+
+/// Request to revise a report's details
+#[derive(Debug, Clone)]
+pub struct UpdateReportRequest {
+    pub report_id: ReportId,
+    pub actor: Subject,
+    pub details: ReportDetails,
+}
+
+/// Use case for editing a report; reporters may edit their own until review starts
+pub struct UpdateReportUseCase {
+    report_repository: Arc<dyn ReportRepository>,
+    policy: Arc<PolicyEngine>,
+}
+
+impl UpdateReportUseCase {
+    pub fn new(report_repository: Arc<dyn ReportRepository>, policy: Arc<PolicyEngine>) -> Self {
+        Self { report_repository, policy }
+    }
+}
+
+#[async_trait]
+impl UseCase<UpdateReportRequest, Report> for UpdateReportUseCase {
+    async fn execute(&self, request: UpdateReportRequest) -> AppResult<Report> {
+        let mut report = self.report_repository.find_by_id(&request.report_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Report {} not found", request.report_id)))?;
+        self.policy.authorize(&request.actor, "reports:update", &report_policy_attributes(&report))?;
+
+        report.revise(request.details)?;
+        self.report_repository.update_details(&report).await
+    }
+}
+
+/// Request to move a report through review
+#[derive(Debug, Clone)]
+pub struct ChangeReportStatusRequest {
+    pub report_id: ReportId,
+    pub new_status: String,
+    pub actor: Subject,
+    pub notes: Option<String>,
+}
+
+/// Use case for validators reviewing, verifying, rejecting or resolving reports (`reports:validate`)
+pub struct ChangeReportStatusUseCase {
+    report_repository: Arc<dyn ReportRepository>,
+    policy: Arc<PolicyEngine>,
+}
+
+impl ChangeReportStatusUseCase {
+    pub fn new(report_repository: Arc<dyn ReportRepository>, policy: Arc<PolicyEngine>) -> Self {
+        Self { report_repository, policy }
+    }
+}
+
+#[async_trait]
+impl ValidatedUseCase<ChangeReportStatusRequest, Report> for ChangeReportStatusUseCase {
+    async fn validate(&self, request: &ChangeReportStatusRequest) -> AppResult<()> {
+        request.new_status.parse::<ReportStatus>().map(|_| ())
+    }
+}
+
+#[async_trait]
+impl UseCase<ChangeReportStatusRequest, Report> for ChangeReportStatusUseCase {
+    async fn execute(&self, request: ChangeReportStatusRequest) -> AppResult<Report> {
+        let new_status: ReportStatus = request.new_status.parse()?;
+        let mut report = self.report_repository.find_by_id(&request.report_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Report {} not found", request.report_id)))?;
+        self.policy.authorize(&request.actor, "reports:validate", &report_policy_attributes(&report))?;
+
+        if new_status == ReportStatus::Verified && report.reporter_id() == Some(request.actor.user_id) {
+            return Err(AppError::Forbidden("Reports must be verified by someone other than their reporter".to_string()));
+        }
+
+        let change = report.change_status(new_status, request.actor.user_id, request.notes)?;
+        self.report_repository.change_status(&report, &change).await
+    }
+}
+
+/// Request to link reports to the disaster they describe
+#[derive(Debug, Clone)]
+pub struct LinkReportsRequest {
+    pub disaster_id: DisasterId,
+    pub report_ids: Vec<ReportId>,
+    pub actor: Subject,
+    pub notes: Option<String>,
+}
+
+/// Outcome for one report of a link request
+#[derive(Debug, Clone, Serialize)]
+pub struct LinkedReport {
+    pub report_id: ReportId,
+    /// False when the report was already linked to the disaster
+    pub linked: bool,
+    pub status: ReportStatus,
+}
+
+/// Use case for validators linking reports to a disaster (`reports:validate`)
+/// Linking confirms a report, so reports still awaiting review are verified on the way
+pub struct LinkReportsToDisasterUseCase {
+    report_repository: Arc<dyn ReportRepository>,
+    disaster_repository: Arc<dyn DisasterRepository>,
+    policy: Arc<PolicyEngine>,
+}
+
+impl LinkReportsToDisasterUseCase {
+    pub fn new(
+        report_repository: Arc<dyn ReportRepository>,
+        disaster_repository: Arc<dyn DisasterRepository>,
+        policy: Arc<PolicyEngine>,
+    ) -> Self {
+        Self { report_repository, disaster_repository, policy }
+    }
+
+    /// Derive the disaster's confidence score from the credibility of every report linked to it
+    async fn reassess_confidence(&self, disaster_id: &DisasterId) -> AppResult<()> {
+        let mut disaster = self.disaster_repository.find_by_id(disaster_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Disaster {} not found", disaster_id)))?;
+        let scores: Vec<f64> = self.report_repository.find_by_disaster(disaster_id).await?
+            .into_iter()
+            .filter(|report| report.status != ReportStatus::Rejected)
+            .map(|report| report.credibility_score)
+            .collect();
+
+        if disaster.assess_confidence(CredibilityService::combined_confidence(&scores), scores.len() as u32) {
+            self.disaster_repository.update(&disaster).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ValidatedUseCase<LinkReportsRequest, Vec<LinkedReport>> for LinkReportsToDisasterUseCase {
+    async fn validate(&self, request: &LinkReportsRequest) -> AppResult<()> {
+        if request.report_ids.is_empty() {
+            return Err(AppError::Validation("At least one report id is required".to_string()));
+        }
+        if request.report_ids.len() > 100 {
+            return Err(AppError::Validation("At most 100 reports can be linked at once".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl UseCase<LinkReportsRequest, Vec<LinkedReport>> for LinkReportsToDisasterUseCase {
+    async fn execute(&self, request: LinkReportsRequest) -> AppResult<Vec<LinkedReport>> {
+        self.disaster_repository.find_by_id(&request.disaster_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Disaster {} not found", request.disaster_id)))?;
+
+        // Authorize every report before linking any of them
+        let mut reports = Vec::with_capacity(request.report_ids.len());
+        for report_id in &request.report_ids {
+            let report = self.report_repository.find_by_id(report_id).await?
+                .ok_or_else(|| AppError::NotFound(format!("Report {} not found", report_id)))?;
+            self.policy.authorize(&request.actor, "reports:validate", &report_policy_attributes(&report))?;
+            if report.status == ReportStatus::Rejected {
+                return Err(AppError::BusinessRuleViolation(format!("Report {} was rejected and cannot be linked", report.id)));
+            }
+            reports.push(report);
+        }
+
+        let notes = request.notes.clone()
+            .unwrap_or_else(|| format!("Linked to disaster {}", request.disaster_id));
+        let mut results = Vec::with_capacity(reports.len());
+        for mut report in reports {
+            let linked = self.report_repository
+                .link_to_disaster(&report.id, &request.disaster_id, &request.actor.user_id)
+                .await?;
+            if matches!(report.status, ReportStatus::Reported | ReportStatus::UnderReview) {
+                let change = report.change_status(ReportStatus::Verified, request.actor.user_id, Some(notes.clone()))?;
+                report = self.report_repository.change_status(&report, &change).await?;
+            }
+            results.push(LinkedReport { report_id: report.id, linked, status: report.status });
+        }
+
+        tracing::info!(
+            "{} report(s) linked to disaster {} by {}",
+            results.iter().filter(|r| r.linked).count(),
+            request.disaster_id,
+            request.actor.user_id
+        );
+
+        if let Err(e) = self.reassess_confidence(&request.disaster_id).await {
+            tracing::warn!("Could not update confidence of disaster {}: {}", request.disaster_id, e);
+        }
+        Ok(results)
+    }
+}
+
+/// Request to rescore a report, e.g. after a validator asks for a fresh assessment
+#[derive(Debug, Clone)]
+pub struct AssessReportCredibilityRequest {
+    pub report_id: ReportId,
+    /// Also rescore pending reports around it, whose corroboration may have changed
+    pub include_neighbours: bool,
+}
+
+/// Use case for scoring a report's credibility and placing it in an incident cluster
+pub struct AssessReportCredibilityUseCase {
+    report_repository: Arc<dyn ReportRepository>,
+    weather: Option<Arc<dyn WeatherService>>,
+    policy: CredibilityPolicy,
+}
+
+impl AssessReportCredibilityUseCase {
+    /// Weather is only compared for reports this recent; older ones keep their stored observation
+    const LIVE_WEATHER_WINDOW_HOURS: i64 = 3;
+
+    pub fn new(
+        report_repository: Arc<dyn ReportRepository>,
+        weather: Option<Arc<dyn WeatherService>>,
+        policy: CredibilityPolicy,
+    ) -> Self {
+        Self { report_repository, weather, policy }
+    }
+
+    /// Score one report, then optionally rescore the pending reports it corroborates
+    pub async fn score(&self, report_id: &ReportId, include_neighbours: bool) -> AppResult<Report> {
+        let (report, neighbours) = self.score_one(report_id).await?;
+        if include_neighbours {
+            for neighbour in neighbours {
+                if let Err(e) = self.score_one(&neighbour).await {
+                    tracing::warn!("Could not rescore report {} near {}: {}", neighbour, report_id, e);
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Returns the scored report and the pending reports of the same kind around it
+    async fn score_one(&self, report_id: &ReportId) -> AppResult<(Report, Vec<ReportId>)> {
+        let mut report = self.report_repository.find_by_id(report_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Report {} not found", report_id)))?;
+
+        let history = match report.reporter_id() {
+            Some(reporter_id) => self.report_repository.reporter_history(&reporter_id, &report.id).await?,
+            None => ReporterHistory::default(),
+        };
+        let nearby = self.report_repository.find_nearby(
+            &report.details.location,
+            self.policy.radius_m,
+            report.created_at - self.policy.window,
+            report.created_at + self.policy.window,
+            &report.id,
+        ).await?;
+        let media = self.report_repository.find_media(&report.id).await?;
+        let weather = self.weather_agreement(&report).await;
+
+        let assessment = CredibilityService::assess(&self.policy, &report, history, &nearby, &media, weather);
+        report.record_credibility(assessment.score, assessment.factors, assessment.cluster_id);
+        self.report_repository.record_credibility(&report, &assessment.absorbed_clusters).await?;
+
+        let same_type = std::mem::discriminant(&report.details.disaster_type);
+        let neighbours = nearby.into_iter()
+            .filter(|other| std::mem::discriminant(&other.disaster_type) == same_type)
+            .filter(|other| matches!(other.status, ReportStatus::Reported | ReportStatus::UnderReview))
+            .map(|other| other.id)
+            .collect();
+        Ok((report, neighbours))
+    }
+
+    async fn weather_agreement(&self, report: &Report) -> WeatherAgreement {
+        let stored = report.credibility.as_ref().map(|factors| factors.weather);
+        let is_recent = Utc::now() - report.created_at < chrono::Duration::hours(Self::LIVE_WEATHER_WINDOW_HOURS);
+        let weather = match &self.weather {
+            Some(weather) if is_recent && stored.is_none_or(|w| w == WeatherAgreement::Unavailable) => weather,
+            // Current conditions say nothing about an old report; keep what was observed at the time
+            _ => return stored.unwrap_or(WeatherAgreement::Unavailable),
+        };
+
+        let location = Coordinates {
+            latitude: report.details.location.latitude,
+            longitude: report.details.location.longitude,
+            altitude: report.details.location.altitude,
+        };
+        match weather.get_current_weather(location).await {
+            Ok(observed) => CredibilityService::weather_agreement(&report.details.disaster_type, Some(&observed)),
+            Err(e) => {
+                tracing::warn!("Weather lookup failed for report {}: {}", report.id, e);
+                WeatherAgreement::Unavailable
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl UseCase<AssessReportCredibilityRequest, Report> for AssessReportCredibilityUseCase {
+    async fn execute(&self, request: AssessReportCredibilityRequest) -> AppResult<Report> {
+        self.score(&request.report_id, request.include_neighbours).await
+    }
+}
@@ -0,0 +1,315 @@
+/// Location tracking use cases
+/// Responders' devices upload GPS fixes in batches, possibly long after they were taken. Fixes are
+/// stored as a time series; the newest one moves the responder's team, and the first one inside
+/// the geofence around where they were sent marks the assignment arrived and the disaster responded.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::use_cases::{assignment_policy_attributes, UseCase, ValidatedUseCase};
+use crate::domain::entities::response_team::ResponseTeam;
+use crate::domain::entities::tracking::{FixSource, LocationFix, LocationSample};
+use crate::domain::entities::volunteer::{AssignmentStatus, Volunteer, VolunteerAssignment};
+use crate::domain::ports::repositories::{
+    DisasterRepository, EmergencyResponseRepository, LocationTrackingRepository, ReportRepository,
+    ResponseTeamRepository, VolunteerRepository,
+};
+use crate::domain::services::geofence::GeofencePolicy;
+use crate::domain::value_objects::Coordinates;
+use crate::shared::policy::{Attributes, PolicyEngine, Subject};
+use crate::shared::{AppError, AppResult, DisasterId, UserId};
+
+/// A batch of fixes from one device
+#[derive(Debug, Clone)]
+pub struct RecordLocationFixesRequest {
+    pub actor: Subject,
+    /// Assignment the fixes were recorded for; the caller's assignment in progress when omitted
+    pub assignment_id: Option<Uuid>,
+    /// Response team the caller travels with
+    pub team_id: Option<Uuid>,
+    pub samples: Vec<LocationSample>,
+}
+
+/// A fix that failed validation, by its position in the batch
+#[derive(Debug, Clone, Serialize)]
+pub struct RejectedFix {
+    pub index: usize,
+    pub reason: String,
+}
+
+/// Arrival detected from the batch
+#[derive(Debug, Clone, Serialize)]
+pub struct TrackedArrival {
+    pub assignment_id: Option<Uuid>,
+    pub team_id: Option<Uuid>,
+    pub disaster_id: Option<DisasterId>,
+    pub arrived_at: DateTime<Utc>,
+    /// Whether this arrival became the disaster's first response
+    pub first_response: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LocationBatchResult {
+    pub accepted: usize,
+    /// Valid fixes already stored by an earlier upload of the same batch
+    pub duplicates: usize,
+    pub rejected: Vec<RejectedFix>,
+    pub assignment_id: Option<Uuid>,
+    pub arrivals: Vec<TrackedArrival>,
+}
+
+/// Use case for responders' devices sharing their position
+/// Volunteers report for their own assignments (`volunteers:respond`); reporting for a response
+/// team also moves the team (`emergency:update_status`)
+pub struct RecordLocationFixesUseCase {
+    tracking_repository: Arc<dyn LocationTrackingRepository>,
+    volunteer_repository: Arc<dyn VolunteerRepository>,
+    report_repository: Arc<dyn ReportRepository>,
+    disaster_repository: Arc<dyn DisasterRepository>,
+    response_team_repository: Arc<dyn ResponseTeamRepository>,
+    emergency_response_repository: Arc<dyn EmergencyResponseRepository>,
+    policy: Arc<PolicyEngine>,
+    geofence: GeofencePolicy,
+    max_batch_size: usize,
+}
+
+impl RecordLocationFixesUseCase {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        tracking_repository: Arc<dyn LocationTrackingRepository>,
+        volunteer_repository: Arc<dyn VolunteerRepository>,
+        report_repository: Arc<dyn ReportRepository>,
+        disaster_repository: Arc<dyn DisasterRepository>,
+        response_team_repository: Arc<dyn ResponseTeamRepository>,
+        emergency_response_repository: Arc<dyn EmergencyResponseRepository>,
+        policy: Arc<PolicyEngine>,
+        geofence: GeofencePolicy,
+        max_batch_size: usize,
+    ) -> Self {
+        Self {
+            tracking_repository,
+            volunteer_repository,
+            report_repository,
+            disaster_repository,
+            response_team_repository,
+            emergency_response_repository,
+            policy,
+            geofence,
+            max_batch_size,
+        }
+    }
+
+    /// The named assignment, which must be the caller's and under way, or else the caller's
+    /// newest assignment under way
+    async fn resolve_assignment(
+        &self,
+        request: &RecordLocationFixesRequest,
+        volunteer: Option<&Volunteer>,
+    ) -> AppResult<Option<VolunteerAssignment>> {
+        let Some(volunteer) = volunteer else {
+            return match request.assignment_id {
+                Some(_) => Err(AppError::Forbidden("You are not registered as a volunteer".to_string())),
+                None => Ok(None),
+            };
+        };
+        let under_way = |a: &VolunteerAssignment| matches!(a.status, AssignmentStatus::Accepted | AssignmentStatus::Arrived);
+
+        let assignment = match request.assignment_id {
+            Some(id) => {
+                let assignment = self.volunteer_repository.find_assignment(&id).await?
+                    .filter(|a| a.volunteer_id == volunteer.id)
+                    .ok_or_else(|| AppError::NotFound(format!("Assignment {} not found", id)))?;
+                if !under_way(&assignment) {
+                    return Err(AppError::BusinessRuleViolation(format!(
+                        "Assignment {} is {}; positions are only tracked once it is accepted",
+                        assignment.id, assignment.status
+                    )));
+                }
+                assignment
+            }
+            None => match self.volunteer_repository.find_assignments(&volunteer.id, true).await?
+                .into_iter()
+                .find(under_way)
+            {
+                Some(assignment) => assignment,
+                None => return Ok(None),
+            },
+        };
+        self.policy.authorize(&request.actor, "volunteers:respond", &assignment_policy_attributes(&assignment, volunteer))?;
+        Ok(Some(assignment))
+    }
+
+    /// Where the volunteer was sent: the reported incident, else the disaster
+    async fn assignment_target(&self, assignment: &VolunteerAssignment) -> AppResult<Option<Coordinates>> {
+        if let Some(report_id) = assignment.report_id {
+            if let Some(report) = self.report_repository.find_by_id(&report_id).await? {
+                return Ok(Some(report.details.location));
+            }
+        }
+        match assignment.disaster_id {
+            Some(disaster_id) => Ok(self.disaster_repository.find_by_id(&disaster_id).await?.map(|d| d.location)),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns whether the arrival became the disaster's first response
+    async fn record_first_response(&self, disaster_id: &DisasterId, responder: UserId, arrived_at: DateTime<Utc>) -> bool {
+        let result = async {
+            let Some(mut disaster) = self.disaster_repository.find_by_id(disaster_id).await? else {
+                return Ok(false);
+            };
+            if !disaster.record_first_response(responder, arrived_at) {
+                return Ok(false);
+            }
+            self.disaster_repository.update(&disaster).await.map(|_| true)
+        };
+        match result.await {
+            Ok(recorded) => recorded,
+            Err(e) => {
+                tracing::warn!("Could not record first response on disaster {}: {}", disaster_id, e);
+                false
+            }
+        }
+    }
+
+    async fn detect_assignment_arrival(
+        &self,
+        assignment: &mut VolunteerAssignment,
+        fixes: &[LocationFix],
+        responder: UserId,
+    ) -> AppResult<Option<TrackedArrival>> {
+        if assignment.status != AssignmentStatus::Accepted {
+            return Ok(None);
+        }
+        let Some(target) = self.assignment_target(assignment).await? else {
+            return Ok(None);
+        };
+        let Some(arrival) = self.geofence.first_arrival(fixes, &target) else {
+            return Ok(None);
+        };
+
+        assignment.arrive_at(arrival.recorded_at)?;
+        self.volunteer_repository.update_assignment(assignment).await?;
+        let arrived_at = assignment.arrived_at.unwrap_or(arrival.recorded_at);
+        let first_response = match assignment.disaster_id {
+            Some(disaster_id) => self.record_first_response(&disaster_id, responder, arrived_at).await,
+            None => false,
+        };
+        tracing::info!("Assignment {} arrived at {} (tracked)", assignment.id, arrived_at);
+        Ok(Some(TrackedArrival {
+            assignment_id: Some(assignment.id),
+            team_id: None,
+            disaster_id: assignment.disaster_id,
+            arrived_at,
+            first_response,
+        }))
+    }
+
+    /// Move the team to the newest fix and check whether it reached its response's destination
+    async fn track_team(&self, mut team: ResponseTeam, fixes: &[LocationFix], responder: UserId) -> AppResult<Option<TrackedArrival>> {
+        if let Some(newest) = fixes.iter().max_by_key(|fix| fix.recorded_at) {
+            if newest.recorded_at > team.location_updated_at {
+                team.update_location(newest.position.clone());
+                team.location_updated_at = newest.recorded_at;
+                team = self.response_team_repository.update(&team).await?;
+            }
+        }
+
+        let Some(response_id) = team.current_response_id else {
+            return Ok(None);
+        };
+        let Some(response) = self.emergency_response_repository.find_by_id(&response_id).await? else {
+            return Ok(None);
+        };
+        if !response.is_active() || response.on_scene_at.is_some() {
+            return Ok(None);
+        }
+        let Some(arrival) = self.geofence.first_arrival(fixes, &response.destination) else {
+            return Ok(None);
+        };
+        let first_response = self.record_first_response(&response.disaster_id, responder, arrival.recorded_at).await;
+        tracing::info!("Team {} reached the destination of response {} at {}", team.id, response.id, arrival.recorded_at);
+        Ok(Some(TrackedArrival {
+            assignment_id: None,
+            team_id: Some(team.id),
+            disaster_id: Some(response.disaster_id),
+            arrived_at: arrival.recorded_at,
+            first_response,
+        }))
+    }
+}
+
+#[async_trait]
+impl ValidatedUseCase<RecordLocationFixesRequest, LocationBatchResult> for RecordLocationFixesUseCase {
+    async fn validate(&self, request: &RecordLocationFixesRequest) -> AppResult<()> {
+        if request.samples.is_empty() {
+            return Err(AppError::Validation("At least one fix is required".to_string()));
+        }
+        if request.samples.len() > self.max_batch_size {
+            return Err(AppError::Validation(format!("At most {} fixes can be sent at once", self.max_batch_size)));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl UseCase<RecordLocationFixesRequest, LocationBatchResult> for RecordLocationFixesUseCase {
+    async fn execute(&self, request: RecordLocationFixesRequest) -> AppResult<LocationBatchResult> {
+        let user_id = request.actor.user_id;
+        let volunteer = self.volunteer_repository.find_by_user(&user_id).await?;
+        let mut assignment = self.resolve_assignment(&request, volunteer.as_ref()).await?;
+        let team = match request.team_id {
+            Some(team_id) => {
+                self.policy.authorize(
+                    &request.actor,
+                    "emergency:update_status",
+                    &Attributes::new().with("team_id", team_id.to_string()),
+                )?;
+                Some(self.response_team_repository.find_by_id(&team_id).await?
+                    .ok_or_else(|| AppError::NotFound(format!("Response team {} not found", team_id)))?)
+            }
+            None => None,
+        };
+        if volunteer.is_none() && team.is_none() {
+            return Err(AppError::Forbidden(
+                "Only volunteers and response team members can share their position".to_string(),
+            ));
+        }
+
+        let source = FixSource {
+            user_id,
+            volunteer_id: volunteer.as_ref().map(|v| v.id),
+            team_id: team.as_ref().map(|t| t.id),
+            assignment_id: assignment.as_ref().map(|a| a.id),
+        };
+        let received_at = Utc::now();
+        let mut fixes = Vec::with_capacity(request.samples.len());
+        let mut rejected = Vec::new();
+        for (index, sample) in request.samples.into_iter().enumerate() {
+            match LocationFix::from_sample(&source, sample, received_at) {
+                Ok(fix) => fixes.push(fix),
+                Err(e) => rejected.push(RejectedFix { index, reason: e.to_string() }),
+            }
+        }
+
+        let accepted = if fixes.is_empty() { 0 } else { self.tracking_repository.record_fixes(&fixes).await? };
+        let mut arrivals = Vec::new();
+        if let Some(assignment) = assignment.as_mut() {
+            arrivals.extend(self.detect_assignment_arrival(assignment, &fixes, user_id).await?);
+        }
+        if let Some(team) = team {
+            arrivals.extend(self.track_team(team, &fixes, user_id).await?);
+        }
+
+        Ok(LocationBatchResult {
+            accepted,
+            duplicates: fixes.len() - accepted,
+            rejected,
+            assignment_id: source.assignment_id,
+            arrivals,
+        })
+    }
+}
@@ -9,7 +9,8 @@ use uuid::Uuid;
 use crate::application::use_cases::{UseCase, ValidatedUseCase};
 use crate::domain::entities::User;
 use crate::domain::value_objects::*;
-use crate::domain::ports::repositories::UserRepository;
+use crate::domain::ports::repositories::{LocationRepository, UserRepository};
+use crate::domain::services::address_geocoding;
 use crate::domain::ports::services::{AuthService, NotificationService};
 use crate::domain::events::{UserRegisteredEvent, UserActivatedEvent, EventPublisher};
 use crate::shared::{AppResult, AppError};
@@ -44,6 +45,7 @@ pub struct UserResponse {
 /// Use case for user registration
 pub struct RegisterUserUseCase {
     user_repository: Arc<dyn UserRepository>,
+    location_repository: Arc<dyn LocationRepository>,
     auth_service: Arc<dyn AuthService>,
     notification_service: Arc<dyn NotificationService>,
     event_publisher: Arc<dyn EventPublisher>,
@@ -52,12 +54,14 @@ pub struct RegisterUserUseCase {
 impl RegisterUserUseCase {
     pub fn new(
         user_repository: Arc<dyn UserRepository>,
+        location_repository: Arc<dyn LocationRepository>,
         auth_service: Arc<dyn AuthService>,
         notification_service: Arc<dyn NotificationService>,
         event_publisher: Arc<dyn EventPublisher>,
     ) -> Self {
         Self {
             user_repository,
+            location_repository,
             auth_service,
             notification_service,
             event_publisher,
@@ -155,6 +159,9 @@ impl UseCase<RegisterUserRequest, UserResponse> for RegisterUserUseCase {
 
         // Save user
         let saved_user = self.user_repository.save(&user).await?;
+        if let Some(address) = &request.address {
+            geocode_home_address(self.user_repository.as_ref(), self.location_repository.as_ref(), &saved_user, address).await;
+        }
 
         // Publish domain event
         let event = UserRegisteredEvent {
@@ -200,11 +207,12 @@ pub struct UpdateUserProfileRequest {
 /// Use case for updating user profile
 pub struct UpdateUserProfileUseCase {
     user_repository: Arc<dyn UserRepository>,
+    location_repository: Arc<dyn LocationRepository>,
 }
 
 impl UpdateUserProfileUseCase {
-    pub fn new(user_repository: Arc<dyn UserRepository>) -> Self {
-        Self { user_repository }
+    pub fn new(user_repository: Arc<dyn UserRepository>, location_repository: Arc<dyn LocationRepository>) -> Self {
+        Self { user_repository, location_repository }
     }
 }
 
@@ -258,7 +266,7 @@ impl UseCase<UpdateUserProfileRequest, UserResponse> for UpdateUserProfileUseCas
             user.phone_number = if phone.is_empty() { None } else { Some(PhoneNumber::new(phone)?) };
         }
 
-        if let Some(address) = request.address {
+        if let Some(address) = request.address.clone() {
             let line = format!(
                 "{}, {}, {}, {}{}",
                 address.street,
@@ -280,6 +288,9 @@ impl UseCase<UpdateUserProfileRequest, UserResponse> for UpdateUserProfileUseCas
 
         // Save updated user
         let saved_user = self.user_repository.update(&user).await?;
+        if let Some(address) = &request.address {
+            geocode_home_address(self.user_repository.as_ref(), self.location_repository.as_ref(), &saved_user, address).await;
+        }
 
         Ok(UserResponse {
             id: *saved_user.id(),
@@ -295,6 +306,33 @@ impl UseCase<UpdateUserProfileRequest, UserResponse> for UpdateUserProfileUseCas
     }
 }
 
+/// Place a user's home address so geofenced alerts reach them without a location fix.
+/// An address the gazetteer cannot place drops the old position rather than keeping a stale one.
+/// The user is already saved by then, so failures are logged instead of failing the request.
+async fn geocode_home_address(
+    users: &dyn UserRepository,
+    locations: &dyn LocationRepository,
+    user: &User,
+    address: &Address,
+) {
+    let result = async {
+        let Some(line) = user.address.as_deref() else {
+            return users.clear_address_geocode(user.id()).await;
+        };
+        for (query, level) in address_geocoding::lookups(address) {
+            let candidates = locations.search_by_name(&query).await?;
+            if let Some(hit) = address_geocoding::pick(address, level, &candidates) {
+                return users.save_address_geocode(user.id(), line, &hit.coordinates).await;
+            }
+        }
+        users.clear_address_geocode(user.id()).await
+    }.await;
+
+    if let Err(e) = result {
+        tracing::warn!("Failed to geocode the home address of user {}: {}", user.id(), e);
+    }
+}
+
 /// Request to activate/deactivate user
 #[derive(Debug, Clone)]
 pub struct ChangeUserStatusRequest {
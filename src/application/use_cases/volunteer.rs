@@ -0,0 +1,376 @@
+/// Volunteer use cases
+/// Volunteer sign-up, profile and availability changes, matching volunteers to a disaster's needs,
+/// and assignments from hand-out through acceptance, arrival and completion
+
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::use_cases::{disaster_policy_attributes, UseCase, ValidatedUseCase};
+use crate::domain::entities::report::ReportStatus;
+use crate::domain::entities::volunteer::{AssignmentStatus, Volunteer, VolunteerAssignment, VolunteerProfile};
+use crate::domain::ports::repositories::{DisasterRepository, ReportRepository, VolunteerRepository};
+use crate::domain::ports::services::NotificationService;
+use crate::domain::services::volunteer_matching::{
+    MatchingPolicy, SkillRequirement, VolunteerMatch, VolunteerMatchingService,
+};
+use crate::shared::policy::{Attributes, PolicyEngine, Subject};
+use crate::shared::{AppError, AppResult, DisasterId, ReportId, UserId, VolunteerId};
+
+/// Resource attributes policies can match for an assignment
+pub fn assignment_policy_attributes(assignment: &VolunteerAssignment, volunteer: &Volunteer) -> Attributes {
+    let mut attributes = Attributes::new()
+        .with("volunteer_user_id", volunteer.user_id.to_string())
+        .with("status", assignment.status.as_str());
+    if let Some(report_id) = assignment.report_id {
+        attributes.insert("report_id", report_id.to_string());
+    }
+    if let Some(disaster_id) = assignment.disaster_id {
+        attributes.insert("disaster_id", disaster_id.to_string());
+    }
+    attributes
+}
+
+/// Request to sign up as a volunteer
+#[derive(Debug, Clone)]
+pub struct RegisterVolunteerRequest {
+    pub user_id: UserId,
+    pub profile: VolunteerProfile,
+}
+
+/// Use case for users registering their volunteer profile
+pub struct RegisterVolunteerUseCase {
+    volunteer_repository: Arc<dyn VolunteerRepository>,
+}
+
+impl RegisterVolunteerUseCase {
+    pub fn new(volunteer_repository: Arc<dyn VolunteerRepository>) -> Self {
+        Self { volunteer_repository }
+    }
+}
+
+#[async_trait]
+impl UseCase<RegisterVolunteerRequest, Volunteer> for RegisterVolunteerUseCase {
+    async fn execute(&self, request: RegisterVolunteerRequest) -> AppResult<Volunteer> {
+        if self.volunteer_repository.find_by_user(&request.user_id).await?.is_some() {
+            return Err(AppError::Conflict(format!("User {} is already registered as a volunteer", request.user_id)));
+        }
+        let volunteer = Volunteer::register(request.user_id, request.profile)?;
+        let saved = self.volunteer_repository.create(&volunteer).await?;
+        tracing::info!("User {} registered as volunteer {}", saved.user_id, saved.id);
+        Ok(saved)
+    }
+}
+
+/// New availability, with an optional note such as the hours a volunteer can cover
+#[derive(Debug, Clone)]
+pub struct VolunteerAvailability {
+    pub is_available: bool,
+    pub notes: Option<String>,
+}
+
+/// Request to change a volunteer's own profile and/or availability
+#[derive(Debug, Clone)]
+pub struct UpdateVolunteerRequest {
+    pub user_id: UserId,
+    pub profile: Option<VolunteerProfile>,
+    pub availability: Option<VolunteerAvailability>,
+}
+
+/// Use case for volunteers keeping their skills, certifications and availability current
+pub struct UpdateVolunteerUseCase {
+    volunteer_repository: Arc<dyn VolunteerRepository>,
+}
+
+impl UpdateVolunteerUseCase {
+    pub fn new(volunteer_repository: Arc<dyn VolunteerRepository>) -> Self {
+        Self { volunteer_repository }
+    }
+}
+
+#[async_trait]
+impl ValidatedUseCase<UpdateVolunteerRequest, Volunteer> for UpdateVolunteerUseCase {
+    async fn validate(&self, request: &UpdateVolunteerRequest) -> AppResult<()> {
+        if request.profile.is_none() && request.availability.is_none() {
+            return Err(AppError::Validation("Nothing to update".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl UseCase<UpdateVolunteerRequest, Volunteer> for UpdateVolunteerUseCase {
+    async fn execute(&self, request: UpdateVolunteerRequest) -> AppResult<Volunteer> {
+        let mut volunteer = self.volunteer_repository.find_by_user(&request.user_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("User {} is not registered as a volunteer", request.user_id)))?;
+
+        if let Some(profile) = request.profile {
+            volunteer.update_profile(profile)?;
+        }
+        if let Some(availability) = request.availability {
+            volunteer.set_availability(availability.is_available, availability.notes)?;
+        }
+        self.volunteer_repository.update(&volunteer).await
+    }
+}
+
+/// Request to send a volunteer to the incident a report describes
+#[derive(Debug, Clone)]
+pub struct AssignVolunteerRequest {
+    pub volunteer_id: VolunteerId,
+    pub report_id: ReportId,
+    pub actor: Subject,
+    pub notes: Option<String>,
+}
+
+/// Use case for coordinators assigning volunteers (`volunteers:manage`)
+/// The volunteer is notified and then accepts or declines
+pub struct AssignVolunteerUseCase {
+    volunteer_repository: Arc<dyn VolunteerRepository>,
+    report_repository: Arc<dyn ReportRepository>,
+    disaster_repository: Arc<dyn DisasterRepository>,
+    notification_service: Arc<dyn NotificationService>,
+    policy: Arc<PolicyEngine>,
+}
+
+impl AssignVolunteerUseCase {
+    pub fn new(
+        volunteer_repository: Arc<dyn VolunteerRepository>,
+        report_repository: Arc<dyn ReportRepository>,
+        disaster_repository: Arc<dyn DisasterRepository>,
+        notification_service: Arc<dyn NotificationService>,
+        policy: Arc<PolicyEngine>,
+    ) -> Self {
+        Self { volunteer_repository, report_repository, disaster_repository, notification_service, policy }
+    }
+
+    /// Tell the volunteer about the disaster the report is linked to, or about the report itself
+    async fn notify(&self, volunteer: &Volunteer, disaster_id: Option<DisasterId>, report_title: &str) -> AppResult<()> {
+        if let Some(disaster_id) = disaster_id {
+            if let Some(disaster) = self.disaster_repository.find_by_id(&disaster_id).await? {
+                return self.notification_service.notify_volunteers(&disaster, &[volunteer.user_id]).await;
+            }
+        }
+        self.notification_service
+            .send_push_notification(
+                volunteer.user_id,
+                "New volunteer assignment",
+                &format!("You have been assigned to \"{}\". Open the app to accept or decline.", report_title),
+            )
+            .await
+    }
+}
+
+#[async_trait]
+impl UseCase<AssignVolunteerRequest, VolunteerAssignment> for AssignVolunteerUseCase {
+    async fn execute(&self, request: AssignVolunteerRequest) -> AppResult<VolunteerAssignment> {
+        let volunteer = self.volunteer_repository.find_by_id(&request.volunteer_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Volunteer {} not found", request.volunteer_id)))?;
+        let report = self.report_repository.find_by_id(&request.report_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Report {} not found", request.report_id)))?;
+        if matches!(report.status, ReportStatus::Rejected | ReportStatus::Resolved) {
+            return Err(AppError::BusinessRuleViolation(format!(
+                "Report {} is {} and needs no volunteers",
+                report.id, report.status
+            )));
+        }
+
+        let mut assignment = VolunteerAssignment::assign(&volunteer, report.id, request.actor.user_id, request.notes)?;
+        assignment.disaster_id = self.report_repository.find_linked_disasters(&report.id).await?.first().copied();
+        self.policy.authorize(&request.actor, "volunteers:manage", &assignment_policy_attributes(&assignment, &volunteer))?;
+        let saved = self.volunteer_repository.create_assignment(&assignment).await?;
+
+        // The assignment stands even if the volunteer cannot be reached right now
+        if let Err(e) = self.notify(&volunteer, saved.disaster_id, &report.details.title).await {
+            tracing::warn!("Could not notify volunteer {} about assignment {}: {}", volunteer.id, saved.id, e);
+        }
+        tracing::info!(
+            "Volunteer {} assigned to report {} by {}",
+            volunteer.id, report.id, request.actor.user_id
+        );
+        Ok(saved)
+    }
+}
+
+/// Most volunteers one matching run may propose or assign
+const MAX_MATCHES: usize = 50;
+
+/// Request to rank volunteers for a disaster and optionally assign the best of them
+#[derive(Debug, Clone)]
+pub struct MatchVolunteersRequest {
+    pub disaster_id: DisasterId,
+    /// Only match against these recorded resource needs; all of them when empty
+    pub resource_types: Vec<String>,
+    /// Volunteers to propose, or to assign with `auto_assign`
+    pub limit: usize,
+    pub auto_assign: bool,
+    pub actor: Subject,
+}
+
+/// Ranked volunteers and, when auto-assigning, the assignments that were made
+#[derive(Debug, Clone, Serialize)]
+pub struct VolunteerMatchResult {
+    pub disaster_id: DisasterId,
+    pub requirements: Vec<SkillRequirement>,
+    pub matches: Vec<VolunteerMatch>,
+    pub assignments: Vec<VolunteerAssignment>,
+}
+
+/// Use case for ranking available volunteers against a disaster's needs (`volunteers:read`)
+/// and assigning the best matches (`volunteers:manage`), who are then notified
+pub struct MatchVolunteersUseCase {
+    volunteer_repository: Arc<dyn VolunteerRepository>,
+    disaster_repository: Arc<dyn DisasterRepository>,
+    notification_service: Arc<dyn NotificationService>,
+    policy: Arc<PolicyEngine>,
+    matching: MatchingPolicy,
+}
+
+impl MatchVolunteersUseCase {
+    pub fn new(
+        volunteer_repository: Arc<dyn VolunteerRepository>,
+        disaster_repository: Arc<dyn DisasterRepository>,
+        notification_service: Arc<dyn NotificationService>,
+        policy: Arc<PolicyEngine>,
+        matching: MatchingPolicy,
+    ) -> Self {
+        Self { volunteer_repository, disaster_repository, notification_service, policy, matching }
+    }
+}
+
+#[async_trait]
+impl ValidatedUseCase<MatchVolunteersRequest, VolunteerMatchResult> for MatchVolunteersUseCase {
+    async fn validate(&self, request: &MatchVolunteersRequest) -> AppResult<()> {
+        if request.limit == 0 || request.limit > MAX_MATCHES {
+            return Err(AppError::Validation(format!("Limit must be between 1 and {}", MAX_MATCHES)));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl UseCase<MatchVolunteersRequest, VolunteerMatchResult> for MatchVolunteersUseCase {
+    async fn execute(&self, request: MatchVolunteersRequest) -> AppResult<VolunteerMatchResult> {
+        let disaster = self.disaster_repository.find_by_id(&request.disaster_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Disaster {} not found", request.disaster_id)))?;
+        if !disaster.is_active() {
+            return Err(AppError::BusinessRuleViolation(format!(
+                "Disaster {} is {} and needs no volunteers",
+                disaster.id, disaster.status.as_str()
+            )));
+        }
+        let action = if request.auto_assign { "volunteers:manage" } else { "volunteers:read" };
+        self.policy.authorize(&request.actor, action, &disaster_policy_attributes(&disaster))?;
+
+        let requirements = VolunteerMatchingService::requirements(&disaster, &request.resource_types);
+        let mut candidates = self.volunteer_repository
+            .find_match_candidates(&disaster.location, self.matching.max_distance_m, Utc::now() - self.matching.fatigue_window)
+            .await?;
+        // Volunteers already helping with this disaster are not proposed again
+        let engaged: HashSet<VolunteerId> = self.volunteer_repository.find_disaster_assignments(&disaster.id).await?
+            .into_iter()
+            .filter(|assignment| assignment.status.is_active())
+            .map(|assignment| assignment.volunteer_id)
+            .collect();
+        candidates.retain(|candidate| !engaged.contains(&candidate.volunteer.id));
+
+        let mut matches = VolunteerMatchingService::rank(&self.matching, &requirements, candidates);
+        matches.truncate(request.limit);
+
+        let mut assignments = Vec::new();
+        if request.auto_assign {
+            for matched in &matches {
+                let capabilities: Vec<&str> = matched.matched_capabilities.iter().map(|c| c.as_str()).collect();
+                let notes = (!capabilities.is_empty()).then(|| format!("Matched for {}", capabilities.join(", ")));
+                let assignment = VolunteerAssignment::assign_to_disaster(&matched.volunteer, disaster.id, request.actor.user_id, notes)?;
+                // One volunteer taken in the meantime should not stop the others being assigned
+                match self.volunteer_repository.create_assignment(&assignment).await {
+                    Ok(saved) => assignments.push(saved),
+                    Err(e) => tracing::warn!("Could not assign matched volunteer {} to disaster {}: {}", matched.volunteer.id, disaster.id, e),
+                }
+            }
+
+            let assigned_users: Vec<UserId> = matches.iter()
+                .filter(|m| assignments.iter().any(|a| a.volunteer_id == m.volunteer.id))
+                .map(|m| m.volunteer.user_id)
+                .collect();
+            if !assigned_users.is_empty() {
+                if let Err(e) = self.notification_service.notify_volunteers(&disaster, &assigned_users).await {
+                    tracing::warn!("Could not notify volunteers matched to disaster {}: {}", disaster.id, e);
+                }
+            }
+            tracing::info!(
+                "{} volunteers auto-assigned to disaster {} by {}",
+                assignments.len(), disaster.id, request.actor.user_id
+            );
+        }
+
+        Ok(VolunteerMatchResult { disaster_id: disaster.id, requirements, matches, assignments })
+    }
+}
+
+/// Request to move an assignment along
+#[derive(Debug, Clone)]
+pub struct ChangeAssignmentStatusRequest {
+    pub assignment_id: Uuid,
+    pub new_status: String,
+    pub actor: Subject,
+    pub notes: Option<String>,
+}
+
+/// Use case for assignment progress
+/// Only the volunteer accepts or declines (`volunteers:respond`); arrival and completion may also be
+/// recorded by a coordinator, who alone can cancel (`volunteers:manage`)
+pub struct ChangeAssignmentStatusUseCase {
+    volunteer_repository: Arc<dyn VolunteerRepository>,
+    policy: Arc<PolicyEngine>,
+}
+
+impl ChangeAssignmentStatusUseCase {
+    pub fn new(volunteer_repository: Arc<dyn VolunteerRepository>, policy: Arc<PolicyEngine>) -> Self {
+        Self { volunteer_repository, policy }
+    }
+}
+
+#[async_trait]
+impl ValidatedUseCase<ChangeAssignmentStatusRequest, VolunteerAssignment> for ChangeAssignmentStatusUseCase {
+    async fn validate(&self, request: &ChangeAssignmentStatusRequest) -> AppResult<()> {
+        match request.new_status.parse::<AssignmentStatus>()? {
+            AssignmentStatus::Assigned => Err(AppError::Validation("Assignments cannot be moved back to assigned".to_string())),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl UseCase<ChangeAssignmentStatusRequest, VolunteerAssignment> for ChangeAssignmentStatusUseCase {
+    async fn execute(&self, request: ChangeAssignmentStatusRequest) -> AppResult<VolunteerAssignment> {
+        let new_status: AssignmentStatus = request.new_status.parse()?;
+        let mut assignment = self.volunteer_repository.find_assignment(&request.assignment_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Assignment {} not found", request.assignment_id)))?;
+        let volunteer = self.volunteer_repository.find_by_id(&assignment.volunteer_id).await?
+            .ok_or_else(|| AppError::DataConsistency(format!("Volunteer of assignment {} not found", assignment.id)))?;
+
+        let attributes = assignment_policy_attributes(&assignment, &volunteer);
+        let is_own = volunteer.user_id == request.actor.user_id;
+        match new_status {
+            AssignmentStatus::Accepted | AssignmentStatus::Declined if !is_own => {
+                return Err(AppError::Forbidden("Only the assigned volunteer can accept or decline an assignment".to_string()));
+            }
+            AssignmentStatus::Cancelled => self.policy.authorize(&request.actor, "volunteers:manage", &attributes)?,
+            _ if is_own => self.policy.authorize(&request.actor, "volunteers:respond", &attributes)?,
+            _ => self.policy.authorize(&request.actor, "volunteers:manage", &attributes)?,
+        }
+
+        assignment.transition(new_status, request.notes)?;
+        let saved = self.volunteer_repository.update_assignment(&assignment).await?;
+        tracing::info!(
+            "Assignment {} of volunteer {} is now {} (by {})",
+            saved.id, volunteer.id, saved.status, request.actor.user_id
+        );
+        Ok(saved)
+    }
+}
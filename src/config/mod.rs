@@ -18,9 +18,18 @@ pub struct AppConfig {
     pub server: ServerConfig,
     pub database: DatabaseConfig,
     pub auth: AuthConfig,
+    pub paseto: PasetoKeyConfig,
     pub redis: RedisConfig,
     pub external_apis: ExternalApisConfig,
     pub logging: LoggingConfig,
+    pub routing: RoutingConfig,
+    pub notification_outbox: NotificationOutboxConfig,
+    pub media_storage: MediaStorageConfig,
+    pub report_triage: ReportTriageConfig,
+    pub volunteer_matching: VolunteerMatchingConfig,
+    pub realtime: RealtimeConfig,
+    pub location_tracking: LocationTrackingConfig,
+    pub rate_limiting: RateLimitingConfig,
     pub features: FeatureFlags,
 }
 
@@ -54,6 +63,37 @@ pub struct AuthConfig {
     pub password_hash_cost: u32,
     pub max_login_attempts: u32,
     pub lockout_duration_minutes: u32,
+    /// Issuer label shown in authenticator apps
+    pub mfa_issuer: String,
+    /// Secret used to encrypt stored TOTP secrets
+    pub mfa_encryption_key: String,
+    pub email_verification_ttl_minutes: i64,
+    pub password_reset_ttl_minutes: i64,
+    /// Verification or reset codes a single account may request per hour
+    pub verification_codes_per_hour: u32,
+    /// Externally reachable base URL used in emailed links
+    pub public_base_url: String,
+    /// TOML file with access policies; the built-in policies apply when unset
+    pub policy_file: Option<String>,
+}
+
+/// PASETO key material; each key may be given inline or through a `*_FILE` path
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasetoKeyConfig {
+    /// "public" for Ed25519-signed tokens partners can verify, or "local" for encrypted tokens
+    pub purpose: String,
+    /// `k4.secret.` PASERK of the current signing key
+    pub secret_key: Option<String>,
+    /// `k4.local.` PASERK of the current encryption key
+    pub local_key: Option<String>,
+    /// Retired `k4.public.`/`k4.local.` PASERKs still accepted while their tokens expire
+    pub previous_keys: Vec<String>,
+}
+
+impl PasetoKeyConfig {
+    pub fn uses_local_tokens(&self) -> bool {
+        self.purpose == "local"
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -83,13 +123,116 @@ pub struct LoggingConfig {
     pub max_files: u32,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoutingConfig {
+    /// OpenStreetMap `.osm.pbf` extract or GeoJSON road network; routing falls back to
+    /// straight-line estimates when unset
+    pub road_graph_path: Option<String>,
+    pub closure_refresh_seconds: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationOutboxConfig {
+    /// Delivery workers on this instance; 0 leaves delivery to other instances
+    pub workers: usize,
+    pub batch_size: u32,
+    pub poll_interval_ms: u64,
+    /// How long a claimed message stays leased before another worker may take it over
+    pub lease_seconds: u64,
+    pub max_attempts: u32,
+    pub base_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaStorageConfig {
+    /// `local` or `s3`
+    pub backend: String,
+    /// Directory the local backend writes uploads to
+    pub local_root: String,
+    /// Base URL uploads are served from; defaults to `{PUBLIC_BASE_URL}/media` for local
+    /// storage and to the bucket URL for S3
+    pub public_url: Option<String>,
+    pub max_upload_bytes: usize,
+    pub s3: S3Config,
+}
+
+/// How far apart reports may be and still corroborate each other or fall into one incident cluster
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportTriageConfig {
+    pub cluster_radius_m: f64,
+    pub cluster_window_minutes: i64,
+}
+
+/// Limits for ranking volunteers against a disaster's needs and assigning the best matches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolunteerMatchingConfig {
+    /// Volunteers assigned automatically when a disaster gains resource needs or a response starts
+    pub auto_assign_count: usize,
+    pub max_distance_km: f64,
+    /// Hours on shift in the last 24 hours after which a volunteer is left to rest
+    pub max_shift_hours: f64,
+}
+
+/// WebSocket/SSE gateway settings; instances share events over Redis pub/sub when Redis is configured
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RealtimeConfig {
+    pub redis_channel: String,
+    /// Interval between pings (WebSocket) or keep-alive comments (SSE)
+    pub heartbeat_seconds: u64,
+    /// How often an open stream re-checks that its session is still valid
+    pub session_check_seconds: u64,
+    /// Events buffered per connection before a slow client starts missing them
+    pub buffer_size: usize,
+}
+
+/// GPS fixes uploaded by responders' devices and arrival detection from them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocationTrackingConfig {
+    /// Distance from where a responder was sent within which they count as arrived
+    pub arrival_radius_m: f64,
+    /// Fixes less accurate than this never count as an arrival
+    pub max_arrival_accuracy_m: f64,
+    pub max_batch_size: usize,
+}
+
+/// Request rate limits; shared by all instances through Redis when it is configured
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateLimitingConfig {
+    pub enabled: bool,
+    /// Requests per minute per caller and route, unless the route or the caller's role has its own limit
+    pub requests_per_minute: u32,
+    /// Take the client address from `X-Forwarded-For`/`Forwarded`; only safe behind a proxy that sets them
+    pub trust_forwarded_for: bool,
+    /// Stop limiting emergency alert routes for signed-in callers while an emergency is declared
+    pub emergency_bypass: bool,
+    /// How often active disasters are re-checked for a declared emergency
+    pub emergency_check_seconds: u64,
+}
+
+/// Any S3-compatible object store (AWS S3, MinIO, R2, ...)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct S3Config {
+    pub endpoint: String,
+    pub region: String,
+    pub bucket: String,
+    pub access_key_id: Option<String>,
+    pub secret_access_key: Option<String>,
+    /// Address the bucket as `{endpoint}/{bucket}` rather than `{bucket}.{endpoint host}`
+    pub path_style: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeatureFlags {
+    /// Push the caller's own notifications over the realtime gateway
     pub enable_real_time_notifications: bool,
+    /// Push disaster updates (by disaster, province or area) over the realtime gateway
+    pub enable_real_time_tracking: bool,
     pub enable_weather_integration: bool,
     pub enable_analytics: bool,
     pub enable_ml_predictions: bool,
     pub enable_mobile_app_support: bool,
+    pub enable_volunteer_matching: bool,
 }
 
 impl AppConfig {
@@ -168,6 +311,38 @@ impl AppConfig {
                     .unwrap_or_else(|_| "15".to_string())
                     .parse()
                     .unwrap_or(15),
+                mfa_issuer: env::var("MFA_ISSUER")
+                    .unwrap_or_else(|_| "Terra Siaga".to_string()),
+                mfa_encryption_key: env::var("MFA_ENCRYPTION_KEY")
+                    .or_else(|_| env::var("JWT_SECRET"))
+                    .map_err(|_| AppError::Configuration("MFA_ENCRYPTION_KEY or JWT_SECRET is required".to_string()))?,
+                email_verification_ttl_minutes: env::var("EMAIL_VERIFICATION_TTL_MINUTES")
+                    .unwrap_or_else(|_| "1440".to_string())
+                    .parse()
+                    .unwrap_or(1440),
+                password_reset_ttl_minutes: env::var("PASSWORD_RESET_TTL_MINUTES")
+                    .unwrap_or_else(|_| "30".to_string())
+                    .parse()
+                    .unwrap_or(30),
+                verification_codes_per_hour: env::var("VERIFICATION_CODES_PER_HOUR")
+                    .unwrap_or_else(|_| "3".to_string())
+                    .parse()
+                    .unwrap_or(3),
+                public_base_url: env::var("PUBLIC_BASE_URL")
+                    .unwrap_or_else(|_| "http://localhost:8080".to_string())
+                    .trim_end_matches('/')
+                    .to_string(),
+                policy_file: env::var("POLICY_FILE").ok().filter(|path| !path.trim().is_empty()),
+            },
+            paseto: PasetoKeyConfig {
+                purpose: env::var("PASETO_PURPOSE")
+                    .unwrap_or_else(|_| "public".to_string())
+                    .to_lowercase(),
+                secret_key: env_or_file("PASETO_SECRET_KEY")?,
+                local_key: env_or_file("PASETO_LOCAL_KEY")?,
+                previous_keys: env_or_file("PASETO_PREVIOUS_KEYS")?
+                    .map(|keys| keys.split([',', '\n']).map(|k| k.trim().to_string()).filter(|k| !k.is_empty()).collect())
+                    .unwrap_or_default(),
             },
             redis: RedisConfig {
                 url: env::var("REDIS_URL")
@@ -214,11 +389,153 @@ impl AppConfig {
                     .parse()
                     .unwrap_or(10),
             },
+            routing: RoutingConfig {
+                road_graph_path: env::var("ROAD_GRAPH_PATH").ok().filter(|p| !p.trim().is_empty()),
+                closure_refresh_seconds: env::var("ROUTING_CLOSURE_REFRESH_SECONDS")
+                    .unwrap_or_else(|_| "60".to_string())
+                    .parse()
+                    .unwrap_or(60),
+            },
+            notification_outbox: NotificationOutboxConfig {
+                workers: env::var("NOTIFICATION_OUTBOX_WORKERS")
+                    .unwrap_or_else(|_| "4".to_string())
+                    .parse()
+                    .unwrap_or(4),
+                batch_size: env::var("NOTIFICATION_OUTBOX_BATCH_SIZE")
+                    .unwrap_or_else(|_| "50".to_string())
+                    .parse()
+                    .unwrap_or(50),
+                poll_interval_ms: env::var("NOTIFICATION_OUTBOX_POLL_INTERVAL_MS")
+                    .unwrap_or_else(|_| "1000".to_string())
+                    .parse()
+                    .unwrap_or(1000),
+                lease_seconds: env::var("NOTIFICATION_OUTBOX_LEASE_SECONDS")
+                    .unwrap_or_else(|_| "60".to_string())
+                    .parse()
+                    .unwrap_or(60),
+                max_attempts: env::var("NOTIFICATION_OUTBOX_MAX_ATTEMPTS")
+                    .unwrap_or_else(|_| "5".to_string())
+                    .parse()
+                    .unwrap_or(5),
+                base_backoff_ms: env::var("NOTIFICATION_OUTBOX_BASE_BACKOFF_MS")
+                    .unwrap_or_else(|_| "2000".to_string())
+                    .parse()
+                    .unwrap_or(2000),
+                max_backoff_ms: env::var("NOTIFICATION_OUTBOX_MAX_BACKOFF_MS")
+                    .unwrap_or_else(|_| "600000".to_string())
+                    .parse()
+                    .unwrap_or(600_000),
+            },
+            media_storage: MediaStorageConfig {
+                backend: env::var("MEDIA_STORAGE")
+                    .unwrap_or_else(|_| "local".to_string())
+                    .to_lowercase(),
+                local_root: env::var("MEDIA_LOCAL_ROOT")
+                    .unwrap_or_else(|_| "./uploads".to_string()),
+                public_url: env::var("MEDIA_PUBLIC_URL").ok().filter(|url| !url.trim().is_empty()),
+                max_upload_bytes: env::var("MEDIA_MAX_UPLOAD_BYTES")
+                    .unwrap_or_else(|_| "26214400".to_string())
+                    .parse()
+                    .unwrap_or(25 * 1024 * 1024),
+                s3: S3Config {
+                    endpoint: env::var("S3_ENDPOINT")
+                        .unwrap_or_else(|_| "https://s3.amazonaws.com".to_string()),
+                    region: env::var("S3_REGION")
+                        .unwrap_or_else(|_| "us-east-1".to_string()),
+                    bucket: env::var("S3_BUCKET").unwrap_or_default(),
+                    access_key_id: env_or_file("S3_ACCESS_KEY_ID")?,
+                    secret_access_key: env_or_file("S3_SECRET_ACCESS_KEY")?,
+                    path_style: env::var("S3_PATH_STYLE")
+                        .unwrap_or_else(|_| "true".to_string())
+                        .parse()
+                        .unwrap_or(true),
+                },
+            },
+            report_triage: ReportTriageConfig {
+                cluster_radius_m: env::var("REPORT_CLUSTER_RADIUS_M")
+                    .unwrap_or_else(|_| "2000".to_string())
+                    .parse()
+                    .unwrap_or(2000.0),
+                cluster_window_minutes: env::var("REPORT_CLUSTER_WINDOW_MINUTES")
+                    .unwrap_or_else(|_| "360".to_string())
+                    .parse()
+                    .unwrap_or(360),
+            },
+            volunteer_matching: VolunteerMatchingConfig {
+                auto_assign_count: env::var("VOLUNTEER_AUTO_ASSIGN_COUNT")
+                    .unwrap_or_else(|_| "5".to_string())
+                    .parse()
+                    .unwrap_or(5),
+                max_distance_km: env::var("VOLUNTEER_MATCH_MAX_DISTANCE_KM")
+                    .unwrap_or_else(|_| "50".to_string())
+                    .parse()
+                    .unwrap_or(50.0),
+                max_shift_hours: env::var("VOLUNTEER_MAX_SHIFT_HOURS")
+                    .unwrap_or_else(|_| "12".to_string())
+                    .parse()
+                    .unwrap_or(12.0),
+            },
+            realtime: RealtimeConfig {
+                redis_channel: env::var("REALTIME_REDIS_CHANNEL")
+                    .unwrap_or_else(|_| "terra_siaga:realtime".to_string()),
+                heartbeat_seconds: env::var("REALTIME_HEARTBEAT_SECONDS")
+                    .unwrap_or_else(|_| "30".to_string())
+                    .parse()
+                    .unwrap_or(30),
+                session_check_seconds: env::var("REALTIME_SESSION_CHECK_SECONDS")
+                    .unwrap_or_else(|_| "300".to_string())
+                    .parse()
+                    .unwrap_or(300),
+                buffer_size: env::var("REALTIME_BUFFER_SIZE")
+                    .unwrap_or_else(|_| "256".to_string())
+                    .parse()
+                    .unwrap_or(256),
+            },
+            location_tracking: LocationTrackingConfig {
+                arrival_radius_m: env::var("TRACKING_ARRIVAL_RADIUS_M")
+                    .unwrap_or_else(|_| "150".to_string())
+                    .parse()
+                    .unwrap_or(150.0),
+                max_arrival_accuracy_m: env::var("TRACKING_MAX_ARRIVAL_ACCURACY_M")
+                    .unwrap_or_else(|_| "100".to_string())
+                    .parse()
+                    .unwrap_or(100.0),
+                max_batch_size: env::var("TRACKING_MAX_BATCH_SIZE")
+                    .unwrap_or_else(|_| "500".to_string())
+                    .parse()
+                    .unwrap_or(500),
+            },
+            rate_limiting: RateLimitingConfig {
+                enabled: env::var("RATE_LIMIT_ENABLED")
+                    .unwrap_or_else(|_| "true".to_string())
+                    .parse()
+                    .unwrap_or(true),
+                requests_per_minute: env::var("RATE_LIMIT_REQUESTS_PER_MINUTE")
+                    .unwrap_or_else(|_| "60".to_string())
+                    .parse()
+                    .unwrap_or(60),
+                trust_forwarded_for: env::var("RATE_LIMIT_TRUST_FORWARDED_FOR")
+                    .unwrap_or_else(|_| "false".to_string())
+                    .parse()
+                    .unwrap_or(false),
+                emergency_bypass: env::var("RATE_LIMIT_EMERGENCY_BYPASS")
+                    .unwrap_or_else(|_| "true".to_string())
+                    .parse()
+                    .unwrap_or(true),
+                emergency_check_seconds: env::var("RATE_LIMIT_EMERGENCY_CHECK_SECONDS")
+                    .unwrap_or_else(|_| "30".to_string())
+                    .parse()
+                    .unwrap_or(30),
+            },
             features: FeatureFlags {
                 enable_real_time_notifications: env::var("ENABLE_REAL_TIME_NOTIFICATIONS")
                     .unwrap_or_else(|_| "true".to_string())
                     .parse()
                     .unwrap_or(true),
+                enable_real_time_tracking: env::var("ENABLE_REAL_TIME_TRACKING")
+                    .unwrap_or_else(|_| "true".to_string())
+                    .parse()
+                    .unwrap_or(true),
                 enable_weather_integration: env::var("ENABLE_WEATHER_INTEGRATION")
                     .unwrap_or_else(|_| "true".to_string())
                     .parse()
@@ -235,6 +552,10 @@ impl AppConfig {
                     .unwrap_or_else(|_| "true".to_string())
                     .parse()
                     .unwrap_or(true),
+                enable_volunteer_matching: env::var("ENABLE_VOLUNTEER_MATCHING")
+                    .unwrap_or_else(|_| "false".to_string())
+                    .parse()
+                    .unwrap_or(false),
             },
         };
 
@@ -292,11 +613,56 @@ impl AppConfig {
             return Err(AppError::Configuration("Max login attempts must be greater than 0".to_string()));
         }
 
+        if !["public", "local"].contains(&self.paseto.purpose.as_str()) {
+            return Err(AppError::Configuration("PASETO_PURPOSE must be either public or local".to_string()));
+        }
+
         // Validate Redis config
         if !self.redis.url.is_empty() && self.redis.max_connections == 0 {
             return Err(AppError::Configuration("Redis max_connections must be greater than 0 when Redis is enabled".to_string()));
         }
 
+        // Validate volunteer matching limits
+        if self.volunteer_matching.auto_assign_count > 50 {
+            return Err(AppError::Configuration("VOLUNTEER_AUTO_ASSIGN_COUNT must be at most 50".to_string()));
+        }
+        if self.volunteer_matching.max_distance_km <= 0.0 || self.volunteer_matching.max_shift_hours <= 0.0 {
+            return Err(AppError::Configuration("Volunteer matching distance and shift limits must be positive".to_string()));
+        }
+
+        // Validate realtime gateway settings
+        if self.realtime.heartbeat_seconds == 0 || self.realtime.session_check_seconds == 0 || self.realtime.buffer_size == 0 {
+            return Err(AppError::Configuration("Realtime heartbeat, session check and buffer size must be greater than 0".to_string()));
+        }
+
+        // Validate location tracking settings
+        let tracking = &self.location_tracking;
+        if tracking.arrival_radius_m <= 0.0 || tracking.max_arrival_accuracy_m <= 0.0 {
+            return Err(AppError::Configuration("Tracking arrival radius and accuracy must be positive".to_string()));
+        }
+        if !(1..=5000).contains(&tracking.max_batch_size) {
+            return Err(AppError::Configuration("TRACKING_MAX_BATCH_SIZE must be between 1 and 5000".to_string()));
+        }
+
+        // Validate rate limiting settings
+        if self.rate_limiting.enabled && (self.rate_limiting.requests_per_minute == 0 || self.rate_limiting.emergency_check_seconds == 0) {
+            return Err(AppError::Configuration("Rate limit requests per minute and emergency check interval must be greater than 0".to_string()));
+        }
+
+        // Validate media storage config
+        match self.media_storage.backend.as_str() {
+            "local" => {}
+            "s3" => {
+                let s3 = &self.media_storage.s3;
+                if s3.bucket.is_empty() || s3.access_key_id.is_none() || s3.secret_access_key.is_none() {
+                    return Err(AppError::Configuration(
+                        "S3 media storage requires S3_BUCKET, S3_ACCESS_KEY_ID and S3_SECRET_ACCESS_KEY".to_string()
+                    ));
+                }
+            }
+            _ => return Err(AppError::Configuration("MEDIA_STORAGE must be either local or s3".to_string())),
+        }
+
         // Validate logging config
         if !["trace", "debug", "info", "warn", "error"].contains(&self.logging.level.as_str()) {
             return Err(AppError::Configuration("Invalid log level. Must be one of: trace, debug, info, warn, error".to_string()));
@@ -337,3 +703,16 @@ impl AppConfig {
         self.environment() == "production"
     }
 }
+
+/// Read `NAME`, or the contents of the file named by `NAME_FILE` (e.g. a mounted secret)
+fn env_or_file(name: &str) -> AppResult<Option<String>> {
+    if let Some(value) = env::var(name).ok().filter(|v| !v.trim().is_empty()) {
+        return Ok(Some(value.trim().to_string()));
+    }
+    match env::var(format!("{}_FILE", name)) {
+        Ok(path) if !path.trim().is_empty() => std::fs::read_to_string(path.trim())
+            .map(|contents| Some(contents.trim().to_string()))
+            .map_err(|e| AppError::Configuration(format!("Failed to read {}_FILE {}: {}", name, path, e))),
+        _ => Ok(None),
+    }
+}
@@ -0,0 +1,327 @@
+/// Analytics read model
+/// One fact row per disaster, kept current from the disaster event stream, plus the filters
+/// and aggregates the dashboard endpoints are answered with. Durations are in minutes.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use crate::domain::entities::disaster::{Disaster, DisasterSeverity, DisasterStatus, DisasterType};
+use crate::domain::value_objects::Coordinates;
+use crate::shared::events::DisasterEvent;
+use crate::shared::{AppError, AppResult, DisasterId};
+
+/// Width of the periods disasters are counted in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Granularity {
+    Daily,
+    Weekly,
+    #[default]
+    Monthly,
+    Yearly,
+}
+
+impl Granularity {
+    pub fn parse(raw: &str) -> AppResult<Self> {
+        match raw.trim().to_lowercase().as_str() {
+            "daily" | "day" => Ok(Granularity::Daily),
+            "weekly" | "week" => Ok(Granularity::Weekly),
+            "monthly" | "month" => Ok(Granularity::Monthly),
+            "yearly" | "year" => Ok(Granularity::Yearly),
+            other => Err(AppError::Validation(format!(
+                "Unknown granularity '{}', expected daily, weekly, monthly or yearly",
+                other
+            ))),
+        }
+    }
+
+    /// Field name for Postgres `date_trunc`; weeks start on Monday
+    pub fn trunc_unit(&self) -> &'static str {
+        match self {
+            Granularity::Daily => "day",
+            Granularity::Weekly => "week",
+            Granularity::Monthly => "month",
+            Granularity::Yearly => "year",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LocationFilter {
+    /// Province or city name, case-insensitive
+    Area(String),
+    Near { center: Coordinates, radius_km: f64 },
+}
+
+/// Which disasters an analytics query covers, by the time they were reported
+#[derive(Debug, Clone, Default)]
+pub struct AnalyticsFilter {
+    pub from: Option<DateTime<Utc>>,
+    /// Exclusive
+    pub to: Option<DateTime<Utc>>,
+    /// Type name as given by `DisasterType::name`
+    pub disaster_type: Option<String>,
+    pub location: Option<LocationFilter>,
+    pub granularity: Granularity,
+}
+
+/// Read model row for one disaster
+#[derive(Debug, Clone, Serialize)]
+pub struct DisasterFact {
+    pub disaster_id: DisasterId,
+    pub disaster_type: DisasterType,
+    pub severity: DisasterSeverity,
+    pub status: DisasterStatus,
+    pub position: Coordinates,
+    pub province: Option<String>,
+    pub city: Option<String>,
+    pub reported_at: DateTime<Utc>,
+    pub verified_at: Option<DateTime<Utc>>,
+    pub first_response_at: Option<DateTime<Utc>>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+impl DisasterFact {
+    /// Fact for a disaster's current state, used to seed disasters with no history yet
+    pub fn from_disaster(disaster: &Disaster) -> Self {
+        Self {
+            disaster_id: disaster.id,
+            disaster_type: disaster.disaster_type.clone(),
+            severity: disaster.severity.clone(),
+            status: disaster.status.clone(),
+            position: disaster.location.clone(),
+            province: disaster.province.clone(),
+            city: None,
+            reported_at: disaster.timeline.reported_at,
+            verified_at: disaster.timeline.verified_at,
+            first_response_at: disaster.timeline.first_response_at,
+            resolved_at: disaster.timeline.resolved_at,
+            closed_at: disaster.timeline.closed_at,
+        }
+    }
+
+    /// Fact for a freshly opened disaster; `None` for any other event
+    pub fn opened(event: &DisasterEvent) -> Option<Self> {
+        let DisasterEvent::DisasterOpened { disaster_id, disaster_type, severity, location, reported_at, .. } = event else {
+            return None;
+        };
+        Some(Self {
+            disaster_id: *disaster_id,
+            disaster_type: disaster_type.clone(),
+            severity: severity.clone(),
+            status: DisasterStatus::Reported,
+            position: location.clone(),
+            province: None,
+            city: None,
+            reported_at: *reported_at,
+            verified_at: None,
+            first_response_at: None,
+            resolved_at: None,
+            closed_at: None,
+        })
+    }
+
+    /// Whether the event changes anything the fact holds
+    pub fn tracks(event: &DisasterEvent) -> bool {
+        matches!(
+            event,
+            DisasterEvent::DisasterOpened { .. }
+                | DisasterEvent::DisasterStatusUpdated { .. }
+                | DisasterEvent::DisasterSeverityEscalated { .. }
+                | DisasterEvent::DisasterFirstResponderArrived { .. }
+        )
+    }
+
+    /// Fold a change into the fact, following the timeline rules of `Disaster::apply`
+    pub fn apply(&mut self, event: &DisasterEvent) {
+        match event {
+            DisasterEvent::DisasterOpened { .. } => {
+                if let Some(opened) = Self::opened(event) {
+                    *self = Self { province: self.province.take(), city: self.city.take(), ..opened };
+                }
+            }
+            DisasterEvent::DisasterStatusUpdated { new_status, updated_at, .. } => {
+                match new_status {
+                    DisasterStatus::Verified => {
+                        self.verified_at.get_or_insert(*updated_at);
+                    }
+                    DisasterStatus::Responded => {
+                        self.first_response_at.get_or_insert(*updated_at);
+                    }
+                    DisasterStatus::Resolved => self.resolved_at = Some(*updated_at),
+                    DisasterStatus::Closed => self.closed_at = Some(*updated_at),
+                    DisasterStatus::Reported => {}
+                }
+                self.status = new_status.clone();
+            }
+            DisasterEvent::DisasterSeverityEscalated { new_severity, .. } => {
+                self.severity = new_severity.clone();
+            }
+            DisasterEvent::DisasterFirstResponderArrived { arrived_at, .. }
+                if self.first_response_at.is_none_or(|first| *arrived_at < first) =>
+            {
+                self.first_response_at = Some(*arrived_at);
+            }
+            _ => {}
+        }
+    }
+
+    /// Minutes from report to verification
+    pub fn minutes_to_verify(&self) -> Option<f64> {
+        self.verified_at.map(|t| minutes_between(self.reported_at, t))
+    }
+
+    /// Minutes from report to the first responder on scene
+    pub fn minutes_to_first_response(&self) -> Option<f64> {
+        self.first_response_at.map(|t| minutes_between(self.reported_at, t))
+    }
+}
+
+fn minutes_between(from: DateTime<Utc>, to: DateTime<Utc>) -> f64 {
+    (to - from).num_seconds() as f64 / 60.0
+}
+
+/// Disaster counts for the dashboard; `active` covers everything not yet resolved or closed
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DisasterCounts {
+    pub total: i64,
+    pub active: i64,
+    pub verified: i64,
+    pub resolved: i64,
+    pub by_status: BTreeMap<String, i64>,
+    pub by_type: BTreeMap<String, i64>,
+    pub by_severity: BTreeMap<String, i64>,
+}
+
+/// Spread of a duration over the disasters that reached the milestone
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DurationStats {
+    pub samples: i64,
+    pub average_minutes: Option<f64>,
+    pub p50_minutes: Option<f64>,
+    pub p90_minutes: Option<f64>,
+    pub p95_minutes: Option<f64>,
+    pub fastest_minutes: Option<f64>,
+    pub slowest_minutes: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DashboardSummary {
+    pub counts: DisasterCounts,
+    pub time_to_verify: DurationStats,
+    pub time_to_first_response: DurationStats,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PeriodCount {
+    /// Start of the period in UTC
+    pub period: DateTime<Utc>,
+    pub total: i64,
+    pub by_type: BTreeMap<String, i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DisasterTrends {
+    pub granularity: Granularity,
+    pub by_period: Vec<PeriodCount>,
+    pub by_type: BTreeMap<String, i64>,
+    pub by_severity: BTreeMap<String, i64>,
+    pub by_province: BTreeMap<String, i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResponseTimeAnalytics {
+    pub time_to_verify: DurationStats,
+    pub time_to_first_response: DurationStats,
+    /// Time to first response per disaster type
+    pub by_disaster_type: BTreeMap<String, DurationStats>,
+    /// Time to first response per severity
+    pub by_severity: BTreeMap<String, DurationStats>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AreaCount {
+    pub name: String,
+    pub total: i64,
+    pub active: i64,
+}
+
+/// Grid cell of roughly 11 km holding several disasters
+#[derive(Debug, Clone, Serialize)]
+pub struct Hotspot {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub total: i64,
+    pub active: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GeographicAnalytics {
+    pub by_province: Vec<AreaCount>,
+    pub by_city: Vec<AreaCount>,
+    pub hotspots: Vec<Hotspot>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use crate::shared::UserId;
+
+    #[test]
+    fn facts_follow_the_disaster_timeline() {
+        let disaster_id = DisasterId::new();
+        let by = UserId::new();
+        let reported_at = Utc::now() - Duration::hours(3);
+        let status = |new_status: DisasterStatus, minutes: i64| DisasterEvent::DisasterStatusUpdated {
+            disaster_id,
+            old_status: DisasterStatus::Reported,
+            new_status,
+            updated_by: by,
+            updated_at: reported_at + Duration::minutes(minutes),
+        };
+
+        let mut fact = DisasterFact::opened(&DisasterEvent::DisasterOpened {
+            disaster_id,
+            title: "Banjir".to_string(),
+            description: "Flooding along the river".to_string(),
+            disaster_type: DisasterType::Flood,
+            severity: DisasterSeverity::Major,
+            location: Coordinates::new(-6.2, 106.8).unwrap(),
+            reporter_id: by,
+            reported_at,
+        }).unwrap();
+        fact.province = Some("DKI Jakarta".to_string());
+
+        fact.apply(&status(DisasterStatus::Verified, 12));
+        fact.apply(&status(DisasterStatus::Responded, 45));
+        fact.apply(&DisasterEvent::DisasterFirstResponderArrived {
+            disaster_id,
+            responder_id: by,
+            arrived_at: reported_at + Duration::minutes(30),
+        });
+        fact.apply(&DisasterEvent::DisasterSeverityEscalated {
+            disaster_id,
+            old_severity: DisasterSeverity::Major,
+            new_severity: DisasterSeverity::Critical,
+            escalated_by: by,
+            escalated_at: reported_at + Duration::minutes(50),
+        });
+        fact.apply(&status(DisasterStatus::Resolved, 120));
+
+        assert_eq!(fact.status, DisasterStatus::Resolved);
+        assert_eq!(fact.severity, DisasterSeverity::Critical);
+        assert_eq!(fact.minutes_to_verify(), Some(12.0));
+        assert_eq!(fact.minutes_to_first_response(), Some(30.0));
+        assert_eq!(fact.resolved_at, Some(reported_at + Duration::minutes(120)));
+        assert_eq!(fact.province.as_deref(), Some("DKI Jakarta"));
+    }
+
+    #[test]
+    fn granularity_names_are_parsed() {
+        assert_eq!(Granularity::parse("Weekly").unwrap(), Granularity::Weekly);
+        assert_eq!(Granularity::parse("year").unwrap().trunc_unit(), "year");
+        assert!(Granularity::parse("hourly").is_err());
+    }
+}
@@ -5,8 +5,9 @@ use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 use crate::shared::{DisasterId, UserId, LocationId, AppResult, AppError, AuditFields, Priority};
-use crate::domain::value_objects::Coordinates;
+use crate::domain::value_objects::{Coordinates, GeoArea};
 use crate::shared::types::SeverityLevel;
+use crate::shared::events::DisasterEvent;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Disaster {
@@ -18,6 +19,9 @@ pub struct Disaster {
     pub status: DisasterStatus,
     pub priority: Priority,
     pub location: Coordinates,
+    /// Province of the primary location, resolved by the read model; not part of the event history
+    #[serde(default)]
+    pub province: Option<String>,
     pub reporter_id: UserId,
     pub assigned_responders: Vec<UserId>,
     pub affected_population: Option<u32>,
@@ -28,6 +32,13 @@ pub struct Disaster {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub version: i64,
+    /// Version last read from or written to storage (0 = never persisted),
+    /// used by repositories for optimistic concurrency checks
+    #[serde(skip)]
+    pub persisted_version: i64,
+    /// Change events recorded since the aggregate was loaded or last saved
+    #[serde(skip)]
+    pub uncommitted_events: Vec<DisasterEvent>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -45,6 +56,42 @@ pub enum DisasterType {
     Other(String),
 }
 
+impl DisasterType {
+    /// Parse a type name such as `flood` or `volcanic_eruption`; unknown names become `Other`
+    pub fn parse(raw: &str) -> Self {
+        match raw.trim().to_lowercase().as_str() {
+            "earthquake" => DisasterType::Earthquake,
+            "flood" => DisasterType::Flood,
+            "tsunami" => DisasterType::Tsunami,
+            "landslide" => DisasterType::Landslide,
+            "volcanic_eruption" | "volcaniceruption" | "volcano" => DisasterType::VolcanicEruption,
+            "fire" => DisasterType::Fire,
+            "storm" => DisasterType::Storm,
+            "drought" => DisasterType::Drought,
+            "epidemic" => DisasterType::Epidemic,
+            "technological" | "technological_disaster" => DisasterType::TechnologicalDisaster,
+            other => DisasterType::Other(other.to_string()),
+        }
+    }
+
+    /// Type name as accepted by `parse`; other types keep their own label
+    pub fn name(&self) -> &str {
+        match self {
+            DisasterType::Earthquake => "earthquake",
+            DisasterType::Flood => "flood",
+            DisasterType::Tsunami => "tsunami",
+            DisasterType::Landslide => "landslide",
+            DisasterType::VolcanicEruption => "volcanic_eruption",
+            DisasterType::Fire => "fire",
+            DisasterType::Storm => "storm",
+            DisasterType::Drought => "drought",
+            DisasterType::Epidemic => "epidemic",
+            DisasterType::TechnologicalDisaster => "technological_disaster",
+            DisasterType::Other(label) => label,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, PartialOrd)]
 pub enum DisasterSeverity {
     Minor = 1,
@@ -55,6 +102,31 @@ pub enum DisasterSeverity {
     Catastrophic,
 }
 
+impl DisasterSeverity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DisasterSeverity::Minor => "minor",
+            DisasterSeverity::Moderate => "moderate",
+            DisasterSeverity::Major => "major",
+            DisasterSeverity::Severe => "severe",
+            DisasterSeverity::Critical => "critical",
+            DisasterSeverity::Catastrophic => "catastrophic",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "minor" => Some(DisasterSeverity::Minor),
+            "moderate" => Some(DisasterSeverity::Moderate),
+            "major" => Some(DisasterSeverity::Major),
+            "severe" => Some(DisasterSeverity::Severe),
+            "critical" => Some(DisasterSeverity::Critical),
+            "catastrophic" => Some(DisasterSeverity::Catastrophic),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum DisasterStatus {
     Reported,
@@ -64,6 +136,29 @@ pub enum DisasterStatus {
     Closed,
 }
 
+impl DisasterStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DisasterStatus::Reported => "reported",
+            DisasterStatus::Verified => "verified",
+            DisasterStatus::Responded => "responded",
+            DisasterStatus::Resolved => "resolved",
+            DisasterStatus::Closed => "closed",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "reported" => Some(DisasterStatus::Reported),
+            "verified" => Some(DisasterStatus::Verified),
+            "responded" => Some(DisasterStatus::Responded),
+            "resolved" => Some(DisasterStatus::Resolved),
+            "closed" => Some(DisasterStatus::Closed),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EstimatedDamage {
     pub economic_loss: Option<f64>,
@@ -101,6 +196,16 @@ pub struct VerificationInfo {
     pub confidence_score: Option<f32>,
 }
 
+/// Mapped hazard, affected or safe area of a disaster
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisasterZone {
+    pub id: Uuid,
+    pub disaster_id: Option<DisasterId>,
+    pub zone_type: Option<String>,
+    pub description: Option<String>,
+    pub area: GeoArea,
+}
+
 impl Disaster {
     /// Create a new disaster report
     pub fn new(
@@ -110,24 +215,51 @@ impl Disaster {
         severity: DisasterSeverity,
         location: Coordinates,
         reporter_id: UserId,
-    ) -> Self {
-        let now = Utc::now();
-        Self {
-            id: DisasterId::new(),
+    ) -> AppResult<Self> {
+        let opened = DisasterEvent::DisasterOpened {
+            disaster_id: DisasterId::new(),
             title,
             description,
             disaster_type,
-            severity: severity.clone(),
-            status: DisasterStatus::Reported,
-            priority: Self::calculate_priority(&severity),
+            severity,
             location,
             reporter_id,
+            reported_at: Utc::now(),
+        };
+
+        let mut disaster = Self::from_opened(&opened)?;
+        disaster.record(opened);
+        Ok(disaster)
+    }
+
+    /// Initial state (version 0) described by a `DisasterOpened` event
+    fn from_opened(event: &DisasterEvent) -> AppResult<Self> {
+        let DisasterEvent::DisasterOpened {
+            disaster_id, title, description, disaster_type, severity, location, reporter_id, reported_at,
+        } = event else {
+            return Err(AppError::DataConsistency(format!(
+                "Disaster history must start with DisasterOpened, found {}",
+                crate::shared::events::DomainEvent::event_type(event)
+            )));
+        };
+
+        Ok(Self {
+            id: *disaster_id,
+            title: title.clone(),
+            description: description.clone(),
+            disaster_type: disaster_type.clone(),
+            severity: severity.clone(),
+            status: DisasterStatus::Reported,
+            priority: Self::calculate_priority(severity),
+            location: location.clone(),
+            province: None,
+            reporter_id: *reporter_id,
             assigned_responders: Vec::new(),
             affected_population: None,
             estimated_damage: None,
             resources_needed: Vec::new(),
             timeline: DisasterTimeline {
-                reported_at: now,
+                reported_at: *reported_at,
                 occurred_at: None,
                 verified_at: None,
                 first_response_at: None,
@@ -141,10 +273,109 @@ impl Disaster {
                 verification_method: None,
                 confidence_score: None,
             },
-            created_at: now,
-            updated_at: now,
-            version: 1,
+            created_at: *reported_at,
+            updated_at: *reported_at,
+            version: 0,
+            persisted_version: 0,
+            uncommitted_events: Vec::new(),
+        })
+    }
+
+    /// Rebuild a disaster by replaying its full history
+    pub fn from_events(events: &[DisasterEvent]) -> AppResult<Self> {
+        let (first, rest) = events.split_first()
+            .ok_or_else(|| AppError::NotFound("Disaster has no recorded history".to_string()))?;
+
+        let mut disaster = Self::from_opened(first)?;
+        disaster.apply(first);
+        for event in rest {
+            disaster.apply(event);
+        }
+        Ok(disaster)
+    }
+
+    /// Apply a recorded change to the current state and bump the version
+    pub fn apply(&mut self, event: &DisasterEvent) {
+        match event {
+            // Initial state is built by `from_opened`
+            DisasterEvent::DisasterOpened { .. } => {}
+            DisasterEvent::DisasterStatusUpdated { new_status, updated_by, updated_at, .. } => {
+                match new_status {
+                    DisasterStatus::Verified => {
+                        if !self.verification.is_verified {
+                            self.verification.is_verified = true;
+                            self.verification.verified_by = Some(*updated_by);
+                            self.verification.verified_at = Some(*updated_at);
+                            self.timeline.verified_at = Some(*updated_at);
+                        }
+                    },
+                    DisasterStatus::Responded => {
+                        if self.timeline.first_response_at.is_none() {
+                            self.timeline.first_response_at = Some(*updated_at);
+                        }
+                    },
+                    DisasterStatus::Resolved => {
+                        self.timeline.resolved_at = Some(*updated_at);
+                    },
+                    DisasterStatus::Closed => {
+                        self.timeline.closed_at = Some(*updated_at);
+                    },
+                    DisasterStatus::Reported => {}
+                }
+                self.status = new_status.clone();
+                self.updated_at = *updated_at;
+            },
+            DisasterEvent::DisasterSeverityEscalated { new_severity, escalated_at, .. } => {
+                self.severity = new_severity.clone();
+                self.priority = Self::calculate_priority(&self.severity);
+                self.updated_at = *escalated_at;
+            },
+            DisasterEvent::DisasterResponderAssigned { responder_id, assigned_at, .. } => {
+                self.assigned_responders.push(*responder_id);
+                self.updated_at = *assigned_at;
+            },
+            DisasterEvent::DisasterResponderRemoved { responder_id, removed_at, .. } => {
+                self.assigned_responders.retain(|id| id != responder_id);
+                self.updated_at = *removed_at;
+            },
+            DisasterEvent::DisasterResourceNeedAdded { resource, added_at, .. } => {
+                self.resources_needed.push(resource.clone());
+                self.updated_at = *added_at;
+            },
+            DisasterEvent::DisasterDamageEstimated { damage, estimated_at, .. } => {
+                self.estimated_damage = Some(damage.clone());
+                self.updated_at = *estimated_at;
+            },
+            DisasterEvent::DisasterConfidenceAssessed { confidence_score, assessed_at, .. } => {
+                self.verification.confidence_score = Some(*confidence_score);
+                self.updated_at = *assessed_at;
+            },
+            DisasterEvent::DisasterFirstResponderArrived { arrived_at, .. } => {
+                if self.timeline.first_response_at.is_none_or(|first| *arrived_at < first) {
+                    self.timeline.first_response_at = Some(*arrived_at);
+                }
+                self.updated_at = self.updated_at.max(*arrived_at);
+            },
+            // Integration events published on the bus carry no aggregate state
+            DisasterEvent::DisasterReported { .. }
+            | DisasterEvent::DisasterVerified { .. }
+            | DisasterEvent::DisasterStatusChanged { .. }
+            | DisasterEvent::DisasterSeverityUpdated { .. } => {}
         }
+        self.version += 1;
+    }
+
+    /// Apply a new change and queue it for persistence
+    fn record(&mut self, event: DisasterEvent) {
+        self.apply(&event);
+        self.uncommitted_events.push(event);
+    }
+
+    pub fn uncommitted_events(&self) -> &[DisasterEvent] { &self.uncommitted_events }
+
+    /// Hand over queued events, e.g. once they have been appended to the event store
+    pub fn take_uncommitted_events(&mut self) -> Vec<DisasterEvent> {
+        std::mem::take(&mut self.uncommitted_events)
     }
 
     // Getter methods for clean architecture compliance
@@ -156,6 +387,7 @@ impl Disaster {
     pub fn status(&self) -> &DisasterStatus { &self.status }
     pub fn priority(&self) -> &Priority { &self.priority }
     pub fn location(&self) -> &Coordinates { &self.location }
+    pub fn province(&self) -> Option<&str> { self.province.as_deref() }
     pub fn reporter_id(&self) -> &UserId { &self.reporter_id }
     pub fn assigned_responders(&self) -> &[UserId] { &self.assigned_responders }
     pub fn affected_population(&self) -> Option<u32> { self.affected_population }
@@ -164,6 +396,7 @@ impl Disaster {
     pub fn created_at(&self) -> DateTime<Utc> { self.created_at }
     pub fn updated_at(&self) -> DateTime<Utc> { self.updated_at }
     pub fn version(&self) -> i64 { self.version }
+    pub fn persisted_version(&self) -> i64 { self.persisted_version }
 
     /// Calculate priority based on severity
     fn calculate_priority(severity: &DisasterSeverity) -> Priority {
@@ -186,10 +419,13 @@ impl Disaster {
             ));
         }
 
-        self.severity = new_severity;
-        self.priority = Self::calculate_priority(&self.severity);
-        self.updated_at = Utc::now();
-        self.version += 1;
+        self.record(DisasterEvent::DisasterSeverityEscalated {
+            disaster_id: self.id,
+            old_severity: self.severity.clone(),
+            new_severity,
+            escalated_by: updater_id,
+            escalated_at: Utc::now(),
+        });
         Ok(())
     }
 
@@ -198,38 +434,30 @@ impl Disaster {
         // Validate status transition
         self.validate_status_transition(&new_status)?;
 
-        let now = Utc::now();
-        
-        // Update timeline based on status
-        match new_status {
-            DisasterStatus::Verified => {
-                if !self.verification.is_verified {
-                    self.verification.is_verified = true;
-                    self.verification.verified_by = Some(updater_id);
-                    self.verification.verified_at = Some(now);
-                    self.timeline.verified_at = Some(now);
-                }
-            },
-            DisasterStatus::Responded => {
-                if self.timeline.first_response_at.is_none() {
-                    self.timeline.first_response_at = Some(now);
-                }
-            },
-            DisasterStatus::Resolved => {
-                self.timeline.resolved_at = Some(now);
-            },
-            DisasterStatus::Closed => {
-                self.timeline.closed_at = Some(now);
-            },
-            _ => {}
-        }
-
-        self.status = new_status;
-        self.updated_at = now;
-        self.version += 1;
+        self.record(DisasterEvent::DisasterStatusUpdated {
+            disaster_id: self.id,
+            old_status: self.status.clone(),
+            new_status,
+            updated_by: updater_id,
+            updated_at: Utc::now(),
+        });
         Ok(())
     }
 
+    /// Close a disaster whose source warning was withdrawn, whatever stage it had reached
+    pub fn close_withdrawn(&mut self, updater_id: UserId) {
+        if self.status == DisasterStatus::Closed {
+            return;
+        }
+        self.record(DisasterEvent::DisasterStatusUpdated {
+            disaster_id: self.id,
+            old_status: self.status.clone(),
+            new_status: DisasterStatus::Closed,
+            updated_by: updater_id,
+            updated_at: Utc::now(),
+        });
+    }
+
     /// Validate status transition business rules
     fn validate_status_transition(&self, new_status: &DisasterStatus) -> AppResult<()> {
         use DisasterStatus::*;
@@ -261,44 +489,80 @@ impl Disaster {
             ));
         }
 
-        self.assigned_responders.push(responder_id);
-        self.updated_at = Utc::now();
-        self.version += 1;
+        self.record(DisasterEvent::DisasterResponderAssigned {
+            disaster_id: self.id,
+            responder_id,
+            assigned_at: Utc::now(),
+        });
         Ok(())
     }
 
     /// Remove responder from disaster
     pub fn remove_responder(&mut self, responder_id: &UserId) -> AppResult<()> {
-        let initial_len = self.assigned_responders.len();
-        self.assigned_responders.retain(|id| id != responder_id);
-        
-        if self.assigned_responders.len() == initial_len {
+        if !self.assigned_responders.contains(responder_id) {
             return Err(AppError::NotFound(
                 "Responder not found in assigned responders".to_string()
             ));
         }
 
-        self.updated_at = Utc::now();
-        self.version += 1;
+        self.record(DisasterEvent::DisasterResponderRemoved {
+            disaster_id: self.id,
+            responder_id: *responder_id,
+            removed_at: Utc::now(),
+        });
         Ok(())
     }
 
     /// Add resource need
     pub fn add_resource_need(&mut self, resource: ResourceNeed) -> AppResult<()> {
-        self.resources_needed.push(resource);
-        self.updated_at = Utc::now();
-        self.version += 1;
+        self.record(DisasterEvent::DisasterResourceNeedAdded {
+            disaster_id: self.id,
+            resource,
+            added_at: Utc::now(),
+        });
         Ok(())
     }
 
     /// Update estimated damage
     pub fn update_estimated_damage(&mut self, damage: EstimatedDamage) -> AppResult<()> {
-        self.estimated_damage = Some(damage);
-        self.updated_at = Utc::now();
-        self.version += 1;
+        self.record(DisasterEvent::DisasterDamageEstimated {
+            disaster_id: self.id,
+            damage,
+            estimated_at: Utc::now(),
+        });
         Ok(())
     }
 
+    /// Record how confident the linked reports make us that the disaster is real;
+    /// returns `false` when the confidence did not change
+    pub fn assess_confidence(&mut self, confidence_score: f32, report_count: u32) -> bool {
+        let confidence_score = confidence_score.clamp(0.0, 1.0);
+        if self.verification.confidence_score.is_some_and(|current| (current - confidence_score).abs() < 0.001) {
+            return false;
+        }
+        self.record(DisasterEvent::DisasterConfidenceAssessed {
+            disaster_id: self.id,
+            confidence_score,
+            report_count,
+            assessed_at: Utc::now(),
+        });
+        true
+    }
+
+    /// Record a responder reaching the scene at `arrived_at`, which may lie in the past for fixes
+    /// a device uploads late; returns `false` when an earlier response is already on record
+    pub fn record_first_response(&mut self, responder_id: UserId, arrived_at: DateTime<Utc>) -> bool {
+        if self.timeline.first_response_at.is_some_and(|first| first <= arrived_at) {
+            return false;
+        }
+        self.record(DisasterEvent::DisasterFirstResponderArrived {
+            disaster_id: self.id,
+            responder_id,
+            arrived_at,
+        });
+        true
+    }
+
     /// Check if disaster is active (not resolved or closed)
     pub fn is_active(&self) -> bool {
         !matches!(self.status, DisasterStatus::Resolved | DisasterStatus::Closed)
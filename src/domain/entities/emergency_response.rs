@@ -0,0 +1,371 @@
+/// Emergency response domain entity
+/// Tracks a dispatched response from dispatch until the team completes its work on scene
+
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+use crate::domain::value_objects::Coordinates;
+use crate::shared::{AppError, AppResult, DisasterId, EmergencyResponseId, LocationId, OrganizationId, ResourceId, UserId};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmergencyResponse {
+    pub id: EmergencyResponseId,
+    pub disaster_id: DisasterId,
+    pub response_type: String,
+    /// 1 (lowest) to 5 (highest)
+    pub priority: u8,
+    pub status: EmergencyResponseStatus,
+    pub dispatched_by: UserId,
+    pub assigned_teams: Vec<Uuid>,
+    pub personnel_count: u32,
+    pub equipment: Vec<String>,
+    pub destination: Coordinates,
+    pub last_known_location: Option<Coordinates>,
+    pub estimated_arrival: Option<DateTime<Utc>>,
+    pub estimated_completion: Option<DateTime<Utc>>,
+    pub notes: Option<String>,
+    pub status_history: Vec<StatusChange>,
+    pub dispatched_at: DateTime<Utc>,
+    pub en_route_at: Option<DateTime<Utc>>,
+    pub on_scene_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub version: i64,
+    /// Version loaded from storage, used for optimistic concurrency on update
+    #[serde(skip)]
+    pub persisted_version: i64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum EmergencyResponseStatus {
+    Dispatched,
+    EnRoute,
+    OnScene,
+    Completed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusChange {
+    pub from_status: Option<EmergencyResponseStatus>,
+    pub to_status: EmergencyResponseStatus,
+    pub changed_by: UserId,
+    pub location: Option<Coordinates>,
+    pub notes: Option<String>,
+    pub changed_at: DateTime<Utc>,
+}
+
+/// Quantity of a resource available for allocation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceStock {
+    pub category: String,
+    pub name: String,
+    pub unit: String,
+    pub quantity: u64,
+}
+
+/// Condition of an inventory item held by an organization
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceStatus {
+    Available,
+    Reserved,
+    Depleted,
+}
+
+impl ResourceStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Available => "available",
+            Self::Reserved => "reserved",
+            Self::Depleted => "depleted",
+        }
+    }
+}
+
+impl FromStr for ResourceStatus {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "available" => Ok(Self::Available),
+            "reserved" => Ok(Self::Reserved),
+            "depleted" => Ok(Self::Depleted),
+            other => Err(AppError::Validation(format!(
+                "Invalid resource status '{}'. Must be one of: available, reserved, depleted",
+                other
+            ))),
+        }
+    }
+}
+
+/// Inventory item (`emergency_resources`) owned by an organization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmergencyResource {
+    pub id: ResourceId,
+    pub organization_id: Option<OrganizationId>,
+    pub name: String,
+    /// food, medical, shelter, ...
+    pub category: String,
+    pub quantity: u32,
+    /// kg, box, piece, ...
+    pub unit: String,
+    pub location_id: Option<LocationId>,
+    pub expiry_date: Option<NaiveDate>,
+    pub status: ResourceStatus,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl EmergencyResponseStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Dispatched => "dispatched",
+            Self::EnRoute => "en_route",
+            Self::OnScene => "on_scene",
+            Self::Completed => "completed",
+        }
+    }
+
+    /// The only status a response may move to next
+    pub fn next(&self) -> Option<Self> {
+        match self {
+            Self::Dispatched => Some(Self::EnRoute),
+            Self::EnRoute => Some(Self::OnScene),
+            Self::OnScene => Some(Self::Completed),
+            Self::Completed => None,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        !matches!(self, Self::Completed)
+    }
+}
+
+impl fmt::Display for EmergencyResponseStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for EmergencyResponseStatus {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().replace(['-', ' '], "_").as_str() {
+            "dispatched" => Ok(Self::Dispatched),
+            "en_route" | "enroute" => Ok(Self::EnRoute),
+            "on_scene" | "onscene" => Ok(Self::OnScene),
+            "completed" => Ok(Self::Completed),
+            other => Err(AppError::Validation(format!(
+                "Invalid emergency response status '{}'. Must be one of: dispatched, en_route, on_scene, completed",
+                other
+            ))),
+        }
+    }
+}
+
+impl EmergencyResponse {
+    /// Create a response in the `Dispatched` state
+    pub fn new(
+        disaster_id: DisasterId,
+        response_type: String,
+        priority: u8,
+        dispatched_by: UserId,
+        destination: Coordinates,
+        personnel_count: u32,
+        equipment: Vec<String>,
+    ) -> AppResult<Self> {
+        if !(1..=5).contains(&priority) {
+            return Err(AppError::Validation("Priority level must be between 1 and 5".to_string()));
+        }
+        if response_type.trim().is_empty() {
+            return Err(AppError::Validation("Response type is required".to_string()));
+        }
+
+        let now = Utc::now();
+        Ok(Self {
+            id: EmergencyResponseId::new(),
+            disaster_id,
+            response_type,
+            priority,
+            status: EmergencyResponseStatus::Dispatched,
+            dispatched_by,
+            assigned_teams: Vec::new(),
+            personnel_count,
+            equipment,
+            destination,
+            last_known_location: None,
+            estimated_arrival: None,
+            estimated_completion: None,
+            notes: None,
+            status_history: vec![StatusChange {
+                from_status: None,
+                to_status: EmergencyResponseStatus::Dispatched,
+                changed_by: dispatched_by,
+                location: None,
+                notes: None,
+                changed_at: now,
+            }],
+            dispatched_at: now,
+            en_route_at: None,
+            on_scene_at: None,
+            completed_at: None,
+            created_at: now,
+            updated_at: now,
+            version: 1,
+            persisted_version: 0,
+        })
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.status.is_active()
+    }
+
+    /// Move the response to its next lifecycle state
+    pub fn transition_to(
+        &mut self,
+        new_status: EmergencyResponseStatus,
+        changed_by: UserId,
+        location: Option<Coordinates>,
+        notes: Option<String>,
+    ) -> AppResult<()> {
+        if self.status.next() != Some(new_status) {
+            return Err(AppError::BusinessRuleViolation(format!(
+                "Invalid emergency response transition from {} to {}",
+                self.status, new_status
+            )));
+        }
+
+        let now = Utc::now();
+        match new_status {
+            EmergencyResponseStatus::EnRoute => self.en_route_at = Some(now),
+            EmergencyResponseStatus::OnScene => self.on_scene_at = Some(now),
+            EmergencyResponseStatus::Completed => self.completed_at = Some(now),
+            EmergencyResponseStatus::Dispatched => {}
+        }
+        if let Some(location) = &location {
+            self.last_known_location = Some(location.clone());
+        }
+
+        self.status_history.push(StatusChange {
+            from_status: Some(self.status),
+            to_status: new_status,
+            changed_by,
+            location,
+            notes,
+            changed_at: now,
+        });
+        self.status = new_status;
+        self.touch(now);
+        Ok(())
+    }
+
+    /// Send additional teams to an ongoing response, optionally redirecting it.
+    /// Returns the number of newly assigned teams.
+    pub fn dispatch_teams(
+        &mut self,
+        team_ids: &[Uuid],
+        destination: Option<Coordinates>,
+        equipment: &[String],
+    ) -> AppResult<usize> {
+        if !self.is_active() {
+            return Err(AppError::BusinessRuleViolation(
+                "Cannot dispatch teams to a completed emergency response".to_string()
+            ));
+        }
+        if team_ids.is_empty() {
+            return Err(AppError::Validation("At least one team must be dispatched".to_string()));
+        }
+
+        let before = self.assigned_teams.len();
+        for team_id in team_ids {
+            if !self.assigned_teams.contains(team_id) {
+                self.assigned_teams.push(*team_id);
+            }
+        }
+        for item in equipment {
+            if !self.equipment.contains(item) {
+                self.equipment.push(item.clone());
+            }
+        }
+        if let Some(destination) = destination {
+            self.destination = destination;
+        }
+
+        self.touch(Utc::now());
+        Ok(self.assigned_teams.len() - before)
+    }
+
+    pub fn set_estimates(&mut self, arrival: Option<DateTime<Utc>>, completion: Option<DateTime<Utc>>) {
+        if arrival.is_some() {
+            self.estimated_arrival = arrival;
+        }
+        if completion.is_some() {
+            self.estimated_completion = completion;
+        }
+    }
+
+    /// Minutes from dispatch until the team reached the scene
+    pub fn response_time_minutes(&self) -> Option<i64> {
+        self.on_scene_at.map(|at| (at - self.dispatched_at).num_minutes())
+    }
+
+    fn touch(&mut self, now: DateTime<Utc>) {
+        self.updated_at = now;
+        self.version += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response() -> EmergencyResponse {
+        EmergencyResponse::new(
+            DisasterId::new(),
+            "rescue".to_string(),
+            4,
+            UserId::new(),
+            Coordinates::new(-6.2, 106.8).unwrap(),
+            6,
+            vec!["Rescue Vehicle".to_string()],
+        ).unwrap()
+    }
+
+    #[test]
+    fn follows_lifecycle_in_order() {
+        let mut response = response();
+        let actor = UserId::new();
+
+        response.transition_to(EmergencyResponseStatus::EnRoute, actor, None, None).unwrap();
+        response.transition_to(EmergencyResponseStatus::OnScene, actor, None, Some("Arrived".to_string())).unwrap();
+        response.transition_to(EmergencyResponseStatus::Completed, actor, None, None).unwrap();
+
+        assert!(!response.is_active());
+        assert_eq!(response.status_history.len(), 4);
+        assert!(response.response_time_minutes().is_some());
+        assert_eq!(response.version, 4);
+    }
+
+    #[test]
+    fn rejects_skipped_or_backward_transitions() {
+        let mut response = response();
+        let actor = UserId::new();
+
+        assert!(response.transition_to(EmergencyResponseStatus::OnScene, actor, None, None).is_err());
+        response.transition_to(EmergencyResponseStatus::EnRoute, actor, None, None).unwrap();
+        assert!(response.transition_to(EmergencyResponseStatus::Dispatched, actor, None, None).is_err());
+        assert!(response.transition_to(EmergencyResponseStatus::EnRoute, actor, None, None).is_err());
+    }
+
+    #[test]
+    fn parses_status_aliases() {
+        assert_eq!("en-route".parse::<EmergencyResponseStatus>().unwrap(), EmergencyResponseStatus::EnRoute);
+        assert_eq!("On Scene".parse::<EmergencyResponseStatus>().unwrap(), EmergencyResponseStatus::OnScene);
+        assert!("cancelled".parse::<EmergencyResponseStatus>().is_err());
+    }
+}
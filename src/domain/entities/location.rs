@@ -12,6 +12,8 @@ pub struct Location {
     pub name: String,
     pub coordinates: Coordinates,
     pub address: Address,
+    /// Sub-city area (kecamatan/desa) the location belongs to
+    pub region: Option<String>,
     pub location_type: LocationType,
     pub administrative_level: AdministrativeLevel,
     pub population: Option<u32>,
@@ -63,6 +65,23 @@ pub struct EmergencyContact {
     pub is_primary: bool,
 }
 
+/// Criteria for a paged location listing; unset criteria match everything
+#[derive(Debug, Clone, Default)]
+pub struct LocationSearch {
+    /// Part of the name or street address; names are also matched loosely
+    pub text: Option<String>,
+    /// Any of these types; every type when empty
+    pub types: Vec<LocationType>,
+    /// Part of the province name
+    pub province: Option<String>,
+    /// Part of the city name
+    pub city: Option<String>,
+    /// Center and radius in km; results then come nearest first
+    pub near: Option<(Coordinates, f64)>,
+    /// Only locations whose risk allows sheltering evacuees, as in `Location::is_suitable_for_evacuation`
+    pub evacuation_only: bool,
+}
+
 impl Location {
     /// Create a new location with validation
     pub fn new(
@@ -89,6 +108,7 @@ impl Location {
             name: name.trim().to_string(),
             coordinates,
             address,
+            region: None,
             location_type,
             administrative_level,
             population: None,
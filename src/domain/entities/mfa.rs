@@ -0,0 +1,36 @@
+/// Multi-factor authentication entities
+/// Per-user TOTP enrollment; the shared secret is only ever held encrypted
+
+use chrono::{DateTime, Utc};
+use crate::shared::UserId;
+
+#[derive(Debug, Clone)]
+pub struct MfaEnrollment {
+    pub user_id: UserId,
+    /// Nonce-prefixed AES-256-GCM ciphertext of the raw TOTP secret
+    pub secret_ciphertext: Vec<u8>,
+    /// Set once the user proves possession with a first valid code
+    pub confirmed_at: Option<DateTime<Utc>>,
+    /// Last accepted time step, so a code cannot be replayed within its window
+    pub last_used_step: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl MfaEnrollment {
+    pub fn new(user_id: UserId, secret_ciphertext: Vec<u8>) -> Self {
+        let now = Utc::now();
+        Self {
+            user_id,
+            secret_ciphertext,
+            confirmed_at: None,
+            last_used_step: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.confirmed_at.is_some()
+    }
+}
@@ -5,9 +5,31 @@ pub mod user;
 pub mod disaster;
 pub mod location;
 pub mod notification;
+pub mod emergency_response;
+pub mod response_team;
+pub mod mfa;
+pub mod verification;
+pub mod session;
+pub mod organization;
+pub mod role;
+pub mod report;
+pub mod volunteer;
+pub mod tracking;
+pub mod analytics;
 
 // Re-export entities
 pub use user::User;
-pub use disaster::Disaster;
+pub use disaster::{Disaster, DisasterZone};
 pub use location::Location;
 pub use notification::Notification;
+pub use emergency_response::EmergencyResponse;
+pub use response_team::{EmergencyStation, ResponseTeam};
+pub use mfa::MfaEnrollment;
+pub use verification::{VerificationCode, VerificationPurpose};
+pub use session::UserSession;
+pub use organization::{Organization, OrganizationMembership, OrganizationRole};
+pub use role::{Role, RoleAuditAction, RoleAuditEntry};
+pub use report::{CommentThread, Report, ReportComment, ReportMedia, ReportStatus, ReportStatusChange};
+pub use volunteer::{AssignmentStatus, Volunteer, VolunteerAssignment, VolunteerProfile};
+pub use tracking::{FixSource, LocationFix, LocationSample};
+pub use analytics::{AnalyticsFilter, DisasterFact, Granularity, LocationFilter};
@@ -3,7 +3,9 @@
 
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use uuid::Uuid;
 use crate::shared::{NotificationId, UserId, AppResult, AppError, AuditFields, Priority};
+use crate::shared::error::ErrorRecovery;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Notification {
@@ -264,3 +266,247 @@ impl Notification {
         Ok(())
     }
 }
+
+impl NotificationType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            NotificationType::DisasterAlert => "disaster_alert",
+            NotificationType::EmergencyResponse => "emergency_response",
+            NotificationType::StatusUpdate => "status_update",
+            NotificationType::SystemNotification => "system_notification",
+            NotificationType::VerificationRequest => "verification_request",
+            NotificationType::AssignmentNotification => "assignment_notification",
+            NotificationType::ReminderNotification => "reminder_notification",
+            NotificationType::WeatherAlert => "weather_alert",
+            NotificationType::Other(other) => other,
+        }
+    }
+}
+
+impl NotificationChannel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationChannel::InApp => "in_app",
+            NotificationChannel::Email => "email",
+            NotificationChannel::SMS => "sms",
+            NotificationChannel::WhatsApp => "whatsapp",
+            NotificationChannel::Push => "push",
+        }
+    }
+}
+
+impl std::str::FromStr for NotificationChannel {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> AppResult<Self> {
+        match s.to_lowercase().as_str() {
+            "in_app" | "inapp" => Ok(NotificationChannel::InApp),
+            "email" => Ok(NotificationChannel::Email),
+            "sms" => Ok(NotificationChannel::SMS),
+            "whatsapp" => Ok(NotificationChannel::WhatsApp),
+            "push" => Ok(NotificationChannel::Push),
+            other => Err(AppError::Validation(format!("Unknown notification channel '{}'", other))),
+        }
+    }
+}
+
+/// Retry schedule for outbox deliveries
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay_ms: u64,
+    pub max_delay_ms: u64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum OutboxStatus {
+    Pending,
+    Processing,
+    Sent,
+    DeadLettered,
+}
+
+impl OutboxStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutboxStatus::Pending => "pending",
+            OutboxStatus::Processing => "processing",
+            OutboxStatus::Sent => "sent",
+            OutboxStatus::DeadLettered => "dead_lettered",
+        }
+    }
+}
+
+impl std::str::FromStr for OutboxStatus {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> AppResult<Self> {
+        match s {
+            "pending" => Ok(OutboxStatus::Pending),
+            "processing" => Ok(OutboxStatus::Processing),
+            "sent" => Ok(OutboxStatus::Sent),
+            "dead_lettered" => Ok(OutboxStatus::DeadLettered),
+            other => Err(AppError::Validation(format!("Unknown outbox status '{}'", other))),
+        }
+    }
+}
+
+/// A notification queued for delivery on one channel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboxMessage {
+    pub id: Uuid,
+    pub notification_id: NotificationId,
+    pub recipient_id: UserId,
+    pub channel: NotificationChannel,
+    pub notification_type: String,
+    pub title: String,
+    pub message: String,
+    pub priority: Priority,
+    pub status: OutboxStatus,
+    pub attempts: u32,
+    pub max_attempts: u32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub dead_lettered_at: Option<DateTime<Utc>>,
+}
+
+impl OutboxMessage {
+    /// One message per channel; the first reuses the notification id
+    pub fn from_notification(notification: &Notification, max_attempts: u32) -> Vec<Self> {
+        let now = Utc::now();
+        notification.channels.iter().enumerate()
+            .map(|(index, channel)| Self {
+                id: if index == 0 { notification.id.0 } else { Uuid::new_v4() },
+                notification_id: notification.id,
+                recipient_id: notification.recipient_id,
+                channel: channel.clone(),
+                notification_type: notification.notification_type.as_str().to_string(),
+                title: notification.title.clone(),
+                message: notification.message.clone(),
+                priority: notification.priority.clone(),
+                status: OutboxStatus::Pending,
+                attempts: 0,
+                max_attempts: max_attempts.max(1),
+                next_attempt_at: notification.delivery_info.scheduled_at.unwrap_or(now),
+                last_error: None,
+                expires_at: notification.metadata.expires_at,
+                created_at: now,
+                updated_at: now,
+                sent_at: None,
+                dead_lettered_at: None,
+            })
+            .collect()
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|expires_at| Utc::now() > expires_at)
+    }
+
+    pub fn record_success(&mut self) {
+        let now = Utc::now();
+        self.attempts += 1;
+        self.status = OutboxStatus::Sent;
+        self.sent_at = Some(now);
+        self.last_error = None;
+        self.updated_at = now;
+    }
+
+    /// Schedule the next attempt with exponential backoff, or dead-letter the message when
+    /// the error is permanent or the attempts are exhausted
+    pub fn record_failure(&mut self, error: &AppError, policy: &RetryPolicy) {
+        let now = Utc::now();
+        self.attempts += 1;
+        self.last_error = Some(error.to_string());
+        self.updated_at = now;
+
+        if ErrorRecovery::should_retry(error, self.attempts, self.max_attempts) && !self.is_expired() {
+            let delay = ErrorRecovery::calculate_backoff(self.attempts - 1, policy.base_delay_ms, policy.max_delay_ms);
+            self.status = OutboxStatus::Pending;
+            self.next_attempt_at = now + chrono::Duration::from_std(delay).unwrap_or_else(|_| chrono::Duration::zero());
+        } else {
+            self.status = OutboxStatus::DeadLettered;
+            self.dead_lettered_at = Some(now);
+        }
+    }
+
+    /// Move a message to the dead-letter state without attempting delivery
+    pub fn dead_letter(&mut self, reason: String) {
+        let now = Utc::now();
+        self.status = OutboxStatus::DeadLettered;
+        self.last_error = Some(reason);
+        self.dead_lettered_at = Some(now);
+        self.updated_at = now;
+    }
+
+    /// Give a dead-lettered message a fresh set of attempts
+    pub fn requeue(&mut self) -> AppResult<()> {
+        if self.status != OutboxStatus::DeadLettered {
+            return Err(AppError::BusinessRuleViolation(
+                "Only dead-lettered messages can be requeued".to_string()
+            ));
+        }
+        let now = Utc::now();
+        self.status = OutboxStatus::Pending;
+        self.attempts = 0;
+        self.next_attempt_at = now;
+        self.dead_lettered_at = None;
+        self.updated_at = now;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(max_attempts: u32) -> OutboxMessage {
+        let notification = Notification::new(
+            UserId::new(),
+            "Peringatan".to_string(),
+            "Evakuasi segera".to_string(),
+            NotificationType::DisasterAlert,
+            Priority::Emergency,
+            vec![NotificationChannel::SMS, NotificationChannel::Email],
+        ).unwrap();
+        let messages = OutboxMessage::from_notification(&notification, max_attempts);
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].id, notification.id.0);
+        messages.into_iter().next().unwrap()
+    }
+
+    #[test]
+    fn retryable_failures_back_off_until_exhausted() {
+        let policy = RetryPolicy { max_attempts: 3, base_delay_ms: 1_000, max_delay_ms: 60_000 };
+        let mut message = message(policy.max_attempts);
+
+        message.record_failure(&AppError::ExternalService("gateway timeout".to_string()), &policy);
+        assert_eq!(message.status, OutboxStatus::Pending);
+        let first_delay = message.next_attempt_at - message.updated_at;
+
+        message.record_failure(&AppError::ExternalService("gateway timeout".to_string()), &policy);
+        assert_eq!(message.status, OutboxStatus::Pending);
+        assert!(message.next_attempt_at - message.updated_at > first_delay);
+
+        message.record_failure(&AppError::ExternalService("gateway timeout".to_string()), &policy);
+        assert_eq!(message.status, OutboxStatus::DeadLettered);
+        assert_eq!(message.attempts, 3);
+
+        message.requeue().unwrap();
+        assert_eq!((message.status, message.attempts), (OutboxStatus::Pending, 0));
+    }
+
+    #[test]
+    fn permanent_failures_dead_letter_immediately() {
+        let policy = RetryPolicy { max_attempts: 5, base_delay_ms: 1_000, max_delay_ms: 60_000 };
+        let mut message = message(policy.max_attempts);
+
+        message.record_failure(&AppError::Validation("User has no phone number".to_string()), &policy);
+        assert_eq!(message.status, OutboxStatus::DeadLettered);
+        assert!(message.requeue().is_ok());
+        assert!(message.requeue().is_err());
+    }
+}
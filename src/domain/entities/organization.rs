@@ -0,0 +1,177 @@
+/// Organization entities
+/// Partner agencies and NGOs, their members and the role each member holds inside one organization
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use crate::shared::{AppError, AppResult, LocationId, OrganizationId, Permission, UserId};
+
+/// Role of a member inside a single organization; it grants nothing in other organizations
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum OrganizationRole {
+    Admin,
+    Coordinator,
+    Volunteer,
+    Member,
+}
+
+impl OrganizationRole {
+    /// Value of the `organization_members.role` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Admin => "admin",
+            Self::Coordinator => "coordinator",
+            Self::Volunteer => "volunteer",
+            Self::Member => "member",
+        }
+    }
+
+    /// Permissions the role grants, scoped to its own organization
+    pub fn permissions(&self) -> &'static [Permission] {
+        match self {
+            Self::Admin => &[
+                Permission::ManageOrganization,
+                Permission::ReadOrgData,
+                Permission::WriteOrgData,
+                Permission::ManageVolunteers,
+                Permission::ReadVolunteerData,
+                Permission::ManageResources,
+                Permission::ReadResourceData,
+                Permission::UpdateResourceData,
+            ],
+            Self::Coordinator => &[
+                Permission::ReadOrgData,
+                Permission::ManageVolunteers,
+                Permission::ReadVolunteerData,
+                Permission::ReadResourceData,
+                Permission::UpdateResourceData,
+            ],
+            Self::Volunteer => &[
+                Permission::ReadOrgData,
+                Permission::ReadResourceData,
+            ],
+            Self::Member => &[Permission::ReadOrgData],
+        }
+    }
+
+    pub fn grants(&self, permission: &Permission) -> bool {
+        self.permissions().contains(permission)
+    }
+
+    /// Whether a member holding this role may give `target` to someone, or take it away
+    pub fn can_assign(&self, target: OrganizationRole) -> bool {
+        match self {
+            Self::Admin => true,
+            Self::Coordinator => matches!(target, Self::Volunteer | Self::Member),
+            Self::Volunteer | Self::Member => false,
+        }
+    }
+}
+
+impl fmt::Display for OrganizationRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for OrganizationRole {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> AppResult<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "admin" => Ok(Self::Admin),
+            "coordinator" => Ok(Self::Coordinator),
+            "volunteer" => Ok(Self::Volunteer),
+            "member" => Ok(Self::Member),
+            other => Err(AppError::Validation(format!("Unknown organization role '{}'", other))),
+        }
+    }
+}
+
+/// Agency or NGO that owns volunteers and emergency resources
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Organization {
+    pub id: OrganizationId,
+    pub name: String,
+    pub description: Option<String>,
+    pub logo_url: Option<String>,
+    pub website: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub address: Option<String>,
+    pub location_id: Option<LocationId>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Organization {
+    pub fn new(name: String) -> AppResult<Self> {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(AppError::Validation("Organization name is required".to_string()));
+        }
+
+        let now = Utc::now();
+        Ok(Self {
+            id: OrganizationId::new(),
+            name,
+            description: None,
+            logo_url: None,
+            website: None,
+            phone: None,
+            email: None,
+            address: None,
+            location_id: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+}
+
+/// A user's membership in one organization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrganizationMembership {
+    pub organization_id: OrganizationId,
+    pub user_id: UserId,
+    pub role: OrganizationRole,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl OrganizationMembership {
+    pub fn new(organization_id: OrganizationId, user_id: UserId, role: OrganizationRole) -> Self {
+        let now = Utc::now();
+        Self {
+            organization_id,
+            user_id,
+            role,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roles_round_trip_through_storage_values() {
+        for role in [OrganizationRole::Admin, OrganizationRole::Coordinator, OrganizationRole::Volunteer, OrganizationRole::Member] {
+            assert_eq!(role.as_str().parse::<OrganizationRole>().unwrap(), role);
+        }
+        assert!("owner".parse::<OrganizationRole>().is_err());
+    }
+
+    #[test]
+    fn coordinators_manage_volunteers_but_not_the_organization() {
+        let coordinator = OrganizationRole::Coordinator;
+        assert!(coordinator.grants(&Permission::ManageVolunteers));
+        assert!(!coordinator.grants(&Permission::ManageOrganization));
+        assert!(!coordinator.grants(&Permission::ManageResources));
+        assert!(coordinator.can_assign(OrganizationRole::Volunteer));
+        assert!(!coordinator.can_assign(OrganizationRole::Admin));
+    }
+}
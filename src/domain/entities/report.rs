@@ -0,0 +1,504 @@
+/// Report domain entity
+/// A first-hand account of an incident, submitted by a user or anonymously, that validators review
+/// and link to the disaster it describes
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+use crate::domain::entities::disaster::DisasterType;
+use crate::domain::value_objects::{Coordinates, PhoneNumber};
+use crate::shared::{AppError, AppResult, ReportId, UserId};
+
+const MAX_TITLE_LEN: usize = 200;
+const MAX_COMMENT_LEN: usize = 2000;
+const MAX_CAPTION_LEN: usize = 500;
+
+/// Lifecycle of a report; stored in `reports.status`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportStatus {
+    Reported,
+    UnderReview,
+    Verified,
+    Rejected,
+    Resolved,
+}
+
+impl ReportStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Reported => "reported",
+            Self::UnderReview => "under_review",
+            Self::Verified => "verified",
+            Self::Rejected => "rejected",
+            Self::Resolved => "resolved",
+        }
+    }
+
+    /// Rejected reports may be reopened for review; resolved reports are final
+    pub fn can_transition_to(&self, next: ReportStatus) -> bool {
+        use ReportStatus::*;
+        matches!(
+            (self, next),
+            (Reported, UnderReview | Verified | Rejected)
+                | (UnderReview, Reported | Verified | Rejected)
+                | (Verified, Rejected | Resolved)
+                | (Rejected, UnderReview)
+        )
+    }
+}
+
+impl FromStr for ReportStatus {
+    type Err = AppError;
+
+    /// Also accepts the legacy `pending`, `valid` and `invalid` values
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "reported" | "pending" => Ok(Self::Reported),
+            "under_review" => Ok(Self::UnderReview),
+            "verified" | "valid" => Ok(Self::Verified),
+            "rejected" | "invalid" => Ok(Self::Rejected),
+            "resolved" => Ok(Self::Resolved),
+            other => Err(AppError::Validation(format!(
+                "Invalid report status '{}'. Must be one of: reported, under_review, verified, rejected, resolved",
+                other
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for ReportStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Who submitted a report
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ReportSource {
+    User { user_id: UserId },
+    /// Submitted without an account; the contact details are optional
+    Anonymous { name: Option<String>, phone: Option<String> },
+}
+
+impl ReportSource {
+    pub fn anonymous(name: Option<String>, phone: Option<String>) -> AppResult<Self> {
+        let name = name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+        let phone = phone
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .map(|p| PhoneNumber::new(p).map(|p| p.value().to_string()))
+            .transpose()?;
+        Ok(Self::Anonymous { name, phone })
+    }
+
+    /// The source without an anonymous reporter's phone number, for callers who do not validate reports
+    pub fn redacted(self) -> Self {
+        match self {
+            Self::Anonymous { name, .. } => Self::Anonymous { name, phone: None },
+            user => user,
+        }
+    }
+}
+
+/// Casualty figures as estimated by the reporter
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CasualtyEstimate {
+    pub casualties: Option<u32>,
+    pub injuries: Option<u32>,
+    pub missing: Option<u32>,
+    pub affected_people: Option<u32>,
+}
+
+/// What the reporter describes; editable until the report is reviewed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportDetails {
+    pub disaster_type: DisasterType,
+    pub title: String,
+    pub description: Option<String>,
+    pub location: Coordinates,
+    pub address: Option<String>,
+    /// Estimated radius of the affected area in meters
+    pub impact_radius_m: Option<f64>,
+    /// 1 (minor) to 5 (catastrophic)
+    pub estimated_severity: u8,
+    pub casualties: CasualtyEstimate,
+}
+
+impl ReportDetails {
+    fn validate(mut self) -> AppResult<Self> {
+        self.title = self.title.trim().to_string();
+        if self.title.is_empty() {
+            return Err(AppError::Validation("Report title is required".to_string()));
+        }
+        if self.title.chars().count() > MAX_TITLE_LEN {
+            return Err(AppError::Validation(format!("Report title must be at most {} characters", MAX_TITLE_LEN)));
+        }
+        if !(1..=5).contains(&self.estimated_severity) {
+            return Err(AppError::Validation("Estimated severity must be between 1 and 5".to_string()));
+        }
+        if self.impact_radius_m.is_some_and(|r| !r.is_finite() || r < 0.0) {
+            return Err(AppError::Validation("Impact radius must be a non-negative number of meters".to_string()));
+        }
+        self.description = self.description.map(|d| d.trim().to_string()).filter(|d| !d.is_empty());
+        self.address = self.address.map(|a| a.trim().to_string()).filter(|a| !a.is_empty());
+        Ok(self)
+    }
+}
+
+/// How the weather at a report's location bears on the reported disaster type
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WeatherAgreement {
+    Supports,
+    Neutral,
+    Contradicts,
+    /// No weather data was available
+    Unavailable,
+}
+
+/// Inputs of a report's credibility score, each from 0.0 to 1.0
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CredibilityFactors {
+    /// Track record of the reporter's earlier reports
+    pub reporter: f64,
+    /// Support from other reporters' nearby reports of the same disaster type
+    pub corroboration: f64,
+    pub corroborating_reports: u32,
+    pub media: f64,
+    pub weather: WeatherAgreement,
+    pub assessed_at: DateTime<Utc>,
+}
+
+/// Outcome of the latest verification or rejection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportValidation {
+    pub validated_by: UserId,
+    pub notes: Option<String>,
+    pub validated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Report {
+    pub id: ReportId,
+    pub source: ReportSource,
+    pub details: ReportDetails,
+    pub status: ReportStatus,
+    /// 0.0 (unsupported) to 1.0 (confirmed)
+    pub credibility_score: f64,
+    /// What the score was computed from; `None` until the report is first assessed
+    pub credibility: Option<CredibilityFactors>,
+    /// Reports of the same incident share a cluster, named after its earliest report
+    pub cluster_id: Option<Uuid>,
+    pub validation: Option<ReportValidation>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Report {
+    /// A new report together with the history entry recording its submission
+    pub fn submit(source: ReportSource, details: ReportDetails) -> AppResult<(Self, ReportStatusChange)> {
+        let now = Utc::now();
+        let report = Self {
+            id: ReportId::new(),
+            source,
+            details: details.validate()?,
+            status: ReportStatus::Reported,
+            credibility_score: 0.0,
+            credibility: None,
+            cluster_id: None,
+            validation: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let submitted = ReportStatusChange {
+            id: Uuid::new_v4(),
+            report_id: report.id,
+            changed_by: report.reporter_id(),
+            status_from: None,
+            status_to: ReportStatus::Reported,
+            notes: Some("Report submitted".to_string()),
+            created_at: now,
+        };
+        Ok((report, submitted))
+    }
+
+    pub fn reporter_id(&self) -> Option<UserId> {
+        match &self.source {
+            ReportSource::User { user_id } => Some(*user_id),
+            ReportSource::Anonymous { .. } => None,
+        }
+    }
+
+    pub fn is_anonymous(&self) -> bool {
+        matches!(self.source, ReportSource::Anonymous { .. })
+    }
+
+    /// Replace the reported details; only allowed before review starts
+    pub fn revise(&mut self, details: ReportDetails) -> AppResult<()> {
+        if self.status != ReportStatus::Reported {
+            return Err(AppError::BusinessRuleViolation(format!(
+                "Report {} is {} and can no longer be edited",
+                self.id, self.status
+            )));
+        }
+        self.details = details.validate()?;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Record a new credibility assessment and the incident cluster the report belongs to
+    pub fn record_credibility(&mut self, score: f64, factors: CredibilityFactors, cluster_id: Uuid) {
+        self.credibility_score = score.clamp(0.0, 1.0);
+        self.credibility = Some(factors);
+        self.cluster_id = Some(cluster_id);
+    }
+
+    /// Move the report to `next`, returning the history entry to store with it
+    /// Verifying or rejecting records the validator and their notes
+    pub fn change_status(&mut self, next: ReportStatus, changed_by: UserId, notes: Option<String>) -> AppResult<ReportStatusChange> {
+        if !self.status.can_transition_to(next) {
+            return Err(AppError::BusinessRuleViolation(format!(
+                "Report {} cannot move from {} to {}",
+                self.id, self.status, next
+            )));
+        }
+
+        let now = Utc::now();
+        let notes = notes.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+        if matches!(next, ReportStatus::Verified | ReportStatus::Rejected) {
+            self.validation = Some(ReportValidation {
+                validated_by: changed_by,
+                notes: notes.clone(),
+                validated_at: now,
+            });
+        }
+
+        let change = ReportStatusChange {
+            id: Uuid::new_v4(),
+            report_id: self.id,
+            changed_by: Some(changed_by),
+            status_from: Some(self.status),
+            status_to: next,
+            notes,
+            created_at: now,
+        };
+        self.status = next;
+        self.updated_at = now;
+        Ok(change)
+    }
+}
+
+/// One entry of a report's status history; the first has no `status_from`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportStatusChange {
+    pub id: Uuid,
+    pub report_id: ReportId,
+    /// `None` for anonymous submissions
+    pub changed_by: Option<UserId>,
+    pub status_from: Option<ReportStatus>,
+    pub status_to: ReportStatus,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Kind of file attached to a report
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MediaKind {
+    Image,
+    Video,
+    Document,
+}
+
+impl MediaKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Image => "image",
+            Self::Video => "video",
+            Self::Document => "document",
+        }
+    }
+
+    /// Photo and video types accepted for upload
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        match content_type.trim().to_lowercase().as_str() {
+            "image/jpeg" | "image/png" | "image/webp" | "image/heic" | "image/heif" => Some(Self::Image),
+            "video/mp4" | "video/quicktime" | "video/webm" | "video/3gpp" => Some(Self::Video),
+            _ => None,
+        }
+    }
+
+    pub fn from_column(value: &str) -> Self {
+        match value {
+            "image" | "photo" => Self::Image,
+            "video" => Self::Video,
+            _ => Self::Document,
+        }
+    }
+}
+
+/// A photo or video attached to a report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportMedia {
+    pub id: Uuid,
+    pub report_id: ReportId,
+    pub kind: MediaKind,
+    pub url: String,
+    /// Object key in the media storage; `None` for media only known by URL
+    pub storage_key: Option<String>,
+    pub content_type: Option<String>,
+    pub size_bytes: Option<i64>,
+    pub caption: Option<String>,
+    pub is_primary: bool,
+    pub uploaded_by: Option<UserId>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ReportMedia {
+    pub fn caption(raw: Option<String>) -> AppResult<Option<String>> {
+        let caption = raw.map(|c| c.trim().to_string()).filter(|c| !c.is_empty());
+        if caption.as_ref().is_some_and(|c| c.chars().count() > MAX_CAPTION_LEN) {
+            return Err(AppError::Validation(format!("Caption must be at most {} characters", MAX_CAPTION_LEN)));
+        }
+        Ok(caption)
+    }
+}
+
+/// A comment on a report; replies point at their parent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportComment {
+    pub id: Uuid,
+    pub report_id: ReportId,
+    pub author_id: Option<UserId>,
+    pub content: String,
+    pub parent_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ReportComment {
+    pub fn new(report_id: ReportId, author_id: UserId, content: &str, parent_id: Option<Uuid>) -> AppResult<Self> {
+        let content = content.trim();
+        if content.is_empty() {
+            return Err(AppError::Validation("Comment cannot be empty".to_string()));
+        }
+        if content.chars().count() > MAX_COMMENT_LEN {
+            return Err(AppError::Validation(format!("Comment must be at most {} characters", MAX_COMMENT_LEN)));
+        }
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::new_v4(),
+            report_id,
+            author_id: Some(author_id),
+            content: content.to_string(),
+            parent_id,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+}
+
+/// A comment with its replies, oldest first
+#[derive(Debug, Clone, Serialize)]
+pub struct CommentThread {
+    #[serde(flatten)]
+    pub comment: ReportComment,
+    pub replies: Vec<CommentThread>,
+}
+
+impl CommentThread {
+    /// Nest a report's comments under their parents; replies to missing comments become top-level
+    pub fn build(mut comments: Vec<ReportComment>) -> Vec<CommentThread> {
+        comments.sort_by_key(|c| c.created_at);
+        let ids: Vec<Uuid> = comments.iter().map(|c| c.id).collect();
+        let (roots, replies): (Vec<_>, Vec<_>) = comments
+            .into_iter()
+            .partition(|c| c.parent_id.is_none_or(|parent| !ids.contains(&parent)));
+
+        fn attach(comment: ReportComment, replies: &[ReportComment]) -> CommentThread {
+            let children = replies.iter()
+                .filter(|reply| reply.parent_id == Some(comment.id))
+                .map(|reply| attach(reply.clone(), replies))
+                .collect();
+            CommentThread { comment, replies: children }
+        }
+
+        roots.into_iter().map(|root| attach(root, &replies)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn details() -> ReportDetails {
+        ReportDetails {
+            disaster_type: DisasterType::Flood,
+            title: "  Banjir di Kampung Melayu ".to_string(),
+            description: Some("Air setinggi satu meter".to_string()),
+            location: Coordinates::new(-6.22, 106.86).unwrap(),
+            address: None,
+            impact_radius_m: Some(500.0),
+            estimated_severity: 3,
+            casualties: CasualtyEstimate::default(),
+        }
+    }
+
+    #[test]
+    fn anonymous_submission_records_history_without_actor() {
+        let source = ReportSource::anonymous(Some("Ahmad".to_string()), Some("+6281234567890".to_string())).unwrap();
+        let (report, submitted) = Report::submit(source, details()).unwrap();
+
+        assert!(report.is_anonymous());
+        assert_eq!(report.details.title, "Banjir di Kampung Melayu");
+        assert_eq!(report.status, ReportStatus::Reported);
+        assert_eq!(submitted.changed_by, None);
+        assert_eq!(submitted.status_from, None);
+
+        assert!(ReportSource::anonymous(None, Some("0812".to_string())).is_err());
+        let mut invalid = details();
+        invalid.estimated_severity = 6;
+        assert!(Report::submit(ReportSource::anonymous(None, None).unwrap(), invalid).is_err());
+    }
+
+    #[test]
+    fn status_changes_follow_the_lifecycle() {
+        let reporter = UserId::new();
+        let validator = UserId::new();
+        let (mut report, _) = Report::submit(ReportSource::User { user_id: reporter }, details()).unwrap();
+
+        let change = report.change_status(ReportStatus::Verified, validator, Some("Confirmed on site".to_string())).unwrap();
+        assert_eq!(change.status_from, Some(ReportStatus::Reported));
+        assert_eq!(report.validation.as_ref().map(|v| v.validated_by), Some(validator));
+
+        // Verified reports are locked for the reporter and cannot go back to reported
+        assert!(report.revise(details()).is_err());
+        assert!(report.change_status(ReportStatus::Reported, validator, None).is_err());
+        report.change_status(ReportStatus::Resolved, validator, None).unwrap();
+        assert!(report.change_status(ReportStatus::UnderReview, validator, None).is_err());
+
+        assert_eq!("valid".parse::<ReportStatus>().unwrap(), ReportStatus::Verified);
+    }
+
+    #[test]
+    fn comments_nest_under_their_parents() {
+        let report_id = ReportId::new();
+        let author = UserId::new();
+        let root = ReportComment::new(report_id, author, "Tim sudah di lokasi", None).unwrap();
+        let reply = ReportComment::new(report_id, author, "Terima kasih", Some(root.id)).unwrap();
+        let nested = ReportComment::new(report_id, author, "Sama-sama", Some(reply.id)).unwrap();
+        let orphan = ReportComment::new(report_id, author, "Balasan yatim", Some(Uuid::new_v4())).unwrap();
+
+        let threads = CommentThread::build(vec![nested, orphan, reply, root.clone()]);
+        assert_eq!(threads.len(), 2);
+        let first = threads.iter().find(|t| t.comment.id == root.id).unwrap();
+        assert_eq!(first.replies.len(), 1);
+        assert_eq!(first.replies[0].replies.len(), 1);
+
+        assert!(ReportComment::new(report_id, author, "   ", None).is_err());
+    }
+}
@@ -0,0 +1,282 @@
+/// Response team registry entities
+/// Emergency stations and the dispatchable teams operating from them
+
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+use crate::domain::value_objects::Coordinates;
+use crate::shared::{AppError, AppResult, EmergencyResponseId};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum TeamType {
+    Medical,
+    Fire,
+    Rescue,
+    Police,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum TeamAvailability {
+    Available,
+    Dispatched,
+    OffDuty,
+    OutOfService,
+}
+
+/// Fixed base (hospital, fire station, police post) that teams operate from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmergencyStation {
+    pub id: Uuid,
+    pub name: String,
+    pub station_type: TeamType,
+    pub location: Coordinates,
+    pub address: Option<String>,
+    pub contact_phone: Option<String>,
+    pub staff_count: u32,
+    pub vehicles: Vec<String>,
+    pub is_operational: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Dispatchable unit with its own crew and vehicles
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResponseTeam {
+    pub id: Uuid,
+    pub station_id: Option<Uuid>,
+    pub name: String,
+    pub team_type: TeamType,
+    pub location: Coordinates,
+    pub location_updated_at: DateTime<Utc>,
+    pub personnel: u32,
+    pub vehicles: Vec<String>,
+    pub availability: TeamAvailability,
+    pub current_response_id: Option<EmergencyResponseId>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub version: i64,
+    /// Version loaded from storage, used for optimistic concurrency on update
+    #[serde(skip)]
+    pub persisted_version: i64,
+}
+
+impl TeamType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Medical => "medical",
+            Self::Fire => "fire",
+            Self::Rescue => "rescue",
+            Self::Police => "police",
+        }
+    }
+}
+
+impl fmt::Display for TeamType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TeamType {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "medical" => Ok(Self::Medical),
+            "fire" => Ok(Self::Fire),
+            "rescue" => Ok(Self::Rescue),
+            "police" => Ok(Self::Police),
+            other => Err(AppError::Validation(format!(
+                "Invalid team type '{}'. Must be one of: medical, fire, rescue, police",
+                other
+            ))),
+        }
+    }
+}
+
+impl TeamAvailability {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Available => "available",
+            Self::Dispatched => "dispatched",
+            Self::OffDuty => "off_duty",
+            Self::OutOfService => "out_of_service",
+        }
+    }
+}
+
+impl fmt::Display for TeamAvailability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TeamAvailability {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().replace(['-', ' '], "_").as_str() {
+            "available" => Ok(Self::Available),
+            "dispatched" => Ok(Self::Dispatched),
+            "off_duty" => Ok(Self::OffDuty),
+            "out_of_service" => Ok(Self::OutOfService),
+            other => Err(AppError::Validation(format!(
+                "Invalid availability '{}'. Must be one of: available, dispatched, off_duty, out_of_service",
+                other
+            ))),
+        }
+    }
+}
+
+impl EmergencyStation {
+    pub fn new(name: String, station_type: TeamType, location: Coordinates) -> AppResult<Self> {
+        if name.trim().is_empty() {
+            return Err(AppError::Validation("Station name is required".to_string()));
+        }
+
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::new_v4(),
+            name,
+            station_type,
+            location,
+            address: None,
+            contact_phone: None,
+            staff_count: 0,
+            vehicles: Vec::new(),
+            is_operational: true,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+}
+
+impl ResponseTeam {
+    pub fn new(name: String, team_type: TeamType, location: Coordinates, personnel: u32) -> AppResult<Self> {
+        if name.trim().is_empty() {
+            return Err(AppError::Validation("Team name is required".to_string()));
+        }
+        if personnel == 0 {
+            return Err(AppError::Validation("A team needs at least one member".to_string()));
+        }
+
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::new_v4(),
+            station_id: None,
+            name,
+            team_type,
+            location,
+            location_updated_at: now,
+            personnel,
+            vehicles: Vec::new(),
+            availability: TeamAvailability::Available,
+            current_response_id: None,
+            created_at: now,
+            updated_at: now,
+            version: 1,
+            persisted_version: 0,
+        })
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.availability == TeamAvailability::Available
+    }
+
+    /// Whether the team can be sent to a response needing `personnel_needed` people
+    pub fn has_capacity(&self, personnel_needed: u32) -> bool {
+        self.is_available() && self.personnel >= personnel_needed
+    }
+
+    /// Claim the team for a response
+    pub fn dispatch(&mut self, response_id: EmergencyResponseId) -> AppResult<()> {
+        if !self.is_available() {
+            return Err(AppError::ResourceAllocation(format!(
+                "Team {} is not available ({})",
+                self.name, self.availability
+            )));
+        }
+
+        self.availability = TeamAvailability::Dispatched;
+        self.current_response_id = Some(response_id);
+        self.touch();
+        Ok(())
+    }
+
+    /// Return the team to the available pool once its response is over
+    pub fn release(&mut self) {
+        if self.availability == TeamAvailability::Dispatched {
+            self.availability = TeamAvailability::Available;
+        }
+        self.current_response_id = None;
+        self.touch();
+    }
+
+    /// Change availability manually; dispatch state is managed through `dispatch`/`release`
+    pub fn set_availability(&mut self, availability: TeamAvailability) -> AppResult<()> {
+        if availability == self.availability {
+            return Ok(());
+        }
+        if self.availability == TeamAvailability::Dispatched || availability == TeamAvailability::Dispatched {
+            return Err(AppError::BusinessRuleViolation(
+                "Dispatch state changes only through emergency response dispatch".to_string()
+            ));
+        }
+
+        self.availability = availability;
+        self.touch();
+        Ok(())
+    }
+
+    pub fn update_location(&mut self, location: Coordinates) {
+        self.location = location;
+        self.location_updated_at = Utc::now();
+        self.touch();
+    }
+
+    fn touch(&mut self) {
+        self.updated_at = Utc::now();
+        self.version += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn team(personnel: u32) -> ResponseTeam {
+        ResponseTeam::new(
+            "Alpha Rescue".to_string(),
+            TeamType::Rescue,
+            Coordinates::new(-6.2088, 106.8456).unwrap(),
+            personnel,
+        ).unwrap()
+    }
+
+    #[test]
+    fn dispatch_claims_team_until_released() {
+        let mut team = team(8);
+        let response_id = EmergencyResponseId::new();
+
+        assert!(team.has_capacity(6));
+        team.dispatch(response_id).unwrap();
+        assert!(!team.has_capacity(1));
+        assert!(team.dispatch(EmergencyResponseId::new()).is_err());
+        assert!(team.set_availability(TeamAvailability::Available).is_err());
+
+        team.release();
+        assert!(team.is_available());
+        assert!(team.current_response_id.is_none());
+    }
+
+    #[test]
+    fn capacity_requires_enough_personnel() {
+        let team = team(4);
+        assert!(team.has_capacity(4));
+        assert!(!team.has_capacity(5));
+    }
+}
@@ -0,0 +1,206 @@
+/// Role entities
+/// Platform-wide roles stored in the database, the permissions they grant and the audit trail of changes to them
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+use crate::shared::{AppError, AppResult, Permission, UserId};
+
+/// Longest role name the `roles.name` column accepts
+const MAX_ROLE_NAME_LEN: usize = 50;
+
+/// A named set of permissions; built-in roles mirror `UserRole` and cannot be renamed or deleted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Role {
+    /// Assigned by the database; zero until the role is stored
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub permissions: Vec<Permission>,
+    pub is_system: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Role {
+    pub fn new(name: String, description: Option<String>, permissions: Vec<Permission>) -> AppResult<Self> {
+        let now = Utc::now();
+        let mut role = Self {
+            id: 0,
+            name: Self::validate_name(&name)?,
+            description,
+            permissions: Vec::new(),
+            is_system: false,
+            created_at: now,
+            updated_at: now,
+        };
+        role.set_permissions(permissions);
+        Ok(role)
+    }
+
+    fn validate_name(name: &str) -> AppResult<String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(AppError::Validation("Role name is required".to_string()));
+        }
+        if name.chars().count() > MAX_ROLE_NAME_LEN {
+            return Err(AppError::Validation(format!("Role name must be at most {} characters", MAX_ROLE_NAME_LEN)));
+        }
+        Ok(name.to_string())
+    }
+
+    pub fn rename(&mut self, name: &str) -> AppResult<()> {
+        let name = Self::validate_name(name)?;
+        if self.is_system && name != self.name {
+            return Err(AppError::BusinessRuleViolation(format!("Built-in role '{}' cannot be renamed", self.name)));
+        }
+        self.name = name;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Replace the granted permissions, dropping duplicates
+    pub fn set_permissions(&mut self, permissions: Vec<Permission>) {
+        let mut unique = Vec::with_capacity(permissions.len());
+        for permission in permissions {
+            if !unique.contains(&permission) {
+                unique.push(permission);
+            }
+        }
+        self.permissions = unique;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn grants(&self, permission: &Permission) -> bool {
+        self.permissions.contains(permission)
+    }
+
+    pub fn permission_names(&self) -> Vec<&'static str> {
+        self.permissions.iter().map(Permission::as_str).collect()
+    }
+}
+
+/// Parse permission strings such as `reports:write`, rejecting unknown ones
+pub fn parse_permissions(names: &[String]) -> AppResult<Vec<Permission>> {
+    names.iter()
+        .map(|name| Permission::from_str(name.trim())
+            .ok_or_else(|| AppError::Validation(format!("Unknown permission '{}'", name))))
+        .collect()
+}
+
+/// Kind of change recorded in the role audit trail
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RoleAuditAction {
+    Created,
+    Updated,
+    Deleted,
+    Assigned,
+    Revoked,
+}
+
+impl RoleAuditAction {
+    /// Value of the `role_audit_log.action` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Updated => "updated",
+            Self::Deleted => "deleted",
+            Self::Assigned => "assigned",
+            Self::Revoked => "revoked",
+        }
+    }
+}
+
+impl fmt::Display for RoleAuditAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RoleAuditAction {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> AppResult<Self> {
+        match s {
+            "created" => Ok(Self::Created),
+            "updated" => Ok(Self::Updated),
+            "deleted" => Ok(Self::Deleted),
+            "assigned" => Ok(Self::Assigned),
+            "revoked" => Ok(Self::Revoked),
+            other => Err(AppError::Validation(format!("Unknown role audit action '{}'", other))),
+        }
+    }
+}
+
+/// One change to a role or to who holds it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoleAuditEntry {
+    pub id: Uuid,
+    /// `None` once the acting user has been deleted
+    pub actor_id: Option<UserId>,
+    pub action: RoleAuditAction,
+    pub role_id: i32,
+    pub role_name: String,
+    /// User the role was assigned to or revoked from
+    pub target_user_id: Option<UserId>,
+    /// Action-specific data such as the permissions before and after an update
+    pub details: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl RoleAuditEntry {
+    pub fn new(actor_id: UserId, action: RoleAuditAction, role: &Role) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            actor_id: Some(actor_id),
+            action,
+            role_id: role.id,
+            role_name: role.name.clone(),
+            target_user_id: None,
+            details: serde_json::Value::Object(Default::default()),
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn for_user(mut self, user_id: UserId) -> Self {
+        self.target_user_id = Some(user_id);
+        self
+    }
+
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = details;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn permissions_are_deduplicated_and_parsed_strictly() {
+        let permissions = parse_permissions(&["reports:write".to_string(), " area:write ".to_string(), "reports:write".to_string()]).unwrap();
+        let role = Role::new("Logistics Officer".to_string(), None, permissions).unwrap();
+        assert_eq!(role.permission_names(), vec!["reports:write", "area:write"]);
+        assert!(parse_permissions(&["reports:everything".to_string()]).is_err());
+    }
+
+    #[test]
+    fn built_in_roles_keep_their_name() {
+        let mut role = Role::new("coordinator".to_string(), None, Vec::new()).unwrap();
+        role.is_system = true;
+        assert!(role.rename("Field Lead").is_err());
+        assert!(role.rename(" coordinator ").is_ok());
+        assert!(Role::new("  ".to_string(), None, Vec::new()).is_err());
+    }
+
+    #[test]
+    fn every_permission_round_trips_through_its_name() {
+        for permission in Permission::all() {
+            assert_eq!(Permission::from_str(permission.as_str()), Some(permission));
+        }
+    }
+}
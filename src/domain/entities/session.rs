@@ -0,0 +1,25 @@
+/// Session entities
+/// Per-user index of sign-in sessions; live session state stays in the session cache
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::shared::UserId;
+
+#[derive(Debug, Clone)]
+pub struct UserSession {
+    /// Same value as the session id carried in the user's tokens
+    pub id: Uuid,
+    pub user_id: UserId,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub device_fingerprint: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl UserSession {
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none() && self.expires_at > Utc::now()
+    }
+}
@@ -0,0 +1,135 @@
+/// Location tracking entities
+/// GPS fixes sent by responders' devices, stored as a time series in `volunteer_locations`.
+/// Devices queue fixes while offline and upload them later, so a fix carries the time it was
+/// taken on the device as well as the time the server received it.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::domain::value_objects::Coordinates;
+use crate::shared::{AppError, AppResult, UserId, VolunteerId};
+
+/// How far ahead of the server clock a device clock may run
+const MAX_CLOCK_SKEW_MINUTES: i64 = 5;
+/// Oldest fix a device may still upload after being offline
+const MAX_FIX_AGE_DAYS: i64 = 7;
+/// Fixes less accurate than this are useless for tracking anyone
+const MAX_ACCURACY_M: f64 = 10_000.0;
+/// Faster than any vehicle responders use; anything above is a bad reading
+const MAX_SPEED_MPS: f64 = 150.0;
+
+/// One position as taken by a device
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocationSample {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Horizontal accuracy radius in meters
+    pub accuracy_m: Option<f64>,
+    pub speed_mps: Option<f64>,
+    /// Direction of travel, degrees clockwise from north
+    pub heading_deg: Option<f64>,
+    /// Device time the fix was taken
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Who a batch of fixes belongs to and what work it was recorded for
+#[derive(Debug, Clone)]
+pub struct FixSource {
+    pub user_id: UserId,
+    pub volunteer_id: Option<VolunteerId>,
+    pub team_id: Option<Uuid>,
+    pub assignment_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocationFix {
+    pub id: Uuid,
+    pub user_id: UserId,
+    pub volunteer_id: Option<VolunteerId>,
+    pub team_id: Option<Uuid>,
+    pub assignment_id: Option<Uuid>,
+    pub position: Coordinates,
+    pub accuracy_m: Option<f64>,
+    pub speed_mps: Option<f64>,
+    pub heading_deg: Option<f64>,
+    pub recorded_at: DateTime<Utc>,
+    pub received_at: DateTime<Utc>,
+}
+
+fn check_optional(what: &str, value: Option<f64>, range: std::ops::RangeInclusive<f64>) -> AppResult<()> {
+    match value {
+        Some(v) if !v.is_finite() || !range.contains(&v) => Err(AppError::Validation(format!(
+            "{} must be between {} and {}",
+            what, range.start(), range.end()
+        ))),
+        _ => Ok(()),
+    }
+}
+
+impl LocationFix {
+    /// Validate a device sample received at `received_at` and attribute it to `source`
+    pub fn from_sample(source: &FixSource, sample: LocationSample, received_at: DateTime<Utc>) -> AppResult<Self> {
+        if !sample.latitude.is_finite() || !sample.longitude.is_finite() {
+            return Err(AppError::Validation("Latitude and longitude must be numbers".to_string()));
+        }
+        let position = Coordinates::new(sample.latitude, sample.longitude)
+            .map_err(|e| AppError::Validation(e.to_string()))?;
+        check_optional("Accuracy", sample.accuracy_m, 0.0..=MAX_ACCURACY_M)?;
+        check_optional("Speed", sample.speed_mps, 0.0..=MAX_SPEED_MPS)?;
+        check_optional("Heading", sample.heading_deg, 0.0..=360.0)?;
+
+        if sample.recorded_at > received_at + Duration::minutes(MAX_CLOCK_SKEW_MINUTES) {
+            return Err(AppError::Validation("Fix is timestamped in the future".to_string()));
+        }
+        if sample.recorded_at < received_at - Duration::days(MAX_FIX_AGE_DAYS) {
+            return Err(AppError::Validation(format!("Fix is older than {} days", MAX_FIX_AGE_DAYS)));
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            user_id: source.user_id,
+            volunteer_id: source.volunteer_id,
+            team_id: source.team_id,
+            assignment_id: source.assignment_id,
+            position,
+            accuracy_m: sample.accuracy_m,
+            speed_mps: sample.speed_mps,
+            heading_deg: sample.heading_deg.map(|h| h % 360.0),
+            recorded_at: sample.recorded_at,
+            received_at,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(recorded_at: DateTime<Utc>) -> LocationSample {
+        LocationSample {
+            latitude: -6.2,
+            longitude: 106.8,
+            accuracy_m: Some(12.0),
+            speed_mps: Some(3.5),
+            heading_deg: Some(360.0),
+            recorded_at,
+        }
+    }
+
+    #[test]
+    fn samples_are_validated_against_ranges_and_clock() {
+        let now = Utc::now();
+        let source = FixSource { user_id: UserId::new(), volunteer_id: None, team_id: None, assignment_id: None };
+
+        let fix = LocationFix::from_sample(&source, sample(now - Duration::days(2)), now).unwrap();
+        assert_eq!(fix.user_id, source.user_id);
+        assert_eq!(fix.heading_deg, Some(0.0));
+
+        assert!(LocationFix::from_sample(&source, sample(now + Duration::minutes(2)), now).is_ok());
+        assert!(LocationFix::from_sample(&source, sample(now + Duration::minutes(30)), now).is_err());
+        assert!(LocationFix::from_sample(&source, sample(now - Duration::days(8)), now).is_err());
+        assert!(LocationFix::from_sample(&source, LocationSample { latitude: 91.0, ..sample(now) }, now).is_err());
+        assert!(LocationFix::from_sample(&source, LocationSample { accuracy_m: Some(-1.0), ..sample(now) }, now).is_err());
+        assert!(LocationFix::from_sample(&source, LocationSample { speed_mps: Some(f64::NAN), ..sample(now) }, now).is_err());
+    }
+}
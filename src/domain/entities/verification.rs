@@ -0,0 +1,67 @@
+/// Verification code entities
+/// Single-use, expiring codes for email verification and password resets
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use uuid::Uuid;
+use crate::shared::{AppError, AppResult, UserId};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationPurpose {
+    EmailVerification,
+    PhoneVerification,
+    PasswordReset,
+}
+
+impl VerificationPurpose {
+    /// Value of the `verification_codes.type` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VerificationPurpose::EmailVerification => "email",
+            VerificationPurpose::PhoneVerification => "phone",
+            VerificationPurpose::PasswordReset => "password_reset",
+        }
+    }
+}
+
+impl FromStr for VerificationPurpose {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> AppResult<Self> {
+        match s {
+            "email" => Ok(VerificationPurpose::EmailVerification),
+            "phone" => Ok(VerificationPurpose::PhoneVerification),
+            "password_reset" => Ok(VerificationPurpose::PasswordReset),
+            other => Err(AppError::Validation(format!("Unknown verification purpose '{}'", other))),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VerificationCode {
+    pub id: Uuid,
+    pub user_id: UserId,
+    pub purpose: VerificationPurpose,
+    /// SHA-256 of the token sent to the user; the token itself is never stored
+    pub code_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub is_used: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl VerificationCode {
+    pub fn new(user_id: UserId, purpose: VerificationPurpose, code_hash: String, ttl: Duration) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            purpose,
+            code_hash,
+            expires_at: now + ttl,
+            is_used: false,
+            created_at: now,
+        }
+    }
+}
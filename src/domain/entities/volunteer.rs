@@ -0,0 +1,328 @@
+/// Volunteer domain entity
+/// A user's volunteer profile (skills, certifications, availability) and the assignments
+/// coordinators or volunteer matching hand them, tracked from assignment through arrival to completion
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+use crate::shared::{AppError, AppResult, DisasterId, LocationId, ReportId, UserId, VolunteerId};
+
+const MAX_TAGS: usize = 30;
+const MAX_TAG_LEN: usize = 100;
+const MAX_NOTES_LEN: usize = 1000;
+
+/// Trim, drop empty entries and case-insensitive duplicates, keeping the first spelling
+fn normalize_tags(what: &str, tags: Vec<String>) -> AppResult<Vec<String>> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = tag.trim().to_string();
+        if tag.is_empty() || normalized.iter().any(|t| t.eq_ignore_ascii_case(&tag)) {
+            continue;
+        }
+        if tag.chars().count() > MAX_TAG_LEN {
+            return Err(AppError::Validation(format!("Each of the {} must be at most {} characters", what, MAX_TAG_LEN)));
+        }
+        normalized.push(tag);
+    }
+    if normalized.len() > MAX_TAGS {
+        return Err(AppError::Validation(format!("At most {} {} can be listed", MAX_TAGS, what)));
+    }
+    Ok(normalized)
+}
+
+fn normalize_notes(notes: Option<String>) -> AppResult<Option<String>> {
+    let notes = notes.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    if notes.as_ref().is_some_and(|n| n.chars().count() > MAX_NOTES_LEN) {
+        return Err(AppError::Validation(format!("Notes must be at most {} characters", MAX_NOTES_LEN)));
+    }
+    Ok(notes)
+}
+
+/// What a volunteer can do; editable by the volunteer at any time
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct VolunteerProfile {
+    pub skills: Vec<String>,
+    pub certifications: Vec<String>,
+    pub experience_years: Option<u32>,
+    pub specialization: Option<String>,
+}
+
+impl VolunteerProfile {
+    fn validate(self) -> AppResult<Self> {
+        if self.experience_years.is_some_and(|years| years > 80) {
+            return Err(AppError::Validation("Experience must be at most 80 years".to_string()));
+        }
+        let specialization = self.specialization
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty());
+        if specialization.as_ref().is_some_and(|s| s.chars().count() > MAX_TAG_LEN) {
+            return Err(AppError::Validation(format!("Specialization must be at most {} characters", MAX_TAG_LEN)));
+        }
+        Ok(Self {
+            skills: normalize_tags("skills", self.skills)?,
+            certifications: normalize_tags("certifications", self.certifications)?,
+            experience_years: self.experience_years,
+            specialization,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Volunteer {
+    pub id: VolunteerId,
+    pub user_id: UserId,
+    pub profile: VolunteerProfile,
+    pub is_available: bool,
+    pub availability_notes: Option<String>,
+    pub current_location_id: Option<LocationId>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Volunteer {
+    /// A user signing up to volunteer; new volunteers are available straight away
+    pub fn register(user_id: UserId, profile: VolunteerProfile) -> AppResult<Self> {
+        let now = Utc::now();
+        Ok(Self {
+            id: VolunteerId::new(),
+            user_id,
+            profile: profile.validate()?,
+            is_available: true,
+            availability_notes: None,
+            current_location_id: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub fn update_profile(&mut self, profile: VolunteerProfile) -> AppResult<()> {
+        self.profile = profile.validate()?;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn set_availability(&mut self, is_available: bool, notes: Option<String>) -> AppResult<()> {
+        self.availability_notes = normalize_notes(notes)?;
+        self.is_available = is_available;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+}
+
+/// Lifecycle of an assignment; stored in `volunteer_tracking.status`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AssignmentStatus {
+    /// Handed out by a coordinator, waiting for the volunteer to respond
+    Assigned,
+    Accepted,
+    Declined,
+    Arrived,
+    Completed,
+    /// Withdrawn by a coordinator before completion
+    Cancelled,
+}
+
+impl AssignmentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Assigned => "assigned",
+            Self::Accepted => "accepted",
+            Self::Declined => "declined",
+            Self::Arrived => "arrived",
+            Self::Completed => "completed",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    /// Assignments still holding the volunteer
+    pub fn is_active(&self) -> bool {
+        matches!(self, Self::Assigned | Self::Accepted | Self::Arrived)
+    }
+
+    pub fn can_transition_to(&self, next: AssignmentStatus) -> bool {
+        use AssignmentStatus::*;
+        matches!(
+            (self, next),
+            (Assigned, Accepted | Declined | Cancelled)
+                | (Accepted, Arrived | Cancelled)
+                | (Arrived, Completed | Cancelled)
+        )
+    }
+}
+
+impl FromStr for AssignmentStatus {
+    type Err = AppError;
+
+    /// Also accepts the legacy `en_route` and `on_site` values
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "assigned" => Ok(Self::Assigned),
+            "accepted" | "en_route" => Ok(Self::Accepted),
+            "declined" => Ok(Self::Declined),
+            "arrived" | "on_site" => Ok(Self::Arrived),
+            "completed" => Ok(Self::Completed),
+            "cancelled" => Ok(Self::Cancelled),
+            other => Err(AppError::Validation(format!(
+                "Invalid assignment status '{}'. Must be one of: assigned, accepted, declined, arrived, completed, cancelled",
+                other
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for AssignmentStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A volunteer sent to the incident a report describes, or to a disaster as a whole;
+/// one row of `volunteer_tracking`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolunteerAssignment {
+    pub id: Uuid,
+    pub volunteer_id: VolunteerId,
+    pub report_id: Option<ReportId>,
+    /// Disaster the volunteer supports; set for matched assignments and for reports linked to one
+    pub disaster_id: Option<DisasterId>,
+    pub status: AssignmentStatus,
+    /// Coordinator who made the assignment
+    pub assigned_by: Option<UserId>,
+    pub notes: Option<String>,
+    pub assigned_at: DateTime<Utc>,
+    /// When the volunteer accepted or declined
+    pub responded_at: Option<DateTime<Utc>>,
+    pub arrived_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl VolunteerAssignment {
+    /// Only available volunteers can be assigned
+    pub fn assign(volunteer: &Volunteer, report_id: ReportId, assigned_by: UserId, notes: Option<String>) -> AppResult<Self> {
+        Self::new(volunteer, Some(report_id), None, assigned_by, notes)
+    }
+
+    /// Send an available volunteer to a disaster without picking a report first
+    pub fn assign_to_disaster(volunteer: &Volunteer, disaster_id: DisasterId, assigned_by: UserId, notes: Option<String>) -> AppResult<Self> {
+        Self::new(volunteer, None, Some(disaster_id), assigned_by, notes)
+    }
+
+    fn new(
+        volunteer: &Volunteer,
+        report_id: Option<ReportId>,
+        disaster_id: Option<DisasterId>,
+        assigned_by: UserId,
+        notes: Option<String>,
+    ) -> AppResult<Self> {
+        if !volunteer.is_available {
+            return Err(AppError::BusinessRuleViolation(format!("Volunteer {} is not available", volunteer.id)));
+        }
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::new_v4(),
+            volunteer_id: volunteer.id,
+            report_id,
+            disaster_id,
+            status: AssignmentStatus::Assigned,
+            assigned_by: Some(assigned_by),
+            notes: normalize_notes(notes)?,
+            assigned_at: now,
+            responded_at: None,
+            arrived_at: None,
+            completed_at: None,
+            updated_at: now,
+        })
+    }
+
+    /// Move the assignment to `next`, stamping the matching timestamp; notes replace earlier ones
+    pub fn transition(&mut self, next: AssignmentStatus, notes: Option<String>) -> AppResult<()> {
+        if !self.status.can_transition_to(next) {
+            return Err(AppError::BusinessRuleViolation(format!(
+                "Assignment {} cannot move from {} to {}",
+                self.id, self.status, next
+            )));
+        }
+
+        let now = Utc::now();
+        match next {
+            AssignmentStatus::Accepted | AssignmentStatus::Declined => self.responded_at = Some(now),
+            AssignmentStatus::Arrived => self.arrived_at = Some(now),
+            AssignmentStatus::Completed => self.completed_at = Some(now),
+            AssignmentStatus::Assigned | AssignmentStatus::Cancelled => {}
+        }
+        if let Some(notes) = normalize_notes(notes)? {
+            self.notes = Some(notes);
+        }
+        self.status = next;
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Mark an accepted assignment arrived as of `at`, e.g. the device time of the GPS fix that
+    /// reached the target; never earlier than the volunteer accepted
+    pub fn arrive_at(&mut self, at: DateTime<Utc>) -> AppResult<()> {
+        self.transition(AssignmentStatus::Arrived, None)?;
+        self.arrived_at = Some(at.max(self.responded_at.unwrap_or(self.assigned_at)));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn volunteer() -> Volunteer {
+        Volunteer::register(UserId::new(), VolunteerProfile {
+            skills: vec![" First Aid ".to_string(), "first aid".to_string(), "".to_string(), "Evacuation".to_string()],
+            certifications: vec!["PMI First Aid".to_string()],
+            experience_years: Some(5),
+            specialization: Some("  ".to_string()),
+        }).unwrap()
+    }
+
+    #[test]
+    fn registration_normalizes_skills_and_certifications() {
+        let volunteer = volunteer();
+        assert_eq!(volunteer.profile.skills, vec!["First Aid", "Evacuation"]);
+        assert_eq!(volunteer.profile.specialization, None);
+        assert!(volunteer.is_available);
+
+        let too_experienced = VolunteerProfile { experience_years: Some(120), ..VolunteerProfile::default() };
+        assert!(Volunteer::register(UserId::new(), too_experienced).is_err());
+    }
+
+    #[test]
+    fn assignment_moves_through_arrival_to_completion() {
+        let mut volunteer = volunteer();
+        let mut assignment = VolunteerAssignment::assign(&volunteer, ReportId::new(), UserId::new(), None).unwrap();
+        assert!(assignment.transition(AssignmentStatus::Arrived, None).is_err());
+
+        assignment.transition(AssignmentStatus::Accepted, None).unwrap();
+        assignment.transition(AssignmentStatus::Arrived, Some("On site".to_string())).unwrap();
+        assignment.transition(AssignmentStatus::Completed, None).unwrap();
+        assert!(assignment.responded_at.is_some() && assignment.arrived_at.is_some() && assignment.completed_at.is_some());
+        assert_eq!(assignment.notes.as_deref(), Some("On site"));
+        assert!(!assignment.status.is_active());
+        assert!(assignment.transition(AssignmentStatus::Cancelled, None).is_err());
+
+        volunteer.set_availability(false, Some("Off shift".to_string())).unwrap();
+        assert!(VolunteerAssignment::assign(&volunteer, ReportId::new(), UserId::new(), None).is_err());
+    }
+
+    #[test]
+    fn tracked_arrival_keeps_the_fix_time_but_not_before_acceptance() {
+        let volunteer = volunteer();
+        let mut assignment = VolunteerAssignment::assign(&volunteer, ReportId::new(), UserId::new(), None).unwrap();
+        assert!(assignment.arrive_at(Utc::now()).is_err());
+
+        assignment.transition(AssignmentStatus::Accepted, None).unwrap();
+        let accepted_at = assignment.responded_at.unwrap();
+        assignment.arrive_at(accepted_at - chrono::Duration::minutes(10)).unwrap();
+        assert_eq!(assignment.status, AssignmentStatus::Arrived);
+        assert_eq!(assignment.arrived_at, Some(accepted_at));
+    }
+}
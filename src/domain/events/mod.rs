@@ -11,9 +11,11 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use crate::domain::value_objects::*;
 use crate::domain::entities::disaster::DisasterSeverity;
+use crate::domain::ports::events as port_events;
+use crate::shared::events::EventPayload;
 
 /// Base trait for all domain events
-pub trait DomainEvent: Send + Sync + std::fmt::Debug {
+pub trait DomainEvent: EventPayload + Send + Sync + std::fmt::Debug {
     fn event_id(&self) -> Uuid;
     fn event_type(&self) -> &'static str;
     fn occurred_at(&self) -> DateTime<Utc>;
@@ -160,7 +162,10 @@ pub struct MassNotificationTriggeredEvent {
     pub event_id: Uuid,
     pub disaster_id: DisasterId,
     pub triggered_by: UserId,
+    /// Radius of the target circle, or of a circle with the same area for polygon targets
     pub affected_area_radius_km: f64,
+    #[serde(default)]
+    pub affected_zone_id: Option<Uuid>,
     pub notification_type: String,
     pub estimated_recipients: u32,
     pub occurred_at: DateTime<Utc>,
@@ -195,6 +200,23 @@ impl DomainEvent for LocationUpdatedEvent {
     fn version(&self) -> u64 { self.version }
 }
 
+/// Port-level events, so they can be persisted in and replayed from the event store
+impl DomainEvent for port_events::DisasterStatusChangedEvent {
+    fn event_id(&self) -> Uuid { self.event_id }
+    fn event_type(&self) -> &'static str { "disaster.status_changed" }
+    fn occurred_at(&self) -> DateTime<Utc> { self.occurred_at }
+    fn aggregate_id(&self) -> Uuid { self.disaster_id.value() }
+    fn version(&self) -> u64 { self.version as u64 }
+}
+
+impl DomainEvent for port_events::EmergencyAlertTriggeredEvent {
+    fn event_id(&self) -> Uuid { self.event_id }
+    fn event_type(&self) -> &'static str { "emergency.alert_triggered" }
+    fn occurred_at(&self) -> DateTime<Utc> { self.occurred_at }
+    fn aggregate_id(&self) -> Uuid { self.disaster_id.value() }
+    fn version(&self) -> u64 { self.version as u64 }
+}
+
 /// Aggregate root trait for entities that can publish events
 pub trait AggregateRoot {
     fn uncommitted_events(&self) -> &Vec<Box<dyn DomainEvent>>;
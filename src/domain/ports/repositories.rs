@@ -3,12 +3,25 @@
 
 use async_trait::async_trait;
 use crate::AppError;
-use crate::shared::{AppResult, UserId, DisasterId, LocationId, NotificationId, PaginationParams, PaginatedResponse};
-use crate::domain::entities::notification::{Notification, NotificationStatus, NotificationChannel};
-use crate::domain::entities::disaster::Disaster;
+use crate::shared::{AppResult, UserId, DisasterId, LocationId, ReportId, NotificationId, EmergencyResponseId, OrganizationId, ResourceId, VolunteerId, PaginationParams, PaginatedResponse};
+use crate::domain::entities::notification::{DeliveryAttempt, Notification, NotificationStatus, NotificationChannel, OutboxMessage};
+use crate::domain::entities::disaster::{Disaster, DisasterZone};
 use crate::domain::entities::user::User;
-use crate::domain::entities::location::Location;
-use crate::domain::value_objects::{Coordinates, Email};
+use crate::domain::entities::location::{Location, LocationSearch, LocationType};
+use crate::domain::entities::emergency_response::{EmergencyResource, EmergencyResponse, ResourceStock};
+use crate::domain::entities::mfa::MfaEnrollment;
+use crate::domain::entities::verification::{VerificationCode, VerificationPurpose};
+use crate::domain::entities::session::UserSession;
+use crate::domain::entities::organization::{Organization, OrganizationMembership};
+use crate::domain::entities::role::{Role, RoleAuditEntry};
+use crate::domain::entities::report::{Report, ReportComment, ReportMedia, ReportStatus, ReportStatusChange};
+use crate::domain::services::credibility::{NearbyReport, ReporterHistory, TriageCluster};
+use crate::domain::services::volunteer_matching::VolunteerCandidate;
+use crate::domain::entities::volunteer::{Volunteer, VolunteerAssignment};
+use crate::domain::entities::tracking::LocationFix;
+use crate::domain::entities::analytics::{AnalyticsFilter, DashboardSummary, DisasterFact, DisasterTrends, GeographicAnalytics, ResponseTimeAnalytics};
+use crate::domain::entities::response_team::{EmergencyStation, ResponseTeam, TeamAvailability, TeamType};
+use crate::domain::value_objects::{Coordinates, Email, GeoArea};
 
 // Base repository trait with common CRUD operations
 #[async_trait]
@@ -34,7 +47,7 @@ pub trait DisasterRepository: Send + Sync {
     async fn find_by_reporter(&self, reporter_id: &UserId) -> AppResult<Vec<Disaster>>;
     async fn find_nearby(&self, lat: f64, lng: f64, radius_km: f64) -> AppResult<Vec<Disaster>>;
     async fn find_active(&self) -> AppResult<Vec<Disaster>>;
-    async fn update_status(&self, id: &DisasterId, status: crate::domain::entities::disaster::DisasterStatus) -> AppResult<bool>;
+    async fn update_status(&self, id: &DisasterId, status: crate::domain::entities::disaster::DisasterStatus, updated_by: &UserId) -> AppResult<bool>;
     async fn assign_responder(&self, disaster_id: &DisasterId, responder_id: &UserId) -> AppResult<bool>;
     async fn find_by_location(&self, location_id: &LocationId) -> AppResult<Vec<Disaster>>;
 }
@@ -57,7 +70,13 @@ pub trait UserRepository: Send + Sync {
     async fn update_password(&self, uid: &UserId, password_hash: &str) -> AppResult<bool>;
     async fn count_by_role(&self, role: &crate::domain::value_objects::UserRole) -> AppResult<u64>;
     async fn find_users_in_radius(&self, center: &Coordinates, radius_km: f64) -> AppResult<Vec<User>>;
+    /// Active users whose last known location falls inside the area
+    async fn find_users_in_area(&self, area: &GeoArea) -> AppResult<Vec<User>>;
     async fn count_by_status(&self, status: &str) -> AppResult<u64>;
+    /// Record where a user's home address lies, replacing any earlier position
+    async fn save_address_geocode(&self, uid: &UserId, address: &str, position: &Coordinates) -> AppResult<()>;
+    /// Forget a user's home address position, e.g. after the address changed to one that cannot be placed
+    async fn clear_address_geocode(&self, uid: &UserId) -> AppResult<()>;
 }
 
 // Notification repository interface  
@@ -82,6 +101,24 @@ pub trait NotificationRepository: Send + Sync {
     async fn find_by_channel(&self, channel: NotificationChannel) -> AppResult<Vec<Notification>>;
 }
 
+// Notification outbox interface, drained by the delivery workers
+#[async_trait]
+pub trait NotificationOutboxRepository: Send + Sync {
+    /// Store the notification and one outbox message per channel in a single transaction
+    async fn enqueue(&self, notification: &Notification) -> AppResult<Vec<OutboxMessage>>;
+    /// Enqueue several notifications in one transaction; none are queued if any fails
+    async fn enqueue_batch(&self, notifications: &[Notification]) -> AppResult<Vec<OutboxMessage>>;
+    /// Lease due messages to a worker, reclaiming messages whose lease has expired
+    async fn claim_due(&self, worker_id: &str, limit: u32, lease: std::time::Duration) -> AppResult<Vec<OutboxMessage>>;
+    /// Persist an attempt and the message state it produced; false when the lease was lost
+    async fn record_attempt(&self, worker_id: &str, message: &OutboxMessage, attempt: &DeliveryAttempt) -> AppResult<bool>;
+    async fn dead_letter(&self, worker_id: &str, message: &OutboxMessage) -> AppResult<bool>;
+    async fn find_by_id(&self, id: &uuid::Uuid) -> AppResult<Option<OutboxMessage>>;
+    async fn find_dead_lettered(&self, limit: u32, offset: u32) -> AppResult<Vec<OutboxMessage>>;
+    async fn find_attempts(&self, id: &uuid::Uuid) -> AppResult<Vec<DeliveryAttempt>>;
+    async fn requeue(&self, message: &OutboxMessage) -> AppResult<bool>;
+}
+
 #[async_trait]
 pub trait LocationRepository: Send + Sync {
     async fn find_by_id(&self, id: &LocationId) -> AppResult<Option<crate::domain::entities::location::Location>>;
@@ -98,4 +135,267 @@ pub trait LocationRepository: Send + Sync {
     async fn find_nearby(&self, lat: f64, lng: f64, radius_km: f64) -> AppResult<Vec<Location>>;
     async fn save_location(&self, location: &Location) -> AppResult<Location>;
     async fn delete_location(&self, id: LocationId) -> AppResult<bool>;
-}
\ No newline at end of file
+    async fn find_by_type(&self, location_type: &LocationType) -> AppResult<Vec<Location>>;
+    async fn list_provinces(&self) -> AppResult<Vec<String>>;
+    async fn list_cities(&self, province: &str) -> AppResult<Vec<String>>;
+    /// One page of matching locations and the number of matches across all pages
+    async fn search(&self, criteria: &LocationSearch, limit: i64, offset: i64) -> AppResult<(Vec<Location>, u64)>;
+}
+
+// Emergency response repository interface
+#[async_trait]
+pub trait EmergencyResponseRepository: Send + Sync {
+    async fn find_by_id(&self, id: &EmergencyResponseId) -> AppResult<Option<EmergencyResponse>>;
+    async fn save(&self, entity: &EmergencyResponse) -> AppResult<EmergencyResponse>;
+    async fn update(&self, entity: &EmergencyResponse) -> AppResult<EmergencyResponse>;
+    async fn find_by_disaster(&self, disaster_id: &DisasterId) -> AppResult<Vec<EmergencyResponse>>;
+    async fn find_active(&self) -> AppResult<Vec<EmergencyResponse>>;
+}
+
+// Resource inventory interface (`emergency_resources`)
+#[async_trait]
+pub trait ResourceInventoryRepository: Send + Sync {
+    async fn available_stock(&self) -> AppResult<Vec<ResourceStock>>;
+    async fn find_by_id(&self, id: &ResourceId) -> AppResult<Option<EmergencyResource>>;
+    async fn find_by_organization(&self, organization_id: &OrganizationId) -> AppResult<Vec<EmergencyResource>>;
+    async fn save(&self, entity: &EmergencyResource) -> AppResult<EmergencyResource>;
+    async fn update(&self, entity: &EmergencyResource) -> AppResult<EmergencyResource>;
+    async fn delete(&self, id: &ResourceId) -> AppResult<bool>;
+}
+
+// Response team registry interface
+#[async_trait]
+pub trait ResponseTeamRepository: Send + Sync {
+    async fn find_by_id(&self, id: &uuid::Uuid) -> AppResult<Option<ResponseTeam>>;
+    async fn save(&self, entity: &ResponseTeam) -> AppResult<ResponseTeam>;
+    async fn update(&self, entity: &ResponseTeam) -> AppResult<ResponseTeam>;
+    async fn delete(&self, id: &uuid::Uuid) -> AppResult<bool>;
+    async fn find_all(&self) -> AppResult<Vec<ResponseTeam>>;
+
+    async fn find_by_filter(&self, team_type: Option<TeamType>, availability: Option<TeamAvailability>) -> AppResult<Vec<ResponseTeam>>;
+    async fn find_by_response(&self, response_id: &EmergencyResponseId) -> AppResult<Vec<ResponseTeam>>;
+}
+
+// Emergency station registry interface
+#[async_trait]
+pub trait EmergencyStationRepository: Send + Sync {
+    async fn find_by_id(&self, id: &uuid::Uuid) -> AppResult<Option<EmergencyStation>>;
+    async fn save(&self, entity: &EmergencyStation) -> AppResult<EmergencyStation>;
+    async fn update(&self, entity: &EmergencyStation) -> AppResult<EmergencyStation>;
+    async fn delete(&self, id: &uuid::Uuid) -> AppResult<bool>;
+    async fn find_all(&self) -> AppResult<Vec<EmergencyStation>>;
+}
+
+// Disaster zone interface, used to close roads inside active hazard areas
+#[async_trait]
+pub trait DisasterZoneRepository: Send + Sync {
+    async fn find_by_id(&self, id: &uuid::Uuid) -> AppResult<Option<DisasterZone>>;
+    /// Exterior rings of non-safe zones that belong to disasters still in progress
+    async fn find_active_closure_areas(&self) -> AppResult<Vec<Vec<Coordinates>>>;
+}
+
+// MFA enrollment interface; recovery codes are only ever stored hashed
+#[async_trait]
+pub trait MfaRepository: Send + Sync {
+    async fn find_by_user(&self, user_id: &UserId) -> AppResult<Option<MfaEnrollment>>;
+    /// Store or replace an unconfirmed enrollment; conflicts if MFA is already active
+    async fn save_pending(&self, enrollment: &MfaEnrollment) -> AppResult<()>;
+    /// Activate a pending enrollment and install its recovery codes
+    async fn confirm(&self, user_id: &UserId, step: i64, recovery_code_hashes: &[String]) -> AppResult<bool>;
+    /// Accept a time step only if it is newer than the last one used
+    async fn record_step(&self, user_id: &UserId, step: i64) -> AppResult<bool>;
+    async fn consume_recovery_code(&self, user_id: &UserId, code_hash: &str) -> AppResult<bool>;
+    async fn replace_recovery_codes(&self, user_id: &UserId, code_hashes: &[String]) -> AppResult<()>;
+    async fn count_unused_recovery_codes(&self, user_id: &UserId) -> AppResult<u64>;
+    async fn delete(&self, user_id: &UserId) -> AppResult<bool>;
+}
+
+// Verification code interface; codes are looked up by hash and consumed atomically
+#[async_trait]
+pub trait VerificationCodeRepository: Send + Sync {
+    /// Store a new code, invalidating the user's outstanding codes for the same purpose
+    async fn issue(&self, code: &VerificationCode) -> AppResult<()>;
+    /// Mark an unexpired, unused code as used and return its owner
+    async fn consume(&self, purpose: VerificationPurpose, code_hash: &str) -> AppResult<Option<UserId>>;
+    async fn count_issued_since(&self, user_id: &UserId, purpose: VerificationPurpose, since: chrono::DateTime<chrono::Utc>) -> AppResult<u64>;
+}
+
+// Session index interface; lets sessions be enumerated per user without scanning the cache
+#[async_trait]
+pub trait SessionRepository: Send + Sync {
+    async fn record(&self, session: &UserSession) -> AppResult<()>;
+    async fn find_by_id(&self, id: &uuid::Uuid) -> AppResult<Option<UserSession>>;
+    /// Unrevoked, unexpired sessions of a user, newest first
+    async fn find_active_by_user(&self, user_id: &UserId) -> AppResult<Vec<UserSession>>;
+    async fn revoke(&self, id: &uuid::Uuid) -> AppResult<bool>;
+    /// Revoke every active session of a user except `keep`; returns the revoked ids
+    async fn revoke_all_for_user(&self, user_id: &UserId, keep: Option<&uuid::Uuid>) -> AppResult<Vec<uuid::Uuid>>;
+}
+
+// Organization interface; memberships carry the role a user holds inside each organization
+#[async_trait]
+pub trait OrganizationRepository: Send + Sync {
+    /// Create an organization with `founder` as its first admin
+    async fn create(&self, organization: &Organization, founder: &UserId) -> AppResult<Organization>;
+    async fn find_by_id(&self, id: &OrganizationId) -> AppResult<Option<Organization>>;
+    async fn find_all(&self) -> AppResult<Vec<Organization>>;
+    async fn update(&self, organization: &Organization) -> AppResult<Organization>;
+    async fn delete(&self, id: &OrganizationId) -> AppResult<bool>;
+
+    async fn find_membership(&self, organization_id: &OrganizationId, user_id: &UserId) -> AppResult<Option<OrganizationMembership>>;
+    async fn find_memberships_by_user(&self, user_id: &UserId) -> AppResult<Vec<OrganizationMembership>>;
+    async fn find_members(&self, organization_id: &OrganizationId) -> AppResult<Vec<OrganizationMembership>>;
+    /// Add a member or change their role; refuses to demote the last admin
+    async fn save_membership(&self, membership: &OrganizationMembership) -> AppResult<OrganizationMembership>;
+    /// Remove a member; refuses to remove the last admin
+    async fn remove_member(&self, organization_id: &OrganizationId, user_id: &UserId) -> AppResult<bool>;
+}
+
+// Province assignment interface; scopes coordinators to the provinces they work in
+#[async_trait]
+pub trait ProvinceAssignmentRepository: Send + Sync {
+    async fn find_by_user(&self, user_id: &UserId) -> AppResult<Vec<String>>;
+    /// Replace the user's assignments with `provinces`
+    async fn replace(&self, user_id: &UserId, provinces: &[String], assigned_by: &UserId) -> AppResult<()>;
+}
+
+// CAP import ledger; one row per (sender, identifier), so each external alert is ingested once
+#[async_trait]
+pub trait CapAlertImportRepository: Send + Sync {
+    /// Reserve an alert for import; `false` if it was imported (or is being imported) already
+    async fn claim(&self, sender: &str, identifier: &str, msg_type: &str, imported_by: &UserId) -> AppResult<bool>;
+    /// Link a claimed alert to the disaster it created, updated or closed
+    async fn attach_disaster(&self, sender: &str, identifier: &str, disaster_id: &DisasterId) -> AppResult<()>;
+    /// Drop a claim whose import failed so the alert can be retried
+    async fn release(&self, sender: &str, identifier: &str) -> AppResult<()>;
+    /// Disaster linked to the most recently imported of the referenced `(sender, identifier)` alerts
+    async fn find_disaster(&self, references: &[(String, String)]) -> AppResult<Option<DisasterId>>;
+}
+
+// Role interface; built-in and custom roles, their permissions and who holds them
+// Every change is written to the audit trail in the same transaction
+#[async_trait]
+pub trait RoleRepository: Send + Sync {
+    async fn find_all(&self) -> AppResult<Vec<Role>>;
+    async fn find_by_id(&self, id: i32) -> AppResult<Option<Role>>;
+    async fn find_by_name(&self, name: &str) -> AppResult<Option<Role>>;
+    async fn create(&self, role: &Role, actor: &UserId) -> AppResult<Role>;
+    /// Save name, description and permissions
+    async fn update(&self, role: &Role, actor: &UserId) -> AppResult<Role>;
+    /// Delete a custom role and its assignments; built-in roles are refused
+    async fn delete(&self, id: i32, actor: &UserId) -> AppResult<bool>;
+
+    /// Roles assigned to a user through `user_roles`
+    async fn find_by_user(&self, user_id: &UserId) -> AppResult<Vec<Role>>;
+    /// Returns false when the user already holds the role
+    async fn assign(&self, user_id: &UserId, role_id: i32, actor: &UserId) -> AppResult<bool>;
+    async fn revoke(&self, user_id: &UserId, role_id: i32, actor: &UserId) -> AppResult<bool>;
+    /// Users holding the role, through `user_roles` or as their built-in role
+    async fn find_holders(&self, role_id: i32) -> AppResult<Vec<UserId>>;
+
+    /// Audit trail, newest first, optionally for one role
+    async fn find_audit(&self, role_id: Option<i32>, limit: i64, offset: i64) -> AppResult<Vec<RoleAuditEntry>>;
+}
+
+// Report interface; citizen reports with their media, comment threads and status history
+// Status changes are stored together with their history entry
+#[async_trait]
+pub trait ReportRepository: Send + Sync {
+    /// Store a new report, its location and the submission history entry
+    async fn create(&self, report: &Report, submitted: &ReportStatusChange) -> AppResult<Report>;
+    async fn find_by_id(&self, id: &ReportId) -> AppResult<Option<Report>>;
+    /// Newest first, optionally narrowed to one status and/or reporter
+    async fn find_all(&self, status: Option<ReportStatus>, reporter_id: Option<&UserId>, limit: i64, offset: i64) -> AppResult<Vec<Report>>;
+    /// Save the reported details and location
+    async fn update_details(&self, report: &Report) -> AppResult<Report>;
+    async fn change_status(&self, report: &Report, change: &ReportStatusChange) -> AppResult<Report>;
+    /// Status history, oldest first
+    async fn find_history(&self, id: &ReportId) -> AppResult<Vec<ReportStatusChange>>;
+
+    async fn add_media(&self, media: &ReportMedia) -> AppResult<ReportMedia>;
+    async fn find_media(&self, id: &ReportId) -> AppResult<Vec<ReportMedia>>;
+
+    async fn add_comment(&self, comment: &ReportComment) -> AppResult<ReportComment>;
+    async fn find_comments(&self, id: &ReportId) -> AppResult<Vec<ReportComment>>;
+
+    /// Link a report to the disaster it describes; returns false when already linked
+    async fn link_to_disaster(&self, report_id: &ReportId, disaster_id: &DisasterId, linked_by: &UserId) -> AppResult<bool>;
+    async fn find_linked_disasters(&self, id: &ReportId) -> AppResult<Vec<DisasterId>>;
+    async fn find_by_disaster(&self, disaster_id: &DisasterId) -> AppResult<Vec<Report>>;
+
+    /// Outcomes of the reporter's other reports
+    async fn reporter_history(&self, reporter_id: &UserId, exclude: &ReportId) -> AppResult<ReporterHistory>;
+    /// Reports other than `exclude` within `radius_m` of `center`, created between `from` and `to`
+    async fn find_nearby(
+        &self,
+        center: &Coordinates,
+        radius_m: f64,
+        from: chrono::DateTime<chrono::Utc>,
+        to: chrono::DateTime<chrono::Utc>,
+        exclude: &ReportId,
+    ) -> AppResult<Vec<NearbyReport>>;
+    /// Store the report's credibility and cluster, moving the reports of `absorbed_clusters` into that cluster
+    async fn record_credibility(&self, report: &Report, absorbed_clusters: &[uuid::Uuid]) -> AppResult<()>;
+    /// Clusters with reports awaiting review, most credible first
+    async fn find_triage_queue(&self, limit: i64, offset: i64) -> AppResult<Vec<TriageCluster>>;
+    /// Reports of one cluster, oldest first
+    async fn find_cluster(&self, cluster_id: &uuid::Uuid) -> AppResult<Vec<Report>>;
+}
+
+// Volunteer interface; volunteer profiles and their assignments, tracked in `volunteer_tracking`
+#[async_trait]
+pub trait VolunteerRepository: Send + Sync {
+    /// Fails with a conflict when the user already has a volunteer profile
+    async fn create(&self, volunteer: &Volunteer) -> AppResult<Volunteer>;
+    async fn update(&self, volunteer: &Volunteer) -> AppResult<Volunteer>;
+    async fn find_by_id(&self, id: &VolunteerId) -> AppResult<Option<Volunteer>>;
+    async fn find_by_user(&self, user_id: &UserId) -> AppResult<Option<Volunteer>>;
+    /// Optionally narrowed to (un)available volunteers and/or one skill, matched case-insensitively
+    async fn find_all(&self, available: Option<bool>, skill: Option<&str>, limit: i64, offset: i64) -> AppResult<Vec<Volunteer>>;
+
+    /// Available volunteers whose last known position is within `max_distance_m` of `target`
+    /// or unknown, with their open assignments and the hours they spent on shift since `fatigue_since`
+    async fn find_match_candidates(
+        &self,
+        target: &Coordinates,
+        max_distance_m: f64,
+        fatigue_since: chrono::DateTime<chrono::Utc>,
+    ) -> AppResult<Vec<VolunteerCandidate>>;
+
+    /// Fails with a conflict when the volunteer already holds an open assignment for the report or disaster
+    async fn create_assignment(&self, assignment: &VolunteerAssignment) -> AppResult<VolunteerAssignment>;
+    async fn update_assignment(&self, assignment: &VolunteerAssignment) -> AppResult<VolunteerAssignment>;
+    async fn find_assignment(&self, id: &uuid::Uuid) -> AppResult<Option<VolunteerAssignment>>;
+    /// Newest first; `active_only` keeps assignments that still hold the volunteer
+    async fn find_assignments(&self, volunteer_id: &VolunteerId, active_only: bool) -> AppResult<Vec<VolunteerAssignment>>;
+    async fn find_report_assignments(&self, report_id: &ReportId) -> AppResult<Vec<VolunteerAssignment>>;
+    /// Assignments made for the disaster, including those for its reports, newest first
+    async fn find_disaster_assignments(&self, disaster_id: &DisasterId) -> AppResult<Vec<VolunteerAssignment>>;
+}
+
+// GPS fix time series (`volunteer_locations`)
+#[async_trait]
+pub trait LocationTrackingRepository: Send + Sync {
+    /// Store a batch, skipping fixes already held for the same user and device time
+    /// (re-sent offline batches); returns how many were new
+    async fn record_fixes(&self, fixes: &[LocationFix]) -> AppResult<usize>;
+    async fn latest_for_user(&self, user_id: &UserId) -> AppResult<Option<LocationFix>>;
+    async fn latest_for_team(&self, team_id: &uuid::Uuid) -> AppResult<Option<LocationFix>>;
+    /// Newest fix since `since` of everyone on an open assignment for the disaster (or its reports)
+    /// or on a team of one of its active responses
+    async fn latest_for_disaster(&self, disaster_id: &DisasterId, since: chrono::DateTime<chrono::Utc>) -> AppResult<Vec<LocationFix>>;
+    /// Breadcrumb trail in device time order, at most `limit` of the most recent fixes
+    async fn trail_for_assignment(&self, assignment_id: &uuid::Uuid, since: Option<chrono::DateTime<chrono::Utc>>, limit: i64) -> AppResult<Vec<LocationFix>>;
+    async fn trail_for_team(&self, team_id: &uuid::Uuid, since: Option<chrono::DateTime<chrono::Utc>>, limit: i64) -> AppResult<Vec<LocationFix>>;
+}
+
+// Analytics read model (`analytics_disaster_facts`), maintained by projecting disaster events
+#[async_trait]
+pub trait AnalyticsRepository: Send + Sync {
+    async fn dashboard(&self, filter: &AnalyticsFilter) -> AppResult<DashboardSummary>;
+    async fn trends(&self, filter: &AnalyticsFilter) -> AppResult<DisasterTrends>;
+    async fn response_times(&self, filter: &AnalyticsFilter) -> AppResult<ResponseTimeAnalytics>;
+    async fn geographic(&self, filter: &AnalyticsFilter) -> AppResult<GeographicAnalytics>;
+    /// Matching facts, newest report first, at most `limit`
+    async fn facts(&self, filter: &AnalyticsFilter, limit: i64) -> AppResult<Vec<DisasterFact>>;
+}
@@ -5,6 +5,21 @@ use async_trait::async_trait;
 use crate::domain::User;
 use crate::shared::{AppResult, AppError, UserId, Coordinates};
 
+// Road routing service interface
+#[async_trait]
+pub trait RoutingService: Send + Sync {
+    /// Whether a road network is loaded; callers fall back to straight-line estimates otherwise
+    fn is_available(&self) -> bool;
+
+    /// Fastest route avoiding closed disaster zones, or `None` if the points are not connected
+    async fn route(
+        &self,
+        from: &crate::domain::value_objects::Coordinates,
+        to: &crate::domain::value_objects::Coordinates,
+        mode: crate::shared::routing::TravelMode,
+    ) -> AppResult<Option<crate::shared::routing::Route>>;
+}
+
 // Authentication service interface
 #[async_trait]
 pub trait AuthService: Send + Sync {
@@ -25,6 +40,13 @@ pub trait AuthService: Send + Sync {
     async fn clear_failed_attempts(&self, _email: &str) -> AppResult<()> { Ok(()) }
 }
 
+// Session revocation interface; lets use cases sign users out without knowing how sessions are stored
+#[async_trait]
+pub trait SessionRevoker: Send + Sync {
+    /// Revoke every session of a user, optionally keeping the one making the request
+    async fn revoke_user_sessions(&self, user_id: &UserId, keep_session_id: Option<&str>) -> AppResult<()>;
+}
+
 #[derive(Debug, Clone)]
 pub struct TokenPair {
     pub access_token: String,
@@ -88,6 +110,8 @@ pub struct WeatherData {
     pub wind_speed: f64,
     pub wind_direction: String,
     pub conditions: String,
+    /// Rain over the last hour, when the provider reports it
+    pub precipitation_mm: Option<f64>,
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
 
@@ -100,5 +124,17 @@ pub struct WeatherAlert {
     pub end_time: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+// Media storage interface; report photos and videos on local disk or an S3-compatible bucket
+#[async_trait]
+pub trait MediaStorage: Send + Sync {
+    /// Short backend name for logs, e.g. `local` or `s3`
+    fn backend(&self) -> &'static str;
+
+    /// Store `bytes` under `key`, replacing any existing object, and return its public URL
+    async fn put(&self, key: &str, content_type: &str, bytes: Vec<u8>) -> AppResult<String>;
+
+    async fn delete(&self, key: &str) -> AppResult<()>;
+}
+
 // Type alias for backward compatibility
 pub type GeoService = dyn GeolocationService;
@@ -0,0 +1,90 @@
+/// Home address geocoding against the location gazetteer
+/// Places a user's address at the most specific gazetteer entry it names, so geofenced
+/// alerts reach users who never shared a GPS fix. A street-level hit must lie in the
+/// address's city; failing that the city itself, and failing that the province.
+
+use crate::domain::entities::Location;
+use crate::domain::value_objects::Address;
+
+/// How closely a gazetteer entry has to agree with the address
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchLevel {
+    /// Same city and province
+    City,
+    /// Same province
+    Province,
+}
+
+impl MatchLevel {
+    pub fn accepts(&self, address: &Address, location: &Location) -> bool {
+        let same = |a: &str, b: &str| a.trim().eq_ignore_ascii_case(b.trim());
+        let same_province = same(&address.province, &location.address.province);
+        match self {
+            MatchLevel::City => same_province && same(&address.city, &location.address.city),
+            MatchLevel::Province => same_province,
+        }
+    }
+}
+
+/// Gazetteer searches to try for an address, most specific first
+pub fn lookups(address: &Address) -> Vec<(String, MatchLevel)> {
+    [
+        (address.street.as_str(), MatchLevel::City),
+        (address.city.as_str(), MatchLevel::City),
+        (address.province.as_str(), MatchLevel::Province),
+    ]
+        .into_iter()
+        .filter(|(query, _)| !query.trim().is_empty())
+        .map(|(query, level)| (query.trim().to_string(), level))
+        .collect()
+}
+
+/// First search result that agrees with the address at the given level
+pub fn pick<'a>(address: &Address, level: MatchLevel, candidates: &'a [Location]) -> Option<&'a Location> {
+    candidates.iter().find(|location| level.accepts(address, location))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::location::{AdministrativeLevel, LocationType};
+    use crate::domain::value_objects::Coordinates;
+
+    fn place(name: &str, city: &str, province: &str, lat: f64, lng: f64) -> Location {
+        Location::new(
+            name.to_string(),
+            Coordinates::new(lat, lng).unwrap(),
+            Address::new(name.to_string(), city.to_string(), province.to_string(), None, None).unwrap(),
+            LocationType::City,
+            AdministrativeLevel::Regency,
+        ).unwrap()
+    }
+
+    #[test]
+    fn citizen_address_is_placed_in_its_own_city() {
+        let home = Address::new(
+            "Jl. Medan Merdeka Barat".to_string(),
+            "Jakarta Pusat".to_string(),
+            "DKI Jakarta".to_string(),
+            None,
+            None,
+        ).unwrap();
+        let searches = lookups(&home);
+        assert_eq!(searches.len(), 3);
+
+        // The street name also matches Medan in North Sumatra, which must not be taken
+        let street_hits = vec![place("Medan", "Medan", "Sumatera Utara", 3.5952, 98.6722)];
+        assert!(pick(&home, searches[0].1, &street_hits).is_none());
+
+        let city_hits = vec![
+            place("Jakarta Utara", "Jakarta Utara", "DKI Jakarta", -6.1384, 106.8636),
+            place("Jakarta Pusat", "Jakarta Pusat", "DKI Jakarta", -6.1865, 106.8341),
+        ];
+        let geocoded = pick(&home, searches[1].1, &city_hits).unwrap();
+        assert_eq!(geocoded.name, "Jakarta Pusat");
+
+        // A 5 km alert around Monas now reaches this citizen
+        let monas = Coordinates::new(-6.1754, 106.8272).unwrap();
+        assert!(geocoded.coordinates.distance_to(&monas) <= 5.0);
+    }
+}
@@ -0,0 +1,367 @@
+/// Report credibility scoring and duplicate clustering
+/// Scores a report from its reporter's track record, corroboration by nearby reports of the same
+/// disaster type, attached media and the weather at its location, and groups reports that
+/// are close in space and time into one incident cluster
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::mem::discriminant;
+use uuid::Uuid;
+
+use crate::domain::entities::disaster::DisasterType;
+use crate::domain::entities::report::{
+    CredibilityFactors, MediaKind, Report, ReportMedia, ReportStatus, WeatherAgreement,
+};
+use crate::domain::ports::services::WeatherData;
+use crate::shared::{ReportId, UserId};
+
+/// Prior for reports whose reporter cannot be tracked
+const ANONYMOUS_REPORTER_PRIOR: f64 = 0.3;
+
+/// Tunables for scoring and clustering
+#[derive(Debug, Clone)]
+pub struct CredibilityPolicy {
+    /// Reports further apart than this never corroborate or cluster
+    pub radius_m: f64,
+    /// Reports further apart in time than this never corroborate or cluster
+    pub window: Duration,
+    pub reporter_weight: f64,
+    pub corroboration_weight: f64,
+    pub media_weight: f64,
+    pub weather_weight: f64,
+}
+
+impl Default for CredibilityPolicy {
+    fn default() -> Self {
+        Self {
+            radius_m: 2_000.0,
+            window: Duration::hours(6),
+            reporter_weight: 0.35,
+            corroboration_weight: 0.35,
+            media_weight: 0.15,
+            weather_weight: 0.15,
+        }
+    }
+}
+
+/// Outcomes of a reporter's earlier reports
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReporterHistory {
+    /// Verified or resolved
+    pub confirmed: u32,
+    pub rejected: u32,
+}
+
+/// Another report within the policy's radius and window of the one being assessed
+#[derive(Debug, Clone)]
+pub struct NearbyReport {
+    pub id: ReportId,
+    pub reporter_id: Option<UserId>,
+    pub disaster_type: DisasterType,
+    pub status: ReportStatus,
+    pub cluster_id: Option<Uuid>,
+    pub distance_m: f64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl NearbyReport {
+    /// Cluster the report belongs to; unassessed reports are a cluster of their own
+    pub fn cluster(&self) -> Uuid {
+        self.cluster_id.unwrap_or(self.id.0)
+    }
+}
+
+/// One incident in the coordinators' triage queue
+#[derive(Debug, Clone, Serialize)]
+pub struct TriageCluster {
+    pub cluster_id: Uuid,
+    /// Most credible report of the cluster still awaiting review
+    pub lead: Report,
+    pub report_count: u32,
+    pub pending_count: u32,
+    /// Reports already verified or resolved
+    pub confirmed_count: u32,
+    pub top_score: f64,
+    pub first_reported_at: DateTime<Utc>,
+    pub last_reported_at: DateTime<Utc>,
+}
+
+/// Result of assessing one report
+#[derive(Debug, Clone)]
+pub struct CredibilityAssessment {
+    pub score: f64,
+    pub factors: CredibilityFactors,
+    pub cluster_id: Uuid,
+    /// Neighbouring clusters that turned out to describe the same incident and are folded into `cluster_id`
+    pub absorbed_clusters: Vec<Uuid>,
+}
+
+pub struct CredibilityService;
+
+impl CredibilityService {
+    /// Score `report` and place it in the cluster of the earliest matching nearby report
+    pub fn assess(
+        policy: &CredibilityPolicy,
+        report: &Report,
+        history: ReporterHistory,
+        nearby: &[NearbyReport],
+        media: &[ReportMedia],
+        weather: WeatherAgreement,
+    ) -> CredibilityAssessment {
+        let matching: Vec<&NearbyReport> = nearby.iter()
+            .filter(|other| other.id != report.id && Self::matches(policy, report, other))
+            .collect();
+
+        let reporter = match report.reporter_id() {
+            Some(_) => Self::reporter_factor(history),
+            None => ANONYMOUS_REPORTER_PRIOR,
+        };
+        let (corroboration, corroborating_reports) = Self::corroboration_factor(policy, report, &matching);
+        let media_factor = Self::media_factor(media);
+        let weather_factor = match weather {
+            WeatherAgreement::Supports => 1.0,
+            WeatherAgreement::Neutral | WeatherAgreement::Unavailable => 0.5,
+            WeatherAgreement::Contradicts => 0.0,
+        };
+
+        let total_weight = policy.reporter_weight + policy.corroboration_weight + policy.media_weight + policy.weather_weight;
+        let score = if total_weight > 0.0 {
+            (reporter * policy.reporter_weight
+                + corroboration * policy.corroboration_weight
+                + media_factor * policy.media_weight
+                + weather_factor * policy.weather_weight)
+                / total_weight
+        } else {
+            0.0
+        };
+
+        let (cluster_id, absorbed_clusters) = Self::cluster(report, &matching);
+        CredibilityAssessment {
+            score: score.clamp(0.0, 1.0),
+            factors: CredibilityFactors {
+                reporter,
+                corroboration,
+                corroborating_reports,
+                media: media_factor,
+                weather,
+                assessed_at: Utc::now(),
+            },
+            cluster_id,
+            absorbed_clusters,
+        }
+    }
+
+    /// Same disaster type, within the radius and the time window, and not rejected
+    fn matches(policy: &CredibilityPolicy, report: &Report, other: &NearbyReport) -> bool {
+        discriminant(&report.details.disaster_type) == discriminant(&other.disaster_type)
+            && other.status != ReportStatus::Rejected
+            && other.distance_m <= policy.radius_m
+            && (other.created_at - report.created_at).num_seconds().abs() <= policy.window.num_seconds()
+    }
+
+    /// Laplace-smoothed share of confirmed reports, so a new reporter starts at 0.5
+    fn reporter_factor(history: ReporterHistory) -> f64 {
+        (history.confirmed as f64 + 1.0) / ((history.confirmed + history.rejected) as f64 + 2.0)
+    }
+
+    /// Each independent nearby report adds support that fades with distance and time;
+    /// reports already verified count for more. Saturates towards 1.0.
+    fn corroboration_factor(policy: &CredibilityPolicy, report: &Report, matching: &[&NearbyReport]) -> (f64, u32) {
+        let reporter_id = report.reporter_id();
+        let window_seconds = policy.window.num_seconds().max(1) as f64;
+        let mut support = 0.0;
+        let mut count = 0;
+        for other in matching {
+            // A reporter cannot corroborate themselves
+            if reporter_id.is_some() && other.reporter_id == reporter_id {
+                continue;
+            }
+            let proximity = 1.0 - (other.distance_m / policy.radius_m.max(1.0)).min(1.0);
+            let recency = 1.0 - ((other.created_at - report.created_at).num_seconds().abs() as f64 / window_seconds).min(1.0);
+            let confirmed = if matches!(other.status, ReportStatus::Verified | ReportStatus::Resolved) { 1.5 } else { 1.0 };
+            support += (0.5 + 0.5 * proximity) * (0.5 + 0.5 * recency) * confirmed;
+            count += 1;
+        }
+        (1.0 - (-support).exp(), count)
+    }
+
+    fn media_factor(media: &[ReportMedia]) -> f64 {
+        let has_video = media.iter().any(|m| m.kind == MediaKind::Video);
+        match media.len() {
+            0 => 0.0,
+            _ if has_video => 1.0,
+            1 => 0.6,
+            2 => 0.85,
+            _ => 1.0,
+        }
+    }
+
+    /// Join the cluster of the earliest matching report, absorbing any other clusters it matched
+    fn cluster(report: &Report, matching: &[&NearbyReport]) -> (Uuid, Vec<Uuid>) {
+        let Some(earliest) = matching.iter().min_by_key(|other| other.created_at) else {
+            return (report.cluster_id.unwrap_or(report.id.0), Vec::new());
+        };
+        let cluster_id = if report.created_at < earliest.created_at {
+            report.cluster_id.unwrap_or(report.id.0)
+        } else {
+            earliest.cluster()
+        };
+
+        let mut absorbed: Vec<Uuid> = matching.iter()
+            .map(|other| other.cluster())
+            .chain(report.cluster_id)
+            .filter(|cluster| *cluster != cluster_id)
+            .collect();
+        absorbed.sort();
+        absorbed.dedup();
+        (cluster_id, absorbed)
+    }
+
+    /// Whether current weather makes a report of `disaster_type` more or less plausible
+    pub fn weather_agreement(disaster_type: &DisasterType, weather: Option<&WeatherData>) -> WeatherAgreement {
+        let Some(weather) = weather else {
+            return WeatherAgreement::Unavailable;
+        };
+        let conditions = weather.conditions.to_lowercase();
+        let wet = weather.precipitation_mm.is_some_and(|mm| mm > 0.0)
+            || ["rain", "drizzle", "thunderstorm", "shower"].iter().any(|c| conditions.contains(c));
+        let stormy = conditions.contains("thunderstorm")
+            || conditions.contains("squall")
+            || conditions.contains("tornado")
+            || weather.wind_speed >= 17.0;
+
+        match disaster_type {
+            DisasterType::Flood | DisasterType::Landslide if wet => WeatherAgreement::Supports,
+            DisasterType::Flood | DisasterType::Landslide => WeatherAgreement::Neutral,
+            DisasterType::Storm if stormy => WeatherAgreement::Supports,
+            DisasterType::Storm if weather.wind_speed < 5.0 && !wet => WeatherAgreement::Contradicts,
+            DisasterType::Storm => WeatherAgreement::Neutral,
+            DisasterType::Fire | DisasterType::Drought if wet => WeatherAgreement::Contradicts,
+            DisasterType::Fire | DisasterType::Drought if weather.humidity < 40.0 || weather.temperature >= 32.0 => {
+                WeatherAgreement::Supports
+            }
+            _ => WeatherAgreement::Neutral,
+        }
+    }
+
+    /// Confidence that a disaster is real given the credibility of the reports linked to it:
+    /// the chance that not all of them are wrong
+    pub fn combined_confidence(scores: &[f64]) -> f32 {
+        let doubt: f64 = scores.iter().map(|score| 1.0 - score.clamp(0.0, 1.0)).product();
+        if scores.is_empty() { 0.0 } else { (1.0 - doubt) as f32 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::report::{CasualtyEstimate, ReportDetails, ReportSource};
+    use crate::domain::value_objects::Coordinates;
+
+    fn report(user_id: Option<UserId>, disaster_type: DisasterType) -> Report {
+        let source = match user_id {
+            Some(user_id) => ReportSource::User { user_id },
+            None => ReportSource::anonymous(None, None).unwrap(),
+        };
+        let details = ReportDetails {
+            disaster_type,
+            title: "Banjir".to_string(),
+            description: None,
+            location: Coordinates::new(-6.22, 106.86).unwrap(),
+            address: None,
+            impact_radius_m: None,
+            estimated_severity: 3,
+            casualties: CasualtyEstimate::default(),
+        };
+        Report::submit(source, details).unwrap().0
+    }
+
+    fn nearby(of: &Report, reporter_id: Option<UserId>, distance_m: f64, minutes: i64) -> NearbyReport {
+        NearbyReport {
+            id: ReportId::new(),
+            reporter_id,
+            disaster_type: of.details.disaster_type.clone(),
+            status: ReportStatus::Reported,
+            cluster_id: None,
+            distance_m,
+            created_at: of.created_at - Duration::minutes(minutes),
+        }
+    }
+
+    fn weather(conditions: &str, precipitation_mm: Option<f64>) -> WeatherData {
+        WeatherData {
+            temperature: 27.0,
+            humidity: 85.0,
+            wind_speed: 3.0,
+            wind_direction: "N".to_string(),
+            conditions: conditions.to_string(),
+            precipitation_mm,
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn corroborated_reports_from_trusted_reporters_score_higher() {
+        let policy = CredibilityPolicy::default();
+        let reporter = UserId::new();
+        let subject = report(Some(reporter), DisasterType::Flood);
+        let trusted = ReporterHistory { confirmed: 8, rejected: 0 };
+
+        let alone = CredibilityService::assess(&policy, &subject, ReporterHistory::default(), &[], &[], WeatherAgreement::Unavailable);
+        let neighbours = vec![
+            nearby(&subject, Some(UserId::new()), 300.0, 10),
+            nearby(&subject, None, 900.0, 40),
+            // The reporter's own earlier report does not corroborate
+            nearby(&subject, Some(reporter), 50.0, 5),
+        ];
+        let supported = CredibilityService::assess(&policy, &subject, trusted, &neighbours, &[], WeatherAgreement::Supports);
+
+        assert!((alone.factors.reporter - 0.5).abs() < 1e-9);
+        assert_eq!(alone.factors.corroborating_reports, 0);
+        assert_eq!(supported.factors.corroborating_reports, 2);
+        assert!(supported.score > alone.score);
+
+        let anonymous = CredibilityService::assess(&policy, &report(None, DisasterType::Flood), trusted, &[], &[], WeatherAgreement::Unavailable);
+        assert!(anonymous.score < alone.score);
+    }
+
+    #[test]
+    fn close_reports_of_the_same_type_share_the_earliest_cluster() {
+        let policy = CredibilityPolicy::default();
+        let subject = report(Some(UserId::new()), DisasterType::Flood);
+
+        let mut first = nearby(&subject, None, 500.0, 120);
+        first.cluster_id = Some(Uuid::new_v4());
+        let second = nearby(&subject, None, 700.0, 30);
+        let mut far = nearby(&subject, None, 5_000.0, 10);
+        far.cluster_id = Some(Uuid::new_v4());
+        let mut other_type = nearby(&subject, None, 100.0, 10);
+        other_type.disaster_type = DisasterType::Fire;
+
+        let assessment = CredibilityService::assess(
+            &policy, &subject, ReporterHistory::default(),
+            &[first.clone(), second.clone(), far, other_type], &[], WeatherAgreement::Unavailable,
+        );
+        assert_eq!(Some(assessment.cluster_id), first.cluster_id);
+        assert_eq!(assessment.absorbed_clusters, vec![second.id.0]);
+
+        let alone = CredibilityService::assess(&policy, &subject, ReporterHistory::default(), &[], &[], WeatherAgreement::Unavailable);
+        assert_eq!(alone.cluster_id, subject.id.0);
+        assert!(alone.absorbed_clusters.is_empty());
+    }
+
+    #[test]
+    fn weather_supports_or_contradicts_by_disaster_type() {
+        let rain = weather("Rain", Some(4.2));
+        let clear = weather("Clear", None);
+
+        assert_eq!(CredibilityService::weather_agreement(&DisasterType::Flood, Some(&rain)), WeatherAgreement::Supports);
+        assert_eq!(CredibilityService::weather_agreement(&DisasterType::Fire, Some(&rain)), WeatherAgreement::Contradicts);
+        assert_eq!(CredibilityService::weather_agreement(&DisasterType::Storm, Some(&clear)), WeatherAgreement::Contradicts);
+        assert_eq!(CredibilityService::weather_agreement(&DisasterType::Earthquake, Some(&rain)), WeatherAgreement::Neutral);
+        assert_eq!(CredibilityService::weather_agreement(&DisasterType::Flood, None), WeatherAgreement::Unavailable);
+
+        assert_eq!(CredibilityService::combined_confidence(&[]), 0.0);
+        assert!((CredibilityService::combined_confidence(&[0.5, 0.5]) - 0.75).abs() < 1e-6);
+    }
+}
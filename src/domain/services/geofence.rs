@@ -0,0 +1,73 @@
+/// Geofence arrival detection
+/// Decides from tracked GPS fixes when a responder reached the place they were sent to.
+/// Fixes too inaccurate to tell whether someone is on site are ignored.
+
+use crate::domain::entities::tracking::LocationFix;
+use crate::domain::value_objects::Coordinates;
+
+#[derive(Debug, Clone)]
+pub struct GeofencePolicy {
+    /// Distance from the target within which a responder counts as arrived
+    pub arrival_radius_m: f64,
+    /// Fixes reporting a worse accuracy than this never count as an arrival
+    pub max_accuracy_m: f64,
+}
+
+impl Default for GeofencePolicy {
+    fn default() -> Self {
+        Self { arrival_radius_m: 150.0, max_accuracy_m: 100.0 }
+    }
+}
+
+impl GeofencePolicy {
+    /// Whether the fix puts the responder within the arrival radius of `target`
+    pub fn is_inside(&self, fix: &LocationFix, target: &Coordinates) -> bool {
+        let accurate = fix.accuracy_m.is_none_or(|accuracy| accuracy <= self.max_accuracy_m);
+        accurate && fix.position.distance_to(target) * 1000.0 <= self.arrival_radius_m
+    }
+
+    /// Earliest fix by device time that counts as an arrival at `target`
+    pub fn first_arrival<'a>(&self, fixes: &'a [LocationFix], target: &Coordinates) -> Option<&'a LocationFix> {
+        fixes.iter()
+            .filter(|fix| self.is_inside(fix, target))
+            .min_by_key(|fix| fix.recorded_at)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use crate::domain::entities::tracking::{FixSource, LocationSample};
+    use crate::shared::UserId;
+
+    fn fix(latitude: f64, longitude: f64, accuracy_m: f64, minutes_ago: i64) -> LocationFix {
+        let source = FixSource { user_id: UserId::new(), volunteer_id: None, team_id: None, assignment_id: None };
+        let now = Utc::now();
+        LocationFix::from_sample(&source, LocationSample {
+            latitude,
+            longitude,
+            accuracy_m: Some(accuracy_m),
+            speed_mps: None,
+            heading_deg: None,
+            recorded_at: now - Duration::minutes(minutes_ago),
+        }, now).unwrap()
+    }
+
+    #[test]
+    fn earliest_accurate_fix_inside_the_radius_is_the_arrival() {
+        let policy = GeofencePolicy::default();
+        let target = Coordinates::new(-6.2000, 106.8000).unwrap();
+        // ~1.1 km away, ~55 m away but too inaccurate, then ~55 m and ~11 m away
+        let fixes = vec![
+            fix(-6.2100, 106.8000, 10.0, 30),
+            fix(-6.2005, 106.8000, 500.0, 20),
+            fix(-6.2001, 106.8000, 8.0, 5),
+            fix(-6.2005, 106.8000, 15.0, 10),
+        ];
+
+        let arrival = policy.first_arrival(&fixes, &target).unwrap();
+        assert_eq!(arrival.id, fixes[3].id);
+        assert!(policy.first_arrival(&fixes[..2], &target).is_none());
+    }
+}
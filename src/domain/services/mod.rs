@@ -6,6 +6,11 @@ use crate::domain::entities::{Disaster, Location, User};
 use crate::domain::entities::disaster::{DisasterType, DisasterSeverity};
 use crate::UserRole;
 
+pub mod address_geocoding;
+pub mod credibility;
+pub mod geofence;
+pub mod volunteer_matching;
+
 /// Disaster Assessment Service - Evaluates disaster severity and impact
 pub struct DisasterAssessmentService;
 
@@ -0,0 +1,365 @@
+/// Volunteer matching
+/// Ranks available volunteers against what a disaster needs: how well their skills and
+/// certifications fit, how far their last known position is from it, how much work they already
+/// carry and how experienced they are. Volunteers over their shift limit are left to rest.
+
+use chrono::Duration;
+use serde::Serialize;
+
+use crate::domain::entities::disaster::{Disaster, DisasterType};
+use crate::domain::entities::volunteer::Volunteer;
+use crate::shared::Priority;
+
+/// Skill fit assumed when a disaster calls for nothing in particular
+const NEUTRAL_SKILL_FIT: f64 = 0.5;
+/// Distance factor for volunteers who have never shared a position
+const UNKNOWN_DISTANCE_FACTOR: f64 = 0.3;
+/// Years of experience at which the experience factor reaches about two thirds
+const EXPERIENCE_SCALE_YEARS: f64 = 5.0;
+
+/// Tunables for ranking and the limits that keep volunteers from being overworked
+#[derive(Debug, Clone)]
+pub struct MatchingPolicy {
+    /// Volunteers whose last known position is further away are not considered
+    pub max_distance_m: f64,
+    /// Hours on shift within `fatigue_window` after which a volunteer gets no new assignments
+    pub max_shift_hours: f64,
+    pub fatigue_window: Duration,
+    /// Open assignments after which a volunteer gets no new ones
+    pub max_active_assignments: u32,
+    pub skill_weight: f64,
+    pub distance_weight: f64,
+    pub workload_weight: f64,
+    pub experience_weight: f64,
+}
+
+impl Default for MatchingPolicy {
+    fn default() -> Self {
+        Self {
+            max_distance_m: 50_000.0,
+            max_shift_hours: 12.0,
+            fatigue_window: Duration::hours(24),
+            max_active_assignments: 2,
+            skill_weight: 0.45,
+            distance_weight: 0.25,
+            workload_weight: 0.15,
+            experience_weight: 0.15,
+        }
+    }
+}
+
+/// Kind of help a disaster calls for, recognised by keyword in resource needs and in volunteers'
+/// skills, certifications and specialization (English and Indonesian)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Capability {
+    Medical,
+    SearchAndRescue,
+    Evacuation,
+    Firefighting,
+    Logistics,
+    Shelter,
+    Counseling,
+    Coordination,
+}
+
+impl Capability {
+    pub const ALL: [Capability; 8] = [
+        Self::Medical,
+        Self::SearchAndRescue,
+        Self::Evacuation,
+        Self::Firefighting,
+        Self::Logistics,
+        Self::Shelter,
+        Self::Counseling,
+        Self::Coordination,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Medical => "medical",
+            Self::SearchAndRescue => "search_and_rescue",
+            Self::Evacuation => "evacuation",
+            Self::Firefighting => "firefighting",
+            Self::Logistics => "logistics",
+            Self::Shelter => "shelter",
+            Self::Counseling => "counseling",
+            Self::Coordination => "coordination",
+        }
+    }
+
+    /// Prefixes matched at the start of a word; a trailing space asks for the whole word
+    fn keywords(&self) -> &'static [&'static str] {
+        match self {
+            Self::Medical => &[
+                "medic", "medis", "first aid", "pertolongan pertama", "p3k", "nurs", "perawat", "doctor",
+                "dokter", "ambulan", "health", "kesehatan", "medicine", "obat", "bidan", "emt ",
+            ],
+            Self::SearchAndRescue => &[
+                "rescue", "search", "sar ", "penyelamat", "pencarian", "basarnas", "collapsed", "reruntuhan",
+            ],
+            Self::Evacuation => &["evacuat", "evakuasi", "boat", "perahu", "swift water", "water rescue"],
+            Self::Firefighting => &["fire", "pemadam", "kebakaran", "damkar"],
+            Self::Logistics => &[
+                "logisti", "supply", "supplies", "food", "makanan", "pangan", "clean water", "air bersih",
+                "distribut", "distribusi", "driver", "pengemudi", "sopir", "warehouse", "gudang",
+            ],
+            Self::Shelter => &["shelter", "pengungsi", "posko", "tent", "tenda", "dapur umum", "kitchen", "camp "],
+            Self::Counseling => &["counsel", "konseling", "psycho", "psiko", "trauma", "psychosocial", "psikososial"],
+            Self::Coordination => &[
+                "coordinat", "koordinasi", "communication", "komunikasi", "radio", "incident command", "manajemen bencana",
+            ],
+        }
+    }
+
+    /// Capabilities named anywhere in `text`
+    pub fn recognize(text: &str) -> Vec<Capability> {
+        let words: Vec<String> = text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+        let haystack = format!(" {} ", words.join(" "));
+        Self::ALL
+            .into_iter()
+            .filter(|capability| {
+                capability.keywords().iter().any(|keyword| haystack.contains(&format!(" {}", keyword)))
+            })
+            .collect()
+    }
+
+    /// What a disaster of this type usually calls for before any needs are recorded
+    pub fn defaults_for(disaster_type: &DisasterType) -> &'static [Capability] {
+        use Capability::*;
+        match disaster_type {
+            DisasterType::Earthquake => &[SearchAndRescue, Medical, Shelter],
+            DisasterType::Flood => &[Evacuation, SearchAndRescue, Logistics, Shelter],
+            DisasterType::Tsunami => &[SearchAndRescue, Evacuation, Medical, Shelter],
+            DisasterType::Landslide => &[SearchAndRescue, Medical],
+            DisasterType::VolcanicEruption => &[Evacuation, Shelter, Medical],
+            DisasterType::Fire => &[Firefighting, Medical, Evacuation],
+            DisasterType::Storm => &[SearchAndRescue, Shelter, Logistics],
+            DisasterType::Drought => &[Logistics],
+            DisasterType::Epidemic => &[Medical, Logistics, Counseling],
+            DisasterType::TechnologicalDisaster => &[SearchAndRescue, Medical],
+            DisasterType::Other(_) => &[Medical, Logistics],
+        }
+    }
+}
+
+/// A capability the disaster needs and how much it matters
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct SkillRequirement {
+    pub capability: Capability,
+    pub weight: f64,
+}
+
+/// An available volunteer with what the matcher needs to know about their situation
+#[derive(Debug, Clone)]
+pub struct VolunteerCandidate {
+    pub volunteer: Volunteer,
+    /// Metres from the disaster to the volunteer's last known position, if they ever shared one
+    pub distance_m: Option<f64>,
+    /// Assignments still holding the volunteer
+    pub active_assignments: u32,
+    /// Hours spent on accepted assignments within the policy's fatigue window
+    pub shift_hours: f64,
+}
+
+/// A ranked candidate with the factors behind the score
+#[derive(Debug, Clone, Serialize)]
+pub struct VolunteerMatch {
+    pub volunteer: Volunteer,
+    pub score: f64,
+    pub skill_fit: f64,
+    pub matched_capabilities: Vec<Capability>,
+    pub distance_m: Option<f64>,
+    pub active_assignments: u32,
+    pub shift_hours: f64,
+}
+
+pub struct VolunteerMatchingService;
+
+impl VolunteerMatchingService {
+    /// What to match volunteers against: the disaster's recorded needs (only those of
+    /// `resource_types` when given), weighted by urgency, plus the usual needs of its type
+    pub fn requirements(disaster: &Disaster, resource_types: &[String]) -> Vec<SkillRequirement> {
+        let mut requirements: Vec<SkillRequirement> = Vec::new();
+        let mut add = |capability: Capability, weight: f64| {
+            match requirements.iter_mut().find(|r| r.capability == capability) {
+                Some(existing) => existing.weight = existing.weight.max(weight),
+                None => requirements.push(SkillRequirement { capability, weight }),
+            }
+        };
+
+        for need in &disaster.resources_needed {
+            if !resource_types.is_empty()
+                && !resource_types.iter().any(|t| t.eq_ignore_ascii_case(&need.resource_type))
+            {
+                continue;
+            }
+            let weight = match need.urgency {
+                Priority::Low => 1.0,
+                Priority::Normal => 1.5,
+                Priority::High => 2.0,
+                Priority::Critical => 2.5,
+                Priority::Emergency => 3.0,
+            };
+            let text = format!("{} {}", need.resource_type, need.description.as_deref().unwrap_or_default());
+            for capability in Capability::recognize(&text) {
+                add(capability, weight);
+            }
+        }
+        for capability in Capability::defaults_for(&disaster.disaster_type) {
+            add(*capability, 1.0);
+        }
+        requirements
+    }
+
+    /// Candidates that may take another assignment, best match first. Volunteers too far away,
+    /// at their assignment or shift limit, or with none of the required capabilities are left out.
+    pub fn rank(
+        policy: &MatchingPolicy,
+        requirements: &[SkillRequirement],
+        candidates: Vec<VolunteerCandidate>,
+    ) -> Vec<VolunteerMatch> {
+        let total_weight = policy.skill_weight + policy.distance_weight + policy.workload_weight + policy.experience_weight;
+        let mut matches: Vec<VolunteerMatch> = candidates
+            .into_iter()
+            .filter(|c| c.volunteer.is_available)
+            .filter(|c| c.active_assignments < policy.max_active_assignments)
+            .filter(|c| c.shift_hours < policy.max_shift_hours)
+            .filter(|c| c.distance_m.is_none_or(|d| d <= policy.max_distance_m))
+            .filter_map(|c| {
+                let (skill_fit, matched_capabilities) = Self::skill_fit(requirements, &c.volunteer);
+                if !requirements.is_empty() && matched_capabilities.is_empty() {
+                    return None;
+                }
+                let distance = c.distance_m
+                    .map(|d| 1.0 - (d / policy.max_distance_m.max(1.0)).min(1.0))
+                    .unwrap_or(UNKNOWN_DISTANCE_FACTOR);
+                let workload = 0.5 * (1.0 - c.active_assignments as f64 / policy.max_active_assignments.max(1) as f64)
+                    + 0.5 * (1.0 - (c.shift_hours / policy.max_shift_hours.max(1.0)).min(1.0));
+                let experience = 1.0 - (-(c.volunteer.profile.experience_years.unwrap_or(0) as f64) / EXPERIENCE_SCALE_YEARS).exp();
+                let score = if total_weight > 0.0 {
+                    (skill_fit * policy.skill_weight
+                        + distance * policy.distance_weight
+                        + workload * policy.workload_weight
+                        + experience * policy.experience_weight)
+                        / total_weight
+                } else {
+                    0.0
+                };
+                Some(VolunteerMatch {
+                    volunteer: c.volunteer,
+                    score: score.clamp(0.0, 1.0),
+                    skill_fit,
+                    matched_capabilities,
+                    distance_m: c.distance_m,
+                    active_assignments: c.active_assignments,
+                    shift_hours: c.shift_hours,
+                })
+            })
+            .collect();
+        matches.sort_by(|a, b| {
+            b.score.total_cmp(&a.score)
+                .then_with(|| a.distance_m.unwrap_or(f64::MAX).total_cmp(&b.distance_m.unwrap_or(f64::MAX)))
+        });
+        matches
+    }
+
+    /// Weighted share of the requirements the volunteer covers; a certification counts fully,
+    /// a listed skill or specialization alone three quarters
+    fn skill_fit(requirements: &[SkillRequirement], volunteer: &Volunteer) -> (f64, Vec<Capability>) {
+        let total: f64 = requirements.iter().map(|r| r.weight).sum();
+        if total <= 0.0 {
+            return (NEUTRAL_SKILL_FIT, Vec::new());
+        }
+
+        let profile = &volunteer.profile;
+        let certified = Capability::recognize(&profile.certifications.join(", "));
+        let skilled = Capability::recognize(&format!(
+            "{}, {}",
+            profile.skills.join(", "),
+            profile.specialization.as_deref().unwrap_or_default()
+        ));
+        let mut covered = 0.0;
+        let mut matched = Vec::new();
+        for requirement in requirements {
+            let coverage = if certified.contains(&requirement.capability) {
+                1.0
+            } else if skilled.contains(&requirement.capability) {
+                0.75
+            } else {
+                continue;
+            };
+            covered += requirement.weight * coverage;
+            matched.push(requirement.capability);
+        }
+        (covered / total, matched)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::volunteer::VolunteerProfile;
+    use crate::shared::UserId;
+
+    fn candidate(skills: &[&str], certifications: &[&str], years: u32, distance_m: Option<f64>) -> VolunteerCandidate {
+        let profile = VolunteerProfile {
+            skills: skills.iter().map(|s| s.to_string()).collect(),
+            certifications: certifications.iter().map(|s| s.to_string()).collect(),
+            experience_years: Some(years),
+            specialization: None,
+        };
+        VolunteerCandidate {
+            volunteer: Volunteer::register(UserId::new(), profile).unwrap(),
+            distance_m,
+            active_assignments: 0,
+            shift_hours: 0.0,
+        }
+    }
+
+    #[test]
+    fn recognizes_english_and_indonesian_keywords() {
+        assert_eq!(Capability::recognize("Pertolongan Pertama"), vec![Capability::Medical]);
+        assert_eq!(Capability::recognize("Evakuasi"), vec![Capability::Evacuation]);
+        assert_eq!(Capability::recognize("Tim SAR"), vec![Capability::SearchAndRescue]);
+        // Whole-word keywords do not match inside other words
+        assert!(Capability::recognize("Sarana dasar").is_empty());
+        assert_eq!(
+            Capability::recognize("medical supplies"),
+            vec![Capability::Medical, Capability::Logistics]
+        );
+    }
+
+    #[test]
+    fn ranks_by_fit_and_distance_and_respects_limits() {
+        let requirements = [
+            SkillRequirement { capability: Capability::Medical, weight: 3.0 },
+            SkillRequirement { capability: Capability::Evacuation, weight: 1.0 },
+        ];
+        let medic = candidate(&["Medis Darurat"], &["PMI First Aid"], 4, Some(20_000.0));
+        let evacuator = candidate(&["Evakuasi"], &[], 4, Some(2_000.0));
+        let cook = candidate(&["Memasak"], &[], 10, Some(500.0));
+        let mut tired = candidate(&["First Aid"], &["Paramedic"], 8, Some(1_000.0));
+        tired.shift_hours = 12.5;
+        let mut busy = candidate(&["First Aid"], &[], 8, Some(1_000.0));
+        busy.active_assignments = 2;
+        let far = candidate(&["First Aid"], &[], 8, Some(80_000.0));
+
+        let medic_id = medic.volunteer.id;
+        let evacuator_id = evacuator.volunteer.id;
+        let ranked = VolunteerMatchingService::rank(
+            &MatchingPolicy::default(),
+            &requirements,
+            vec![evacuator, cook, tired, busy, far, medic],
+        );
+
+        let ids: Vec<_> = ranked.iter().map(|m| m.volunteer.id).collect();
+        assert_eq!(ids, vec![medic_id, evacuator_id]);
+        assert!((ranked[0].skill_fit - 0.75).abs() < 1e-9);
+        assert_eq!(ranked[0].matched_capabilities, vec![Capability::Medical]);
+    }
+}
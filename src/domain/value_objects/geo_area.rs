@@ -0,0 +1,160 @@
+/// Geographic area value object
+/// Polygon or multipolygon boundary, e.g. a hazard zone or an alert target area
+
+use geo::{ChamberlainDuquetteArea, Contains, Coord, LineString, MultiPolygon, Point, Polygon};
+use serde::{Deserialize, Serialize};
+use crate::domain::value_objects::Coordinates;
+use crate::shared::{AppError, AppResult};
+
+/// Upper bound on vertices so a single alert cannot submit an unbounded geometry
+const MAX_VERTICES: usize = 20_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GeoPolygon {
+    pub exterior: Vec<Coordinates>,
+    pub holes: Vec<Vec<Coordinates>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GeoArea {
+    pub polygons: Vec<GeoPolygon>,
+}
+
+impl GeoArea {
+    pub fn new(polygons: Vec<GeoPolygon>) -> AppResult<Self> {
+        if polygons.is_empty() {
+            return Err(AppError::Validation("Area must contain at least one polygon".to_string()));
+        }
+
+        let mut vertices = 0;
+        for polygon in &polygons {
+            for ring in std::iter::once(&polygon.exterior).chain(polygon.holes.iter()) {
+                let distinct = if ring.first() == ring.last() { ring.len().saturating_sub(1) } else { ring.len() };
+                if distinct < 3 {
+                    return Err(AppError::Validation("Polygon rings need at least three distinct points".to_string()));
+                }
+                vertices += ring.len();
+            }
+        }
+        if vertices > MAX_VERTICES {
+            return Err(AppError::Validation(format!("Area cannot have more than {} vertices", MAX_VERTICES)));
+        }
+
+        Ok(Self { polygons })
+    }
+
+    /// Parse a GeoJSON Polygon or MultiPolygon, bare or wrapped in a Feature
+    pub fn from_geojson(value: &serde_json::Value) -> AppResult<Self> {
+        use geojson::{GeoJson, Value};
+
+        let geojson = GeoJson::from_json_value(value.clone())
+            .map_err(|e| AppError::Validation(format!("Invalid GeoJSON area: {}", e)))?;
+        let geometry = match geojson {
+            GeoJson::Geometry(geometry) => geometry,
+            GeoJson::Feature(feature) => feature.geometry
+                .ok_or_else(|| AppError::Validation("GeoJSON feature has no geometry".to_string()))?,
+            GeoJson::FeatureCollection(_) => {
+                return Err(AppError::Validation("Area must be a Polygon or MultiPolygon, not a FeatureCollection".to_string()));
+            }
+        };
+
+        let polygons = match geometry.value {
+            Value::Polygon(rings) => vec![Self::polygon_from_rings(&rings)?],
+            Value::MultiPolygon(polygons) => polygons.iter()
+                .map(|rings| Self::polygon_from_rings(rings))
+                .collect::<AppResult<Vec<_>>>()?,
+            _ => return Err(AppError::Validation("Area must be a Polygon or MultiPolygon".to_string())),
+        };
+        Self::new(polygons)
+    }
+
+    fn polygon_from_rings(rings: &[Vec<Vec<f64>>]) -> AppResult<GeoPolygon> {
+        let mut rings = rings.iter().map(|ring| {
+            ring.iter()
+                .map(|position| match position.as_slice() {
+                    [lng, lat, ..] => Coordinates::new(*lat, *lng).map_err(|e| AppError::Validation(e.to_string())),
+                    _ => Err(AppError::Validation("GeoJSON positions need longitude and latitude".to_string())),
+                })
+                .collect::<AppResult<Vec<_>>>()
+        });
+
+        let exterior = rings.next()
+            .ok_or_else(|| AppError::Validation("Polygon has no exterior ring".to_string()))??;
+        let holes = rings.collect::<AppResult<Vec<_>>>()?;
+        Ok(GeoPolygon { exterior, holes })
+    }
+
+    /// GeoJSON MultiPolygon geometry, suitable for `ST_GeomFromGeoJSON`
+    pub fn to_geojson(&self) -> serde_json::Value {
+        let ring = |ring: &Vec<Coordinates>| -> Vec<[f64; 2]> {
+            let mut positions: Vec<[f64; 2]> = ring.iter().map(|c| [c.longitude, c.latitude]).collect();
+            if positions.first() != positions.last() {
+                positions.push(positions[0]);
+            }
+            positions
+        };
+        let coordinates: Vec<Vec<Vec<[f64; 2]>>> = self.polygons.iter()
+            .map(|polygon| std::iter::once(&polygon.exterior).chain(polygon.holes.iter()).map(ring).collect())
+            .collect();
+
+        serde_json::json!({ "type": "MultiPolygon", "coordinates": coordinates })
+    }
+
+    fn to_multi_polygon(&self) -> MultiPolygon<f64> {
+        let ring = |ring: &Vec<Coordinates>| -> LineString<f64> {
+            ring.iter().map(|c| Coord { x: c.longitude, y: c.latitude }).collect()
+        };
+        MultiPolygon::new(self.polygons.iter()
+            .map(|polygon| Polygon::new(ring(&polygon.exterior), polygon.holes.iter().map(ring).collect()))
+            .collect())
+    }
+
+    pub fn contains(&self, point: &Coordinates) -> bool {
+        self.to_multi_polygon().contains(&Point::new(point.longitude, point.latitude))
+    }
+
+    /// Approximate surface area in square kilometres
+    pub fn area_km2(&self) -> f64 {
+        self.to_multi_polygon().chamberlain_duquette_unsigned_area() / 1_000_000.0
+    }
+
+    /// Radius of a circle with the same surface area
+    pub fn equivalent_radius_km(&self) -> f64 {
+        (self.area_km2() / std::f64::consts::PI).sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_feature_wrapped_polygon_with_hole() {
+        let area = GeoArea::from_geojson(&serde_json::json!({
+            "type": "Feature",
+            "properties": {},
+            "geometry": {
+                "type": "Polygon",
+                "coordinates": [
+                    [[106.80, -6.20], [106.90, -6.20], [106.90, -6.10], [106.80, -6.10], [106.80, -6.20]],
+                    [[106.84, -6.16], [106.86, -6.16], [106.86, -6.14], [106.84, -6.14], [106.84, -6.16]]
+                ]
+            }
+        })).unwrap();
+
+        assert!(area.contains(&Coordinates::new(-6.18, 106.82).unwrap()));
+        assert!(!area.contains(&Coordinates::new(-6.15, 106.85).unwrap()));
+        assert!(!area.contains(&Coordinates::new(-6.30, 106.82).unwrap()));
+        assert!(area.area_km2() > 100.0 && area.area_km2() < 130.0);
+    }
+
+    #[test]
+    fn rejects_degenerate_and_non_polygon_geometry() {
+        assert!(GeoArea::from_geojson(&serde_json::json!({
+            "type": "Polygon", "coordinates": [[[106.8, -6.2], [106.9, -6.2], [106.8, -6.2]]]
+        })).is_err());
+        assert!(GeoArea::from_geojson(&serde_json::json!({
+            "type": "Point", "coordinates": [106.8, -6.2]
+        })).is_err());
+    }
+}
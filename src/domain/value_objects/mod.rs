@@ -6,6 +6,7 @@ pub mod username;
 pub mod phone_number;
 pub mod coordinates;
 pub mod address;
+pub mod geo_area;
 pub mod user_role;
 pub mod user_status;
 
@@ -14,6 +15,7 @@ pub use username::Username;
 pub use phone_number::PhoneNumber;
 pub use coordinates::Coordinates;
 pub use address::Address;
+pub use geo_area::{GeoArea, GeoPolygon};
 pub use user_status::UserStatus;
 
 // Backward compatibility: re-export ID types from shared::types
@@ -30,6 +30,8 @@ pub trait CacheService: Send + Sync {
     async fn expire(&self, key: &str, ttl: Duration) -> AppResult<()>;
 
     async fn delete(&self, key: &str) -> AppResult<()>;
+    /// Read and delete a key in one step; of several concurrent callers only one receives the value
+    async fn take_string(&self, key: &str) -> AppResult<Option<String>>;
     async fn exists(&self, key: &str) -> AppResult<bool>;
     async fn clear_pattern(&self, pattern: &str) -> AppResult<u64>;
     async fn increment(&self, key: &str, delta: i64) -> AppResult<i64>;
@@ -205,6 +207,21 @@ impl CacheService for RedisCache {
         Ok(())
     }
 
+    async fn take_string(&self, key: &str) -> AppResult<Option<String>> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AppError::InternalServer(format!("Redis connection error: {}", e)))?;
+
+        let prefixed_key = self.prefixed_key(key);
+        cmd("GETDEL")
+            .arg(&prefixed_key)
+            .query_async::<Option<String>>(&mut conn)
+            .await
+            .map_err(|e| AppError::InternalServer(format!("Redis getdel error: {}", e)))
+    }
+
     async fn exists(&self, key: &str) -> AppResult<bool> {
         let mut conn = self
             .pool
@@ -365,6 +382,10 @@ impl CacheService for InMemoryCache {
         Ok(())
     }
 
+    async fn take_string(&self, key: &str) -> AppResult<Option<String>> {
+        Ok(self.cache.remove(key).await)
+    }
+
     async fn exists(&self, key: &str) -> AppResult<bool> {
         Ok(self.cache.contains_key(key))
     }
@@ -480,6 +501,13 @@ impl CacheService for LayeredCache {
         self.primary.delete(key).await
     }
 
+    async fn take_string(&self, key: &str) -> AppResult<Option<String>> {
+        match self.primary.take_string(key).await? {
+            some @ Some(_) => Ok(some),
+            None => self.fallback.take_string(key).await,
+        }
+    }
+
     async fn exists(&self, key: &str) -> AppResult<bool> {
         self.primary.exists(key).await
     }
@@ -585,6 +613,12 @@ impl CacheService for HybridCache {
         Ok(())
     }
 
+    async fn take_string(&self, key: &str) -> AppResult<Option<String>> {
+        // Redis decides the single winner; the memory copy is dropped either way
+        let _ = self.memory_cache.delete(key).await;
+        self.redis_cache.take_string(key).await
+    }
+
     async fn exists(&self, key: &str) -> AppResult<bool> {
         // Check memory cache first
         if self.memory_cache.exists(key).await? {
@@ -748,4 +782,26 @@ mod tests {
         let result = cache.decrement("counter", 2).await.unwrap();
         assert_eq!(result, 6);
     }
+
+    #[tokio::test]
+    async fn test_take_has_a_single_winner() {
+        let cache = Arc::new(InMemoryCache::new(1000, 3600));
+        cache.set_string("ticket", "token".to_string(), None).await.unwrap();
+
+        let takers: Vec<_> = (0..8)
+            .map(|_| {
+                let cache = cache.clone();
+                tokio::spawn(async move { cache.take_string("ticket").await.unwrap() })
+            })
+            .collect();
+        let mut winners = 0;
+        for taker in takers {
+            if let Some(value) = taker.await.unwrap() {
+                assert_eq!(value, "token");
+                winners += 1;
+            }
+        }
+        assert_eq!(winners, 1);
+        assert!(!cache.exists("ticket").await.unwrap());
+    }
 }
@@ -1,8 +1,9 @@
 /// Enhanced Application Container with PASETO integration
 /// Provides comprehensive dependency injection for all Terra Siaga components
 
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::env;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
 use actix_web::web;
 use futures_util::TryFutureExt;
 use diesel::r2d2::{Pool, ConnectionManager};
@@ -18,18 +19,64 @@ use crate::infrastructure::{
         EmailProvider, SmsProvider, WhatsAppProvider,
     },
     security::paseto_service::{PasetoSecurityService, PasetoConfig},
+    security::mfa_service::MfaService,
+    security::role_service::RoleService,
     repository::user_repository::PostgresUserRepository,
+    repository::disaster_repository::PostgresDisasterRepository,
+    repository::event_sourced_disaster_repository::EventSourcedDisasterRepository,
+    event_store::PostgresEventStore,
+    repository::location_repository::PostgresLocationRepository,
     repository::notification_repository::PostgresNotificationRepository,
+    repository::emergency_response_repository::PostgresEmergencyResponseRepository,
+    repository::resource_inventory_repository::PostgresResourceInventoryRepository,
+    repository::response_team_repository::PostgresResponseTeamRepository,
+    repository::emergency_station_repository::PostgresEmergencyStationRepository,
+    repository::disaster_zone_repository::PostgresDisasterZoneRepository,
+    repository::notification_outbox_repository::PostgresNotificationOutboxRepository,
+    repository::mfa_repository::PostgresMfaRepository,
+    repository::verification_code_repository::PostgresVerificationCodeRepository,
+    repository::session_repository::PostgresSessionRepository,
+    repository::organization_repository::PostgresOrganizationRepository,
+    repository::province_assignment_repository::PostgresProvinceAssignmentRepository,
+    repository::cap_alert_import_repository::PostgresCapAlertImportRepository,
+    repository::role_repository::PostgresRoleRepository,
+    repository::report_repository::PostgresReportRepository,
+    repository::volunteer_repository::PostgresVolunteerRepository,
+    repository::location_tracking_repository::PostgresLocationTrackingRepository,
+    repository::analytics_repository::PostgresAnalyticsRepository,
+    storage::build_media_storage,
+    external_services::weather::WeatherService as ExternalWeatherService,
+    external_services::{WeatherConfig, WeatherProvider},
+    outbox::NotificationOutboxWorker,
+    messaging::EventBusPublisher,
+    realtime::RealtimeHub,
+    routing::RoadRoutingService,
     database::DatabaseService,
 };
 use crate::domain::{
     ports::{
-        repositories::{UserRepository, DisasterRepository, NotificationRepository},
-        services::{NotificationService, GeolocationService, AuthService},
+        repositories::{
+            UserRepository, DisasterRepository, LocationRepository, NotificationRepository,
+            EmergencyResponseRepository, ResourceInventoryRepository,
+            ResponseTeamRepository, EmergencyStationRepository, DisasterZoneRepository,
+            NotificationOutboxRepository, MfaRepository, VerificationCodeRepository, SessionRepository,
+            OrganizationRepository, ProvinceAssignmentRepository, CapAlertImportRepository, RoleRepository, ReportRepository,
+            VolunteerRepository, LocationTrackingRepository, AnalyticsRepository,
+        },
+        services::{NotificationService, GeolocationService, AuthService, RoutingService, MediaStorage, WeatherService},
     },
     // removed value_objects::Coordinates import to avoid type mismatch
 };
 use crate::shared::{AppResult, AppError};
+use crate::shared::geo_utils::EmergencyZoneManager;
+use crate::shared::policy::PolicyEngine;
+use crate::shared::events::{EventBus, ProjectionManager};
+use crate::shared::rate_limiter::{
+    EmergencyBypass, RateLimitConfig, RateLimitGuard, RateLimitStrategy, RateLimiterFactory,
+};
+use crate::domain::services::credibility::CredibilityPolicy;
+use crate::domain::services::volunteer_matching::MatchingPolicy;
+use crate::domain::services::geofence::GeofencePolicy;
 use crate::application::use_cases::*;
 use crate::infrastructure::database::DbPool;
 use crate::infrastructure::monitoring::HealthMonitoringService;
@@ -38,26 +85,77 @@ use crate::infrastructure::monitoring::HealthMonitoringService;
 pub struct AppContainer {
     // Core services
     pub paseto_service: Arc<PasetoSecurityService>,
+    pub mfa_service: Arc<MfaService>,
     pub auth_service: Arc<dyn AuthService>,
     pub cache_service: Arc<dyn CacheService>,
     pub notification_service: Arc<dyn NotificationService>,
+    pub routing_service: Arc<dyn RoutingService>,
+    /// Safe zones and evacuation routes, timed over the same road graph as `routing_service`
+    pub emergency_zone_manager: Arc<RwLock<EmergencyZoneManager>>,
+    pub media_storage: Arc<dyn MediaStorage>,
+    pub weather_service: Option<Arc<dyn WeatherService>>,
     pub health_monitoring: HealthMonitoringService,
+    pub policy_engine: Arc<PolicyEngine>,
+    pub role_service: Arc<RoleService>,
 
     // Repositories
     pub user_repository: Arc<dyn UserRepository>,
     pub disaster_repository: Arc<dyn DisasterRepository>,
+    pub location_repository: Arc<dyn LocationRepository>,
     pub notification_repository: Arc<dyn NotificationRepository>,
+    pub notification_outbox_repository: Arc<dyn NotificationOutboxRepository>,
+    pub emergency_response_repository: Arc<dyn EmergencyResponseRepository>,
+    pub resource_inventory_repository: Arc<dyn ResourceInventoryRepository>,
+    pub response_team_repository: Arc<dyn ResponseTeamRepository>,
+    pub emergency_station_repository: Arc<dyn EmergencyStationRepository>,
+    pub organization_repository: Arc<dyn OrganizationRepository>,
+    pub province_assignment_repository: Arc<dyn ProvinceAssignmentRepository>,
+    pub report_repository: Arc<dyn ReportRepository>,
+    pub volunteer_repository: Arc<dyn VolunteerRepository>,
+    pub location_tracking_repository: Arc<dyn LocationTrackingRepository>,
+    pub analytics_repository: Arc<dyn AnalyticsRepository>,
+    pub event_store: Arc<PostgresEventStore>,
+
+    // Events
+    pub event_bus: Arc<EventBus>,
+    pub realtime_hub: Arc<RealtimeHub>,
+    pub projection_manager: Arc<ProjectionManager>,
+
+    // Request rate limits; `None` when rate limiting is disabled
+    pub rate_limit_guard: Option<Arc<RateLimitGuard>>,
 
     // Use cases
     pub register_user_use_case: Arc<RegisterUserUseCase>,
+    pub send_email_verification_use_case: Arc<SendEmailVerificationUseCase>,
+    pub verify_email_use_case: Arc<VerifyEmailUseCase>,
+    pub request_password_reset_use_case: Arc<RequestPasswordResetUseCase>,
+    pub reset_password_use_case: Arc<ResetPasswordUseCase>,
+    pub change_password_use_case: Arc<ChangePasswordUseCase>,
     pub update_user_profile_use_case: Arc<UpdateUserProfileUseCase>,
     pub change_user_status_use_case: Arc<ChangeUserStatusUseCase>,
     pub report_disaster_use_case: Arc<ReportDisasterUseCase>,
+    pub import_cap_alert_use_case: Arc<ImportCapAlertUseCase>,
     pub update_disaster_status_use_case: Arc<UpdateDisasterStatusUseCase>,
     pub get_nearby_disasters_use_case: Arc<GetNearbyDisastersUseCase>,
     pub dispatch_emergency_response_use_case: Arc<DispatchEmergencyResponseUseCase>,
+    pub update_emergency_response_status_use_case: Arc<UpdateEmergencyResponseStatusUseCase>,
     pub send_emergency_alert_use_case: Arc<SendEmergencyAlertUseCase>,
     pub send_custom_notification_use_case: Arc<SendCustomNotificationUseCase>,
+    pub submit_report_use_case: Arc<SubmitReportUseCase>,
+    pub update_report_use_case: Arc<UpdateReportUseCase>,
+    pub upload_report_media_use_case: Arc<UploadReportMediaUseCase>,
+    pub change_report_status_use_case: Arc<ChangeReportStatusUseCase>,
+    pub link_reports_to_disaster_use_case: Arc<LinkReportsToDisasterUseCase>,
+    pub assess_report_credibility_use_case: Arc<AssessReportCredibilityUseCase>,
+    pub register_volunteer_use_case: Arc<RegisterVolunteerUseCase>,
+    pub update_volunteer_use_case: Arc<UpdateVolunteerUseCase>,
+    pub assign_volunteer_use_case: Arc<AssignVolunteerUseCase>,
+    pub change_assignment_status_use_case: Arc<ChangeAssignmentStatusUseCase>,
+    pub match_volunteers_use_case: Arc<MatchVolunteersUseCase>,
+    pub record_location_fixes_use_case: Arc<RecordLocationFixesUseCase>,
+
+    // Background workers
+    pub notification_outbox_worker: Arc<NotificationOutboxWorker>,
 
     // Database pool for direct access if needed
     pub database_pool: Option<Arc<DatabaseService>>,
@@ -85,12 +183,36 @@ impl AppContainer {
 
         // Build PASETO security service
         let paseto_config = Self::build_paseto_config(config)?;
+        let session_repository: Arc<dyn SessionRepository> = if let Some(db_pool) = &database_pool {
+            Arc::new(PostgresSessionRepository::new(db_pool.pool().clone()))
+        } else {
+            return Err(AppError::Integration("Database pool is required for SessionRepository".to_string()));
+        };
+        // Role permissions live in the database; tokens carry them as scopes
+        let role_repository: Arc<dyn RoleRepository> = if let Some(db_pool) = &database_pool {
+            Arc::new(PostgresRoleRepository::new(db_pool.pool().clone()))
+        } else {
+            return Err(AppError::Integration("Database pool is required for RoleRepository".to_string()));
+        };
+        let role_service = Arc::new(RoleService::new(role_repository, cache_service.clone()));
         let paseto_service = PasetoSecurityService::new(
             paseto_config,
             cache_service.clone(),
-        )?;
+        )?
+            .with_session_index(session_repository)
+            .with_role_service(role_service.clone());
         let paseto_service = Arc::new(paseto_service);
 
+        // Access policies refine role permissions; a broken policy file stops startup
+        let policy_engine = Arc::new(match &config.auth.policy_file {
+            Some(path) => {
+                let engine = PolicyEngine::from_file(path)?;
+                tracing::info!("Loaded {} access policies from {}", engine.policies().len(), path);
+                engine
+            }
+            None => PolicyEngine::builtin(),
+        });
+
         // Build JWT-based auth service for password operations and legacy flows
         let jwt_auth_service: Arc<dyn crate::domain::ports::services::AuthService> = Arc::new(
             crate::infrastructure::security::service::ProductionSecurityService::new(
@@ -108,12 +230,140 @@ impl AppContainer {
         } else {
             return Err(AppError::Integration("Database pool is required for UserRepository".to_string()));
         };
-        let disaster_repository = Self::create_placeholder_disaster_repository();
+        let event_store = if let Some(db_pool) = &database_pool {
+            Arc::new(PostgresEventStore::new(db_pool.pool().clone()))
+        } else {
+            return Err(AppError::Integration("Database pool is required for EventStore".to_string()));
+        };
+        let event_bus = Arc::new(EventBus::new(event_store.clone()));
+        let event_publisher: Arc<dyn crate::domain::events::EventPublisher> =
+            Arc::new(EventBusPublisher::new(event_bus.clone()));
+        let realtime_hub = Self::build_realtime_hub(config).await;
+        let disaster_repository: Arc<dyn DisasterRepository> = if let Some(db_pool) = &database_pool {
+            Arc::new(EventSourcedDisasterRepository::new(
+                Arc::new(PostgresDisasterRepository::new(db_pool.pool().clone())),
+                event_store.clone(),
+                event_store.clone(),
+            ).with_event_bus(event_bus.clone()))
+        } else {
+            return Err(AppError::Integration("Database pool is required for DisasterRepository".to_string()));
+        };
+        let location_repository: Arc<dyn LocationRepository> = if let Some(db_pool) = &database_pool {
+            Arc::new(PostgresLocationRepository::new(db_pool.pool().clone()))
+        } else {
+            return Err(AppError::Integration("Database pool is required for LocationRepository".to_string()));
+        };
         let notification_repository: Arc<dyn NotificationRepository> = if let Some(db_pool) = &database_pool {
             Arc::new(PostgresNotificationRepository::new(db_pool.pool().clone()))
         } else {
             return Err(AppError::Integration("Database pool is required for NotificationRepository".to_string()));
         };
+        let notification_outbox_repository: Arc<dyn NotificationOutboxRepository> = if let Some(db_pool) = &database_pool {
+            Arc::new(PostgresNotificationOutboxRepository::new(
+                db_pool.pool().clone(),
+                config.notification_outbox.max_attempts,
+            ))
+        } else {
+            return Err(AppError::Integration("Database pool is required for NotificationOutboxRepository".to_string()));
+        };
+        let mfa_repository: Arc<dyn MfaRepository> = if let Some(db_pool) = &database_pool {
+            Arc::new(PostgresMfaRepository::new(db_pool.pool().clone()))
+        } else {
+            return Err(AppError::Integration("Database pool is required for MfaRepository".to_string()));
+        };
+        let verification_code_repository: Arc<dyn VerificationCodeRepository> = if let Some(db_pool) = &database_pool {
+            Arc::new(PostgresVerificationCodeRepository::new(db_pool.pool().clone()))
+        } else {
+            return Err(AppError::Integration("Database pool is required for VerificationCodeRepository".to_string()));
+        };
+        let mfa_service = Arc::new(MfaService::new(
+            mfa_repository,
+            cache_service.clone(),
+            &config.auth.mfa_encryption_key,
+            config.auth.mfa_issuer.clone(),
+        )?);
+
+        let emergency_response_repository: Arc<dyn EmergencyResponseRepository> = if let Some(db_pool) = &database_pool {
+            Arc::new(PostgresEmergencyResponseRepository::new(db_pool.pool().clone()))
+        } else {
+            return Err(AppError::Integration("Database pool is required for EmergencyResponseRepository".to_string()));
+        };
+        let resource_inventory_repository: Arc<dyn ResourceInventoryRepository> = if let Some(db_pool) = &database_pool {
+            Arc::new(PostgresResourceInventoryRepository::new(db_pool.pool().clone()))
+        } else {
+            return Err(AppError::Integration("Database pool is required for ResourceInventoryRepository".to_string()));
+        };
+        let response_team_repository: Arc<dyn ResponseTeamRepository> = if let Some(db_pool) = &database_pool {
+            Arc::new(PostgresResponseTeamRepository::new(db_pool.pool().clone()))
+        } else {
+            return Err(AppError::Integration("Database pool is required for ResponseTeamRepository".to_string()));
+        };
+        let emergency_station_repository: Arc<dyn EmergencyStationRepository> = if let Some(db_pool) = &database_pool {
+            Arc::new(PostgresEmergencyStationRepository::new(db_pool.pool().clone()))
+        } else {
+            return Err(AppError::Integration("Database pool is required for EmergencyStationRepository".to_string()));
+        };
+        let organization_repository: Arc<dyn OrganizationRepository> = if let Some(db_pool) = &database_pool {
+            Arc::new(PostgresOrganizationRepository::new(db_pool.pool().clone()))
+        } else {
+            return Err(AppError::Integration("Database pool is required for OrganizationRepository".to_string()));
+        };
+        let province_assignment_repository: Arc<dyn ProvinceAssignmentRepository> = if let Some(db_pool) = &database_pool {
+            Arc::new(PostgresProvinceAssignmentRepository::new(db_pool.pool().clone()))
+        } else {
+            return Err(AppError::Integration("Database pool is required for ProvinceAssignmentRepository".to_string()));
+        };
+        let cap_alert_import_repository: Arc<dyn CapAlertImportRepository> = if let Some(db_pool) = &database_pool {
+            Arc::new(PostgresCapAlertImportRepository::new(db_pool.pool().clone()))
+        } else {
+            return Err(AppError::Integration("Database pool is required for CapAlertImportRepository".to_string()));
+        };
+        let disaster_zone_repository: Arc<dyn DisasterZoneRepository> = if let Some(db_pool) = &database_pool {
+            Arc::new(PostgresDisasterZoneRepository::new(db_pool.pool().clone()))
+        } else {
+            return Err(AppError::Integration("Database pool is required for DisasterZoneRepository".to_string()));
+        };
+        let report_repository: Arc<dyn ReportRepository> = if let Some(db_pool) = &database_pool {
+            Arc::new(PostgresReportRepository::new(db_pool.pool().clone()))
+        } else {
+            return Err(AppError::Integration("Database pool is required for ReportRepository".to_string()));
+        };
+        let volunteer_repository: Arc<dyn VolunteerRepository> = if let Some(db_pool) = &database_pool {
+            Arc::new(PostgresVolunteerRepository::new(db_pool.pool().clone()))
+        } else {
+            return Err(AppError::Integration("Database pool is required for VolunteerRepository".to_string()));
+        };
+        let location_tracking_repository: Arc<dyn LocationTrackingRepository> = if let Some(db_pool) = &database_pool {
+            Arc::new(PostgresLocationTrackingRepository::new(db_pool.pool().clone()))
+        } else {
+            return Err(AppError::Integration("Database pool is required for LocationTrackingRepository".to_string()));
+        };
+
+        // Dashboard read model, kept current by projecting disaster events
+        let analytics_projection = if let Some(db_pool) = &database_pool {
+            Arc::new(PostgresAnalyticsRepository::new(db_pool.pool().clone(), disaster_repository.clone()))
+        } else {
+            return Err(AppError::Integration("Database pool is required for AnalyticsRepository".to_string()));
+        };
+        let mut projection_manager = ProjectionManager::new(event_store.clone());
+        projection_manager.add_projection(analytics_projection.clone());
+        let projection_manager = Arc::new(projection_manager);
+        let analytics_repository: Arc<dyn AnalyticsRepository> = analytics_projection;
+
+        // Report photos and videos, on local disk or an S3-compatible bucket
+        let media_storage = build_media_storage(&config.media_storage, &config.auth.public_base_url)?;
+
+        // Live weather for report credibility; reports are scored without it when disabled
+        let weather_service = Self::build_weather_service(config);
+
+        // Road routing over the offline graph, closing roads inside active disaster zones
+        let road_routing = RoadRoutingService::from_config(&config.routing, disaster_zone_repository.clone()).await;
+        let emergency_zone_manager = match road_routing.engine() {
+            Some(engine) => EmergencyZoneManager::new().with_routing_engine(engine),
+            None => EmergencyZoneManager::new(),
+        };
+        let emergency_zone_manager = Arc::new(RwLock::new(emergency_zone_manager));
+        let routing_service: Arc<dyn RoutingService> = Arc::new(road_routing);
 
         // Build external services
         let notification_service = Self::build_notification_service(config)?;
@@ -147,29 +397,76 @@ impl AppContainer {
         // Build use cases
         let register_user_use_case = Arc::new(RegisterUserUseCase::new(
             user_repository.clone(),
+            location_repository.clone(),
             jwt_auth_service.clone(),
             notification_service.clone(),
-            Self::create_placeholder_event_publisher(),
+            event_publisher.clone(),
+        ));
+
+        let verification_code_issuer = Arc::new(VerificationCodeIssuer::new(
+            verification_code_repository.clone(),
+            notification_service.clone(),
+            VerificationPolicy {
+                email_verification_ttl: chrono::Duration::minutes(config.auth.email_verification_ttl_minutes),
+                password_reset_ttl: chrono::Duration::minutes(config.auth.password_reset_ttl_minutes),
+                max_codes_per_hour: config.auth.verification_codes_per_hour,
+                public_base_url: config.auth.public_base_url.clone(),
+            },
+        ));
+        let send_email_verification_use_case = Arc::new(SendEmailVerificationUseCase::new(
+            user_repository.clone(),
+            verification_code_issuer.clone(),
+        ));
+        let verify_email_use_case = Arc::new(VerifyEmailUseCase::new(
+            verification_code_repository.clone(),
+            user_repository.clone(),
+        ));
+        let request_password_reset_use_case = Arc::new(RequestPasswordResetUseCase::new(
+            user_repository.clone(),
+            verification_code_issuer,
+        ));
+        let reset_password_use_case = Arc::new(ResetPasswordUseCase::new(
+            verification_code_repository,
+            user_repository.clone(),
+            jwt_auth_service.clone(),
+            notification_service.clone(),
+            paseto_service.clone(),
+        ));
+        let change_password_use_case = Arc::new(ChangePasswordUseCase::new(
+            user_repository.clone(),
+            jwt_auth_service.clone(),
+            notification_service.clone(),
+            paseto_service.clone(),
         ));
 
         let update_user_profile_use_case = Arc::new(UpdateUserProfileUseCase::new(
             user_repository.clone(),
+            location_repository.clone(),
         ));
 
         let change_user_status_use_case = Arc::new(ChangeUserStatusUseCase::new(
             user_repository.clone(),
-            Self::create_placeholder_event_publisher(),
+            event_publisher.clone(),
         ));
 
         let report_disaster_use_case = Arc::new(ReportDisasterUseCase::new(
             disaster_repository.clone(),
             notification_service.clone(),
-            Self::create_placeholder_event_publisher(),
+            event_publisher.clone(),
+        ));
+
+        let import_cap_alert_use_case = Arc::new(ImportCapAlertUseCase::new(
+            report_disaster_use_case.clone(),
+            disaster_repository.clone(),
+            cap_alert_import_repository,
+            event_publisher.clone(),
         ));
 
         let update_disaster_status_use_case = Arc::new(UpdateDisasterStatusUseCase::new(
             disaster_repository.clone(),
-            Self::create_placeholder_event_publisher(),
+            province_assignment_repository.clone(),
+            policy_engine.clone(),
+            event_publisher.clone(),
         ));
 
         let get_nearby_disasters_use_case = Arc::new(GetNearbyDisastersUseCase::new(
@@ -178,53 +475,240 @@ impl AppContainer {
 
         let dispatch_emergency_response_use_case = Arc::new(DispatchEmergencyResponseUseCase::new(
             disaster_repository.clone(),
+            emergency_response_repository.clone(),
+            response_team_repository.clone(),
             user_repository.clone(),
             notification_service.clone(),
             Self::create_placeholder_geo_service(),
-            Self::create_placeholder_event_publisher(),
+            routing_service.clone(),
+            event_publisher.clone(),
+        ));
+
+        let update_emergency_response_status_use_case = Arc::new(UpdateEmergencyResponseStatusUseCase::new(
+            emergency_response_repository.clone(),
+            response_team_repository.clone(),
+            policy_engine.clone(),
         ));
 
         let send_emergency_alert_use_case = Arc::new(SendEmergencyAlertUseCase::new(
-            notification_repository.clone(),
+            notification_outbox_repository.clone(),
             user_repository.clone(),
             disaster_repository.clone(),
-            notification_service.clone(),
+            disaster_zone_repository.clone(),
             Self::create_placeholder_geo_service(),
-            Self::create_placeholder_event_publisher(),
+            event_publisher.clone(),
         ));
 
         let send_custom_notification_use_case = Arc::new(SendCustomNotificationUseCase::new(
-            notification_repository.clone(),
+            notification_outbox_repository.clone(),
             user_repository.clone(),
+        ));
+
+        let assess_report_credibility_use_case = Arc::new(AssessReportCredibilityUseCase::new(
+            report_repository.clone(),
+            weather_service.clone(),
+            CredibilityPolicy {
+                radius_m: config.report_triage.cluster_radius_m,
+                window: chrono::Duration::minutes(config.report_triage.cluster_window_minutes),
+                ..CredibilityPolicy::default()
+            },
+        ));
+        let max_upload_bytes = config.media_storage.max_upload_bytes;
+        let submit_report_use_case = Arc::new(SubmitReportUseCase::new(
+            report_repository.clone(),
+            media_storage.clone(),
+            assess_report_credibility_use_case.clone(),
+            max_upload_bytes,
+        ));
+        let update_report_use_case = Arc::new(UpdateReportUseCase::new(
+            report_repository.clone(),
+            policy_engine.clone(),
+        ));
+        let upload_report_media_use_case = Arc::new(UploadReportMediaUseCase::new(
+            report_repository.clone(),
+            media_storage.clone(),
+            policy_engine.clone(),
+            assess_report_credibility_use_case.clone(),
+            max_upload_bytes,
+        ));
+        let change_report_status_use_case = Arc::new(ChangeReportStatusUseCase::new(
+            report_repository.clone(),
+            policy_engine.clone(),
+        ));
+        let link_reports_to_disaster_use_case = Arc::new(LinkReportsToDisasterUseCase::new(
+            report_repository.clone(),
+            disaster_repository.clone(),
+            policy_engine.clone(),
+        ));
+
+        let register_volunteer_use_case = Arc::new(RegisterVolunteerUseCase::new(volunteer_repository.clone()));
+        let update_volunteer_use_case = Arc::new(UpdateVolunteerUseCase::new(volunteer_repository.clone()));
+        let assign_volunteer_use_case = Arc::new(AssignVolunteerUseCase::new(
+            volunteer_repository.clone(),
+            report_repository.clone(),
+            disaster_repository.clone(),
             notification_service.clone(),
-            Self::create_placeholder_event_publisher(),
+            policy_engine.clone(),
+        ));
+        let change_assignment_status_use_case = Arc::new(ChangeAssignmentStatusUseCase::new(
+            volunteer_repository.clone(),
+            policy_engine.clone(),
+        ));
+        let match_volunteers_use_case = Arc::new(MatchVolunteersUseCase::new(
+            volunteer_repository.clone(),
+            disaster_repository.clone(),
+            notification_service.clone(),
+            policy_engine.clone(),
+            MatchingPolicy {
+                max_distance_m: config.volunteer_matching.max_distance_km * 1000.0,
+                max_shift_hours: config.volunteer_matching.max_shift_hours,
+                ..MatchingPolicy::default()
+            },
+        ));
+        let record_location_fixes_use_case = Arc::new(RecordLocationFixesUseCase::new(
+            location_tracking_repository.clone(),
+            volunteer_repository.clone(),
+            report_repository.clone(),
+            disaster_repository.clone(),
+            response_team_repository.clone(),
+            emergency_response_repository.clone(),
+            policy_engine.clone(),
+            GeofencePolicy {
+                arrival_radius_m: config.location_tracking.arrival_radius_m,
+                max_accuracy_m: config.location_tracking.max_arrival_accuracy_m,
+            },
+            config.location_tracking.max_batch_size,
         ));
 
+        // Delivery workers are started by the server once the container is built
+        let notification_outbox_worker = Arc::new(NotificationOutboxWorker::new(
+            notification_outbox_repository.clone(),
+            user_repository.clone(),
+            notification_service.clone(),
+            event_publisher,
+            config.notification_outbox.clone(),
+        ));
+
+        let rate_limit_guard = Self::build_rate_limit_guard(config, disaster_repository.clone()).await;
+
         tracing::info!("Application container built successfully");
 
         Ok(AppContainer {
             paseto_service,
+            mfa_service,
             auth_service: jwt_auth_service,
             cache_service,
             notification_service,
+            routing_service,
+            emergency_zone_manager,
+            media_storage,
+            weather_service,
             health_monitoring,
+            policy_engine,
+            role_service,
             user_repository,
             disaster_repository,
+            location_repository,
             notification_repository,
+            notification_outbox_repository,
+            emergency_response_repository,
+            resource_inventory_repository,
+            response_team_repository,
+            emergency_station_repository,
+            organization_repository,
+            province_assignment_repository,
+            report_repository,
+            volunteer_repository,
+            location_tracking_repository,
+            analytics_repository,
+            event_store,
+            event_bus,
+            realtime_hub,
+            projection_manager,
+            rate_limit_guard,
             register_user_use_case,
+            send_email_verification_use_case,
+            verify_email_use_case,
+            request_password_reset_use_case,
+            reset_password_use_case,
+            change_password_use_case,
             update_user_profile_use_case,
             change_user_status_use_case,
             report_disaster_use_case,
+            import_cap_alert_use_case,
             update_disaster_status_use_case,
             get_nearby_disasters_use_case,
             dispatch_emergency_response_use_case,
+            update_emergency_response_status_use_case,
             send_emergency_alert_use_case,
             send_custom_notification_use_case,
+            submit_report_use_case,
+            update_report_use_case,
+            upload_report_media_use_case,
+            change_report_status_use_case,
+            link_reports_to_disaster_use_case,
+            assess_report_credibility_use_case,
+            register_volunteer_use_case,
+            update_volunteer_use_case,
+            assign_volunteer_use_case,
+            change_assignment_status_use_case,
+            match_volunteers_use_case,
+            record_location_fixes_use_case,
+            notification_outbox_worker,
             database_pool,
             config: config.clone(),
         })
     }
 
+    /// Build the realtime hub, sharing events over Redis when it is configured and reachable
+    async fn build_realtime_hub(config: &AppConfig) -> Arc<RealtimeHub> {
+        let realtime = &config.realtime;
+        if config.redis.url.is_empty() {
+            return Arc::new(RealtimeHub::local(realtime.buffer_size));
+        }
+        match RealtimeHub::with_redis(realtime.buffer_size, &config.redis.url, &realtime.redis_channel).await {
+            Ok(hub) => Arc::new(hub),
+            Err(e) => {
+                tracing::warn!("Realtime events stay on this instance, Redis fan-out unavailable: {}", e);
+                Arc::new(RealtimeHub::local(realtime.buffer_size))
+            }
+        }
+    }
+
+    /// Build the request rate limits, counted in Redis when it is configured and reachable
+    async fn build_rate_limit_guard(config: &AppConfig, disasters: Arc<dyn DisasterRepository>) -> Option<Arc<RateLimitGuard>> {
+        let settings = &config.rate_limiting;
+        if !settings.enabled {
+            return None;
+        }
+        let key_prefix = format!("terra_siaga:{}:rate_limit", config.environment);
+        let rate_limiter = RateLimiterFactory::create(Some(&config.redis.url), &key_prefix).await;
+        let limits = RateLimitConfig {
+            default_strategy: RateLimitStrategy::FixedWindow {
+                requests: settings.requests_per_minute,
+                window: std::time::Duration::from_secs(60),
+            },
+            ..RateLimitConfig::default()
+        };
+        let emergency = settings.emergency_bypass.then(|| {
+            EmergencyBypass::new(disasters, std::time::Duration::from_secs(settings.emergency_check_seconds))
+        });
+        Some(Arc::new(RateLimitGuard::new(rate_limiter, limits, emergency)))
+    }
+
+    /// Build the weather client used to cross-check reports, if the integration is enabled
+    fn build_weather_service(config: &AppConfig) -> Option<Arc<dyn WeatherService>> {
+        if !config.features.enable_weather_integration || config.external_apis.weather_api_key.is_empty() {
+            return None;
+        }
+        Some(Arc::new(ExternalWeatherService::new(WeatherConfig {
+            provider: WeatherProvider::OpenWeatherMap,
+            api_key: config.external_apis.weather_api_key.clone(),
+            timeout: std::time::Duration::from_secs(5),
+            cache_duration: std::time::Duration::from_secs(300),
+        })))
+    }
+
     /// Build cache service based on configuration
     async fn build_cache_service(config: &AppConfig) -> AppResult<Arc<dyn CacheService>> {
         let cache_config = CacheConfig {
@@ -244,15 +728,22 @@ impl AppContainer {
 
     /// Build PASETO configuration
     fn build_paseto_config(config: &AppConfig) -> AppResult<PasetoConfig> {
-        // Get security key from environment or use default
-        let secret_key = env::var("TERRA_SIAGA_SECRET_KEY")
-            .unwrap_or_else(|_| "default-secret-key-change-in-production".to_string());
+        let keys = &config.paseto;
+        let mut local_key = keys.local_key.clone();
+        if local_key.is_none() {
+            // Older deployments derived the local key from the first 32 bytes of this secret
+            if let Ok(secret) = env::var("TERRA_SIAGA_SECRET_KEY") {
+                if let Some(bytes) = secret.as_bytes().get(..32) {
+                    local_key = Some(format!("k4.local.{}", URL_SAFE_NO_PAD.encode(bytes)));
+                }
+            }
+        }
 
         let paseto_config = if config.is_production() {
             PasetoConfig {
-                local_key: secret_key.as_bytes()[..32.min(secret_key.len())].to_vec(),
-                public_key: vec![], // Would be loaded from secure storage
-                private_key: vec![], // Would be loaded from secure storage
+                local_key,
+                secret_key: keys.secret_key.clone(),
+                previous_keys: keys.previous_keys.clone(),
                 token_expiration_hours: env::var("JWT_EXPIRATION_HOURS")
                     .unwrap_or_else(|_| "24".to_string())
                     .parse()
@@ -262,7 +753,7 @@ impl AppContainer {
                     .parse()
                     .unwrap_or(8),
                 elevated_session_minutes: 15,
-                use_local_tokens: true, // Use encrypted tokens in production
+                use_local_tokens: keys.uses_local_tokens(),
                 access_token_ttl_minutes: env::var("ACCESS_TOKEN_TTL_MINUTES")
                     .unwrap_or_else(|_| "15".to_string())
                     .parse()
@@ -273,9 +764,29 @@ impl AppContainer {
                     .unwrap_or(7),
             }
         } else {
-            PasetoConfig::default()
+            // Configured keys win; the generated defaults only cover what is missing
+            let defaults = PasetoConfig::default();
+            PasetoConfig {
+                local_key: local_key.or(defaults.local_key.clone()),
+                secret_key: keys.secret_key.clone().or(defaults.secret_key.clone()),
+                previous_keys: keys.previous_keys.clone(),
+                use_local_tokens: keys.uses_local_tokens(),
+                ..defaults
+            }
         };
 
+        let active_key = if paseto_config.use_local_tokens { &paseto_config.local_key } else { &paseto_config.secret_key };
+        if active_key.is_none() {
+            return Err(AppError::Configuration(format!(
+                "{} is required for {} PASETO tokens; generate one with `terra-siaga generate-paseto-keys`",
+                if paseto_config.use_local_tokens { "PASETO_LOCAL_KEY" } else { "PASETO_SECRET_KEY" },
+                keys.purpose,
+            )));
+        }
+        if !config.is_production() && keys.secret_key.is_none() && keys.local_key.is_none() {
+            tracing::warn!("No PASETO keys configured; using ephemeral keys that are lost on restart");
+        }
+
         Ok(paseto_config)
     }
 
@@ -338,72 +849,6 @@ impl AppContainer {
     }
 
     // Placeholder implementations - these would be replaced with actual implementations
-    fn create_placeholder_disaster_repository() -> Arc<dyn DisasterRepository> {
-        use crate::domain::ports::repositories::DisasterRepository;
-        use async_trait::async_trait;
-        use crate::domain::entities::disaster::Disaster;
-        use crate::domain::value_objects::*;
-
-        struct PlaceholderDisasterRepository;
-
-        #[async_trait]
-        impl DisasterRepository for PlaceholderDisasterRepository {
-            async fn find_by_id(&self, _id: &DisasterId) -> AppResult<Option<Disaster>> {
-                Ok(None)
-            }
-
-            async fn save(&self, disaster: &Disaster) -> AppResult<Disaster> {
-                Ok(disaster.clone())
-            }
-
-            async fn update(&self, disaster: &Disaster) -> AppResult<Disaster> {
-                Ok(disaster.clone())
-            }
-
-            async fn delete(&self, _id: &DisasterId) -> AppResult<bool> {
-                Ok(false)
-            }
-
-            async fn find_all(&self) -> AppResult<Vec<Disaster>> {
-                Ok(vec![])
-            }
-
-            async fn find_by_status(&self, _status: crate::domain::entities::disaster::DisasterStatus) -> AppResult<Vec<Disaster>> {
-                Ok(vec![])
-            }
-
-            async fn find_by_severity(&self, _severity: crate::domain::entities::disaster::DisasterSeverity) -> AppResult<Vec<Disaster>> {
-                Ok(vec![])
-            }
-
-            async fn find_by_reporter(&self, _reporter_id: &UserId) -> AppResult<Vec<Disaster>> {
-                Ok(vec![])
-            }
-
-            async fn find_nearby(&self, _lat: f64, _lng: f64, _radius_km: f64) -> AppResult<Vec<Disaster>> {
-                Ok(vec![])
-            }
-
-            async fn find_active(&self) -> AppResult<Vec<Disaster>> {
-                Ok(vec![])
-            }
-
-            async fn update_status(&self, _id: &DisasterId, _status: crate::domain::entities::disaster::DisasterStatus) -> AppResult<bool> {
-                Ok(true)
-            }
-
-            async fn assign_responder(&self, _disaster_id: &DisasterId, _responder_id: &UserId) -> AppResult<bool> {
-                Ok(true)
-            }
-
-            async fn find_by_location(&self, _location_id: &LocationId) -> AppResult<Vec<Disaster>> {
-                Ok(vec![])
-            }
-        }
-
-        Arc::new(PlaceholderDisasterRepository)
-    }
-
     fn create_placeholder_notification_repository() -> Arc<dyn NotificationRepository> {
         // removed: now using real PostgresNotificationRepository in build()
         use crate::domain::ports::repositories::NotificationRepository;
@@ -439,26 +884,6 @@ impl AppContainer {
         Arc::new(PlaceholderNotificationRepository)
     }
 
-    fn create_placeholder_event_publisher() -> Arc<dyn crate::domain::events::EventPublisher> {
-        use crate::domain::events::{EventPublisher, DomainEvent};
-        use async_trait::async_trait;
-
-        struct PlaceholderEventPublisher;
-
-        #[async_trait]
-        impl EventPublisher for PlaceholderEventPublisher {
-            async fn publish(&self, _event: &dyn DomainEvent) -> AppResult<()> {
-                Ok(())
-            }
-
-            async fn publish_batch(&self, _events: &[&dyn DomainEvent]) -> AppResult<()> {
-                Ok(())
-            }
-        }
-
-        Arc::new(PlaceholderEventPublisher)
-    }
-
     fn create_placeholder_geo_service() -> Arc<dyn GeolocationService> {
         use async_trait::async_trait;
         use crate::shared::types::Coordinates;
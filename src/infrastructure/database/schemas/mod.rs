@@ -10,16 +10,61 @@ pub mod sql_types {
     pub struct Geometry;
 }
 
+diesel::table! {
+    aggregate_snapshots (aggregate_id) {
+        aggregate_id -> Uuid,
+        aggregate_type -> Text,
+        version -> Int8,
+        state -> Jsonb,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::Geography;
+
+    analytics_disaster_facts (disaster_id) {
+        disaster_id -> Uuid,
+        disaster_type -> Text,
+        severity -> Text,
+        status -> Text,
+        position -> Geography,
+        province -> Nullable<Text>,
+        city -> Nullable<Text>,
+        reported_at -> Timestamp,
+        verified_at -> Nullable<Timestamp>,
+        first_response_at -> Nullable<Timestamp>,
+        resolved_at -> Nullable<Timestamp>,
+        closed_at -> Nullable<Timestamp>,
+        projected_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     auth_sessions (id) {
         id -> Uuid,
         user_id -> Nullable<Uuid>,
-        token -> Text,
+        token -> Nullable<Text>,
         user_agent -> Nullable<Text>,
         ip_address -> Nullable<Text>,
         expires_at -> Timestamp,
         created_at -> Nullable<Timestamp>,
         updated_at -> Nullable<Timestamp>,
+        device_fingerprint -> Nullable<Text>,
+        revoked_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    cap_alert_imports (id) {
+        id -> Uuid,
+        sender -> Text,
+        identifier -> Text,
+        msg_type -> Text,
+        disaster_id -> Nullable<Uuid>,
+        imported_by -> Nullable<Uuid>,
+        imported_at -> Timestamptz,
     }
 }
 
@@ -31,6 +76,10 @@ diesel::table! {
         economic_loss_estimation -> Nullable<Int8>,
         infrastructure_damage -> Nullable<Text>,
         updated_at -> Nullable<Timestamp>,
+        casualties -> Nullable<Int4>,
+        injuries -> Nullable<Int4>,
+        displaced_people -> Nullable<Int4>,
+        damaged_buildings -> Nullable<Int4>,
     }
 }
 
@@ -54,6 +103,8 @@ diesel::table! {
         disaster_id -> Uuid,
         report_id -> Uuid,
         created_at -> Nullable<Timestamp>,
+        linked_by -> Nullable<Uuid>,
+        is_origin -> Bool,
     }
 }
 
@@ -99,6 +150,19 @@ diesel::table! {
         primary_location_id -> Nullable<Uuid>,
         created_at -> Nullable<Timestamp>,
         updated_at -> Nullable<Timestamp>,
+        disaster_type_other -> Nullable<Text>,
+        priority -> Int4,
+        assigned_responders -> Array<Uuid>,
+        resource_needs -> Jsonb,
+        reported_at -> Nullable<Timestamp>,
+        verified_at -> Nullable<Timestamp>,
+        first_response_at -> Nullable<Timestamp>,
+        closed_at -> Nullable<Timestamp>,
+        is_verified -> Bool,
+        verified_by -> Nullable<Uuid>,
+        verification_method -> Nullable<Text>,
+        confidence_score -> Nullable<Float4>,
+        version -> Int8,
     }
 }
 
@@ -118,6 +182,65 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    emergency_response_status_history (id) {
+        id -> Int8,
+        response_id -> Uuid,
+        from_status -> Nullable<Text>,
+        to_status -> Text,
+        changed_by -> Uuid,
+        latitude -> Nullable<Float8>,
+        longitude -> Nullable<Float8>,
+        notes -> Nullable<Text>,
+        changed_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    emergency_responses (id) {
+        id -> Uuid,
+        disaster_id -> Uuid,
+        response_type -> Text,
+        priority -> Int2,
+        status -> Text,
+        dispatched_by -> Uuid,
+        assigned_teams -> Array<Uuid>,
+        personnel_count -> Int4,
+        equipment -> Array<Text>,
+        destination_latitude -> Float8,
+        destination_longitude -> Float8,
+        last_latitude -> Nullable<Float8>,
+        last_longitude -> Nullable<Float8>,
+        estimated_arrival -> Nullable<Timestamptz>,
+        estimated_completion -> Nullable<Timestamptz>,
+        notes -> Nullable<Text>,
+        dispatched_at -> Timestamptz,
+        en_route_at -> Nullable<Timestamptz>,
+        on_scene_at -> Nullable<Timestamptz>,
+        completed_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        version -> Int8,
+    }
+}
+
+diesel::table! {
+    emergency_stations (id) {
+        id -> Uuid,
+        name -> Text,
+        station_type -> Text,
+        latitude -> Float8,
+        longitude -> Float8,
+        address -> Nullable<Text>,
+        contact_phone -> Nullable<Text>,
+        staff_count -> Int4,
+        vehicles -> Array<Text>,
+        is_operational -> Bool,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     evacuation_center_facilities (id) {
         id -> Uuid,
@@ -146,6 +269,21 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    event_store (event_id) {
+        sequence -> Int8,
+        event_id -> Uuid,
+        aggregate_id -> Uuid,
+        aggregate_type -> Text,
+        event_type -> Text,
+        event_version -> Int8,
+        event_data -> Jsonb,
+        metadata -> Jsonb,
+        occurred_at -> Timestamptz,
+        created_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::Geography;
@@ -161,6 +299,59 @@ diesel::table! {
         address -> Nullable<Text>,
         created_at -> Nullable<Timestamp>,
         updated_at -> Nullable<Timestamp>,
+        details -> Jsonb,
+    }
+}
+
+diesel::table! {
+    mfa_recovery_codes (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        #[max_length = 64]
+        code_hash -> Varchar,
+        used_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    notification_delivery_attempts (id) {
+        id -> Uuid,
+        outbox_id -> Uuid,
+        #[max_length = 20]
+        channel -> Varchar,
+        attempted_at -> Timestamptz,
+        success -> Bool,
+        error_message -> Nullable<Text>,
+        response_details -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    notification_outbox (id) {
+        id -> Uuid,
+        notification_id -> Uuid,
+        recipient_id -> Uuid,
+        #[max_length = 20]
+        channel -> Varchar,
+        #[max_length = 50]
+        notification_type -> Varchar,
+        title -> Text,
+        message -> Text,
+        priority -> Int2,
+        #[max_length = 20]
+        status -> Varchar,
+        attempts -> Int4,
+        max_attempts -> Int4,
+        next_attempt_at -> Timestamptz,
+        locked_by -> Nullable<Text>,
+        locked_until -> Nullable<Timestamptz>,
+        last_error -> Nullable<Text>,
+        expires_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        sent_at -> Nullable<Timestamptz>,
+        dead_lettered_at -> Nullable<Timestamptz>,
     }
 }
 
@@ -254,6 +445,10 @@ diesel::table! {
         is_primary -> Nullable<Bool>,
         created_at -> Nullable<Timestamp>,
         updated_at -> Nullable<Timestamp>,
+        storage_key -> Nullable<Text>,
+        content_type -> Nullable<Text>,
+        size_bytes -> Nullable<Int8>,
+        uploaded_by -> Nullable<Uuid>,
     }
 }
 
@@ -282,6 +477,8 @@ diesel::table! {
         validation_date -> Nullable<Timestamp>,
         created_at -> Nullable<Timestamp>,
         updated_at -> Nullable<Timestamp>,
+        credibility_factors -> Nullable<Jsonb>,
+        cluster_id -> Nullable<Uuid>,
     }
 }
 
@@ -299,6 +496,46 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    response_teams (id) {
+        id -> Uuid,
+        station_id -> Nullable<Uuid>,
+        name -> Text,
+        team_type -> Text,
+        latitude -> Float8,
+        longitude -> Float8,
+        location_updated_at -> Timestamptz,
+        personnel -> Int4,
+        vehicles -> Array<Text>,
+        availability -> Text,
+        current_response_id -> Nullable<Uuid>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        version -> Int8,
+    }
+}
+
+diesel::table! {
+    role_audit_log (id) {
+        id -> Uuid,
+        actor_id -> Nullable<Uuid>,
+        action -> Text,
+        role_id -> Int4,
+        role_name -> Text,
+        target_user_id -> Nullable<Uuid>,
+        details -> Jsonb,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    role_permissions (role_id, permission) {
+        role_id -> Int4,
+        permission -> Text,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     roles (id) {
         id -> Int4,
@@ -307,6 +544,7 @@ diesel::table! {
         description -> Nullable<Text>,
         created_at -> Nullable<Timestamp>,
         updated_at -> Nullable<Timestamp>,
+        is_system -> Bool,
     }
 }
 
@@ -323,6 +561,38 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::Geography;
+
+    user_address_geocodes (user_id) {
+        user_id -> Uuid,
+        address -> Text,
+        geometry -> Geography,
+        geocoded_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    user_mfa (user_id) {
+        user_id -> Uuid,
+        secret_ciphertext -> Bytea,
+        confirmed_at -> Nullable<Timestamptz>,
+        last_used_step -> Nullable<Int8>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    user_province_assignments (user_id, province) {
+        user_id -> Uuid,
+        province -> Text,
+        assigned_by -> Nullable<Uuid>,
+        created_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     user_roles (user_id, role_id) {
         user_id -> Uuid,
@@ -377,6 +647,13 @@ diesel::table! {
         volunteer_id -> Nullable<Uuid>,
         geometry -> Nullable<Geography>,
         recorded_at -> Nullable<Timestamp>,
+        user_id -> Nullable<Uuid>,
+        team_id -> Nullable<Uuid>,
+        assignment_id -> Nullable<Uuid>,
+        accuracy_m -> Nullable<Float8>,
+        speed_mps -> Nullable<Float8>,
+        heading_deg -> Nullable<Float8>,
+        received_at -> Timestamp,
     }
 }
 
@@ -392,6 +669,9 @@ diesel::table! {
         completed_at -> Nullable<Timestamp>,
         created_at -> Nullable<Timestamp>,
         updated_at -> Nullable<Timestamp>,
+        assigned_by -> Nullable<Uuid>,
+        responded_at -> Nullable<Timestamp>,
+        disaster_id -> Nullable<Uuid>,
     }
 }
 
@@ -426,7 +706,10 @@ diesel::table! {
     }
 }
 
+diesel::joinable!(analytics_disaster_facts -> disasters (disaster_id));
 diesel::joinable!(auth_sessions -> users (user_id));
+diesel::joinable!(cap_alert_imports -> disasters (disaster_id));
+diesel::joinable!(cap_alert_imports -> users (imported_by));
 diesel::joinable!(disaster_analytics -> disasters (disaster_id));
 diesel::joinable!(disaster_movements -> disasters (disaster_id));
 diesel::joinable!(disaster_reports -> disasters (disaster_id));
@@ -434,10 +717,16 @@ diesel::joinable!(disaster_reports -> reports (report_id));
 diesel::joinable!(disaster_zones -> disasters (disaster_id));
 diesel::joinable!(disasters -> disaster_types (disaster_type_id));
 diesel::joinable!(disasters -> locations (primary_location_id));
+diesel::joinable!(emergency_response_status_history -> emergency_responses (response_id));
 diesel::joinable!(emergency_resources -> locations (location_id));
 diesel::joinable!(emergency_resources -> organizations (organization_id));
+diesel::joinable!(emergency_responses -> disasters (disaster_id));
+diesel::joinable!(emergency_responses -> users (dispatched_by));
 diesel::joinable!(evacuation_center_facilities -> evacuation_centers (evacuation_center_id));
 diesel::joinable!(evacuation_centers -> locations (location_id));
+diesel::joinable!(mfa_recovery_codes -> users (user_id));
+diesel::joinable!(notification_delivery_attempts -> notification_outbox (outbox_id));
+diesel::joinable!(notification_outbox -> users (recipient_id));
 diesel::joinable!(notifications -> users (user_id));
 diesel::joinable!(organization_members -> organizations (organization_id));
 diesel::joinable!(organization_members -> users (user_id));
@@ -453,11 +742,20 @@ diesel::joinable!(reports -> locations (location_id));
 diesel::joinable!(resource_allocations -> disasters (disaster_id));
 diesel::joinable!(resource_allocations -> emergency_resources (resource_id));
 diesel::joinable!(resource_allocations -> users (allocated_by));
+diesel::joinable!(response_teams -> emergency_stations (station_id));
+diesel::joinable!(user_address_geocodes -> users (user_id));
+diesel::joinable!(user_mfa -> users (user_id));
+diesel::joinable!(role_permissions -> roles (role_id));
 diesel::joinable!(user_roles -> roles (role_id));
 diesel::joinable!(user_roles -> users (user_id));
 diesel::joinable!(users -> roles (role_id));
+diesel::joinable!(user_province_assignments -> users (user_id));
 diesel::joinable!(verification_codes -> users (user_id));
+diesel::joinable!(volunteer_locations -> response_teams (team_id));
+diesel::joinable!(volunteer_locations -> users (user_id));
 diesel::joinable!(volunteer_locations -> volunteers (volunteer_id));
+diesel::joinable!(volunteer_locations -> volunteer_tracking (assignment_id));
+diesel::joinable!(volunteer_tracking -> disasters (disaster_id));
 diesel::joinable!(volunteer_tracking -> reports (report_id));
 diesel::joinable!(volunteer_tracking -> volunteers (volunteer_id));
 diesel::joinable!(volunteers -> locations (current_location_id));
@@ -465,7 +763,10 @@ diesel::joinable!(volunteers -> users (user_id));
 diesel::joinable!(weather_data -> locations (location_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    aggregate_snapshots,
+    analytics_disaster_facts,
     auth_sessions,
+    cap_alert_imports,
     disaster_analytics,
     disaster_movements,
     disaster_reports,
@@ -473,9 +774,16 @@ diesel::allow_tables_to_appear_in_same_query!(
     disaster_zones,
     disasters,
     emergency_resources,
+    emergency_response_status_history,
+    emergency_responses,
+    emergency_stations,
     evacuation_center_facilities,
     evacuation_centers,
+    event_store,
     locations,
+    mfa_recovery_codes,
+    notification_delivery_attempts,
+    notification_outbox,
     notifications,
     organization_members,
     organizations,
@@ -485,8 +793,14 @@ diesel::allow_tables_to_appear_in_same_query!(
     report_media,
     reports,
     resource_allocations,
+    response_teams,
+    role_audit_log,
+    role_permissions,
     roles,
     spatial_ref_sys,
+    user_address_geocodes,
+    user_mfa,
+    user_province_assignments,
     user_roles,
     users,
     verification_codes,
@@ -4,62 +4,304 @@
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use diesel::prelude::*;
-use diesel::r2d2::{ConnectionManager, Pool};
-use serde_json;
-use std::sync::Arc;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
-use crate::domain::events::{DomainEvent, EventStore};
-use crate::shared::{AppError, AppResult};
+use crate::domain::events::{self as domain_events, DomainEvent, EventStore};
+use crate::domain::ports::events as port_events;
+use crate::infrastructure::database::{DbConnection, DbPool};
+use crate::infrastructure::database::schemas::{aggregate_snapshots, event_store};
+use crate::shared::events::{
+    self as shared_events, aggregate_type_of, AggregateSnapshot, EventMetadata, SnapshotStore, StoredEvent,
+};
+use crate::shared::error::DatabaseError;
+use crate::shared::{AppError, AppResult, UserId};
+
+type EventDecoder = fn(serde_json::Value) -> serde_json::Result<Box<dyn DomainEvent>>;
+
+fn decode_as<E: DomainEvent + DeserializeOwned + 'static>(
+    payload: serde_json::Value,
+) -> serde_json::Result<Box<dyn DomainEvent>> {
+    serde_json::from_value::<E>(payload).map(|event| Box::new(event) as Box<dyn DomainEvent>)
+}
+
+/// Maps `event_type()` strings to the serde types used to replay them.
+/// Several types may share an event type; decoding tries them in registration order.
+#[derive(Clone, Default)]
+pub struct EventRegistry {
+    decoders: HashMap<String, Vec<EventDecoder>>,
+    aggregate_types: HashMap<String, String>,
+}
+
+impl EventRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry with every domain event known to the application
+    pub fn with_domain_events() -> Self {
+        let mut registry = Self::new();
+        registry
+            .register::<domain_events::UserRegisteredEvent>("UserRegistered", "user")
+            .register::<domain_events::UserActivatedEvent>("UserActivated", "user")
+            .register::<domain_events::UserDeactivatedEvent>("UserDeactivated", "user")
+            .register::<domain_events::DisasterReportedEvent>("DisasterReported", "disaster")
+            .register::<domain_events::DisasterStatusUpdatedEvent>("DisasterStatusUpdated", "disaster")
+            .register::<domain_events::EmergencyResponseDispatchedEvent>("EmergencyResponseDispatched", "disaster")
+            .register::<domain_events::NotificationSentEvent>("NotificationSent", "notification")
+            .register::<domain_events::MassNotificationTriggeredEvent>("MassNotificationTriggered", "disaster")
+            .register::<domain_events::LocationUpdatedEvent>("LocationUpdated", "user")
+            .register::<port_events::DisasterStatusChangedEvent>("disaster.status_changed", "disaster")
+            .register::<port_events::EmergencyAlertTriggeredEvent>("emergency.alert_triggered", "disaster");
+        registry
+    }
+
+    /// Register `E` as a decoder for `event_type`
+    pub fn register<E: DomainEvent + DeserializeOwned + 'static>(
+        &mut self,
+        event_type: &str,
+        aggregate_type: &str,
+    ) -> &mut Self {
+        self.decoders.entry(event_type.to_string()).or_default().push(decode_as::<E>);
+        self.aggregate_types
+            .entry(event_type.to_string())
+            .or_insert_with(|| aggregate_type.to_string());
+        self
+    }
+
+    pub fn is_registered(&self, event_type: &str) -> bool {
+        self.decoders.contains_key(event_type)
+    }
+
+    /// Aggregate type recorded for an event type, falling back to its dotted prefix
+    pub fn aggregate_type<'a>(&'a self, event_type: &'a str) -> &'a str {
+        self.aggregate_types
+            .get(event_type)
+            .map(String::as_str)
+            .unwrap_or_else(|| aggregate_type_of(event_type))
+    }
+
+    /// Rebuild a concrete event from its stored payload
+    pub fn decode(&self, event_type: &str, payload: &serde_json::Value) -> AppResult<Box<dyn DomainEvent>> {
+        let decoders = self.decoders.get(event_type).ok_or_else(|| {
+            AppError::EventProcessing(format!("No event type registered for '{}'", event_type))
+        })?;
+
+        let mut last_error = None;
+        for decode in decoders {
+            match decode(payload.clone()) {
+                Ok(event) => return Ok(event),
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        Err(AppError::Serialization(format!(
+            "Cannot decode '{}' event: {}",
+            event_type,
+            last_error.map(|e| e.to_string()).unwrap_or_default()
+        )))
+    }
+}
+
+/// Envelope fields kept in the `metadata` column
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct EnvelopeMetadata {
+    correlation_id: Option<String>,
+    causation_id: Option<String>,
+    user_id: Option<UserId>,
+    session_id: Option<String>,
+}
 
 /// Event store record for database persistence
-#[derive(Queryable, Insertable, Debug)]
+#[derive(Queryable, Debug, Clone)]
 #[diesel(table_name = event_store)]
 pub struct EventStoreRecord {
+    pub sequence: i64,
     pub event_id: Uuid,
     pub aggregate_id: Uuid,
+    pub aggregate_type: String,
     pub event_type: String,
-    pub event_data: serde_json::Value,
     pub event_version: i64,
+    pub event_data: serde_json::Value,
+    pub metadata: serde_json::Value,
     pub occurred_at: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
 }
 
-table! {
-    event_store (event_id) {
-        event_id -> Uuid,
-        aggregate_id -> Uuid,
-        event_type -> Varchar,
-        event_data -> Jsonb,
-        event_version -> BigInt,
-        occurred_at -> Timestamptz,
-        created_at -> Timestamptz,
+#[derive(Insertable, Debug)]
+#[diesel(table_name = event_store)]
+struct NewEventStoreRecord {
+    event_id: Uuid,
+    aggregate_id: Uuid,
+    aggregate_type: String,
+    event_type: String,
+    event_version: i64,
+    event_data: serde_json::Value,
+    metadata: serde_json::Value,
+    occurred_at: DateTime<Utc>,
+    created_at: DateTime<Utc>,
+}
+
+impl EventStoreRecord {
+    fn into_stored_event(self) -> StoredEvent {
+        let envelope: EnvelopeMetadata = serde_json::from_value(self.metadata).unwrap_or_default();
+        StoredEvent {
+            sequence: self.sequence,
+            metadata: EventMetadata {
+                event_id: self.event_id,
+                event_type: self.event_type,
+                aggregate_id: self.aggregate_id.to_string(),
+                aggregate_type: self.aggregate_type,
+                event_version: self.event_version as u32,
+                occurred_at: self.occurred_at,
+                correlation_id: envelope.correlation_id,
+                causation_id: envelope.causation_id,
+                user_id: envelope.user_id,
+                session_id: envelope.session_id,
+            },
+            payload: self.event_data,
+        }
+    }
+}
+
+#[derive(Queryable, Debug)]
+#[diesel(table_name = aggregate_snapshots)]
+struct SnapshotRecord {
+    aggregate_id: Uuid,
+    aggregate_type: String,
+    version: i64,
+    state: serde_json::Value,
+    created_at: DateTime<Utc>,
+}
+
+impl From<SnapshotRecord> for AggregateSnapshot {
+    fn from(record: SnapshotRecord) -> Self {
+        Self {
+            aggregate_id: record.aggregate_id.to_string(),
+            aggregate_type: record.aggregate_type,
+            version: record.version as u32,
+            state: record.state,
+            taken_at: record.created_at,
+        }
     }
 }
 
+fn parse_aggregate_id(aggregate_id: &str) -> AppResult<Uuid> {
+    Uuid::parse_str(aggregate_id)
+        .map_err(|_| AppError::Validation(format!("Invalid aggregate id: {}", aggregate_id)))
+}
+
+fn check_version(aggregate_id: Uuid, expected: u64, current: u64) -> AppResult<()> {
+    if current != expected {
+        return Err(AppError::Conflict(format!(
+            "Aggregate {}: expected version {}, but current version is {}",
+            aggregate_id, expected, current
+        )));
+    }
+    Ok(())
+}
+
 /// PostgreSQL-based event store implementation
 pub struct PostgresEventStore {
-    pool: Arc<Pool<ConnectionManager<PgConnection>>>,
+    pool: DbPool,
+    registry: Arc<EventRegistry>,
 }
 
 impl PostgresEventStore {
-    pub fn new(pool: Arc<Pool<ConnectionManager<PgConnection>>>) -> Self {
-        Self { pool }
-    }
-
-    /// Serialize domain event to JSON
-    fn serialize_event(&self, event: &dyn DomainEvent) -> AppResult<serde_json::Value> {
-        // This is a simplified serialization - in production you'd want proper event serialization
-        let event_data = serde_json::json!({
-            "event_id": event.event_id(),
-            "event_type": event.event_type(),
-            "occurred_at": event.occurred_at(),
-            "aggregate_id": event.aggregate_id(),
-            "version": event.version(),
-            // Additional event-specific data would be serialized here based on event type
+    pub fn new(pool: DbPool) -> Self {
+        Self::with_registry(pool, EventRegistry::with_domain_events())
+    }
+
+    pub fn with_registry(pool: DbPool, registry: EventRegistry) -> Self {
+        Self { pool, registry: Arc::new(registry) }
+    }
+
+    pub fn registry(&self) -> &EventRegistry {
+        &self.registry
+    }
+
+    fn get_connection(&self) -> AppResult<DbConnection> {
+        self.pool.get()
+            .map_err(|e| AppError::Database(DatabaseError::ConnectionPool(e)))
+    }
+
+    /// Latest stream version, counting a snapshot taken of pre-event-store state
+    fn current_version(conn: &mut PgConnection, aggregate: Uuid) -> QueryResult<u64> {
+        let version: Option<i64> = event_store::table
+            .filter(event_store::aggregate_id.eq(aggregate))
+            .select(diesel::dsl::max(event_store::event_version))
+            .first(conn)?;
+        let snapshot: Option<i64> = aggregate_snapshots::table
+            .filter(aggregate_snapshots::aggregate_id.eq(aggregate))
+            .select(aggregate_snapshots::version)
+            .first(conn)
+            .optional()?;
+        Ok(version.unwrap_or(0).max(snapshot.unwrap_or(0)) as u64)
+    }
+
+    fn insert_record(conn: &mut PgConnection, record: &NewEventStoreRecord) -> AppResult<()> {
+        match diesel::insert_into(event_store::table).values(record).execute(conn) {
+            Err(diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::UniqueViolation, _)) => {
+                Err(AppError::Conflict(format!(
+                    "Concurrent write to aggregate {} at version {}",
+                    record.aggregate_id, record.event_version
+                )))
+            }
+            other => other.map(|_| ()).map_err(AppError::from),
+        }
+    }
+
+    /// Append one event on a connection the caller manages, so the write can join a wider transaction
+    pub(crate) fn append_in(
+        conn: &mut PgConnection,
+        aggregate_id: &str,
+        expected_version: Option<u32>,
+        event: &dyn shared_events::DomainEvent,
+        metadata: Option<&EventMetadata>,
+    ) -> AppResult<()> {
+        let aggregate = parse_aggregate_id(aggregate_id)?;
+        let payload = event.payload()?;
+        let envelope = metadata.map(|m| EnvelopeMetadata {
+            correlation_id: m.correlation_id.clone().or_else(|| event.correlation_id()),
+            causation_id: m.causation_id.clone().or_else(|| event.causation_id()),
+            user_id: m.user_id,
+            session_id: m.session_id.clone(),
+        }).unwrap_or_else(|| EnvelopeMetadata {
+            correlation_id: event.correlation_id(),
+            causation_id: event.causation_id(),
+            ..Default::default()
         });
-        
-        Ok(event_data)
+        let envelope = serde_json::to_value(envelope)
+            .map_err(|e| AppError::Serialization(e.to_string()))?;
+
+        let current = Self::current_version(conn, aggregate)?;
+        if let Some(expected) = expected_version {
+            check_version(aggregate, expected as u64, current)?;
+        }
+
+        let record = NewEventStoreRecord {
+            event_id: metadata.map(|m| m.event_id).unwrap_or_else(Uuid::new_v4),
+            aggregate_id: aggregate,
+            aggregate_type: metadata
+                .map(|m| m.aggregate_type.clone())
+                .unwrap_or_else(|| aggregate_type_of(event.event_type()).to_string()),
+            event_type: event.event_type().to_string(),
+            event_version: (current + 1) as i64,
+            event_data: payload,
+            metadata: envelope,
+            occurred_at: event.occurred_at(),
+            created_at: Utc::now(),
+        };
+        Self::insert_record(conn, &record)
+    }
+
+    fn load_stored(&self, query: event_store::BoxedQuery<'_, diesel::pg::Pg>) -> AppResult<Vec<StoredEvent>> {
+        let mut conn = self.get_connection()?;
+        let records: Vec<EventStoreRecord> = query.load(&mut conn)?;
+        Ok(records.into_iter().map(EventStoreRecord::into_stored_event).collect())
     }
 }
 
@@ -71,54 +313,29 @@ impl EventStore for PostgresEventStore {
         events: &[Box<dyn DomainEvent>],
         expected_version: u64,
     ) -> AppResult<()> {
-        use self::event_store::dsl::*;
-
-        let mut conn = self.pool.get()
-            .map_err(|e| AppError::Database(e.to_string()))?;
-
-        // Start transaction
-        conn.transaction(|conn| -> Result<(), AppError> {
-            // Check current version
-            let current_version: Option<i64> = event_store
-                .filter(aggregate_id.eq(aggregate_id))
-                .select(diesel::dsl::max(event_version))
-                .first(conn)
-                .optional()
-                .map_err(|e| AppError::Database(e.to_string()))?;
-
-            let current_version = current_version.unwrap_or(0) as u64;
-            
-            if current_version != expected_version {
-                return Err(AppError::Conflict(format!(
-                    "Expected version {}, but current version is {}",
-                    expected_version, current_version
-                )));
-            }
+        let mut conn = self.get_connection()?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let current = Self::current_version(conn, aggregate_id)?;
+            check_version(aggregate_id, expected_version, current)?;
 
-            // Insert events
             for (index, event) in events.iter().enumerate() {
-                let event_data = self.serialize_event(event.as_ref())?;
-                
-                let record = EventStoreRecord {
+                let record = NewEventStoreRecord {
                     event_id: event.event_id(),
-                    aggregate_id: event.aggregate_id(),
+                    aggregate_id,
+                    aggregate_type: self.registry.aggregate_type(event.event_type()).to_string(),
                     event_type: event.event_type().to_string(),
-                    event_data,
                     event_version: (expected_version + index as u64 + 1) as i64,
+                    event_data: event.payload()?,
+                    metadata: serde_json::json!({}),
                     occurred_at: event.occurred_at(),
                     created_at: Utc::now(),
                 };
-
-                diesel::insert_into(event_store)
-                    .values(&record)
-                    .execute(conn)
-                    .map_err(|e| AppError::Database(e.to_string()))?;
+                Self::insert_record(conn, &record)?;
             }
 
             Ok(())
-        })?;
-
-        Ok(())
+        })
     }
 
     async fn get_events(
@@ -126,41 +343,156 @@ impl EventStore for PostgresEventStore {
         aggregate_id: Uuid,
         from_version: Option<u64>,
     ) -> AppResult<Vec<Box<dyn DomainEvent>>> {
-        use self::event_store::dsl::*;
+        let mut conn = self.get_connection()?;
+
+        let records: Vec<EventStoreRecord> = event_store::table
+            .filter(event_store::aggregate_id.eq(aggregate_id))
+            .filter(event_store::event_version.gt(from_version.unwrap_or(0) as i64))
+            .order(event_store::event_version.asc())
+            .load(&mut conn)?;
+
+        records
+            .iter()
+            .map(|record| self.registry.decode(&record.event_type, &record.event_data))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl shared_events::EventStore for PostgresEventStore {
+    async fn append_event(
+        &self,
+        aggregate_id: &str,
+        expected_version: Option<u32>,
+        event: Box<dyn shared_events::DomainEvent>,
+        metadata: Option<EventMetadata>,
+    ) -> AppResult<()> {
+        let mut conn = self.get_connection()?;
+        conn.transaction::<_, AppError, _>(|conn| {
+            Self::append_in(conn, aggregate_id, expected_version, event.as_ref(), metadata.as_ref())
+        })
+    }
 
-        let mut conn = self.pool.get()
-            .map_err(|e| AppError::Database(e.to_string()))?;
+    async fn get_events_for_aggregate(
+        &self,
+        aggregate_id: &str,
+        from_version: Option<u32>,
+    ) -> AppResult<Vec<StoredEvent>> {
+        let aggregate = parse_aggregate_id(aggregate_id)?;
+        self.load_stored(
+            event_store::table
+                .filter(event_store::aggregate_id.eq(aggregate))
+                .filter(event_store::event_version.gt(from_version.unwrap_or(0) as i64))
+                .order(event_store::event_version.asc())
+                .into_boxed(),
+        )
+    }
 
-        let mut query = event_store
-            .filter(aggregate_id.eq(aggregate_id))
-            .order(event_version.asc())
+    async fn get_events_by_type(
+        &self,
+        event_type: &str,
+        from_timestamp: Option<DateTime<Utc>>,
+        limit: Option<u32>,
+    ) -> AppResult<Vec<StoredEvent>> {
+        let mut query = event_store::table
+            .filter(event_store::event_type.eq(event_type.to_string()))
+            .order(event_store::sequence.asc())
             .into_boxed();
 
-        if let Some(version) = from_version {
-            query = query.filter(event_version.gt(version as i64));
+        if let Some(from) = from_timestamp {
+            query = query.filter(event_store::occurred_at.ge(from));
         }
+        if let Some(limit) = limit {
+            query = query.limit(limit as i64);
+        }
+
+        self.load_stored(query)
+    }
+
+    async fn get_all_events(&self, after_sequence: i64, limit: u32) -> AppResult<Vec<StoredEvent>> {
+        self.load_stored(
+            event_store::table
+                .filter(event_store::sequence.gt(after_sequence))
+                .order(event_store::sequence.asc())
+                .limit(limit as i64)
+                .into_boxed(),
+        )
+    }
+}
+
+#[async_trait]
+impl SnapshotStore for PostgresEventStore {
+    async fn save_snapshot(&self, snapshot: AggregateSnapshot) -> AppResult<()> {
+        let aggregate = parse_aggregate_id(&snapshot.aggregate_id)?;
+        let mut conn = self.get_connection()?;
 
-        let records: Vec<EventStoreRecord> = query
-            .load(&mut conn)
-            .map_err(|e| AppError::Database(e.to_string()))?;
+        // Only the latest snapshot is kept; an older one never replaces a newer one
+        diesel::sql_query(
+            "INSERT INTO aggregate_snapshots (aggregate_id, aggregate_type, version, state, created_at) \
+             VALUES ($1, $2, $3, $4, $5) \
+             ON CONFLICT (aggregate_id) DO UPDATE \
+             SET version = EXCLUDED.version, state = EXCLUDED.state, created_at = EXCLUDED.created_at \
+             WHERE aggregate_snapshots.version < EXCLUDED.version"
+        )
+            .bind::<diesel::sql_types::Uuid, _>(aggregate)
+            .bind::<diesel::sql_types::Text, _>(&snapshot.aggregate_type)
+            .bind::<diesel::sql_types::BigInt, _>(snapshot.version as i64)
+            .bind::<diesel::sql_types::Jsonb, _>(&snapshot.state)
+            .bind::<diesel::sql_types::Timestamptz, _>(snapshot.taken_at)
+            .execute(&mut conn)?;
+        Ok(())
+    }
 
-        // In a real implementation, you'd deserialize these back to proper event types
-        // For now, returning empty vector as this requires event type registry
-        Ok(vec![])
+    async fn load_snapshot(&self, aggregate_id: &str) -> AppResult<Option<AggregateSnapshot>> {
+        let aggregate = parse_aggregate_id(aggregate_id)?;
+        let mut conn = self.get_connection()?;
+        let record: Option<SnapshotRecord> = aggregate_snapshots::table
+            .filter(aggregate_snapshots::aggregate_id.eq(aggregate))
+            .first(&mut conn)
+            .optional()?;
+        Ok(record.map(AggregateSnapshot::from))
     }
 }
 
-/// In-memory event store for testing
+/// In-memory event store for testing; serializes events the same way as the Postgres store
 pub struct InMemoryEventStore {
-    events: std::sync::Mutex<std::collections::HashMap<Uuid, Vec<Box<dyn DomainEvent>>>>,
+    events: Mutex<Vec<StoredEvent>>,
+    snapshots: Mutex<HashMap<String, AggregateSnapshot>>,
+    registry: EventRegistry,
 }
 
 impl InMemoryEventStore {
     pub fn new() -> Self {
         Self {
-            events: std::sync::Mutex::new(std::collections::HashMap::new()),
+            events: Mutex::new(Vec::new()),
+            snapshots: Mutex::new(HashMap::new()),
+            registry: EventRegistry::with_domain_events(),
         }
     }
+
+    fn current_version(&self, events: &[StoredEvent], aggregate_id: &str) -> u64 {
+        let snapshot = self.snapshots.lock().unwrap()
+            .get(aggregate_id)
+            .map(|s| s.version as u64)
+            .unwrap_or(0);
+        events.iter()
+            .filter(|e| e.metadata.aggregate_id == aggregate_id)
+            .map(|e| e.metadata.event_version as u64)
+            .max()
+            .unwrap_or(0)
+            .max(snapshot)
+    }
+
+    fn push(events: &mut Vec<StoredEvent>, metadata: EventMetadata, payload: serde_json::Value) {
+        let sequence = events.len() as i64 + 1;
+        events.push(StoredEvent { sequence, metadata, payload });
+    }
+}
+
+impl Default for InMemoryEventStore {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[async_trait]
@@ -172,21 +504,23 @@ impl EventStore for InMemoryEventStore {
         expected_version: u64,
     ) -> AppResult<()> {
         let mut store = self.events.lock().unwrap();
-        
-        let current_events = store.entry(aggregate_id).or_insert_with(Vec::new);
-        let current_version = current_events.len() as u64;
-        
-        if current_version != expected_version {
-            return Err(AppError::Conflict(format!(
-                "Expected version {}, but current version is {}",
-                expected_version, current_version
-            )));
-        }
+        let key = aggregate_id.to_string();
+        check_version(aggregate_id, expected_version, self.current_version(&store, &key))?;
 
-        // Clone events for storage (this is simplified - in real implementation you'd serialize)
-        for event in events {
-            // Note: This is a simplified approach - proper cloning of trait objects requires more work
-            current_events.push(event.clone());
+        for (index, event) in events.iter().enumerate() {
+            let metadata = EventMetadata {
+                event_id: event.event_id(),
+                event_type: event.event_type().to_string(),
+                aggregate_id: key.clone(),
+                aggregate_type: self.registry.aggregate_type(event.event_type()).to_string(),
+                event_version: (expected_version + index as u64 + 1) as u32,
+                occurred_at: event.occurred_at(),
+                correlation_id: None,
+                causation_id: None,
+                user_id: None,
+                session_id: None,
+            };
+            Self::push(&mut store, metadata, event.payload()?);
         }
 
         Ok(())
@@ -198,13 +532,214 @@ impl EventStore for InMemoryEventStore {
         from_version: Option<u64>,
     ) -> AppResult<Vec<Box<dyn DomainEvent>>> {
         let store = self.events.lock().unwrap();
-        
-        if let Some(events) = store.get(&aggregate_id) {
-            let start_index = from_version.unwrap_or(0) as usize;
-            let result = events[start_index..].to_vec();
-            Ok(result)
-        } else {
-            Ok(vec![])
+        let key = aggregate_id.to_string();
+
+        store.iter()
+            .filter(|e| e.metadata.aggregate_id == key)
+            .filter(|e| e.metadata.event_version as u64 > from_version.unwrap_or(0))
+            .map(|e| self.registry.decode(&e.metadata.event_type, &e.payload))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl shared_events::EventStore for InMemoryEventStore {
+    async fn append_event(
+        &self,
+        aggregate_id: &str,
+        expected_version: Option<u32>,
+        event: Box<dyn shared_events::DomainEvent>,
+        metadata: Option<EventMetadata>,
+    ) -> AppResult<()> {
+        let aggregate = parse_aggregate_id(aggregate_id)?;
+        let mut store = self.events.lock().unwrap();
+        let current = self.current_version(&store, &aggregate.to_string());
+        if let Some(expected) = expected_version {
+            check_version(aggregate, expected as u64, current)?;
         }
+
+        let mut metadata = metadata.unwrap_or_else(|| EventMetadata {
+            event_id: Uuid::new_v4(),
+            event_type: event.event_type().to_string(),
+            aggregate_id: aggregate.to_string(),
+            aggregate_type: aggregate_type_of(event.event_type()).to_string(),
+            event_version: 0,
+            occurred_at: event.occurred_at(),
+            correlation_id: event.correlation_id(),
+            causation_id: event.causation_id(),
+            user_id: None,
+            session_id: None,
+        });
+        metadata.aggregate_id = aggregate.to_string();
+        metadata.event_version = (current + 1) as u32;
+
+        Self::push(&mut store, metadata, event.payload()?);
+        Ok(())
+    }
+
+    async fn get_events_for_aggregate(
+        &self,
+        aggregate_id: &str,
+        from_version: Option<u32>,
+    ) -> AppResult<Vec<StoredEvent>> {
+        let key = parse_aggregate_id(aggregate_id)?.to_string();
+        let store = self.events.lock().unwrap();
+        Ok(store.iter()
+            .filter(|e| e.metadata.aggregate_id == key)
+            .filter(|e| e.metadata.event_version > from_version.unwrap_or(0))
+            .cloned()
+            .collect())
+    }
+
+    async fn get_events_by_type(
+        &self,
+        event_type: &str,
+        from_timestamp: Option<DateTime<Utc>>,
+        limit: Option<u32>,
+    ) -> AppResult<Vec<StoredEvent>> {
+        let store = self.events.lock().unwrap();
+        Ok(store.iter()
+            .filter(|e| e.metadata.event_type == event_type)
+            .filter(|e| from_timestamp.map(|from| e.metadata.occurred_at >= from).unwrap_or(true))
+            .take(limit.map(|l| l as usize).unwrap_or(usize::MAX))
+            .cloned()
+            .collect())
+    }
+
+    async fn get_all_events(&self, after_sequence: i64, limit: u32) -> AppResult<Vec<StoredEvent>> {
+        let store = self.events.lock().unwrap();
+        Ok(store.iter()
+            .filter(|e| e.sequence > after_sequence)
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
+}
+
+#[async_trait]
+impl SnapshotStore for InMemoryEventStore {
+    async fn save_snapshot(&self, snapshot: AggregateSnapshot) -> AppResult<()> {
+        let key = parse_aggregate_id(&snapshot.aggregate_id)?.to_string();
+        let mut snapshots = self.snapshots.lock().unwrap();
+        if snapshots.get(&key).map(|s| s.version < snapshot.version).unwrap_or(true) {
+            snapshots.insert(key, snapshot);
+        }
+        Ok(())
+    }
+
+    async fn load_snapshot(&self, aggregate_id: &str) -> AppResult<Option<AggregateSnapshot>> {
+        let key = parse_aggregate_id(aggregate_id)?.to_string();
+        Ok(self.snapshots.lock().unwrap().get(&key).cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::disaster::DisasterSeverity;
+    use crate::domain::value_objects::Coordinates;
+    use crate::shared::events::{DisasterEvent, EventPayload, Projection, ProjectionManager, Status};
+    use crate::shared::DisasterId;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn reported(disaster_id: DisasterId) -> domain_events::DisasterReportedEvent {
+        domain_events::DisasterReportedEvent {
+            event_id: Uuid::new_v4(),
+            disaster_id,
+            reported_by: UserId::new(),
+            disaster_type: "flood".to_string(),
+            severity: DisasterSeverity::Major,
+            location: Coordinates::new(-6.2, 106.8).unwrap(),
+            description: "River overflow".to_string(),
+            occurred_at: Utc::now(),
+            version: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn replays_full_payloads_as_concrete_events() {
+        let store = InMemoryEventStore::new();
+        let disaster_id = DisasterId::new();
+        let changed = port_events::DisasterStatusChangedEvent {
+            event_id: Uuid::new_v4(),
+            disaster_id,
+            old_status: "reported".to_string(),
+            new_status: "verified".to_string(),
+            changed_by: UserId::new(),
+            occurred_at: Utc::now(),
+            version: 2,
+        };
+        let events: Vec<Box<dyn DomainEvent>> = vec![Box::new(reported(disaster_id)), Box::new(changed)];
+
+        store.save_events(disaster_id.value(), &events, 0).await.unwrap();
+        let replayed = store.get_events(disaster_id.value(), None).await.unwrap();
+
+        assert_eq!(replayed.len(), 2);
+        assert_eq!(replayed[0].event_type(), "DisasterReported");
+        assert_eq!(replayed[1].event_type(), "disaster.status_changed");
+        assert_eq!(replayed[1].payload().unwrap()["new_status"], "verified");
+    }
+
+    #[tokio::test]
+    async fn rejects_stale_expected_version() {
+        let store = InMemoryEventStore::new();
+        let disaster_id = DisasterId::new();
+        let events: Vec<Box<dyn DomainEvent>> = vec![Box::new(reported(disaster_id))];
+
+        store.save_events(disaster_id.value(), &events, 0).await.unwrap();
+        let result = store.save_events(disaster_id.value(), &events, 0).await;
+        assert!(matches!(result, Err(AppError::Conflict(_))));
+    }
+
+    #[test]
+    fn registry_keys_match_event_types() {
+        let registry = EventRegistry::with_domain_events();
+        let event = reported(DisasterId::new());
+        let decoded = registry.decode(event.event_type(), &event.payload().unwrap()).unwrap();
+        assert_eq!(decoded.aggregate_id(), event.disaster_id.value());
+        assert!(registry.decode("Unknown", &serde_json::json!({})).is_err());
+    }
+
+    struct CountingProjection(AtomicUsize);
+
+    #[async_trait]
+    impl Projection for CountingProjection {
+        async fn project(&self, _event: &StoredEvent) -> AppResult<()> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn reset(&self) -> AppResult<()> {
+            self.0.store(0, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn rebuild_projections_replays_every_stored_event() {
+        let store = Arc::new(InMemoryEventStore::new());
+        let disaster_id = DisasterId::new();
+        for _ in 0..3 {
+            let event = DisasterEvent::DisasterStatusChanged {
+                disaster_id,
+                old_status: Status::Pending,
+                new_status: Status::Active,
+                changed_by: UserId::new(),
+                reason: None,
+                changed_at: Utc::now(),
+            };
+            shared_events::EventStore::append_event(
+                store.as_ref(), &disaster_id.to_string(), None, Box::new(event), None,
+            ).await.unwrap();
+        }
+
+        let projection = Arc::new(CountingProjection(AtomicUsize::new(0)));
+        let mut manager = ProjectionManager::new(store.clone());
+        manager.add_projection(projection.clone());
+        manager.rebuild_projections().await.unwrap();
+
+        assert_eq!(projection.0.load(Ordering::SeqCst), 3);
+        let stored = shared_events::EventStore::get_all_events(store.as_ref(), 0, 10).await.unwrap();
+        assert!(matches!(stored[2].decode::<DisasterEvent>().unwrap(), DisasterEvent::DisasterStatusChanged { .. }));
     }
 }
@@ -92,7 +92,24 @@ impl NotificationService for ExternalNotificationService {
     }
 
     async fn notify_volunteers(&self, disaster: &Disaster, volunteers: &[UserId]) -> AppResult<()> {
-        todo!()
+        let title = format!("Volunteer call-out: {}", disaster.title);
+        let body = format!(
+            "You have been assigned to help with {} ({:?} severity). Open the app to accept or decline.",
+            disaster.title,
+            disaster.severity
+        );
+
+        let mut failed = 0;
+        for volunteer in volunteers {
+            if let Err(e) = self.send_push_notification(*volunteer, &title, &body).await {
+                warn!("Failed to notify volunteer {}: {}", volunteer, e);
+                failed += 1;
+            }
+        }
+        if failed > 0 && failed == volunteers.len() {
+            return Err(AppError::ExternalService(format!("No volunteer could be notified about disaster {}", disaster.id)));
+        }
+        Ok(())
     }
 
     async fn send_emergency_alert(&self, message: &str, recipients: &[UserId]) -> AppResult<()> {
@@ -3,8 +3,13 @@
 
 use crate::shared::error::{AppResult, AppError};
 use crate::infrastructure::external_services::{WeatherConfig, WeatherProvider};
+use crate::domain::ports::services::{self, WeatherAlert};
+use crate::shared::Coordinates;
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
+const OPENWEATHER_CURRENT_URL: &str = "https://api.openweathermap.org/data/2.5/weather";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WeatherData {
     pub temperature: f64,
@@ -74,3 +79,126 @@ impl WeatherService {
         })
     }
 }
+
+/// Subset of the OpenWeatherMap current-weather payload
+#[derive(Debug, Deserialize)]
+struct OpenWeatherCurrent {
+    main: OpenWeatherMain,
+    #[serde(default)]
+    wind: Option<OpenWeatherWind>,
+    #[serde(default)]
+    weather: Vec<OpenWeatherCondition>,
+    #[serde(default)]
+    rain: Option<OpenWeatherRain>,
+    dt: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenWeatherMain {
+    temp: f64,
+    humidity: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenWeatherWind {
+    speed: f64,
+    #[serde(default)]
+    deg: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenWeatherCondition {
+    main: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenWeatherRain {
+    #[serde(rename = "1h", default)]
+    one_hour: Option<f64>,
+}
+
+fn compass_point(degrees: f64) -> String {
+    const POINTS: [&str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+    let index = ((degrees.rem_euclid(360.0) + 22.5) / 45.0) as usize % POINTS.len();
+    POINTS[index].to_string()
+}
+
+impl WeatherService {
+    /// Live observation used by report credibility scoring. Only OpenWeatherMap is
+    /// wired to a real API; other providers report an error so callers treat the
+    /// weather signal as unavailable instead of trusting placeholder data.
+    async fn fetch_current(&self, coordinates: &Coordinates) -> AppResult<services::WeatherData> {
+        if !matches!(self.config.provider, WeatherProvider::OpenWeatherMap) {
+            return Err(AppError::ExternalService(
+                "Live weather is only available from OpenWeatherMap".to_string(),
+            ));
+        }
+
+        let response = self.client
+            .get(OPENWEATHER_CURRENT_URL)
+            .query(&[
+                ("lat", coordinates.latitude.to_string()),
+                ("lon", coordinates.longitude.to_string()),
+                ("appid", self.config.api_key.clone()),
+                ("units", "metric".to_string()),
+            ])
+            .send()
+            .await
+            .map_err(|e| AppError::ExternalService(format!("OpenWeatherMap request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::ExternalService(format!(
+                "OpenWeatherMap returned {}", response.status()
+            )));
+        }
+
+        let current: OpenWeatherCurrent = response.json().await
+            .map_err(|e| AppError::ExternalService(format!("Invalid OpenWeatherMap response: {}", e)))?;
+
+        Ok(services::WeatherData {
+            temperature: current.main.temp,
+            humidity: current.main.humidity,
+            wind_speed: current.wind.as_ref().map(|w| w.speed).unwrap_or(0.0),
+            wind_direction: current.wind.as_ref()
+                .and_then(|w| w.deg)
+                .map(compass_point)
+                .unwrap_or_default(),
+            conditions: current.weather.into_iter().next()
+                .map(|c| c.main)
+                .unwrap_or_else(|| "Unknown".to_string()),
+            precipitation_mm: Some(current.rain.and_then(|r| r.one_hour).unwrap_or(0.0)),
+            timestamp: chrono::DateTime::from_timestamp(current.dt, 0).unwrap_or_else(chrono::Utc::now),
+        })
+    }
+}
+
+#[async_trait]
+impl services::WeatherService for WeatherService {
+    async fn get_current_weather(&self, coordinates: Coordinates) -> AppResult<services::WeatherData> {
+        self.fetch_current(&coordinates).await
+    }
+
+    async fn get_weather_forecast(&self, _coordinates: Coordinates, _days: u8) -> AppResult<Vec<services::WeatherData>> {
+        Err(AppError::ExternalService("Weather forecasts are not supported yet".to_string()))
+    }
+
+    async fn get_weather_alerts(&self, _coordinates: Coordinates) -> AppResult<Vec<WeatherAlert>> {
+        Ok(Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_openweather_payload_with_rain() {
+        let payload = r#"{"main":{"temp":27.4,"humidity":91},"wind":{"speed":5.2,"deg":200},
+            "weather":[{"main":"Rain"}],"rain":{"1h":12.5},"dt":1760000000}"#;
+        let current: OpenWeatherCurrent = serde_json::from_str(payload).unwrap();
+        assert_eq!(current.rain.and_then(|r| r.one_hour), Some(12.5));
+        assert_eq!(current.weather[0].main, "Rain");
+        assert_eq!(compass_point(200.0), "S");
+        assert_eq!(compass_point(350.0), "N");
+    }
+}
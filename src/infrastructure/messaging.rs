@@ -1,9 +1,13 @@
 /// Messaging infrastructure
 /// Handles internal messaging and event publishing
 
+use std::sync::Arc;
+use async_trait::async_trait;
 use tokio::sync::mpsc;
 use serde_json::Value;
+use crate::domain::events::{DomainEvent, EventPublisher};
 use crate::shared::error::AppResult;
+use crate::shared::events::{aggregate_type_of, EventBus, EventMetadata, StoredEvent};
 
 pub struct MessageBroker {
     sender: mpsc::UnboundedSender<Message>,
@@ -35,3 +39,59 @@ impl MessageBroker {
         Ok(())
     }
 }
+
+/// Domain `EventPublisher` backed by the shared `EventBus`
+/// Events are announced to bus listeners (such as the realtime gateway) but not appended to the
+/// event store, whose streams belong to the event-sourced aggregates
+pub struct EventBusPublisher {
+    bus: Arc<EventBus>,
+}
+
+impl EventBusPublisher {
+    pub fn new(bus: Arc<EventBus>) -> Self {
+        Self { bus }
+    }
+
+    fn aggregate_type(event_type: &str) -> &str {
+        match event_type {
+            "UserRegistered" | "UserActivated" | "UserDeactivated" | "LocationUpdated" => "user",
+            "DisasterReported" | "DisasterStatusUpdated" | "EmergencyResponseDispatched" | "MassNotificationTriggered" => "disaster",
+            "NotificationSent" => "notification",
+            dotted => aggregate_type_of(dotted),
+        }
+    }
+
+    fn stored(event: &dyn DomainEvent) -> AppResult<StoredEvent> {
+        Ok(StoredEvent {
+            sequence: 0,
+            metadata: EventMetadata {
+                event_id: event.event_id(),
+                event_type: event.event_type().to_string(),
+                aggregate_id: event.aggregate_id().to_string(),
+                aggregate_type: Self::aggregate_type(event.event_type()).to_string(),
+                event_version: event.version() as u32,
+                occurred_at: event.occurred_at(),
+                correlation_id: None,
+                causation_id: None,
+                user_id: None,
+                session_id: None,
+            },
+            payload: event.payload()?,
+        })
+    }
+}
+
+#[async_trait]
+impl EventPublisher for EventBusPublisher {
+    async fn publish(&self, event: &dyn DomainEvent) -> AppResult<()> {
+        self.bus.announce(Self::stored(event)?);
+        Ok(())
+    }
+
+    async fn publish_batch(&self, events: &[&dyn DomainEvent]) -> AppResult<()> {
+        for event in events {
+            self.publish(*event).await?;
+        }
+        Ok(())
+    }
+}
@@ -8,6 +8,11 @@ pub mod external_services;
 pub mod repository;
 pub mod monitoring;
 pub mod security;
+pub mod event_store;
+pub mod routing;
+pub mod outbox;
+pub mod storage;
+pub mod realtime;
 
 // Dependency injection container
 pub mod container;
@@ -0,0 +1,158 @@
+/// Notification outbox workers
+/// Claim queued deliveries, send them through the channel providers and record every attempt
+
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::Utc;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::config::NotificationOutboxConfig;
+use crate::domain::entities::notification::{DeliveryAttempt, NotificationChannel, OutboxMessage, RetryPolicy};
+use crate::domain::events::{EventPublisher, NotificationSentEvent};
+use crate::domain::ports::repositories::{NotificationOutboxRepository, UserRepository};
+use crate::domain::ports::services::NotificationService;
+use crate::shared::{AppError, AppResult};
+
+pub struct NotificationOutboxWorker {
+    outbox: Arc<dyn NotificationOutboxRepository>,
+    user_repository: Arc<dyn UserRepository>,
+    notification_service: Arc<dyn NotificationService>,
+    event_publisher: Arc<dyn EventPublisher>,
+    config: NotificationOutboxConfig,
+}
+
+impl NotificationOutboxWorker {
+    pub fn new(
+        outbox: Arc<dyn NotificationOutboxRepository>,
+        user_repository: Arc<dyn UserRepository>,
+        notification_service: Arc<dyn NotificationService>,
+        event_publisher: Arc<dyn EventPublisher>,
+        config: NotificationOutboxConfig,
+    ) -> Self {
+        Self {
+            outbox,
+            user_repository,
+            notification_service,
+            event_publisher,
+            config,
+        }
+    }
+
+    fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: self.config.max_attempts,
+            base_delay_ms: self.config.base_backoff_ms,
+            max_delay_ms: self.config.max_backoff_ms,
+        }
+    }
+
+    /// Start the configured number of delivery loops
+    pub fn spawn(self: Arc<Self>) -> Vec<JoinHandle<()>> {
+        (0..self.config.workers)
+            .map(|index| {
+                let worker_id = format!("outbox-{}-{}", std::process::id(), index);
+                tokio::spawn(self.clone().run(worker_id))
+            })
+            .collect()
+    }
+
+    async fn run(self: Arc<Self>, worker_id: String) {
+        let idle = Duration::from_millis(self.config.poll_interval_ms);
+        let lease = Duration::from_secs(self.config.lease_seconds);
+        tracing::info!("Notification outbox worker {} started", worker_id);
+
+        loop {
+            match self.outbox.claim_due(&worker_id, self.config.batch_size, lease).await {
+                Ok(batch) if batch.is_empty() => tokio::time::sleep(idle).await,
+                Ok(batch) => {
+                    for message in batch {
+                        if let Err(e) = self.process(&worker_id, message).await {
+                            tracing::error!("Outbox worker {} failed to record delivery: {}", worker_id, e);
+                        }
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("Outbox worker {} could not claim messages: {}", worker_id, e);
+                    tokio::time::sleep(idle).await;
+                }
+            }
+        }
+    }
+
+    async fn process(&self, worker_id: &str, mut message: OutboxMessage) -> AppResult<()> {
+        if message.is_expired() {
+            message.dead_letter("Notification expired before delivery".to_string());
+            self.outbox.dead_letter(worker_id, &message).await?;
+            return Ok(());
+        }
+
+        let attempted_at = Utc::now();
+        let result = self.send(&message).await;
+        let attempt = DeliveryAttempt {
+            channel: message.channel.clone(),
+            attempted_at,
+            success: result.is_ok(),
+            error_message: result.as_ref().err().map(|e| e.to_string()),
+            response_details: None,
+        };
+
+        match &result {
+            Ok(()) => message.record_success(),
+            Err(e) => message.record_failure(e, &self.retry_policy()),
+        }
+
+        if !self.outbox.record_attempt(worker_id, &message, &attempt).await? {
+            tracing::warn!("Outbox message {} was reclaimed by another worker during delivery", message.id);
+            return Ok(());
+        }
+
+        match result {
+            Ok(()) => {
+                let event = NotificationSentEvent {
+                    event_id: Uuid::new_v4(),
+                    notification_id: message.notification_id,
+                    recipient_id: message.recipient_id,
+                    notification_type: message.notification_type.clone(),
+                    channel: message.channel.as_str().to_string(),
+                    content: message.message.clone(),
+                    occurred_at: Utc::now(),
+                    version: 1,
+                };
+                let _ = self.event_publisher.publish(&event).await;
+            }
+            Err(e) if message.dead_lettered_at.is_some() => {
+                tracing::warn!(
+                    "Outbox message {} dead-lettered after {} attempt(s): {}",
+                    message.id, message.attempts, e
+                );
+            }
+            Err(_) => {}
+        }
+        Ok(())
+    }
+
+    async fn send(&self, message: &OutboxMessage) -> AppResult<()> {
+        let user = self.user_repository.find_by_id(&message.recipient_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Recipient {} not found", message.recipient_id)))?;
+        let text = format!("{}\n\n{}", message.title, message.message);
+
+        match message.channel {
+            NotificationChannel::SMS | NotificationChannel::WhatsApp => {
+                let phone = user.phone_number()
+                    .ok_or_else(|| AppError::Validation("User has no phone number".to_string()))?;
+                if message.channel == NotificationChannel::SMS {
+                    self.notification_service.send_sms(phone.value(), &text).await
+                } else {
+                    self.notification_service.send_whatsapp(phone.value(), &text).await
+                }
+            }
+            NotificationChannel::Email => {
+                self.notification_service.send_email(user.email().value(), &message.title, &message.message).await
+            }
+            NotificationChannel::Push | NotificationChannel::InApp => {
+                self.notification_service.send_push_notification(message.recipient_id, &message.title, &message.message).await
+            }
+        }
+    }
+}
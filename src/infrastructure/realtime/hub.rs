@@ -0,0 +1,191 @@
+/// Realtime hub
+/// Turns event bus traffic into realtime messages and fans them out to every connection on this
+/// instance and, through Redis pub/sub, to the connections held by other instances
+
+use std::sync::Arc;
+use std::time::Duration;
+use deadpool_redis::redis::{self, AsyncCommands};
+use deadpool_redis::{Pool as RedisPool, Runtime};
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::domain::events::NotificationSentEvent;
+use crate::domain::ports::repositories::DisasterRepository;
+use crate::shared::events::{EventBus, StoredEvent};
+use crate::shared::{AppError, AppResult, DisasterId, UserId};
+use super::topics::{MessageScope, RealtimeMessage};
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Domain events about a disaster that its own event stream already announces
+const COVERED_BY_STREAM: &[&str] = &["DisasterReported", "DisasterStatusUpdated"];
+
+/// What travels over Redis; the origin lets an instance skip its own messages
+#[derive(Serialize, Deserialize)]
+struct Envelope {
+    origin: Uuid,
+    message: RealtimeMessage,
+}
+
+struct RedisFanout {
+    client: redis::Client,
+    pool: RedisPool,
+    channel: String,
+}
+
+impl RedisFanout {
+    async fn publish(&self, envelope: &Envelope) -> AppResult<()> {
+        let payload = serde_json::to_string(envelope).map_err(|e| AppError::Serialization(e.to_string()))?;
+        let mut conn = self.pool.get().await
+            .map_err(|e| AppError::Integration(format!("Failed to get Redis connection: {}", e)))?;
+        conn.publish::<_, _, ()>(&self.channel, payload).await
+            .map_err(|e| AppError::Integration(format!("Redis publish failed: {}", e)))
+    }
+
+    /// Relay messages from other instances until the subscription drops
+    async fn relay(&self, origin: Uuid, local: &broadcast::Sender<RealtimeMessage>) -> AppResult<()> {
+        let mut pubsub = self.client.get_async_pubsub().await
+            .map_err(|e| AppError::Integration(format!("Redis subscribe connection failed: {}", e)))?;
+        pubsub.subscribe(&self.channel).await
+            .map_err(|e| AppError::Integration(format!("Redis subscribe failed: {}", e)))?;
+        tracing::info!("Realtime hub subscribed to Redis channel {}", self.channel);
+
+        let mut messages = pubsub.on_message();
+        while let Some(message) = messages.next().await {
+            let envelope = message.get_payload::<String>()
+                .map_err(|e| e.to_string())
+                .and_then(|payload| serde_json::from_str::<Envelope>(&payload).map_err(|e| e.to_string()));
+            match envelope {
+                Ok(envelope) if envelope.origin != origin => {
+                    let _ = local.send(envelope.message);
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Ignoring malformed realtime message on {}: {}", self.channel, e),
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct RealtimeHub {
+    instance_id: Uuid,
+    local: broadcast::Sender<RealtimeMessage>,
+    fanout: Option<RedisFanout>,
+}
+
+impl RealtimeHub {
+    /// Hub serving only this instance's connections
+    pub fn local(buffer_size: usize) -> Self {
+        let (local, _) = broadcast::channel(buffer_size.max(1));
+        Self { instance_id: Uuid::new_v4(), local, fanout: None }
+    }
+
+    /// Hub sharing messages with other instances over a Redis channel
+    pub async fn with_redis(buffer_size: usize, redis_url: &str, channel: &str) -> AppResult<Self> {
+        let client = redis::Client::open(redis_url)
+            .map_err(|e| AppError::Configuration(format!("Invalid Redis URL for realtime fan-out: {}", e)))?;
+        let pool = deadpool_redis::Config::from_url(redis_url)
+            .create_pool(Some(Runtime::Tokio1))
+            .map_err(|e| AppError::InternalServer(format!("Failed to create Redis pool: {}", e)))?;
+
+        let mut conn = pool.get().await
+            .map_err(|e| AppError::Integration(format!("Failed to get Redis connection: {}", e)))?;
+        redis::cmd("PING").query_async::<()>(&mut *conn).await
+            .map_err(|e| AppError::Integration(format!("Redis ping failed: {}", e)))?;
+
+        Ok(Self {
+            fanout: Some(RedisFanout { client, pool, channel: channel.to_string() }),
+            ..Self::local(buffer_size)
+        })
+    }
+
+    pub fn is_distributed(&self) -> bool {
+        self.fanout.is_some()
+    }
+
+    /// Receive every message delivered on this instance
+    pub fn subscribe(&self) -> broadcast::Receiver<RealtimeMessage> {
+        self.local.subscribe()
+    }
+
+    /// Deliver locally straight away, then share with other instances
+    pub async fn publish(&self, message: RealtimeMessage) {
+        let _ = self.local.send(message.clone());
+        if let Some(fanout) = &self.fanout {
+            let envelope = Envelope { origin: self.instance_id, message };
+            if let Err(e) = fanout.publish(&envelope).await {
+                tracing::warn!("Realtime event {} not shared with other instances: {}", envelope.message.event_id, e);
+            }
+        }
+    }
+
+    /// Start forwarding bus events and, with Redis, relaying other instances' messages
+    pub fn spawn(self: Arc<Self>, bus: &EventBus, disasters: Arc<dyn DisasterRepository>) -> Vec<JoinHandle<()>> {
+        let mut handles = vec![tokio::spawn(self.clone().forward(bus.listen(), disasters))];
+        if self.fanout.is_some() {
+            handles.push(tokio::spawn(self.relay()));
+        }
+        handles
+    }
+
+    async fn forward(self: Arc<Self>, mut events: broadcast::Receiver<StoredEvent>, disasters: Arc<dyn DisasterRepository>) {
+        loop {
+            match events.recv().await {
+                Ok(event) => {
+                    if let Some(scope) = Self::scope_of(&event, disasters.as_ref()).await {
+                        self.publish(RealtimeMessage {
+                            event_id: event.metadata.event_id,
+                            event_type: event.metadata.event_type,
+                            scope,
+                            occurred_at: event.metadata.occurred_at,
+                            payload: event.payload,
+                        }).await;
+                    }
+                }
+                Err(RecvError::Lagged(missed)) => tracing::warn!("Realtime hub fell behind and skipped {} events", missed),
+                Err(RecvError::Closed) => break,
+            }
+        }
+    }
+
+    async fn relay(self: Arc<Self>) {
+        let Some(fanout) = &self.fanout else { return };
+        loop {
+            match fanout.relay(self.instance_id, &self.local).await {
+                Ok(()) => tracing::warn!("Realtime Redis subscription closed; reconnecting"),
+                Err(e) => tracing::warn!("Realtime Redis subscription failed: {}", e),
+            }
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    }
+
+    /// Who may see an event; `None` for events the gateway does not push
+    async fn scope_of(event: &StoredEvent, disasters: &dyn DisasterRepository) -> Option<MessageScope> {
+        let metadata = &event.metadata;
+        if metadata.event_type == "NotificationSent" {
+            let sent: NotificationSentEvent = event.decode().ok()?;
+            return Some(MessageScope::User { user_id: UserId(sent.recipient_id.value()) });
+        }
+        if metadata.aggregate_type != "disaster" || COVERED_BY_STREAM.contains(&metadata.event_type.as_str()) {
+            return None;
+        }
+
+        let disaster_id = DisasterId(Uuid::parse_str(&metadata.aggregate_id).ok()?);
+        let disaster = match disasters.find_by_id(&disaster_id).await {
+            Ok(disaster) => disaster,
+            Err(e) => {
+                tracing::warn!("Realtime event {} pushed without disaster location: {}", metadata.event_id, e);
+                None
+            }
+        };
+        Some(MessageScope::Disaster {
+            disaster_id,
+            province: disaster.as_ref().and_then(|d| d.province.clone()),
+            latitude: disaster.as_ref().map(|d| d.location.latitude),
+            longitude: disaster.as_ref().map(|d| d.location.longitude),
+        })
+    }
+}
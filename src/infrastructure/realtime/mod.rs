@@ -0,0 +1,8 @@
+/// Realtime gateway support
+/// Topic subscriptions and the hub feeding WebSocket and SSE connections from the event bus
+
+pub mod hub;
+pub mod topics;
+
+pub use hub::RealtimeHub;
+pub use topics::{BoundingBox, MessageScope, RealtimeMessage, Subscription, Topic, MAX_TOPICS};
@@ -0,0 +1,238 @@
+/// Realtime messages and the topics clients subscribe to
+/// Topics are plain strings on the wire: `disasters`, `disaster:{id}`, `province:{name}`,
+/// `bbox:{min_lon},{min_lat},{max_lon},{max_lat}` and `notifications`
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+use crate::shared::{AppError, DisasterId, UserId};
+
+/// Topics a single connection may hold at once
+pub const MAX_TOPICS: usize = 20;
+
+/// Who an event concerns, resolved once when it enters the gateway
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MessageScope {
+    Disaster {
+        disaster_id: DisasterId,
+        province: Option<String>,
+        latitude: Option<f64>,
+        longitude: Option<f64>,
+    },
+    /// Private to one user, e.g. their own notifications
+    User { user_id: UserId },
+}
+
+/// An event as pushed to WebSocket and SSE clients
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RealtimeMessage {
+    pub event_id: Uuid,
+    pub event_type: String,
+    pub scope: MessageScope,
+    pub occurred_at: DateTime<Utc>,
+    pub payload: serde_json::Value,
+}
+
+/// Longitude/latitude rectangle; does not wrap across the antimeridian
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub min_lon: f64,
+    pub min_lat: f64,
+    pub max_lon: f64,
+    pub max_lat: f64,
+}
+
+impl BoundingBox {
+    pub fn contains(&self, latitude: f64, longitude: f64) -> bool {
+        (self.min_lat..=self.max_lat).contains(&latitude) && (self.min_lon..=self.max_lon).contains(&longitude)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Topic {
+    /// Every disaster update
+    Disasters,
+    Disaster(DisasterId),
+    /// Updates for disasters in a province, matched case-insensitively
+    Province(String),
+    Area(BoundingBox),
+    /// The subscriber's own notifications
+    Notifications,
+}
+
+impl Topic {
+    /// Whether the topic carries private notifications rather than public disaster updates
+    pub fn is_notifications(&self) -> bool {
+        matches!(self, Self::Notifications)
+    }
+
+    pub fn matches(&self, message: &RealtimeMessage, subscriber: &UserId) -> bool {
+        match (self, &message.scope) {
+            (Self::Notifications, MessageScope::User { user_id }) => user_id == subscriber,
+            (Self::Disasters, MessageScope::Disaster { .. }) => true,
+            (Self::Disaster(id), MessageScope::Disaster { disaster_id, .. }) => id == disaster_id,
+            (Self::Province(name), MessageScope::Disaster { province: Some(province), .. }) => {
+                province.trim().eq_ignore_ascii_case(name)
+            }
+            (Self::Area(area), MessageScope::Disaster { latitude: Some(lat), longitude: Some(lon), .. }) => {
+                area.contains(*lat, *lon)
+            }
+            _ => false,
+        }
+    }
+}
+
+fn invalid(raw: &str, reason: &str) -> AppError {
+    AppError::Validation(format!("Invalid topic '{}': {}", raw, reason))
+}
+
+impl FromStr for Topic {
+    type Err = AppError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let raw = raw.trim();
+        let (kind, value) = raw.split_once(':').map(|(k, v)| (k, v.trim())).unwrap_or((raw, ""));
+        match (kind.to_lowercase().as_str(), value) {
+            ("disasters", "") => Ok(Self::Disasters),
+            ("notifications", "") => Ok(Self::Notifications),
+            ("disaster", id) => Uuid::parse_str(id)
+                .map(|id| Self::Disaster(DisasterId(id)))
+                .map_err(|_| invalid(raw, "expected a disaster id")),
+            ("province", name) if !name.is_empty() && name.chars().count() <= 100 => Ok(Self::Province(name.to_string())),
+            ("province", _) => Err(invalid(raw, "expected a province name of at most 100 characters")),
+            ("bbox", bounds) => {
+                let parts = bounds.split(',')
+                    .map(|part| part.trim().parse::<f64>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|_| invalid(raw, "expected min_lon,min_lat,max_lon,max_lat"))?;
+                let [min_lon, min_lat, max_lon, max_lat] = parts[..] else {
+                    return Err(invalid(raw, "expected min_lon,min_lat,max_lon,max_lat"));
+                };
+                let in_range = (-180.0..=180.0).contains(&min_lon) && (-180.0..=180.0).contains(&max_lon)
+                    && (-90.0..=90.0).contains(&min_lat) && (-90.0..=90.0).contains(&max_lat);
+                if !in_range || min_lon > max_lon || min_lat > max_lat {
+                    return Err(invalid(raw, "bounds are out of range or inverted"));
+                }
+                Ok(Self::Area(BoundingBox { min_lon, min_lat, max_lon, max_lat }))
+            }
+            _ => Err(invalid(raw, "expected disasters, disaster:{id}, province:{name}, bbox:{bounds} or notifications")),
+        }
+    }
+}
+
+impl fmt::Display for Topic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Disasters => f.write_str("disasters"),
+            Self::Disaster(id) => write!(f, "disaster:{}", id),
+            Self::Province(name) => write!(f, "province:{}", name),
+            Self::Area(b) => write!(f, "bbox:{},{},{},{}", b.min_lon, b.min_lat, b.max_lon, b.max_lat),
+            Self::Notifications => f.write_str("notifications"),
+        }
+    }
+}
+
+/// Topics held by one connection
+#[derive(Debug, Clone)]
+pub struct Subscription {
+    pub user_id: UserId,
+    topics: Vec<Topic>,
+}
+
+impl Subscription {
+    pub fn new(user_id: UserId) -> Self {
+        Self { user_id, topics: Vec::new() }
+    }
+
+    pub fn topics(&self) -> &[Topic] {
+        &self.topics
+    }
+
+    /// Add topics not already held, keeping the total within `MAX_TOPICS`
+    pub fn add(&mut self, topics: Vec<Topic>) -> Result<(), AppError> {
+        let new: Vec<Topic> = topics.into_iter().fold(Vec::new(), |mut acc, topic| {
+            if !self.topics.contains(&topic) && !acc.contains(&topic) {
+                acc.push(topic);
+            }
+            acc
+        });
+        if self.topics.len() + new.len() > MAX_TOPICS {
+            return Err(AppError::Validation(format!("At most {} topics can be subscribed at once", MAX_TOPICS)));
+        }
+        self.topics.extend(new);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, topics: &[Topic]) {
+        self.topics.retain(|topic| !topics.contains(topic));
+    }
+
+    pub fn matches(&self, message: &RealtimeMessage) -> bool {
+        self.topics.iter().any(|topic| topic.matches(message, &self.user_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn disaster_message(disaster_id: DisasterId) -> RealtimeMessage {
+        RealtimeMessage {
+            event_id: Uuid::new_v4(),
+            event_type: "disaster.status_updated".to_string(),
+            scope: MessageScope::Disaster {
+                disaster_id,
+                province: Some("Jawa Barat".to_string()),
+                latitude: Some(-6.9),
+                longitude: Some(107.6),
+            },
+            occurred_at: Utc::now(),
+            payload: serde_json::Value::Null,
+        }
+    }
+
+    #[test]
+    fn topics_round_trip_and_reject_bad_input() {
+        let id = DisasterId::new();
+        for raw in ["disasters", "notifications", &format!("disaster:{}", id), "province:Jawa Barat", "bbox:106.5,-7.5,108,-6"] {
+            let topic: Topic = raw.parse().unwrap();
+            assert_eq!(topic.to_string(), raw);
+        }
+        for raw in ["disaster:nope", "province:", "bbox:1,2,3", "bbox:108,-7.5,106.5,-6", "bbox:0,-95,1,0", "weather"] {
+            assert!(raw.parse::<Topic>().is_err(), "{} should be rejected", raw);
+        }
+    }
+
+    #[test]
+    fn subscriptions_match_by_disaster_province_area_and_recipient() {
+        let user = UserId::new();
+        let id = DisasterId::new();
+        let message = disaster_message(id);
+
+        let mut subscription = Subscription::new(user);
+        assert!(!subscription.matches(&message));
+        subscription.add(vec!["province:jawa barat".parse().unwrap()]).unwrap();
+        assert!(subscription.matches(&message));
+
+        subscription = Subscription::new(user);
+        subscription.add(vec!["bbox:110,-8,112,-6".parse().unwrap(), Topic::Disaster(DisasterId::new())]).unwrap();
+        assert!(!subscription.matches(&message));
+        subscription.add(vec!["bbox:107,-7,108,-6".parse().unwrap(), Topic::Disaster(DisasterId::new())]).unwrap();
+        assert!(subscription.matches(&message));
+        subscription.remove(&["bbox:107,-7,108,-6".parse().unwrap()]);
+        assert!(!subscription.matches(&message));
+
+        subscription.add(vec![Topic::Notifications]).unwrap();
+        let mut notification = disaster_message(id);
+        notification.scope = MessageScope::User { user_id: UserId::new() };
+        assert!(!subscription.matches(&notification));
+        notification.scope = MessageScope::User { user_id: user };
+        assert!(subscription.matches(&notification));
+
+        let too_many = (0..MAX_TOPICS).map(|_| Topic::Disaster(DisasterId::new())).collect();
+        assert!(subscription.add(too_many).is_err());
+    }
+}
@@ -0,0 +1,502 @@
+/// Analytics repository implementation
+/// Facts in `analytics_disaster_facts`, one row per disaster, written by projecting disaster
+/// events and aggregated in SQL for the dashboard. Every query binds the filter as $1..$7.
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use diesel::query_builder::{BoxedSqlQuery, SqlQuery};
+use diesel::sql_types::{BigInt, Bool, Float8, Nullable, Text, Timestamp, Uuid as SqlUuid};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::entities::analytics::{
+    AnalyticsFilter, AreaCount, DashboardSummary, DisasterCounts, DisasterFact, DisasterTrends,
+    DurationStats, GeographicAnalytics, Hotspot, LocationFilter, PeriodCount, ResponseTimeAnalytics,
+};
+use crate::domain::entities::disaster::{DisasterSeverity, DisasterStatus, DisasterType};
+use crate::domain::ports::repositories::{AnalyticsRepository, DisasterRepository};
+use crate::domain::value_objects::Coordinates;
+use crate::infrastructure::database::{DbConnection, DbPool};
+use crate::shared::error::DatabaseError;
+use crate::shared::events::{aggregate_type_of, DisasterEvent, Projection, StoredEvent};
+use crate::shared::{AppError, AppResult, DisasterId};
+
+const FILTER: &str = "($1 IS NULL OR f.reported_at >= $1) AND ($2 IS NULL OR f.reported_at < $2) \
+     AND ($3 IS NULL OR lower(f.disaster_type) = lower($3)) \
+     AND ($4 IS NULL OR lower(f.province) = lower($4) OR lower(f.city) = lower($4)) \
+     AND ($5 IS NULL OR ST_DWithin(f.position, ST_SetSRID(ST_MakePoint($6, $5), 4326)::geography, $7))";
+
+/// Disasters still being worked
+const OPEN: &str = "f.status NOT IN ('resolved', 'closed')";
+
+/// Grid cell size for hotspots in degrees, about 11 km at the equator
+const HOTSPOT_CELL_DEG: f64 = 0.1;
+const MAX_HOTSPOTS: i64 = 20;
+
+#[derive(QueryableByName, Debug)]
+struct FactRow {
+    #[diesel(sql_type = SqlUuid)]
+    disaster_id: Uuid,
+    #[diesel(sql_type = Text)]
+    disaster_type: String,
+    #[diesel(sql_type = Text)]
+    severity: String,
+    #[diesel(sql_type = Text)]
+    status: String,
+    #[diesel(sql_type = Float8)]
+    latitude: f64,
+    #[diesel(sql_type = Float8)]
+    longitude: f64,
+    #[diesel(sql_type = Nullable<Text>)]
+    province: Option<String>,
+    #[diesel(sql_type = Nullable<Text>)]
+    city: Option<String>,
+    #[diesel(sql_type = Timestamp)]
+    reported_at: NaiveDateTime,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    verified_at: Option<NaiveDateTime>,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    first_response_at: Option<NaiveDateTime>,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    resolved_at: Option<NaiveDateTime>,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    closed_at: Option<NaiveDateTime>,
+}
+
+#[derive(QueryableByName, Debug)]
+struct TotalsRow {
+    #[diesel(sql_type = BigInt)]
+    total: i64,
+    #[diesel(sql_type = BigInt)]
+    active: i64,
+    #[diesel(sql_type = BigInt)]
+    verified: i64,
+    #[diesel(sql_type = BigInt)]
+    resolved: i64,
+}
+
+#[derive(QueryableByName, Debug)]
+struct BucketRow {
+    #[diesel(sql_type = Text)]
+    bucket: String,
+    #[diesel(sql_type = BigInt)]
+    total: i64,
+}
+
+#[derive(QueryableByName, Debug)]
+struct PeriodRow {
+    #[diesel(sql_type = Timestamp)]
+    period: NaiveDateTime,
+    #[diesel(sql_type = Text)]
+    bucket: String,
+    #[diesel(sql_type = BigInt)]
+    total: i64,
+}
+
+#[derive(QueryableByName, Debug)]
+struct DurationRow {
+    #[diesel(sql_type = Text)]
+    bucket: String,
+    #[diesel(sql_type = BigInt)]
+    samples: i64,
+    #[diesel(sql_type = Nullable<Float8>)]
+    average: Option<f64>,
+    #[diesel(sql_type = Nullable<Float8>)]
+    p50: Option<f64>,
+    #[diesel(sql_type = Nullable<Float8>)]
+    p90: Option<f64>,
+    #[diesel(sql_type = Nullable<Float8>)]
+    p95: Option<f64>,
+    #[diesel(sql_type = Nullable<Float8>)]
+    fastest: Option<f64>,
+    #[diesel(sql_type = Nullable<Float8>)]
+    slowest: Option<f64>,
+}
+
+#[derive(QueryableByName, Debug)]
+struct AreaRow {
+    #[diesel(sql_type = Text)]
+    name: String,
+    #[diesel(sql_type = BigInt)]
+    total: i64,
+    #[diesel(sql_type = BigInt)]
+    active: i64,
+}
+
+#[derive(QueryableByName, Debug)]
+struct HotspotRow {
+    #[diesel(sql_type = Float8)]
+    latitude: f64,
+    #[diesel(sql_type = Float8)]
+    longitude: f64,
+    #[diesel(sql_type = BigInt)]
+    total: i64,
+    #[diesel(sql_type = BigInt)]
+    active: i64,
+}
+
+#[derive(QueryableByName, Debug)]
+struct FlagRow {
+    #[diesel(sql_type = Bool)]
+    flag: bool,
+}
+
+/// Milestone a duration is measured to, from the report
+#[derive(Debug, Clone, Copy)]
+enum Milestone {
+    Verified,
+    FirstResponse,
+}
+
+impl Milestone {
+    fn column(&self) -> &'static str {
+        match self {
+            Milestone::Verified => "f.verified_at",
+            Milestone::FirstResponse => "f.first_response_at",
+        }
+    }
+}
+
+pub struct PostgresAnalyticsRepository {
+    pool: DbPool,
+    /// Source for facts of disasters that predate their event history
+    disasters: Arc<dyn DisasterRepository>,
+}
+
+impl PostgresAnalyticsRepository {
+    pub fn new(pool: DbPool, disasters: Arc<dyn DisasterRepository>) -> Self {
+        Self { pool, disasters }
+    }
+
+    fn get_connection(&self) -> AppResult<DbConnection> {
+        self.pool.get()
+            .map_err(|e| AppError::Database(DatabaseError::ConnectionPool(e)))
+    }
+
+    fn utc(t: NaiveDateTime) -> DateTime<Utc> {
+        Utc.from_utc_datetime(&t)
+    }
+
+    /// `sql` with the filter bound to $1..$7; further binds continue at $8
+    fn filtered(sql: String, filter: &AnalyticsFilter) -> BoxedSqlQuery<'static, Pg, SqlQuery> {
+        let (area, near) = match &filter.location {
+            Some(LocationFilter::Area(name)) => (Some(name.trim().to_string()), None),
+            Some(LocationFilter::Near { center, radius_km }) => {
+                (None, Some((center.latitude, center.longitude, radius_km * 1000.0)))
+            }
+            None => (None, None),
+        };
+        diesel::sql_query(sql)
+            .into_boxed()
+            .bind::<Nullable<Timestamp>, _>(filter.from.map(|t| t.naive_utc()))
+            .bind::<Nullable<Timestamp>, _>(filter.to.map(|t| t.naive_utc()))
+            .bind::<Nullable<Text>, _>(filter.disaster_type.clone())
+            .bind::<Nullable<Text>, _>(area)
+            .bind::<Nullable<Float8>, _>(near.map(|(lat, _, _)| lat))
+            .bind::<Nullable<Float8>, _>(near.map(|(_, lng, _)| lng))
+            .bind::<Nullable<Float8>, _>(near.map(|(_, _, radius_m)| radius_m))
+    }
+
+    fn to_domain(row: FactRow) -> AppResult<DisasterFact> {
+        let severity = DisasterSeverity::parse(&row.severity).ok_or_else(|| AppError::DataConsistency(format!(
+            "Analytics fact {} has unknown severity '{}'", row.disaster_id, row.severity
+        )))?;
+        let status = DisasterStatus::parse(&row.status).ok_or_else(|| AppError::DataConsistency(format!(
+            "Analytics fact {} has unknown status '{}'", row.disaster_id, row.status
+        )))?;
+        Ok(DisasterFact {
+            disaster_id: DisasterId(row.disaster_id),
+            disaster_type: DisasterType::parse(&row.disaster_type),
+            severity,
+            status,
+            position: Coordinates { latitude: row.latitude, longitude: row.longitude, altitude: None },
+            province: row.province,
+            city: row.city,
+            reported_at: Self::utc(row.reported_at),
+            verified_at: row.verified_at.map(Self::utc),
+            first_response_at: row.first_response_at.map(Self::utc),
+            resolved_at: row.resolved_at.map(Self::utc),
+            closed_at: row.closed_at.map(Self::utc),
+        })
+    }
+
+    fn fact_columns() -> &'static str {
+        "SELECT f.disaster_id, f.disaster_type, f.severity, f.status, \
+                ST_Y(f.position::geometry) AS latitude, ST_X(f.position::geometry) AS longitude, \
+                f.province, f.city, f.reported_at, f.verified_at, f.first_response_at, f.resolved_at, f.closed_at \
+         FROM analytics_disaster_facts f"
+    }
+
+    fn load_fact(conn: &mut DbConnection, disaster_id: &DisasterId) -> AppResult<Option<DisasterFact>> {
+        let row = diesel::sql_query(format!("{} WHERE f.disaster_id = $1", Self::fact_columns()))
+            .bind::<SqlUuid, _>(disaster_id.0)
+            .get_result::<FactRow>(conn)
+            .optional()?;
+        row.map(Self::to_domain).transpose()
+    }
+
+    /// Write the fact, filling province and city from the disaster's primary location when the
+    /// fact has none; a disaster that no longer exists is skipped
+    fn upsert_fact(conn: &mut DbConnection, fact: &DisasterFact) -> AppResult<()> {
+        diesel::sql_query(
+            "INSERT INTO analytics_disaster_facts \
+                 (disaster_id, disaster_type, severity, status, position, province, city, \
+                  reported_at, verified_at, first_response_at, resolved_at, closed_at, projected_at) \
+             SELECT d.id, $2, $3, $4, ST_SetSRID(ST_MakePoint($5, $6), 4326)::geography, \
+                    COALESCE($7, l.province), COALESCE($8, l.city), $9, $10, $11, $12, $13, CURRENT_TIMESTAMP \
+             FROM disasters d LEFT JOIN locations l ON l.id = d.primary_location_id \
+             WHERE d.id = $1 \
+             ON CONFLICT (disaster_id) DO UPDATE SET \
+                 disaster_type = EXCLUDED.disaster_type, severity = EXCLUDED.severity, status = EXCLUDED.status, \
+                 position = EXCLUDED.position, \
+                 province = COALESCE(EXCLUDED.province, analytics_disaster_facts.province), \
+                 city = COALESCE(EXCLUDED.city, analytics_disaster_facts.city), \
+                 reported_at = EXCLUDED.reported_at, verified_at = EXCLUDED.verified_at, \
+                 first_response_at = EXCLUDED.first_response_at, resolved_at = EXCLUDED.resolved_at, \
+                 closed_at = EXCLUDED.closed_at, projected_at = EXCLUDED.projected_at"
+        )
+            .bind::<SqlUuid, _>(fact.disaster_id.0)
+            .bind::<Text, _>(fact.disaster_type.name())
+            .bind::<Text, _>(fact.severity.as_str())
+            .bind::<Text, _>(fact.status.as_str())
+            .bind::<Float8, _>(fact.position.longitude)
+            .bind::<Float8, _>(fact.position.latitude)
+            .bind::<Nullable<Text>, _>(fact.province.as_deref())
+            .bind::<Nullable<Text>, _>(fact.city.as_deref())
+            .bind::<Timestamp, _>(fact.reported_at.naive_utc())
+            .bind::<Nullable<Timestamp>, _>(fact.verified_at.map(|t| t.naive_utc()))
+            .bind::<Nullable<Timestamp>, _>(fact.first_response_at.map(|t| t.naive_utc()))
+            .bind::<Nullable<Timestamp>, _>(fact.resolved_at.map(|t| t.naive_utc()))
+            .bind::<Nullable<Timestamp>, _>(fact.closed_at.map(|t| t.naive_utc()))
+            .execute(conn)?;
+        Ok(())
+    }
+
+    fn totals(conn: &mut DbConnection, filter: &AnalyticsFilter) -> AppResult<TotalsRow> {
+        Ok(Self::filtered(format!(
+            "SELECT COUNT(*) AS total, COUNT(*) FILTER (WHERE {open}) AS active, \
+                    COUNT(*) FILTER (WHERE f.verified_at IS NOT NULL) AS verified, \
+                    COUNT(*) FILTER (WHERE NOT ({open})) AS resolved \
+             FROM analytics_disaster_facts f WHERE {filter}",
+            open = OPEN, filter = FILTER
+        ), filter)
+            .get_result::<TotalsRow>(conn)?)
+    }
+
+    /// Counts per value of `bucket`, an SQL expression over `f`
+    fn breakdown(conn: &mut DbConnection, filter: &AnalyticsFilter, bucket: &str) -> AppResult<BTreeMap<String, i64>> {
+        let rows = Self::filtered(format!(
+            "SELECT {} AS bucket, COUNT(*) AS total FROM analytics_disaster_facts f WHERE {} GROUP BY 1",
+            bucket, FILTER
+        ), filter)
+            .load::<BucketRow>(conn)?;
+        Ok(rows.into_iter().map(|row| (row.bucket, row.total)).collect())
+    }
+
+    /// Minutes from report to `milestone` per value of `bucket`, leaving out disasters that have
+    /// not reached it and clock errors that put it before the report
+    fn durations(
+        conn: &mut DbConnection,
+        filter: &AnalyticsFilter,
+        milestone: Milestone,
+        bucket: &str,
+    ) -> AppResult<BTreeMap<String, DurationStats>> {
+        let rows = Self::filtered(format!(
+            "SELECT s.bucket, COUNT(*) AS samples, AVG(s.minutes) AS average, \
+                    percentile_cont(0.5) WITHIN GROUP (ORDER BY s.minutes) AS p50, \
+                    percentile_cont(0.9) WITHIN GROUP (ORDER BY s.minutes) AS p90, \
+                    percentile_cont(0.95) WITHIN GROUP (ORDER BY s.minutes) AS p95, \
+                    MIN(s.minutes) AS fastest, MAX(s.minutes) AS slowest \
+             FROM (SELECT {bucket} AS bucket, \
+                          (EXTRACT(EPOCH FROM ({milestone} - f.reported_at)) / 60.0)::float8 AS minutes \
+                   FROM analytics_disaster_facts f \
+                   WHERE {filter} AND {milestone} IS NOT NULL) s \
+             WHERE s.minutes >= 0 GROUP BY s.bucket",
+            bucket = bucket, milestone = milestone.column(), filter = FILTER
+        ), filter)
+            .load::<DurationRow>(conn)?;
+        Ok(rows.into_iter().map(|row| (row.bucket, DurationStats {
+            samples: row.samples,
+            average_minutes: row.average,
+            p50_minutes: row.p50,
+            p90_minutes: row.p90,
+            p95_minutes: row.p95,
+            fastest_minutes: row.fastest,
+            slowest_minutes: row.slowest,
+        })).collect())
+    }
+
+    fn overall_duration(conn: &mut DbConnection, filter: &AnalyticsFilter, milestone: Milestone) -> AppResult<DurationStats> {
+        Ok(Self::durations(conn, filter, milestone, "''")?.into_values().next().unwrap_or_default())
+    }
+
+    fn areas(conn: &mut DbConnection, filter: &AnalyticsFilter, column: &str) -> AppResult<Vec<AreaCount>> {
+        let rows = Self::filtered(format!(
+            "SELECT {column} AS name, COUNT(*) AS total, COUNT(*) FILTER (WHERE {open}) AS active \
+             FROM analytics_disaster_facts f WHERE {filter} AND {column} IS NOT NULL \
+             GROUP BY 1 ORDER BY total DESC, name",
+            column = column, open = OPEN, filter = FILTER
+        ), filter)
+            .load::<AreaRow>(conn)?;
+        Ok(rows.into_iter().map(|row| AreaCount { name: row.name, total: row.total, active: row.active }).collect())
+    }
+}
+
+#[async_trait]
+impl AnalyticsRepository for PostgresAnalyticsRepository {
+    async fn dashboard(&self, filter: &AnalyticsFilter) -> AppResult<DashboardSummary> {
+        let mut conn = self.get_connection()?;
+        let totals = Self::totals(&mut conn, filter)?;
+        Ok(DashboardSummary {
+            counts: DisasterCounts {
+                total: totals.total,
+                active: totals.active,
+                verified: totals.verified,
+                resolved: totals.resolved,
+                by_status: Self::breakdown(&mut conn, filter, "f.status")?,
+                by_type: Self::breakdown(&mut conn, filter, "f.disaster_type")?,
+                by_severity: Self::breakdown(&mut conn, filter, "f.severity")?,
+            },
+            time_to_verify: Self::overall_duration(&mut conn, filter, Milestone::Verified)?,
+            time_to_first_response: Self::overall_duration(&mut conn, filter, Milestone::FirstResponse)?,
+        })
+    }
+
+    async fn trends(&self, filter: &AnalyticsFilter) -> AppResult<DisasterTrends> {
+        let mut conn = self.get_connection()?;
+        let rows = Self::filtered(format!(
+            "SELECT date_trunc($8, f.reported_at) AS period, f.disaster_type AS bucket, COUNT(*) AS total \
+             FROM analytics_disaster_facts f WHERE {} GROUP BY 1, 2 ORDER BY 1, 2",
+            FILTER
+        ), filter)
+            .bind::<Text, _>(filter.granularity.trunc_unit())
+            .load::<PeriodRow>(&mut conn)?;
+
+        let mut by_period: Vec<PeriodCount> = Vec::new();
+        for row in rows {
+            let period = Self::utc(row.period);
+            match by_period.last_mut() {
+                Some(last) if last.period == period => {
+                    last.total += row.total;
+                    last.by_type.insert(row.bucket, row.total);
+                }
+                _ => by_period.push(PeriodCount {
+                    period,
+                    total: row.total,
+                    by_type: BTreeMap::from([(row.bucket, row.total)]),
+                }),
+            }
+        }
+
+        Ok(DisasterTrends {
+            granularity: filter.granularity,
+            by_period,
+            by_type: Self::breakdown(&mut conn, filter, "f.disaster_type")?,
+            by_severity: Self::breakdown(&mut conn, filter, "f.severity")?,
+            by_province: Self::breakdown(&mut conn, filter, "COALESCE(f.province, 'unknown')")?,
+        })
+    }
+
+    async fn response_times(&self, filter: &AnalyticsFilter) -> AppResult<ResponseTimeAnalytics> {
+        let mut conn = self.get_connection()?;
+        Ok(ResponseTimeAnalytics {
+            time_to_verify: Self::overall_duration(&mut conn, filter, Milestone::Verified)?,
+            time_to_first_response: Self::overall_duration(&mut conn, filter, Milestone::FirstResponse)?,
+            by_disaster_type: Self::durations(&mut conn, filter, Milestone::FirstResponse, "f.disaster_type")?,
+            by_severity: Self::durations(&mut conn, filter, Milestone::FirstResponse, "f.severity")?,
+        })
+    }
+
+    async fn geographic(&self, filter: &AnalyticsFilter) -> AppResult<GeographicAnalytics> {
+        let mut conn = self.get_connection()?;
+        let hotspots = Self::filtered(format!(
+            "SELECT ST_Y(h.cell) AS latitude, ST_X(h.cell) AS longitude, h.total, h.active FROM ( \
+                 SELECT ST_SnapToGrid(f.position::geometry, $8) AS cell, COUNT(*) AS total, \
+                        COUNT(*) FILTER (WHERE {open}) AS active \
+                 FROM analytics_disaster_facts f WHERE {filter} GROUP BY 1 \
+             ) h WHERE h.total > 1 ORDER BY h.total DESC, h.active DESC LIMIT $9",
+            open = OPEN, filter = FILTER
+        ), filter)
+            .bind::<Float8, _>(HOTSPOT_CELL_DEG)
+            .bind::<BigInt, _>(MAX_HOTSPOTS)
+            .load::<HotspotRow>(&mut conn)?;
+
+        Ok(GeographicAnalytics {
+            by_province: Self::areas(&mut conn, filter, "f.province")?,
+            by_city: Self::areas(&mut conn, filter, "f.city")?,
+            hotspots: hotspots.into_iter().map(|row| Hotspot {
+                latitude: row.latitude,
+                longitude: row.longitude,
+                total: row.total,
+                active: row.active,
+            }).collect(),
+        })
+    }
+
+    async fn facts(&self, filter: &AnalyticsFilter, limit: i64) -> AppResult<Vec<DisasterFact>> {
+        let mut conn = self.get_connection()?;
+        let rows = Self::filtered(format!(
+            "{} WHERE {} ORDER BY f.reported_at DESC LIMIT $8",
+            Self::fact_columns(), FILTER
+        ), filter)
+            .bind::<BigInt, _>(limit)
+            .load::<FactRow>(&mut conn)?;
+        rows.into_iter().map(Self::to_domain).collect()
+    }
+}
+
+#[async_trait]
+impl Projection for PostgresAnalyticsRepository {
+    async fn project(&self, event: &StoredEvent) -> AppResult<()> {
+        if aggregate_type_of(&event.metadata.event_type) != "disaster" {
+            return Ok(());
+        }
+        let disaster_event = match event.decode::<DisasterEvent>() {
+            Ok(disaster_event) if DisasterFact::tracks(&disaster_event) => disaster_event,
+            Ok(_) => return Ok(()),
+            Err(e) => {
+                tracing::warn!("Analytics projection skipped an event: {}", e);
+                return Ok(());
+            }
+        };
+
+        let disaster_id = disaster_event.disaster_id();
+        let existing = Self::load_fact(&mut self.get_connection()?, &disaster_id)?;
+        let fact = match existing.or_else(|| DisasterFact::opened(&disaster_event)) {
+            Some(fact) => Some(fact),
+            None => self.disasters.find_by_id(&disaster_id).await?.map(|d| DisasterFact::from_disaster(&d)),
+        };
+        let Some(mut fact) = fact else {
+            return Ok(());
+        };
+        fact.apply(&disaster_event);
+        Self::upsert_fact(&mut self.get_connection()?, &fact)
+    }
+
+    /// Empty the facts and seed one per stored disaster, so disasters without any recorded
+    /// history still count; the replay that follows brings the rest up to date
+    async fn reset(&self) -> AppResult<()> {
+        let disasters = self.disasters.find_all().await?;
+        let mut conn = self.get_connection()?;
+        conn.transaction::<_, AppError, _>(|conn| {
+            diesel::sql_query("TRUNCATE analytics_disaster_facts").execute(conn)?;
+            for disaster in &disasters {
+                Self::upsert_fact(conn, &DisasterFact::from_disaster(disaster))?;
+            }
+            Ok(())
+        })
+    }
+
+    async fn needs_rebuild(&self) -> AppResult<bool> {
+        let mut conn = self.get_connection()?;
+        let row = diesel::sql_query(
+            "SELECT EXISTS (SELECT 1 FROM disasters) \
+                    AND NOT EXISTS (SELECT 1 FROM analytics_disaster_facts) AS flag"
+        )
+            .get_result::<FlagRow>(&mut conn)?;
+        Ok(row.flag)
+    }
+}
@@ -0,0 +1,90 @@
+/// CAP alert import repository implementation
+/// Ledger of ingested CAP alerts in `cap_alert_imports`, unique per sender and identifier
+
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::domain::ports::repositories::CapAlertImportRepository;
+use crate::infrastructure::database::schemas::cap_alert_imports;
+use crate::infrastructure::database::{DbConnection, DbPool};
+use crate::shared::error::DatabaseError;
+use crate::shared::{AppError, AppResult, DisasterId, UserId};
+
+pub struct PostgresCapAlertImportRepository {
+    pool: DbPool,
+}
+
+impl PostgresCapAlertImportRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    fn get_connection(&self) -> AppResult<DbConnection> {
+        self.pool.get()
+            .map_err(|e| AppError::Database(DatabaseError::ConnectionPool(e)))
+    }
+}
+
+#[async_trait]
+impl CapAlertImportRepository for PostgresCapAlertImportRepository {
+    async fn claim(&self, sender: &str, identifier: &str, msg_type: &str, imported_by: &UserId) -> AppResult<bool> {
+        let mut conn = self.get_connection()?;
+        let inserted = diesel::insert_into(cap_alert_imports::table)
+            .values((
+                cap_alert_imports::id.eq(Uuid::new_v4()),
+                cap_alert_imports::sender.eq(sender),
+                cap_alert_imports::identifier.eq(identifier),
+                cap_alert_imports::msg_type.eq(msg_type),
+                cap_alert_imports::imported_by.eq(Some(imported_by.0)),
+                cap_alert_imports::imported_at.eq(Utc::now()),
+            ))
+            .on_conflict((cap_alert_imports::sender, cap_alert_imports::identifier))
+            .do_nothing()
+            .execute(&mut conn)?;
+        Ok(inserted > 0)
+    }
+
+    async fn attach_disaster(&self, sender: &str, identifier: &str, disaster_id: &DisasterId) -> AppResult<()> {
+        let mut conn = self.get_connection()?;
+        diesel::update(
+            cap_alert_imports::table
+                .filter(cap_alert_imports::sender.eq(sender))
+                .filter(cap_alert_imports::identifier.eq(identifier)),
+        )
+            .set(cap_alert_imports::disaster_id.eq(Some(disaster_id.0)))
+            .execute(&mut conn)?;
+        Ok(())
+    }
+
+    async fn release(&self, sender: &str, identifier: &str) -> AppResult<()> {
+        let mut conn = self.get_connection()?;
+        diesel::delete(
+            cap_alert_imports::table
+                .filter(cap_alert_imports::sender.eq(sender))
+                .filter(cap_alert_imports::identifier.eq(identifier)),
+        )
+            .execute(&mut conn)?;
+        Ok(())
+    }
+
+    async fn find_disaster(&self, references: &[(String, String)]) -> AppResult<Option<DisasterId>> {
+        let mut conn = self.get_connection()?;
+        let mut latest: Option<(chrono::DateTime<Utc>, Uuid)> = None;
+        for (sender, identifier) in references {
+            let found: Option<(chrono::DateTime<Utc>, Option<Uuid>)> = cap_alert_imports::table
+                .filter(cap_alert_imports::sender.eq(sender))
+                .filter(cap_alert_imports::identifier.eq(identifier))
+                .select((cap_alert_imports::imported_at, cap_alert_imports::disaster_id))
+                .first(&mut conn)
+                .optional()?;
+            if let Some((imported_at, Some(disaster_id))) = found {
+                if latest.is_none_or(|(newest, _)| imported_at > newest) {
+                    latest = Some((imported_at, disaster_id));
+                }
+            }
+        }
+        Ok(latest.map(|(_, id)| DisasterId(id)))
+    }
+}
@@ -1,16 +1,169 @@
 /// Disaster repository implementation
-/// Provides data access for disaster-related entities
+/// Maps the Disaster aggregate onto the `disasters`, `locations`, `disaster_analytics`
+/// and `disaster_reports` tables, using PostGIS for spatial queries
 
 use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, Utc};
 use diesel::prelude::*;
+use diesel::sql_types::{Array, Bool, Float4, Float8, Int4, Int8, Jsonb, Nullable, Text, Timestamp, Uuid as SqlUuid};
 use uuid::Uuid;
-use crate::shared::{AppResult, error::AppError};
+use crate::shared::{AppResult, error::AppError, Priority};
 use crate::infrastructure::database::{DbPool, DbConnection};
+use crate::infrastructure::database::schemas::{disaster_analytics, disaster_reports, disasters, locations, reports};
 use crate::domain::ports::{Repository, repositories::DisasterRepository};
-use crate::domain::entities::disaster::{Disaster, DisasterStatus, DisasterSeverity};
+use crate::domain::entities::disaster::{
+    Disaster, DisasterStatus, DisasterSeverity, DisasterType, DisasterTimeline,
+    EstimatedDamage, ResourceNeed, VerificationInfo,
+};
+use crate::domain::value_objects::Coordinates;
+use crate::infrastructure::event_store::PostgresEventStore;
+use crate::infrastructure::repository::event_sourced_disaster_repository::DisasterProjection;
+use crate::shared::events::DisasterEvent;
 use crate::{DisasterId, LocationId, UserId};
 use crate::shared::error::DatabaseError;
 
+/// Region recorded for locations created implicitly from a disaster's coordinates
+const UNASSIGNED_REGION: &str = "unassigned";
+
+/// Base projection joining a disaster with its primary location, analytics row
+/// and the reporter of the earliest linked report
+const SELECT_DISASTERS: &str = r#"
+SELECT d.id, d.disaster_type_id, d.disaster_type_other, d.name, d.description, d.severity,
+       d.status, d.priority, d.start_time, d.end_time, d.primary_location_id,
+       ST_Y(l.geometry::geometry) AS latitude, ST_X(l.geometry::geometry) AS longitude, l.province,
+       (SELECT r.reporter_id FROM disaster_reports dr JOIN reports r ON r.id = dr.report_id
+         WHERE dr.disaster_id = d.id ORDER BY dr.created_at ASC LIMIT 1) AS reporter_id,
+       d.assigned_responders, d.resource_needs, d.reported_at, d.verified_at,
+       d.first_response_at, d.closed_at, d.is_verified, d.verified_by,
+       d.verification_method, d.confidence_score,
+       a.affected_population, a.economic_loss_estimation, a.infrastructure_damage,
+       a.casualties, a.injuries, a.displaced_people, a.damaged_buildings,
+       d.created_at, d.updated_at, d.version
+FROM disasters d
+LEFT JOIN locations l ON l.id = d.primary_location_id
+LEFT JOIN disaster_analytics a ON a.disaster_id = d.id
+"#;
+
+/// Row returned by `SELECT_DISASTERS`
+#[derive(QueryableByName, Debug)]
+struct DisasterRow {
+    #[diesel(sql_type = SqlUuid)]
+    id: Uuid,
+    #[diesel(sql_type = Nullable<Int4>)]
+    disaster_type_id: Option<i32>,
+    #[diesel(sql_type = Nullable<Text>)]
+    disaster_type_other: Option<String>,
+    #[diesel(sql_type = Text)]
+    name: String,
+    #[diesel(sql_type = Nullable<Text>)]
+    description: Option<String>,
+    #[diesel(sql_type = Nullable<Int4>)]
+    severity: Option<i32>,
+    #[diesel(sql_type = Nullable<Text>)]
+    status: Option<String>,
+    #[diesel(sql_type = Int4)]
+    priority: i32,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    start_time: Option<NaiveDateTime>,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    end_time: Option<NaiveDateTime>,
+    #[diesel(sql_type = Nullable<SqlUuid>)]
+    #[allow(dead_code)]
+    primary_location_id: Option<Uuid>,
+    #[diesel(sql_type = Nullable<Float8>)]
+    latitude: Option<f64>,
+    #[diesel(sql_type = Nullable<Float8>)]
+    longitude: Option<f64>,
+    #[diesel(sql_type = Nullable<Text>)]
+    province: Option<String>,
+    #[diesel(sql_type = Nullable<SqlUuid>)]
+    reporter_id: Option<Uuid>,
+    #[diesel(sql_type = Array<SqlUuid>)]
+    assigned_responders: Vec<Uuid>,
+    #[diesel(sql_type = Jsonb)]
+    resource_needs: serde_json::Value,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    reported_at: Option<NaiveDateTime>,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    verified_at: Option<NaiveDateTime>,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    first_response_at: Option<NaiveDateTime>,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    closed_at: Option<NaiveDateTime>,
+    #[diesel(sql_type = Bool)]
+    is_verified: bool,
+    #[diesel(sql_type = Nullable<SqlUuid>)]
+    verified_by: Option<Uuid>,
+    #[diesel(sql_type = Nullable<Text>)]
+    verification_method: Option<String>,
+    #[diesel(sql_type = Nullable<Float4>)]
+    confidence_score: Option<f32>,
+    #[diesel(sql_type = Nullable<Int4>)]
+    affected_population: Option<i32>,
+    #[diesel(sql_type = Nullable<Int8>)]
+    economic_loss_estimation: Option<i64>,
+    #[diesel(sql_type = Nullable<Text>)]
+    infrastructure_damage: Option<String>,
+    #[diesel(sql_type = Nullable<Int4>)]
+    casualties: Option<i32>,
+    #[diesel(sql_type = Nullable<Int4>)]
+    injuries: Option<i32>,
+    #[diesel(sql_type = Nullable<Int4>)]
+    displaced_people: Option<i32>,
+    #[diesel(sql_type = Nullable<Int4>)]
+    damaged_buildings: Option<i32>,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    created_at: Option<NaiveDateTime>,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    updated_at: Option<NaiveDateTime>,
+    #[diesel(sql_type = Int8)]
+    version: i64,
+}
+
+/// Insertable/updatable columns of the `disasters` table owned by the aggregate
+#[derive(Insertable, AsChangeset, Debug)]
+#[diesel(table_name = disasters)]
+#[diesel(treat_none_as_null = true)]
+struct DisasterRecord {
+    disaster_type_id: Option<i32>,
+    disaster_type_other: Option<String>,
+    name: String,
+    description: Option<String>,
+    severity: Option<i32>,
+    status: Option<String>,
+    priority: i32,
+    start_time: Option<NaiveDateTime>,
+    end_time: Option<NaiveDateTime>,
+    assigned_responders: Vec<Uuid>,
+    resource_needs: serde_json::Value,
+    reported_at: Option<NaiveDateTime>,
+    verified_at: Option<NaiveDateTime>,
+    first_response_at: Option<NaiveDateTime>,
+    closed_at: Option<NaiveDateTime>,
+    is_verified: bool,
+    verified_by: Option<Uuid>,
+    verification_method: Option<String>,
+    confidence_score: Option<f32>,
+    updated_at: Option<NaiveDateTime>,
+    version: i64,
+}
+
+/// One row per disaster holding affected population and the damage estimate
+#[derive(Insertable, AsChangeset, Debug)]
+#[diesel(table_name = disaster_analytics)]
+#[diesel(treat_none_as_null = true)]
+struct DisasterAnalyticsRecord {
+    disaster_id: Option<Uuid>,
+    affected_population: Option<i32>,
+    economic_loss_estimation: Option<i64>,
+    infrastructure_damage: Option<String>,
+    casualties: Option<i32>,
+    injuries: Option<i32>,
+    displaced_people: Option<i32>,
+    damaged_buildings: Option<i32>,
+    updated_at: Option<NaiveDateTime>,
+}
+
 pub struct PostgresDisasterRepository {
     pool: DbPool,
 }
@@ -20,101 +173,831 @@ impl PostgresDisasterRepository {
         Self { pool }
     }
 
-    fn get_connection(&self) -> AppResult<crate::infrastructure::database::DbConnection> {
+    fn get_connection(&self) -> AppResult<DbConnection> {
         self.pool.get()
             .map_err(|e| AppError::Database(
                 DatabaseError::ConnectionPool(e)
             ))
     }
+
+    // ------------------------------------------------------------------
+    // Enum <-> column mappings
+    // ------------------------------------------------------------------
+
+    /// Map a domain disaster type onto the seeded `disaster_types` rows
+    pub(crate) fn type_to_columns(disaster_type: &DisasterType) -> (Option<i32>, Option<String>) {
+        match disaster_type {
+            DisasterType::Flood => (Some(1), None),
+            DisasterType::Earthquake => (Some(2), None),
+            DisasterType::Fire => (Some(3), None),
+            DisasterType::Landslide => (Some(4), None),
+            DisasterType::Tsunami => (Some(5), None),
+            DisasterType::VolcanicEruption => (Some(6), None),
+            DisasterType::Storm => (Some(7), None),
+            DisasterType::Drought => (Some(8), None),
+            DisasterType::Epidemic => (Some(9), None),
+            DisasterType::TechnologicalDisaster => (Some(10), None),
+            DisasterType::Other(label) => (None, Some(label.clone())),
+        }
+    }
+
+    pub(crate) fn columns_to_type(type_id: Option<i32>, other: Option<String>) -> DisasterType {
+        match type_id {
+            Some(1) => DisasterType::Flood,
+            Some(2) => DisasterType::Earthquake,
+            Some(3) => DisasterType::Fire,
+            Some(4) => DisasterType::Landslide,
+            Some(5) => DisasterType::Tsunami,
+            Some(6) => DisasterType::VolcanicEruption,
+            Some(7) => DisasterType::Storm,
+            Some(8) => DisasterType::Drought,
+            Some(9) => DisasterType::Epidemic,
+            Some(10) => DisasterType::TechnologicalDisaster,
+            _ => DisasterType::Other(other.unwrap_or_else(|| "unknown".to_string())),
+        }
+    }
+
+    fn severity_to_i32(severity: &DisasterSeverity) -> i32 {
+        match severity {
+            DisasterSeverity::Minor => 1,
+            DisasterSeverity::Moderate => 2,
+            DisasterSeverity::Major => 3,
+            DisasterSeverity::Severe => 4,
+            DisasterSeverity::Critical => 5,
+            DisasterSeverity::Catastrophic => 6,
+        }
+    }
+
+    fn i32_to_severity(value: Option<i32>) -> DisasterSeverity {
+        match value.unwrap_or(1) {
+            i32::MIN..=1 => DisasterSeverity::Minor,
+            2 => DisasterSeverity::Moderate,
+            3 => DisasterSeverity::Major,
+            4 => DisasterSeverity::Severe,
+            5 => DisasterSeverity::Critical,
+            _ => DisasterSeverity::Catastrophic,
+        }
+    }
+
+    fn status_to_str(status: &DisasterStatus) -> &'static str {
+        match status {
+            DisasterStatus::Reported => "reported",
+            DisasterStatus::Verified => "verified",
+            DisasterStatus::Responded => "responded",
+            DisasterStatus::Resolved => "resolved",
+            DisasterStatus::Closed => "closed",
+        }
+    }
+
+    fn str_to_status(value: Option<&str>) -> DisasterStatus {
+        match value.unwrap_or("").to_lowercase().as_str() {
+            "verified" | "active" => DisasterStatus::Verified,
+            "responded" | "contained" => DisasterStatus::Responded,
+            "resolved" => DisasterStatus::Resolved,
+            "closed" => DisasterStatus::Closed,
+            _ => DisasterStatus::Reported,
+        }
+    }
+
+    fn priority_to_i32(priority: &Priority) -> i32 {
+        priority.clone() as i32
+    }
+
+    fn i32_to_priority(value: i32) -> Priority {
+        match value {
+            i32::MIN..=1 => Priority::Low,
+            2 => Priority::Normal,
+            3 => Priority::High,
+            4 => Priority::Critical,
+            _ => Priority::Emergency,
+        }
+    }
+
+    fn naive(dt: Option<DateTime<Utc>>) -> Option<NaiveDateTime> {
+        dt.map(|d| d.naive_utc())
+    }
+
+    fn utc(dt: Option<NaiveDateTime>) -> Option<DateTime<Utc>> {
+        dt.map(|d| d.and_utc())
+    }
+
+    // ------------------------------------------------------------------
+    // Row <-> entity conversion
+    // ------------------------------------------------------------------
+
+    fn to_record(entity: &Disaster) -> AppResult<DisasterRecord> {
+        let (disaster_type_id, disaster_type_other) = Self::type_to_columns(&entity.disaster_type);
+        let resource_needs = serde_json::to_value(&entity.resources_needed)
+            .map_err(|e| AppError::Serialization(e.to_string()))?;
+
+        Ok(DisasterRecord {
+            disaster_type_id,
+            disaster_type_other,
+            name: entity.title.clone(),
+            description: Some(entity.description.clone()),
+            severity: Some(Self::severity_to_i32(&entity.severity)),
+            status: Some(Self::status_to_str(&entity.status).to_string()),
+            priority: Self::priority_to_i32(&entity.priority),
+            start_time: Self::naive(entity.timeline.occurred_at),
+            end_time: Self::naive(entity.timeline.resolved_at),
+            assigned_responders: entity.assigned_responders.iter().map(|r| r.0).collect(),
+            resource_needs,
+            reported_at: Some(entity.timeline.reported_at.naive_utc()),
+            verified_at: Self::naive(entity.timeline.verified_at),
+            first_response_at: Self::naive(entity.timeline.first_response_at),
+            closed_at: Self::naive(entity.timeline.closed_at),
+            is_verified: entity.verification.is_verified,
+            verified_by: entity.verification.verified_by.map(|u| u.0),
+            verification_method: entity.verification.verification_method.clone(),
+            confidence_score: entity.verification.confidence_score,
+            updated_at: Some(entity.updated_at.naive_utc()),
+            version: entity.version,
+        })
+    }
+
+    fn to_analytics_record(entity: &Disaster) -> DisasterAnalyticsRecord {
+        let damage = entity.estimated_damage.as_ref();
+        DisasterAnalyticsRecord {
+            disaster_id: Some(entity.id.0),
+            affected_population: entity.affected_population.map(|p| p as i32),
+            economic_loss_estimation: damage.and_then(|d| d.economic_loss).map(|l| l.round() as i64),
+            infrastructure_damage: damage.and_then(|d| d.description.clone()),
+            casualties: damage.map(|d| d.casualties as i32),
+            injuries: damage.map(|d| d.injuries as i32),
+            displaced_people: damage.map(|d| d.displaced_people as i32),
+            damaged_buildings: damage.map(|d| d.damaged_buildings as i32),
+            updated_at: Some(Utc::now().naive_utc()),
+        }
+    }
+
+    fn to_domain(row: DisasterRow) -> AppResult<Disaster> {
+        let now = Utc::now();
+        let created_at = Self::utc(row.created_at).unwrap_or(now);
+        let reported_at = Self::utc(row.reported_at).unwrap_or(created_at);
+        let verified_at = Self::utc(row.verified_at);
+
+        let location = Coordinates::new(row.latitude.unwrap_or(0.0), row.longitude.unwrap_or(0.0))
+            .map_err(|e| AppError::DataConsistency(format!("Disaster {} has invalid location: {}", row.id, e)))?;
+
+        let resources_needed: Vec<ResourceNeed> = serde_json::from_value(row.resource_needs)
+            .map_err(|e| AppError::Serialization(format!("Invalid resource needs for disaster {}: {}", row.id, e)))?;
+
+        // Damage columns are written together; casualties doubles as the presence marker
+        let estimated_damage = row.casualties.map(|casualties| EstimatedDamage {
+            economic_loss: row.economic_loss_estimation.map(|l| l as f64),
+            casualties: casualties.max(0) as u32,
+            injuries: row.injuries.unwrap_or(0).max(0) as u32,
+            displaced_people: row.displaced_people.unwrap_or(0).max(0) as u32,
+            damaged_buildings: row.damaged_buildings.unwrap_or(0).max(0) as u32,
+            description: row.infrastructure_damage.clone(),
+        });
+
+        Ok(Disaster {
+            id: DisasterId(row.id),
+            title: row.name,
+            description: row.description.unwrap_or_default(),
+            disaster_type: Self::columns_to_type(row.disaster_type_id, row.disaster_type_other),
+            severity: Self::i32_to_severity(row.severity),
+            status: Self::str_to_status(row.status.as_deref()),
+            priority: Self::i32_to_priority(row.priority),
+            location,
+            province: row.province,
+            reporter_id: UserId(row.reporter_id.unwrap_or(Uuid::nil())),
+            assigned_responders: row.assigned_responders.into_iter().map(UserId).collect(),
+            affected_population: row.affected_population.map(|p| p.max(0) as u32),
+            estimated_damage,
+            resources_needed,
+            timeline: DisasterTimeline {
+                reported_at,
+                occurred_at: Self::utc(row.start_time),
+                verified_at,
+                first_response_at: Self::utc(row.first_response_at),
+                resolved_at: Self::utc(row.end_time),
+                closed_at: Self::utc(row.closed_at),
+            },
+            verification: VerificationInfo {
+                is_verified: row.is_verified,
+                verified_by: row.verified_by.map(UserId),
+                verified_at,
+                verification_method: row.verification_method,
+                confidence_score: row.confidence_score,
+            },
+            created_at,
+            updated_at: Self::utc(row.updated_at).unwrap_or(created_at),
+            version: row.version,
+            persisted_version: row.version,
+            uncommitted_events: Vec::new(),
+        })
+    }
+
+    fn rows_to_domain(rows: Vec<DisasterRow>) -> Vec<Disaster> {
+        rows.into_iter()
+            .filter_map(|row| {
+                let id = row.id;
+                Self::to_domain(row)
+                    .map_err(|e| tracing::warn!("Skipping unreadable disaster {}: {}", id, e))
+                    .ok()
+            })
+            .collect()
+    }
+
+    fn upsert_analytics(conn: &mut PgConnection, entity: &Disaster) -> QueryResult<usize> {
+        let record = Self::to_analytics_record(entity);
+        diesel::insert_into(disaster_analytics::table)
+            .values(&record)
+            .on_conflict(disaster_analytics::disaster_id)
+            .do_update()
+            .set(&record)
+            .execute(conn)
+    }
+
+    /// Insert the row, its primary location, analytics and originating report
+    fn insert_rows(conn: &mut PgConnection, entity: &Disaster) -> AppResult<()> {
+        let record = Self::to_record(entity)?;
+        let (disaster_type_id, _) = Self::type_to_columns(&entity.disaster_type);
+        let location_id = Uuid::new_v4();
+        let report_id = Uuid::new_v4();
+        let created_at = entity.created_at.naive_utc();
+
+        // Primary location carrying the PostGIS point
+        diesel::sql_query(
+            "INSERT INTO locations (id, name, region, geometry) \
+             VALUES ($1, $2, $3, ST_SetSRID(ST_MakePoint($4, $5), 4326)::geography)"
+        )
+            .bind::<SqlUuid, _>(location_id)
+            .bind::<Text, _>(&entity.title)
+            .bind::<Text, _>(UNASSIGNED_REGION)
+            .bind::<Float8, _>(entity.location.longitude)
+            .bind::<Float8, _>(entity.location.latitude)
+            .execute(conn)?;
+
+        let inserted = diesel::insert_into(disasters::table)
+            .values((
+                disasters::id.eq(entity.id.0),
+                disasters::primary_location_id.eq(Some(location_id)),
+                disasters::created_at.eq(Some(created_at)),
+                &record,
+            ))
+            .execute(conn);
+
+        match inserted {
+            Err(diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::UniqueViolation, _)) => {
+                return Err(AppError::Conflict(format!("Disaster {} already exists", entity.id)));
+            }
+            other => { other?; }
+        }
+
+        Self::upsert_analytics(conn, entity)?;
+
+        // The originating report, linked so the reporter is recorded in disaster_reports
+        diesel::insert_into(reports::table)
+            .values((
+                reports::id.eq(report_id),
+                reports::reporter_id.eq(Some(entity.reporter_id.0)),
+                reports::is_anonymous.eq(Some(false)),
+                reports::disaster_type_id.eq(disaster_type_id),
+                reports::title.eq(&entity.title),
+                reports::description.eq(Some(&entity.description)),
+                reports::location_id.eq(Some(location_id)),
+                reports::estimated_severity.eq(Some(Self::severity_to_i32(&entity.severity).min(5))),
+                reports::status.eq(Some(if entity.verification.is_verified { "verified" } else { "reported" })),
+                reports::created_at.eq(Some(entity.timeline.reported_at.naive_utc())),
+            ))
+            .execute(conn)?;
+
+        diesel::insert_into(disaster_reports::table)
+            .values((
+                disaster_reports::disaster_id.eq(entity.id.0),
+                disaster_reports::report_id.eq(report_id),
+                disaster_reports::created_at.eq(Some(entity.timeline.reported_at.naive_utc())),
+                disaster_reports::is_origin.eq(true),
+            ))
+            .execute(conn)?;
+
+        Ok(())
+    }
+
+    /// Update the row on top of the version it was loaded at, with its location and analytics
+    fn update_rows(conn: &mut PgConnection, entity: &Disaster) -> AppResult<()> {
+        let record = Self::to_record(entity)?;
+
+        // Optimistic concurrency: only apply on top of the version we loaded
+        let updated = diesel::update(
+            disasters::table
+                .filter(disasters::id.eq(entity.id.0))
+                .filter(disasters::version.eq(entity.persisted_version)),
+        )
+            .set(&record)
+            .execute(conn)?;
+
+        if updated == 0 {
+            let current: Option<i64> = disasters::table
+                .filter(disasters::id.eq(entity.id.0))
+                .select(disasters::version)
+                .first(conn)
+                .optional()?;
+
+            return Err(match current {
+                Some(current) => AppError::Conflict(format!(
+                    "Disaster {} was modified concurrently (expected version {}, found {})",
+                    entity.id, entity.persisted_version, current
+                )),
+                None => AppError::NotFound(format!("Disaster {} not found", entity.id)),
+            });
+        }
+
+        diesel::sql_query(
+            "UPDATE locations SET geometry = ST_SetSRID(ST_MakePoint($1, $2), 4326)::geography \
+             WHERE id = (SELECT primary_location_id FROM disasters WHERE id = $3)"
+        )
+            .bind::<Float8, _>(entity.location.longitude)
+            .bind::<Float8, _>(entity.location.latitude)
+            .bind::<SqlUuid, _>(entity.id.0)
+            .execute(conn)?;
+
+        Self::upsert_analytics(conn, entity)?;
+        Ok(())
+    }
+
+    fn load_one(&self, conn: &mut PgConnection, disaster_id: Uuid) -> AppResult<Option<Disaster>> {
+        let row = diesel::sql_query(format!("{} WHERE d.id = $1", SELECT_DISASTERS))
+            .bind::<SqlUuid, _>(disaster_id)
+            .get_result::<DisasterRow>(conn)
+            .optional()?;
+
+        row.map(Self::to_domain).transpose()
+    }
 }
 
 #[async_trait]
 impl Repository<Disaster, DisasterId> for PostgresDisasterRepository {
-    async fn find_by_id(&self, _id: &DisasterId) -> AppResult<Option<Disaster>> {
-        // Implementation would go here - using mock for now
-        Ok(None)
+    async fn find_by_id(&self, id: &DisasterId) -> AppResult<Option<Disaster>> {
+        DisasterRepository::find_by_id(self, id).await
     }
 
     async fn save(&self, entity: &Disaster) -> AppResult<Disaster> {
-        // Implementation would go here - using mock for now
-        Ok(entity.clone())
+        DisasterRepository::save(self, entity).await
     }
 
     async fn update(&self, entity: &Disaster) -> AppResult<Disaster> {
-        // Implementation would go here - using mock for now
-        Ok(entity.clone())
+        DisasterRepository::update(self, entity).await
     }
 
-    async fn delete(&self, _id: &DisasterId) -> AppResult<bool> {
-        // Implementation would go here - using mock for now
-        Ok(true)
+    async fn delete(&self, id: &DisasterId) -> AppResult<bool> {
+        DisasterRepository::delete(self, id).await
     }
 
     async fn find_all(&self) -> AppResult<Vec<Disaster>> {
-        // Implementation would go here - using mock for now
-        Ok(Vec::new())
+        DisasterRepository::find_all(self).await
     }
 }
 
 #[async_trait]
 impl DisasterRepository for PostgresDisasterRepository {
-    async fn find_by_id(&self, _id: &DisasterId) -> AppResult<Option<Disaster>> {
-        Ok(None)
+    async fn find_by_id(&self, id: &DisasterId) -> AppResult<Option<Disaster>> {
+        let mut conn = self.get_connection()?;
+        self.load_one(&mut conn, id.0)
     }
 
     async fn save(&self, entity: &Disaster) -> AppResult<Disaster> {
-        Ok(entity.clone())
+        let mut conn = self.get_connection()?;
+        conn.transaction::<_, AppError, _>(|conn| Self::insert_rows(conn, entity))?;
+
+        let mut saved = entity.clone();
+        saved.persisted_version = saved.version;
+        Ok(saved)
     }
 
     async fn update(&self, entity: &Disaster) -> AppResult<Disaster> {
-        Ok(entity.clone())
+        let mut conn = self.get_connection()?;
+        conn.transaction::<_, AppError, _>(|conn| Self::update_rows(conn, entity))?;
+
+        let mut saved = entity.clone();
+        saved.persisted_version = saved.version;
+        Ok(saved)
     }
 
-    async fn delete(&self, _id: &DisasterId) -> AppResult<bool> {
-        Ok(true)
+    async fn delete(&self, id: &DisasterId) -> AppResult<bool> {
+        let mut conn = self.get_connection()?;
+        conn.transaction::<_, AppError, _>(|conn| {
+            let Some(location_id) = disasters::table
+                .filter(disasters::id.eq(id.0))
+                .select(disasters::primary_location_id)
+                .first::<Option<Uuid>>(conn)
+                .optional()? else {
+                return Ok(false);
+            };
+
+            // The report `save` created for the reporter; reports filed separately and linked later are kept
+            let origin_reports: Vec<Uuid> = disaster_reports::table
+                .filter(disaster_reports::disaster_id.eq(id.0))
+                .filter(disaster_reports::is_origin.eq(true))
+                .select(disaster_reports::report_id)
+                .load(conn)?;
+
+            // disaster_reports and disaster_analytics cascade
+            diesel::delete(disasters::table.filter(disasters::id.eq(id.0))).execute(conn)?;
+            // An origin report since linked to another disaster still belongs to that one
+            diesel::delete(
+                reports::table
+                    .filter(reports::id.eq_any(&origin_reports))
+                    .filter(diesel::dsl::not(diesel::dsl::exists(
+                        disaster_reports::table.filter(disaster_reports::report_id.eq(reports::id)),
+                    ))),
+            )
+                .execute(conn)?;
+
+            if let Some(location_id) = location_id {
+                // Kept when anything else still refers to it; the savepoint keeps that failure
+                // from aborting the delete
+                let removed = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                    diesel::delete(locations::table.filter(locations::id.eq(location_id))).execute(conn)
+                });
+                if let Err(e) = removed {
+                    tracing::debug!("Keeping location {} of deleted disaster {}: {}", location_id, id, e);
+                }
+            }
+            Ok(true)
+        })
     }
 
     async fn find_all(&self) -> AppResult<Vec<Disaster>> {
-        Ok(Vec::new())
+        let mut conn = self.get_connection()?;
+        let rows = diesel::sql_query(format!("{} ORDER BY d.created_at DESC", SELECT_DISASTERS))
+            .load::<DisasterRow>(&mut conn)?;
+        Ok(Self::rows_to_domain(rows))
     }
 
-    async fn find_by_status(&self, _status: DisasterStatus) -> AppResult<Vec<Disaster>> {
-        // Implementation would go here - using mock data for now
-        Ok(Vec::new())
+    async fn find_by_status(&self, status: DisasterStatus) -> AppResult<Vec<Disaster>> {
+        let mut conn = self.get_connection()?;
+        let rows = diesel::sql_query(format!("{} WHERE d.status = $1 ORDER BY d.created_at DESC", SELECT_DISASTERS))
+            .bind::<Text, _>(Self::status_to_str(&status))
+            .load::<DisasterRow>(&mut conn)?;
+        Ok(Self::rows_to_domain(rows))
     }
 
-    async fn find_by_severity(&self, _severity: DisasterSeverity) -> AppResult<Vec<Disaster>> {
-        // Implementation would go here - using mock data for now
-        Ok(Vec::new())
+    async fn find_by_severity(&self, severity: DisasterSeverity) -> AppResult<Vec<Disaster>> {
+        let mut conn = self.get_connection()?;
+        let rows = diesel::sql_query(format!("{} WHERE d.severity = $1 ORDER BY d.created_at DESC", SELECT_DISASTERS))
+            .bind::<Int4, _>(Self::severity_to_i32(&severity))
+            .load::<DisasterRow>(&mut conn)?;
+        Ok(Self::rows_to_domain(rows))
     }
 
-    async fn find_by_reporter(&self, _reporter_id: &UserId) -> AppResult<Vec<Disaster>> {
-        // Implementation would go here - using mock data for now
-        Ok(Vec::new())
+    async fn find_by_reporter(&self, reporter_id: &UserId) -> AppResult<Vec<Disaster>> {
+        let mut conn = self.get_connection()?;
+        let rows = diesel::sql_query(format!(
+            "{} WHERE EXISTS (SELECT 1 FROM disaster_reports dr JOIN reports r ON r.id = dr.report_id \
+             WHERE dr.disaster_id = d.id AND r.reporter_id = $1) ORDER BY d.created_at DESC",
+            SELECT_DISASTERS
+        ))
+            .bind::<SqlUuid, _>(reporter_id.0)
+            .load::<DisasterRow>(&mut conn)?;
+        Ok(Self::rows_to_domain(rows))
     }
 
-    async fn find_nearby(&self, _lat: f64, _lng: f64, _radius_km: f64) -> AppResult<Vec<Disaster>> {
-        // Implementation would go here - using mock data for now
-        Ok(Vec::new())
+    async fn find_nearby(&self, lat: f64, lng: f64, radius_km: f64) -> AppResult<Vec<Disaster>> {
+        let mut conn = self.get_connection()?;
+        let rows = diesel::sql_query(format!(
+            "{} WHERE ST_DWithin(l.geometry, ST_SetSRID(ST_MakePoint($1, $2), 4326)::geography, $3) \
+             ORDER BY ST_Distance(l.geometry, ST_SetSRID(ST_MakePoint($1, $2), 4326)::geography) ASC",
+            SELECT_DISASTERS
+        ))
+            .bind::<Float8, _>(lng)
+            .bind::<Float8, _>(lat)
+            .bind::<Float8, _>(radius_km * 1000.0)
+            .load::<DisasterRow>(&mut conn)?;
+        Ok(Self::rows_to_domain(rows))
     }
 
     async fn find_active(&self) -> AppResult<Vec<Disaster>> {
-        // Implementation would go here - using mock data for now
-        Ok(Vec::new())
+        let mut conn = self.get_connection()?;
+        let rows = diesel::sql_query(format!(
+            "{} WHERE COALESCE(d.status, 'reported') NOT IN ('resolved', 'closed') ORDER BY d.severity DESC, d.created_at DESC",
+            SELECT_DISASTERS
+        ))
+            .load::<DisasterRow>(&mut conn)?;
+        Ok(Self::rows_to_domain(rows))
+    }
+
+    async fn find_by_location(&self, location_id: &LocationId) -> AppResult<Vec<Disaster>> {
+        let mut conn = self.get_connection()?;
+        let rows = diesel::sql_query(format!("{} WHERE d.primary_location_id = $1 ORDER BY d.created_at DESC", SELECT_DISASTERS))
+            .bind::<SqlUuid, _>(location_id.0)
+            .load::<DisasterRow>(&mut conn)?;
+        Ok(Self::rows_to_domain(rows))
+    }
+
+    async fn update_status(&self, id: &DisasterId, status: DisasterStatus, updated_by: &UserId) -> AppResult<bool> {
+        let mut conn = self.get_connection()?;
+        let now = Utc::now().naive_utc();
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            // Stamp the matching timeline column the first time a status is reached
+            let updated = diesel::update(disasters::table.filter(disasters::id.eq(id.0)))
+                .set((
+                    disasters::status.eq(Some(Self::status_to_str(&status))),
+                    disasters::version.eq(disasters::version + 1),
+                    disasters::updated_at.eq(Some(now)),
+                ))
+                .execute(conn)?;
+
+            if updated > 0 {
+                let stamp = match status {
+                    DisasterStatus::Verified => Some(
+                        "verified_at = COALESCE(verified_at, $1), verified_by = COALESCE(verified_by, $3), is_verified = TRUE"
+                    ),
+                    DisasterStatus::Responded => Some("first_response_at = COALESCE(first_response_at, $1)"),
+                    DisasterStatus::Resolved => Some("end_time = COALESCE(end_time, $1)"),
+                    DisasterStatus::Closed => Some("closed_at = COALESCE(closed_at, $1)"),
+                    DisasterStatus::Reported => None,
+                };
+                if let Some(set_clause) = stamp {
+                    let mut stamp_query = diesel::sql_query(format!("UPDATE disasters SET {} WHERE id = $2", set_clause))
+                        .into_boxed()
+                        .bind::<Timestamp, _>(now)
+                        .bind::<SqlUuid, _>(id.0);
+                    if status == DisasterStatus::Verified {
+                        stamp_query = stamp_query.bind::<SqlUuid, _>(updated_by.0);
+                    }
+                    stamp_query.execute(conn)?;
+                }
+            }
+
+            Ok(updated > 0)
+        })
+    }
+
+    async fn assign_responder(&self, disaster_id: &DisasterId, responder_id: &UserId) -> AppResult<bool> {
+        let mut conn = self.get_connection()?;
+        let updated = diesel::sql_query(
+            "UPDATE disasters SET assigned_responders = array_append(assigned_responders, $2), \
+             version = version + 1, updated_at = NOW() \
+             WHERE id = $1 AND NOT ($2 = ANY(assigned_responders))"
+        )
+            .bind::<SqlUuid, _>(disaster_id.0)
+            .bind::<SqlUuid, _>(responder_id.0)
+            .execute(&mut conn)?;
+        Ok(updated > 0)
+    }
+}
+
+#[async_trait]
+impl DisasterProjection for PostgresDisasterRepository {
+    async fn commit(&self, entity: &Disaster, is_new: bool, base: u32, events: &[DisasterEvent]) -> AppResult<Disaster> {
+        let mut conn = self.get_connection()?;
+        let key = entity.id.to_string();
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            if is_new {
+                Self::insert_rows(conn, entity)?;
+            } else {
+                Self::update_rows(conn, entity)?;
+            }
+            for (index, event) in events.iter().enumerate() {
+                PostgresEventStore::append_in(conn, &key, Some(base + index as u32), event, None)?;
+            }
+            Ok(())
+        })?;
+
+        let mut saved = entity.clone();
+        saved.persisted_version = saved.version;
+        Ok(saved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use diesel::r2d2::{ConnectionManager, Pool};
+    use diesel_migrations::MigrationHarness;
+    use crate::infrastructure::database::MIGRATIONS;
+
+    fn monas() -> Coordinates {
+        Coordinates::new(-6.1754, 106.8272).unwrap()
+    }
+
+    /// A flood carrying every field the repository spreads over its tables
+    fn documented_flood(reporter: UserId) -> Disaster {
+        let mut disaster = Disaster::new(
+            "Banjir Kampung Melayu".to_string(),
+            "Ciliwung meluap setinggi 1,5 m".to_string(),
+            DisasterType::Flood,
+            DisasterSeverity::Major,
+            monas(),
+            reporter,
+        ).unwrap();
+        let verifier = UserId(Uuid::new_v4());
+        let reported_at = disaster.timeline.reported_at;
+        disaster.status = DisasterStatus::Responded;
+        disaster.timeline.occurred_at = Some(reported_at - Duration::hours(2));
+        disaster.timeline.verified_at = Some(reported_at + Duration::minutes(20));
+        disaster.timeline.first_response_at = Some(reported_at + Duration::minutes(45));
+        disaster.verification = VerificationInfo {
+            is_verified: true,
+            verified_by: Some(verifier),
+            verified_at: disaster.timeline.verified_at,
+            verification_method: Some("field_check".to_string()),
+            confidence_score: Some(0.9),
+        };
+        disaster.resources_needed = vec![ResourceNeed {
+            resource_type: "perahu karet".to_string(),
+            quantity: 4,
+            urgency: Priority::Critical,
+            description: Some("Evakuasi lansia".to_string()),
+        }];
+        disaster.affected_population = Some(1200);
+        disaster.estimated_damage = Some(EstimatedDamage {
+            economic_loss: Some(2_500_000_000.0),
+            casualties: 0,
+            injuries: 3,
+            displaced_people: 450,
+            damaged_buildings: 80,
+            description: Some("Rumah di bantaran terendam".to_string()),
+        });
+        disaster
+    }
+
+    /// The row `SELECT_DISASTERS` returns for what `insert_rows` writes
+    fn stored_row(entity: &Disaster) -> DisasterRow {
+        let record = PostgresDisasterRepository::to_record(entity).unwrap();
+        let analytics = PostgresDisasterRepository::to_analytics_record(entity);
+        DisasterRow {
+            id: entity.id.0,
+            disaster_type_id: record.disaster_type_id,
+            disaster_type_other: record.disaster_type_other,
+            name: record.name,
+            description: record.description,
+            severity: record.severity,
+            status: record.status,
+            priority: record.priority,
+            start_time: record.start_time,
+            end_time: record.end_time,
+            primary_location_id: Some(Uuid::new_v4()),
+            latitude: Some(entity.location.latitude),
+            longitude: Some(entity.location.longitude),
+            province: None,
+            reporter_id: Some(entity.reporter_id.0),
+            assigned_responders: record.assigned_responders,
+            resource_needs: record.resource_needs,
+            reported_at: record.reported_at,
+            verified_at: record.verified_at,
+            first_response_at: record.first_response_at,
+            closed_at: record.closed_at,
+            is_verified: record.is_verified,
+            verified_by: record.verified_by,
+            verification_method: record.verification_method,
+            confidence_score: record.confidence_score,
+            affected_population: analytics.affected_population,
+            economic_loss_estimation: analytics.economic_loss_estimation,
+            infrastructure_damage: analytics.infrastructure_damage,
+            casualties: analytics.casualties,
+            injuries: analytics.injuries,
+            displaced_people: analytics.displaced_people,
+            damaged_buildings: analytics.damaged_buildings,
+            created_at: Some(entity.created_at.naive_utc()),
+            updated_at: record.updated_at,
+            version: record.version,
+        }
+    }
+
+    #[test]
+    fn timeline_verification_and_resource_needs_survive_the_columns() {
+        let disaster = documented_flood(UserId(Uuid::new_v4()));
+        let loaded = PostgresDisasterRepository::to_domain(stored_row(&disaster)).unwrap();
+
+        assert_eq!(loaded.status, DisasterStatus::Responded);
+        assert_eq!(loaded.severity, DisasterSeverity::Major);
+        assert_eq!(loaded.timeline.occurred_at, disaster.timeline.occurred_at);
+        assert_eq!(loaded.timeline.verified_at, disaster.timeline.verified_at);
+        assert_eq!(loaded.timeline.first_response_at, disaster.timeline.first_response_at);
+        assert_eq!(loaded.verification.verified_by, disaster.verification.verified_by);
+        assert_eq!(loaded.verification.verification_method.as_deref(), Some("field_check"));
+        assert_eq!(loaded.resources_needed.len(), 1);
+        assert_eq!(loaded.resources_needed[0].urgency, Priority::Critical);
+        assert_eq!(loaded.resources_needed[0].quantity, 4);
+        let damage = loaded.estimated_damage.unwrap();
+        assert_eq!((damage.injuries, damage.displaced_people), (3, 450));
+        assert_eq!(loaded.affected_population, Some(1200));
+        // A loaded disaster is clean: it updates on top of the version it was read at
+        assert_eq!(loaded.persisted_version, loaded.version);
     }
 
-    async fn find_by_location(&self, _location_id: &LocationId) -> AppResult<Vec<Disaster>> {
-        // Implementation would go here - using mock data for now
-        Ok(Vec::new())
+    // The tests below need a PostGIS database:
+    // TEST_DATABASE_URL=postgres://... cargo test disaster_repository -- --ignored
+
+    fn database() -> PostgresDisasterRepository {
+        let url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must name a PostGIS database");
+        let pool = Pool::builder().max_size(2).build(ConnectionManager::<PgConnection>::new(url)).unwrap();
+        pool.get().unwrap().run_pending_migrations(MIGRATIONS).unwrap();
+        PostgresDisasterRepository::new(pool)
+    }
+
+    fn stored_user(repository: &PostgresDisasterRepository) -> UserId {
+        let id = Uuid::new_v4();
+        diesel::sql_query("INSERT INTO users (id, username, password_hash, email) VALUES ($1, $2, 'hash', $3)")
+            .bind::<SqlUuid, _>(id)
+            .bind::<Text, _>(format!("pelapor-{}", id.simple()))
+            .bind::<Text, _>(format!("{}@example.id", id.simple()))
+            .execute(&mut repository.get_connection().unwrap())
+            .unwrap();
+        UserId(id)
+    }
+
+    #[tokio::test]
+    #[ignore] // Needs TEST_DATABASE_URL
+    async fn stale_update_is_a_conflict() {
+        let repository = database();
+        let saved = DisasterRepository::save(&repository, &documented_flood(stored_user(&repository))).await.unwrap();
+
+        let mut first = DisasterRepository::find_by_id(&repository, &saved.id).await.unwrap().unwrap();
+        let mut second = first.clone();
+        first.update_severity(DisasterSeverity::Severe, saved.reporter_id).unwrap();
+        DisasterRepository::update(&repository, &first).await.unwrap();
+
+        second.update_severity(DisasterSeverity::Critical, saved.reporter_id).unwrap();
+        assert!(matches!(DisasterRepository::update(&repository, &second).await, Err(AppError::Conflict(_))));
+        let current = DisasterRepository::find_by_id(&repository, &saved.id).await.unwrap().unwrap();
+        assert_eq!(current.severity, DisasterSeverity::Severe);
     }
 
-    async fn update_status(&self, _id: &DisasterId, _status: DisasterStatus) -> AppResult<bool> {
-        // Implementation would go here - using mock data for now
-        Ok(true)
+    #[tokio::test]
+    #[ignore] // Needs TEST_DATABASE_URL
+    async fn find_nearby_keeps_to_the_radius() {
+        let repository = database();
+        let reporter = stored_user(&repository);
+        let jakarta = DisasterRepository::save(&repository, &documented_flood(reporter)).await.unwrap();
+        let mut bandung = documented_flood(reporter);
+        bandung.location = Coordinates::new(-6.9175, 107.6191).unwrap();
+        let bandung = DisasterRepository::save(&repository, &bandung).await.unwrap();
+
+        let nearby: Vec<DisasterId> = repository.find_nearby(-6.1800, 106.8300, 10.0).await.unwrap()
+            .into_iter()
+            .map(|disaster| disaster.id)
+            .collect();
+        assert!(nearby.contains(&jakarta.id));
+        assert!(!nearby.contains(&bandung.id));
+    }
+
+    #[tokio::test]
+    #[ignore] // Needs TEST_DATABASE_URL
+    async fn stored_disaster_reads_back_unchanged() {
+        let repository = database();
+        let disaster = documented_flood(stored_user(&repository));
+        DisasterRepository::save(&repository, &disaster).await.unwrap();
+
+        let loaded = DisasterRepository::find_by_id(&repository, &disaster.id).await.unwrap().unwrap();
+        let micros = |t: Option<DateTime<Utc>>| t.map(|t| t.timestamp_micros());
+        assert_eq!(micros(loaded.timeline.first_response_at), micros(disaster.timeline.first_response_at));
+        assert_eq!(micros(loaded.verification.verified_at), micros(disaster.verification.verified_at));
+        assert_eq!(loaded.verification.verified_by, disaster.verification.verified_by);
+        assert_eq!(loaded.resources_needed[0].resource_type, "perahu karet");
+        assert_eq!(loaded.reporter_id, disaster.reporter_id);
+        assert!(loaded.location.distance_to(&monas()) < 0.01);
     }
 
-    async fn assign_responder(&self, _disaster_id: &DisasterId, _responder_id: &UserId) -> AppResult<bool> {
-        // Implementation would go here - using mock data for now
-        Ok(true)
+    #[tokio::test]
+    #[ignore] // Needs TEST_DATABASE_URL
+    async fn delete_removes_only_the_origin_report() {
+        let repository = database();
+        let reporter = stored_user(&repository);
+        let disaster = DisasterRepository::save(&repository, &documented_flood(reporter)).await.unwrap();
+        let mut conn = repository.get_connection().unwrap();
+
+        // A separate report from the same spot, linked by a validator afterwards
+        let location_id: Option<Uuid> = disasters::table
+            .filter(disasters::id.eq(disaster.id.0))
+            .select(disasters::primary_location_id)
+            .first(&mut conn)
+            .unwrap();
+        let witness = Uuid::new_v4();
+        diesel::insert_into(reports::table)
+            .values((reports::id.eq(witness), reports::title.eq("Air masuk rumah"), reports::location_id.eq(location_id)))
+            .execute(&mut conn)
+            .unwrap();
+        diesel::insert_into(disaster_reports::table)
+            .values((
+                disaster_reports::disaster_id.eq(disaster.id.0),
+                disaster_reports::report_id.eq(witness),
+                disaster_reports::linked_by.eq(Some(reporter.0)),
+            ))
+            .execute(&mut conn)
+            .unwrap();
+        let origin: Uuid = disaster_reports::table
+            .filter(disaster_reports::disaster_id.eq(disaster.id.0))
+            .filter(disaster_reports::is_origin.eq(true))
+            .select(disaster_reports::report_id)
+            .first(&mut conn)
+            .unwrap();
+
+        assert!(DisasterRepository::delete(&repository, &disaster.id).await.unwrap());
+        let remaining: Vec<Uuid> = reports::table
+            .filter(reports::id.eq_any([origin, witness]))
+            .select(reports::id)
+            .load(&mut conn)
+            .unwrap();
+        assert_eq!(remaining, vec![witness]);
+        assert!(!DisasterRepository::delete(&repository, &disaster.id).await.unwrap());
     }
 }
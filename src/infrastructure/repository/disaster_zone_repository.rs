@@ -0,0 +1,115 @@
+/// Disaster zone repository implementation
+/// Reads hazard polygons from `disaster_zones` for road closure routing and alert targeting
+
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::sql_types::{Nullable, Text, Uuid as SqlUuid, Varchar};
+use uuid::Uuid;
+
+use crate::domain::entities::DisasterZone;
+use crate::domain::ports::repositories::DisasterZoneRepository;
+use crate::domain::value_objects::{Coordinates, GeoArea};
+use crate::infrastructure::database::{DbConnection, DbPool};
+use crate::shared::error::DatabaseError;
+use crate::shared::{AppError, AppResult};
+
+#[derive(QueryableByName, Debug)]
+struct ZoneAreaRow {
+    #[diesel(sql_type = SqlUuid)]
+    id: Uuid,
+    #[diesel(sql_type = Text)]
+    area: String,
+}
+
+#[derive(QueryableByName, Debug)]
+struct ZoneRow {
+    #[diesel(sql_type = SqlUuid)]
+    id: Uuid,
+    #[diesel(sql_type = Nullable<SqlUuid>)]
+    disaster_id: Option<Uuid>,
+    #[diesel(sql_type = Nullable<Varchar>)]
+    zone_type: Option<String>,
+    #[diesel(sql_type = Nullable<Text>)]
+    description: Option<String>,
+    #[diesel(sql_type = Nullable<Text>)]
+    area: Option<String>,
+}
+
+pub struct PostgresDisasterZoneRepository {
+    pool: DbPool,
+}
+
+impl PostgresDisasterZoneRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    fn get_connection(&self) -> AppResult<DbConnection> {
+        self.pool.get()
+            .map_err(|e| AppError::Database(DatabaseError::ConnectionPool(e)))
+    }
+
+    /// Exterior ring of a GeoJSON polygon
+    fn exterior_ring(area: &str) -> Option<Vec<Coordinates>> {
+        let geometry: geojson::Geometry = area.parse().ok()?;
+        let geojson::Value::Polygon(rings) = geometry.value else { return None };
+        let ring = rings.into_iter().next()?;
+        ring.iter()
+            .filter(|position| position.len() >= 2)
+            .map(|position| Coordinates::new(position[1], position[0]).ok())
+            .collect()
+    }
+}
+
+#[async_trait]
+impl DisasterZoneRepository for PostgresDisasterZoneRepository {
+    async fn find_by_id(&self, id: &Uuid) -> AppResult<Option<DisasterZone>> {
+        let mut conn = self.get_connection()?;
+        let row = diesel::sql_query(
+            "SELECT id, disaster_id, zone_type, description, ST_AsGeoJSON(area) AS area \
+             FROM disaster_zones WHERE id = $1"
+        )
+            .bind::<SqlUuid, _>(id)
+            .get_result::<ZoneRow>(&mut conn)
+            .optional()?;
+        let Some(row) = row else { return Ok(None) };
+
+        let geometry = row.area
+            .ok_or_else(|| AppError::DataConsistency(format!("Disaster zone {} has no area", row.id)))?;
+        let geometry: serde_json::Value = serde_json::from_str(&geometry)
+            .map_err(|e| AppError::DataConsistency(format!("Disaster zone {} has unreadable area: {}", row.id, e)))?;
+        let area = GeoArea::from_geojson(&geometry)
+            .map_err(|e| AppError::DataConsistency(format!("Disaster zone {} has unusable area: {}", row.id, e)))?;
+
+        Ok(Some(DisasterZone {
+            id: row.id,
+            disaster_id: row.disaster_id.map(Into::into),
+            zone_type: row.zone_type,
+            description: row.description,
+            area,
+        }))
+    }
+
+    async fn find_active_closure_areas(&self) -> AppResult<Vec<Vec<Coordinates>>> {
+        let mut conn = self.get_connection()?;
+        let rows = diesel::sql_query(
+            "SELECT dz.id, ST_AsGeoJSON(dz.area) AS area \
+             FROM disaster_zones dz \
+             JOIN disasters d ON d.id = dz.disaster_id \
+             WHERE dz.area IS NOT NULL \
+               AND COALESCE(dz.zone_type, 'affected') <> 'safe' \
+               AND COALESCE(d.status, 'reported') NOT IN ('resolved', 'closed')"
+        )
+            .load::<ZoneAreaRow>(&mut conn)?;
+
+        Ok(rows.into_iter()
+            .filter_map(|row| {
+                let ring = Self::exterior_ring(&row.area);
+                if ring.is_none() {
+                    tracing::warn!("Skipping disaster zone {} with unreadable area", row.id);
+                }
+                ring
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,319 @@
+/// Emergency response repository implementation
+/// Persists response lifecycle state and its append-only status history
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::domain::entities::emergency_response::{EmergencyResponse, EmergencyResponseStatus, StatusChange};
+use crate::domain::ports::repositories::EmergencyResponseRepository;
+use crate::domain::value_objects::Coordinates;
+use crate::infrastructure::database::schemas::{emergency_response_status_history, emergency_responses};
+use crate::infrastructure::database::{DbConnection, DbPool};
+use crate::shared::error::DatabaseError;
+use crate::shared::{AppError, AppResult, DisasterId, EmergencyResponseId, UserId};
+
+#[derive(Queryable, Selectable, Insertable, AsChangeset, Debug, Clone)]
+#[diesel(table_name = emergency_responses)]
+#[diesel(treat_none_as_null = true)]
+struct EmergencyResponseRecord {
+    id: Uuid,
+    disaster_id: Uuid,
+    response_type: String,
+    priority: i16,
+    status: String,
+    dispatched_by: Uuid,
+    assigned_teams: Vec<Uuid>,
+    personnel_count: i32,
+    equipment: Vec<String>,
+    destination_latitude: f64,
+    destination_longitude: f64,
+    last_latitude: Option<f64>,
+    last_longitude: Option<f64>,
+    estimated_arrival: Option<DateTime<Utc>>,
+    estimated_completion: Option<DateTime<Utc>>,
+    notes: Option<String>,
+    dispatched_at: DateTime<Utc>,
+    en_route_at: Option<DateTime<Utc>>,
+    on_scene_at: Option<DateTime<Utc>>,
+    completed_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    version: i64,
+}
+
+#[derive(Queryable, Debug)]
+struct StatusHistoryRecord {
+    _id: i64,
+    response_id: Uuid,
+    from_status: Option<String>,
+    to_status: String,
+    changed_by: Uuid,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    notes: Option<String>,
+    changed_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = emergency_response_status_history)]
+struct NewStatusHistoryRecord {
+    response_id: Uuid,
+    from_status: Option<String>,
+    to_status: String,
+    changed_by: Uuid,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    notes: Option<String>,
+    changed_at: DateTime<Utc>,
+}
+
+pub struct PostgresEmergencyResponseRepository {
+    pool: DbPool,
+}
+
+impl PostgresEmergencyResponseRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    fn get_connection(&self) -> AppResult<DbConnection> {
+        self.pool.get()
+            .map_err(|e| AppError::Database(DatabaseError::ConnectionPool(e)))
+    }
+
+    fn parse_status(value: &str) -> AppResult<EmergencyResponseStatus> {
+        value.parse()
+            .map_err(|_| AppError::DataConsistency(format!("Unknown emergency response status '{}'", value)))
+    }
+
+    fn coordinates(latitude: Option<f64>, longitude: Option<f64>) -> Option<Coordinates> {
+        match (latitude, longitude) {
+            (Some(lat), Some(lng)) => Coordinates::new(lat, lng).ok(),
+            _ => None,
+        }
+    }
+
+    fn to_record(entity: &EmergencyResponse) -> EmergencyResponseRecord {
+        EmergencyResponseRecord {
+            id: entity.id.0,
+            disaster_id: entity.disaster_id.0,
+            response_type: entity.response_type.clone(),
+            priority: entity.priority as i16,
+            status: entity.status.as_str().to_string(),
+            dispatched_by: entity.dispatched_by.0,
+            assigned_teams: entity.assigned_teams.clone(),
+            personnel_count: entity.personnel_count as i32,
+            equipment: entity.equipment.clone(),
+            destination_latitude: entity.destination.latitude,
+            destination_longitude: entity.destination.longitude,
+            last_latitude: entity.last_known_location.as_ref().map(|c| c.latitude),
+            last_longitude: entity.last_known_location.as_ref().map(|c| c.longitude),
+            estimated_arrival: entity.estimated_arrival,
+            estimated_completion: entity.estimated_completion,
+            notes: entity.notes.clone(),
+            dispatched_at: entity.dispatched_at,
+            en_route_at: entity.en_route_at,
+            on_scene_at: entity.on_scene_at,
+            completed_at: entity.completed_at,
+            created_at: entity.created_at,
+            updated_at: entity.updated_at,
+            version: entity.version,
+        }
+    }
+
+    fn history_record(response_id: Uuid, change: &StatusChange) -> NewStatusHistoryRecord {
+        NewStatusHistoryRecord {
+            response_id,
+            from_status: change.from_status.map(|s| s.as_str().to_string()),
+            to_status: change.to_status.as_str().to_string(),
+            changed_by: change.changed_by.0,
+            latitude: change.location.as_ref().map(|c| c.latitude),
+            longitude: change.location.as_ref().map(|c| c.longitude),
+            notes: change.notes.clone(),
+            changed_at: change.changed_at,
+        }
+    }
+
+    fn to_domain(record: EmergencyResponseRecord, history: Vec<StatusHistoryRecord>) -> AppResult<EmergencyResponse> {
+        let destination = Coordinates::new(record.destination_latitude, record.destination_longitude)
+            .map_err(|e| AppError::DataConsistency(format!(
+                "Emergency response {} has invalid destination: {}", record.id, e
+            )))?;
+
+        let status_history = history.into_iter()
+            .map(|h| Ok(StatusChange {
+                from_status: h.from_status.as_deref().map(Self::parse_status).transpose()?,
+                to_status: Self::parse_status(&h.to_status)?,
+                changed_by: UserId(h.changed_by),
+                location: Self::coordinates(h.latitude, h.longitude),
+                notes: h.notes,
+                changed_at: h.changed_at,
+            }))
+            .collect::<AppResult<Vec<_>>>()?;
+
+        Ok(EmergencyResponse {
+            id: EmergencyResponseId(record.id),
+            disaster_id: DisasterId(record.disaster_id),
+            response_type: record.response_type,
+            priority: record.priority.clamp(1, 5) as u8,
+            status: Self::parse_status(&record.status)?,
+            dispatched_by: UserId(record.dispatched_by),
+            assigned_teams: record.assigned_teams,
+            personnel_count: record.personnel_count.max(0) as u32,
+            equipment: record.equipment,
+            destination,
+            last_known_location: Self::coordinates(record.last_latitude, record.last_longitude),
+            estimated_arrival: record.estimated_arrival,
+            estimated_completion: record.estimated_completion,
+            notes: record.notes,
+            status_history,
+            dispatched_at: record.dispatched_at,
+            en_route_at: record.en_route_at,
+            on_scene_at: record.on_scene_at,
+            completed_at: record.completed_at,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+            version: record.version,
+            persisted_version: record.version,
+        })
+    }
+
+    /// Attach status history to loaded records with a single query
+    fn load_with_history(conn: &mut PgConnection, records: Vec<EmergencyResponseRecord>) -> AppResult<Vec<EmergencyResponse>> {
+        let ids: Vec<Uuid> = records.iter().map(|r| r.id).collect();
+        let mut history: HashMap<Uuid, Vec<StatusHistoryRecord>> = HashMap::new();
+        if !ids.is_empty() {
+            let rows: Vec<StatusHistoryRecord> = emergency_response_status_history::table
+                .filter(emergency_response_status_history::response_id.eq_any(&ids))
+                .order((emergency_response_status_history::changed_at.asc(), emergency_response_status_history::id.asc()))
+                .load(conn)?;
+            for row in rows {
+                history.entry(row.response_id).or_default().push(row);
+            }
+        }
+
+        records.into_iter()
+            .map(|record| {
+                let entries = history.remove(&record.id).unwrap_or_default();
+                Self::to_domain(record, entries)
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl EmergencyResponseRepository for PostgresEmergencyResponseRepository {
+    async fn find_by_id(&self, id: &EmergencyResponseId) -> AppResult<Option<EmergencyResponse>> {
+        let mut conn = self.get_connection()?;
+        let record: Option<EmergencyResponseRecord> = emergency_responses::table
+            .filter(emergency_responses::id.eq(id.0))
+            .select(EmergencyResponseRecord::as_select())
+            .first(&mut conn)
+            .optional()?;
+
+        match record {
+            Some(record) => Ok(Self::load_with_history(&mut conn, vec![record])?.pop()),
+            None => Ok(None),
+        }
+    }
+
+    async fn save(&self, entity: &EmergencyResponse) -> AppResult<EmergencyResponse> {
+        let mut conn = self.get_connection()?;
+        let record = Self::to_record(entity);
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            match diesel::insert_into(emergency_responses::table).values(&record).execute(conn) {
+                Err(diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::UniqueViolation, _)) => {
+                    return Err(AppError::Conflict(format!("Emergency response {} already exists", entity.id)));
+                }
+                Err(diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::ForeignKeyViolation, info)) => {
+                    return Err(AppError::Validation(format!("Emergency response references unknown data: {}", info.message())));
+                }
+                other => { other?; }
+            }
+
+            let history: Vec<NewStatusHistoryRecord> = entity.status_history.iter()
+                .map(|change| Self::history_record(entity.id.0, change))
+                .collect();
+            diesel::insert_into(emergency_response_status_history::table)
+                .values(&history)
+                .execute(conn)?;
+            Ok(())
+        })?;
+
+        let mut saved = entity.clone();
+        saved.persisted_version = saved.version;
+        Ok(saved)
+    }
+
+    async fn update(&self, entity: &EmergencyResponse) -> AppResult<EmergencyResponse> {
+        let mut conn = self.get_connection()?;
+        let record = Self::to_record(entity);
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            // Optimistic concurrency: only apply on top of the version we loaded
+            let updated = diesel::update(
+                emergency_responses::table
+                    .filter(emergency_responses::id.eq(entity.id.0))
+                    .filter(emergency_responses::version.eq(entity.persisted_version)),
+            )
+                .set(&record)
+                .execute(conn)?;
+
+            if updated == 0 {
+                let exists = emergency_responses::table
+                    .filter(emergency_responses::id.eq(entity.id.0))
+                    .count()
+                    .get_result::<i64>(conn)? > 0;
+                return Err(if exists {
+                    AppError::Conflict(format!("Emergency response {} was modified concurrently", entity.id))
+                } else {
+                    AppError::NotFound(format!("Emergency response {} not found", entity.id))
+                });
+            }
+
+            // History is append-only; store the entries recorded since loading
+            let stored: i64 = emergency_response_status_history::table
+                .filter(emergency_response_status_history::response_id.eq(entity.id.0))
+                .count()
+                .get_result(conn)?;
+            let new_entries: Vec<NewStatusHistoryRecord> = entity.status_history.iter()
+                .skip(stored as usize)
+                .map(|change| Self::history_record(entity.id.0, change))
+                .collect();
+            if !new_entries.is_empty() {
+                diesel::insert_into(emergency_response_status_history::table)
+                    .values(&new_entries)
+                    .execute(conn)?;
+            }
+            Ok(())
+        })?;
+
+        let mut saved = entity.clone();
+        saved.persisted_version = saved.version;
+        Ok(saved)
+    }
+
+    async fn find_by_disaster(&self, disaster_id: &DisasterId) -> AppResult<Vec<EmergencyResponse>> {
+        let mut conn = self.get_connection()?;
+        let records = emergency_responses::table
+            .filter(emergency_responses::disaster_id.eq(disaster_id.0))
+            .order(emergency_responses::dispatched_at.desc())
+            .select(EmergencyResponseRecord::as_select())
+            .load(&mut conn)?;
+        Self::load_with_history(&mut conn, records)
+    }
+
+    async fn find_active(&self) -> AppResult<Vec<EmergencyResponse>> {
+        let mut conn = self.get_connection()?;
+        let records = emergency_responses::table
+            .filter(emergency_responses::status.ne(EmergencyResponseStatus::Completed.as_str()))
+            .order((emergency_responses::priority.desc(), emergency_responses::dispatched_at.asc()))
+            .select(EmergencyResponseRecord::as_select())
+            .load(&mut conn)?;
+        Self::load_with_history(&mut conn, records)
+    }
+}
@@ -0,0 +1,144 @@
+/// Emergency station repository implementation
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::domain::entities::response_team::{EmergencyStation, TeamType};
+use crate::domain::ports::repositories::EmergencyStationRepository;
+use crate::domain::value_objects::Coordinates;
+use crate::infrastructure::database::schemas::emergency_stations;
+use crate::infrastructure::database::{DbConnection, DbPool};
+use crate::shared::error::DatabaseError;
+use crate::shared::{AppError, AppResult};
+
+#[derive(Queryable, Selectable, Insertable, AsChangeset, Debug, Clone)]
+#[diesel(table_name = emergency_stations)]
+#[diesel(treat_none_as_null = true)]
+struct EmergencyStationRecord {
+    id: Uuid,
+    name: String,
+    station_type: String,
+    latitude: f64,
+    longitude: f64,
+    address: Option<String>,
+    contact_phone: Option<String>,
+    staff_count: i32,
+    vehicles: Vec<String>,
+    is_operational: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+pub struct PostgresEmergencyStationRepository {
+    pool: DbPool,
+}
+
+impl PostgresEmergencyStationRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    fn get_connection(&self) -> AppResult<DbConnection> {
+        self.pool.get()
+            .map_err(|e| AppError::Database(DatabaseError::ConnectionPool(e)))
+    }
+
+    fn to_record(entity: &EmergencyStation) -> EmergencyStationRecord {
+        EmergencyStationRecord {
+            id: entity.id,
+            name: entity.name.clone(),
+            station_type: entity.station_type.as_str().to_string(),
+            latitude: entity.location.latitude,
+            longitude: entity.location.longitude,
+            address: entity.address.clone(),
+            contact_phone: entity.contact_phone.clone(),
+            staff_count: entity.staff_count as i32,
+            vehicles: entity.vehicles.clone(),
+            is_operational: entity.is_operational,
+            created_at: entity.created_at,
+            updated_at: entity.updated_at,
+        }
+    }
+
+    fn to_domain(record: EmergencyStationRecord) -> AppResult<EmergencyStation> {
+        let location = Coordinates::new(record.latitude, record.longitude)
+            .map_err(|e| AppError::DataConsistency(format!(
+                "Emergency station {} has invalid location: {}", record.id, e
+            )))?;
+        let station_type: TeamType = record.station_type.parse()
+            .map_err(|_| AppError::DataConsistency(format!("Unknown station type '{}'", record.station_type)))?;
+
+        Ok(EmergencyStation {
+            id: record.id,
+            name: record.name,
+            station_type,
+            location,
+            address: record.address,
+            contact_phone: record.contact_phone,
+            staff_count: record.staff_count.max(0) as u32,
+            vehicles: record.vehicles,
+            is_operational: record.is_operational,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+        })
+    }
+}
+
+#[async_trait]
+impl EmergencyStationRepository for PostgresEmergencyStationRepository {
+    async fn find_by_id(&self, id: &Uuid) -> AppResult<Option<EmergencyStation>> {
+        let mut conn = self.get_connection()?;
+        let record: Option<EmergencyStationRecord> = emergency_stations::table
+            .filter(emergency_stations::id.eq(id))
+            .select(EmergencyStationRecord::as_select())
+            .first(&mut conn)
+            .optional()?;
+        record.map(Self::to_domain).transpose()
+    }
+
+    async fn save(&self, entity: &EmergencyStation) -> AppResult<EmergencyStation> {
+        let mut conn = self.get_connection()?;
+        match diesel::insert_into(emergency_stations::table)
+            .values(&Self::to_record(entity))
+            .execute(&mut conn)
+        {
+            Err(diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::UniqueViolation, _)) => {
+                Err(AppError::Conflict(format!("Emergency station {} already exists", entity.id)))
+            }
+            other => {
+                other?;
+                Ok(entity.clone())
+            }
+        }
+    }
+
+    async fn update(&self, entity: &EmergencyStation) -> AppResult<EmergencyStation> {
+        let mut conn = self.get_connection()?;
+        let updated = diesel::update(emergency_stations::table.filter(emergency_stations::id.eq(entity.id)))
+            .set(&Self::to_record(entity))
+            .execute(&mut conn)?;
+
+        if updated == 0 {
+            return Err(AppError::NotFound(format!("Emergency station {} not found", entity.id)));
+        }
+        Ok(entity.clone())
+    }
+
+    async fn delete(&self, id: &Uuid) -> AppResult<bool> {
+        let mut conn = self.get_connection()?;
+        let deleted = diesel::delete(emergency_stations::table.filter(emergency_stations::id.eq(id)))
+            .execute(&mut conn)?;
+        Ok(deleted > 0)
+    }
+
+    async fn find_all(&self) -> AppResult<Vec<EmergencyStation>> {
+        let mut conn = self.get_connection()?;
+        let records = emergency_stations::table
+            .order(emergency_stations::name.asc())
+            .select(EmergencyStationRecord::as_select())
+            .load(&mut conn)?;
+        records.into_iter().map(Self::to_domain).collect()
+    }
+}
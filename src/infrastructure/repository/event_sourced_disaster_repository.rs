@@ -0,0 +1,345 @@
+/// Event-sourced disaster repository
+/// Appends the aggregate's change events to the event store and keeps the
+/// `disasters` table as the query-side projection of the latest state, committing both
+/// together; committed events are then announced on the event bus, when one is attached
+
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::Arc;
+
+use crate::domain::entities::disaster::{Disaster, DisasterStatus};
+use crate::domain::ports::repositories::DisasterRepository;
+use crate::shared::events::{AggregateSnapshot, DisasterEvent, EventBus, EventStore, SnapshotStore, StoredEvent};
+use crate::shared::types::{DisasterId, LocationId, UserId};
+use crate::shared::{AppError, AppResult};
+
+const AGGREGATE_TYPE: &str = "disaster";
+
+/// Take a snapshot every this many versions by default
+pub const DEFAULT_SNAPSHOT_INTERVAL: u32 = 50;
+
+/// Query-side disaster store able to commit a row write together with the events behind it
+#[async_trait]
+pub trait DisasterProjection: DisasterRepository {
+    /// Insert (`is_new`) or update the row for `entity` and append `events` to its stream,
+    /// the first expected at version `base`, in one transaction
+    async fn commit(&self, entity: &Disaster, is_new: bool, base: u32, events: &[DisasterEvent]) -> AppResult<Disaster>;
+}
+
+pub struct EventSourcedDisasterRepository {
+    state: Arc<dyn DisasterProjection>,
+    events: Arc<dyn EventStore>,
+    snapshots: Arc<dyn SnapshotStore>,
+    snapshot_interval: u32,
+    bus: Option<Arc<EventBus>>,
+}
+
+impl EventSourcedDisasterRepository {
+    pub fn new(
+        state: Arc<dyn DisasterProjection>,
+        events: Arc<dyn EventStore>,
+        snapshots: Arc<dyn SnapshotStore>,
+    ) -> Self {
+        Self { state, events, snapshots, snapshot_interval: DEFAULT_SNAPSHOT_INTERVAL, bus: None }
+    }
+
+    pub fn with_event_bus(mut self, bus: Arc<EventBus>) -> Self {
+        self.bus = Some(bus);
+        self
+    }
+
+    pub fn with_snapshot_interval(mut self, interval: u32) -> Self {
+        self.snapshot_interval = interval.max(1);
+        self
+    }
+
+    /// Rebuild a disaster from its latest snapshot and the events recorded after it
+    pub async fn load_from_history(&self, id: &DisasterId) -> AppResult<Option<Disaster>> {
+        let key = id.to_string();
+        let snapshot = self.snapshots.load_snapshot(&key).await?;
+        let from_version = snapshot.as_ref().map(|s| s.version);
+        let stored = self.events.get_events_for_aggregate(&key, from_version).await?;
+
+        let mut disaster = match snapshot {
+            Some(snapshot) => {
+                let mut disaster: Disaster = serde_json::from_value(snapshot.state)
+                    .map_err(|e| AppError::Serialization(format!("Invalid snapshot for disaster {}: {}", id, e)))?;
+                disaster.version = snapshot.version as i64;
+                disaster
+            }
+            None => {
+                let Some(first) = stored.first() else { return Ok(None) };
+                Disaster::from_events(&[first.decode::<DisasterEvent>()?])?
+            }
+        };
+
+        // Without a snapshot the opening event has already been applied
+        let skip = if from_version.is_some() { 0 } else { 1 };
+        for event in stored.iter().skip(skip) {
+            // Other event types may share the aggregate id (e.g. bus-published integration events)
+            let Ok(event) = event.decode::<DisasterEvent>() else { continue };
+            disaster.apply(&event);
+        }
+        if let Some(last) = stored.last() {
+            disaster.version = disaster.version.max(last.metadata.event_version as i64);
+        }
+
+        disaster.persisted_version = disaster.version;
+        Ok(Some(disaster))
+    }
+
+    /// Commit the row and the aggregate's pending events, returning them as they should be announced;
+    /// a snapshot is written when the new version crosses an interval boundary
+    async fn commit_pending(&self, entity: &Disaster, is_new: bool, pending: Vec<DisasterEvent>) -> AppResult<(Disaster, Vec<StoredEvent>)> {
+        let key = entity.id.to_string();
+        let base = (entity.version - pending.len() as i64).max(0) as u32;
+
+        // Disasters persisted before event sourcing have no stream; anchor one on the stored state
+        if !pending.is_empty() && base > 0 && self.snapshots.load_snapshot(&key).await?.is_none() {
+            let existing = self.events.get_events_for_aggregate(&key, Some(base - 1)).await?;
+            if existing.is_empty() {
+                if let Some(current) = self.state.find_by_id(&entity.id).await? {
+                    self.write_snapshot(&current, base).await?;
+                }
+            }
+        }
+
+        let appended = if self.bus.is_some() {
+            pending.iter().map(|event| StoredEvent::capture(event)).collect::<AppResult<Vec<_>>>()?
+        } else {
+            Vec::new()
+        };
+        let saved = self.state.commit(entity, is_new, base, &pending).await?;
+
+        let version = entity.version as u32;
+        if !pending.is_empty() && version / self.snapshot_interval > base / self.snapshot_interval {
+            self.write_snapshot(entity, version).await?;
+        }
+        Ok((saved, appended))
+    }
+
+    /// Announce events once the query-side row reflects them
+    fn announce(&self, appended: Vec<StoredEvent>) {
+        if let Some(bus) = &self.bus {
+            for event in appended {
+                bus.announce(event);
+            }
+        }
+    }
+
+    async fn write_snapshot(&self, disaster: &Disaster, version: u32) -> AppResult<()> {
+        let state = serde_json::to_value(disaster)
+            .map_err(|e| AppError::Serialization(e.to_string()))?;
+        self.snapshots.save_snapshot(AggregateSnapshot {
+            aggregate_id: disaster.id.to_string(),
+            aggregate_type: AGGREGATE_TYPE.to_string(),
+            version,
+            state,
+            taken_at: Utc::now(),
+        }).await
+    }
+
+    /// Load, mutate and persist through the aggregate so the change is recorded
+    async fn mutate<F>(&self, id: &DisasterId, change: F) -> AppResult<bool>
+    where
+        F: FnOnce(&mut Disaster) -> AppResult<()> + Send,
+    {
+        let Some(mut disaster) = self.state.find_by_id(id).await? else {
+            return Ok(false);
+        };
+        change(&mut disaster)?;
+        self.update(&disaster).await?;
+        Ok(true)
+    }
+
+}
+
+#[async_trait]
+impl DisasterRepository for EventSourcedDisasterRepository {
+    async fn find_by_id(&self, id: &DisasterId) -> AppResult<Option<Disaster>> {
+        self.state.find_by_id(id).await
+    }
+
+    async fn save(&self, entity: &Disaster) -> AppResult<Disaster> {
+        let mut entity = entity.clone();
+        let pending = entity.take_uncommitted_events();
+        let (saved, appended) = self.commit_pending(&entity, true, pending).await?;
+        self.announce(appended);
+        Ok(saved)
+    }
+
+    async fn update(&self, entity: &Disaster) -> AppResult<Disaster> {
+        let mut entity = entity.clone();
+        let pending = entity.take_uncommitted_events();
+        let (updated, appended) = self.commit_pending(&entity, false, pending).await?;
+        self.announce(appended);
+        Ok(updated)
+    }
+
+    async fn delete(&self, id: &DisasterId) -> AppResult<bool> {
+        self.state.delete(id).await
+    }
+
+    async fn find_all(&self) -> AppResult<Vec<Disaster>> {
+        self.state.find_all().await
+    }
+
+    async fn find_by_status(&self, status: DisasterStatus) -> AppResult<Vec<Disaster>> {
+        self.state.find_by_status(status).await
+    }
+
+    async fn find_by_severity(&self, severity: crate::domain::entities::disaster::DisasterSeverity) -> AppResult<Vec<Disaster>> {
+        self.state.find_by_severity(severity).await
+    }
+
+    async fn find_by_reporter(&self, reporter_id: &UserId) -> AppResult<Vec<Disaster>> {
+        self.state.find_by_reporter(reporter_id).await
+    }
+
+    async fn find_nearby(&self, lat: f64, lng: f64, radius_km: f64) -> AppResult<Vec<Disaster>> {
+        self.state.find_nearby(lat, lng, radius_km).await
+    }
+
+    async fn find_active(&self) -> AppResult<Vec<Disaster>> {
+        self.state.find_active().await
+    }
+
+    async fn update_status(&self, id: &DisasterId, status: DisasterStatus, updated_by: &UserId) -> AppResult<bool> {
+        let updated_by = *updated_by;
+        self.mutate(id, |disaster| disaster.update_status(status, updated_by)).await
+    }
+
+    async fn assign_responder(&self, disaster_id: &DisasterId, responder_id: &UserId) -> AppResult<bool> {
+        let responder_id = *responder_id;
+        let Some(mut disaster) = self.state.find_by_id(disaster_id).await? else {
+            return Ok(false);
+        };
+        if disaster.assigned_responders.contains(&responder_id) {
+            return Ok(false);
+        }
+        disaster.assign_responder(responder_id)?;
+        self.update(&disaster).await?;
+        Ok(true)
+    }
+
+    async fn find_by_location(&self, location_id: &LocationId) -> AppResult<Vec<Disaster>> {
+        self.state.find_by_location(location_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::disaster::{DisasterSeverity, DisasterType, EstimatedDamage};
+    use crate::domain::value_objects::Coordinates;
+    use crate::infrastructure::event_store::InMemoryEventStore;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    struct InMemoryDisasters {
+        rows: Mutex<HashMap<DisasterId, Disaster>>,
+        events: Arc<InMemoryEventStore>,
+    }
+
+    #[async_trait]
+    impl DisasterProjection for InMemoryDisasters {
+        async fn commit(&self, entity: &Disaster, _is_new: bool, base: u32, events: &[DisasterEvent]) -> AppResult<Disaster> {
+            let key = entity.id.to_string();
+            for (index, event) in events.iter().enumerate() {
+                self.events.append_event(&key, Some(base + index as u32), Box::new(event.clone()), None).await?;
+            }
+            self.save(entity).await
+        }
+    }
+
+    #[async_trait]
+    impl DisasterRepository for InMemoryDisasters {
+        async fn find_by_id(&self, id: &DisasterId) -> AppResult<Option<Disaster>> {
+            Ok(self.rows.lock().unwrap().get(id).cloned())
+        }
+        async fn save(&self, entity: &Disaster) -> AppResult<Disaster> {
+            let mut saved = entity.clone();
+            saved.persisted_version = saved.version;
+            self.rows.lock().unwrap().insert(entity.id, saved.clone());
+            Ok(saved)
+        }
+        async fn update(&self, entity: &Disaster) -> AppResult<Disaster> {
+            self.save(entity).await
+        }
+        async fn delete(&self, id: &DisasterId) -> AppResult<bool> {
+            Ok(self.rows.lock().unwrap().remove(id).is_some())
+        }
+        async fn find_all(&self) -> AppResult<Vec<Disaster>> {
+            Ok(self.rows.lock().unwrap().values().cloned().collect())
+        }
+        async fn find_by_status(&self, _status: DisasterStatus) -> AppResult<Vec<Disaster>> { Ok(Vec::new()) }
+        async fn find_by_severity(&self, _severity: DisasterSeverity) -> AppResult<Vec<Disaster>> { Ok(Vec::new()) }
+        async fn find_by_reporter(&self, _reporter_id: &UserId) -> AppResult<Vec<Disaster>> { Ok(Vec::new()) }
+        async fn find_nearby(&self, _lat: f64, _lng: f64, _radius_km: f64) -> AppResult<Vec<Disaster>> { Ok(Vec::new()) }
+        async fn find_active(&self) -> AppResult<Vec<Disaster>> { Ok(Vec::new()) }
+        async fn update_status(&self, _id: &DisasterId, _status: DisasterStatus, _updated_by: &UserId) -> AppResult<bool> { Ok(false) }
+        async fn assign_responder(&self, _id: &DisasterId, _responder_id: &UserId) -> AppResult<bool> { Ok(false) }
+        async fn find_by_location(&self, _location_id: &LocationId) -> AppResult<Vec<Disaster>> { Ok(Vec::new()) }
+    }
+
+    fn repository(interval: u32) -> EventSourcedDisasterRepository {
+        let store = Arc::new(InMemoryEventStore::new());
+        let rows = InMemoryDisasters { rows: Mutex::new(HashMap::new()), events: store.clone() };
+        EventSourcedDisasterRepository::new(Arc::new(rows), store.clone(), store)
+            .with_snapshot_interval(interval)
+    }
+
+    fn flood() -> Disaster {
+        Disaster::new(
+            "Jakarta flood".to_string(),
+            "River overflow in the north".to_string(),
+            DisasterType::Flood,
+            DisasterSeverity::Moderate,
+            Coordinates::new(-6.2, 106.8).unwrap(),
+            UserId::new(),
+        ).unwrap()
+    }
+
+    #[tokio::test]
+    async fn replayed_history_matches_live_aggregate() {
+        let repo = repository(3);
+        let disaster = repo.save(&flood()).await.unwrap();
+
+        let mut disaster = repo.find_by_id(&disaster.id).await.unwrap().unwrap();
+        disaster.update_status(DisasterStatus::Verified, UserId::new()).unwrap();
+        disaster.update_severity(DisasterSeverity::Severe, UserId::new()).unwrap();
+        disaster.assign_responder(UserId::new()).unwrap();
+        disaster.update_estimated_damage(EstimatedDamage {
+            economic_loss: Some(1_000_000.0),
+            casualties: 0,
+            injuries: 4,
+            displaced_people: 120,
+            damaged_buildings: 30,
+            description: None,
+        }).unwrap();
+        repo.update(&disaster).await.unwrap();
+        assert!(repo.update_status(&disaster.id, DisasterStatus::Responded, &UserId::new()).await.unwrap());
+
+        let live = repo.find_by_id(&disaster.id).await.unwrap().unwrap();
+        let replayed = repo.load_from_history(&disaster.id).await.unwrap().unwrap();
+
+        assert_eq!(live.version, 6);
+        assert_eq!(replayed.version, live.version);
+        assert_eq!(replayed.status, live.status);
+        assert_eq!(replayed.severity, live.severity);
+        assert_eq!(replayed.assigned_responders, live.assigned_responders);
+        assert_eq!(replayed.timeline.first_response_at, live.timeline.first_response_at);
+        assert_eq!(replayed.estimated_damage.as_ref().map(|d| d.displaced_people), Some(120));
+        assert!(replayed.uncommitted_events().is_empty());
+    }
+
+    #[test]
+    fn history_must_start_with_opening_event() {
+        let event = DisasterEvent::DisasterResponderAssigned {
+            disaster_id: DisasterId::new(),
+            responder_id: UserId::new(),
+            assigned_at: Utc::now(),
+        };
+        assert!(Disaster::from_events(&[event]).is_err());
+    }
+}
@@ -1,14 +1,105 @@
 /// Location repository implementation
-/// Provides data access for location-related entities
+/// Provides data access for location-related entities backed by PostGIS
 
 use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
 use diesel::prelude::*;
+use diesel::pg::Pg;
+use diesel::query_builder::{BoxedSqlQuery, SqlQuery};
+use diesel::sql_types::{Bool, Float8, Int8, Jsonb, Nullable, Text, Timestamp, Uuid as SqlUuid};
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
-use crate::shared::{AppResult, error::AppError};
+use crate::shared::{AppResult, error::AppError, AuditFields};
 use crate::infrastructure::database::{DbPool, DbConnection};
+use crate::infrastructure::database::schemas::locations;
 use crate::domain::ports::{Repository, repositories::LocationRepository};
-use crate::domain::entities::location::Location;
-use crate::LocationId;
+use crate::domain::entities::location::{
+    AdministrativeLevel, EmergencyContact, Location, LocationSearch, LocationType, RiskFactor,
+};
+use crate::domain::value_objects::{Address, Coordinates};
+use crate::{LocationId, UserId};
+use crate::shared::error::DatabaseError;
+
+/// Upper bound on rows returned by list and search queries
+const MAX_RESULTS: i64 = 500;
+
+/// Minimum trigram similarity for fuzzy name matches
+const NAME_SIMILARITY_THRESHOLD: f64 = 0.3;
+
+const SELECT_LOCATIONS: &str = r#"
+SELECT l.id, l.name, l.region, l.province, l.city, l.postal_code, l.address,
+       ST_Y(l.geometry::geometry) AS latitude, ST_X(l.geometry::geometry) AS longitude,
+       l.details, l.created_at, l.updated_at
+FROM locations l
+"#;
+
+#[derive(QueryableByName, Debug)]
+struct LocationRow {
+    #[diesel(sql_type = SqlUuid)]
+    id: Uuid,
+    #[diesel(sql_type = Text)]
+    name: String,
+    #[diesel(sql_type = Text)]
+    region: String,
+    #[diesel(sql_type = Nullable<Text>)]
+    province: Option<String>,
+    #[diesel(sql_type = Nullable<Text>)]
+    city: Option<String>,
+    #[diesel(sql_type = Nullable<Text>)]
+    postal_code: Option<String>,
+    #[diesel(sql_type = Nullable<Text>)]
+    address: Option<String>,
+    #[diesel(sql_type = Nullable<Float8>)]
+    latitude: Option<f64>,
+    #[diesel(sql_type = Nullable<Float8>)]
+    longitude: Option<f64>,
+    #[diesel(sql_type = Jsonb)]
+    details: serde_json::Value,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    created_at: Option<NaiveDateTime>,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    updated_at: Option<NaiveDateTime>,
+}
+
+/// Search criteria, bound as $1..$10 by `PostgresLocationRepository::searched`
+const SEARCH_FILTER: &str = "($1 IS NULL OR l.name ILIKE '%' || $1 || '%' OR l.address ILIKE '%' || $1 || '%' \
+        OR similarity(l.name, $2) >= $3) \
+    AND (jsonb_array_length($4) = 0 OR $4 @> jsonb_build_array(l.details -> 'location_type')) \
+    AND ($5 IS NULL OR l.province ILIKE '%' || $5 || '%') \
+    AND ($6 IS NULL OR l.city ILIKE '%' || $6 || '%') \
+    AND ($9 IS NULL OR ST_DWithin(l.geometry, ST_SetSRID(ST_MakePoint($8, $7), 4326)::geography, $9)) \
+    AND (NOT $10 OR COALESCE((SELECT max((rf ->> 'severity_level')::int) \
+        FROM jsonb_array_elements(COALESCE(l.details -> 'risk_factors', '[]'::jsonb)) rf), 0) <= 2)";
+
+#[derive(QueryableByName, Debug)]
+struct CountRow {
+    #[diesel(sql_type = Int8)]
+    count: i64,
+}
+
+#[derive(QueryableByName, Debug)]
+struct NameRow {
+    #[diesel(sql_type = Text)]
+    name: String,
+}
+
+/// Domain attributes without a dedicated column, stored in `locations.details`
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct LocationDetails {
+    location_type: Option<LocationType>,
+    administrative_level: Option<AdministrativeLevel>,
+    country: Option<String>,
+    population: Option<u32>,
+    area_km2: Option<f64>,
+    elevation_meters: Option<f64>,
+    is_disaster_prone: bool,
+    risk_factors: Vec<RiskFactor>,
+    emergency_contacts: Vec<EmergencyContact>,
+    created_by: Option<UserId>,
+    updated_by: Option<UserId>,
+    version: u64,
+}
 
 pub struct PostgresLocationRepository {
     pool: DbPool,
@@ -18,95 +109,451 @@ impl PostgresLocationRepository {
     pub fn new(pool: DbPool) -> Self {
         Self { pool }
     }
+
+    fn get_connection(&self) -> AppResult<DbConnection> {
+        self.pool.get()
+            .map_err(|e| AppError::Database(
+                DatabaseError::ConnectionPool(e)
+            ))
+    }
+
+    fn to_details(entity: &Location) -> AppResult<serde_json::Value> {
+        let details = LocationDetails {
+            location_type: Some(entity.location_type.clone()),
+            administrative_level: Some(entity.administrative_level.clone()),
+            country: entity.address.country.clone(),
+            population: entity.population,
+            area_km2: entity.area_km2,
+            elevation_meters: entity.elevation_meters,
+            is_disaster_prone: entity.is_disaster_prone,
+            risk_factors: entity.risk_factors.clone(),
+            emergency_contacts: entity.emergency_contacts.clone(),
+            created_by: entity.audit.created_by,
+            updated_by: entity.audit.updated_by,
+            version: entity.audit.version,
+        };
+        serde_json::to_value(details).map_err(|e| AppError::Serialization(e.to_string()))
+    }
+
+    fn to_domain(row: LocationRow) -> AppResult<Location> {
+        let details: LocationDetails = serde_json::from_value(row.details)
+            .map_err(|e| AppError::Serialization(format!("Invalid details for location {}: {}", row.id, e)))?;
+
+        let mut coordinates = Coordinates::new(row.latitude.unwrap_or(0.0), row.longitude.unwrap_or(0.0))
+            .map_err(|e| AppError::DataConsistency(format!("Location {} has invalid geometry: {}", row.id, e)))?;
+        if let Some(elevation) = details.elevation_meters {
+            coordinates = coordinates.with_altitude(elevation);
+        }
+
+        let now = Utc::now();
+        let created_at = row.created_at.map(|t| t.and_utc()).unwrap_or(now);
+
+        Ok(Location {
+            id: LocationId(row.id),
+            address: Address {
+                street: row.address.unwrap_or_else(|| row.name.clone()),
+                city: row.city.unwrap_or_else(|| row.region.clone()),
+                province: row.province.unwrap_or_default(),
+                country: details.country,
+                postal_code: row.postal_code,
+            },
+            name: row.name,
+            coordinates,
+            region: Some(row.region),
+            location_type: details.location_type
+                .unwrap_or_else(|| LocationType::Other("unclassified".to_string())),
+            administrative_level: details.administrative_level.unwrap_or(AdministrativeLevel::Village),
+            population: details.population,
+            area_km2: details.area_km2,
+            elevation_meters: details.elevation_meters,
+            is_disaster_prone: details.is_disaster_prone,
+            risk_factors: details.risk_factors,
+            emergency_contacts: details.emergency_contacts,
+            audit: AuditFields {
+                created_at,
+                updated_at: row.updated_at.map(|t| t.and_utc()).unwrap_or(created_at),
+                created_by: details.created_by,
+                updated_by: details.updated_by,
+                version: details.version,
+            },
+        })
+    }
+
+    fn rows_to_domain(rows: Vec<LocationRow>) -> Vec<Location> {
+        rows.into_iter()
+            .filter_map(|row| {
+                let id = row.id;
+                Self::to_domain(row)
+                    .map_err(|e| tracing::warn!("Skipping unreadable location {}: {}", id, e))
+                    .ok()
+            })
+            .collect()
+    }
+
+    /// Escape LIKE wildcards so user input is matched literally
+    fn escape_like(input: &str) -> String {
+        input.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+    }
+
+    /// Bind the `SEARCH_FILTER` parameters; the caller binds anything from $11 on
+    fn searched(sql: String, criteria: &LocationSearch) -> AppResult<BoxedSqlQuery<'static, Pg, SqlQuery>> {
+        let text = criteria.text.as_deref().map(str::trim).filter(|t| !t.is_empty());
+        let types = serde_json::to_value(&criteria.types)
+            .map_err(|e| AppError::Serialization(e.to_string()))?;
+        let area = |value: &Option<String>| {
+            value.as_deref().map(str::trim).filter(|v| !v.is_empty()).map(Self::escape_like)
+        };
+
+        Ok(diesel::sql_query(sql)
+            .into_boxed()
+            .bind::<Nullable<Text>, _>(text.map(Self::escape_like))
+            .bind::<Nullable<Text>, _>(text.map(str::to_string))
+            .bind::<Float8, _>(NAME_SIMILARITY_THRESHOLD)
+            .bind::<Jsonb, _>(types)
+            .bind::<Nullable<Text>, _>(area(&criteria.province))
+            .bind::<Nullable<Text>, _>(area(&criteria.city))
+            .bind::<Nullable<Float8>, _>(criteria.near.as_ref().map(|(center, _)| center.latitude))
+            .bind::<Nullable<Float8>, _>(criteria.near.as_ref().map(|(center, _)| center.longitude))
+            .bind::<Nullable<Float8>, _>(criteria.near.as_ref().map(|(_, radius_km)| radius_km * 1000.0))
+            .bind::<Bool, _>(criteria.evacuation_only))
+    }
+
+    fn load_within_radius(&self, lat: f64, lng: f64, radius_km: f64) -> AppResult<Vec<Location>> {
+        if radius_km <= 0.0 {
+            return Err(AppError::Validation("Radius must be greater than zero".to_string()));
+        }
+
+        let mut conn = self.get_connection()?;
+        let rows = diesel::sql_query(format!(
+            "{} WHERE ST_DWithin(l.geometry, ST_SetSRID(ST_MakePoint($1, $2), 4326)::geography, $3) \
+             ORDER BY ST_Distance(l.geometry, ST_SetSRID(ST_MakePoint($1, $2), 4326)::geography) ASC \
+             LIMIT $4",
+            SELECT_LOCATIONS
+        ))
+            .bind::<Float8, _>(lng)
+            .bind::<Float8, _>(lat)
+            .bind::<Float8, _>(radius_km * 1000.0)
+            .bind::<Int8, _>(MAX_RESULTS)
+            .load::<LocationRow>(&mut conn)?;
+        Ok(Self::rows_to_domain(rows))
+    }
+
+    fn load_one(&self, id: Uuid) -> AppResult<Option<Location>> {
+        let mut conn = self.get_connection()?;
+        let row = diesel::sql_query(format!("{} WHERE l.id = $1", SELECT_LOCATIONS))
+            .bind::<SqlUuid, _>(id)
+            .get_result::<LocationRow>(&mut conn)
+            .optional()?;
+        row.map(Self::to_domain).transpose()
+    }
+
+    fn insert(&self, entity: &Location) -> AppResult<Location> {
+        let mut conn = self.get_connection()?;
+        let details = Self::to_details(entity)?;
+        let region = entity.region.clone().unwrap_or_else(|| entity.address.city.clone());
+
+        let result = diesel::sql_query(
+            "INSERT INTO locations (id, name, region, province, city, postal_code, address, geometry, details, created_at, updated_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, ST_SetSRID(ST_MakePoint($8, $9), 4326)::geography, $10, $11, $12)"
+        )
+            .bind::<SqlUuid, _>(entity.id.0)
+            .bind::<Text, _>(&entity.name)
+            .bind::<Text, _>(&region)
+            .bind::<Text, _>(&entity.address.province)
+            .bind::<Text, _>(&entity.address.city)
+            .bind::<Nullable<Text>, _>(entity.address.postal_code.as_deref())
+            .bind::<Text, _>(&entity.address.street)
+            .bind::<Float8, _>(entity.coordinates.longitude)
+            .bind::<Float8, _>(entity.coordinates.latitude)
+            .bind::<Jsonb, _>(&details)
+            .bind::<Timestamp, _>(entity.audit.created_at.naive_utc())
+            .bind::<Timestamp, _>(entity.audit.updated_at.naive_utc())
+            .execute(&mut conn);
+
+        match result {
+            Err(diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::UniqueViolation, _)) => {
+                Err(AppError::Conflict(format!("Location {} already exists", entity.id)))
+            }
+            other => {
+                other?;
+                let mut saved = entity.clone();
+                saved.region = Some(region);
+                Ok(saved)
+            }
+        }
+    }
+
+    /// Update the row if it still holds the version `entity` was read at
+    fn modify(&self, entity: &Location) -> AppResult<Location> {
+        let mut conn = self.get_connection()?;
+        let expected_version = i64::try_from(entity.audit.version)
+            .map_err(|_| AppError::Validation(format!("Location {} has an invalid version", entity.id)))?;
+        let mut updated = entity.clone();
+        updated.audit.updated_at = Utc::now();
+        updated.audit.version += 1;
+        let details = Self::to_details(&updated)?;
+        let region = updated.region.clone().unwrap_or_else(|| updated.address.city.clone());
+
+        let affected = diesel::sql_query(
+            "UPDATE locations SET name = $2, region = $3, province = $4, city = $5, postal_code = $6, address = $7, \
+             geometry = ST_SetSRID(ST_MakePoint($8, $9), 4326)::geography, details = $10, updated_at = $11 \
+             WHERE id = $1 AND COALESCE((details ->> 'version')::bigint, 0) = $12"
+        )
+            .bind::<SqlUuid, _>(updated.id.0)
+            .bind::<Text, _>(&updated.name)
+            .bind::<Text, _>(&region)
+            .bind::<Text, _>(&updated.address.province)
+            .bind::<Text, _>(&updated.address.city)
+            .bind::<Nullable<Text>, _>(updated.address.postal_code.as_deref())
+            .bind::<Text, _>(&updated.address.street)
+            .bind::<Float8, _>(updated.coordinates.longitude)
+            .bind::<Float8, _>(updated.coordinates.latitude)
+            .bind::<Jsonb, _>(&details)
+            .bind::<Timestamp, _>(updated.audit.updated_at.naive_utc())
+            .bind::<Int8, _>(expected_version)
+            .execute(&mut conn)?;
+
+        if affected == 0 {
+            let exists = diesel::select(diesel::dsl::exists(locations::table.filter(locations::id.eq(entity.id.0))))
+                .get_result::<bool>(&mut conn)?;
+            return Err(if exists {
+                AppError::Conflict(format!("Location {} was modified concurrently, reload and retry", entity.id))
+            } else {
+                AppError::NotFound(format!("Location {} not found", entity.id))
+            });
+        }
+
+        updated.region = Some(region);
+        Ok(updated)
+    }
+
+    fn remove(&self, id: Uuid) -> AppResult<bool> {
+        let mut conn = self.get_connection()?;
+        let result = diesel::delete(locations::table.filter(locations::id.eq(id)))
+            .execute(&mut conn);
+
+        match result {
+            Err(diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::ForeignKeyViolation, _)) => {
+                Err(AppError::Conflict(format!("Location {} is still referenced", id)))
+            }
+            other => Ok(other? > 0),
+        }
+    }
+
+    fn load_all(&self) -> AppResult<Vec<Location>> {
+        let mut conn = self.get_connection()?;
+        let rows = diesel::sql_query(format!("{} ORDER BY l.province, l.city, l.name LIMIT $1", SELECT_LOCATIONS))
+            .bind::<Int8, _>(MAX_RESULTS)
+            .load::<LocationRow>(&mut conn)?;
+        Ok(Self::rows_to_domain(rows))
+    }
 }
 
 #[async_trait]
 impl Repository<Location, LocationId> for PostgresLocationRepository {
-    async fn find_by_id(&self, _id: &LocationId) -> AppResult<Option<Location>> {
-        // Implementation would go here - using mock for now
-        Ok(None)
+    async fn find_by_id(&self, id: &LocationId) -> AppResult<Option<Location>> {
+        self.load_one(id.0)
     }
 
     async fn save(&self, entity: &Location) -> AppResult<Location> {
-        // Implementation would go here - using mock for now
-        Ok(entity.clone())
+        self.insert(entity)
     }
 
     async fn update(&self, entity: &Location) -> AppResult<Location> {
-        // Implementation would go here - using mock for now
-        Ok(entity.clone())
+        self.modify(entity)
     }
 
-    async fn delete(&self, _id: &LocationId) -> AppResult<bool> {
-        // Implementation would go here - using mock for now
-        Ok(true)
+    async fn delete(&self, id: &LocationId) -> AppResult<bool> {
+        self.remove(id.0)
     }
 
     async fn find_all(&self) -> AppResult<Vec<Location>> {
-        // Implementation would go here - using mock for now
-        Ok(Vec::new())
+        self.load_all()
     }
 }
 
 #[async_trait]
 impl LocationRepository for PostgresLocationRepository {
-    async fn find_by_coordinates(&self, _lat: f64, _lng: f64, _radius_km: f64) -> AppResult<Vec<Location>> {
-        // Implementation would go here
-        Ok(Vec::new())
+    async fn find_by_coordinates(&self, lat: f64, lng: f64, radius_km: f64) -> AppResult<Vec<Location>> {
+        self.load_within_radius(lat, lng, radius_km)
     }
 
-    async fn find_by_region(&self, _region: &str) -> AppResult<Vec<Location>> {
-        // Implementation would go here
-        Ok(Vec::new())
+    async fn find_by_region(&self, region: &str) -> AppResult<Vec<Location>> {
+        let mut conn = self.get_connection()?;
+        let rows = diesel::sql_query(format!(
+            "{} WHERE lower(l.region) = lower($1) ORDER BY l.name LIMIT $2",
+            SELECT_LOCATIONS
+        ))
+            .bind::<Text, _>(region.trim())
+            .bind::<Int8, _>(MAX_RESULTS)
+            .load::<LocationRow>(&mut conn)?;
+        Ok(Self::rows_to_domain(rows))
     }
 
-    async fn find_by_province(&self, _province: &str) -> AppResult<Vec<Location>> {
-        // Implementation would go here
-        Ok(Vec::new())
+    async fn find_by_province(&self, province: &str) -> AppResult<Vec<Location>> {
+        let mut conn = self.get_connection()?;
+        let rows = diesel::sql_query(format!(
+            "{} WHERE lower(l.province) = lower($1) ORDER BY l.city, l.name LIMIT $2",
+            SELECT_LOCATIONS
+        ))
+            .bind::<Text, _>(province.trim())
+            .bind::<Int8, _>(MAX_RESULTS)
+            .load::<LocationRow>(&mut conn)?;
+        Ok(Self::rows_to_domain(rows))
     }
 
-    async fn search_by_name(&self, _name: &str) -> AppResult<Vec<Location>> {
-        // Implementation would go here
-        Ok(Vec::new())
+    async fn search_by_name(&self, name: &str) -> AppResult<Vec<Location>> {
+        let term = name.trim();
+        if term.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Substring matches first, then trigram matches ranked by similarity
+        let mut conn = self.get_connection()?;
+        let rows = diesel::sql_query(format!(
+            "{} WHERE l.name ILIKE '%' || $1 || '%' OR l.address ILIKE '%' || $1 || '%' OR similarity(l.name, $2) >= $3 \
+             ORDER BY (l.name ILIKE '%' || $1 || '%') DESC, similarity(l.name, $2) DESC, l.name \
+             LIMIT $4",
+            SELECT_LOCATIONS
+        ))
+            .bind::<Text, _>(Self::escape_like(term))
+            .bind::<Text, _>(term)
+            .bind::<Float8, _>(NAME_SIMILARITY_THRESHOLD)
+            .bind::<Int8, _>(MAX_RESULTS)
+            .load::<LocationRow>(&mut conn)?;
+        Ok(Self::rows_to_domain(rows))
     }
 
-    async fn find_nearby(&self, _lat: f64, _lng: f64, _radius_km: f64) -> AppResult<Vec<Location>> {
-        // Implementation would go here
-        Ok(Vec::new())
+    async fn find_nearby(&self, lat: f64, lng: f64, radius_km: f64) -> AppResult<Vec<Location>> {
+        self.load_within_radius(lat, lng, radius_km)
+    }
+
+    async fn search(&self, criteria: &LocationSearch, limit: i64, offset: i64) -> AppResult<(Vec<Location>, u64)> {
+        if criteria.near.as_ref().is_some_and(|(_, radius_km)| *radius_km <= 0.0) {
+            return Err(AppError::Validation("Radius must be greater than zero".to_string()));
+        }
+
+        let order = if criteria.near.is_some() {
+            "ST_Distance(l.geometry, ST_SetSRID(ST_MakePoint($8, $7), 4326)::geography) ASC, l.name"
+        } else if criteria.text.as_deref().is_some_and(|t| !t.trim().is_empty()) {
+            "(l.name ILIKE '%' || $1 || '%') DESC, similarity(l.name, $2) DESC, l.name"
+        } else {
+            "l.province, l.city, l.name"
+        };
+
+        let mut conn = self.get_connection()?;
+        let total = Self::searched(format!("SELECT count(*) AS count FROM locations l WHERE {}", SEARCH_FILTER), criteria)?
+            .get_result::<CountRow>(&mut conn)?
+            .count;
+        if total == 0 {
+            return Ok((Vec::new(), 0));
+        }
+
+        let rows = Self::searched(
+            format!("{} WHERE {} ORDER BY {} LIMIT $11 OFFSET $12", SELECT_LOCATIONS, SEARCH_FILTER, order),
+            criteria,
+        )?
+            .bind::<Int8, _>(limit.clamp(1, MAX_RESULTS))
+            .bind::<Int8, _>(offset.max(0))
+            .load::<LocationRow>(&mut conn)?;
+        Ok((Self::rows_to_domain(rows), total as u64))
     }
 
     async fn save_location(&self, location: &Location) -> AppResult<Location> {
-        // Implementation would go here
-        Ok(location.clone())
+        if self.load_one(location.id.0)?.is_some() {
+            self.modify(location)
+        } else {
+            self.insert(location)
+        }
+    }
+
+    async fn delete_location(&self, id: LocationId) -> AppResult<bool> {
+        self.remove(id.0)
+    }
+
+    async fn find_by_type(&self, location_type: &LocationType) -> AppResult<Vec<Location>> {
+        let type_value = serde_json::to_value(location_type)
+            .map_err(|e| AppError::Serialization(e.to_string()))?;
+
+        let mut conn = self.get_connection()?;
+        let rows = diesel::sql_query(format!(
+            "{} WHERE l.details -> 'location_type' = $1 ORDER BY l.province, l.city, l.name LIMIT $2",
+            SELECT_LOCATIONS
+        ))
+            .bind::<Jsonb, _>(&type_value)
+            .bind::<Int8, _>(MAX_RESULTS)
+            .load::<LocationRow>(&mut conn)?;
+        Ok(Self::rows_to_domain(rows))
     }
 
-    async fn delete_location(&self, _id: LocationId) -> AppResult<bool> {
-        // Implementation would go here
-        Ok(true)
+    async fn list_provinces(&self) -> AppResult<Vec<String>> {
+        let mut conn = self.get_connection()?;
+        let rows = diesel::sql_query(
+            "SELECT DISTINCT province AS name FROM locations WHERE province IS NOT NULL ORDER BY province"
+        )
+            .load::<NameRow>(&mut conn)?;
+        Ok(rows.into_iter().map(|r| r.name).collect())
+    }
+
+    async fn list_cities(&self, province: &str) -> AppResult<Vec<String>> {
+        let mut conn = self.get_connection()?;
+        let rows = diesel::sql_query(
+            "SELECT DISTINCT city AS name FROM locations \
+             WHERE city IS NOT NULL AND lower(province) = lower($1) ORDER BY city"
+        )
+            .bind::<Text, _>(province.trim())
+            .load::<NameRow>(&mut conn)?;
+        Ok(rows.into_iter().map(|r| r.name).collect())
     }
 
     async fn find_by_id(&self, id: &LocationId) -> AppResult<Option<Location>> {
-        // Implementation would go here
-        Ok(None)
+        self.load_one(id.0)
     }
 
     async fn save(&self, entity: &Location) -> AppResult<Location> {
-        Ok(entity.clone())
+        self.insert(entity)
     }
 
     async fn update(&self, entity: &Location) -> AppResult<Location> {
-        Ok(entity.clone())
+        self.modify(entity)
     }
 
-    async fn delete(&self, _id: &LocationId) -> AppResult<bool> {
-        Ok(true)
+    async fn delete(&self, id: &LocationId) -> AppResult<bool> {
+        self.remove(id.0)
     }
 
     async fn find_all(&self) -> AppResult<Vec<Location>> {
-        Ok(Vec::new())
+        self.load_all()
+    }
+
+    async fn find_by_name(&self, name: &str) -> AppResult<Option<Location>> {
+        let mut conn = self.get_connection()?;
+        let row = diesel::sql_query(format!(
+            "{} WHERE lower(l.name) = lower($1) ORDER BY l.created_at ASC LIMIT 1",
+            SELECT_LOCATIONS
+        ))
+            .bind::<Text, _>(name.trim())
+            .get_result::<LocationRow>(&mut conn)
+            .optional()?;
+        row.map(Self::to_domain).transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_like_treats_wildcards_literally() {
+        assert_eq!(PostgresLocationRepository::escape_like("50%_off\\"), "50\\%\\_off\\\\");
+        assert_eq!(PostgresLocationRepository::escape_like("Bukit Duri"), "Bukit Duri");
     }
 
-    async fn find_by_name(&self, _name: &str) -> AppResult<Option<Location>> {
-        Ok(None)
+    #[test]
+    fn empty_details_fall_back_to_defaults() {
+        let details: LocationDetails = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert!(details.location_type.is_none());
+        assert!(details.risk_factors.is_empty());
+        assert_eq!(details.version, 0);
     }
 }
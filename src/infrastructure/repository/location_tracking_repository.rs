@@ -0,0 +1,194 @@
+/// Location tracking repository implementation
+/// GPS fixes in `volunteer_locations`, one row per fix; a unique (user, device time) index
+/// drops fixes a device re-sends after an unconfirmed upload
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Float8, Nullable, Timestamp, Uuid as SqlUuid};
+use uuid::Uuid;
+
+use crate::domain::entities::tracking::LocationFix;
+use crate::domain::ports::repositories::LocationTrackingRepository;
+use crate::domain::value_objects::Coordinates;
+use crate::infrastructure::database::{DbConnection, DbPool};
+use crate::shared::error::DatabaseError;
+use crate::shared::{AppError, AppResult, DisasterId, UserId, VolunteerId};
+
+const FIX_COLUMNS: &str = r#"
+SELECT l.id, l.user_id, l.volunteer_id, l.team_id, l.assignment_id,
+       ST_Y(l.geometry::geometry) AS latitude, ST_X(l.geometry::geometry) AS longitude,
+       l.accuracy_m, l.speed_mps, l.heading_deg, l.recorded_at, l.received_at
+FROM volunteer_locations l
+"#;
+
+/// Rows without a position, time or user predate device tracking and are skipped
+const TRACKED: &str = "l.geometry IS NOT NULL AND l.recorded_at IS NOT NULL AND l.user_id IS NOT NULL";
+
+#[derive(QueryableByName, Debug)]
+struct FixRow {
+    #[diesel(sql_type = SqlUuid)]
+    id: Uuid,
+    #[diesel(sql_type = Nullable<SqlUuid>)]
+    user_id: Option<Uuid>,
+    #[diesel(sql_type = Nullable<SqlUuid>)]
+    volunteer_id: Option<Uuid>,
+    #[diesel(sql_type = Nullable<SqlUuid>)]
+    team_id: Option<Uuid>,
+    #[diesel(sql_type = Nullable<SqlUuid>)]
+    assignment_id: Option<Uuid>,
+    #[diesel(sql_type = Float8)]
+    latitude: f64,
+    #[diesel(sql_type = Float8)]
+    longitude: f64,
+    #[diesel(sql_type = Nullable<Float8>)]
+    accuracy_m: Option<f64>,
+    #[diesel(sql_type = Nullable<Float8>)]
+    speed_mps: Option<f64>,
+    #[diesel(sql_type = Nullable<Float8>)]
+    heading_deg: Option<f64>,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    recorded_at: Option<NaiveDateTime>,
+    #[diesel(sql_type = Timestamp)]
+    received_at: NaiveDateTime,
+}
+
+pub struct PostgresLocationTrackingRepository {
+    pool: DbPool,
+}
+
+impl PostgresLocationTrackingRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    fn get_connection(&self) -> AppResult<DbConnection> {
+        self.pool.get()
+            .map_err(|e| AppError::Database(DatabaseError::ConnectionPool(e)))
+    }
+
+    fn utc(t: NaiveDateTime) -> DateTime<Utc> {
+        Utc.from_utc_datetime(&t)
+    }
+
+    fn to_domain(row: FixRow) -> AppResult<LocationFix> {
+        let (Some(user_id), Some(recorded_at)) = (row.user_id, row.recorded_at) else {
+            return Err(AppError::DataConsistency(format!("Location fix {} has no user or time", row.id)));
+        };
+        Ok(LocationFix {
+            id: row.id,
+            user_id: UserId(user_id),
+            volunteer_id: row.volunteer_id.map(VolunteerId),
+            team_id: row.team_id,
+            assignment_id: row.assignment_id,
+            position: Coordinates { latitude: row.latitude, longitude: row.longitude, altitude: None },
+            accuracy_m: row.accuracy_m,
+            speed_mps: row.speed_mps,
+            heading_deg: row.heading_deg,
+            recorded_at: Self::utc(recorded_at),
+            received_at: Self::utc(row.received_at),
+        })
+    }
+
+    /// Trail of fixes matching `filter` (bound to $1), newest `limit` returned oldest first
+    fn trail(&self, filter: &str, key: Uuid, since: Option<DateTime<Utc>>, limit: i64) -> AppResult<Vec<LocationFix>> {
+        let mut conn = self.get_connection()?;
+        let rows = diesel::sql_query(format!(
+            "SELECT * FROM ({} WHERE {} AND {} AND ($2 IS NULL OR l.recorded_at >= $2) \
+             ORDER BY l.recorded_at DESC LIMIT $3) trail ORDER BY recorded_at",
+            FIX_COLUMNS, TRACKED, filter
+        ))
+            .bind::<SqlUuid, _>(key)
+            .bind::<Nullable<Timestamp>, _>(since.map(|t| t.naive_utc()))
+            .bind::<BigInt, _>(limit)
+            .load::<FixRow>(&mut conn)?;
+        rows.into_iter().map(Self::to_domain).collect()
+    }
+
+    fn latest(&self, filter: &str, key: Uuid) -> AppResult<Option<LocationFix>> {
+        let mut conn = self.get_connection()?;
+        let row = diesel::sql_query(format!(
+            "{} WHERE {} AND {} ORDER BY l.recorded_at DESC LIMIT 1",
+            FIX_COLUMNS, TRACKED, filter
+        ))
+            .bind::<SqlUuid, _>(key)
+            .get_result::<FixRow>(&mut conn)
+            .optional()?;
+        row.map(Self::to_domain).transpose()
+    }
+}
+
+#[async_trait]
+impl LocationTrackingRepository for PostgresLocationTrackingRepository {
+    async fn record_fixes(&self, fixes: &[LocationFix]) -> AppResult<usize> {
+        let mut conn = self.get_connection()?;
+        conn.transaction::<_, AppError, _>(|conn| {
+            let mut inserted = 0;
+            for fix in fixes {
+                inserted += diesel::sql_query(
+                    "INSERT INTO volunteer_locations \
+                         (id, user_id, volunteer_id, team_id, assignment_id, geometry, \
+                          accuracy_m, speed_mps, heading_deg, recorded_at, received_at) \
+                     VALUES ($1, $2, $3, $4, $5, ST_SetSRID(ST_MakePoint($6, $7), 4326)::geography, \
+                             $8, $9, $10, $11, $12) \
+                     ON CONFLICT (user_id, recorded_at) DO NOTHING"
+                )
+                    .bind::<SqlUuid, _>(fix.id)
+                    .bind::<SqlUuid, _>(fix.user_id.0)
+                    .bind::<Nullable<SqlUuid>, _>(fix.volunteer_id.map(|id| id.0))
+                    .bind::<Nullable<SqlUuid>, _>(fix.team_id)
+                    .bind::<Nullable<SqlUuid>, _>(fix.assignment_id)
+                    .bind::<Float8, _>(fix.position.longitude)
+                    .bind::<Float8, _>(fix.position.latitude)
+                    .bind::<Nullable<Float8>, _>(fix.accuracy_m)
+                    .bind::<Nullable<Float8>, _>(fix.speed_mps)
+                    .bind::<Nullable<Float8>, _>(fix.heading_deg)
+                    .bind::<Timestamp, _>(fix.recorded_at.naive_utc())
+                    .bind::<Timestamp, _>(fix.received_at.naive_utc())
+                    .execute(conn)?;
+            }
+            Ok(inserted)
+        })
+    }
+
+    async fn latest_for_user(&self, user_id: &UserId) -> AppResult<Option<LocationFix>> {
+        self.latest("l.user_id = $1", user_id.0)
+    }
+
+    async fn latest_for_team(&self, team_id: &Uuid) -> AppResult<Option<LocationFix>> {
+        self.latest("l.team_id = $1", *team_id)
+    }
+
+    async fn latest_for_disaster(&self, disaster_id: &DisasterId, since: DateTime<Utc>) -> AppResult<Vec<LocationFix>> {
+        let mut conn = self.get_connection()?;
+        let rows = diesel::sql_query(format!(
+            "SELECT * FROM ( \
+                 SELECT DISTINCT ON (fix.user_id) fix.* FROM ({} \
+                 WHERE {} AND l.recorded_at >= $2 AND ( \
+                     l.assignment_id IN ( \
+                         SELECT t.id FROM volunteer_tracking t \
+                         WHERE t.status IN ('assigned', 'accepted', 'arrived') \
+                           AND (t.disaster_id = $1 OR t.report_id IN \
+                                (SELECT report_id FROM disaster_reports WHERE disaster_id = $1))) \
+                     OR l.team_id IN ( \
+                         SELECT unnest(r.assigned_teams) FROM emergency_responses r \
+                         WHERE r.disaster_id = $1 AND r.status <> 'completed')) \
+                 ) fix \
+                 ORDER BY fix.user_id, fix.recorded_at DESC \
+             ) latest ORDER BY recorded_at DESC",
+            FIX_COLUMNS, TRACKED
+        ))
+            .bind::<SqlUuid, _>(disaster_id.0)
+            .bind::<Timestamp, _>(since.naive_utc())
+            .load::<FixRow>(&mut conn)?;
+        rows.into_iter().map(Self::to_domain).collect()
+    }
+
+    async fn trail_for_assignment(&self, assignment_id: &Uuid, since: Option<DateTime<Utc>>, limit: i64) -> AppResult<Vec<LocationFix>> {
+        self.trail("l.assignment_id = $1", *assignment_id, since, limit)
+    }
+
+    async fn trail_for_team(&self, team_id: &Uuid, since: Option<DateTime<Utc>>, limit: i64) -> AppResult<Vec<LocationFix>> {
+        self.trail("l.team_id = $1", *team_id, since, limit)
+    }
+}
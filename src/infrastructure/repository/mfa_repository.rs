@@ -0,0 +1,187 @@
+/// MFA repository implementation
+/// Stores encrypted TOTP enrollments and hashed single-use recovery codes
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::sql_types::{Bytea, Timestamptz};
+use uuid::Uuid;
+
+use crate::domain::entities::MfaEnrollment;
+use crate::domain::ports::repositories::MfaRepository;
+use crate::infrastructure::database::schemas::{mfa_recovery_codes, user_mfa};
+use crate::infrastructure::database::{DbConnection, DbPool};
+use crate::shared::error::DatabaseError;
+use crate::shared::{AppError, AppResult, UserId};
+
+#[derive(Queryable, Selectable, Debug)]
+#[diesel(table_name = user_mfa)]
+struct UserMfaRecord {
+    user_id: Uuid,
+    secret_ciphertext: Vec<u8>,
+    confirmed_at: Option<DateTime<Utc>>,
+    last_used_step: Option<i64>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = mfa_recovery_codes)]
+struct NewRecoveryCode<'a> {
+    id: Uuid,
+    user_id: Uuid,
+    code_hash: &'a str,
+    created_at: DateTime<Utc>,
+}
+
+pub struct PostgresMfaRepository {
+    pool: DbPool,
+}
+
+impl PostgresMfaRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    fn get_connection(&self) -> AppResult<DbConnection> {
+        self.pool.get()
+            .map_err(|e| AppError::Database(DatabaseError::ConnectionPool(e)))
+    }
+
+    fn write_recovery_codes(conn: &mut DbConnection, user_id: Uuid, code_hashes: &[String]) -> AppResult<()> {
+        diesel::delete(mfa_recovery_codes::table.filter(mfa_recovery_codes::user_id.eq(user_id)))
+            .execute(conn)?;
+
+        let now = Utc::now();
+        let rows: Vec<NewRecoveryCode> = code_hashes.iter()
+            .map(|hash| NewRecoveryCode { id: Uuid::new_v4(), user_id, code_hash: hash, created_at: now })
+            .collect();
+        diesel::insert_into(mfa_recovery_codes::table)
+            .values(&rows)
+            .execute(conn)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl MfaRepository for PostgresMfaRepository {
+    async fn find_by_user(&self, user_id: &UserId) -> AppResult<Option<MfaEnrollment>> {
+        let mut conn = self.get_connection()?;
+        let record: Option<UserMfaRecord> = user_mfa::table
+            .filter(user_mfa::user_id.eq(user_id.0))
+            .select(UserMfaRecord::as_select())
+            .first(&mut conn)
+            .optional()?;
+
+        Ok(record.map(|record| MfaEnrollment {
+            user_id: UserId(record.user_id),
+            secret_ciphertext: record.secret_ciphertext,
+            confirmed_at: record.confirmed_at,
+            last_used_step: record.last_used_step,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+        }))
+    }
+
+    async fn save_pending(&self, enrollment: &MfaEnrollment) -> AppResult<()> {
+        let mut conn = self.get_connection()?;
+
+        // Restarting enrollment may replace a pending secret but never an active one
+        let written = diesel::sql_query(
+            "INSERT INTO user_mfa (user_id, secret_ciphertext, created_at, updated_at) \
+             VALUES ($1, $2, $3, $3) \
+             ON CONFLICT (user_id) DO UPDATE \
+             SET secret_ciphertext = EXCLUDED.secret_ciphertext, updated_at = EXCLUDED.updated_at \
+             WHERE user_mfa.confirmed_at IS NULL"
+        )
+            .bind::<diesel::sql_types::Uuid, _>(enrollment.user_id.0)
+            .bind::<Bytea, _>(&enrollment.secret_ciphertext)
+            .bind::<Timestamptz, _>(enrollment.updated_at)
+            .execute(&mut conn);
+
+        match written {
+            Ok(0) => Err(AppError::Conflict("MFA is already enabled for this account".to_string())),
+            Ok(_) => Ok(()),
+            Err(diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::ForeignKeyViolation, _)) => {
+                Err(AppError::NotFound(format!("User {} not found", enrollment.user_id)))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn confirm(&self, user_id: &UserId, step: i64, recovery_code_hashes: &[String]) -> AppResult<bool> {
+        let mut conn = self.get_connection()?;
+        conn.transaction::<_, AppError, _>(|conn| {
+            let now = Utc::now();
+            let updated = diesel::update(
+                user_mfa::table
+                    .filter(user_mfa::user_id.eq(user_id.0))
+                    .filter(user_mfa::confirmed_at.is_null()),
+            )
+                .set((
+                    user_mfa::confirmed_at.eq(Some(now)),
+                    user_mfa::last_used_step.eq(Some(step)),
+                    user_mfa::updated_at.eq(now),
+                ))
+                .execute(conn)?;
+            if updated == 0 {
+                return Ok(false);
+            }
+
+            Self::write_recovery_codes(conn, user_id.0, recovery_code_hashes)?;
+            Ok(true)
+        })
+    }
+
+    async fn record_step(&self, user_id: &UserId, step: i64) -> AppResult<bool> {
+        let mut conn = self.get_connection()?;
+        let updated = diesel::update(
+            user_mfa::table
+                .filter(user_mfa::user_id.eq(user_id.0))
+                .filter(user_mfa::confirmed_at.is_not_null())
+                .filter(user_mfa::last_used_step.is_null().or(user_mfa::last_used_step.lt(step))),
+        )
+            .set((user_mfa::last_used_step.eq(Some(step)), user_mfa::updated_at.eq(Utc::now())))
+            .execute(&mut conn)?;
+        Ok(updated > 0)
+    }
+
+    async fn consume_recovery_code(&self, user_id: &UserId, code_hash: &str) -> AppResult<bool> {
+        let mut conn = self.get_connection()?;
+        let updated = diesel::update(
+            mfa_recovery_codes::table
+                .filter(mfa_recovery_codes::user_id.eq(user_id.0))
+                .filter(mfa_recovery_codes::code_hash.eq(code_hash))
+                .filter(mfa_recovery_codes::used_at.is_null()),
+        )
+            .set(mfa_recovery_codes::used_at.eq(Some(Utc::now())))
+            .execute(&mut conn)?;
+        Ok(updated > 0)
+    }
+
+    async fn replace_recovery_codes(&self, user_id: &UserId, code_hashes: &[String]) -> AppResult<()> {
+        let mut conn = self.get_connection()?;
+        conn.transaction::<_, AppError, _>(|conn| Self::write_recovery_codes(conn, user_id.0, code_hashes))
+    }
+
+    async fn count_unused_recovery_codes(&self, user_id: &UserId) -> AppResult<u64> {
+        let mut conn = self.get_connection()?;
+        let count: i64 = mfa_recovery_codes::table
+            .filter(mfa_recovery_codes::user_id.eq(user_id.0))
+            .filter(mfa_recovery_codes::used_at.is_null())
+            .count()
+            .get_result(&mut conn)?;
+        Ok(count as u64)
+    }
+
+    async fn delete(&self, user_id: &UserId) -> AppResult<bool> {
+        let mut conn = self.get_connection()?;
+        conn.transaction::<_, AppError, _>(|conn| {
+            diesel::delete(mfa_recovery_codes::table.filter(mfa_recovery_codes::user_id.eq(user_id.0)))
+                .execute(conn)?;
+            let deleted = diesel::delete(user_mfa::table.filter(user_mfa::user_id.eq(user_id.0)))
+                .execute(conn)?;
+            Ok(deleted > 0)
+        })
+    }
+}
@@ -5,9 +5,45 @@ pub mod user_repository;
 pub mod disaster_repository;
 pub mod location_repository;
 pub mod notification_repository;
+pub mod event_sourced_disaster_repository;
+pub mod emergency_response_repository;
+pub mod resource_inventory_repository;
+pub mod response_team_repository;
+pub mod emergency_station_repository;
+pub mod disaster_zone_repository;
+pub mod notification_outbox_repository;
+pub mod mfa_repository;
+pub mod verification_code_repository;
+pub mod session_repository;
+pub mod organization_repository;
+pub mod province_assignment_repository;
+pub mod role_repository;
+pub mod report_repository;
+pub mod volunteer_repository;
+pub mod location_tracking_repository;
+pub mod analytics_repository;
+pub mod cap_alert_import_repository;
 
 // Re-export repository implementations
 pub use user_repository::PostgresUserRepository;
 pub use disaster_repository::PostgresDisasterRepository;
 pub use location_repository::PostgresLocationRepository;
 pub use notification_repository::PostgresNotificationRepository;
+pub use event_sourced_disaster_repository::EventSourcedDisasterRepository;
+pub use emergency_response_repository::PostgresEmergencyResponseRepository;
+pub use resource_inventory_repository::PostgresResourceInventoryRepository;
+pub use response_team_repository::PostgresResponseTeamRepository;
+pub use emergency_station_repository::PostgresEmergencyStationRepository;
+pub use disaster_zone_repository::PostgresDisasterZoneRepository;
+pub use notification_outbox_repository::PostgresNotificationOutboxRepository;
+pub use mfa_repository::PostgresMfaRepository;
+pub use verification_code_repository::PostgresVerificationCodeRepository;
+pub use session_repository::PostgresSessionRepository;
+pub use organization_repository::PostgresOrganizationRepository;
+pub use province_assignment_repository::PostgresProvinceAssignmentRepository;
+pub use role_repository::PostgresRoleRepository;
+pub use report_repository::PostgresReportRepository;
+pub use volunteer_repository::PostgresVolunteerRepository;
+pub use location_tracking_repository::PostgresLocationTrackingRepository;
+pub use analytics_repository::PostgresAnalyticsRepository;
+pub use cap_alert_import_repository::PostgresCapAlertImportRepository;
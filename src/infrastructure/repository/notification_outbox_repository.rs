@@ -0,0 +1,357 @@
+/// Notification outbox repository implementation
+/// Queues per-channel deliveries next to the notification rows and leases them to workers
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Double, Text};
+use uuid::Uuid;
+
+use crate::domain::entities::notification::{DeliveryAttempt, Notification, OutboxMessage, OutboxStatus};
+use crate::domain::ports::repositories::NotificationOutboxRepository;
+use crate::infrastructure::database::schemas::{notification_delivery_attempts, notification_outbox, notifications};
+use crate::infrastructure::database::{DbConnection, DbPool};
+use crate::shared::error::DatabaseError;
+use crate::shared::types::Priority;
+use crate::shared::{AppError, AppResult, NotificationId, UserId};
+
+#[derive(Queryable, QueryableByName, Selectable, Insertable, Debug)]
+#[diesel(table_name = notification_outbox)]
+struct OutboxRecord {
+    id: Uuid,
+    notification_id: Uuid,
+    recipient_id: Uuid,
+    channel: String,
+    notification_type: String,
+    title: String,
+    message: String,
+    priority: i16,
+    status: String,
+    attempts: i32,
+    max_attempts: i32,
+    next_attempt_at: DateTime<Utc>,
+    locked_by: Option<String>,
+    locked_until: Option<DateTime<Utc>>,
+    last_error: Option<String>,
+    expires_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    sent_at: Option<DateTime<Utc>>,
+    dead_lettered_at: Option<DateTime<Utc>>,
+}
+
+/// Delivery state written back by workers; always releases the lease
+#[derive(AsChangeset)]
+#[diesel(table_name = notification_outbox)]
+#[diesel(treat_none_as_null = true)]
+struct OutboxStateChangeset {
+    status: String,
+    attempts: i32,
+    next_attempt_at: DateTime<Utc>,
+    locked_by: Option<String>,
+    locked_until: Option<DateTime<Utc>>,
+    last_error: Option<String>,
+    updated_at: DateTime<Utc>,
+    sent_at: Option<DateTime<Utc>>,
+    dead_lettered_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Queryable, Selectable, Insertable, Debug)]
+#[diesel(table_name = notification_delivery_attempts)]
+struct DeliveryAttemptRecord {
+    id: Uuid,
+    outbox_id: Uuid,
+    channel: String,
+    attempted_at: DateTime<Utc>,
+    success: bool,
+    error_message: Option<String>,
+    response_details: Option<String>,
+}
+
+pub struct PostgresNotificationOutboxRepository {
+    pool: DbPool,
+    max_attempts: u32,
+}
+
+impl PostgresNotificationOutboxRepository {
+    pub fn new(pool: DbPool, max_attempts: u32) -> Self {
+        Self { pool, max_attempts }
+    }
+
+    fn get_connection(&self) -> AppResult<DbConnection> {
+        self.pool.get()
+            .map_err(|e| AppError::Database(DatabaseError::ConnectionPool(e)))
+    }
+
+    fn to_record(message: &OutboxMessage) -> OutboxRecord {
+        OutboxRecord {
+            id: message.id,
+            notification_id: message.notification_id.0,
+            recipient_id: message.recipient_id.0,
+            channel: message.channel.as_str().to_string(),
+            notification_type: message.notification_type.clone(),
+            title: message.title.clone(),
+            message: message.message.clone(),
+            priority: message.priority.clone() as i16,
+            status: message.status.as_str().to_string(),
+            attempts: message.attempts as i32,
+            max_attempts: message.max_attempts as i32,
+            next_attempt_at: message.next_attempt_at,
+            locked_by: None,
+            locked_until: None,
+            last_error: message.last_error.clone(),
+            expires_at: message.expires_at,
+            created_at: message.created_at,
+            updated_at: message.updated_at,
+            sent_at: message.sent_at,
+            dead_lettered_at: message.dead_lettered_at,
+        }
+    }
+
+    fn to_domain(record: OutboxRecord) -> AppResult<OutboxMessage> {
+        let priority = match record.priority {
+            1 => Priority::Low,
+            2 => Priority::Normal,
+            3 => Priority::High,
+            4 => Priority::Critical,
+            5 => Priority::Emergency,
+            other => return Err(AppError::DataConsistency(format!(
+                "Outbox message {} has unknown priority {}", record.id, other
+            ))),
+        };
+
+        Ok(OutboxMessage {
+            id: record.id,
+            notification_id: NotificationId(record.notification_id),
+            recipient_id: UserId(record.recipient_id),
+            channel: record.channel.parse()
+                .map_err(|_| AppError::DataConsistency(format!("Unknown outbox channel '{}'", record.channel)))?,
+            notification_type: record.notification_type,
+            title: record.title,
+            message: record.message,
+            priority,
+            status: record.status.parse()
+                .map_err(|_| AppError::DataConsistency(format!("Unknown outbox status '{}'", record.status)))?,
+            attempts: record.attempts.max(0) as u32,
+            max_attempts: record.max_attempts.max(0) as u32,
+            next_attempt_at: record.next_attempt_at,
+            last_error: record.last_error,
+            expires_at: record.expires_at,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+            sent_at: record.sent_at,
+            dead_lettered_at: record.dead_lettered_at,
+        })
+    }
+
+    fn state_changeset(message: &OutboxMessage) -> OutboxStateChangeset {
+        OutboxStateChangeset {
+            status: message.status.as_str().to_string(),
+            attempts: message.attempts as i32,
+            next_attempt_at: message.next_attempt_at,
+            locked_by: None,
+            locked_until: None,
+            last_error: message.last_error.clone(),
+            updated_at: message.updated_at,
+            sent_at: message.sent_at,
+            dead_lettered_at: message.dead_lettered_at,
+        }
+    }
+
+    /// Insert the notification rows and outbox messages for one notification
+    fn write_messages(conn: &mut DbConnection, notification: &Notification, messages: &[OutboxMessage]) -> AppResult<()> {
+        for message in messages {
+            let created_at = message.created_at.naive_utc();
+            diesel::insert_into(notifications::table)
+                .values((
+                    notifications::id.eq(message.id),
+                    notifications::user_id.eq(Some(message.recipient_id.0)),
+                    notifications::title.eq(&message.title),
+                    notifications::message.eq(&message.message),
+                    notifications::channel.eq(message.channel.as_str()),
+                    notifications::status.eq(Some("pending")),
+                    notifications::is_read.eq(Some(false)),
+                    notifications::created_at.eq(Some(created_at)),
+                    notifications::updated_at.eq(Some(created_at)),
+                ))
+                .execute(conn)?;
+        }
+
+        let records: Vec<OutboxRecord> = messages.iter().map(Self::to_record).collect();
+        match diesel::insert_into(notification_outbox::table).values(&records).execute(conn) {
+            Err(diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::ForeignKeyViolation, _)) => {
+                Err(AppError::Validation(format!("Unknown notification recipient {}", notification.recipient_id)))
+            }
+            other => {
+                other?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Write back a worker's result and mirror it on the notification row
+    fn apply_state(conn: &mut DbConnection, worker_id: &str, message: &OutboxMessage) -> AppResult<bool> {
+        let updated = diesel::update(
+            notification_outbox::table
+                .filter(notification_outbox::id.eq(message.id))
+                .filter(notification_outbox::locked_by.eq(worker_id)),
+        )
+            .set(&Self::state_changeset(message))
+            .execute(conn)?;
+
+        let notification_status = match message.status {
+            OutboxStatus::Sent => "sent",
+            OutboxStatus::DeadLettered => "failed",
+            OutboxStatus::Pending | OutboxStatus::Processing => "pending",
+        };
+        if updated > 0 {
+            diesel::update(notifications::table.filter(notifications::id.eq(message.id)))
+                .set((
+                    notifications::status.eq(Some(notification_status.to_string())),
+                    notifications::send_at.eq(message.sent_at.map(|at| at.naive_utc())),
+                    notifications::updated_at.eq(Some(message.updated_at.naive_utc())),
+                ))
+                .execute(conn)?;
+        }
+        Ok(updated > 0)
+    }
+}
+
+#[async_trait]
+impl NotificationOutboxRepository for PostgresNotificationOutboxRepository {
+    async fn enqueue(&self, notification: &Notification) -> AppResult<Vec<OutboxMessage>> {
+        let messages = OutboxMessage::from_notification(notification, self.max_attempts);
+        let mut conn = self.get_connection()?;
+        conn.transaction::<_, AppError, _>(|conn| Self::write_messages(conn, notification, &messages))?;
+        Ok(messages)
+    }
+
+    async fn enqueue_batch(&self, notifications: &[Notification]) -> AppResult<Vec<OutboxMessage>> {
+        let batches: Vec<Vec<OutboxMessage>> = notifications.iter()
+            .map(|notification| OutboxMessage::from_notification(notification, self.max_attempts))
+            .collect();
+        let mut conn = self.get_connection()?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            for (notification, messages) in notifications.iter().zip(&batches) {
+                Self::write_messages(conn, notification, messages)?;
+            }
+            Ok(())
+        })?;
+
+        Ok(batches.into_iter().flatten().collect())
+    }
+
+    async fn claim_due(&self, worker_id: &str, limit: u32, lease: std::time::Duration) -> AppResult<Vec<OutboxMessage>> {
+        let mut conn = self.get_connection()?;
+        let records = diesel::sql_query(
+            "UPDATE notification_outbox \
+             SET status = 'processing', locked_by = $1, \
+                 locked_until = NOW() + make_interval(secs => $2), updated_at = NOW() \
+             WHERE id IN ( \
+                 SELECT id FROM notification_outbox \
+                 WHERE (status = 'pending' AND next_attempt_at <= NOW()) \
+                    OR (status = 'processing' AND locked_until < NOW()) \
+                 ORDER BY priority DESC, next_attempt_at \
+                 LIMIT $3 \
+                 FOR UPDATE SKIP LOCKED) \
+             RETURNING *"
+        )
+            .bind::<Text, _>(worker_id)
+            .bind::<Double, _>(lease.as_secs_f64())
+            .bind::<BigInt, _>(limit as i64)
+            .load::<OutboxRecord>(&mut conn)?;
+
+        let mut messages = records.into_iter().map(Self::to_domain).collect::<AppResult<Vec<_>>>()?;
+        messages.sort_by(|a, b| (b.priority.clone() as i16).cmp(&(a.priority.clone() as i16))
+            .then(a.next_attempt_at.cmp(&b.next_attempt_at)));
+        Ok(messages)
+    }
+
+    async fn record_attempt(&self, worker_id: &str, message: &OutboxMessage, attempt: &DeliveryAttempt) -> AppResult<bool> {
+        let mut conn = self.get_connection()?;
+        let record = DeliveryAttemptRecord {
+            id: Uuid::new_v4(),
+            outbox_id: message.id,
+            channel: attempt.channel.as_str().to_string(),
+            attempted_at: attempt.attempted_at,
+            success: attempt.success,
+            error_message: attempt.error_message.clone(),
+            response_details: attempt.response_details.clone(),
+        };
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            diesel::insert_into(notification_delivery_attempts::table)
+                .values(&record)
+                .execute(conn)?;
+            Self::apply_state(conn, worker_id, message)
+        })
+    }
+
+    async fn dead_letter(&self, worker_id: &str, message: &OutboxMessage) -> AppResult<bool> {
+        let mut conn = self.get_connection()?;
+        conn.transaction::<_, AppError, _>(|conn| Self::apply_state(conn, worker_id, message))
+    }
+
+    async fn find_by_id(&self, id: &Uuid) -> AppResult<Option<OutboxMessage>> {
+        let mut conn = self.get_connection()?;
+        let record: Option<OutboxRecord> = notification_outbox::table
+            .filter(notification_outbox::id.eq(id))
+            .select(OutboxRecord::as_select())
+            .first(&mut conn)
+            .optional()?;
+        record.map(Self::to_domain).transpose()
+    }
+
+    async fn find_dead_lettered(&self, limit: u32, offset: u32) -> AppResult<Vec<OutboxMessage>> {
+        let mut conn = self.get_connection()?;
+        let records = notification_outbox::table
+            .filter(notification_outbox::status.eq(OutboxStatus::DeadLettered.as_str()))
+            .order(notification_outbox::dead_lettered_at.desc())
+            .limit(limit as i64)
+            .offset(offset as i64)
+            .select(OutboxRecord::as_select())
+            .load(&mut conn)?;
+        records.into_iter().map(Self::to_domain).collect()
+    }
+
+    async fn find_attempts(&self, id: &Uuid) -> AppResult<Vec<DeliveryAttempt>> {
+        let mut conn = self.get_connection()?;
+        let records = notification_delivery_attempts::table
+            .filter(notification_delivery_attempts::outbox_id.eq(id))
+            .order(notification_delivery_attempts::attempted_at.asc())
+            .select(DeliveryAttemptRecord::as_select())
+            .load(&mut conn)?;
+
+        records.into_iter()
+            .map(|record| Ok(DeliveryAttempt {
+                channel: record.channel.parse()
+                    .map_err(|_| AppError::DataConsistency(format!("Unknown attempt channel '{}'", record.channel)))?,
+                attempted_at: record.attempted_at,
+                success: record.success,
+                error_message: record.error_message,
+                response_details: record.response_details,
+            }))
+            .collect()
+    }
+
+    async fn requeue(&self, message: &OutboxMessage) -> AppResult<bool> {
+        let mut conn = self.get_connection()?;
+        conn.transaction::<_, AppError, _>(|conn| {
+            let updated = diesel::update(
+                notification_outbox::table
+                    .filter(notification_outbox::id.eq(message.id))
+                    .filter(notification_outbox::status.eq(OutboxStatus::DeadLettered.as_str())),
+            )
+                .set(&Self::state_changeset(message))
+                .execute(conn)?;
+
+            if updated > 0 {
+                diesel::update(notifications::table.filter(notifications::id.eq(message.id)))
+                    .set(notifications::status.eq(Some("pending".to_string())))
+                    .execute(conn)?;
+            }
+            Ok(updated > 0)
+        })
+    }
+}
@@ -0,0 +1,298 @@
+/// Organization repository implementation
+/// Organizations and their scoped memberships in `organizations` and `organization_members`
+
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, TimeZone, Utc};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::domain::entities::organization::{Organization, OrganizationMembership, OrganizationRole};
+use crate::domain::ports::repositories::OrganizationRepository;
+use crate::infrastructure::database::schemas::{organization_members, organizations};
+use crate::infrastructure::database::{DbConnection, DbPool};
+use crate::shared::error::DatabaseError;
+use crate::shared::{AppError, AppResult, LocationId, OrganizationId, UserId};
+
+#[derive(Queryable, Selectable, Insertable, AsChangeset, Debug, Clone)]
+#[diesel(table_name = organizations)]
+#[diesel(treat_none_as_null = true)]
+struct OrganizationRecord {
+    id: Uuid,
+    name: String,
+    description: Option<String>,
+    logo_url: Option<String>,
+    website: Option<String>,
+    phone: Option<String>,
+    email: Option<String>,
+    address: Option<String>,
+    location_id: Option<Uuid>,
+    created_at: Option<NaiveDateTime>,
+    updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(Queryable, Selectable, Insertable, Debug, Clone)]
+#[diesel(table_name = organization_members)]
+struct MemberRecord {
+    organization_id: Uuid,
+    user_id: Uuid,
+    role: String,
+    created_at: Option<NaiveDateTime>,
+    updated_at: Option<NaiveDateTime>,
+}
+
+pub struct PostgresOrganizationRepository {
+    pool: DbPool,
+}
+
+impl PostgresOrganizationRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    fn get_connection(&self) -> AppResult<DbConnection> {
+        self.pool.get()
+            .map_err(|e| AppError::Database(DatabaseError::ConnectionPool(e)))
+    }
+
+    fn to_record(entity: &Organization) -> OrganizationRecord {
+        OrganizationRecord {
+            id: entity.id.0,
+            name: entity.name.clone(),
+            description: entity.description.clone(),
+            logo_url: entity.logo_url.clone(),
+            website: entity.website.clone(),
+            phone: entity.phone.clone(),
+            email: entity.email.clone(),
+            address: entity.address.clone(),
+            location_id: entity.location_id.map(|id| id.0),
+            created_at: Some(entity.created_at.naive_utc()),
+            updated_at: Some(entity.updated_at.naive_utc()),
+        }
+    }
+
+    fn to_domain(record: OrganizationRecord) -> Organization {
+        let created_at = record.created_at.map(|t| Utc.from_utc_datetime(&t)).unwrap_or_else(Utc::now);
+        Organization {
+            id: OrganizationId(record.id),
+            name: record.name,
+            description: record.description,
+            logo_url: record.logo_url,
+            website: record.website,
+            phone: record.phone,
+            email: record.email,
+            address: record.address,
+            location_id: record.location_id.map(LocationId),
+            created_at,
+            updated_at: record.updated_at.map(|t| Utc.from_utc_datetime(&t)).unwrap_or(created_at),
+        }
+    }
+
+    fn member_to_record(membership: &OrganizationMembership) -> MemberRecord {
+        MemberRecord {
+            organization_id: membership.organization_id.0,
+            user_id: membership.user_id.0,
+            role: membership.role.as_str().to_string(),
+            created_at: Some(membership.created_at.naive_utc()),
+            updated_at: Some(membership.updated_at.naive_utc()),
+        }
+    }
+
+    fn member_to_domain(record: MemberRecord) -> AppResult<OrganizationMembership> {
+        let role: OrganizationRole = record.role.parse()
+            .map_err(|_| AppError::DataConsistency(format!(
+                "Member {} of organization {} has unknown role '{}'",
+                record.user_id, record.organization_id, record.role
+            )))?;
+        let created_at = record.created_at.map(|t| Utc.from_utc_datetime(&t)).unwrap_or_else(Utc::now);
+
+        Ok(OrganizationMembership {
+            organization_id: OrganizationId(record.organization_id),
+            user_id: UserId(record.user_id),
+            role,
+            created_at,
+            updated_at: record.updated_at.map(|t| Utc.from_utc_datetime(&t)).unwrap_or(created_at),
+        })
+    }
+
+    /// Lock the organization row so concurrent role changes cannot both remove the last admin
+    fn lock_organization(conn: &mut DbConnection, organization_id: Uuid) -> AppResult<()> {
+        organizations::table
+            .filter(organizations::id.eq(organization_id))
+            .select(organizations::id)
+            .for_update()
+            .first::<Uuid>(conn)
+            .optional()?
+            .ok_or_else(|| AppError::NotFound(format!("Organization {} not found", organization_id)))?;
+        Ok(())
+    }
+
+    /// Fails when `user_id` is the only admin left in the organization
+    fn ensure_other_admin(conn: &mut DbConnection, organization_id: Uuid, user_id: Uuid) -> AppResult<()> {
+        let current_role: Option<String> = organization_members::table
+            .filter(organization_members::organization_id.eq(organization_id))
+            .filter(organization_members::user_id.eq(user_id))
+            .select(organization_members::role)
+            .first(conn)
+            .optional()?;
+        if current_role.as_deref() != Some(OrganizationRole::Admin.as_str()) {
+            return Ok(());
+        }
+
+        let admins: i64 = organization_members::table
+            .filter(organization_members::organization_id.eq(organization_id))
+            .filter(organization_members::role.eq(OrganizationRole::Admin.as_str()))
+            .count()
+            .get_result(conn)?;
+        if admins <= 1 {
+            return Err(AppError::Conflict("An organization must keep at least one admin".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl OrganizationRepository for PostgresOrganizationRepository {
+    async fn create(&self, organization: &Organization, founder: &UserId) -> AppResult<Organization> {
+        let mut conn = self.get_connection()?;
+        let founder_membership = OrganizationMembership::new(organization.id, *founder, OrganizationRole::Admin);
+
+        let created = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            diesel::insert_into(organizations::table)
+                .values(&Self::to_record(organization))
+                .execute(conn)?;
+            diesel::insert_into(organization_members::table)
+                .values(&Self::member_to_record(&founder_membership))
+                .execute(conn)?;
+            Ok(())
+        });
+
+        match created {
+            Ok(()) => Ok(organization.clone()),
+            Err(diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::UniqueViolation, _)) => {
+                Err(AppError::Conflict(format!("Organization {} already exists", organization.id)))
+            }
+            Err(diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::ForeignKeyViolation, _)) => {
+                Err(AppError::NotFound("Founder or location of the organization not found".to_string()))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn find_by_id(&self, id: &OrganizationId) -> AppResult<Option<Organization>> {
+        let mut conn = self.get_connection()?;
+        let record: Option<OrganizationRecord> = organizations::table
+            .filter(organizations::id.eq(id.0))
+            .select(OrganizationRecord::as_select())
+            .first(&mut conn)
+            .optional()?;
+        Ok(record.map(Self::to_domain))
+    }
+
+    async fn find_all(&self) -> AppResult<Vec<Organization>> {
+        let mut conn = self.get_connection()?;
+        let records = organizations::table
+            .order(organizations::name.asc())
+            .select(OrganizationRecord::as_select())
+            .load(&mut conn)?;
+        Ok(records.into_iter().map(Self::to_domain).collect())
+    }
+
+    async fn update(&self, organization: &Organization) -> AppResult<Organization> {
+        let mut conn = self.get_connection()?;
+        let updated = diesel::update(organizations::table.filter(organizations::id.eq(organization.id.0)))
+            .set(&Self::to_record(organization))
+            .execute(&mut conn)?;
+
+        if updated == 0 {
+            return Err(AppError::NotFound(format!("Organization {} not found", organization.id)));
+        }
+        Ok(organization.clone())
+    }
+
+    async fn delete(&self, id: &OrganizationId) -> AppResult<bool> {
+        let mut conn = self.get_connection()?;
+        // Memberships cascade; resources stay in the inventory without an owner
+        let deleted = diesel::delete(organizations::table.filter(organizations::id.eq(id.0)))
+            .execute(&mut conn)?;
+        Ok(deleted > 0)
+    }
+
+    async fn find_membership(&self, organization_id: &OrganizationId, user_id: &UserId) -> AppResult<Option<OrganizationMembership>> {
+        let mut conn = self.get_connection()?;
+        let record: Option<MemberRecord> = organization_members::table
+            .filter(organization_members::organization_id.eq(organization_id.0))
+            .filter(organization_members::user_id.eq(user_id.0))
+            .select(MemberRecord::as_select())
+            .first(&mut conn)
+            .optional()?;
+        record.map(Self::member_to_domain).transpose()
+    }
+
+    async fn find_memberships_by_user(&self, user_id: &UserId) -> AppResult<Vec<OrganizationMembership>> {
+        let mut conn = self.get_connection()?;
+        let records = organization_members::table
+            .filter(organization_members::user_id.eq(user_id.0))
+            .order(organization_members::created_at.asc())
+            .select(MemberRecord::as_select())
+            .load(&mut conn)?;
+        records.into_iter().map(Self::member_to_domain).collect()
+    }
+
+    async fn find_members(&self, organization_id: &OrganizationId) -> AppResult<Vec<OrganizationMembership>> {
+        let mut conn = self.get_connection()?;
+        let records = organization_members::table
+            .filter(organization_members::organization_id.eq(organization_id.0))
+            .order(organization_members::created_at.asc())
+            .select(MemberRecord::as_select())
+            .load(&mut conn)?;
+        records.into_iter().map(Self::member_to_domain).collect()
+    }
+
+    async fn save_membership(&self, membership: &OrganizationMembership) -> AppResult<OrganizationMembership> {
+        let mut conn = self.get_connection()?;
+        let record = Self::member_to_record(membership);
+
+        let saved = conn.transaction::<_, AppError, _>(|conn| {
+            Self::lock_organization(conn, record.organization_id)?;
+            if membership.role != OrganizationRole::Admin {
+                Self::ensure_other_admin(conn, record.organization_id, record.user_id)?;
+            }
+
+            let saved: MemberRecord = diesel::insert_into(organization_members::table)
+                .values(&record)
+                .on_conflict((organization_members::organization_id, organization_members::user_id))
+                .do_update()
+                .set((
+                    organization_members::role.eq(&record.role),
+                    organization_members::updated_at.eq(record.updated_at),
+                ))
+                .returning(MemberRecord::as_returning())
+                .get_result(conn)
+                .map_err(|e| match e {
+                    diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::ForeignKeyViolation, _) => {
+                        AppError::NotFound(format!("User {} not found", membership.user_id))
+                    }
+                    other => other.into(),
+                })?;
+            Ok(saved)
+        })?;
+
+        Self::member_to_domain(saved)
+    }
+
+    async fn remove_member(&self, organization_id: &OrganizationId, user_id: &UserId) -> AppResult<bool> {
+        let mut conn = self.get_connection()?;
+        conn.transaction::<_, AppError, _>(|conn| {
+            Self::lock_organization(conn, organization_id.0)?;
+            Self::ensure_other_admin(conn, organization_id.0, user_id.0)?;
+
+            let deleted = diesel::delete(
+                organization_members::table
+                    .filter(organization_members::organization_id.eq(organization_id.0))
+                    .filter(organization_members::user_id.eq(user_id.0)),
+            )
+                .execute(conn)?;
+            Ok(deleted > 0)
+        })
+    }
+}
@@ -0,0 +1,80 @@
+/// Province assignment repository implementation
+/// Provinces each user is assigned to, in `user_province_assignments`
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::domain::ports::repositories::ProvinceAssignmentRepository;
+use crate::infrastructure::database::schemas::user_province_assignments;
+use crate::infrastructure::database::{DbConnection, DbPool};
+use crate::shared::error::DatabaseError;
+use crate::shared::{AppError, AppResult, UserId};
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = user_province_assignments)]
+struct NewProvinceAssignment<'a> {
+    user_id: Uuid,
+    province: &'a str,
+    assigned_by: Option<Uuid>,
+    created_at: DateTime<Utc>,
+}
+
+pub struct PostgresProvinceAssignmentRepository {
+    pool: DbPool,
+}
+
+impl PostgresProvinceAssignmentRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    fn get_connection(&self) -> AppResult<DbConnection> {
+        self.pool.get()
+            .map_err(|e| AppError::Database(DatabaseError::ConnectionPool(e)))
+    }
+}
+
+#[async_trait]
+impl ProvinceAssignmentRepository for PostgresProvinceAssignmentRepository {
+    async fn find_by_user(&self, user_id: &UserId) -> AppResult<Vec<String>> {
+        let mut conn = self.get_connection()?;
+        let provinces = user_province_assignments::table
+            .filter(user_province_assignments::user_id.eq(user_id.0))
+            .order(user_province_assignments::province.asc())
+            .select(user_province_assignments::province)
+            .load(&mut conn)?;
+        Ok(provinces)
+    }
+
+    async fn replace(&self, user_id: &UserId, provinces: &[String], assigned_by: &UserId) -> AppResult<()> {
+        let mut conn = self.get_connection()?;
+        let now = Utc::now();
+        let rows: Vec<NewProvinceAssignment> = provinces.iter()
+            .map(|province| NewProvinceAssignment {
+                user_id: user_id.0,
+                province,
+                assigned_by: Some(assigned_by.0),
+                created_at: now,
+            })
+            .collect();
+
+        let replaced = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            diesel::delete(user_province_assignments::table.filter(user_province_assignments::user_id.eq(user_id.0)))
+                .execute(conn)?;
+            diesel::insert_into(user_province_assignments::table)
+                .values(&rows)
+                .on_conflict_do_nothing()
+                .execute(conn)?;
+            Ok(())
+        });
+
+        match replaced {
+            Err(diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::ForeignKeyViolation, _)) => {
+                Err(AppError::NotFound(format!("User {} not found", user_id)))
+            }
+            other => Ok(other?),
+        }
+    }
+}
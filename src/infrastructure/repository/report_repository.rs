@@ -0,0 +1,808 @@
+/// Report repository implementation
+/// Maps the Report aggregate onto `reports` and its location, with media in `report_media`,
+/// comments in `report_comments`, status history in `report_history` and disaster links in `disaster_reports`
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use diesel::prelude::*;
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use diesel::sql_types::{Array, Float8, Int4, Int8, Jsonb, Nullable, Text, Timestamp, Uuid as SqlUuid};
+use uuid::Uuid;
+
+use crate::domain::entities::report::{
+    CasualtyEstimate, MediaKind, Report, ReportComment, ReportDetails, ReportMedia, ReportSource,
+    ReportStatus, ReportStatusChange, ReportValidation,
+};
+use crate::domain::ports::repositories::ReportRepository;
+use crate::domain::services::credibility::{NearbyReport, ReporterHistory, TriageCluster};
+use crate::domain::value_objects::Coordinates;
+use crate::infrastructure::database::schemas::{disaster_reports, report_comments, report_history, report_media, reports};
+use crate::infrastructure::database::{DbConnection, DbPool};
+use crate::infrastructure::repository::disaster_repository::PostgresDisasterRepository;
+use crate::shared::error::DatabaseError;
+use crate::shared::{AppError, AppResult, DisasterId, ReportId, UserId};
+
+/// Region recorded for locations created implicitly from a report's coordinates
+const UNASSIGNED_REGION: &str = "unassigned";
+
+const SELECT_REPORTS: &str = r#"
+SELECT r.id, r.reporter_id, r.anonymous_name, r.anonymous_phone, r.disaster_type_id, r.title, r.description,
+       ST_Y(l.geometry::geometry) AS latitude, ST_X(l.geometry::geometry) AS longitude,
+       r.address, r.impact_radius, r.estimated_severity, r.casualties, r.injuries, r.missing, r.affected_people,
+       r.status, r.credibility_score, r.validated_by, r.validation_notes, r.validation_date,
+       r.created_at, r.updated_at, r.credibility_factors, r.cluster_id
+FROM reports r
+LEFT JOIN locations l ON l.id = r.location_id
+"#;
+
+/// Row returned by `SELECT_REPORTS`
+#[derive(QueryableByName, Debug)]
+struct ReportRow {
+    #[diesel(sql_type = SqlUuid)]
+    id: Uuid,
+    #[diesel(sql_type = Nullable<SqlUuid>)]
+    reporter_id: Option<Uuid>,
+    #[diesel(sql_type = Nullable<Text>)]
+    anonymous_name: Option<String>,
+    #[diesel(sql_type = Nullable<Text>)]
+    anonymous_phone: Option<String>,
+    #[diesel(sql_type = Nullable<Int4>)]
+    disaster_type_id: Option<i32>,
+    #[diesel(sql_type = Text)]
+    title: String,
+    #[diesel(sql_type = Nullable<Text>)]
+    description: Option<String>,
+    #[diesel(sql_type = Nullable<Float8>)]
+    latitude: Option<f64>,
+    #[diesel(sql_type = Nullable<Float8>)]
+    longitude: Option<f64>,
+    #[diesel(sql_type = Nullable<Text>)]
+    address: Option<String>,
+    #[diesel(sql_type = Nullable<Float8>)]
+    impact_radius: Option<f64>,
+    #[diesel(sql_type = Nullable<Int4>)]
+    estimated_severity: Option<i32>,
+    #[diesel(sql_type = Nullable<Int4>)]
+    casualties: Option<i32>,
+    #[diesel(sql_type = Nullable<Int4>)]
+    injuries: Option<i32>,
+    #[diesel(sql_type = Nullable<Int4>)]
+    missing: Option<i32>,
+    #[diesel(sql_type = Nullable<Int4>)]
+    affected_people: Option<i32>,
+    #[diesel(sql_type = Nullable<Text>)]
+    status: Option<String>,
+    #[diesel(sql_type = Nullable<Float8>)]
+    credibility_score: Option<f64>,
+    #[diesel(sql_type = Nullable<SqlUuid>)]
+    validated_by: Option<Uuid>,
+    #[diesel(sql_type = Nullable<Text>)]
+    validation_notes: Option<String>,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    validation_date: Option<NaiveDateTime>,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    created_at: Option<NaiveDateTime>,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    updated_at: Option<NaiveDateTime>,
+    #[diesel(sql_type = Nullable<Jsonb>)]
+    credibility_factors: Option<serde_json::Value>,
+    #[diesel(sql_type = Nullable<SqlUuid>)]
+    cluster_id: Option<Uuid>,
+}
+
+/// Row of the nearby-report search
+#[derive(QueryableByName, Debug)]
+struct NearbyRow {
+    #[diesel(sql_type = SqlUuid)]
+    id: Uuid,
+    #[diesel(sql_type = Nullable<SqlUuid>)]
+    reporter_id: Option<Uuid>,
+    #[diesel(sql_type = Nullable<Int4>)]
+    disaster_type_id: Option<i32>,
+    #[diesel(sql_type = Nullable<Text>)]
+    status: Option<String>,
+    #[diesel(sql_type = Nullable<SqlUuid>)]
+    cluster_id: Option<Uuid>,
+    #[diesel(sql_type = Float8)]
+    distance_m: f64,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    created_at: Option<NaiveDateTime>,
+}
+
+#[derive(QueryableByName, Debug)]
+struct HistoryCountsRow {
+    #[diesel(sql_type = Int8)]
+    confirmed: i64,
+    #[diesel(sql_type = Int8)]
+    rejected: i64,
+}
+
+/// Row of the triage queue: one incident cluster with at least one report awaiting review
+#[derive(QueryableByName, Debug)]
+struct TriageRow {
+    #[diesel(sql_type = SqlUuid)]
+    cluster_id: Uuid,
+    #[diesel(sql_type = SqlUuid)]
+    lead_id: Uuid,
+    #[diesel(sql_type = Int8)]
+    report_count: i64,
+    #[diesel(sql_type = Int8)]
+    pending_count: i64,
+    #[diesel(sql_type = Int8)]
+    confirmed_count: i64,
+    #[diesel(sql_type = Nullable<Float8>)]
+    top_score: Option<f64>,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    first_reported_at: Option<NaiveDateTime>,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    last_reported_at: Option<NaiveDateTime>,
+}
+
+/// Columns of `reports` written from the reported details
+#[derive(Insertable, AsChangeset, Debug)]
+#[diesel(table_name = reports)]
+#[diesel(treat_none_as_null = true)]
+struct ReportDetailsRecord {
+    disaster_type_id: Option<i32>,
+    title: String,
+    description: Option<String>,
+    address: Option<String>,
+    impact_radius: Option<f64>,
+    estimated_severity: Option<i32>,
+    casualties: Option<i32>,
+    injuries: Option<i32>,
+    missing: Option<i32>,
+    affected_people: Option<i32>,
+    updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(Queryable, Selectable, Insertable, Debug)]
+#[diesel(table_name = report_history)]
+struct HistoryRecord {
+    id: Uuid,
+    report_id: Option<Uuid>,
+    changed_by: Option<Uuid>,
+    status_from: Option<String>,
+    status_to: Option<String>,
+    notes: Option<String>,
+    created_at: Option<NaiveDateTime>,
+}
+
+#[derive(Queryable, Selectable, Insertable, Debug)]
+#[diesel(table_name = report_media)]
+struct MediaRecord {
+    id: Uuid,
+    report_id: Option<Uuid>,
+    media_type: String,
+    media_url: String,
+    caption: Option<String>,
+    is_primary: Option<bool>,
+    created_at: Option<NaiveDateTime>,
+    storage_key: Option<String>,
+    content_type: Option<String>,
+    size_bytes: Option<i64>,
+    uploaded_by: Option<Uuid>,
+}
+
+#[derive(Queryable, Selectable, Insertable, Debug)]
+#[diesel(table_name = report_comments)]
+struct CommentRecord {
+    id: Uuid,
+    report_id: Option<Uuid>,
+    user_id: Option<Uuid>,
+    content: String,
+    parent_id: Option<Uuid>,
+    created_at: Option<NaiveDateTime>,
+    updated_at: Option<NaiveDateTime>,
+}
+
+pub struct PostgresReportRepository {
+    pool: DbPool,
+}
+
+impl PostgresReportRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    fn get_connection(&self) -> AppResult<DbConnection> {
+        self.pool.get()
+            .map_err(|e| AppError::Database(DatabaseError::ConnectionPool(e)))
+    }
+
+    fn utc(dt: Option<NaiveDateTime>) -> Option<DateTime<Utc>> {
+        dt.map(|d| d.and_utc())
+    }
+
+    fn count(value: Option<i32>) -> Option<u32> {
+        value.map(|v| v.max(0) as u32)
+    }
+
+    /// Reports have no free-text type column, so `Other` types are stored without an id
+    fn details_record(details: &ReportDetails, updated_at: DateTime<Utc>) -> ReportDetailsRecord {
+        let (disaster_type_id, _) = PostgresDisasterRepository::type_to_columns(&details.disaster_type);
+        let casualties = &details.casualties;
+        ReportDetailsRecord {
+            disaster_type_id,
+            title: details.title.clone(),
+            description: details.description.clone(),
+            address: details.address.clone(),
+            impact_radius: details.impact_radius_m,
+            estimated_severity: Some(details.estimated_severity as i32),
+            casualties: casualties.casualties.map(|c| c as i32),
+            injuries: casualties.injuries.map(|c| c as i32),
+            missing: casualties.missing.map(|c| c as i32),
+            affected_people: casualties.affected_people.map(|c| c as i32),
+            updated_at: Some(updated_at.naive_utc()),
+        }
+    }
+
+    fn to_domain(row: ReportRow) -> AppResult<Report> {
+        let location = Coordinates::new(row.latitude.unwrap_or(0.0), row.longitude.unwrap_or(0.0))
+            .map_err(|e| AppError::DataConsistency(format!("Report {} has invalid location: {}", row.id, e)))?;
+        let status = match row.status.as_deref() {
+            Some(status) => status.parse()?,
+            None => ReportStatus::Reported,
+        };
+        let source = match row.reporter_id {
+            Some(user_id) => ReportSource::User { user_id: UserId(user_id) },
+            None => ReportSource::Anonymous { name: row.anonymous_name, phone: row.anonymous_phone },
+        };
+        let validation = row.validated_by.map(|validated_by| ReportValidation {
+            validated_by: UserId(validated_by),
+            notes: row.validation_notes,
+            validated_at: Self::utc(row.validation_date).unwrap_or_else(Utc::now),
+        });
+        let created_at = Self::utc(row.created_at).unwrap_or_else(Utc::now);
+
+        Ok(Report {
+            id: ReportId(row.id),
+            source,
+            details: ReportDetails {
+                disaster_type: PostgresDisasterRepository::columns_to_type(row.disaster_type_id, None),
+                title: row.title,
+                description: row.description,
+                location,
+                address: row.address,
+                impact_radius_m: row.impact_radius,
+                estimated_severity: row.estimated_severity.unwrap_or(1).clamp(1, 5) as u8,
+                casualties: CasualtyEstimate {
+                    casualties: Self::count(row.casualties),
+                    injuries: Self::count(row.injuries),
+                    missing: Self::count(row.missing),
+                    affected_people: Self::count(row.affected_people),
+                },
+            },
+            status,
+            credibility_score: row.credibility_score.unwrap_or(0.0),
+            credibility: row.credibility_factors.and_then(|factors| serde_json::from_value(factors).ok()),
+            cluster_id: row.cluster_id,
+            validation,
+            created_at,
+            updated_at: Self::utc(row.updated_at).unwrap_or(created_at),
+        })
+    }
+
+    fn rows_to_domain(rows: Vec<ReportRow>) -> Vec<Report> {
+        rows.into_iter()
+            .filter_map(|row| {
+                let id = row.id;
+                Self::to_domain(row)
+                    .map_err(|e| tracing::warn!("Skipping unreadable report {}: {}", id, e))
+                    .ok()
+            })
+            .collect()
+    }
+
+    fn load_one(conn: &mut DbConnection, id: Uuid) -> AppResult<Option<Report>> {
+        let row = diesel::sql_query(format!("{} WHERE r.id = $1", SELECT_REPORTS))
+            .bind::<SqlUuid, _>(id)
+            .get_result::<ReportRow>(conn)
+            .optional()?;
+        row.map(Self::to_domain).transpose()
+    }
+
+    fn history_record(change: &ReportStatusChange) -> HistoryRecord {
+        HistoryRecord {
+            id: change.id,
+            report_id: Some(change.report_id.0),
+            changed_by: change.changed_by.map(|u| u.0),
+            status_from: change.status_from.map(|s| s.as_str().to_string()),
+            status_to: Some(change.status_to.as_str().to_string()),
+            notes: change.notes.clone(),
+            created_at: Some(change.created_at.naive_utc()),
+        }
+    }
+
+    fn history_to_domain(record: HistoryRecord) -> Option<ReportStatusChange> {
+        let status_to = record.status_to.as_deref()?.parse().ok()?;
+        Some(ReportStatusChange {
+            id: record.id,
+            report_id: ReportId(record.report_id?),
+            changed_by: record.changed_by.map(UserId),
+            status_from: record.status_from.as_deref().and_then(|s| s.parse().ok()),
+            status_to,
+            notes: record.notes,
+            created_at: Self::utc(record.created_at).unwrap_or_else(Utc::now),
+        })
+    }
+
+    fn media_to_domain(record: MediaRecord) -> Option<ReportMedia> {
+        Some(ReportMedia {
+            id: record.id,
+            report_id: ReportId(record.report_id?),
+            kind: MediaKind::from_column(&record.media_type),
+            url: record.media_url,
+            storage_key: record.storage_key,
+            content_type: record.content_type,
+            size_bytes: record.size_bytes,
+            caption: record.caption,
+            is_primary: record.is_primary.unwrap_or(false),
+            uploaded_by: record.uploaded_by.map(UserId),
+            created_at: Self::utc(record.created_at).unwrap_or_else(Utc::now),
+        })
+    }
+
+    fn comment_to_domain(record: CommentRecord) -> Option<ReportComment> {
+        let created_at = Self::utc(record.created_at).unwrap_or_else(Utc::now);
+        Some(ReportComment {
+            id: record.id,
+            report_id: ReportId(record.report_id?),
+            author_id: record.user_id.map(UserId),
+            content: record.content,
+            parent_id: record.parent_id,
+            created_at,
+            updated_at: Self::utc(record.updated_at).unwrap_or(created_at),
+        })
+    }
+
+    /// Reports of unknown type or status are skipped rather than failing the search
+    fn nearby_to_domain(row: NearbyRow) -> Option<NearbyReport> {
+        Some(NearbyReport {
+            id: ReportId(row.id),
+            reporter_id: row.reporter_id.map(UserId),
+            disaster_type: PostgresDisasterRepository::columns_to_type(row.disaster_type_id, None),
+            status: row.status.as_deref().unwrap_or("reported").parse().ok()?,
+            cluster_id: row.cluster_id,
+            distance_m: row.distance_m,
+            created_at: Self::utc(row.created_at)?,
+        })
+    }
+
+    fn report_not_found(id: &ReportId) -> AppError {
+        AppError::NotFound(format!("Report {} not found", id))
+    }
+}
+
+#[async_trait]
+impl ReportRepository for PostgresReportRepository {
+    async fn create(&self, report: &Report, submitted: &ReportStatusChange) -> AppResult<Report> {
+        let mut conn = self.get_connection()?;
+        let details = Self::details_record(&report.details, report.updated_at);
+        let location_id = Uuid::new_v4();
+        let (anonymous_name, anonymous_phone) = match &report.source {
+            ReportSource::Anonymous { name, phone } => (name.clone(), phone.clone()),
+            ReportSource::User { .. } => (None, None),
+        };
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            diesel::sql_query(
+                "INSERT INTO locations (id, name, address, region, geometry) \
+                 VALUES ($1, $2, $3, $4, ST_SetSRID(ST_MakePoint($5, $6), 4326)::geography)"
+            )
+                .bind::<SqlUuid, _>(location_id)
+                .bind::<Text, _>(&report.details.title)
+                .bind::<Nullable<Text>, _>(report.details.address.as_deref())
+                .bind::<Text, _>(UNASSIGNED_REGION)
+                .bind::<Float8, _>(report.details.location.longitude)
+                .bind::<Float8, _>(report.details.location.latitude)
+                .execute(conn)?;
+
+            let inserted = diesel::insert_into(reports::table)
+                .values((
+                    reports::id.eq(report.id.0),
+                    reports::reporter_id.eq(report.reporter_id().map(|u| u.0)),
+                    reports::anonymous_name.eq(anonymous_name),
+                    reports::anonymous_phone.eq(anonymous_phone),
+                    reports::is_anonymous.eq(Some(report.is_anonymous())),
+                    reports::location_id.eq(Some(location_id)),
+                    reports::status.eq(Some(report.status.as_str())),
+                    reports::credibility_score.eq(Some(report.credibility_score)),
+                    reports::cluster_id.eq(report.cluster_id),
+                    reports::created_at.eq(Some(report.created_at.naive_utc())),
+                    &details,
+                ))
+                .execute(conn);
+            match inserted {
+                Err(DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => {
+                    return Err(AppError::Conflict(format!("Report {} already exists", report.id)));
+                }
+                other => { other?; }
+            }
+
+            diesel::insert_into(report_history::table)
+                .values(&Self::history_record(submitted))
+                .execute(conn)?;
+            Ok(())
+        })?;
+
+        Ok(report.clone())
+    }
+
+    async fn find_by_id(&self, id: &ReportId) -> AppResult<Option<Report>> {
+        let mut conn = self.get_connection()?;
+        Self::load_one(&mut conn, id.0)
+    }
+
+    async fn find_all(&self, status: Option<ReportStatus>, reporter_id: Option<&UserId>, limit: i64, offset: i64) -> AppResult<Vec<Report>> {
+        let mut conn = self.get_connection()?;
+        let rows = diesel::sql_query(format!(
+            "{} WHERE ($1::TEXT IS NULL OR r.status = $1) AND ($2::UUID IS NULL OR r.reporter_id = $2) \
+             ORDER BY r.created_at DESC LIMIT $3 OFFSET $4",
+            SELECT_REPORTS
+        ))
+            .bind::<Nullable<Text>, _>(status.map(|s| s.as_str()))
+            .bind::<Nullable<SqlUuid>, _>(reporter_id.map(|u| u.0))
+            .bind::<Int8, _>(limit)
+            .bind::<Int8, _>(offset)
+            .load::<ReportRow>(&mut conn)?;
+        Ok(Self::rows_to_domain(rows))
+    }
+
+    async fn update_details(&self, report: &Report) -> AppResult<Report> {
+        let mut conn = self.get_connection()?;
+        let details = Self::details_record(&report.details, report.updated_at);
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let updated = diesel::update(reports::table.filter(reports::id.eq(report.id.0)))
+                .set(&details)
+                .execute(conn)?;
+            if updated == 0 {
+                return Err(Self::report_not_found(&report.id));
+            }
+
+            diesel::sql_query(
+                "UPDATE locations SET geometry = ST_SetSRID(ST_MakePoint($1, $2), 4326)::geography, address = $3 \
+                 WHERE id = (SELECT location_id FROM reports WHERE id = $4)"
+            )
+                .bind::<Float8, _>(report.details.location.longitude)
+                .bind::<Float8, _>(report.details.location.latitude)
+                .bind::<Nullable<Text>, _>(report.details.address.as_deref())
+                .bind::<SqlUuid, _>(report.id.0)
+                .execute(conn)?;
+            Ok(())
+        })?;
+
+        Ok(report.clone())
+    }
+
+    async fn change_status(&self, report: &Report, change: &ReportStatusChange) -> AppResult<Report> {
+        let mut conn = self.get_connection()?;
+        let validation = report.validation.as_ref();
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            // Only apply on top of the status the change was decided from
+            let updated = diesel::update(
+                reports::table
+                    .filter(reports::id.eq(report.id.0))
+                    .filter(reports::status.eq(change.status_from.map(|s| s.as_str()))),
+            )
+                .set((
+                    reports::status.eq(Some(report.status.as_str())),
+                    reports::validated_by.eq(validation.map(|v| v.validated_by.0)),
+                    reports::validation_notes.eq(validation.and_then(|v| v.notes.clone())),
+                    reports::validation_date.eq(validation.map(|v| v.validated_at.naive_utc())),
+                    reports::updated_at.eq(Some(report.updated_at.naive_utc())),
+                ))
+                .execute(conn)?;
+
+            if updated == 0 {
+                let current: Option<Option<String>> = reports::table
+                    .filter(reports::id.eq(report.id.0))
+                    .select(reports::status)
+                    .first(conn)
+                    .optional()?;
+                return Err(match current {
+                    Some(current) => AppError::Conflict(format!(
+                        "Report {} changed concurrently (now {})",
+                        report.id,
+                        current.unwrap_or_default()
+                    )),
+                    None => Self::report_not_found(&report.id),
+                });
+            }
+
+            diesel::insert_into(report_history::table)
+                .values(&Self::history_record(change))
+                .execute(conn)?;
+            Ok(())
+        })?;
+
+        Ok(report.clone())
+    }
+
+    async fn find_history(&self, id: &ReportId) -> AppResult<Vec<ReportStatusChange>> {
+        let mut conn = self.get_connection()?;
+        let records = report_history::table
+            .filter(report_history::report_id.eq(id.0))
+            .order(report_history::created_at.asc())
+            .select(HistoryRecord::as_select())
+            .load(&mut conn)?;
+        Ok(records.into_iter().filter_map(Self::history_to_domain).collect())
+    }
+
+    async fn add_media(&self, media: &ReportMedia) -> AppResult<ReportMedia> {
+        let mut conn = self.get_connection()?;
+        let record = MediaRecord {
+            id: media.id,
+            report_id: Some(media.report_id.0),
+            media_type: media.kind.as_str().to_string(),
+            media_url: media.url.clone(),
+            caption: media.caption.clone(),
+            is_primary: Some(media.is_primary),
+            created_at: Some(media.created_at.naive_utc()),
+            storage_key: media.storage_key.clone(),
+            content_type: media.content_type.clone(),
+            size_bytes: media.size_bytes,
+            uploaded_by: media.uploaded_by.map(|u| u.0),
+        };
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            // A report has at most one primary photo or video
+            if media.is_primary {
+                diesel::update(report_media::table.filter(report_media::report_id.eq(media.report_id.0)))
+                    .set(report_media::is_primary.eq(Some(false)))
+                    .execute(conn)?;
+            }
+            match diesel::insert_into(report_media::table).values(&record).execute(conn) {
+                Err(DieselError::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _)) => {
+                    Err(Self::report_not_found(&media.report_id))
+                }
+                other => other.map(|_| ()).map_err(AppError::from),
+            }
+        })?;
+
+        Ok(media.clone())
+    }
+
+    async fn find_media(&self, id: &ReportId) -> AppResult<Vec<ReportMedia>> {
+        let mut conn = self.get_connection()?;
+        let records = report_media::table
+            .filter(report_media::report_id.eq(id.0))
+            .order((report_media::is_primary.desc(), report_media::created_at.asc()))
+            .select(MediaRecord::as_select())
+            .load(&mut conn)?;
+        Ok(records.into_iter().filter_map(Self::media_to_domain).collect())
+    }
+
+    async fn add_comment(&self, comment: &ReportComment) -> AppResult<ReportComment> {
+        let mut conn = self.get_connection()?;
+        let record = CommentRecord {
+            id: comment.id,
+            report_id: Some(comment.report_id.0),
+            user_id: comment.author_id.map(|u| u.0),
+            content: comment.content.clone(),
+            parent_id: comment.parent_id,
+            created_at: Some(comment.created_at.naive_utc()),
+            updated_at: Some(comment.updated_at.naive_utc()),
+        };
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            if let Some(parent_id) = comment.parent_id {
+                let parent_report: Option<Option<Uuid>> = report_comments::table
+                    .filter(report_comments::id.eq(parent_id))
+                    .select(report_comments::report_id)
+                    .first(conn)
+                    .optional()?;
+                if parent_report.flatten() != Some(comment.report_id.0) {
+                    return Err(AppError::Validation(format!(
+                        "Comment {} does not belong to report {}",
+                        parent_id, comment.report_id
+                    )));
+                }
+            }
+            match diesel::insert_into(report_comments::table).values(&record).execute(conn) {
+                Err(DieselError::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _)) => {
+                    Err(Self::report_not_found(&comment.report_id))
+                }
+                other => other.map(|_| ()).map_err(AppError::from),
+            }
+        })?;
+
+        Ok(comment.clone())
+    }
+
+    async fn find_comments(&self, id: &ReportId) -> AppResult<Vec<ReportComment>> {
+        let mut conn = self.get_connection()?;
+        let records = report_comments::table
+            .filter(report_comments::report_id.eq(id.0))
+            .order(report_comments::created_at.asc())
+            .select(CommentRecord::as_select())
+            .load(&mut conn)?;
+        Ok(records.into_iter().filter_map(Self::comment_to_domain).collect())
+    }
+
+    async fn link_to_disaster(&self, report_id: &ReportId, disaster_id: &DisasterId, linked_by: &UserId) -> AppResult<bool> {
+        let mut conn = self.get_connection()?;
+        let inserted = diesel::insert_into(disaster_reports::table)
+            .values((
+                disaster_reports::disaster_id.eq(disaster_id.0),
+                disaster_reports::report_id.eq(report_id.0),
+                disaster_reports::created_at.eq(Some(Utc::now().naive_utc())),
+                disaster_reports::linked_by.eq(Some(linked_by.0)),
+            ))
+            .on_conflict_do_nothing()
+            .execute(&mut conn);
+
+        match inserted {
+            Ok(rows) => Ok(rows > 0),
+            Err(DieselError::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _)) => Err(AppError::NotFound(
+                format!("Disaster {} or report {} not found", disaster_id, report_id)
+            )),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn find_linked_disasters(&self, id: &ReportId) -> AppResult<Vec<DisasterId>> {
+        let mut conn = self.get_connection()?;
+        let ids: Vec<Uuid> = disaster_reports::table
+            .filter(disaster_reports::report_id.eq(id.0))
+            .order(disaster_reports::created_at.asc())
+            .select(disaster_reports::disaster_id)
+            .load(&mut conn)?;
+        Ok(ids.into_iter().map(DisasterId).collect())
+    }
+
+    async fn find_by_disaster(&self, disaster_id: &DisasterId) -> AppResult<Vec<Report>> {
+        let mut conn = self.get_connection()?;
+        let rows = diesel::sql_query(format!(
+            "{} JOIN disaster_reports dr ON dr.report_id = r.id WHERE dr.disaster_id = $1 ORDER BY r.created_at",
+            SELECT_REPORTS
+        ))
+            .bind::<SqlUuid, _>(disaster_id.0)
+            .load::<ReportRow>(&mut conn)?;
+        Ok(Self::rows_to_domain(rows))
+    }
+
+    async fn reporter_history(&self, reporter_id: &UserId, exclude: &ReportId) -> AppResult<ReporterHistory> {
+        let mut conn = self.get_connection()?;
+        let counts = diesel::sql_query(
+            "SELECT COUNT(*) FILTER (WHERE status IN ('verified', 'resolved')) AS confirmed, \
+                    COUNT(*) FILTER (WHERE status = 'rejected') AS rejected \
+             FROM reports WHERE reporter_id = $1 AND id <> $2"
+        )
+            .bind::<SqlUuid, _>(reporter_id.0)
+            .bind::<SqlUuid, _>(exclude.0)
+            .get_result::<HistoryCountsRow>(&mut conn)?;
+        Ok(ReporterHistory {
+            confirmed: counts.confirmed.max(0) as u32,
+            rejected: counts.rejected.max(0) as u32,
+        })
+    }
+
+    async fn find_nearby(
+        &self,
+        center: &Coordinates,
+        radius_m: f64,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        exclude: &ReportId,
+    ) -> AppResult<Vec<NearbyReport>> {
+        let mut conn = self.get_connection()?;
+        let rows = diesel::sql_query(
+            "WITH center AS (SELECT ST_SetSRID(ST_MakePoint($1, $2), 4326)::geography AS point) \
+             SELECT r.id, r.reporter_id, r.disaster_type_id, r.status, r.cluster_id, r.created_at, \
+                    ST_Distance(l.geometry, center.point) AS distance_m \
+             FROM reports r \
+             JOIN locations l ON l.id = r.location_id, center \
+             WHERE r.id <> $3 AND r.created_at BETWEEN $4 AND $5 \
+               AND ST_DWithin(l.geometry, center.point, $6) \
+             ORDER BY r.created_at \
+             LIMIT 500"
+        )
+            .bind::<Float8, _>(center.longitude)
+            .bind::<Float8, _>(center.latitude)
+            .bind::<SqlUuid, _>(exclude.0)
+            .bind::<Timestamp, _>(from.naive_utc())
+            .bind::<Timestamp, _>(to.naive_utc())
+            .bind::<Float8, _>(radius_m)
+            .load::<NearbyRow>(&mut conn)?;
+        Ok(rows.into_iter().filter_map(Self::nearby_to_domain).collect())
+    }
+
+    async fn record_credibility(&self, report: &Report, absorbed_clusters: &[Uuid]) -> AppResult<()> {
+        let mut conn = self.get_connection()?;
+        let factors = report.credibility.as_ref()
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|e| AppError::Serialization(e.to_string()))?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let updated = diesel::update(reports::table.filter(reports::id.eq(report.id.0)))
+                .set((
+                    reports::credibility_score.eq(Some(report.credibility_score)),
+                    reports::credibility_factors.eq(factors),
+                    reports::cluster_id.eq(report.cluster_id),
+                ))
+                .execute(conn)?;
+            if updated == 0 {
+                return Err(Self::report_not_found(&report.id));
+            }
+
+            // Reports never assessed have no cluster yet and are named by their own id
+            if let (Some(cluster_id), false) = (report.cluster_id, absorbed_clusters.is_empty()) {
+                diesel::update(
+                    reports::table.filter(
+                        reports::cluster_id.eq_any(absorbed_clusters)
+                            .or(reports::cluster_id.is_null().and(reports::id.eq_any(absorbed_clusters))),
+                    ),
+                )
+                    .set(reports::cluster_id.eq(Some(cluster_id)))
+                    .execute(conn)?;
+            }
+            Ok(())
+        })
+    }
+
+    async fn find_triage_queue(&self, limit: i64, offset: i64) -> AppResult<Vec<TriageCluster>> {
+        let mut conn = self.get_connection()?;
+        let rows = diesel::sql_query(
+            "SELECT COALESCE(cluster_id, id) AS cluster_id, \
+                    (ARRAY_AGG(id ORDER BY credibility_score DESC NULLS LAST, created_at) \
+                        FILTER (WHERE status IN ('reported', 'under_review')))[1] AS lead_id, \
+                    COUNT(*) AS report_count, \
+                    COUNT(*) FILTER (WHERE status IN ('reported', 'under_review')) AS pending_count, \
+                    COUNT(*) FILTER (WHERE status IN ('verified', 'resolved')) AS confirmed_count, \
+                    MAX(credibility_score) FILTER (WHERE status IN ('reported', 'under_review')) AS top_score, \
+                    MIN(created_at) AS first_reported_at, \
+                    MAX(created_at) AS last_reported_at \
+             FROM reports \
+             WHERE status <> 'rejected' \
+             GROUP BY COALESCE(cluster_id, id) \
+             HAVING COUNT(*) FILTER (WHERE status IN ('reported', 'under_review')) > 0 \
+             ORDER BY top_score DESC NULLS LAST, report_count DESC, first_reported_at \
+             LIMIT $1 OFFSET $2"
+        )
+            .bind::<Int8, _>(limit)
+            .bind::<Int8, _>(offset)
+            .load::<TriageRow>(&mut conn)?;
+
+        let lead_ids: Vec<Uuid> = rows.iter().map(|row| row.lead_id).collect();
+        let mut leads: std::collections::HashMap<Uuid, Report> = Self::rows_to_domain(
+            diesel::sql_query(format!("{} WHERE r.id = ANY($1)", SELECT_REPORTS))
+                .bind::<Array<SqlUuid>, _>(&lead_ids)
+                .load::<ReportRow>(&mut conn)?,
+        )
+            .into_iter()
+            .map(|report| (report.id.0, report))
+            .collect();
+
+        Ok(rows.into_iter()
+            .filter_map(|row| {
+                let lead = leads.remove(&row.lead_id)?;
+                let first_reported_at = Self::utc(row.first_reported_at).unwrap_or(lead.created_at);
+                Some(TriageCluster {
+                    cluster_id: row.cluster_id,
+                    report_count: row.report_count.max(0) as u32,
+                    pending_count: row.pending_count.max(0) as u32,
+                    confirmed_count: row.confirmed_count.max(0) as u32,
+                    top_score: row.top_score.unwrap_or(0.0),
+                    first_reported_at,
+                    last_reported_at: Self::utc(row.last_reported_at).unwrap_or(first_reported_at),
+                    lead,
+                })
+            })
+            .collect())
+    }
+
+    async fn find_cluster(&self, cluster_id: &Uuid) -> AppResult<Vec<Report>> {
+        let mut conn = self.get_connection()?;
+        let rows = diesel::sql_query(format!(
+            "{} WHERE COALESCE(r.cluster_id, r.id) = $1 ORDER BY r.created_at",
+            SELECT_REPORTS
+        ))
+            .bind::<SqlUuid, _>(*cluster_id)
+            .load::<ReportRow>(&mut conn)?;
+        Ok(Self::rows_to_domain(rows))
+    }
+}
@@ -0,0 +1,180 @@
+/// Resource inventory repository implementation
+/// Organization-owned items in `emergency_resources` and the aggregated stock available for allocation
+
+use async_trait::async_trait;
+use chrono::{NaiveDate, NaiveDateTime, TimeZone, Utc};
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Text};
+use uuid::Uuid;
+
+use crate::domain::entities::emergency_response::{EmergencyResource, ResourceStatus, ResourceStock};
+use crate::domain::ports::repositories::ResourceInventoryRepository;
+use crate::infrastructure::database::schemas::emergency_resources;
+use crate::infrastructure::database::{DbConnection, DbPool};
+use crate::shared::error::DatabaseError;
+use crate::shared::{AppError, AppResult, LocationId, OrganizationId, ResourceId};
+
+#[derive(QueryableByName, Debug)]
+struct StockRow {
+    #[diesel(sql_type = Text)]
+    category: String,
+    #[diesel(sql_type = Text)]
+    name: String,
+    #[diesel(sql_type = Text)]
+    unit: String,
+    #[diesel(sql_type = BigInt)]
+    quantity: i64,
+}
+
+#[derive(Queryable, Selectable, Insertable, AsChangeset, Debug, Clone)]
+#[diesel(table_name = emergency_resources)]
+#[diesel(treat_none_as_null = true)]
+struct EmergencyResourceRecord {
+    id: Uuid,
+    name: String,
+    category: String,
+    quantity: i32,
+    unit: String,
+    location_id: Option<Uuid>,
+    organization_id: Option<Uuid>,
+    expiry_date: Option<NaiveDate>,
+    status: Option<String>,
+    created_at: Option<NaiveDateTime>,
+    updated_at: Option<NaiveDateTime>,
+}
+
+pub struct PostgresResourceInventoryRepository {
+    pool: DbPool,
+}
+
+impl PostgresResourceInventoryRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    fn get_connection(&self) -> AppResult<DbConnection> {
+        self.pool.get()
+            .map_err(|e| AppError::Database(DatabaseError::ConnectionPool(e)))
+    }
+
+    fn to_record(entity: &EmergencyResource) -> EmergencyResourceRecord {
+        EmergencyResourceRecord {
+            id: entity.id.0,
+            name: entity.name.clone(),
+            category: entity.category.clone(),
+            quantity: entity.quantity.min(i32::MAX as u32) as i32,
+            unit: entity.unit.clone(),
+            location_id: entity.location_id.map(|id| id.0),
+            organization_id: entity.organization_id.map(|id| id.0),
+            expiry_date: entity.expiry_date,
+            status: Some(entity.status.as_str().to_string()),
+            created_at: Some(entity.created_at.naive_utc()),
+            updated_at: Some(entity.updated_at.naive_utc()),
+        }
+    }
+
+    fn to_domain(record: EmergencyResourceRecord) -> AppResult<EmergencyResource> {
+        let status = match record.status.as_deref() {
+            Some(raw) => raw.parse::<ResourceStatus>()
+                .map_err(|_| AppError::DataConsistency(format!("Resource {} has unknown status '{}'", record.id, raw)))?,
+            None => ResourceStatus::Available,
+        };
+        let created_at = record.created_at.map(|t| Utc.from_utc_datetime(&t)).unwrap_or_else(Utc::now);
+
+        Ok(EmergencyResource {
+            id: ResourceId(record.id),
+            organization_id: record.organization_id.map(OrganizationId),
+            name: record.name,
+            category: record.category,
+            quantity: record.quantity.max(0) as u32,
+            unit: record.unit,
+            location_id: record.location_id.map(LocationId),
+            expiry_date: record.expiry_date,
+            status,
+            created_at,
+            updated_at: record.updated_at.map(|t| Utc.from_utc_datetime(&t)).unwrap_or(created_at),
+        })
+    }
+}
+
+#[async_trait]
+impl ResourceInventoryRepository for PostgresResourceInventoryRepository {
+    async fn available_stock(&self) -> AppResult<Vec<ResourceStock>> {
+        let mut conn = self.get_connection()?;
+        // Expired stock (food, medicine) is not allocatable
+        let rows = diesel::sql_query(
+            "SELECT category, name, unit, SUM(quantity)::BIGINT AS quantity \
+             FROM emergency_resources \
+             WHERE COALESCE(status, 'available') = 'available' \
+               AND quantity > 0 \
+               AND (expiry_date IS NULL OR expiry_date >= CURRENT_DATE) \
+             GROUP BY category, name, unit \
+             ORDER BY category, name"
+        )
+            .load::<StockRow>(&mut conn)?;
+
+        Ok(rows.into_iter()
+            .map(|row| ResourceStock {
+                category: row.category,
+                name: row.name,
+                unit: row.unit,
+                quantity: row.quantity.max(0) as u64,
+            })
+            .collect())
+    }
+
+    async fn find_by_id(&self, id: &ResourceId) -> AppResult<Option<EmergencyResource>> {
+        let mut conn = self.get_connection()?;
+        let record: Option<EmergencyResourceRecord> = emergency_resources::table
+            .filter(emergency_resources::id.eq(id.0))
+            .select(EmergencyResourceRecord::as_select())
+            .first(&mut conn)
+            .optional()?;
+        record.map(Self::to_domain).transpose()
+    }
+
+    async fn find_by_organization(&self, organization_id: &OrganizationId) -> AppResult<Vec<EmergencyResource>> {
+        let mut conn = self.get_connection()?;
+        let records = emergency_resources::table
+            .filter(emergency_resources::organization_id.eq(organization_id.0))
+            .order((emergency_resources::category.asc(), emergency_resources::name.asc()))
+            .select(EmergencyResourceRecord::as_select())
+            .load(&mut conn)?;
+        records.into_iter().map(Self::to_domain).collect()
+    }
+
+    async fn save(&self, entity: &EmergencyResource) -> AppResult<EmergencyResource> {
+        let mut conn = self.get_connection()?;
+        match diesel::insert_into(emergency_resources::table)
+            .values(&Self::to_record(entity))
+            .execute(&mut conn)
+        {
+            Err(diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::ForeignKeyViolation, _)) => {
+                Err(AppError::NotFound("Organization or location of the resource not found".to_string()))
+            }
+            other => {
+                other?;
+                Ok(entity.clone())
+            }
+        }
+    }
+
+    async fn update(&self, entity: &EmergencyResource) -> AppResult<EmergencyResource> {
+        let mut conn = self.get_connection()?;
+        let updated = diesel::update(emergency_resources::table.filter(emergency_resources::id.eq(entity.id.0)))
+            .set(&Self::to_record(entity))
+            .execute(&mut conn)?;
+
+        if updated == 0 {
+            return Err(AppError::NotFound(format!("Resource {} not found", entity.id)));
+        }
+        Ok(entity.clone())
+    }
+
+    async fn delete(&self, id: &ResourceId) -> AppResult<bool> {
+        let mut conn = self.get_connection()?;
+        let deleted = diesel::delete(emergency_resources::table.filter(emergency_resources::id.eq(id.0)))
+            .execute(&mut conn)?;
+        Ok(deleted > 0)
+    }
+}
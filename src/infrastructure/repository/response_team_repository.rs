@@ -0,0 +1,212 @@
+/// Response team repository implementation
+/// Persists the dispatchable team registry with optimistic concurrency on dispatch claims
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::domain::entities::response_team::{ResponseTeam, TeamAvailability, TeamType};
+use crate::domain::ports::repositories::ResponseTeamRepository;
+use crate::domain::value_objects::Coordinates;
+use crate::infrastructure::database::schemas::response_teams;
+use crate::infrastructure::database::{DbConnection, DbPool};
+use crate::shared::error::DatabaseError;
+use crate::shared::{AppError, AppResult, EmergencyResponseId};
+
+#[derive(Queryable, Selectable, Insertable, AsChangeset, Debug, Clone)]
+#[diesel(table_name = response_teams)]
+#[diesel(treat_none_as_null = true)]
+struct ResponseTeamRecord {
+    id: Uuid,
+    station_id: Option<Uuid>,
+    name: String,
+    team_type: String,
+    latitude: f64,
+    longitude: f64,
+    location_updated_at: DateTime<Utc>,
+    personnel: i32,
+    vehicles: Vec<String>,
+    availability: String,
+    current_response_id: Option<Uuid>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    version: i64,
+}
+
+pub struct PostgresResponseTeamRepository {
+    pool: DbPool,
+}
+
+impl PostgresResponseTeamRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    fn get_connection(&self) -> AppResult<DbConnection> {
+        self.pool.get()
+            .map_err(|e| AppError::Database(DatabaseError::ConnectionPool(e)))
+    }
+
+    fn to_record(entity: &ResponseTeam) -> ResponseTeamRecord {
+        ResponseTeamRecord {
+            id: entity.id,
+            station_id: entity.station_id,
+            name: entity.name.clone(),
+            team_type: entity.team_type.as_str().to_string(),
+            latitude: entity.location.latitude,
+            longitude: entity.location.longitude,
+            location_updated_at: entity.location_updated_at,
+            personnel: entity.personnel as i32,
+            vehicles: entity.vehicles.clone(),
+            availability: entity.availability.as_str().to_string(),
+            current_response_id: entity.current_response_id.map(|id| id.0),
+            created_at: entity.created_at,
+            updated_at: entity.updated_at,
+            version: entity.version,
+        }
+    }
+
+    fn to_domain(record: ResponseTeamRecord) -> AppResult<ResponseTeam> {
+        let location = Coordinates::new(record.latitude, record.longitude)
+            .map_err(|e| AppError::DataConsistency(format!(
+                "Response team {} has invalid location: {}", record.id, e
+            )))?;
+        let team_type: TeamType = record.team_type.parse()
+            .map_err(|_| AppError::DataConsistency(format!("Unknown team type '{}'", record.team_type)))?;
+        let availability: TeamAvailability = record.availability.parse()
+            .map_err(|_| AppError::DataConsistency(format!("Unknown team availability '{}'", record.availability)))?;
+
+        Ok(ResponseTeam {
+            id: record.id,
+            station_id: record.station_id,
+            name: record.name,
+            team_type,
+            location,
+            location_updated_at: record.location_updated_at,
+            personnel: record.personnel.max(0) as u32,
+            vehicles: record.vehicles,
+            availability,
+            current_response_id: record.current_response_id.map(EmergencyResponseId),
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+            version: record.version,
+            persisted_version: record.version,
+        })
+    }
+
+    fn to_domain_list(records: Vec<ResponseTeamRecord>) -> AppResult<Vec<ResponseTeam>> {
+        records.into_iter().map(Self::to_domain).collect()
+    }
+}
+
+#[async_trait]
+impl ResponseTeamRepository for PostgresResponseTeamRepository {
+    async fn find_by_id(&self, id: &Uuid) -> AppResult<Option<ResponseTeam>> {
+        let mut conn = self.get_connection()?;
+        let record: Option<ResponseTeamRecord> = response_teams::table
+            .filter(response_teams::id.eq(id))
+            .select(ResponseTeamRecord::as_select())
+            .first(&mut conn)
+            .optional()?;
+        record.map(Self::to_domain).transpose()
+    }
+
+    async fn save(&self, entity: &ResponseTeam) -> AppResult<ResponseTeam> {
+        let mut conn = self.get_connection()?;
+        let record = Self::to_record(entity);
+
+        match diesel::insert_into(response_teams::table).values(&record).execute(&mut conn) {
+            Err(diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::UniqueViolation, _)) => {
+                return Err(AppError::Conflict(format!("Response team {} already exists", entity.id)));
+            }
+            Err(diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::ForeignKeyViolation, _)) => {
+                return Err(AppError::Validation("Response team references an unknown station".to_string()));
+            }
+            other => { other?; }
+        }
+
+        let mut saved = entity.clone();
+        saved.persisted_version = saved.version;
+        Ok(saved)
+    }
+
+    async fn update(&self, entity: &ResponseTeam) -> AppResult<ResponseTeam> {
+        let mut conn = self.get_connection()?;
+        let record = Self::to_record(entity);
+
+        // Optimistic concurrency: two dispatchers cannot both claim the same team
+        let updated = match diesel::update(
+            response_teams::table
+                .filter(response_teams::id.eq(entity.id))
+                .filter(response_teams::version.eq(entity.persisted_version)),
+        )
+            .set(&record)
+            .execute(&mut conn)
+        {
+            Err(diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::ForeignKeyViolation, _)) => {
+                return Err(AppError::Validation("Response team references an unknown station".to_string()));
+            }
+            other => other?,
+        };
+
+        if updated == 0 {
+            let exists = response_teams::table
+                .filter(response_teams::id.eq(entity.id))
+                .count()
+                .get_result::<i64>(&mut conn)? > 0;
+            return Err(if exists {
+                AppError::Conflict(format!("Response team {} was modified concurrently", entity.id))
+            } else {
+                AppError::NotFound(format!("Response team {} not found", entity.id))
+            });
+        }
+
+        let mut saved = entity.clone();
+        saved.persisted_version = saved.version;
+        Ok(saved)
+    }
+
+    async fn delete(&self, id: &Uuid) -> AppResult<bool> {
+        let mut conn = self.get_connection()?;
+        let deleted = diesel::delete(response_teams::table.filter(response_teams::id.eq(id)))
+            .execute(&mut conn)?;
+        Ok(deleted > 0)
+    }
+
+    async fn find_all(&self) -> AppResult<Vec<ResponseTeam>> {
+        let mut conn = self.get_connection()?;
+        let records = response_teams::table
+            .order(response_teams::name.asc())
+            .select(ResponseTeamRecord::as_select())
+            .load(&mut conn)?;
+        Self::to_domain_list(records)
+    }
+
+    async fn find_by_filter(&self, team_type: Option<TeamType>, availability: Option<TeamAvailability>) -> AppResult<Vec<ResponseTeam>> {
+        let mut conn = self.get_connection()?;
+        let mut query = response_teams::table
+            .select(ResponseTeamRecord::as_select())
+            .into_boxed();
+        if let Some(team_type) = team_type {
+            query = query.filter(response_teams::team_type.eq(team_type.as_str()));
+        }
+        if let Some(availability) = availability {
+            query = query.filter(response_teams::availability.eq(availability.as_str()));
+        }
+
+        let records = query
+            .order(response_teams::name.asc())
+            .load(&mut conn)?;
+        Self::to_domain_list(records)
+    }
+
+    async fn find_by_response(&self, response_id: &EmergencyResponseId) -> AppResult<Vec<ResponseTeam>> {
+        let mut conn = self.get_connection()?;
+        let records = response_teams::table
+            .filter(response_teams::current_response_id.eq(response_id.0))
+            .select(ResponseTeamRecord::as_select())
+            .load(&mut conn)?;
+        Self::to_domain_list(records)
+    }
+}
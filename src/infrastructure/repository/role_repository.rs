@@ -0,0 +1,377 @@
+/// Role repository implementation
+/// Roles, their permissions and assignments in `roles`, `role_permissions` and `user_roles`,
+/// with every change recorded in `role_audit_log`
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use diesel::prelude::*;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::domain::entities::role::{Role, RoleAuditAction, RoleAuditEntry};
+use crate::domain::ports::repositories::RoleRepository;
+use crate::infrastructure::database::schemas::{role_audit_log, role_permissions, roles, user_roles, users};
+use crate::infrastructure::database::{DbConnection, DbPool};
+use crate::shared::error::DatabaseError;
+use crate::shared::{AppError, AppResult, Permission, UserId};
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = roles)]
+struct RoleRecord {
+    id: i32,
+    name: String,
+    description: Option<String>,
+    created_at: Option<NaiveDateTime>,
+    updated_at: Option<NaiveDateTime>,
+    is_system: bool,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = roles)]
+struct NewRoleRecord<'a> {
+    name: &'a str,
+    description: Option<&'a str>,
+    created_at: Option<NaiveDateTime>,
+    updated_at: Option<NaiveDateTime>,
+    is_system: bool,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = role_permissions)]
+struct RolePermissionRecord {
+    role_id: i32,
+    permission: &'static str,
+    created_at: NaiveDateTime,
+}
+
+#[derive(Queryable, Selectable, Insertable, Debug, Clone)]
+#[diesel(table_name = role_audit_log)]
+struct AuditRecord {
+    id: Uuid,
+    actor_id: Option<Uuid>,
+    action: String,
+    role_id: i32,
+    role_name: String,
+    target_user_id: Option<Uuid>,
+    details: serde_json::Value,
+    created_at: DateTime<Utc>,
+}
+
+pub struct PostgresRoleRepository {
+    pool: DbPool,
+}
+
+impl PostgresRoleRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    fn get_connection(&self) -> AppResult<DbConnection> {
+        self.pool.get()
+            .map_err(|e| AppError::Database(DatabaseError::ConnectionPool(e)))
+    }
+
+    /// Attach permissions to role rows; permission names the binary no longer knows are skipped
+    fn load_permissions(conn: &mut DbConnection, records: Vec<RoleRecord>) -> AppResult<Vec<Role>> {
+        let ids: Vec<i32> = records.iter().map(|record| record.id).collect();
+        let rows: Vec<(i32, String)> = role_permissions::table
+            .filter(role_permissions::role_id.eq_any(&ids))
+            .order((role_permissions::role_id.asc(), role_permissions::permission.asc()))
+            .select((role_permissions::role_id, role_permissions::permission))
+            .load(conn)?;
+
+        let mut granted: HashMap<i32, Vec<Permission>> = HashMap::new();
+        for (role_id, name) in rows {
+            match Permission::from_str(&name) {
+                Some(permission) => granted.entry(role_id).or_default().push(permission),
+                None => tracing::warn!("Role {} grants unknown permission '{}'; ignoring it", role_id, name),
+            }
+        }
+
+        Ok(records.into_iter()
+            .map(|record| {
+                let permissions = granted.remove(&record.id).unwrap_or_default();
+                Self::to_domain(record, permissions)
+            })
+            .collect())
+    }
+
+    fn to_domain(record: RoleRecord, permissions: Vec<Permission>) -> Role {
+        let created_at = record.created_at.map(|t| Utc.from_utc_datetime(&t)).unwrap_or_else(Utc::now);
+        Role {
+            id: record.id,
+            name: record.name,
+            description: record.description,
+            permissions,
+            is_system: record.is_system,
+            created_at,
+            updated_at: record.updated_at.map(|t| Utc.from_utc_datetime(&t)).unwrap_or(created_at),
+        }
+    }
+
+    fn find_record(conn: &mut DbConnection, id: i32) -> AppResult<Option<RoleRecord>> {
+        Ok(roles::table
+            .filter(roles::id.eq(id))
+            .select(RoleRecord::as_select())
+            .first(conn)
+            .optional()?)
+    }
+
+    fn replace_permissions(conn: &mut DbConnection, role: &Role) -> AppResult<()> {
+        diesel::delete(role_permissions::table.filter(role_permissions::role_id.eq(role.id)))
+            .execute(conn)?;
+        let now = Utc::now().naive_utc();
+        let rows: Vec<RolePermissionRecord> = role.permissions.iter()
+            .map(|permission| RolePermissionRecord { role_id: role.id, permission: permission.as_str(), created_at: now })
+            .collect();
+        diesel::insert_into(role_permissions::table)
+            .values(&rows)
+            .execute(conn)?;
+        Ok(())
+    }
+
+    fn record_audit(conn: &mut DbConnection, entry: &RoleAuditEntry) -> AppResult<()> {
+        diesel::insert_into(role_audit_log::table)
+            .values(&AuditRecord {
+                id: entry.id,
+                actor_id: entry.actor_id.map(|id| id.0),
+                action: entry.action.as_str().to_string(),
+                role_id: entry.role_id,
+                role_name: entry.role_name.clone(),
+                target_user_id: entry.target_user_id.map(|id| id.0),
+                details: entry.details.clone(),
+                created_at: entry.created_at,
+            })
+            .execute(conn)?;
+        Ok(())
+    }
+
+    fn audit_to_domain(record: AuditRecord) -> AppResult<RoleAuditEntry> {
+        Ok(RoleAuditEntry {
+            id: record.id,
+            actor_id: record.actor_id.map(UserId),
+            action: record.action.parse()
+                .map_err(|_| AppError::DataConsistency(format!(
+                    "Role audit entry {} has unknown action '{}'", record.id, record.action
+                )))?,
+            role_id: record.role_id,
+            role_name: record.role_name,
+            target_user_id: record.target_user_id.map(UserId),
+            details: record.details,
+            created_at: record.created_at,
+        })
+    }
+
+    fn unique_name_conflict(name: &str) -> impl Fn(diesel::result::Error) -> AppError + '_ {
+        move |e| match e {
+            diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::UniqueViolation, _) => {
+                AppError::Conflict(format!("A role named '{}' already exists", name))
+            }
+            other => other.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl RoleRepository for PostgresRoleRepository {
+    async fn find_all(&self) -> AppResult<Vec<Role>> {
+        let mut conn = self.get_connection()?;
+        let records = roles::table
+            .order(roles::name.asc())
+            .select(RoleRecord::as_select())
+            .load(&mut conn)?;
+        Self::load_permissions(&mut conn, records)
+    }
+
+    async fn find_by_id(&self, id: i32) -> AppResult<Option<Role>> {
+        let mut conn = self.get_connection()?;
+        let Some(record) = Self::find_record(&mut conn, id)? else { return Ok(None) };
+        Ok(Self::load_permissions(&mut conn, vec![record])?.pop())
+    }
+
+    async fn find_by_name(&self, name: &str) -> AppResult<Option<Role>> {
+        let mut conn = self.get_connection()?;
+        let record: Option<RoleRecord> = roles::table
+            .filter(roles::name.eq(name.trim()))
+            .select(RoleRecord::as_select())
+            .first(&mut conn)
+            .optional()?;
+        let Some(record) = record else { return Ok(None) };
+        Ok(Self::load_permissions(&mut conn, vec![record])?.pop())
+    }
+
+    async fn create(&self, role: &Role, actor: &UserId) -> AppResult<Role> {
+        let mut conn = self.get_connection()?;
+        conn.transaction::<_, AppError, _>(|conn| {
+            let id: i32 = diesel::insert_into(roles::table)
+                .values(&NewRoleRecord {
+                    name: &role.name,
+                    description: role.description.as_deref(),
+                    created_at: Some(role.created_at.naive_utc()),
+                    updated_at: Some(role.updated_at.naive_utc()),
+                    is_system: false,
+                })
+                .returning(roles::id)
+                .get_result(conn)
+                .map_err(Self::unique_name_conflict(&role.name))?;
+
+            let created = Role { id, is_system: false, ..role.clone() };
+            Self::replace_permissions(conn, &created)?;
+            Self::record_audit(conn, &RoleAuditEntry::new(*actor, RoleAuditAction::Created, &created)
+                .with_details(serde_json::json!({ "permissions": created.permission_names() })))?;
+            Ok(created)
+        })
+    }
+
+    async fn update(&self, role: &Role, actor: &UserId) -> AppResult<Role> {
+        let mut conn = self.get_connection()?;
+        conn.transaction::<_, AppError, _>(|conn| {
+            let existing = roles::table
+                .filter(roles::id.eq(role.id))
+                .select(RoleRecord::as_select())
+                .for_update()
+                .first(conn)
+                .optional()?
+                .ok_or_else(|| AppError::NotFound(format!("Role {} not found", role.id)))?;
+            let before = Self::load_permissions(conn, vec![existing.clone()])?.pop()
+                .map(|role| role.permission_names())
+                .unwrap_or_default();
+
+            diesel::update(roles::table.filter(roles::id.eq(role.id)))
+                .set((
+                    roles::name.eq(&role.name),
+                    roles::description.eq(role.description.as_deref()),
+                    roles::updated_at.eq(Some(role.updated_at.naive_utc())),
+                ))
+                .execute(conn)
+                .map_err(Self::unique_name_conflict(&role.name))?;
+            Self::replace_permissions(conn, role)?;
+
+            Self::record_audit(conn, &RoleAuditEntry::new(*actor, RoleAuditAction::Updated, role)
+                .with_details(serde_json::json!({
+                    "previous_name": existing.name,
+                    "previous_permissions": before,
+                    "permissions": role.permission_names(),
+                })))?;
+            Ok(Role { is_system: existing.is_system, ..role.clone() })
+        })
+    }
+
+    async fn delete(&self, id: i32, actor: &UserId) -> AppResult<bool> {
+        let mut conn = self.get_connection()?;
+        conn.transaction::<_, AppError, _>(|conn| {
+            let Some(record) = Self::find_record(conn, id)? else { return Ok(false) };
+            if record.is_system {
+                return Err(AppError::BusinessRuleViolation(format!("Built-in role '{}' cannot be deleted", record.name)));
+            }
+            let role = Self::load_permissions(conn, vec![record])?.pop()
+                .ok_or_else(|| AppError::NotFound(format!("Role {} not found", id)))?;
+            let holders: i64 = user_roles::table
+                .filter(user_roles::role_id.eq(id))
+                .count()
+                .get_result(conn)?;
+
+            // Permissions and assignments cascade; `users.role_id` falls back to NULL
+            diesel::delete(roles::table.filter(roles::id.eq(id))).execute(conn)?;
+            Self::record_audit(conn, &RoleAuditEntry::new(*actor, RoleAuditAction::Deleted, &role)
+                .with_details(serde_json::json!({
+                    "permissions": role.permission_names(),
+                    "revoked_from_users": holders,
+                })))?;
+            Ok(true)
+        })
+    }
+
+    async fn find_by_user(&self, user_id: &UserId) -> AppResult<Vec<Role>> {
+        let mut conn = self.get_connection()?;
+        let records = user_roles::table
+            .inner_join(roles::table)
+            .filter(user_roles::user_id.eq(user_id.0))
+            .order(roles::name.asc())
+            .select(RoleRecord::as_select())
+            .load(&mut conn)?;
+        Self::load_permissions(&mut conn, records)
+    }
+
+    async fn assign(&self, user_id: &UserId, role_id: i32, actor: &UserId) -> AppResult<bool> {
+        let mut conn = self.get_connection()?;
+        conn.transaction::<_, AppError, _>(|conn| {
+            let record = Self::find_record(conn, role_id)?
+                .ok_or_else(|| AppError::NotFound(format!("Role {} not found", role_id)))?;
+
+            let inserted = diesel::insert_into(user_roles::table)
+                .values((
+                    user_roles::user_id.eq(user_id.0),
+                    user_roles::role_id.eq(role_id),
+                    user_roles::created_at.eq(Some(Utc::now().naive_utc())),
+                ))
+                .on_conflict_do_nothing()
+                .execute(conn)
+                .map_err(|e| match e {
+                    diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::ForeignKeyViolation, _) => {
+                        AppError::NotFound(format!("User {} not found", user_id))
+                    }
+                    other => other.into(),
+                })?;
+            if inserted == 0 {
+                return Ok(false);
+            }
+
+            let role = Self::to_domain(record, Vec::new());
+            Self::record_audit(conn, &RoleAuditEntry::new(*actor, RoleAuditAction::Assigned, &role).for_user(*user_id))?;
+            Ok(true)
+        })
+    }
+
+    async fn find_holders(&self, role_id: i32) -> AppResult<Vec<UserId>> {
+        let mut conn = self.get_connection()?;
+        let mut holders: Vec<Uuid> = user_roles::table
+            .filter(user_roles::role_id.eq(role_id))
+            .select(user_roles::user_id)
+            .load(&mut conn)?;
+        holders.extend(
+            users::table
+                .filter(users::role_id.eq(role_id))
+                .select(users::id)
+                .load::<Uuid>(&mut conn)?,
+        );
+        holders.sort_unstable();
+        holders.dedup();
+        Ok(holders.into_iter().map(UserId).collect())
+    }
+
+    async fn revoke(&self, user_id: &UserId, role_id: i32, actor: &UserId) -> AppResult<bool> {
+        let mut conn = self.get_connection()?;
+        conn.transaction::<_, AppError, _>(|conn| {
+            let Some(record) = Self::find_record(conn, role_id)? else { return Ok(false) };
+            let deleted = diesel::delete(
+                user_roles::table
+                    .filter(user_roles::user_id.eq(user_id.0))
+                    .filter(user_roles::role_id.eq(role_id)),
+            )
+                .execute(conn)?;
+            if deleted == 0 {
+                return Ok(false);
+            }
+
+            let role = Self::to_domain(record, Vec::new());
+            Self::record_audit(conn, &RoleAuditEntry::new(*actor, RoleAuditAction::Revoked, &role).for_user(*user_id))?;
+            Ok(true)
+        })
+    }
+
+    async fn find_audit(&self, role_id: Option<i32>, limit: i64, offset: i64) -> AppResult<Vec<RoleAuditEntry>> {
+        let mut conn = self.get_connection()?;
+        let mut query = role_audit_log::table
+            .select(AuditRecord::as_select())
+            .order(role_audit_log::created_at.desc())
+            .limit(limit)
+            .offset(offset)
+            .into_boxed();
+        if let Some(role_id) = role_id {
+            query = query.filter(role_audit_log::role_id.eq(role_id));
+        }
+        let records = query.load(&mut conn)?;
+        records.into_iter().map(Self::audit_to_domain).collect()
+    }
+}
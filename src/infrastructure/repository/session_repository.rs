@@ -0,0 +1,188 @@
+/// Session index repository implementation
+/// One `auth_sessions` row per issued session, keyed by session id
+
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::domain::entities::UserSession;
+use crate::domain::ports::repositories::SessionRepository;
+use crate::infrastructure::database::schemas::auth_sessions;
+use crate::infrastructure::database::{DbConnection, DbPool};
+use crate::shared::error::DatabaseError;
+use crate::shared::{AppError, AppResult, UserId};
+
+#[derive(Queryable, Selectable, Debug)]
+#[diesel(table_name = auth_sessions)]
+struct AuthSessionRecord {
+    id: Uuid,
+    user_id: Option<Uuid>,
+    user_agent: Option<String>,
+    ip_address: Option<String>,
+    device_fingerprint: Option<String>,
+    expires_at: NaiveDateTime,
+    created_at: Option<NaiveDateTime>,
+    revoked_at: Option<NaiveDateTime>,
+}
+
+impl AuthSessionRecord {
+    fn into_entity(self) -> Option<UserSession> {
+        Some(UserSession {
+            id: self.id,
+            user_id: UserId(self.user_id?),
+            ip_address: self.ip_address,
+            user_agent: self.user_agent,
+            device_fingerprint: self.device_fingerprint,
+            created_at: self.created_at.unwrap_or(self.expires_at).and_utc(),
+            expires_at: self.expires_at.and_utc(),
+            revoked_at: self.revoked_at.map(|t| t.and_utc()),
+        })
+    }
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = auth_sessions)]
+struct NewAuthSession<'a> {
+    id: Uuid,
+    user_id: Option<Uuid>,
+    user_agent: Option<&'a str>,
+    ip_address: Option<&'a str>,
+    device_fingerprint: Option<&'a str>,
+    expires_at: NaiveDateTime,
+    created_at: Option<NaiveDateTime>,
+    updated_at: Option<NaiveDateTime>,
+}
+
+pub struct PostgresSessionRepository {
+    pool: DbPool,
+}
+
+impl PostgresSessionRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    fn get_connection(&self) -> AppResult<DbConnection> {
+        self.pool.get()
+            .map_err(|e| AppError::Database(DatabaseError::ConnectionPool(e)))
+    }
+}
+
+#[async_trait]
+impl SessionRepository for PostgresSessionRepository {
+    async fn record(&self, session: &UserSession) -> AppResult<()> {
+        let mut conn = self.get_connection()?;
+        let created_at = session.created_at.naive_utc();
+        let record = NewAuthSession {
+            id: session.id,
+            user_id: Some(session.user_id.0),
+            user_agent: session.user_agent.as_deref(),
+            ip_address: session.ip_address.as_deref(),
+            device_fingerprint: session.device_fingerprint.as_deref(),
+            expires_at: session.expires_at.naive_utc(),
+            created_at: Some(created_at),
+            updated_at: Some(created_at),
+        };
+
+        match diesel::insert_into(auth_sessions::table).values(&record).execute(&mut conn) {
+            Ok(_) => Ok(()),
+            Err(diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::ForeignKeyViolation, _)) => {
+                Err(AppError::NotFound(format!("User {} not found", session.user_id)))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn find_by_id(&self, id: &Uuid) -> AppResult<Option<UserSession>> {
+        let mut conn = self.get_connection()?;
+        let record: Option<AuthSessionRecord> = auth_sessions::table
+            .find(id)
+            .select(AuthSessionRecord::as_select())
+            .first(&mut conn)
+            .optional()?;
+        Ok(record.and_then(AuthSessionRecord::into_entity))
+    }
+
+    async fn find_active_by_user(&self, user_id: &UserId) -> AppResult<Vec<UserSession>> {
+        let mut conn = self.get_connection()?;
+        let records: Vec<AuthSessionRecord> = auth_sessions::table
+            .filter(auth_sessions::user_id.eq(user_id.0))
+            .filter(auth_sessions::revoked_at.is_null())
+            .filter(auth_sessions::expires_at.gt(Utc::now().naive_utc()))
+            .order(auth_sessions::created_at.desc())
+            .select(AuthSessionRecord::as_select())
+            .load(&mut conn)?;
+        Ok(records.into_iter().filter_map(AuthSessionRecord::into_entity).collect())
+    }
+
+    async fn revoke(&self, id: &Uuid) -> AppResult<bool> {
+        let mut conn = self.get_connection()?;
+        let now = Utc::now().naive_utc();
+        let updated = diesel::update(
+            auth_sessions::table
+                .filter(auth_sessions::id.eq(id))
+                .filter(auth_sessions::revoked_at.is_null()),
+        )
+            .set((auth_sessions::revoked_at.eq(Some(now)), auth_sessions::updated_at.eq(Some(now))))
+            .execute(&mut conn)?;
+        Ok(updated > 0)
+    }
+
+    async fn revoke_all_for_user(&self, user_id: &UserId, keep: Option<&Uuid>) -> AppResult<Vec<Uuid>> {
+        let mut conn = self.get_connection()?;
+        let now = Utc::now().naive_utc();
+        let target = auth_sessions::table
+            .filter(auth_sessions::user_id.eq(user_id.0))
+            .filter(auth_sessions::revoked_at.is_null());
+
+        let revoked = match keep {
+            Some(keep) => diesel::update(target.filter(auth_sessions::id.ne(keep)))
+                .set((auth_sessions::revoked_at.eq(Some(now)), auth_sessions::updated_at.eq(Some(now))))
+                .returning(auth_sessions::id)
+                .get_results(&mut conn)?,
+            None => diesel::update(target)
+                .set((auth_sessions::revoked_at.eq(Some(now)), auth_sessions::updated_at.eq(Some(now))))
+                .returning(auth_sessions::id)
+                .get_results(&mut conn)?,
+        };
+        Ok(revoked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn record(user_id: Option<Uuid>, created_at: Option<NaiveDateTime>, revoked_at: Option<NaiveDateTime>) -> AuthSessionRecord {
+        AuthSessionRecord {
+            id: Uuid::new_v4(),
+            user_id,
+            user_agent: Some("phone".to_string()),
+            ip_address: None,
+            device_fingerprint: None,
+            expires_at: (Utc::now() + Duration::hours(1)).naive_utc(),
+            created_at,
+            revoked_at,
+        }
+    }
+
+    #[test]
+    fn index_rows_map_to_sessions_of_their_user() {
+        let owner = Uuid::new_v4();
+        let now = Utc::now().naive_utc();
+
+        let live = record(Some(owner), Some(now), None).into_entity().unwrap();
+        assert_eq!(live.user_id, UserId(owner));
+        assert_eq!(live.created_at, now.and_utc());
+        assert!(live.is_active());
+
+        let revoked = record(Some(owner), None, Some(now)).into_entity().unwrap();
+        assert_eq!(revoked.created_at, revoked.expires_at);
+        assert!(!revoked.is_active());
+
+        // Rows whose user was deleted belong to nobody and are not listed
+        assert!(record(None, Some(now), None).into_entity().is_none());
+    }
+}
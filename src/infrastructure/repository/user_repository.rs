@@ -12,11 +12,27 @@ use crate::domain::ports::repositories::UserRepository;
 use crate::infrastructure::cache::{CacheService, CacheKeys};
 use crate::shared::{AppResult, AppError};
 use std::time::Duration;
-use crate::infrastructure::database::DbPool;
+use crate::infrastructure::database::{DbConnection, DbPool};
 use chrono::Utc;
-use crate::infrastructure::database::schemas::users;
+use crate::infrastructure::database::schemas::{roles, users};
 use crate::shared::error::DatabaseError;
 
+/// Last known position per user: newest volunteer location fix, plus geocoded home addresses
+const KNOWN_POSITIONS: &str = "WITH positions AS ( \
+        (SELECT DISTINCT ON (v.user_id) v.user_id, vl.geometry \
+         FROM volunteer_locations vl JOIN volunteers v ON v.id = vl.volunteer_id \
+         WHERE v.user_id IS NOT NULL AND vl.geometry IS NOT NULL \
+         ORDER BY v.user_id, vl.recorded_at DESC NULLS LAST) \
+        UNION ALL \
+        (SELECT user_id, geometry FROM user_address_geocodes) \
+    )";
+
+#[derive(QueryableByName, Debug)]
+struct UserIdRow {
+    #[diesel(sql_type = diesel::sql_types::Uuid)]
+    user_id: Uuid,
+}
+
 /// Database model for users
 #[derive(Queryable, Insertable, AsChangeset, Debug)]
 #[diesel(table_name = users)]
@@ -42,7 +58,8 @@ pub struct UserModel {
 
 impl UserModel {
     /// Convert database model to domain entity
-    pub fn to_domain(&self) -> AppResult<User> {
+    /// `role_name` is the name of the row `role_id` points to; users without a known built-in role are citizens
+    pub fn to_domain(&self, role_name: Option<&str>) -> AppResult<User> {
         let email = Email::new(self.email.clone())?;
         let username = Username::new(self.username.clone())?;
         let phone_number = match &self.phone {
@@ -50,8 +67,7 @@ impl UserModel {
             None => None,
         };
 
-        // Basic role mapping fallback (improve by joining roles table if needed)
-        let role = UserRole::Citizen;
+        let role = role_name.and_then(UserRole::from_name).unwrap_or(UserRole::Citizen);
 
         // Derive status from is_active/is_verified
         let status = match (self.is_active.unwrap_or(true), self.is_verified.unwrap_or(false)) {
@@ -145,6 +161,72 @@ impl PostgresUserRepository {
         
         Ok(())
     }
+
+    /// Domain users for loaded rows, with each row's built-in role resolved from `roles`
+    fn to_users(conn: &mut DbConnection, models: Vec<UserModel>) -> AppResult<Vec<User>> {
+        let mut role_ids: Vec<i32> = models.iter().filter_map(|m| m.role_id).collect();
+        role_ids.sort_unstable();
+        role_ids.dedup();
+        let names: Vec<(i32, String)> = if role_ids.is_empty() {
+            Vec::new()
+        } else {
+            roles::table
+                .filter(roles::id.eq_any(role_ids))
+                .select((roles::id, roles::name))
+                .load(conn)
+                .map_err(|e|AppError::Database(DatabaseError::Diesel(e)))?
+        };
+
+        Ok(Self::resolve_roles(models, &names))
+    }
+
+    /// Domain users for rows whose `role_id` is looked up in `names`, pairs of role id and role name
+    fn resolve_roles(models: Vec<UserModel>, names: &[(i32, String)]) -> Vec<User> {
+        models.into_iter()
+            .filter_map(|m| {
+                let role_name = m.role_id.and_then(|rid| names.iter().find(|(id, _)| *id == rid)).map(|(_, n)| n.as_str());
+                m.to_domain(role_name).ok()
+            })
+            .collect()
+    }
+
+    fn to_user(conn: &mut DbConnection, model: UserModel) -> AppResult<User> {
+        let role_name: Option<String> = match model.role_id {
+            Some(rid) => roles::table
+                .filter(roles::id.eq(rid))
+                .select(roles::name)
+                .first(conn)
+                .optional()
+                .map_err(|e|AppError::Database(DatabaseError::Diesel(e)))?,
+            None => None,
+        };
+        model.to_domain(role_name.as_deref())
+    }
+
+    /// Id of the built-in role row for a user's role, if it exists
+    fn role_id_for(conn: &mut DbConnection, role: &UserRole) -> AppResult<Option<i32>> {
+        roles::table
+            .filter(roles::name.eq(role.as_str()))
+            .select(roles::id)
+            .first(conn)
+            .optional()
+            .map_err(|e|AppError::Database(DatabaseError::Diesel(e)))
+    }
+
+    /// Load the active users among located user ids
+    fn load_active_users(&self, conn: &mut DbConnection, rows: Vec<UserIdRow>) -> AppResult<Vec<User>> {
+        use crate::infrastructure::database::schemas::users::dsl::*;
+        let ids: Vec<Uuid> = rows.into_iter().map(|row| row.user_id).collect();
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let user_models: Vec<UserModel> = users
+            .filter(id.eq_any(ids))
+            .filter(is_active.eq(true))
+            .load(conn)
+            .map_err(|e|AppError::Database(DatabaseError::Diesel(e)))?;
+        Self::to_users(conn, user_models)
+    }
 }
 
 #[async_trait]
@@ -173,7 +255,7 @@ impl UserRepository for PostgresUserRepository {
 
         match user_model {
             Some(model) => {
-                let user = model.to_domain()?;
+                let user = Self::to_user(&mut conn, model)?;
                 
                 // Cache the result
                 let _ = self.cache.set_string(&cache_key, serde_json::to_string(&user).unwrap_or_default(), Some(Self::USER_CACHE_TTL)).await;
@@ -209,7 +291,7 @@ impl UserRepository for PostgresUserRepository {
 
         match user_model {
             Some(model) => {
-                let user = model.to_domain()?;
+                let user = Self::to_user(&mut conn, model)?;
                 
                 // Cache the result
                 let _ = self.cache.set_string(&cache_key, serde_json::to_string(&user).unwrap_or_default(), Some(Self::USER_CACHE_TTL)).await;
@@ -226,7 +308,8 @@ impl UserRepository for PostgresUserRepository {
         let mut conn = self.pool.get()
             .map_err(|e|AppError::Database(DatabaseError::ConnectionPool(e)))?;
 
-        let user_model = UserModel::from_domain(user);
+        let mut user_model = UserModel::from_domain(user);
+        user_model.role_id = Self::role_id_for(&mut conn, user.role())?;
 
         // Upsert by id
         let saved_model = diesel::insert_into(users)
@@ -238,6 +321,7 @@ impl UserRepository for PostgresUserRepository {
                 password_hash.eq(&user_model.password_hash),
                 email.eq(&user_model.email),
                 phone.eq(&user_model.phone),
+                role_id.eq(&user_model.role_id),
                 full_name.eq(&user_model.full_name),
                 address.eq(&user_model.address),
                 profile_photo_url.eq(&user_model.profile_photo_url),
@@ -250,7 +334,7 @@ impl UserRepository for PostgresUserRepository {
             .get_result::<UserModel>(&mut conn)
             .map_err(|e|AppError::Database(DatabaseError::Diesel(e)))?;
 
-        let saved_user = saved_model.to_domain()?;
+        let saved_user = Self::to_user(&mut conn, saved_model)?;
         
         // Invalidate cache
         self.invalidate_user_cache(&saved_user.id(), &saved_user.email()).await?;
@@ -262,13 +346,15 @@ impl UserRepository for PostgresUserRepository {
         use crate::infrastructure::database::schemas::users::dsl::*;
         let mut conn = self.pool.get()
             .map_err(|e|AppError::Database(DatabaseError::ConnectionPool(e)))?;
-        let user_model = UserModel::from_domain(user);
+        let mut user_model = UserModel::from_domain(user);
+        user_model.role_id = Self::role_id_for(&mut conn, user.role())?;
         let updated_model = diesel::update(users.filter(id.eq(user_model.id)))
             .set((
                 username.eq(&user_model.username),
                 password_hash.eq(&user_model.password_hash),
                 email.eq(&user_model.email),
                 phone.eq(&user_model.phone),
+                role_id.eq(&user_model.role_id),
                 full_name.eq(&user_model.full_name),
                 address.eq(&user_model.address),
                 profile_photo_url.eq(&user_model.profile_photo_url),
@@ -280,7 +366,7 @@ impl UserRepository for PostgresUserRepository {
             ))
             .get_result::<UserModel>(&mut conn)
             .map_err(|e|AppError::Database(DatabaseError::Diesel(e)))?;
-        let updated = updated_model.to_domain()?;
+        let updated = Self::to_user(&mut conn, updated_model)?;
         self.invalidate_user_cache(updated.id(), updated.email()).await?;
         Ok(updated)
     }
@@ -317,31 +403,43 @@ impl UserRepository for PostgresUserRepository {
         let user_models: Vec<UserModel> = users
             .load(&mut conn)
             .map_err(|e|AppError::Database(DatabaseError::Diesel(e)))?;
-        let mut result = Vec::new();
-        for m in user_models { if let Ok(u) = m.to_domain() { result.push(u); } }
-        Ok(result)
+        Self::to_users(&mut conn, user_models)
     }
 
     async fn find_users_in_radius(
         &self,
-        _center: &Coordinates,
-        _radius_km: f64,
+        center: &Coordinates,
+        radius_km: f64,
     ) -> AppResult<Vec<User>> {
-        // Placeholder: return active users without geo filtering (no location table)
-        use crate::infrastructure::database::schemas::users::dsl::*;
+        use diesel::sql_types::Double;
         let mut conn = self.pool.get()
             .map_err(|e|AppError::Database(DatabaseError::ConnectionPool(e)))?;
-        let user_models: Vec<UserModel> = users
-            .filter(is_active.eq(true))
+        let rows: Vec<UserIdRow> = diesel::sql_query(format!(
+            "{} SELECT DISTINCT user_id FROM positions \
+             WHERE ST_DWithin(geometry, ST_SetSRID(ST_MakePoint($1, $2), 4326)::geography, $3)",
+            KNOWN_POSITIONS
+        ))
+            .bind::<Double, _>(center.longitude)
+            .bind::<Double, _>(center.latitude)
+            .bind::<Double, _>(radius_km * 1000.0)
             .load(&mut conn)
             .map_err(|e|AppError::Database(DatabaseError::Diesel(e)))?;
-        let mut nearby_users = Vec::new();
-        for model in user_models {
-            if let Ok(user) = model.to_domain() {
-                nearby_users.push(user);
-            }
-        }
-        Ok(nearby_users)
+        self.load_active_users(&mut conn, rows)
+    }
+
+    async fn find_users_in_area(&self, area: &GeoArea) -> AppResult<Vec<User>> {
+        use diesel::sql_types::Text;
+        let mut conn = self.pool.get()
+            .map_err(|e|AppError::Database(DatabaseError::ConnectionPool(e)))?;
+        let rows: Vec<UserIdRow> = diesel::sql_query(format!(
+            "{} SELECT DISTINCT user_id FROM positions \
+             WHERE ST_Covers(ST_SetSRID(ST_GeomFromGeoJSON($1), 4326)::geography, geometry)",
+            KNOWN_POSITIONS
+        ))
+            .bind::<Text, _>(area.to_geojson().to_string())
+            .load(&mut conn)
+            .map_err(|e|AppError::Database(DatabaseError::Diesel(e)))?;
+        self.load_active_users(&mut conn, rows)
     }
 
     async fn find_by_role(&self, _role: &UserRole) -> AppResult<Vec<User>> {
@@ -349,7 +447,7 @@ impl UserRepository for PostgresUserRepository {
         use crate::infrastructure::database::schemas::users::dsl::*;
         let mut conn = self.pool.get().map_err(|e|AppError::Database(DatabaseError::ConnectionPool(e)))?;
         let rows: Vec<UserModel> = users.filter(is_active.eq(true)).load(&mut conn).map_err(|e|AppError::Database(DatabaseError::Diesel(e)))?;
-        Ok(rows.into_iter().filter_map(|m| m.to_domain().ok()).collect())
+        Self::to_users(&mut conn, rows)
     }
 
     async fn count_by_status(&self, status_val: &str) -> AppResult<u64> {
@@ -372,6 +470,36 @@ impl UserRepository for PostgresUserRepository {
         Ok(count_u64)
     }
 
+    async fn save_address_geocode(&self, uid: &UserId, address_val: &str, position: &Coordinates) -> AppResult<()> {
+        use diesel::sql_types::{Double, Text, Uuid as SqlUuid};
+        let mut conn = self.pool.get()
+            .map_err(|e|AppError::Database(DatabaseError::ConnectionPool(e)))?;
+        diesel::sql_query(
+            "INSERT INTO user_address_geocodes (user_id, address, geometry, geocoded_at) \
+             VALUES ($1, $2, ST_SetSRID(ST_MakePoint($3, $4), 4326)::geography, NOW()) \
+             ON CONFLICT (user_id) DO UPDATE SET \
+                address = EXCLUDED.address, geometry = EXCLUDED.geometry, geocoded_at = EXCLUDED.geocoded_at"
+        )
+            .bind::<SqlUuid, _>(uid.value())
+            .bind::<Text, _>(address_val)
+            .bind::<Double, _>(position.longitude)
+            .bind::<Double, _>(position.latitude)
+            .execute(&mut conn)
+            .map_err(|e|AppError::Database(DatabaseError::Diesel(e)))?;
+        Ok(())
+    }
+
+    async fn clear_address_geocode(&self, uid: &UserId) -> AppResult<()> {
+        use diesel::sql_types::Uuid as SqlUuid;
+        let mut conn = self.pool.get()
+            .map_err(|e|AppError::Database(DatabaseError::ConnectionPool(e)))?;
+        diesel::sql_query("DELETE FROM user_address_geocodes WHERE user_id = $1")
+            .bind::<SqlUuid, _>(uid.value())
+            .execute(&mut conn)
+            .map_err(|e|AppError::Database(DatabaseError::Diesel(e)))?;
+        Ok(())
+    }
+
     async fn find_by_username(&self, username_val: &str) -> AppResult<Option<User>> {
         use crate::infrastructure::database::schemas::users::dsl::*;
         let mut conn = self.pool.get().map_err(|e|AppError::Database(DatabaseError::ConnectionPool(e)))?;
@@ -380,7 +508,7 @@ impl UserRepository for PostgresUserRepository {
             .first::<UserModel>(&mut conn)
             .optional()
             .map_err(|e|AppError::Database(DatabaseError::Diesel(e)))?;
-        Ok(model.and_then(|m| m.to_domain().ok()))
+        Ok(model.and_then(|m| Self::to_user(&mut conn, m).ok()))
     }
 
     async fn find_active_responders(&self) -> AppResult<Vec<User>> {
@@ -388,7 +516,7 @@ impl UserRepository for PostgresUserRepository {
         use crate::infrastructure::database::schemas::users::dsl::*;
         let mut conn = self.pool.get().map_err(|e|AppError::Database(DatabaseError::ConnectionPool(e)))?;
         let models: Vec<UserModel> = users.filter(is_active.eq(true)).load(&mut conn).map_err(|e|AppError::Database(DatabaseError::Diesel(e)))?;
-        Ok(models.into_iter().filter_map(|m| m.to_domain().ok()).collect())
+        Self::to_users(&mut conn, models)
     }
 
     async fn update_last_login(&self, user_id_val: &crate::shared::UserId) -> AppResult<bool> {
@@ -413,12 +541,15 @@ impl UserRepository for PostgresUserRepository {
         let now = Utc::now().naive_utc();
         let updated = diesel::update(users.filter(id.eq(user_id_val.value())))
             .set((is_verified.eq(true), is_active.eq(true), updated_at.eq(Some(now))))
-            .execute(&mut conn)
+            .returning(email)
+            .get_result::<String>(&mut conn)
+            .optional()
             .map_err(|e|AppError::Database(DatabaseError::Diesel(e)))?;
-        if updated > 0 {
+        if let Some(user_email) = &updated {
             let _ = self.cache.delete(&CacheKeys::user(&user_id_val.value())).await;
+            let _ = self.cache.delete(&CacheKeys::user_by_email(user_email)).await;
         }
-        Ok(updated > 0)
+        Ok(updated.is_some())
     }
 
     async fn update_password(&self, user_id_val: &crate::shared::UserId, new_password_hash: &str) -> AppResult<bool> {
@@ -428,13 +559,16 @@ impl UserRepository for PostgresUserRepository {
         let now = Utc::now().naive_utc();
         let updated = diesel::update(users.filter(id.eq(user_id_val.value())))
             .set((password_hash.eq(new_password_hash), updated_at.eq(Some(now))))
-            .execute(&mut conn)
+            .returning(email)
+            .get_result::<String>(&mut conn)
+            .optional()
             .map_err(|e|AppError::Database(DatabaseError::Diesel(e)))?;
-        if updated > 0 {
-            // Invalidate cache keys best-effort: user key; email unknown here
+        if let Some(user_email) = &updated {
+            // Logins resolve users by email, so a stale entry there would keep the old hash alive
             let _ = self.cache.delete(&CacheKeys::user(&user_id_val.value())).await;
+            let _ = self.cache.delete(&CacheKeys::user_by_email(user_email)).await;
         }
-        Ok(updated > 0)
+        Ok(updated.is_some())
     }
 
     async fn count_by_role(&self, _role_val: &UserRole) -> AppResult<u64> {
@@ -445,3 +579,57 @@ impl UserRepository for PostgresUserRepository {
         Ok(count_i64 as u64)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::UserRole;
+
+    fn stored(username: &str, role_id: Option<i32>) -> UserModel {
+        let now = Utc::now().naive_utc();
+        UserModel {
+            id: Uuid::new_v4(),
+            username: username.to_string(),
+            password_hash: "hash".to_string(),
+            email: format!("{}@example.id", username),
+            phone: None,
+            role_id,
+            full_name: Some("Test User".to_string()),
+            address: None,
+            profile_photo_url: None,
+            bio: None,
+            date_of_birth: None,
+            gender: None,
+            is_verified: Some(true),
+            is_active: Some(true),
+            last_login: None,
+            created_at: Some(now),
+            updated_at: Some(now),
+        }
+    }
+
+    #[test]
+    fn built_in_role_round_trips_through_role_id() {
+        let catalog = vec![(3, "coordinator".to_string()), (9, "responder".to_string())];
+        let coordinator = User::new(
+            Email::new("koordinator@example.id".to_string()).unwrap(),
+            Username::new("koordinator".to_string()).unwrap(),
+            "Koordinator".to_string(),
+            "hash".to_string(),
+            UserRole::Coordinator,
+        ).unwrap();
+
+        // `save` fills role_id from the roles row named after the user's role
+        let mut row = UserModel::from_domain(&coordinator);
+        assert_eq!(row.role_id, None);
+        row.role_id = catalog.iter().find(|(_, name)| name == coordinator.role().as_str()).map(|(id, _)| *id);
+
+        let loaded = PostgresUserRepository::resolve_roles(
+            vec![row, stored("responder", Some(9)), stored("warga", None), stored("lama", Some(42))],
+            &catalog,
+        );
+        let roles: Vec<UserRole> = loaded.iter().map(|user| user.role().clone()).collect();
+        assert_eq!(roles, vec![UserRole::Coordinator, UserRole::Responder, UserRole::Citizen, UserRole::Citizen]);
+        assert_eq!(loaded[0].id(), coordinator.id());
+    }
+}
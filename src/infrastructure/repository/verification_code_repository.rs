@@ -0,0 +1,101 @@
+/// Verification code repository implementation
+/// Hashed single-use codes in the `verification_codes` table
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::domain::entities::{VerificationCode, VerificationPurpose};
+use crate::domain::ports::repositories::VerificationCodeRepository;
+use crate::infrastructure::database::schemas::verification_codes;
+use crate::infrastructure::database::{DbConnection, DbPool};
+use crate::shared::error::DatabaseError;
+use crate::shared::{AppError, AppResult, UserId};
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = verification_codes)]
+struct NewVerificationCode<'a> {
+    id: Uuid,
+    user_id: Option<Uuid>,
+    code: &'a str,
+    type_: &'a str,
+    expires_at: NaiveDateTime,
+    is_used: Option<bool>,
+    created_at: Option<NaiveDateTime>,
+}
+
+pub struct PostgresVerificationCodeRepository {
+    pool: DbPool,
+}
+
+impl PostgresVerificationCodeRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    fn get_connection(&self) -> AppResult<DbConnection> {
+        self.pool.get()
+            .map_err(|e| AppError::Database(DatabaseError::ConnectionPool(e)))
+    }
+}
+
+#[async_trait]
+impl VerificationCodeRepository for PostgresVerificationCodeRepository {
+    async fn issue(&self, code: &VerificationCode) -> AppResult<()> {
+        let mut conn = self.get_connection()?;
+        let record = NewVerificationCode {
+            id: code.id,
+            user_id: Some(code.user_id.0),
+            code: &code.code_hash,
+            type_: code.purpose.as_str(),
+            expires_at: code.expires_at.naive_utc(),
+            is_used: Some(code.is_used),
+            created_at: Some(code.created_at.naive_utc()),
+        };
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            diesel::update(
+                verification_codes::table
+                    .filter(verification_codes::user_id.eq(code.user_id.0))
+                    .filter(verification_codes::type_.eq(code.purpose.as_str()))
+                    .filter(verification_codes::is_used.is_distinct_from(true)),
+            )
+                .set(verification_codes::is_used.eq(Some(true)))
+                .execute(conn)?;
+
+            diesel::insert_into(verification_codes::table)
+                .values(&record)
+                .execute(conn)?;
+            Ok(())
+        })
+    }
+
+    async fn consume(&self, purpose: VerificationPurpose, code_hash: &str) -> AppResult<Option<UserId>> {
+        let mut conn = self.get_connection()?;
+        let owner: Option<Option<Uuid>> = diesel::update(
+            verification_codes::table
+                .filter(verification_codes::type_.eq(purpose.as_str()))
+                .filter(verification_codes::code.eq(code_hash))
+                .filter(verification_codes::is_used.is_distinct_from(true))
+                .filter(verification_codes::expires_at.gt(Utc::now().naive_utc())),
+        )
+            .set(verification_codes::is_used.eq(Some(true)))
+            .returning(verification_codes::user_id)
+            .get_result(&mut conn)
+            .optional()?;
+
+        Ok(owner.flatten().map(UserId))
+    }
+
+    async fn count_issued_since(&self, user_id: &UserId, purpose: VerificationPurpose, since: DateTime<Utc>) -> AppResult<u64> {
+        let mut conn = self.get_connection()?;
+        let count: i64 = verification_codes::table
+            .filter(verification_codes::user_id.eq(user_id.0))
+            .filter(verification_codes::type_.eq(purpose.as_str()))
+            .filter(verification_codes::created_at.ge(since.naive_utc()))
+            .count()
+            .get_result(&mut conn)?;
+        Ok(count as u64)
+    }
+}
@@ -0,0 +1,409 @@
+/// Volunteer repository implementation
+/// Volunteer profiles in `volunteers` and their assignments in `volunteer_tracking`
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use diesel::prelude::*;
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use diesel::sql_types::{Bool, Float8, Int8, Nullable, Text, Timestamp, Uuid as SqlUuid};
+use uuid::Uuid;
+
+use crate::domain::entities::volunteer::{AssignmentStatus, Volunteer, VolunteerAssignment, VolunteerProfile};
+use crate::domain::ports::repositories::VolunteerRepository;
+use crate::domain::services::volunteer_matching::VolunteerCandidate;
+use crate::domain::value_objects::Coordinates;
+use crate::infrastructure::database::schemas::{disaster_reports, volunteer_tracking, volunteers};
+use crate::infrastructure::database::{DbConnection, DbPool};
+use crate::shared::error::DatabaseError;
+use crate::shared::{AppError, AppResult, DisasterId, LocationId, ReportId, UserId, VolunteerId};
+
+const ACTIVE_STATUSES: [&str; 3] = ["assigned", "accepted", "arrived"];
+
+#[derive(Queryable, Selectable, Insertable, AsChangeset, Debug, Clone)]
+#[diesel(table_name = volunteers)]
+#[diesel(treat_none_as_null = true)]
+struct VolunteerRecord {
+    id: Uuid,
+    user_id: Option<Uuid>,
+    skills: Option<Vec<Option<String>>>,
+    certifications: Option<Vec<Option<String>>>,
+    availability: Option<bool>,
+    availability_notes: Option<String>,
+    experience_years: Option<i32>,
+    specialization: Option<String>,
+    current_location_id: Option<Uuid>,
+    created_at: Option<NaiveDateTime>,
+    updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(Queryable, Selectable, Insertable, AsChangeset, Debug, Clone)]
+#[diesel(table_name = volunteer_tracking)]
+#[diesel(treat_none_as_null = true)]
+struct AssignmentRecord {
+    id: Uuid,
+    volunteer_id: Option<Uuid>,
+    report_id: Option<Uuid>,
+    status: Option<String>,
+    notes: Option<String>,
+    assigned_at: Option<NaiveDateTime>,
+    arrived_at: Option<NaiveDateTime>,
+    completed_at: Option<NaiveDateTime>,
+    created_at: Option<NaiveDateTime>,
+    updated_at: Option<NaiveDateTime>,
+    assigned_by: Option<Uuid>,
+    responded_at: Option<NaiveDateTime>,
+    disaster_id: Option<Uuid>,
+}
+
+/// Where a volunteer is and how much they are already doing, from `find_match_candidates`
+#[derive(QueryableByName, Debug)]
+struct CandidateRow {
+    #[diesel(sql_type = SqlUuid)]
+    id: Uuid,
+    #[diesel(sql_type = Nullable<Float8>)]
+    distance_m: Option<f64>,
+    #[diesel(sql_type = Int8)]
+    active_assignments: i64,
+    #[diesel(sql_type = Float8)]
+    shift_hours: f64,
+}
+
+pub struct PostgresVolunteerRepository {
+    pool: DbPool,
+}
+
+impl PostgresVolunteerRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    fn get_connection(&self) -> AppResult<DbConnection> {
+        self.pool.get()
+            .map_err(|e| AppError::Database(DatabaseError::ConnectionPool(e)))
+    }
+
+    fn utc(t: Option<NaiveDateTime>) -> Option<DateTime<Utc>> {
+        t.map(|t| Utc.from_utc_datetime(&t))
+    }
+
+    fn to_record(volunteer: &Volunteer) -> VolunteerRecord {
+        VolunteerRecord {
+            id: volunteer.id.0,
+            user_id: Some(volunteer.user_id.0),
+            skills: Some(volunteer.profile.skills.iter().cloned().map(Some).collect()),
+            certifications: Some(volunteer.profile.certifications.iter().cloned().map(Some).collect()),
+            availability: Some(volunteer.is_available),
+            availability_notes: volunteer.availability_notes.clone(),
+            experience_years: volunteer.profile.experience_years.map(|years| years as i32),
+            specialization: volunteer.profile.specialization.clone(),
+            current_location_id: volunteer.current_location_id.map(|id| id.0),
+            created_at: Some(volunteer.created_at.naive_utc()),
+            updated_at: Some(volunteer.updated_at.naive_utc()),
+        }
+    }
+
+    fn to_domain(record: VolunteerRecord) -> AppResult<Volunteer> {
+        let user_id = record.user_id
+            .ok_or_else(|| AppError::DataConsistency(format!("Volunteer {} has no user", record.id)))?;
+        let created_at = Self::utc(record.created_at).unwrap_or_else(Utc::now);
+        Ok(Volunteer {
+            id: VolunteerId(record.id),
+            user_id: UserId(user_id),
+            profile: VolunteerProfile {
+                skills: record.skills.unwrap_or_default().into_iter().flatten().collect(),
+                certifications: record.certifications.unwrap_or_default().into_iter().flatten().collect(),
+                experience_years: record.experience_years.map(|years| years.max(0) as u32),
+                specialization: record.specialization,
+            },
+            is_available: record.availability.unwrap_or(true),
+            availability_notes: record.availability_notes,
+            current_location_id: record.current_location_id.map(LocationId),
+            created_at,
+            updated_at: Self::utc(record.updated_at).unwrap_or(created_at),
+        })
+    }
+
+    fn target(assignment: &VolunteerAssignment) -> String {
+        match (assignment.report_id, assignment.disaster_id) {
+            (Some(report_id), _) => format!("report {}", report_id),
+            (None, Some(disaster_id)) => format!("disaster {}", disaster_id),
+            (None, None) => "an unknown target".to_string(),
+        }
+    }
+
+    fn assignment_to_record(assignment: &VolunteerAssignment) -> AssignmentRecord {
+        AssignmentRecord {
+            id: assignment.id,
+            volunteer_id: Some(assignment.volunteer_id.0),
+            report_id: assignment.report_id.map(|id| id.0),
+            status: Some(assignment.status.as_str().to_string()),
+            notes: assignment.notes.clone(),
+            assigned_at: Some(assignment.assigned_at.naive_utc()),
+            arrived_at: assignment.arrived_at.map(|t| t.naive_utc()),
+            completed_at: assignment.completed_at.map(|t| t.naive_utc()),
+            created_at: Some(assignment.assigned_at.naive_utc()),
+            updated_at: Some(assignment.updated_at.naive_utc()),
+            assigned_by: assignment.assigned_by.map(|id| id.0),
+            responded_at: assignment.responded_at.map(|t| t.naive_utc()),
+            disaster_id: assignment.disaster_id.map(|id| id.0),
+        }
+    }
+
+    fn assignment_to_domain(record: AssignmentRecord) -> AppResult<VolunteerAssignment> {
+        let volunteer_id = record.volunteer_id
+            .filter(|_| record.report_id.is_some() || record.disaster_id.is_some())
+            .ok_or_else(|| AppError::DataConsistency(format!("Assignment {} has no volunteer or target", record.id)))?;
+        let status: AssignmentStatus = record.status.as_deref().unwrap_or("assigned").parse()
+            .map_err(|_| AppError::DataConsistency(format!(
+                "Assignment {} has unknown status '{}'",
+                record.id, record.status.clone().unwrap_or_default()
+            )))?;
+        let assigned_at = Self::utc(record.assigned_at.or(record.created_at)).unwrap_or_else(Utc::now);
+        Ok(VolunteerAssignment {
+            id: record.id,
+            volunteer_id: VolunteerId(volunteer_id),
+            report_id: record.report_id.map(ReportId),
+            disaster_id: record.disaster_id.map(DisasterId),
+            status,
+            assigned_by: record.assigned_by.map(UserId),
+            notes: record.notes,
+            assigned_at,
+            responded_at: Self::utc(record.responded_at),
+            arrived_at: Self::utc(record.arrived_at),
+            completed_at: Self::utc(record.completed_at),
+            updated_at: Self::utc(record.updated_at).unwrap_or(assigned_at),
+        })
+    }
+}
+
+#[async_trait]
+impl VolunteerRepository for PostgresVolunteerRepository {
+    async fn create(&self, volunteer: &Volunteer) -> AppResult<Volunteer> {
+        let mut conn = self.get_connection()?;
+        match diesel::insert_into(volunteers::table)
+            .values(&Self::to_record(volunteer))
+            .execute(&mut conn)
+        {
+            Ok(_) => Ok(volunteer.clone()),
+            Err(DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => {
+                Err(AppError::Conflict(format!("User {} is already registered as a volunteer", volunteer.user_id)))
+            }
+            Err(DieselError::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _)) => {
+                Err(AppError::NotFound(format!("User {} not found", volunteer.user_id)))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn update(&self, volunteer: &Volunteer) -> AppResult<Volunteer> {
+        let mut conn = self.get_connection()?;
+        let updated = diesel::update(volunteers::table.filter(volunteers::id.eq(volunteer.id.0)))
+            .set(&Self::to_record(volunteer))
+            .execute(&mut conn)?;
+        if updated == 0 {
+            return Err(AppError::NotFound(format!("Volunteer {} not found", volunteer.id)));
+        }
+        Ok(volunteer.clone())
+    }
+
+    async fn find_by_id(&self, id: &VolunteerId) -> AppResult<Option<Volunteer>> {
+        let mut conn = self.get_connection()?;
+        let record: Option<VolunteerRecord> = volunteers::table
+            .filter(volunteers::id.eq(id.0))
+            .select(VolunteerRecord::as_select())
+            .first(&mut conn)
+            .optional()?;
+        record.map(Self::to_domain).transpose()
+    }
+
+    async fn find_by_user(&self, user_id: &UserId) -> AppResult<Option<Volunteer>> {
+        let mut conn = self.get_connection()?;
+        let record: Option<VolunteerRecord> = volunteers::table
+            .filter(volunteers::user_id.eq(user_id.0))
+            .select(VolunteerRecord::as_select())
+            .first(&mut conn)
+            .optional()?;
+        record.map(Self::to_domain).transpose()
+    }
+
+    async fn find_all(&self, available: Option<bool>, skill: Option<&str>, limit: i64, offset: i64) -> AppResult<Vec<Volunteer>> {
+        let mut conn = self.get_connection()?;
+        let mut query = volunteers::table
+            .select(VolunteerRecord::as_select())
+            .into_boxed();
+        if let Some(available) = available {
+            query = query.filter(volunteers::availability.eq(available));
+        }
+        if let Some(skill) = skill.map(str::trim).filter(|s| !s.is_empty()) {
+            query = query.filter(
+                diesel::dsl::sql::<Bool>("EXISTS (SELECT 1 FROM unnest(volunteers.skills) AS skill WHERE lower(skill) = lower(")
+                    .bind::<Text, _>(skill.to_string())
+                    .sql("))"),
+            );
+        }
+        let records = query
+            .order((volunteers::experience_years.desc().nulls_last(), volunteers::created_at.asc()))
+            .limit(limit)
+            .offset(offset)
+            .load(&mut conn)?;
+        records.into_iter().map(Self::to_domain).collect()
+    }
+
+    async fn find_match_candidates(
+        &self,
+        target: &Coordinates,
+        max_distance_m: f64,
+        fatigue_since: DateTime<Utc>,
+    ) -> AppResult<Vec<VolunteerCandidate>> {
+        let mut conn = self.get_connection()?;
+        // Position is the latest GPS fix, falling back to the volunteer's home location;
+        // shift hours count accepted assignments from acceptance until completion (or now)
+        let rows = diesel::sql_query(
+            "WITH target AS (SELECT ST_SetSRID(ST_MakePoint($1, $2), 4326)::geography AS point), \
+             last_fix AS ( \
+                 SELECT DISTINCT ON (volunteer_id) volunteer_id, geometry \
+                 FROM volunteer_locations \
+                 WHERE geometry IS NOT NULL \
+                 ORDER BY volunteer_id, recorded_at DESC NULLS LAST \
+             ), \
+             workload AS ( \
+                 SELECT volunteer_id, \
+                        COUNT(*) FILTER (WHERE status IN ('assigned', 'accepted', 'arrived')) AS active_assignments, \
+                        SUM(EXTRACT(EPOCH FROM COALESCE(completed_at, $4) - GREATEST(COALESCE(responded_at, assigned_at), $3)) / 3600.0) \
+                            FILTER (WHERE status IN ('accepted', 'arrived', 'completed')) AS shift_hours \
+                 FROM volunteer_tracking \
+                 WHERE status IN ('assigned', 'accepted', 'arrived') OR completed_at >= $3 \
+                 GROUP BY volunteer_id \
+             ) \
+             SELECT v.id, \
+                    ST_Distance(COALESCE(f.geometry, l.geometry), target.point) AS distance_m, \
+                    COALESCE(w.active_assignments, 0) AS active_assignments, \
+                    GREATEST(COALESCE(w.shift_hours, 0), 0)::float8 AS shift_hours \
+             FROM volunteers v \
+             CROSS JOIN target \
+             LEFT JOIN last_fix f ON f.volunteer_id = v.id \
+             LEFT JOIN locations l ON l.id = v.current_location_id \
+             LEFT JOIN workload w ON w.volunteer_id = v.id \
+             WHERE COALESCE(v.availability, TRUE) \
+               AND (COALESCE(f.geometry, l.geometry) IS NULL \
+                    OR ST_DWithin(COALESCE(f.geometry, l.geometry), target.point, $5)) \
+             ORDER BY distance_m NULLS LAST \
+             LIMIT 500"
+        )
+            .bind::<Float8, _>(target.longitude)
+            .bind::<Float8, _>(target.latitude)
+            .bind::<Timestamp, _>(fatigue_since.naive_utc())
+            .bind::<Timestamp, _>(Utc::now().naive_utc())
+            .bind::<Float8, _>(max_distance_m)
+            .load::<CandidateRow>(&mut conn)?;
+
+        let ids: Vec<Uuid> = rows.iter().map(|row| row.id).collect();
+        let records: Vec<VolunteerRecord> = volunteers::table
+            .filter(volunteers::id.eq_any(&ids))
+            .select(VolunteerRecord::as_select())
+            .load(&mut conn)?;
+        let mut volunteers = records.into_iter()
+            .map(|record| Self::to_domain(record).map(|volunteer| (volunteer.id.0, volunteer)))
+            .collect::<AppResult<std::collections::HashMap<_, _>>>()?;
+
+        Ok(rows.into_iter()
+            .filter_map(|row| {
+                volunteers.remove(&row.id).map(|volunteer| VolunteerCandidate {
+                    volunteer,
+                    distance_m: row.distance_m,
+                    active_assignments: row.active_assignments.max(0) as u32,
+                    shift_hours: row.shift_hours,
+                })
+            })
+            .collect())
+    }
+
+    async fn create_assignment(&self, assignment: &VolunteerAssignment) -> AppResult<VolunteerAssignment> {
+        let mut conn = self.get_connection()?;
+        match diesel::insert_into(volunteer_tracking::table)
+            .values(&Self::assignment_to_record(assignment))
+            .execute(&mut conn)
+        {
+            Ok(_) => Ok(assignment.clone()),
+            Err(DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => {
+                Err(AppError::Conflict(format!(
+                    "Volunteer {} already holds an open assignment for {}",
+                    assignment.volunteer_id, Self::target(assignment)
+                )))
+            }
+            Err(DieselError::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _)) => {
+                Err(AppError::NotFound(format!("{} not found", Self::target(assignment))))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn update_assignment(&self, assignment: &VolunteerAssignment) -> AppResult<VolunteerAssignment> {
+        let mut conn = self.get_connection()?;
+        let updated = diesel::update(volunteer_tracking::table.filter(volunteer_tracking::id.eq(assignment.id)))
+            .set((
+                volunteer_tracking::status.eq(Some(assignment.status.as_str())),
+                volunteer_tracking::notes.eq(assignment.notes.as_deref()),
+                volunteer_tracking::responded_at.eq(assignment.responded_at.map(|t| t.naive_utc())),
+                volunteer_tracking::arrived_at.eq(assignment.arrived_at.map(|t| t.naive_utc())),
+                volunteer_tracking::completed_at.eq(assignment.completed_at.map(|t| t.naive_utc())),
+                volunteer_tracking::updated_at.eq(Some(assignment.updated_at.naive_utc())),
+            ))
+            .execute(&mut conn)?;
+        if updated == 0 {
+            return Err(AppError::NotFound(format!("Assignment {} not found", assignment.id)));
+        }
+        Ok(assignment.clone())
+    }
+
+    async fn find_assignment(&self, id: &Uuid) -> AppResult<Option<VolunteerAssignment>> {
+        let mut conn = self.get_connection()?;
+        let record: Option<AssignmentRecord> = volunteer_tracking::table
+            .filter(volunteer_tracking::id.eq(id))
+            .select(AssignmentRecord::as_select())
+            .first(&mut conn)
+            .optional()?;
+        record.map(Self::assignment_to_domain).transpose()
+    }
+
+    async fn find_assignments(&self, volunteer_id: &VolunteerId, active_only: bool) -> AppResult<Vec<VolunteerAssignment>> {
+        let mut conn = self.get_connection()?;
+        let mut query = volunteer_tracking::table
+            .filter(volunteer_tracking::volunteer_id.eq(volunteer_id.0))
+            .select(AssignmentRecord::as_select())
+            .into_boxed();
+        if active_only {
+            query = query.filter(volunteer_tracking::status.eq_any(ACTIVE_STATUSES));
+        }
+        let records = query
+            .order(volunteer_tracking::assigned_at.desc())
+            .load(&mut conn)?;
+        records.into_iter().map(Self::assignment_to_domain).collect()
+    }
+
+    async fn find_report_assignments(&self, report_id: &ReportId) -> AppResult<Vec<VolunteerAssignment>> {
+        let mut conn = self.get_connection()?;
+        let records = volunteer_tracking::table
+            .filter(volunteer_tracking::report_id.eq(report_id.0))
+            .order(volunteer_tracking::assigned_at.desc())
+            .select(AssignmentRecord::as_select())
+            .load(&mut conn)?;
+        records.into_iter().map(Self::assignment_to_domain).collect()
+    }
+
+    async fn find_disaster_assignments(&self, disaster_id: &DisasterId) -> AppResult<Vec<VolunteerAssignment>> {
+        let mut conn = self.get_connection()?;
+        let linked_reports = disaster_reports::table
+            .filter(disaster_reports::disaster_id.eq(disaster_id.0))
+            .select(disaster_reports::report_id.nullable());
+        let records = volunteer_tracking::table
+            .filter(
+                volunteer_tracking::disaster_id.eq(disaster_id.0)
+                    .or(volunteer_tracking::report_id.eq_any(linked_reports)),
+            )
+            .order(volunteer_tracking::assigned_at.desc())
+            .select(AssignmentRecord::as_select())
+            .load(&mut conn)?;
+        records.into_iter().map(Self::assignment_to_domain).collect()
+    }
+}
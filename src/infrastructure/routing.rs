@@ -0,0 +1,114 @@
+/// Road routing service
+/// Serves routes from the offline road graph and keeps its closures in sync
+/// with the active disaster zones
+
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::config::RoutingConfig;
+use crate::domain::ports::repositories::DisasterZoneRepository;
+use crate::domain::ports::services::RoutingService;
+use crate::domain::value_objects::Coordinates;
+use crate::shared::routing::{RoadGraph, Route, RoutingEngine, TravelMode};
+use crate::shared::{AppError, AppResult};
+
+pub struct RoadRoutingService {
+    engine: Option<Arc<RoutingEngine>>,
+    zone_repository: Arc<dyn DisasterZoneRepository>,
+    refresh_interval: Duration,
+    last_refresh: Mutex<Option<Instant>>,
+}
+
+impl RoadRoutingService {
+    pub fn new(
+        engine: Option<Arc<RoutingEngine>>,
+        zone_repository: Arc<dyn DisasterZoneRepository>,
+        refresh_interval: Duration,
+    ) -> Self {
+        Self {
+            engine,
+            zone_repository,
+            refresh_interval,
+            last_refresh: Mutex::new(None),
+        }
+    }
+
+    /// The loaded road graph engine, shared with other consumers such as evacuation planning
+    pub fn engine(&self) -> Option<Arc<RoutingEngine>> {
+        self.engine.clone()
+    }
+
+    /// Load the configured road graph; a missing or unreadable graph disables road routing
+    pub async fn from_config(config: &RoutingConfig, zone_repository: Arc<dyn DisasterZoneRepository>) -> Self {
+        let engine = match config.road_graph_path.clone() {
+            Some(path) => {
+                let loaded = tokio::task::spawn_blocking(move || RoadGraph::load(&path)).await
+                    .map_err(|e| AppError::InternalServer(format!("Road graph loader failed: {}", e)))
+                    .and_then(|result| result);
+                match loaded {
+                    Ok(graph) => {
+                        tracing::info!(
+                            "Loaded road graph with {} nodes and {} edges",
+                            graph.node_count(), graph.edge_count()
+                        );
+                        Some(Arc::new(RoutingEngine::new(graph)))
+                    }
+                    Err(e) => {
+                        tracing::warn!("Road routing disabled: {}", e);
+                        None
+                    }
+                }
+            }
+            None => {
+                tracing::info!("ROAD_GRAPH_PATH not set; using straight-line travel estimates");
+                None
+            }
+        };
+
+        Self::new(engine, zone_repository, Duration::from_secs(config.closure_refresh_seconds))
+    }
+
+    /// Reload closures from active disaster zones when the cached set is stale
+    async fn refresh_closures(&self, engine: &Arc<RoutingEngine>) -> AppResult<()> {
+        {
+            let mut last_refresh = self.last_refresh.lock().unwrap_or_else(|e| e.into_inner());
+            if last_refresh.is_some_and(|at| at.elapsed() < self.refresh_interval) {
+                return Ok(());
+            }
+            // Claim the refresh so concurrent requests keep using the current closures
+            *last_refresh = Some(Instant::now());
+        }
+
+        let areas = match self.zone_repository.find_active_closure_areas().await {
+            Ok(areas) => areas,
+            Err(e) => {
+                *self.last_refresh.lock().unwrap_or_else(|e| e.into_inner()) = None;
+                return Err(e);
+            }
+        };
+        let engine = engine.clone();
+        tokio::task::spawn_blocking(move || engine.set_closures(&areas)).await
+            .map_err(|e| AppError::InternalServer(format!("Closure update failed: {}", e)))
+    }
+}
+
+#[async_trait]
+impl RoutingService for RoadRoutingService {
+    fn is_available(&self) -> bool {
+        self.engine.is_some()
+    }
+
+    async fn route(&self, from: &Coordinates, to: &Coordinates, mode: TravelMode) -> AppResult<Option<Route>> {
+        let Some(engine) = &self.engine else { return Ok(None) };
+
+        if let Err(e) = self.refresh_closures(engine).await {
+            tracing::warn!("Routing with stale road closures: {}", e);
+        }
+
+        let engine = engine.clone();
+        let (from, to) = (from.clone(), to.clone());
+        tokio::task::spawn_blocking(move || engine.route(&from, &to, mode)).await
+            .map_err(|e| AppError::InternalServer(format!("Route computation failed: {}", e)))
+    }
+}
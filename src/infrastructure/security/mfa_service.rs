@@ -0,0 +1,278 @@
+/// TOTP multi-factor authentication service
+/// Handles enrollment, code verification and recovery codes on top of the MFA repository
+
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::Utc;
+use rand::RngCore;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::digest;
+use serde::Serialize;
+
+use crate::domain::entities::MfaEnrollment;
+use crate::domain::ports::repositories::MfaRepository;
+use crate::infrastructure::cache::CacheService;
+use crate::shared::totp::{generate_recovery_codes, hash_recovery_code, Totp};
+use crate::shared::{AppError, AppResult, UserId};
+
+/// Accepted clock drift, in 30 second steps, between server and authenticator
+const ALLOWED_SKEW_STEPS: u64 = 1;
+
+/// Invalid codes accepted before verification is locked for the user
+const MAX_FAILED_ATTEMPTS: i64 = 5;
+
+/// How long verification stays locked, and how long failures are remembered
+const LOCKOUT_DURATION: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MfaEnrollmentStart {
+    /// Base32 secret for manual entry
+    pub secret: String,
+    /// `otpauth://` URI to render as a QR code
+    pub provisioning_uri: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MfaStatus {
+    pub enabled: bool,
+    pub pending_enrollment: bool,
+    pub recovery_codes_remaining: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MfaMethod {
+    Totp,
+    RecoveryCode,
+}
+
+/// Proof that a second factor was checked for a user; only `MfaService` can issue one
+#[derive(Debug, Clone)]
+pub struct MfaVerification {
+    user_id: UserId,
+    method: MfaMethod,
+}
+
+impl MfaVerification {
+    pub fn user_id(&self) -> &UserId {
+        &self.user_id
+    }
+
+    pub fn method(&self) -> MfaMethod {
+        self.method
+    }
+}
+
+pub struct MfaService {
+    repository: Arc<dyn MfaRepository>,
+    cache: Arc<dyn CacheService>,
+    key: LessSafeKey,
+    issuer: String,
+}
+
+impl MfaService {
+    /// `encryption_secret` is stretched to an AES-256 key for the stored TOTP secrets
+    pub fn new(
+        repository: Arc<dyn MfaRepository>,
+        cache: Arc<dyn CacheService>,
+        encryption_secret: &str,
+        issuer: String,
+    ) -> AppResult<Self> {
+        let key_bytes = digest::digest(&digest::SHA256, encryption_secret.as_bytes());
+        let key = UnboundKey::new(&AES_256_GCM, key_bytes.as_ref())
+            .map_err(|_| AppError::Configuration("Invalid MFA encryption key".to_string()))?;
+        Ok(Self { repository, cache, key: LessSafeKey::new(key), issuer })
+    }
+
+    fn encrypt_secret(&self, user_id: &UserId, secret: &[u8]) -> AppResult<Vec<u8>> {
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::thread_rng().fill_bytes(&mut nonce);
+
+        let mut sealed = secret.to_vec();
+        self.key
+            .seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::from(user_id.0.as_bytes()), &mut sealed)
+            .map_err(|_| AppError::Encryption("Failed to encrypt MFA secret".to_string()))?;
+
+        let mut ciphertext = nonce.to_vec();
+        ciphertext.extend_from_slice(&sealed);
+        Ok(ciphertext)
+    }
+
+    fn decrypt_secret(&self, enrollment: &MfaEnrollment) -> AppResult<Totp> {
+        if enrollment.secret_ciphertext.len() <= NONCE_LEN {
+            return Err(AppError::DataConsistency("Stored MFA secret is truncated".to_string()));
+        }
+        let (nonce, sealed) = enrollment.secret_ciphertext.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce)
+            .map_err(|_| AppError::DataConsistency("Stored MFA secret has an invalid nonce".to_string()))?;
+
+        let mut buffer = sealed.to_vec();
+        let secret = self.key
+            .open_in_place(nonce, Aad::from(enrollment.user_id.0.as_bytes()), &mut buffer)
+            .map_err(|_| AppError::DataConsistency("Stored MFA secret could not be decrypted".to_string()))?;
+        Ok(Totp::new(secret.to_vec()))
+    }
+
+    async fn active_enrollment(&self, user_id: &UserId) -> AppResult<MfaEnrollment> {
+        self.repository.find_by_user(user_id).await?
+            .filter(MfaEnrollment::is_active)
+            .ok_or_else(|| AppError::BadRequest("MFA is not enabled for this account".to_string()))
+    }
+
+    pub async fn status(&self, user_id: &UserId) -> AppResult<MfaStatus> {
+        let enrollment = self.repository.find_by_user(user_id).await?;
+        let enabled = enrollment.as_ref().is_some_and(MfaEnrollment::is_active);
+        let recovery_codes_remaining = if enabled {
+            self.repository.count_unused_recovery_codes(user_id).await?
+        } else {
+            0
+        };
+
+        Ok(MfaStatus {
+            enabled,
+            pending_enrollment: enrollment.is_some() && !enabled,
+            recovery_codes_remaining,
+        })
+    }
+
+    pub async fn is_enabled(&self, user_id: &UserId) -> AppResult<bool> {
+        Ok(self.repository.find_by_user(user_id).await?
+            .is_some_and(|enrollment| enrollment.is_active()))
+    }
+
+    /// Generate a new secret; it only takes effect after `confirm_enrollment`
+    pub async fn begin_enrollment(&self, user_id: &UserId, account_name: &str) -> AppResult<MfaEnrollmentStart> {
+        let totp = Totp::generate();
+        let enrollment = MfaEnrollment::new(*user_id, self.encrypt_secret(user_id, totp.secret())?);
+        self.repository.save_pending(&enrollment).await?;
+
+        Ok(MfaEnrollmentStart {
+            secret: totp.secret_base32(),
+            provisioning_uri: totp.provisioning_uri(&self.issuer, account_name),
+        })
+    }
+
+    /// Activate MFA with a first valid code; returns the plaintext recovery codes, shown only once
+    pub async fn confirm_enrollment(&self, user_id: &UserId, code: &str) -> AppResult<Vec<String>> {
+        let enrollment = self.repository.find_by_user(user_id).await?
+            .ok_or_else(|| AppError::BadRequest("No MFA enrollment in progress".to_string()))?;
+        if enrollment.is_active() {
+            return Err(AppError::Conflict("MFA is already enabled for this account".to_string()));
+        }
+
+        let step = self.decrypt_secret(&enrollment)?
+            .verify(code, Utc::now().timestamp() as u64, ALLOWED_SKEW_STEPS)
+            .ok_or_else(|| AppError::Unauthorized("Invalid MFA code".to_string()))?;
+
+        let codes = generate_recovery_codes();
+        let hashes: Vec<String> = codes.iter().map(|c| hash_recovery_code(c)).collect();
+        if !self.repository.confirm(user_id, step as i64, &hashes).await? {
+            return Err(AppError::Conflict("MFA enrollment was changed concurrently".to_string()));
+        }
+        Ok(codes)
+    }
+
+    /// Check a TOTP code or, failing that shape, a recovery code; too many invalid codes lock
+    /// verification for the user until `LOCKOUT_DURATION` has passed
+    pub async fn verify(&self, user_id: &UserId, code: &str) -> AppResult<MfaVerification> {
+        let lockout_key = format!("mfa_lockout:{}", user_id);
+        if self.cache.exists(&lockout_key).await? {
+            return Err(AppError::RateLimitExceeded(
+                "Too many invalid MFA codes; try again later".to_string()
+            ));
+        }
+
+        let attempts_key = format!("mfa_failed:{}", user_id);
+        let result = self.check_code(user_id, code).await;
+        match &result {
+            Ok(_) => self.cache.delete(&attempts_key).await?,
+            Err(AppError::Unauthorized(_)) => {
+                let attempts = self.cache.increment(&attempts_key, 1).await?;
+                if attempts == 1 {
+                    self.cache.expire(&attempts_key, LOCKOUT_DURATION).await?;
+                }
+                if attempts >= MAX_FAILED_ATTEMPTS {
+                    tracing::warn!("MFA verification locked for user {} after {} invalid codes", user_id, attempts);
+                    self.cache.set_string(&lockout_key, "true".to_string(), Some(LOCKOUT_DURATION)).await?;
+                    self.cache.delete(&attempts_key).await?;
+                }
+            }
+            Err(_) => {}
+        }
+        result
+    }
+
+    async fn check_code(&self, user_id: &UserId, code: &str) -> AppResult<MfaVerification> {
+        let enrollment = self.active_enrollment(user_id).await?;
+        let code = code.trim();
+
+        // Recovery codes are ten alphanumerics, so a short all-digit code must be a TOTP
+        let digits: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+        if digits.len() <= 8 && digits.chars().all(|c| c.is_ascii_digit()) {
+            let step = self.decrypt_secret(&enrollment)?
+                .verify(code, Utc::now().timestamp() as u64, ALLOWED_SKEW_STEPS)
+                .ok_or_else(|| AppError::Unauthorized("Invalid MFA code".to_string()))?;
+            if !self.repository.record_step(user_id, step as i64).await? {
+                return Err(AppError::Unauthorized("MFA code has already been used".to_string()));
+            }
+            return Ok(MfaVerification { user_id: *user_id, method: MfaMethod::Totp });
+        }
+
+        if !self.repository.consume_recovery_code(user_id, &hash_recovery_code(code)).await? {
+            return Err(AppError::Unauthorized("Invalid MFA code".to_string()));
+        }
+        tracing::info!("User {} authenticated with an MFA recovery code", user_id);
+        Ok(MfaVerification { user_id: *user_id, method: MfaMethod::RecoveryCode })
+    }
+
+    /// Replace all recovery codes; requires a fresh second factor
+    pub async fn regenerate_recovery_codes(&self, verification: &MfaVerification) -> AppResult<Vec<String>> {
+        let codes = generate_recovery_codes();
+        let hashes: Vec<String> = codes.iter().map(|c| hash_recovery_code(c)).collect();
+        self.repository.replace_recovery_codes(&verification.user_id, &hashes).await?;
+        Ok(codes)
+    }
+
+    pub async fn disable(&self, verification: &MfaVerification) -> AppResult<()> {
+        self.repository.delete(&verification.user_id).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use crate::infrastructure::cache::InMemoryCache;
+
+    /// Active enrollment whose recovery codes never match
+    struct ActiveEnrollment(MfaEnrollment);
+
+    #[async_trait]
+    impl MfaRepository for ActiveEnrollment {
+        async fn find_by_user(&self, _user_id: &UserId) -> AppResult<Option<MfaEnrollment>> {
+            Ok(Some(self.0.clone()))
+        }
+        async fn save_pending(&self, _enrollment: &MfaEnrollment) -> AppResult<()> { Ok(()) }
+        async fn confirm(&self, _user_id: &UserId, _step: i64, _hashes: &[String]) -> AppResult<bool> { Ok(true) }
+        async fn record_step(&self, _user_id: &UserId, _step: i64) -> AppResult<bool> { Ok(true) }
+        async fn consume_recovery_code(&self, _user_id: &UserId, _code_hash: &str) -> AppResult<bool> { Ok(false) }
+        async fn replace_recovery_codes(&self, _user_id: &UserId, _hashes: &[String]) -> AppResult<()> { Ok(()) }
+        async fn count_unused_recovery_codes(&self, _user_id: &UserId) -> AppResult<u64> { Ok(0) }
+        async fn delete(&self, _user_id: &UserId) -> AppResult<bool> { Ok(true) }
+    }
+
+    #[tokio::test]
+    async fn repeated_invalid_codes_lock_verification() {
+        let user_id = UserId::new();
+        let mut enrollment = MfaEnrollment::new(user_id, Vec::new());
+        enrollment.confirmed_at = Some(Utc::now());
+        let cache = Arc::new(InMemoryCache::new(100, 3600));
+        let service = MfaService::new(Arc::new(ActiveEnrollment(enrollment)), cache, "test-key", "TerraSiaga".to_string()).unwrap();
+
+        for _ in 0..MAX_FAILED_ATTEMPTS {
+            assert!(matches!(service.verify(&user_id, "NOTACODE00").await, Err(AppError::Unauthorized(_))));
+        }
+        assert!(matches!(service.verify(&user_id, "NOTACODE00").await, Err(AppError::RateLimitExceeded(_))));
+    }
+}
@@ -3,10 +3,16 @@
 
 pub mod service;
 pub mod paseto_service;
+pub mod paseto_keys;
+pub mod mfa_service;
+pub mod role_service;
 
 // Re-export security services
 pub use service::ProductionSecurityService;
-pub use paseto_service::{PasetoSecurityService, PasetoConfig, SecureAuthSession, AuthTokenPair};
+pub use paseto_service::{ActiveSessionInfo, PasetoSecurityService, PasetoConfig, SecureAuthSession, AuthTokenPair};
+pub use paseto_keys::{PasetoKeyRing, PublicKeyInfo};
+pub use mfa_service::{MfaService, MfaVerification};
+pub use role_service::{RoleChanges, RoleService};
 
 use crate::shared::AppResult;
 
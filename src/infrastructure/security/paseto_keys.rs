@@ -0,0 +1,294 @@
+/// PASETO v4 key ring
+/// Persistent keys identified by PASERK ids; the current key issues tokens while retired keys keep verifying them
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use pasetors::claims::{Claims, ClaimsValidationRules};
+use pasetors::footer::Footer;
+use pasetors::keys::{AsymmetricKeyPair, AsymmetricPublicKey, AsymmetricSecretKey, Generate, SymmetricKey};
+use pasetors::paserk::{FormatAsPaserk, Id};
+use pasetors::token::UntrustedToken;
+use pasetors::version4::V4;
+use pasetors::{Local, Public};
+use serde::Serialize;
+
+use crate::shared::{AppError, AppResult};
+
+/// Freshly generated key material, formatted as PASERK strings
+#[derive(Debug, Clone)]
+pub struct GeneratedPasetoKeys {
+    pub local_key: String,
+    pub secret_key: String,
+    pub public_key: String,
+}
+
+/// A verification key as published to partner services
+#[derive(Debug, Clone, Serialize)]
+pub struct PublicKeyInfo {
+    /// PASERK id (`k4.pid.`), matching the `kid` in token footers
+    pub kid: String,
+    pub kty: &'static str,
+    pub crv: &'static str,
+    /// Raw Ed25519 public key, base64url without padding
+    pub x: String,
+    /// The same key as a `k4.public.` PASERK
+    pub paserk: String,
+    pub version: &'static str,
+    pub purpose: &'static str,
+    /// Whether new tokens are signed with this key
+    pub current: bool,
+}
+
+struct LocalEntry {
+    id: Id,
+    kid: String,
+    key: SymmetricKey<V4>,
+}
+
+struct PublicEntry {
+    kid: String,
+    key: AsymmetricPublicKey<V4>,
+}
+
+struct SigningEntry {
+    id: Id,
+    key: AsymmetricSecretKey<V4>,
+}
+
+pub struct PasetoKeyRing {
+    /// The first entry encrypts new tokens
+    local: Vec<LocalEntry>,
+    signing: Option<SigningEntry>,
+    /// Starts with the public half of the signing key, when there is one
+    public: Vec<PublicEntry>,
+}
+
+fn paserk<T: FormatAsPaserk + ?Sized>(value: &T) -> AppResult<String> {
+    let mut out = String::new();
+    value.fmt(&mut out)
+        .map_err(|_| AppError::Encryption("Failed to format PASERK".to_string()))?;
+    Ok(out)
+}
+
+impl PasetoKeyRing {
+    /// Build from PASERK strings: a `k4.local.` key, a `k4.secret.` signing key and retired
+    /// `k4.local.`, `k4.public.` or `k4.secret.` keys that are only used for verification
+    pub fn from_paserk(local_key: Option<&str>, secret_key: Option<&str>, previous_keys: &[String]) -> AppResult<Self> {
+        let mut ring = Self { local: Vec::new(), signing: None, public: Vec::new() };
+
+        if let Some(raw) = local_key {
+            ring.push_local(raw)?;
+        }
+        if let Some(raw) = secret_key {
+            let key = AsymmetricSecretKey::<V4>::try_from(raw.trim())
+                .map_err(|_| AppError::Configuration("Invalid PASETO secret key: expected a k4.secret PASERK".to_string()))?;
+            // Footers name the public half so verifiers can match them against published keys
+            let public = AsymmetricPublicKey::<V4>::try_from(&key)
+                .map_err(|_| AppError::Configuration("Invalid PASETO secret key".to_string()))?;
+            ring.signing = Some(SigningEntry { id: Id::from(&public), key });
+            ring.push_public(public)?;
+        }
+
+        for raw in previous_keys.iter().map(|k| k.trim()).filter(|k| !k.is_empty()) {
+            if raw.starts_with("k4.local.") {
+                ring.push_local(raw)?;
+            } else if raw.starts_with("k4.public.") {
+                let key = AsymmetricPublicKey::<V4>::try_from(raw)
+                    .map_err(|_| AppError::Configuration("Invalid previous PASETO public key".to_string()))?;
+                ring.push_public(key)?;
+            } else if raw.starts_with("k4.secret.") {
+                let key = AsymmetricSecretKey::<V4>::try_from(raw)
+                    .map_err(|_| AppError::Configuration("Invalid previous PASETO secret key".to_string()))?;
+                ring.push_public_of(&key)?;
+            } else {
+                return Err(AppError::Configuration(
+                    "Previous PASETO keys must be k4.local, k4.public or k4.secret PASERKs".to_string()
+                ));
+            }
+        }
+        Ok(ring)
+    }
+
+    /// Generate a new local key and Ed25519 signing key pair
+    pub fn generate() -> AppResult<GeneratedPasetoKeys> {
+        let local = SymmetricKey::<V4>::generate()
+            .map_err(|e| AppError::Encryption(format!("Failed to generate PASETO local key: {}", e)))?;
+        let pair = AsymmetricKeyPair::<V4>::generate()
+            .map_err(|e| AppError::Encryption(format!("Failed to generate PASETO key pair: {}", e)))?;
+
+        Ok(GeneratedPasetoKeys {
+            local_key: paserk(&local)?,
+            secret_key: paserk(&pair.secret)?,
+            public_key: paserk(&pair.public)?,
+        })
+    }
+
+    fn push_local(&mut self, raw: &str) -> AppResult<()> {
+        let key = SymmetricKey::<V4>::try_from(raw.trim())
+            .map_err(|_| AppError::Configuration("Invalid PASETO local key: expected a k4.local PASERK".to_string()))?;
+        let id = Id::from(&key);
+        let kid = paserk(&id)?;
+        if !self.local.iter().any(|entry| entry.kid == kid) {
+            self.local.push(LocalEntry { id, kid, key });
+        }
+        Ok(())
+    }
+
+    fn push_public_of(&mut self, secret: &AsymmetricSecretKey<V4>) -> AppResult<()> {
+        let public = AsymmetricPublicKey::<V4>::try_from(secret)
+            .map_err(|_| AppError::Configuration("Invalid PASETO secret key".to_string()))?;
+        self.push_public(public)
+    }
+
+    fn push_public(&mut self, key: AsymmetricPublicKey<V4>) -> AppResult<()> {
+        let kid = paserk(&Id::from(&key))?;
+        if !self.public.iter().any(|entry| entry.kid == kid) {
+            self.public.push(PublicEntry { kid, key });
+        }
+        Ok(())
+    }
+
+    pub fn can_encrypt(&self) -> bool {
+        !self.local.is_empty()
+    }
+
+    pub fn can_sign(&self) -> bool {
+        self.signing.is_some()
+    }
+
+    /// Id of the key that issues new tokens for the given purpose
+    pub fn current_kid(&self, local: bool) -> Option<String> {
+        if local {
+            self.local.first().map(|entry| entry.kid.clone())
+        } else {
+            self.public.first().filter(|_| self.signing.is_some()).map(|entry| entry.kid.clone())
+        }
+    }
+
+    /// Encrypt (`v4.local`) or sign (`v4.public`) claims with the current key, naming it in the footer
+    pub fn issue(&self, claims: &Claims, local: bool) -> AppResult<String> {
+        let mut footer = Footer::new();
+        if local {
+            let current = self.local.first()
+                .ok_or_else(|| AppError::Configuration("No PASETO local key configured".to_string()))?;
+            footer.key_id(&current.id);
+            pasetors::local::encrypt(&current.key, claims, Some(&footer), None)
+                .map_err(|e| AppError::InternalServer(format!("PASETO encryption failed: {}", e)))
+        } else {
+            let signing = self.signing.as_ref()
+                .ok_or_else(|| AppError::Configuration("No PASETO signing key configured".to_string()))?;
+            footer.key_id(&signing.id);
+            pasetors::public::sign(&signing.key, claims, Some(&footer), None)
+                .map_err(|e| AppError::InternalServer(format!("PASETO signing failed: {}", e)))
+        }
+    }
+
+    /// Decrypt or verify a token with the key named in its footer and return the raw claims JSON
+    /// Tokens without a `kid` predate key ids and are checked against the current key
+    pub fn open(&self, token: &str) -> AppResult<String> {
+        let validation_rules = ClaimsValidationRules::new();
+        let trusted = if token.starts_with("v4.local.") {
+            let untrusted = UntrustedToken::<Local, V4>::try_from(token)
+                .map_err(|e| AppError::Unauthorized(format!("Invalid local token format: {}", e)))?;
+            let kid = Self::footer_kid(untrusted.untrusted_footer())?;
+            let entry = match kid.as_deref() {
+                Some(kid) => self.local.iter().find(|entry| entry.kid == kid),
+                None => self.local.first(),
+            }.ok_or_else(|| AppError::Unauthorized("Token was issued with an unknown key".to_string()))?;
+
+            // The footer is authenticated as part of the token, so it need not be supplied again
+            pasetors::local::decrypt(&entry.key, &untrusted, &validation_rules, None, None)
+                .map_err(|e| AppError::Unauthorized(format!("Token decryption failed: {}", e)))?
+        } else {
+            let untrusted = UntrustedToken::<Public, V4>::try_from(token)
+                .map_err(|e| AppError::Unauthorized(format!("Invalid public token format: {}", e)))?;
+            let kid = Self::footer_kid(untrusted.untrusted_footer())?;
+            let entry = match kid.as_deref() {
+                Some(kid) => self.public.iter().find(|entry| entry.kid == kid),
+                None => self.public.first(),
+            }.ok_or_else(|| AppError::Unauthorized("Token was signed with an unknown key".to_string()))?;
+
+            pasetors::public::verify(&entry.key, &untrusted, &validation_rules, None, None)
+                .map_err(|e| AppError::Unauthorized(format!("Token verification failed: {}", e)))?
+        };
+
+        String::from_utf8(trusted.payload().as_bytes().to_vec())
+            .map_err(|e| AppError::Unauthorized(format!("Invalid token payload: {}", e)))
+    }
+
+    fn footer_kid(raw: &[u8]) -> AppResult<Option<String>> {
+        if raw.is_empty() {
+            return Ok(None);
+        }
+        let mut footer = Footer::new();
+        footer.parse_bytes(raw)
+            .map_err(|_| AppError::Unauthorized("Invalid token footer".to_string()))?;
+        Ok(footer.get_claim("kid").and_then(|kid| kid.as_str()).map(str::to_string))
+    }
+
+    /// Verification keys for `v4.public` tokens, current key first
+    pub fn public_keys(&self) -> AppResult<Vec<PublicKeyInfo>> {
+        let current = self.current_kid(false);
+        self.public.iter()
+            .map(|entry| Ok(PublicKeyInfo {
+                kid: entry.kid.clone(),
+                kty: "OKP",
+                crv: "Ed25519",
+                x: URL_SAFE_NO_PAD.encode(entry.key.as_bytes()),
+                paserk: paserk(&entry.key)?,
+                version: "v4",
+                purpose: "public",
+                current: current.as_deref() == Some(entry.kid.as_str()),
+            }))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claims() -> Claims {
+        let mut claims = Claims::new().unwrap();
+        claims.subject("user-1").unwrap();
+        claims
+    }
+
+    #[test]
+    fn public_tokens_name_their_key_and_survive_rotation() {
+        let old = PasetoKeyRing::generate().unwrap();
+        let new = PasetoKeyRing::generate().unwrap();
+
+        let before = PasetoKeyRing::from_paserk(None, Some(&old.secret_key), &[]).unwrap();
+        let token = before.issue(&claims(), false).unwrap();
+        assert!(token.starts_with("v4.public."));
+
+        let rotated = PasetoKeyRing::from_paserk(None, Some(&new.secret_key), &[old.public_key.clone()]).unwrap();
+        assert!(rotated.open(&token).unwrap().contains("user-1"));
+
+        let keys = rotated.public_keys().unwrap();
+        assert_eq!(keys.len(), 2);
+        assert!(keys[0].current && !keys[1].current);
+        assert_eq!(keys[1].paserk, old.public_key);
+
+        let retired = PasetoKeyRing::from_paserk(None, Some(&new.secret_key), &[]).unwrap();
+        assert!(retired.open(&token).is_err());
+    }
+
+    #[test]
+    fn local_tokens_decrypt_with_previous_keys() {
+        let old = PasetoKeyRing::generate().unwrap();
+        let new = PasetoKeyRing::generate().unwrap();
+
+        let token = PasetoKeyRing::from_paserk(Some(&old.local_key), None, &[]).unwrap()
+            .issue(&claims(), true).unwrap();
+        let rotated = PasetoKeyRing::from_paserk(Some(&new.local_key), None, &[old.local_key.clone()]).unwrap();
+        assert!(rotated.open(&token).is_ok());
+        assert_ne!(rotated.current_kid(true), PasetoKeyRing::from_paserk(Some(&old.local_key), None, &[]).unwrap().current_kid(true));
+    }
+
+    #[test]
+    fn rejects_malformed_keys() {
+        assert!(PasetoKeyRing::from_paserk(Some("k4.local.short"), None, &[]).is_err());
+        assert!(PasetoKeyRing::from_paserk(None, None, &["k3.public.abc".to_string()]).is_err());
+    }
+}
@@ -4,16 +4,18 @@
 use std::sync::Arc;
 use std::time::Duration;
 use serde::{Deserialize, Serialize};
-use pasetors::keys::{SymmetricKey, AsymmetricSecretKey, AsymmetricPublicKey};
-use pasetors::token::UntrustedToken;
-use pasetors::{Local, Public, version4::V4};
-use pasetors::claims::{Claims, ClaimsValidationRules};
+use pasetors::claims::Claims;
 use argon2::Argon2;
 use uuid::Uuid;
 use chrono::{DateTime, Utc, Duration as ChronoDuration};
-use crate::domain::entities::User;
+use crate::domain::entities::{User, UserSession};
+use crate::domain::ports::repositories::SessionRepository;
+use crate::domain::ports::services::SessionRevoker;
 use crate::domain::value_objects::{ UserRole, Email};
 use crate::infrastructure::cache::{CacheService};
+use crate::infrastructure::security::mfa_service::MfaVerification;
+use crate::infrastructure::security::paseto_keys::{PasetoKeyRing, PublicKeyInfo};
+use crate::infrastructure::security::role_service::RoleService;
 use crate::shared::{AppResult, AppError};
 use crate::UserId;
 
@@ -52,17 +54,38 @@ pub struct SecureAuthSession {
     pub permissions: Vec<String>,
     pub is_elevated: bool,       // For sensitive operations
     pub mfa_verified: bool,      // Multi-factor authentication status
+    #[serde(default)]
+    pub elevated_until: Option<DateTime<Utc>>,
+    /// Per-user revocation generation the session was created under
+    #[serde(default)]
+    pub generation: i64,
     // Refresh token support
     pub refresh_token_id: Option<String>,
     pub refresh_expires_at: Option<DateTime<Utc>>,    
 }
 
+/// Live view of an indexed session, for device management
+#[derive(Debug, Clone, Serialize)]
+pub struct ActiveSessionInfo {
+    pub session_id: String,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub device_fingerprint: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_activity: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub mfa_elevated: bool,
+}
+
 /// PASETO configuration
 #[derive(Debug, Clone)]
 pub struct PasetoConfig {
-    pub local_key: Vec<u8>,      // For encrypted local tokens
-    pub public_key: Vec<u8>,     // For signed public tokens
-    pub private_key: Vec<u8>,    // For signing public tokens
+    /// `k4.local.` PASERK for encrypted local tokens
+    pub local_key: Option<String>,
+    /// `k4.secret.` PASERK (Ed25519 seed and public key) for signed public tokens
+    pub secret_key: Option<String>,
+    /// Retired keys that still verify tokens issued before a rotation
+    pub previous_keys: Vec<String>,
     pub token_expiration_hours: u64,
     pub session_timeout_hours: u64,
     pub elevated_session_minutes: u64,
@@ -74,14 +97,13 @@ pub struct PasetoConfig {
 
 impl Default for PasetoConfig {
     fn default() -> Self {
-        // Generate secure random keys (in production, these should be from config)
-        let local_key = (0..32).map(|_| rand::random::<u8>()).collect();
-        let (public_key, private_key) = Self::generate_asymmetric_keys();
+        // Ephemeral keys: tokens do not survive a restart and cannot be shared between instances
+        let keys = PasetoKeyRing::generate().ok();
 
         Self {
-            local_key,
-            public_key,
-            private_key,
+            local_key: keys.as_ref().map(|k| k.local_key.clone()),
+            secret_key: keys.map(|k| k.secret_key),
+            previous_keys: Vec::new(),
             token_expiration_hours: 24,
             session_timeout_hours: 72,
             elevated_session_minutes: 15,
@@ -93,15 +115,6 @@ impl Default for PasetoConfig {
     }
 }
 
-impl PasetoConfig {
-    fn generate_asymmetric_keys() -> (Vec<u8>, Vec<u8>) {
-        // In production, use proper key generation
-        let private_key: Vec<u8> = (0..64).map(|_| rand::random::<u8>()).collect();
-        let public_key: Vec<u8> = (0..32).map(|_| rand::random::<u8>()).collect();
-        (public_key, private_key)
-    }
-}
-
 /// Pair of access and refresh tokens
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuthTokenPair {
@@ -117,37 +130,48 @@ pub struct PasetoSecurityService {
     config: PasetoConfig,
     cache: Arc<dyn CacheService>,
     argon2: Argon2<'static>,
-    local_key: SymmetricKey<V4>,
-    private_key: Option<AsymmetricSecretKey<V4>>,
-    public_key: Option<AsymmetricPublicKey<V4>>,
+    keys: PasetoKeyRing,
+    session_index: Option<Arc<dyn SessionRepository>>,
+    role_service: Option<Arc<RoleService>>,
 }
 
 impl PasetoSecurityService {
     pub fn new(config: PasetoConfig, cache: Arc<dyn CacheService>) -> AppResult<Self> {
         // Initialize PASETO keys
-        let local_key = SymmetricKey::<V4>::from(&config.local_key)
-            .map_err(|e| AppError::InternalServer(format!("Invalid local key: {}", e)))?;
-
-        let (private_key, public_key) = if !config.use_local_tokens {
-            let private_key = AsymmetricSecretKey::<V4>::from(&config.private_key)
-                .map_err(|e| AppError::InternalServer(format!("Invalid private key: {}", e)))?;
-            let public_key = AsymmetricPublicKey::<V4>::from(&config.public_key)
-                .map_err(|e| AppError::InternalServer(format!("Invalid public key: {}", e)))?;
-            (Some(private_key), Some(public_key))
-        } else {
-            (None, None)
-        };
+        let keys = PasetoKeyRing::from_paserk(
+            config.local_key.as_deref(),
+            config.secret_key.as_deref(),
+            &config.previous_keys,
+        )?;
+        if config.use_local_tokens && !keys.can_encrypt() {
+            return Err(AppError::Configuration("A PASETO local key is required for local tokens".to_string()));
+        }
+        if !config.use_local_tokens && !keys.can_sign() {
+            return Err(AppError::Configuration("A PASETO secret key is required for public tokens".to_string()));
+        }
 
         Ok(Self {
             config,
             cache,
             argon2: Argon2::default(),
-            local_key,
-            private_key,
-            public_key,
+            keys,
+            session_index: None,
+            role_service: None,
         })
     }
 
+    /// Record sessions per user in durable storage so they can be listed and revoked individually
+    pub fn with_session_index(mut self, index: Arc<dyn SessionRepository>) -> Self {
+        self.session_index = Some(index);
+        self
+    }
+
+    /// Resolve token scopes from the database-backed roles instead of the built-in defaults
+    pub fn with_role_service(mut self, roles: Arc<RoleService>) -> Self {
+        self.role_service = Some(roles);
+        self
+    }
+
     /// Generate secure session ID
     fn generate_session_id() -> String {
         Uuid::new_v4().to_string()
@@ -158,39 +182,19 @@ impl PasetoSecurityService {
         Uuid::new_v4().to_string()
     }
 
-    /// Get user permissions based on role
-    fn get_user_permissions(role: &UserRole) -> Vec<String> {
-        match role {
-            UserRole::Citizen => vec![
-                "report_disaster".to_string(),
-                "view_disasters".to_string(),
-                "receive_notifications".to_string(),
-            ],
-            UserRole::Volunteer => vec![
-                "report_disaster".to_string(),
-                "view_disasters".to_string(),
-                "receive_notifications".to_string(),
-                "volunteer_response".to_string(),
-            ],
-            UserRole::Admin => vec![
-                "report_disaster".to_string(),
-                "view_disasters".to_string(),
-                "receive_notifications".to_string(),
-                "respond_to_disaster".to_string(),
-                "update_disaster_status".to_string(),
-                "manage_users".to_string(),
-                "manage_disasters".to_string(),
-                "send_emergency_alerts".to_string(),
-            ],
-            UserRole::SystemAdmin => vec![
-                "all_permissions".to_string(),
-
-            ],
-            UserRole::SuperAdmin => vec![
-                "all_permissions".to_string(),
-            ],
-            _ => vec![]
+    /// Permission strings granted to the user, as checked by the policy engine and carried in `scope`
+    async fn get_user_permissions(&self, user_id: &UserId, role: &UserRole) -> AppResult<Vec<String>> {
+        match &self.role_service {
+            Some(roles) => roles.permissions_for(user_id, role).await,
+            None => Ok(role.default_permissions().iter().map(|p| p.as_str().to_string()).collect()),
+        }
+    }
 
+    /// Role the session acts as, taking built-in roles assigned through `user_roles` into account
+    async fn get_user_role(&self, user: &User) -> AppResult<UserRole> {
+        match &self.role_service {
+            Some(roles) => roles.primary_role(&user.id, user.role()).await,
+            None => Ok(user.role().clone()),
         }
     }
 
@@ -198,43 +202,17 @@ impl PasetoSecurityService {
     fn encode_claims(&self, claims_json: &str) -> AppResult<String> {
         let paseto_claims = Claims::from_bytes(claims_json.as_bytes())
             .map_err(|e| AppError::InternalServer(format!("Failed to create PASETO claims: {}", e)))?;
-        if self.config.use_local_tokens {
-            pasetors::local::encrypt(&self.local_key, &paseto_claims, None, None)
-                .map_err(|e| AppError::InternalServer(format!("PASETO encryption failed: {}", e)))
-        } else {
-            if let Some(private_key) = &self.private_key {
-                pasetors::public::sign(private_key, &paseto_claims, None, None)
-                    .map_err(|e| AppError::InternalServer(format!("PASETO signing failed: {}", e)))
-            } else {
-                Err(AppError::InternalServer("Private key not available for public tokens".to_string()))
-            }
-        }
+        self.keys.issue(&paseto_claims, self.config.use_local_tokens)
     }
 
     /// Helper: decrypt/verify token and return raw claims JSON
     fn decode_token(&self, token: &str) -> AppResult<String> {
-        if self.config.use_local_tokens {
-            let untrusted_token = UntrustedToken::<Local, V4>::try_from(token)
-                .map_err(|e| AppError::Unauthorized(format!("Invalid local token format: {}", e)))?;
-            let validation_rules = ClaimsValidationRules::new();
-
-            let trusted_token = pasetors::local::decrypt(&self.local_key, &untrusted_token, &validation_rules, None, None)
-                .map_err(|e| AppError::Unauthorized(format!("Token decryption failed: {}", e)))?;
-            String::from_utf8(trusted_token.payload().as_bytes().to_vec())
-                .map_err(|e| AppError::Unauthorized(format!("Invalid token payload: {}", e)))
-        } else {
-            let untrusted_token = UntrustedToken::<Public, V4>::try_from(token)
-                .map_err(|e| AppError::Unauthorized(format!("Invalid public token format: {}", e)))?;
-            if let Some(public_key) = &self.public_key {
-                let validation_rules = ClaimsValidationRules::new();
-                let trusted_token = pasetors::public::verify(public_key, &untrusted_token, &validation_rules, None, None)
-                    .map_err(|e| AppError::Unauthorized(format!("Token verification failed: {}", e)))?;
-                String::from_utf8(trusted_token.payload().as_bytes().to_vec())
-                    .map_err(|e| AppError::Unauthorized(format!("Invalid token payload: {}", e)))
-            } else {
-                Err(AppError::InternalServer("Public key not available for token verification".to_string()))
-            }
-        }
+        self.keys.open(token)
+    }
+
+    /// Keys partner services can use to verify `v4.public` tokens offline
+    pub fn public_keys(&self) -> AppResult<Vec<PublicKeyInfo>> {
+        self.keys.public_keys()
     }
 
     /// Create PASETO token for user (legacy: creates access token only)
@@ -250,13 +228,14 @@ impl PasetoSecurityService {
         let now = Utc::now();
         let exp = now + ChronoDuration::hours(self.config.token_expiration_hours as i64);
 
-        let permissions = Self::get_user_permissions(user.role());
+        let role = self.get_user_role(user).await?;
+        let permissions = self.get_user_permissions(&user.id, &role).await?;
 
         // Create token claims
         let claims = PasetoTokenClaims {
             sub: user.id().to_string(),
             email: user.email().value().to_string(),
-            role: format!("{:?}", user.role()),
+            role: format!("{:?}", role),
             session_id: session_id.clone(),
             iat: now.to_rfc3339(),
             exp: exp.to_rfc3339(),
@@ -269,10 +248,11 @@ impl PasetoSecurityService {
         };
 
         // Store session in cache
+        let generation = self.current_generation(&user.id).await?;
         let session = SecureAuthSession {
             user_id: user.id.clone(),
             email: user.email().clone(),
-            role: user.role().clone(),
+            role: role.clone(),
             session_id: session_id.clone(),
             token_id: token_id.clone(),
             created_at: now,
@@ -283,7 +263,9 @@ impl PasetoSecurityService {
             device_fingerprint,
             permissions,
             is_elevated: false,
-            mfa_verified: false, // Should be set based on actual MFA verification
+            mfa_verified: false, // Set by create_elevated_session after a verified second factor
+            elevated_until: None,
+            generation,
             refresh_token_id: None,
             refresh_expires_at: None,
         };
@@ -293,6 +275,7 @@ impl PasetoSecurityService {
             .map_err(|e| AppError::InternalServer(format!("Failed to serialize session: {}", e)))?;
 
         self.cache.set_string(&session_key, session_json, Some(Duration::from_secs(self.config.session_timeout_hours * 3600))).await?;
+        self.index_session(&session).await;
 
         // Create PASETO token
         let claims_json = serde_json::to_string(&claims)
@@ -339,6 +322,8 @@ impl PasetoSecurityService {
             return Err(AppError::Unauthorized("Session expired".to_string()));
         }
 
+        self.ensure_current_generation(&session_key, &session).await?;
+
         // Validate token ID matches (access token)
         if session.token_id != claims.jti {
             return Err(AppError::Unauthorized("Token ID mismatch".to_string()));
@@ -368,13 +353,14 @@ impl PasetoSecurityService {
         let access_exp = now + ChronoDuration::minutes(self.config.access_token_ttl_minutes as i64);
         let refresh_exp = now + ChronoDuration::days(self.config.refresh_token_ttl_days as i64);
 
-        let permissions = Self::get_user_permissions(user.role());
+        let role = self.get_user_role(user).await?;
+        let permissions = self.get_user_permissions(&user.id, &role).await?;
 
         // Access claims
         let access_claims = PasetoTokenClaims {
             sub: user.id().to_string(),
             email: user.email().value().to_string(),
-            role: format!("{:?}", user.role()),
+            role: format!("{:?}", role),
             session_id: session_id.clone(),
             iat: now.to_rfc3339(),
             exp: access_exp.to_rfc3339(),
@@ -390,7 +376,7 @@ impl PasetoSecurityService {
         let refresh_claims = PasetoTokenClaims {
             sub: user.id().to_string(),
             email: user.email().value().to_string(),
-            role: format!("{:?}", user.role()),
+            role: format!("{:?}", role),
             session_id: session_id.clone(),
             iat: now.to_rfc3339(),
             exp: refresh_exp.to_rfc3339(),
@@ -403,10 +389,11 @@ impl PasetoSecurityService {
         };
 
         // Persist session
+        let generation = self.current_generation(&user.id).await?;
         let session = SecureAuthSession {
             user_id: user.id.clone(),
             email: user.email().clone(),
-            role: user.role().clone(),
+            role: role.clone(),
             session_id: session_id.clone(),
             token_id: access_jti.clone(),
             created_at: now,
@@ -419,6 +406,8 @@ impl PasetoSecurityService {
             permissions,
             is_elevated: false,
             mfa_verified: false,
+            elevated_until: None,
+            generation,
             refresh_token_id: Some(refresh_jti.clone()),
             refresh_expires_at: Some(refresh_exp),
         };
@@ -430,6 +419,7 @@ impl PasetoSecurityService {
         let ttl_secs = (session.expires_at - now).num_seconds().max(0) as u64;
         let ttl = if ttl_secs == 0 { Some(self.config.session_timeout_hours * 3600) } else { Some(ttl_secs) };
         self.cache.set_string(&session_key, session_json, ttl.map(Duration::from_secs)).await?;
+        self.index_session(&session).await;
 
         // Encode tokens
         let access_token = self.encode_claims(&serde_json::to_string(&access_claims)
@@ -473,6 +463,8 @@ impl PasetoSecurityService {
             return Err(AppError::Unauthorized("Session expired".to_string()));
         }
 
+        self.ensure_current_generation(&session_key, &session).await?;
+
         // Validate refresh token id
         match (&session.refresh_token_id, &session.refresh_expires_at) {
             (Some(rid), Some(r_exp)) => {
@@ -490,6 +482,9 @@ impl PasetoSecurityService {
         let mut session = self.validate_refresh_token(refresh_token).await?;
         let now = Utc::now();
 
+        // Role changes made since login reach the session on its next refresh
+        session.permissions = self.get_user_permissions(&session.user_id, &session.role).await?;
+
         // Generate new access token
         let new_access_jti = Self::generate_token_id();
         let access_exp = now + ChronoDuration::minutes(self.config.access_token_ttl_minutes as i64);
@@ -561,7 +556,8 @@ impl PasetoSecurityService {
     }
 
     /// Create elevated session for sensitive operations
-    pub async fn create_elevated_session(&self, session_id: &str, mfa_token: Option<&str>) -> AppResult<String> {
+    /// The session only counts as MFA-verified when a verification for the same user is supplied
+    pub async fn create_elevated_session(&self, session_id: &str, mfa: Option<&MfaVerification>) -> AppResult<SecureAuthSession> {
         let session_key = format!("session:{}", session_id);
         let session_json: String = self.cache.get_string(&session_key).await?
             .ok_or_else(|| AppError::Unauthorized("Session not found".to_string()))?;
@@ -569,26 +565,72 @@ impl PasetoSecurityService {
         let mut session: SecureAuthSession = serde_json::from_str(&session_json)
             .map_err(|e| AppError::InternalServer(format!("Failed to deserialize session: {}", e)))?;
 
-        // Verify MFA if required for elevation
-        if let Some(_mfa_token) = mfa_token {
-            // TODO: Implement MFA verification
-            session.mfa_verified = true;
+        if let Some(verification) = mfa {
+            if verification.user_id() != &session.user_id {
+                return Err(AppError::Unauthorized("MFA verification belongs to a different user".to_string()));
+            }
         }
 
-        // Mark session as elevated
+        // Elevation is time-boxed separately so the underlying session keeps its own lifetime
+        let now = Utc::now();
         session.is_elevated = true;
-        session.last_activity = Utc::now();
-
-        // Set shorter expiration for elevated session
-        let elevated_exp = Utc::now() + ChronoDuration::minutes(self.config.elevated_session_minutes as i64);
-        session.expires_at = elevated_exp;
+        session.mfa_verified = mfa.is_some();
+        session.elevated_until = Some(now + ChronoDuration::minutes(self.config.elevated_session_minutes as i64));
+        session.last_activity = now;
 
-        // Update session in cache with shorter TTL
         let updated_session_json = serde_json::to_string(&session)
             .map_err(|e| AppError::InternalServer(format!("Failed to serialize session: {}", e)))?;
-        self.cache.set_string(&session_key, updated_session_json, Some(Duration::from_secs(self.config.elevated_session_minutes * 60))).await?;
+        let ttl_secs = (session.expires_at - now).num_seconds().max(0) as u64;
+        self.cache.set_string(&session_key, updated_session_json, Some(Duration::from_secs(ttl_secs))).await?;
+
+        Ok(session)
+    }
+
+    fn generation_key(user_id: &UserId) -> String {
+        format!("session_generation:{}", user_id)
+    }
+
+    async fn current_generation(&self, user_id: &UserId) -> AppResult<i64> {
+        Ok(self.cache.get_string(&Self::generation_key(user_id)).await?
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(0))
+    }
+
+    /// Reject sessions created before the user's last bulk revocation
+    async fn ensure_current_generation(&self, session_key: &str, session: &SecureAuthSession) -> AppResult<()> {
+        if session.generation < self.current_generation(&session.user_id).await? {
+            self.cache.delete(session_key).await?;
+            return Err(AppError::Unauthorized("Session revoked".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Revoke every session of a user, optionally keeping the one making the request
+    pub async fn revoke_user_sessions(&self, user_id: &UserId, keep_session_id: Option<&str>) -> AppResult<()> {
+        let generation = self.cache.increment(&Self::generation_key(user_id), 1).await?;
+
+        // The generation bump covers unindexed sessions; indexed ones are also removed outright
+        if let Some(index) = &self.session_index {
+            let keep = keep_session_id.and_then(|id| Uuid::parse_str(id).ok());
+            for id in index.revoke_all_for_user(user_id, keep.as_ref()).await? {
+                self.cache.delete(&format!("session:{}", id)).await?;
+            }
+        }
+
+        let Some(session_id) = keep_session_id else { return Ok(()) };
+        let session_key = format!("session:{}", session_id);
+        let Some(session_json) = self.cache.get_string(&session_key).await? else { return Ok(()) };
+        let mut session: SecureAuthSession = serde_json::from_str(&session_json)
+            .map_err(|e| AppError::InternalServer(format!("Failed to deserialize session: {}", e)))?;
+        if &session.user_id != user_id {
+            return Ok(());
+        }
 
-        Ok(session_id.to_string())
+        session.generation = generation;
+        let updated_session_json = serde_json::to_string(&session)
+            .map_err(|e| AppError::InternalServer(format!("Failed to serialize session: {}", e)))?;
+        let ttl_secs = (session.expires_at - Utc::now()).num_seconds().max(0) as u64;
+        self.cache.set_string(&session_key, updated_session_json, Some(Duration::from_secs(ttl_secs))).await
     }
 
     /// Revoke session and invalidate all related tokens
@@ -601,13 +643,271 @@ impl PasetoSecurityService {
 
         // Remove session
         self.cache.delete(&session_key).await?;
-        
+
+        if let (Some(index), Ok(id)) = (&self.session_index, Uuid::parse_str(session_id)) {
+            index.revoke(&id).await?;
+        }
+
         Ok(())
     }
 
+    /// Revoke one session of a user; returns false when the session is not theirs
+    pub async fn revoke_user_session(&self, user_id: &UserId, session_id: &str) -> AppResult<bool> {
+        let indexed = match (&self.session_index, Uuid::parse_str(session_id)) {
+            (Some(index), Ok(id)) => index.find_by_id(&id).await?
+                .is_some_and(|entry| &entry.user_id == user_id && entry.is_active()),
+            _ => false,
+        };
+        let owned = indexed || self.load_session(session_id).await?
+            .is_some_and(|session| &session.user_id == user_id);
+        if !owned {
+            return Ok(false);
+        }
+
+        self.revoke_session(session_id).await?;
+        Ok(true)
+    }
+
+    /// Active sessions of a user with live activity from the session cache
+    pub async fn list_user_sessions(&self, user_id: &UserId) -> AppResult<Vec<ActiveSessionInfo>> {
+        let index = self.session_index.as_ref()
+            .ok_or_else(|| AppError::Configuration("Session index is not configured".to_string()))?;
+        let generation = self.current_generation(user_id).await?;
+        let now = Utc::now();
+
+        let mut sessions = Vec::new();
+        for entry in index.find_active_by_user(user_id).await? {
+            let live = self.load_session(&entry.id.to_string()).await?
+                .filter(|session| &session.user_id == user_id && session.generation >= generation && session.expires_at > now);
+            let Some(session) = live else {
+                // Ended without passing through revocation (cache expiry or a stale generation)
+                index.revoke(&entry.id).await?;
+                continue;
+            };
+
+            sessions.push(ActiveSessionInfo {
+                mfa_elevated: session.is_mfa_elevated(),
+                session_id: session.session_id,
+                ip_address: session.ip_address,
+                user_agent: session.user_agent,
+                device_fingerprint: session.device_fingerprint,
+                created_at: session.created_at,
+                last_activity: session.last_activity,
+                expires_at: session.expires_at,
+            });
+        }
+        Ok(sessions)
+    }
+
+    async fn load_session(&self, session_id: &str) -> AppResult<Option<SecureAuthSession>> {
+        let Some(session_json) = self.cache.get_string(&format!("session:{}", session_id)).await? else {
+            return Ok(None);
+        };
+        serde_json::from_str(&session_json)
+            .map(Some)
+            .map_err(|e| AppError::InternalServer(format!("Failed to deserialize session: {}", e)))
+    }
+
+    /// Best-effort: a session missing from the index is still covered by bulk revocation
+    async fn index_session(&self, session: &SecureAuthSession) {
+        let (Some(index), Ok(id)) = (&self.session_index, Uuid::parse_str(&session.session_id)) else { return };
+        let entry = UserSession {
+            id,
+            user_id: session.user_id,
+            ip_address: session.ip_address.clone(),
+            user_agent: session.user_agent.clone(),
+            device_fingerprint: session.device_fingerprint.clone(),
+            created_at: session.created_at,
+            expires_at: session.expires_at,
+            revoked_at: None,
+        };
+        if let Err(e) = index.record(&entry).await {
+            tracing::warn!("Failed to index session {}: {}", session.session_id, e);
+        }
+    }
+
     /// Check if session is revoked
     pub async fn is_session_revoked(&self, session_id: &str) -> AppResult<bool> {
         let revocation_key = format!("revoked:{}", session_id);
         Ok(self.cache.exists(&revocation_key).await?)
     }
 }
+
+#[async_trait::async_trait]
+impl SessionRevoker for PasetoSecurityService {
+    async fn revoke_user_sessions(&self, user_id: &UserId, keep_session_id: Option<&str>) -> AppResult<()> {
+        PasetoSecurityService::revoke_user_sessions(self, user_id, keep_session_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use async_trait::async_trait;
+    use crate::domain::value_objects::Username;
+    use crate::infrastructure::cache::InMemoryCache;
+    use crate::shared::policy::{Attributes, PolicyEngine};
+    use crate::shared::Permission;
+
+    /// Session index kept in memory, standing in for the `auth_sessions` table
+    #[derive(Default)]
+    struct MemorySessionIndex {
+        sessions: Mutex<Vec<UserSession>>,
+    }
+
+    #[async_trait]
+    impl SessionRepository for MemorySessionIndex {
+        async fn record(&self, session: &UserSession) -> AppResult<()> {
+            self.sessions.lock().unwrap().push(session.clone());
+            Ok(())
+        }
+
+        async fn find_by_id(&self, id: &Uuid) -> AppResult<Option<UserSession>> {
+            Ok(self.sessions.lock().unwrap().iter().find(|s| &s.id == id).cloned())
+        }
+
+        async fn find_active_by_user(&self, user_id: &UserId) -> AppResult<Vec<UserSession>> {
+            Ok(self.sessions.lock().unwrap().iter()
+                .filter(|s| &s.user_id == user_id && s.is_active())
+                .cloned()
+                .collect())
+        }
+
+        async fn revoke(&self, id: &Uuid) -> AppResult<bool> {
+            let mut sessions = self.sessions.lock().unwrap();
+            match sessions.iter_mut().find(|s| &s.id == id && s.revoked_at.is_none()) {
+                Some(session) => {
+                    session.revoked_at = Some(Utc::now());
+                    Ok(true)
+                }
+                None => Ok(false),
+            }
+        }
+
+        async fn revoke_all_for_user(&self, user_id: &UserId, keep: Option<&Uuid>) -> AppResult<Vec<Uuid>> {
+            let mut revoked = Vec::new();
+            for session in self.sessions.lock().unwrap().iter_mut() {
+                if &session.user_id == user_id && session.revoked_at.is_none() && Some(&session.id) != keep {
+                    session.revoked_at = Some(Utc::now());
+                    revoked.push(session.id);
+                }
+            }
+            Ok(revoked)
+        }
+    }
+
+    fn service() -> PasetoSecurityService {
+        PasetoSecurityService::new(PasetoConfig::default(), Arc::new(InMemoryCache::new(100, 300))).unwrap()
+    }
+
+    fn indexed_service(cache: Arc<InMemoryCache>) -> PasetoSecurityService {
+        PasetoSecurityService::new(PasetoConfig::default(), cache).unwrap()
+            .with_session_index(Arc::new(MemorySessionIndex::default()))
+    }
+
+    fn user(username: &str, role: UserRole) -> User {
+        User::new(
+            Email::new(format!("{}@example.id", username)).unwrap(),
+            Username::new(username.to_string()).unwrap(),
+            "Test User".to_string(),
+            "hash".to_string(),
+            role,
+        ).unwrap()
+    }
+
+    async fn login(paseto: &PasetoSecurityService, user: &User) -> SecureAuthSession {
+        let tokens = paseto.create_token_pair(user, None, None, None).await.unwrap();
+        paseto.validate_paseto_token(&tokens.access_token).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn sessions_carry_the_users_role() {
+        let paseto = service();
+        let engine = PolicyEngine::builtin();
+
+        let coordinator = login(&paseto, &user("koordinator", UserRole::Coordinator)).await;
+        assert_eq!(coordinator.role, UserRole::Coordinator);
+        let subject = coordinator.policy_subject().with("provinces", vec!["Jawa Timur".to_string()]);
+        let disaster = Attributes::new().with("province", "Jawa Timur").with("status", "reported");
+        assert!(engine.decide(&subject, "disasters:verify", &disaster).allowed);
+        assert!(engine.decide(&subject, "disasters:update_status", &disaster).allowed);
+
+        let responder = login(&paseto, &user("responder", UserRole::Responder)).await;
+        assert!(engine.decide(&responder.policy_subject(), Permission::ManageEmergencyResponse.as_str(), &Attributes::new()).allowed);
+
+        let citizen = login(&paseto, &user("warga", UserRole::Citizen)).await;
+        assert!(!engine.decide(&citizen.policy_subject(), "disasters:verify", &disaster).allowed);
+    }
+
+    #[tokio::test]
+    async fn revoking_all_sessions_keeps_the_current_one() {
+        let paseto = indexed_service(Arc::new(InMemoryCache::new(100, 300)));
+        let owner = user("pemilik", UserRole::Citizen);
+        let bystander = user("tetangga", UserRole::Citizen);
+
+        let laptop = paseto.create_token_pair(&owner, None, Some("laptop".to_string()), None).await.unwrap();
+        let phone = paseto.create_token_pair(&owner, None, Some("phone".to_string()), None).await.unwrap();
+        let other = paseto.create_token_pair(&bystander, None, None, None).await.unwrap();
+        assert_eq!(paseto.list_user_sessions(&owner.id).await.unwrap().len(), 2);
+
+        paseto.revoke_user_sessions(&owner.id, Some(&laptop.session_id)).await.unwrap();
+
+        assert!(paseto.validate_paseto_token(&laptop.access_token).await.is_ok());
+        assert!(paseto.validate_paseto_token(&phone.access_token).await.is_err());
+        assert!(paseto.validate_paseto_token(&other.access_token).await.is_ok());
+        let listed: Vec<String> = paseto.list_user_sessions(&owner.id).await.unwrap()
+            .into_iter()
+            .map(|session| session.session_id)
+            .collect();
+        assert_eq!(listed, vec![laptop.session_id]);
+    }
+
+    #[tokio::test]
+    async fn generation_bump_revokes_unindexed_sessions() {
+        let paseto = service();
+        let owner = user("pemilik", UserRole::Citizen);
+        let first = paseto.create_token_pair(&owner, None, None, None).await.unwrap();
+        let second = paseto.create_token_pair(&owner, None, None, None).await.unwrap();
+
+        paseto.revoke_user_sessions(&owner.id, None).await.unwrap();
+
+        assert!(paseto.validate_paseto_token(&first.access_token).await.is_err());
+        assert!(paseto.validate_paseto_token(&second.access_token).await.is_err());
+        // Sessions created after the revocation are unaffected
+        let fresh = paseto.create_token_pair(&owner, None, None, None).await.unwrap();
+        assert!(paseto.validate_paseto_token(&fresh.access_token).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn single_revoke_only_reaches_the_owners_session() {
+        let paseto = indexed_service(Arc::new(InMemoryCache::new(100, 300)));
+        let owner = user("pemilik", UserRole::Citizen);
+        let intruder = user("penyusup", UserRole::Citizen);
+        let tokens = paseto.create_token_pair(&owner, None, None, None).await.unwrap();
+
+        assert!(!paseto.revoke_user_session(&intruder.id, &tokens.session_id).await.unwrap());
+        assert!(paseto.validate_paseto_token(&tokens.access_token).await.is_ok());
+
+        assert!(paseto.revoke_user_session(&owner.id, &tokens.session_id).await.unwrap());
+        assert!(paseto.validate_paseto_token(&tokens.access_token).await.is_err());
+        assert!(paseto.list_user_sessions(&owner.id).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn listing_drops_sessions_that_left_the_cache() {
+        let cache = Arc::new(InMemoryCache::new(100, 300));
+        let paseto = indexed_service(cache.clone());
+        let owner = user("pemilik", UserRole::Citizen);
+        let kept = paseto.create_token_pair(&owner, None, None, None).await.unwrap();
+        let evicted = paseto.create_token_pair(&owner, None, None, None).await.unwrap();
+
+        cache.delete(&format!("session:{}", evicted.session_id)).await.unwrap();
+
+        let listed = paseto.list_user_sessions(&owner.id).await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].session_id, kept.session_id);
+        // The stale index entry is revoked, so it stays gone
+        assert_eq!(paseto.list_user_sessions(&owner.id).await.unwrap().len(), 1);
+    }
+}
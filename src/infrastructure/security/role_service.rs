@@ -0,0 +1,216 @@
+/// Role administration and permission resolution
+/// Caches the role catalog and per-user assignments, and invalidates them whenever a role or assignment changes
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::domain::entities::role::{Role, RoleAuditEntry};
+use crate::domain::ports::repositories::RoleRepository;
+use crate::infrastructure::cache::CacheService;
+use crate::shared::{AppError, AppResult, Permission, UserId, UserRole};
+
+/// Upper bound on how long an instance without shared cache invalidation serves stale roles
+const ROLE_CACHE_TTL: Duration = Duration::from_secs(300);
+const CATALOG_KEY: &str = "roles:catalog";
+
+/// Changes to a role; unset fields keep their value
+#[derive(Debug, Clone, Default)]
+pub struct RoleChanges {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub permissions: Option<Vec<Permission>>,
+}
+
+pub struct RoleService {
+    repository: Arc<dyn RoleRepository>,
+    cache: Arc<dyn CacheService>,
+}
+
+impl RoleService {
+    pub fn new(repository: Arc<dyn RoleRepository>, cache: Arc<dyn CacheService>) -> Self {
+        Self { repository, cache }
+    }
+
+    fn user_key(user_id: &UserId) -> String {
+        format!("roles:user:{}", user_id)
+    }
+
+    /// Every role with its permissions, from cache when possible
+    pub async fn catalog(&self) -> AppResult<Vec<Role>> {
+        if let Some(cached) = self.cache.get_string(CATALOG_KEY).await? {
+            match serde_json::from_str(&cached) {
+                Ok(roles) => return Ok(roles),
+                Err(e) => tracing::warn!("Discarding unreadable role catalog cache: {}", e),
+            }
+        }
+
+        let roles = self.repository.find_all().await?;
+        let json = serde_json::to_string(&roles)
+            .map_err(|e| AppError::InternalServer(format!("Failed to serialize role catalog: {}", e)))?;
+        self.cache.set_string(CATALOG_KEY, json, Some(ROLE_CACHE_TTL)).await?;
+        Ok(roles)
+    }
+
+    /// Ids of the roles assigned to a user, from cache when possible
+    async fn assigned_role_ids(&self, user_id: &UserId) -> AppResult<Vec<i32>> {
+        let key = Self::user_key(user_id);
+        if let Some(cached) = self.cache.get_string(&key).await? {
+            if let Ok(ids) = serde_json::from_str(&cached) {
+                return Ok(ids);
+            }
+        }
+
+        let ids: Vec<i32> = self.repository.find_by_user(user_id).await?
+            .iter()
+            .map(|role| role.id)
+            .collect();
+        let json = serde_json::to_string(&ids)
+            .map_err(|e| AppError::InternalServer(format!("Failed to serialize role assignments: {}", e)))?;
+        self.cache.set_string(&key, json, Some(ROLE_CACHE_TTL)).await?;
+        Ok(ids)
+    }
+
+    /// Highest-ranked role among the account role `fallback` and the built-in roles assigned on top of it
+    fn primary_among(catalog: &[Role], assigned: &[i32], fallback: &UserRole) -> UserRole {
+        catalog.iter()
+            .filter(|candidate| candidate.is_system && assigned.contains(&candidate.id))
+            .filter_map(|builtin| UserRole::from_name(&builtin.name))
+            .fold(fallback.clone(), |best, candidate| if candidate.has_minimum_level(&best) { candidate } else { best })
+    }
+
+    /// The role a user acts as: a built-in role assigned through `user_roles` can raise their account role, never lower it
+    pub async fn primary_role(&self, user_id: &UserId, fallback: &UserRole) -> AppResult<UserRole> {
+        let catalog = self.catalog().await?;
+        let assigned = self.assigned_role_ids(user_id).await?;
+        Ok(Self::primary_among(&catalog, &assigned, fallback))
+    }
+
+    /// Permission strings for a user: their primary role's grants plus those of every assigned role
+    /// The hardcoded defaults apply only when the primary role is missing from the database
+    pub async fn permissions_for(&self, user_id: &UserId, role: &UserRole) -> AppResult<Vec<String>> {
+        let catalog = self.catalog().await?;
+        let assigned = self.assigned_role_ids(user_id).await?;
+        let primary = Self::primary_among(&catalog, &assigned, role);
+
+        let mut permissions = catalog.iter()
+            .find(|candidate| candidate.is_system && candidate.name == primary.as_str())
+            .map(|builtin| builtin.permissions.clone())
+            .unwrap_or_else(|| primary.default_permissions());
+        for granted in catalog.iter().filter(|candidate| assigned.contains(&candidate.id)) {
+            permissions.extend(granted.permissions.iter().cloned());
+        }
+
+        let mut names: Vec<String> = Vec::with_capacity(permissions.len());
+        for permission in permissions {
+            let name = permission.as_str();
+            if !names.iter().any(|existing| existing == name) {
+                names.push(name.to_string());
+            }
+        }
+        Ok(names)
+    }
+
+    async fn invalidate_catalog(&self) -> AppResult<()> {
+        self.cache.delete(CATALOG_KEY).await
+    }
+
+    async fn invalidate_user(&self, user_id: &UserId) -> AppResult<()> {
+        self.cache.delete(&Self::user_key(user_id)).await
+    }
+
+    pub async fn get_role(&self, id: i32) -> AppResult<Role> {
+        self.repository.find_by_id(id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Role {} not found", id)))
+    }
+
+    pub async fn create_role(&self, role: Role, actor: &UserId) -> AppResult<Role> {
+        let created = self.repository.create(&role, actor).await?;
+        self.invalidate_catalog().await?;
+        tracing::info!("Role '{}' ({}) created by {}", created.name, created.id, actor);
+        Ok(created)
+    }
+
+    pub async fn update_role(&self, id: i32, changes: RoleChanges, actor: &UserId) -> AppResult<Role> {
+        let mut role = self.get_role(id).await?;
+        if let Some(name) = changes.name {
+            role.rename(&name)?;
+        }
+        if let Some(description) = changes.description {
+            role.description = Some(description);
+        }
+        if let Some(permissions) = changes.permissions {
+            role.set_permissions(permissions);
+        }
+
+        let updated = self.repository.update(&role, actor).await?;
+        self.invalidate_catalog().await?;
+        tracing::info!("Role '{}' ({}) updated by {}", updated.name, updated.id, actor);
+        Ok(updated)
+    }
+
+    pub async fn delete_role(&self, id: i32, actor: &UserId) -> AppResult<()> {
+        let role = self.get_role(id).await?;
+        if !self.repository.delete(id, actor).await? {
+            return Err(AppError::NotFound(format!("Role {} not found", id)));
+        }
+        // Cached assignments of former holders may still list the id; it no longer resolves against the catalog
+        self.invalidate_catalog().await?;
+        tracing::info!("Role '{}' ({}) deleted by {}", role.name, id, actor);
+        Ok(())
+    }
+
+    /// Users whose sessions carry the role's permissions
+    pub async fn holders(&self, role_id: i32) -> AppResult<Vec<UserId>> {
+        self.repository.find_holders(role_id).await
+    }
+
+    pub async fn user_roles(&self, user_id: &UserId) -> AppResult<Vec<Role>> {
+        self.repository.find_by_user(user_id).await
+    }
+
+    /// Returns false when the user already held the role
+    pub async fn assign_role(&self, user_id: &UserId, role_id: i32, actor: &UserId) -> AppResult<bool> {
+        let assigned = self.repository.assign(user_id, role_id, actor).await?;
+        self.invalidate_user(user_id).await?;
+        Ok(assigned)
+    }
+
+    pub async fn revoke_role(&self, user_id: &UserId, role_id: i32, actor: &UserId) -> AppResult<bool> {
+        let revoked = self.repository.revoke(user_id, role_id, actor).await?;
+        self.invalidate_user(user_id).await?;
+        Ok(revoked)
+    }
+
+    pub async fn audit_trail(&self, role_id: Option<i32>, limit: i64, offset: i64) -> AppResult<Vec<RoleAuditEntry>> {
+        self.repository.find_audit(role_id, limit, offset).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn role(id: i32, name: &str, is_system: bool) -> Role {
+        let mut role = Role::new(name.to_string(), None, vec![]).unwrap();
+        role.id = id;
+        role.is_system = is_system;
+        role
+    }
+
+    #[test]
+    fn assigned_builtin_role_becomes_primary() {
+        let catalog = vec![
+            role(1, UserRole::Volunteer.as_str(), true),
+            role(2, UserRole::Coordinator.as_str(), true),
+            role(3, "flood_desk", false),
+        ];
+
+        // A reporter promoted through user_roles acts as the highest built-in role it holds
+        assert_eq!(RoleService::primary_among(&catalog, &[1, 2, 3], &UserRole::Reporter), UserRole::Coordinator);
+        // Assigning a lower built-in role never downgrades the account role
+        assert_eq!(RoleService::primary_among(&catalog, &[1], &UserRole::Coordinator), UserRole::Coordinator);
+        // Custom roles add permissions but never replace the account role
+        assert_eq!(RoleService::primary_among(&catalog, &[3], &UserRole::Reporter), UserRole::Reporter);
+        assert_eq!(RoleService::primary_among(&catalog, &[], &UserRole::Responder), UserRole::Responder);
+    }
+}
@@ -0,0 +1,56 @@
+/// Local disk media storage
+/// Files are written below a root directory and served by the HTTP server under `/media`
+
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+
+use crate::domain::ports::services::MediaStorage;
+use crate::infrastructure::storage::validate_key;
+use crate::shared::{AppError, AppResult};
+
+pub struct LocalMediaStorage {
+    root: PathBuf,
+    public_url: String,
+}
+
+impl LocalMediaStorage {
+    pub fn new(root: impl AsRef<Path>, public_url: String) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+            public_url: public_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+#[async_trait]
+impl MediaStorage for LocalMediaStorage {
+    fn backend(&self) -> &'static str {
+        "local"
+    }
+
+    async fn put(&self, key: &str, _content_type: &str, bytes: Vec<u8>) -> AppResult<String> {
+        validate_key(key)?;
+        let path = self.root.join(key);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await
+                .map_err(|e| AppError::FileSystem(format!("Failed to create {}: {}", parent.display(), e)))?;
+        }
+        tokio::fs::write(&path, bytes).await
+            .map_err(|e| AppError::FileSystem(format!("Failed to write {}: {}", path.display(), e)))?;
+        Ok(format!("{}/{}", self.public_url, key))
+    }
+
+    async fn delete(&self, key: &str) -> AppResult<()> {
+        validate_key(key)?;
+        let path = self.root.join(key);
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(AppError::FileSystem(format!("Failed to delete {}: {}", path.display(), e))),
+        }
+    }
+}
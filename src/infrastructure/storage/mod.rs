@@ -0,0 +1,44 @@
+/// Media storage backends for report photos and videos
+/// Uploads go to a local directory or to any S3-compatible bucket, chosen by `MEDIA_STORAGE`
+
+pub mod local;
+pub mod s3;
+
+use std::sync::Arc;
+
+use crate::config::MediaStorageConfig;
+use crate::domain::ports::services::MediaStorage;
+use crate::shared::{AppError, AppResult};
+
+pub use local::LocalMediaStorage;
+pub use s3::S3MediaStorage;
+
+/// URL path the local backend's files are served under
+pub const LOCAL_MEDIA_ROUTE: &str = "/media";
+
+/// Build the configured backend; `public_base_url` is the server's own address
+pub fn build_media_storage(config: &MediaStorageConfig, public_base_url: &str) -> AppResult<Arc<dyn MediaStorage>> {
+    match config.backend.as_str() {
+        "local" => {
+            let public_url = config.public_url.clone().unwrap_or_else(|| {
+                format!("{}{}", public_base_url.trim_end_matches('/'), LOCAL_MEDIA_ROUTE)
+            });
+            Ok(Arc::new(LocalMediaStorage::new(&config.local_root, public_url)))
+        }
+        "s3" => Ok(Arc::new(S3MediaStorage::new(config.s3.clone(), config.public_url.clone())?)),
+        other => Err(AppError::Configuration(format!("Unknown media storage backend '{}'", other))),
+    }
+}
+
+/// Object keys are generated by the server, but never let one escape the storage root
+pub(crate) fn validate_key(key: &str) -> AppResult<()> {
+    let valid = !key.is_empty()
+        && !key.starts_with('/')
+        && key.split('/').all(|segment| !segment.is_empty() && segment != "." && segment != "..")
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | '-' | '_' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::Validation(format!("Invalid media key '{}'", key)))
+    }
+}
@@ -14,9 +14,11 @@ use terra_siaga::{
     middleware::{cors, errors as error_middleware},
 };
 use terra_siaga::infrastructure::{HealthService, PasetoSecurityService};
+use terra_siaga::infrastructure::security::PasetoKeyRing;
 use terra_siaga::infrastructure::monitoring::{DatabaseHealthChecker, CacheHealthChecker};
 use terra_siaga::infrastructure::database::DbPool;
-use terra_siaga::middleware::{AuthMiddleware, ErrorHandler};
+use terra_siaga::infrastructure::storage::LOCAL_MEDIA_ROUTE;
+use terra_siaga::middleware::{AuthMiddleware, ErrorHandler, RateLimitMiddleware};
 // Add imports for JSON error handling
 use actix_web::error::JsonPayloadError;
 use terra_siaga::middleware::auth::AuthMiddlewareService;
@@ -35,6 +37,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Load environment variables from .env file
     dotenv::dotenv().ok();
 
+    // `terra-siaga generate-paseto-keys` prints a fresh key set for the PASETO_* settings
+    if std::env::args().nth(1).as_deref() == Some("generate-paseto-keys") {
+        let keys = PasetoKeyRing::generate()?;
+        println!("PASETO_LOCAL_KEY={}", keys.local_key);
+        println!("PASETO_SECRET_KEY={}", keys.secret_key);
+        println!("# Public key, for PASETO_PREVIOUS_KEYS once this key is rotated out:");
+        println!("# {}", keys.public_key);
+        return Ok(());
+    }
+
     info!("🚀 Starting Terra Siaga Emergency Response System...");
 
     // Load and validate configuration
@@ -56,6 +68,25 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     info!("📦 Application container built successfully");
 
+    // Start notification outbox delivery workers
+    let outbox_workers = container.notification_outbox_worker.clone().spawn();
+    info!("📨 Started {} notification outbox worker(s)", outbox_workers.len());
+
+    // Feed the realtime gateway from the event bus
+    if config.features.enable_real_time_notifications || config.features.enable_real_time_tracking {
+        container.realtime_hub.clone().spawn(&container.event_bus, container.disaster_repository.clone());
+        info!(
+            "📡 Realtime gateway started ({})",
+            if container.realtime_hub.is_distributed() { "shared over Redis" } else { "this instance only" }
+        );
+    }
+
+    // Keep the analytics read model current from disaster events
+    if config.features.enable_analytics {
+        container.projection_manager.clone().spawn(&container.event_bus);
+        info!("📊 Analytics projections started");
+    }
+
     // Initialize health monitoring service
     let mut health_service = HealthService::new(
         env!("CARGO_PKG_VERSION").to_string(),
@@ -90,6 +121,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Extract CORS origins to avoid lifetime issues (not required by current CORS config)
     // let cors_origins = config.server.cors_origins.clone();
     let server_config = config.server.clone();
+    // Uploaded report media is served by this process only when it lives on local disk
+    let local_media_root = (config.media_storage.backend == "local")
+        .then(|| config.media_storage.local_root.clone());
 
     // Initialize metrics collection
     let _metrics_exporter = metrics_exporter_prometheus::PrometheusBuilder::new()
@@ -129,6 +163,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             )
 
             // Global middleware stack (order matters!)
+            // Rate limits see the normalized path and its matched route pattern
+            .wrap(RateLimitMiddleware::new())
             .wrap(middleware::Logger::new(
                 r#"%a "%r" %s %b "%{Referer}i" "%{User-Agent}i" %T"#
             ))
@@ -156,6 +192,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
             // Configure API routes
             .configure(api::configure_routes)
+            .configure(|cfg| {
+                if let Some(root) = &local_media_root {
+                    cfg.service(actix_files::Files::new(LOCAL_MEDIA_ROUTE, root));
+                }
+            })
     })
     .bind(format!("{}:{}", server_config.host, server_config.port))
     .map_err(|e| {
@@ -12,8 +12,11 @@ use std::sync::Arc;
 use std::rc::Rc;
 use tracing::{error, warn, debug};
 use crate::infrastructure::security::paseto_service::PasetoSecurityService;
+use crate::domain::entities::OrganizationRole;
+use crate::infrastructure::AppContainer;
 use crate::infrastructure::security::SecureAuthSession;
-use crate::shared::types::{UserRole, UserId};
+use crate::shared::policy::{Attributes, Subject};
+use crate::shared::types::{OrganizationId, Permission, UserRole, UserId};
 use crate::shared::error::AppError;
 
 /// Authentication session extracted from PASETO token
@@ -38,6 +41,19 @@ impl SecureAuthSession {
         required_permissions.iter().all(|perm| self.permissions.contains(perm))
     }
 
+    /// Whether the session was elevated with a verified second factor and is still within the window
+    pub fn is_mfa_elevated(&self) -> bool {
+        self.is_elevated
+            && self.mfa_verified
+            && self.elevated_until.is_some_and(|until| until > chrono::Utc::now())
+    }
+
+    /// Subject for policy checks: the role's permissions plus whether the session is MFA-elevated
+    pub fn policy_subject(&self) -> Subject {
+        Subject::new(self.user_id, self.role.clone(), self.permissions.clone())
+            .with("mfa", self.is_mfa_elevated())
+    }
+
     /// Check if user has minimum role level
     pub fn has_minimum_role(&self, min_role: &UserRole) -> bool {
         self.role.has_minimum_level(min_role)
@@ -49,10 +65,27 @@ impl SecureAuthSession {
     }
 }
 
+/// Organization the request was authorized against, taken from the `{organization_id}` path segment
+#[derive(Debug, Clone)]
+pub struct OrganizationScope {
+    pub organization_id: OrganizationId,
+    /// Caller's role in the organization; `None` for platform administrators acting from outside it
+    pub role: Option<OrganizationRole>,
+}
+
+impl OrganizationScope {
+    /// Whether the caller may give `target` to a member, or take it away
+    pub fn can_assign(&self, target: OrganizationRole) -> bool {
+        self.role.map_or(true, |role| role.can_assign(target))
+    }
+}
+
 /// PASETO authentication middleware
 pub struct AuthMiddleware {
     required_role: Option<UserRole>,
     required_permissions: Option<Vec<String>>,
+    organization_permission: Option<Permission>,
+    optional: bool,
 }
 
 impl AuthMiddleware {
@@ -60,6 +93,8 @@ impl AuthMiddleware {
         Self {
             required_role: None,
             required_permissions: None,
+            organization_permission: None,
+            optional: false,
         }
     }
 
@@ -67,6 +102,8 @@ impl AuthMiddleware {
         Self {
             required_role: Some(role),
             required_permissions: None,
+            organization_permission: None,
+            optional: false,
         }
     }
 
@@ -74,6 +111,28 @@ impl AuthMiddleware {
         Self {
             required_role: None,
             required_permissions: Some(permissions),
+            organization_permission: None,
+            optional: false,
+        }
+    }
+
+    /// Require `permission` inside the organization named by the `{organization_id}` path segment.
+    /// Only the caller's membership role counts; platform administrators (`system:manage`) may act in any organization.
+    pub fn for_organization(permission: Permission) -> Self {
+        Self {
+            required_role: None,
+            required_permissions: None,
+            organization_permission: Some(permission),
+            optional: false,
+        }
+    }
+
+    /// Attach a session when a bearer token is sent, but let anonymous requests through.
+    /// A token that is sent must still be valid.
+    pub fn optional() -> Self {
+        Self {
+            optional: true,
+            ..Self::new()
         }
     }
 }
@@ -95,6 +154,8 @@ where
             service: Rc::new(service),
             required_role: self.required_role.clone(),
             required_permissions: self.required_permissions.clone(),
+            organization_permission: self.organization_permission.clone(),
+            optional: self.optional,
         }))
     }
 }
@@ -103,6 +164,8 @@ pub struct AuthMiddlewareService<S> {
     service: Rc<S>,
     required_role: Option<UserRole>,
     required_permissions: Option<Vec<String>>,
+    organization_permission: Option<Permission>,
+    optional: bool,
 }
 
 impl<S, B> Service<ServiceRequest> for AuthMiddlewareService<S>
@@ -121,6 +184,8 @@ where
         let service = Rc::clone(&self.service);
         let required_role = self.required_role.clone();
         let required_permissions = self.required_permissions.clone();
+        let organization_permission = self.organization_permission.clone();
+        let optional = self.optional;
 
         Box::pin(async move {
             // Get PASETO service from app data
@@ -144,6 +209,10 @@ where
 
             let token = match token_opt {
                 Some(t) if !t.is_empty() => t,
+                _ if optional => {
+                    let res = service.call(req).await?;
+                    return Ok(res.map_into_left_body());
+                }
                 _ => {
                     let resp = HttpResponse::Unauthorized()
                         .json(serde_json::json!({
@@ -180,8 +249,10 @@ where
                 email: session.email,
                 role: session.role.clone(),
                 permissions: session.permissions.clone(),
-                is_elevated: false,
-                mfa_verified: false,
+                is_elevated: session.is_elevated,
+                mfa_verified: session.mfa_verified,
+                elevated_until: session.elevated_until,
+                generation: session.generation,
                 refresh_token_id: None,
                 session_id: session.session_id.clone(),
                 token_id: "".to_string(),
@@ -208,18 +279,72 @@ where
                 }
             }
 
+            // Required permissions are policy actions; path segments become resource attributes
             if let Some(ref perms) = required_permissions {
-                if !auth_session.has_all_permissions(perms) {
+                let denied = match req.app_data::<web::Data<AppContainer>>() {
+                    Some(container) => {
+                        let subject = auth_session.policy_subject();
+                        let resource = path_attributes(&req);
+                        perms.iter()
+                            .find(|action| !container.policy_engine.decide(&subject, action, &resource).allowed)
+                            .cloned()
+                    }
+                    None => perms.iter().find(|perm| !auth_session.has_permission(perm)).cloned(),
+                };
+                if let Some(action) = denied {
                     let resp = HttpResponse::Forbidden()
                         .json(serde_json::json!({
                             "error": "Missing required permissions",
-                            "required_permissions": perms
+                            "required_permissions": perms,
+                            "denied": action
+                        }))
+                        .map_into_right_body();
+                    return Ok(req.into_response(resp));
+                }
+
+                // Sensitive permissions also need an MFA-elevated session, as in `require_permission`
+                let needs_mfa = perms.iter()
+                    .filter_map(|action| Permission::from_str(action))
+                    .find(|permission| permission.requires_mfa());
+                if let Some(permission) = needs_mfa.filter(|_| !auth_session.is_mfa_elevated()) {
+                    let resp = HttpResponse::Forbidden()
+                        .json(serde_json::json!({
+                            "error": format!(
+                                "Permission {} requires an MFA-elevated session; elevate via /api/v1/auth/elevate",
+                                permission.as_str()
+                            ),
+                            "required_permission": permission.as_str()
                         }))
                         .map_into_right_body();
                     return Ok(req.into_response(resp));
                 }
             }
 
+            if let Some(ref permission) = organization_permission {
+                match authorize_organization(&req, &auth_session, permission).await {
+                    Ok(scope) => {
+                        req.extensions_mut().insert(scope);
+                    }
+                    Err(e) => {
+                        let resp = match e {
+                            AppError::Validation(message) => HttpResponse::BadRequest()
+                                .json(serde_json::json!({ "error": message })),
+                            AppError::Forbidden(message) => HttpResponse::Forbidden()
+                                .json(serde_json::json!({
+                                    "error": message,
+                                    "required_permission": permission.as_str()
+                                })),
+                            other => {
+                                error!("Organization authorization failed: {}", other);
+                                HttpResponse::InternalServerError()
+                                    .json(serde_json::json!({ "error": "Authorization service unavailable" }))
+                            }
+                        };
+                        return Ok(req.into_response(resp.map_into_right_body()));
+                    }
+                }
+            }
+
             // Attach session to request extensions for handlers to use
             req.extensions_mut().insert(auth_session);
 
@@ -229,6 +354,62 @@ where
     }
 }
 
+/// Path segments of the matched route, e.g. `{user_id}`, as resource attributes
+fn path_attributes(req: &ServiceRequest) -> Attributes {
+    let mut attributes = Attributes::new();
+    for (name, value) in req.match_info().iter() {
+        attributes.insert(name, value);
+    }
+    attributes
+}
+
+/// Resolve the caller's standing in the organization addressed by the request
+async fn authorize_organization(
+    req: &ServiceRequest,
+    session: &SecureAuthSession,
+    permission: &Permission,
+) -> Result<OrganizationScope, AppError> {
+    let raw_id = req.match_info().get("organization_id")
+        .ok_or_else(|| AppError::InternalServer("Route has no {organization_id} segment".to_string()))?;
+    let organization_id = uuid::Uuid::parse_str(raw_id.trim())
+        .map(OrganizationId)
+        .map_err(|_| AppError::Validation(format!("Invalid organization id: {}", raw_id)))?;
+
+    let container = req.app_data::<web::Data<AppContainer>>()
+        .ok_or_else(|| AppError::InternalServer("Application container not found in app data".to_string()))?;
+    // Platform administrators reach every organization; decided by the same policies as any other permission
+    let platform_admin = container.policy_engine
+        .decide(&session.policy_subject(), Permission::ManageSystem.as_str(), &Attributes::new())
+        .allowed;
+
+    let scope = if platform_admin {
+        OrganizationScope { organization_id, role: None }
+    } else {
+        let membership = container.organization_repository
+            .find_membership(&organization_id, &session.user_id)
+            .await?;
+
+        match membership {
+            Some(membership) if membership.role.grants(permission) => {
+                OrganizationScope { organization_id, role: Some(membership.role) }
+            }
+            Some(_) => return Err(AppError::Forbidden("Insufficient organization role".to_string())),
+            None => {
+                warn!("User {} denied access to organization {}", session.user_id, organization_id);
+                return Err(AppError::Forbidden("Not a member of this organization".to_string()));
+            }
+        }
+    };
+
+    if permission.requires_mfa() && !session.is_mfa_elevated() {
+        return Err(AppError::Forbidden(format!(
+            "Permission {} requires an MFA-elevated session; elevate via /api/v1/auth/elevate",
+            permission.as_str()
+        )));
+    }
+    Ok(scope)
+}
+
 // Helper functions for common auth requirements
 pub fn require_auth() -> AuthMiddleware {
     AuthMiddleware::new()
@@ -248,6 +429,10 @@ pub fn require_permissions(permissions: Vec<&str>) -> AuthMiddleware {
     )
 }
 
+pub fn require_organization_permission(permission: Permission) -> AuthMiddleware {
+    AuthMiddleware::for_organization(permission)
+}
+
 // FromRequest implementation for AuthSession
 impl FromRequest for SecureAuthSession {
     type Error = actix_web::Error;
@@ -261,3 +446,16 @@ impl FromRequest for SecureAuthSession {
         }
     }
 }
+
+impl FromRequest for OrganizationScope {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &actix_web::HttpRequest, _payload: &mut actix_web::dev::Payload) -> Self::Future {
+        if let Some(scope) = req.extensions().get::<OrganizationScope>() {
+            ready(Ok(scope.clone()))
+        } else {
+            ready(Err(actix_web::error::ErrorForbidden("No organization scope found")))
+        }
+    }
+}
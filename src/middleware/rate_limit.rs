@@ -0,0 +1,144 @@
+/// Rate limiting middleware
+/// Counts every request against its caller (signed-in user, else client IP) and the route
+/// pattern it matched, adds `X-RateLimit-*` headers and answers 429 with `Retry-After` once the
+/// limit is used up. If the limiter itself fails, requests are let through.
+
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    http::header::{self, HeaderMap, HeaderName, HeaderValue},
+    web, Error, ResponseError,
+};
+use actix_web::body::EitherBody;
+use futures_util::future::LocalBoxFuture;
+use std::future::{ready, Ready};
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+use tracing::warn;
+use crate::infrastructure::AppContainer;
+use crate::infrastructure::security::paseto_service::PasetoSecurityService;
+use crate::shared::error::AppError;
+use crate::shared::rate_limiter::{RateLimitDecision, RateLimitInfo, RateLimitSubject};
+
+/// Key used for paths that match no route, so made-up paths share one limit
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// Rate limiting middleware, configured from the container's `rate_limit_guard`
+#[derive(Default)]
+pub struct RateLimitMiddleware;
+
+impl RateLimitMiddleware {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RateLimitMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RateLimitMiddlewareService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RateLimitMiddlewareService { service: Rc::new(service) }))
+    }
+}
+
+pub struct RateLimitMiddlewareService<S> {
+    service: Rc<S>,
+}
+
+/// The signed-in user when a valid bearer token is sent, else the client address
+async fn rate_limit_subject(req: &ServiceRequest, trust_forwarded_for: bool) -> RateLimitSubject {
+    let token = req.headers().get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .filter(|token| !token.is_empty());
+    let paseto = req.app_data::<web::Data<Arc<PasetoSecurityService>>>();
+    if let (Some(token), Some(paseto)) = (token, paseto) {
+        if let Ok(session) = paseto.validate_paseto_token(token).await {
+            return RateLimitSubject::User { user_id: session.user_id, role: session.role };
+        }
+    }
+
+    let ip = if trust_forwarded_for {
+        req.connection_info().realip_remote_addr().map(str::to_string)
+    } else {
+        req.peer_addr().map(|addr| addr.ip().to_string())
+    };
+    RateLimitSubject::Ip(ip.unwrap_or_else(|| "unknown".to_string()))
+}
+
+fn insert_rate_limit_headers(headers: &mut HeaderMap, info: &RateLimitInfo) {
+    headers.insert(HeaderName::from_static("x-ratelimit-limit"), HeaderValue::from(info.limit));
+    headers.insert(HeaderName::from_static("x-ratelimit-remaining"), HeaderValue::from(info.remaining));
+    if let Ok(reset) = info.reset_time.duration_since(UNIX_EPOCH) {
+        let reset = reset.as_secs() + u64::from(reset.subsec_nanos() > 0);
+        headers.insert(HeaderName::from_static("x-ratelimit-reset"), HeaderValue::from(reset));
+    }
+    if let Some(retry_after) = info.retry_after {
+        let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+        headers.insert(header::RETRY_AFTER, HeaderValue::from(seconds.max(1)));
+    }
+}
+
+impl<S, B> Service<ServiceRequest> for RateLimitMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+
+        Box::pin(async move {
+            let Some(container) = req.app_data::<web::Data<AppContainer>>().cloned() else {
+                return service.call(req).await.map(ServiceResponse::map_into_left_body);
+            };
+            let Some(guard) = container.rate_limit_guard.clone() else {
+                return service.call(req).await.map(ServiceResponse::map_into_left_body);
+            };
+
+            let subject = rate_limit_subject(&req, container.config.rate_limiting.trust_forwarded_for).await;
+            let route = req.match_pattern().unwrap_or_else(|| UNMATCHED_ROUTE.to_string());
+            let method = req.method().as_str().to_string();
+
+            let info = match guard.check_request_limit(&subject, &method, &route).await {
+                Ok(RateLimitDecision::Limited(info)) => info,
+                Ok(RateLimitDecision::Bypassed) => {
+                    return service.call(req).await.map(ServiceResponse::map_into_left_body);
+                }
+                Err(e) => {
+                    warn!("Rate limit check failed for {} {}, letting the request through: {}", method, route, e);
+                    return service.call(req).await.map(ServiceResponse::map_into_left_body);
+                }
+            };
+
+            if !info.is_allowed() {
+                let mut resp = AppError::RateLimitExceeded(format!(
+                    "Too many requests to {} {}, limit is {}",
+                    method, route, info.limit
+                ))
+                .error_response();
+                insert_rate_limit_headers(resp.headers_mut(), &info);
+                return Ok(req.into_response(resp.map_into_right_body()));
+            }
+
+            let mut res = service.call(req).await?;
+            insert_rate_limit_headers(res.headers_mut(), &info);
+            Ok(res.map_into_left_body())
+        })
+    }
+}
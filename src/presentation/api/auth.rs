@@ -301,15 +301,16 @@ pub async fn elevate_session(
     };
 
     // Create elevated session
-    match container.paseto_service.create_elevated_session(
-        &session.session_id,
+    match crate::presentation::api::v1::auth::elevate_with_mfa(
+        &container,
+        &session,
         elevate_req.mfa_token.as_deref(),
     ).await {
-        Ok(_) => {
+        Ok(elevated) => {
             Ok(HttpResponse::Ok().json(serde_json::json!({
                 "success": true,
                 "message": "Session elevated successfully",
-                "elevated_until": chrono::Utc::now() + chrono::Duration::minutes(15)
+                "elevated_until": elevated.elevated_until
             })))
         }
         Err(e) => {
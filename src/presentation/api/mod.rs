@@ -12,6 +12,7 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
     cfg
         .service(health::health_check)
         .service(health::readiness_check)
+        .route("/.well-known/paseto-keys", web::get().to(v1::auth::public_keys))
         .service(
             web::scope("/api")
                 .service(
@@ -2,8 +2,22 @@
 /// Handles data analytics, statistics, and reporting for disaster management
 
 use actix_web::{web, HttpRequest, HttpResponse, Result};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use crate::domain::entities::analytics::{AnalyticsFilter, DisasterFact, Granularity, LocationFilter};
+use crate::domain::entities::disaster::DisasterType;
+use crate::domain::value_objects::Coordinates;
 use crate::infrastructure::AppContainer;
+use crate::infrastructure::security::SecureAuthSession;
+use crate::middleware::auth::AuthMiddleware;
+use crate::presentation::api::v1::require_permission;
+use crate::shared::{ApiResponse, AppError, AppResult, Permission};
+
+/// Radius around `lat,lng` locations when none is given
+const DEFAULT_RADIUS_KM: f64 = 50.0;
+const MAX_RADIUS_KM: f64 = 2000.0;
+/// Most disasters one export returns
+const MAX_EXPORT_ROWS: i64 = 10_000;
 
 #[derive(Debug, Deserialize)]
 pub struct AnalyticsQuery {
@@ -14,6 +28,90 @@ pub struct AnalyticsQuery {
     pub granularity: Option<String>, // daily, weekly, monthly, yearly
 }
 
+impl AnalyticsQuery {
+    /// Filter for the read model; `date_to` given as a date includes that whole day, and
+    /// `location` is either a province or city name or `lat,lng[,radius_km]`
+    fn filter(&self) -> AppResult<AnalyticsFilter> {
+        let from = self.date_from.as_deref().map(|raw| parse_bound(raw, "date_from", false)).transpose()?;
+        let to = self.date_to.as_deref().map(|raw| parse_bound(raw, "date_to", true)).transpose()?;
+        if let (Some(from), Some(to)) = (from, to) {
+            if from >= to {
+                return Err(AppError::Validation("date_from must be before date_to".to_string()));
+            }
+        }
+        Ok(AnalyticsFilter {
+            from,
+            to,
+            disaster_type: non_empty(&self.disaster_type).map(|raw| DisasterType::parse(raw).name().to_string()),
+            location: non_empty(&self.location).map(parse_location).transpose()?,
+            granularity: non_empty(&self.granularity).map(Granularity::parse).transpose()?.unwrap_or_default(),
+        })
+    }
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// A date (`2026-01-31`) or ISO 8601 datetime; an upper bound given as a date runs to the end of that day
+fn parse_bound(raw: &str, field: &str, end_of_day: bool) -> AppResult<DateTime<Utc>> {
+    let raw = raw.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(raw, "%Y-%m-%d") {
+        let start = date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+        return Ok(if end_of_day { start + Duration::days(1) } else { start });
+    }
+    DateTime::parse_from_rfc3339(raw)
+        .map(|t| t.with_timezone(&Utc))
+        .map_err(|_| AppError::Validation(format!("{} must be a date or ISO 8601 datetime", field)))
+}
+
+fn parse_location(raw: &str) -> AppResult<LocationFilter> {
+    let parts: Vec<&str> = raw.split(',').map(str::trim).collect();
+    let numbers: Option<Vec<f64>> = parts.iter().map(|part| part.parse::<f64>().ok()).collect();
+    let Some(numbers) = numbers.filter(|n| matches!(n.len(), 2 | 3)) else {
+        return Ok(LocationFilter::Area(raw.to_string()));
+    };
+
+    let center = Coordinates::new(numbers[0], numbers[1])
+        .map_err(|e| AppError::Validation(format!("location: {}", e)))?;
+    let radius_km = numbers.get(2).copied().unwrap_or(DEFAULT_RADIUS_KM);
+    if !(radius_km > 0.0 && radius_km <= MAX_RADIUS_KM) {
+        return Err(AppError::Validation(format!("location radius must be between 0 and {} km", MAX_RADIUS_KM)));
+    }
+    Ok(LocationFilter::Near { center, radius_km })
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn csv_time(value: Option<DateTime<Utc>>) -> String {
+    value.map(|t| t.to_rfc3339()).unwrap_or_default()
+}
+
+fn csv_row(fact: &DisasterFact) -> String {
+    format!(
+        "{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+        fact.disaster_id,
+        csv_field(fact.disaster_type.name()),
+        fact.severity.as_str(),
+        fact.status.as_str(),
+        csv_field(fact.province.as_deref().unwrap_or_default()),
+        csv_field(fact.city.as_deref().unwrap_or_default()),
+        fact.position.latitude,
+        fact.position.longitude,
+        fact.reported_at.to_rfc3339(),
+        csv_time(fact.verified_at),
+        csv_time(fact.first_response_at),
+        csv_time(fact.resolved_at),
+        csv_time(fact.closed_at),
+    )
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ReportRequest {
     pub report_type: String,        // disaster_summary, response_time, user_activity
@@ -22,60 +120,39 @@ pub struct ReportRequest {
 }
 
 /// GET /api/v1/analytics/dashboard
+/// Disaster counts with time-to-verify and time-to-first-response spreads
 async fn get_dashboard_data(
     query: web::Query<AnalyticsQuery>,
-    http_req: HttpRequest,
+    session: SecureAuthSession,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    // TODO: Implement dashboard analytics
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Dashboard analytics",
-        "data": {
-            "total_disasters": 0,
-            "active_disasters": 0,
-            "resolved_disasters": 0,
-            "response_teams": 0,
-            "citizens_served": 0,
-            "avg_response_time": "0 minutes"
-        }
-    })))
+    require_permission(&container.policy_engine, &session, Permission::ReadAnalytics)?;
+    let summary = container.analytics_repository.dashboard(&query.filter()?).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(summary)))
 }
 
 /// GET /api/v1/analytics/disasters/trends
+/// Disasters reported per period (by `granularity`, monthly by default), type, severity and province
 async fn get_disaster_trends(
     query: web::Query<AnalyticsQuery>,
-    http_req: HttpRequest,
+    session: SecureAuthSession,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    // TODO: Implement disaster trends analysis
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Disaster trends",
-        "trends": {
-            "by_type": {},
-            "by_month": {},
-            "by_severity": {},
-            "by_location": {}
-        }
-    })))
+    require_permission(&container.policy_engine, &session, Permission::ReadAnalytics)?;
+    let trends = container.analytics_repository.trends(&query.filter()?).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(trends)))
 }
 
 /// GET /api/v1/analytics/response-times
+/// Percentiles of time to verify and to first response, overall and per type and severity
 async fn get_response_time_analytics(
     query: web::Query<AnalyticsQuery>,
-    http_req: HttpRequest,
+    session: SecureAuthSession,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    // TODO: Implement response time analytics
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Response time analytics",
-        "data": {
-            "average_response_time": "15 minutes",
-            "fastest_response": "3 minutes",
-            "slowest_response": "45 minutes",
-            "by_disaster_type": {},
-            "by_severity": {}
-        }
-    })))
+    require_permission(&container.policy_engine, &session, Permission::ReadAnalytics)?;
+    let response_times = container.analytics_repository.response_times(&query.filter()?).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(response_times)))
 }
 
 /// GET /api/v1/analytics/user-activity
@@ -101,21 +178,15 @@ async fn get_user_activity_analytics(
 }
 
 /// GET /api/v1/analytics/geographic
+/// Disasters per province and city, and grid cells where they cluster
 async fn get_geographic_analytics(
     query: web::Query<AnalyticsQuery>,
-    http_req: HttpRequest,
+    session: SecureAuthSession,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    // TODO: Implement geographic analytics
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Geographic analytics",
-        "data": {
-            "hotspots": [],
-            "by_province": {},
-            "by_city": {},
-            "risk_areas": []
-        }
-    })))
+    require_permission(&container.policy_engine, &session, Permission::ReadAnalytics)?;
+    let geographic = container.analytics_repository.geographic(&query.filter()?).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(geographic)))
 }
 
 /// GET /api/v1/analytics/notifications
@@ -209,16 +280,24 @@ async fn download_report(
 }
 
 /// GET /api/v1/analytics/export/disasters
+/// Matching disasters as CSV, newest first
 async fn export_disaster_data(
     query: web::Query<AnalyticsQuery>,
-    http_req: HttpRequest,
+    session: SecureAuthSession,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    // TODO: Implement disaster data export
+    require_permission(&container.policy_engine, &session, Permission::ReadAnalytics)?;
+    let facts = container.analytics_repository.facts(&query.filter()?, MAX_EXPORT_ROWS).await?;
+    let mut body = String::from(
+        "id,type,severity,status,province,city,latitude,longitude,reported_at,verified_at,first_response_at,resolved_at,closed_at\n",
+    );
+    for fact in &facts {
+        body.push_str(&csv_row(fact));
+    }
     Ok(HttpResponse::Ok()
         .content_type("text/csv")
         .insert_header(("Content-Disposition", "attachment; filename=disasters.csv"))
-        .body("id,title,type,severity,date,status\n"))
+        .body(body))
 }
 
 /// GET /api/v1/analytics/predictions
@@ -244,16 +323,16 @@ async fn get_disaster_predictions(
 
 pub fn configure_analytics_routes(cfg: &mut web::ServiceConfig) {
     cfg
-        .route("/dashboard", web::get().to(get_dashboard_data))
-        .route("/disasters/trends", web::get().to(get_disaster_trends))
-        .route("/response-times", web::get().to(get_response_time_analytics))
+        .route("/dashboard", web::get().to(get_dashboard_data).wrap(AuthMiddleware::new()))
+        .route("/disasters/trends", web::get().to(get_disaster_trends).wrap(AuthMiddleware::new()))
+        .route("/response-times", web::get().to(get_response_time_analytics).wrap(AuthMiddleware::new()))
         .route("/user-activity", web::get().to(get_user_activity_analytics))
-        .route("/geographic", web::get().to(get_geographic_analytics))
+        .route("/geographic", web::get().to(get_geographic_analytics).wrap(AuthMiddleware::new()))
         .route("/notifications", web::get().to(get_notification_analytics))
         .route("/performance", web::get().to(get_system_performance))
         .route("/reports/generate", web::post().to(generate_report))
         .route("/reports/{report_id}", web::get().to(get_report_status))
         .route("/reports/{report_id}/download", web::get().to(download_report))
-        .route("/export/disasters", web::get().to(export_disaster_data))
+        .route("/export/disasters", web::get().to(export_disaster_data).wrap(AuthMiddleware::new()))
         .route("/predictions", web::get().to(get_disaster_predictions));
 }
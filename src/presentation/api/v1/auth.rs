@@ -6,12 +6,15 @@ use serde::{Deserialize, Serialize};
 use validator::Validate;
 use crate::AppError;
 use crate::application::ValidatedUseCase;
+use crate::application::use_cases::{RequestPasswordResetRequest, UseCase};
+use crate::domain::entities::notification::NotificationChannel;
 use crate::infrastructure::{
     container::AppContainer,
-    security::{SecureAuthSession},
+    security::{ActiveSessionInfo, SecureAuthSession},
 };
 use crate::domain::value_objects::{Email, UserRole as DomainUserRole};
 use crate::middleware::AuthMiddleware;
+use crate::shared::{ApiResponse, AppResult};
 
 #[derive(Debug, Deserialize, Validate)]
 pub struct LoginRequest {
@@ -67,6 +70,12 @@ pub struct ElevateSessionRequest {
     pub reason: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct MfaCodeRequest {
+    /// TOTP code, or a recovery code where accepted
+    pub code: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct RefreshTokenRequest {
     pub refresh_token: String,
@@ -80,6 +89,16 @@ pub struct ChangePasswordRequest {
 #[derive(Debug, Deserialize)]
 pub struct ResetPasswordRequest {
     pub email: String,
+    /// "email" (default) or "sms"
+    pub channel: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SessionResponse {
+    #[serde(flatten)]
+    pub session: ActiveSessionInfo,
+    /// Whether this is the session making the request
+    pub current: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -274,6 +293,9 @@ pub async fn register(
     // Execute registration use case
     match container.register_user_use_case.execute_validated(use_case_request).await {
         Ok(user_response) => {
+            if let Err(e) = container.send_email_verification_use_case.execute(user_response.id).await {
+                tracing::warn!("Failed to send verification email to user {}: {}", user_response.id, e);
+            }
             Ok(HttpResponse::Created().json(AuthResponse {
                 success: true,
                 message: "Registration successful. Please check your email for verification.".to_string(),
@@ -321,18 +343,23 @@ pub async fn elevate_session(
         }
     };
 
-    // Create elevated session
-    match container.paseto_service.create_elevated_session(
-        &session.session_id,
-        elevate_req.mfa_token.as_deref(),
-    ).await {
-        Ok(_) => {
+    tracing::info!("User {} requested session elevation: {}", session.user_id, elevate_req.reason);
+    match elevate_with_mfa(&container, &session, elevate_req.mfa_token.as_deref()).await {
+        Ok(elevated) => {
             Ok(HttpResponse::Ok().json(serde_json::json!({
                 "success": true,
                 "message": "Session elevated successfully",
-                "elevated_until": chrono::Utc::now() + chrono::Duration::minutes(15)
+                "mfa_verified": elevated.mfa_verified,
+                "elevated_until": elevated.elevated_until
             })))
         }
+        Err(e @ AppError::Unauthorized(_)) => {
+            Ok(HttpResponse::Unauthorized().json(AuthResponse {
+                success: false,
+                message: format!("Failed to elevate session: {}", e),
+                data: None,
+            }))
+        }
         Err(e) => {
             Ok(HttpResponse::BadRequest().json(AuthResponse {
                 success: false,
@@ -343,6 +370,83 @@ pub async fn elevate_session(
     }
 }
 
+/// Elevate a session, verifying the second factor whenever the user has MFA enabled
+pub async fn elevate_with_mfa(
+    container: &AppContainer,
+    session: &SecureAuthSession,
+    mfa_token: Option<&str>,
+) -> AppResult<SecureAuthSession> {
+    let verification = match mfa_token {
+        Some(code) => Some(container.mfa_service.verify(&session.user_id, code).await?),
+        None if container.mfa_service.is_enabled(&session.user_id).await? => {
+            return Err(AppError::Unauthorized("An MFA code is required to elevate this session".to_string()));
+        }
+        None => None,
+    };
+
+    container.paseto_service.create_elevated_session(&session.session_id, verification.as_ref()).await
+}
+
+/// GET /api/v1/auth/mfa
+async fn mfa_status(
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    let status = container.mfa_service.status(&session.user_id).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(status)))
+}
+
+/// POST /api/v1/auth/mfa/enroll
+/// Returns the secret and an `otpauth://` URI for the authenticator app's QR scanner
+async fn begin_mfa_enrollment(
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    let enrollment = container.mfa_service
+        .begin_enrollment(&session.user_id, session.email.value())
+        .await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(enrollment)))
+}
+
+/// POST /api/v1/auth/mfa/confirm
+async fn confirm_mfa_enrollment(
+    req: web::Json<MfaCodeRequest>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    let recovery_codes = container.mfa_service.confirm_enrollment(&session.user_id, &req.code).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "enabled": true,
+        "recovery_codes": recovery_codes
+    }))))
+}
+
+/// POST /api/v1/auth/mfa/recovery-codes
+async fn regenerate_recovery_codes(
+    req: web::Json<MfaCodeRequest>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    let verification = container.mfa_service.verify(&session.user_id, &req.code).await?;
+    let recovery_codes = container.mfa_service.regenerate_recovery_codes(&verification).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "recovery_codes": recovery_codes
+    }))))
+}
+
+/// POST /api/v1/auth/mfa/disable
+async fn disable_mfa(
+    req: web::Json<MfaCodeRequest>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    let verification = container.mfa_service.verify(&session.user_id, &req.code).await?;
+    container.mfa_service.disable(&verification).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "enabled": false
+    }))))
+}
+
 /// POST /api/v1/auth/refresh
 async fn refresh_token(
     req: web::Json<RefreshTokenRequest>,
@@ -415,37 +519,67 @@ pub async fn me(req: HttpRequest) -> Result<HttpResponse> {
 }
 
 /// POST /api/v1/auth/change-password
+/// Signs out every other session of the user once the password has changed
 async fn change_password(
     req: web::Json<ChangePasswordRequest>,
-    http_req: HttpRequest,
+    session: SecureAuthSession,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    // TODO: Implement change password logic
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Password changed successfully"
-    })))
+    let req = req.into_inner();
+    container.change_password_use_case
+        .execute_validated(crate::application::use_cases::ChangePasswordRequest {
+            user_id: session.user_id,
+            session_id: session.session_id,
+            current_password: req.current_password,
+            new_password: req.new_password,
+        })
+        .await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "message": "Password changed successfully; other sessions have been signed out"
+    }))))
 }
 
 /// POST /api/v1/auth/reset-password
+/// Responds identically whether or not the account exists
 async fn reset_password(
     req: web::Json<ResetPasswordRequest>,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    // TODO: Implement password reset request logic
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Password reset email sent"
-    })))
+    let req = req.into_inner();
+    let channel = match req.channel.as_deref() {
+        None => NotificationChannel::Email,
+        Some(raw) => match raw.parse()? {
+            channel @ (NotificationChannel::Email | NotificationChannel::SMS) => channel,
+            _ => return Err(AppError::Validation("Reset codes can be sent by email or sms".to_string()).into()),
+        },
+    };
+
+    container.request_password_reset_use_case
+        .execute(RequestPasswordResetRequest { email: req.email, channel })
+        .await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "message": "If the account exists, a password reset code has been sent"
+    }))))
 }
 
 /// POST /api/v1/auth/confirm-reset-password
+/// Signs out all of the user's sessions
 async fn confirm_reset_password(
     req: web::Json<ConfirmResetPasswordRequest>,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    // TODO: Implement password reset confirmation logic
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Password reset confirmed"
-    })))
+    let req = req.into_inner();
+    container.reset_password_use_case
+        .execute_validated(crate::application::use_cases::ResetPasswordRequest {
+            token: req.token,
+            new_password: req.new_password,
+        })
+        .await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "message": "Password has been reset; please sign in again"
+    }))))
 }
 
 /// GET /api/v1/auth/verify-email/{token}
@@ -453,35 +587,112 @@ async fn verify_email(
     path: web::Path<String>,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    let token = path.into_inner();
-    // TODO: Implement email verification logic
-    Ok(HttpResponse::Ok().json(serde_json::json!({
+    let user_id = container.verify_email_use_case.execute(path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
         "message": "Email verified successfully",
-        "token": token
-    })))
+        "user_id": user_id
+    }))))
 }
 
 /// POST /api/v1/auth/resend-verification
 async fn resend_verification_email(
-    http_req: HttpRequest,
+    session: SecureAuthSession,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    // TODO: Implement resend verification email logic
-    Ok(HttpResponse::Ok().json(serde_json::json!({
+    container.send_email_verification_use_case.execute(session.user_id).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
         "message": "Verification email sent"
-    })))
+    }))))
+}
+
+/// GET /api/v1/auth/sessions
+/// Lists the caller's active sessions across devices
+async fn list_sessions(
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    let sessions: Vec<SessionResponse> = container.paseto_service
+        .list_user_sessions(&session.user_id)
+        .await?
+        .into_iter()
+        .map(|active| SessionResponse {
+            current: active.session_id == session.session_id,
+            session: active,
+        })
+        .collect();
+    Ok(HttpResponse::Ok().json(ApiResponse::success(sessions)))
+}
+
+/// DELETE /api/v1/auth/sessions/{session_id}
+async fn revoke_session(
+    path: web::Path<String>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    let session_id = path.into_inner();
+    if !container.paseto_service.revoke_user_session(&session.user_id, &session_id).await? {
+        return Err(AppError::NotFound(format!("Session {} not found", session_id)).into());
+    }
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "message": "Session revoked",
+        "session_id": session_id
+    }))))
+}
+
+/// DELETE /api/v1/auth/sessions
+/// Signs out every session except the one making the request
+async fn revoke_other_sessions(
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    container.paseto_service.revoke_user_sessions(&session.user_id, Some(&session.session_id)).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "message": "Other sessions have been signed out"
+    }))))
+}
+
+/// POST /api/v1/auth/logout-all
+/// Signs out every session, including the current one
+async fn logout_all(
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    container.paseto_service.revoke_user_sessions(&session.user_id, None).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "message": "Signed out from all devices"
+    }))))
+}
+
+/// GET /api/v1/auth/keys (also served at /.well-known/paseto-keys)
+/// JWKS-style list of keys for verifying `v4.public` tokens; retired keys stay listed until removed from rotation
+pub async fn public_keys(container: web::Data<AppContainer>) -> Result<HttpResponse> {
+    let keys = container.paseto_service.public_keys()?;
+    Ok(HttpResponse::Ok()
+        .insert_header(("Cache-Control", "public, max-age=300"))
+        .json(serde_json::json!({ "keys": keys })))
 }
 
 pub fn configure_auth_routes(cfg: &mut web::ServiceConfig) {
     cfg
+        .route("/keys", web::get().to(public_keys))
         .route("/login", web::post().to(login))
         .route("/register", web::post().to(register))
         .route("/refresh", web::post().to(refresh_token))
-        .route("/logout", web::post().to(logout))
+        .route("/logout", web::post().to(logout).wrap(AuthMiddleware::new()))
+        .route("/logout-all", web::post().to(logout_all).wrap(AuthMiddleware::new()))
+        .route("/sessions", web::get().to(list_sessions).wrap(AuthMiddleware::new()))
+        .route("/sessions", web::delete().to(revoke_other_sessions).wrap(AuthMiddleware::new()))
+        .route("/sessions/{session_id}", web::delete().to(revoke_session).wrap(AuthMiddleware::new()))
         .route("/me", web::get().to(me).wrap(AuthMiddleware::new()))
-        .route("/change-password", web::post().to(change_password))
+        .route("/elevate", web::post().to(elevate_session).wrap(AuthMiddleware::new()))
+        .route("/mfa", web::get().to(mfa_status).wrap(AuthMiddleware::new()))
+        .route("/mfa/enroll", web::post().to(begin_mfa_enrollment).wrap(AuthMiddleware::new()))
+        .route("/mfa/confirm", web::post().to(confirm_mfa_enrollment).wrap(AuthMiddleware::new()))
+        .route("/mfa/recovery-codes", web::post().to(regenerate_recovery_codes).wrap(AuthMiddleware::new()))
+        .route("/mfa/disable", web::post().to(disable_mfa).wrap(AuthMiddleware::new()))
+        .route("/change-password", web::post().to(change_password).wrap(AuthMiddleware::new()))
         .route("/reset-password", web::post().to(reset_password))
         .route("/confirm-reset-password", web::post().to(confirm_reset_password))
         .route("/verify-email/{token}", web::get().to(verify_email))
-        .route("/resend-verification", web::post().to(resend_verification_email));
+        .route("/resend-verification", web::post().to(resend_verification_email).wrap(AuthMiddleware::new()));
 }
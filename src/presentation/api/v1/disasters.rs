@@ -3,7 +3,12 @@
 
 use actix_web::{web, HttpRequest, HttpResponse, Result};
 use serde::{Deserialize, Serialize};
+use crate::application::use_cases::{UpdateDisasterStatusRequest, ValidatedUseCase};
 use crate::infrastructure::AppContainer;
+use crate::infrastructure::security::SecureAuthSession;
+use crate::middleware::auth::AuthMiddleware;
+use crate::presentation::api::v1::parse_uuid;
+use crate::shared::{ApiResponse, DisasterId};
 
 #[derive(Debug, Deserialize)]
 pub struct CreateDisasterRequest {
@@ -44,6 +49,11 @@ pub struct DisasterSearchQuery {
     pub limit: Option<u32>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct StatusChangeRequest {
+    pub notes: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct AssignResponderRequest {
     pub responder_id: String,
@@ -137,32 +147,46 @@ async fn assign_responder(
     })))
 }
 
+/// Move a disaster to `new_status`; the access policies decide whether the caller may
+async fn change_disaster_status(
+    container: &AppContainer,
+    session: &SecureAuthSession,
+    raw_id: &str,
+    new_status: &str,
+    notes: Option<String>,
+) -> Result<HttpResponse> {
+    let disaster_id = DisasterId(parse_uuid(raw_id, "disaster id")?);
+    let disaster = container.update_disaster_status_use_case
+        .execute_validated(UpdateDisasterStatusRequest {
+            disaster_id,
+            new_status: new_status.to_string(),
+            actor: session.policy_subject(),
+            update_notes: notes,
+        })
+        .await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(disaster)))
+}
+
 /// POST /api/v1/disasters/{disaster_id}/verify
 async fn verify_disaster(
     path: web::Path<String>,
-    http_req: HttpRequest,
+    req: Option<web::Json<StatusChangeRequest>>,
+    session: SecureAuthSession,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    let disaster_id = path.into_inner();
-    // TODO: Implement verify disaster logic (responder/admin only)
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Disaster verified",
-        "disaster_id": disaster_id
-    })))
+    let notes = req.and_then(|req| req.into_inner().notes);
+    change_disaster_status(&container, &session, &path, "verified", notes).await
 }
 
 /// POST /api/v1/disasters/{disaster_id}/resolve
 async fn resolve_disaster(
     path: web::Path<String>,
-    http_req: HttpRequest,
+    req: Option<web::Json<StatusChangeRequest>>,
+    session: SecureAuthSession,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    let disaster_id = path.into_inner();
-    // TODO: Implement resolve disaster logic
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Disaster resolved",
-        "disaster_id": disaster_id
-    })))
+    let notes = req.and_then(|req| req.into_inner().notes);
+    change_disaster_status(&container, &session, &path, "resolved", notes).await
 }
 
 /// GET /api/v1/disasters/{disaster_id}/responders
@@ -249,8 +273,8 @@ pub fn configure_disaster_routes(cfg: &mut web::ServiceConfig) {
         .route("/{disaster_id}", web::put().to(update_disaster))
         .route("/{disaster_id}", web::delete().to(delete_disaster))
         .route("/{disaster_id}/assign", web::post().to(assign_responder))
-        .route("/{disaster_id}/verify", web::post().to(verify_disaster))
-        .route("/{disaster_id}/resolve", web::post().to(resolve_disaster))
+        .route("/{disaster_id}/verify", web::post().to(verify_disaster).wrap(AuthMiddleware::new()))
+        .route("/{disaster_id}/resolve", web::post().to(resolve_disaster).wrap(AuthMiddleware::new()))
         .route("/{disaster_id}/responders", web::get().to(get_disaster_responders))
         .route("/{disaster_id}/timeline", web::get().to(get_disaster_timeline))
         .route("/nearby", web::get().to(get_nearby_disasters))
@@ -1,9 +1,42 @@
 /// Emergency response API endpoints
 /// Handles real-time emergency coordination, team dispatch, and crisis management
 
-use actix_web::{web, HttpRequest, HttpResponse, Result};
+use actix_web::{http::header, web, HttpRequest, HttpResponse, Result};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+use crate::application::use_cases::{
+    rank_teams_by_distance, AlertTarget, CapImportOutcome, DispatchEmergencyResponseRequest, ImportCapAlertRequest,
+    MatchVolunteersRequest, SendEmergencyAlertRequest, UpdateEmergencyResponseStatusRequest, UseCase,
+    ValidatedUseCase,
+};
+use crate::domain::entities::disaster::{DisasterSeverity, ResourceNeed};
+use crate::domain::entities::emergency_response::{
+    EmergencyResponse, EmergencyResponseStatus, ResourceStock, StatusChange,
+};
+use crate::domain::entities::location::{Location, LocationType};
+use crate::domain::entities::response_team::{EmergencyStation, ResponseTeam, TeamAvailability, TeamType};
+use crate::domain::entities::volunteer::VolunteerAssignment;
+use crate::domain::value_objects::{Coordinates as GeoPoint, GeoArea};
 use crate::infrastructure::AppContainer;
+use crate::infrastructure::security::SecureAuthSession;
+use crate::middleware::auth::AuthMiddleware;
+use crate::presentation::api::v1::locations::LocationResponse;
+use crate::presentation::api::v1::{parse_uuid, require_permission};
+use crate::shared::cap::CAP_CONTENT_TYPE;
+use crate::shared::routing::{Route, TravelMode, WALKING_SPEED_KMH};
+use crate::shared::types::Priority;
+use crate::shared::{ApiResponse, AppError, AppResult, DisasterId, EmergencyResponseId, Permission};
+
+/// Personnel assumed for a response when the request does not specify a team size
+const DEFAULT_TEAM_SIZE: u32 = 4;
+const DEFAULT_SHELTER_LIMIT: usize = 5;
+const MAX_SHELTER_LIMIT: usize = 50;
+const DEFAULT_ROUTE_LIMIT: usize = 3;
+const MAX_ROUTE_LIMIT: usize = 10;
+const DEFAULT_TEAM_LIMIT: usize = 20;
+const MAX_TEAM_LIMIT: usize = 100;
 
 #[derive(Debug, Deserialize)]
 pub struct EmergencyResponseRequest {
@@ -34,10 +67,10 @@ pub struct Coordinates {
 
 #[derive(Debug, Deserialize)]
 pub struct ResourceRequest {
+    pub disaster_id: String,
     pub resource_type: String,    // vehicle, equipment, personnel
     pub quantity: u32,
     pub urgency: String,         // low, medium, high, critical
-    pub location: Coordinates,
     pub description: String,
 }
 
@@ -49,150 +82,773 @@ pub struct StatusUpdateRequest {
     pub estimated_completion: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ActiveEmergenciesQuery {
+    pub disaster_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NearestShelterQuery {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EvacuationRouteQuery {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EvacuationRouteView {
+    pub destination: LocationResponse,
+    pub distance_km: f64,
+    pub estimated_minutes: f64,
+    pub closures_avoided: usize,
+    pub waypoints: Vec<GeoPoint>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BroadcastAlertRequest {
+    pub disaster_id: String,
+    pub alert_type: String,       // evacuation, shelter, warning, all_clear
+    pub severity: String,         // minor, moderate, major, severe, critical, catastrophic
+    // Exactly one target: latitude/longitude/radius_km, zone_id, or a GeoJSON area
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub radius_km: Option<f64>,
+    pub zone_id: Option<String>,
+    pub area: Option<serde_json::Value>,
+    pub message: String,
+    pub channels: Vec<String>,    // sms, email, push, whatsapp
+    pub expires_at: Option<String>, // ISO 8601 datetime
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TeamListQuery {
+    pub team_type: Option<String>,
+    pub availability: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AvailableTeamsQuery {
+    pub team_type: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub min_personnel: Option<u32>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateTeamRequest {
+    pub name: String,
+    pub team_type: String,
+    pub station_id: Option<String>,
+    pub location: Coordinates,
+    pub personnel: u32,
+    pub vehicles: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateTeamRequest {
+    pub name: Option<String>,
+    pub team_type: Option<String>,
+    pub station_id: Option<String>,
+    pub personnel: Option<u32>,
+    pub vehicles: Option<Vec<String>>,
+    pub availability: Option<String>, // available, off_duty, out_of_service
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StationRequest {
+    pub name: String,
+    pub station_type: String,
+    pub location: Coordinates,
+    pub address: Option<String>,
+    pub contact_phone: Option<String>,
+    pub staff_count: Option<u32>,
+    pub vehicles: Option<Vec<String>>,
+    pub is_operational: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RankedTeamView {
+    #[serde(flatten)]
+    pub team: ResponseTeam,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distance_km: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EmergencyResponseView {
+    pub id: Uuid,
+    pub disaster_id: Uuid,
+    pub response_type: String,
+    pub priority: u8,
+    pub status: EmergencyResponseStatus,
+    pub dispatched_by: Uuid,
+    pub assigned_teams: Vec<Uuid>,
+    pub personnel_count: u32,
+    pub equipment: Vec<String>,
+    pub destination: GeoPoint,
+    pub last_known_location: Option<GeoPoint>,
+    pub estimated_arrival: Option<DateTime<Utc>>,
+    pub estimated_completion: Option<DateTime<Utc>>,
+    pub notes: Option<String>,
+    pub dispatched_at: DateTime<Utc>,
+    pub en_route_at: Option<DateTime<Utc>>,
+    pub on_scene_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub response_time_minutes: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_history: Option<Vec<StatusChange>>,
+}
+
+impl EmergencyResponseView {
+    fn from_response(response: EmergencyResponse, with_history: bool) -> Self {
+        Self {
+            id: response.id.0,
+            disaster_id: response.disaster_id.0,
+            response_time_minutes: response.response_time_minutes(),
+            response_type: response.response_type,
+            priority: response.priority,
+            status: response.status,
+            dispatched_by: response.dispatched_by.0,
+            assigned_teams: response.assigned_teams,
+            personnel_count: response.personnel_count,
+            equipment: response.equipment,
+            destination: response.destination,
+            last_known_location: response.last_known_location,
+            estimated_arrival: response.estimated_arrival,
+            estimated_completion: response.estimated_completion,
+            notes: response.notes,
+            dispatched_at: response.dispatched_at,
+            en_route_at: response.en_route_at,
+            on_scene_at: response.on_scene_at,
+            completed_at: response.completed_at,
+            status_history: with_history.then_some(response.status_history),
+        }
+    }
+}
+
+fn parse_point(point: &Coordinates) -> AppResult<GeoPoint> {
+    GeoPoint::new(point.latitude, point.longitude).map_err(|e| AppError::Validation(e.to_string()))
+}
+
+fn parse_timestamp(raw: &str, field: &str) -> AppResult<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(raw)
+        .map(|t| t.with_timezone(&Utc))
+        .map_err(|_| AppError::Validation(format!("{} must be an ISO 8601 datetime", field)))
+}
+
+/// Map a priority label (or 1-5) onto the dispatch priority scale
+fn parse_priority_level(raw: &str) -> AppResult<u8> {
+    match raw.trim().to_lowercase().as_str() {
+        "low" => Ok(2),
+        "medium" | "normal" => Ok(3),
+        "high" => Ok(4),
+        "critical" | "emergency" => Ok(5),
+        other => other.parse::<u8>().ok()
+            .filter(|level| (1..=5).contains(level))
+            .ok_or_else(|| AppError::Validation(format!(
+                "Invalid priority '{}'. Must be low, medium, high, critical or 1-5", raw
+            ))),
+    }
+}
+
+fn parse_urgency(raw: &str) -> AppResult<Priority> {
+    match raw.trim().to_lowercase().as_str() {
+        "low" => Ok(Priority::Low),
+        "medium" | "normal" => Ok(Priority::Normal),
+        "high" => Ok(Priority::High),
+        "critical" => Ok(Priority::Critical),
+        "emergency" => Ok(Priority::Emergency),
+        _ => Err(AppError::Validation(format!(
+            "Invalid urgency '{}'. Must be low, medium, high, critical or emergency", raw
+        ))),
+    }
+}
+
+fn parse_severity(raw: &str) -> AppResult<DisasterSeverity> {
+    match raw.trim().to_lowercase().as_str() {
+        "minor" | "low" => Ok(DisasterSeverity::Minor),
+        "moderate" | "medium" => Ok(DisasterSeverity::Moderate),
+        "major" | "high" => Ok(DisasterSeverity::Major),
+        "severe" => Ok(DisasterSeverity::Severe),
+        "critical" => Ok(DisasterSeverity::Critical),
+        "catastrophic" => Ok(DisasterSeverity::Catastrophic),
+        _ => Err(AppError::Validation(format!("Invalid severity '{}'", raw))),
+    }
+}
+
+async fn load_response(container: &AppContainer, raw_id: &str) -> AppResult<EmergencyResponse> {
+    let id = EmergencyResponseId(parse_uuid(raw_id, "emergency id")?);
+    container.emergency_response_repository.find_by_id(&id).await?
+        .ok_or_else(|| AppError::NotFound(format!("Emergency response {} not found", id)))
+}
+
+async fn load_team(container: &AppContainer, raw_id: &str) -> AppResult<ResponseTeam> {
+    let id = parse_uuid(raw_id, "team id")?;
+    container.response_team_repository.find_by_id(&id).await?
+        .ok_or_else(|| AppError::NotFound(format!("Response team {} not found", id)))
+}
+
+async fn load_station(container: &AppContainer, raw_id: &str) -> AppResult<EmergencyStation> {
+    let id = parse_uuid(raw_id, "station id")?;
+    container.emergency_station_repository.find_by_id(&id).await?
+        .ok_or_else(|| AppError::NotFound(format!("Emergency station {} not found", id)))
+}
+
+/// Resolve an optional station reference, making sure it exists
+async fn resolve_station_id(container: &AppContainer, raw_id: Option<&str>) -> AppResult<Option<Uuid>> {
+    match raw_id.map(str::trim).filter(|id| !id.is_empty()) {
+        Some(raw) => Ok(Some(load_station(container, raw).await?.id)),
+        None => Ok(None),
+    }
+}
+
+/// Shelters open for evacuation, closest first
+async fn load_shelters(container: &AppContainer, origin: &GeoPoint) -> AppResult<Vec<Location>> {
+    let mut shelters = Vec::new();
+    for location_type in [LocationType::EvacuationCenter, LocationType::School] {
+        shelters.extend(container.location_repository.find_by_type(&location_type).await?);
+    }
+    shelters.retain(|l| l.is_suitable_for_evacuation());
+    shelters.sort_by(|a, b| {
+        origin.distance_to(&a.coordinates).total_cmp(&origin.distance_to(&b.coordinates))
+    });
+    Ok(shelters)
+}
+
+/// Claim registry teams for a response, releasing any partial claims if one of them is unavailable
+async fn claim_teams(
+    container: &AppContainer,
+    response_id: EmergencyResponseId,
+    team_ids: &[Uuid],
+) -> AppResult<Vec<ResponseTeam>> {
+    let mut claimed: Vec<ResponseTeam> = Vec::new();
+    let mut failure = None;
+    for team_id in team_ids {
+        let result = async {
+            let mut team = container.response_team_repository.find_by_id(team_id).await?
+                .ok_or_else(|| AppError::NotFound(format!("Response team {} not found", team_id)))?;
+            team.dispatch(response_id)?;
+            container.response_team_repository.update(&team).await
+        }.await;
+
+        match result {
+            Ok(team) => claimed.push(team),
+            Err(e) => {
+                failure = Some(e);
+                break;
+            }
+        }
+    }
+
+    match failure {
+        None => Ok(claimed),
+        Some(e) => {
+            for mut team in claimed {
+                team.release();
+                if let Err(release_err) = container.response_team_repository.update(&team).await {
+                    tracing::error!("Failed to release team {}: {}", team.id, release_err);
+                }
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Assign the volunteers best matching the disaster's needs when volunteer matching is enabled;
+/// a failed match never fails the request that triggered it
+async fn auto_assign_volunteers(
+    container: &AppContainer,
+    session: &SecureAuthSession,
+    disaster_id: DisasterId,
+    resource_types: Vec<String>,
+) -> Vec<VolunteerAssignment> {
+    let count = container.config.volunteer_matching.auto_assign_count;
+    if !container.config.features.enable_volunteer_matching || count == 0 {
+        return Vec::new();
+    }
+    match container.match_volunteers_use_case
+        .execute_validated(MatchVolunteersRequest {
+            disaster_id,
+            resource_types,
+            limit: count,
+            auto_assign: true,
+            actor: session.policy_subject(),
+        })
+        .await
+    {
+        Ok(result) => result.assignments,
+        Err(e) => {
+            tracing::warn!("Volunteer auto-assignment for disaster {} failed: {}", disaster_id, e);
+            Vec::new()
+        }
+    }
+}
+
 /// POST /api/v1/emergency/response
 async fn initiate_emergency_response(
     req: web::Json<EmergencyResponseRequest>,
-    http_req: HttpRequest,
+    session: SecureAuthSession,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    // TODO: Implement emergency response initiation
-    Ok(HttpResponse::Created().json(serde_json::json!({
-        "message": "Emergency response initiated",
-        "response_id": "emergency_resp_123456",
-        "disaster_id": req.disaster_id,
-        "type": req.response_type,
-        "priority": req.priority
-    })))
+    require_permission(&container.policy_engine, &session, Permission::ManageEmergencyResponse)?;
+    let req = req.into_inner();
+    let mut equipment = req.special_equipment.unwrap_or_default();
+    equipment.extend(req.required_resources);
+
+    let disaster_id = DisasterId(parse_uuid(&req.disaster_id, "disaster id")?);
+    let dispatched = container.dispatch_emergency_response_use_case
+        .execute_validated(DispatchEmergencyResponseRequest {
+            disaster_id,
+            dispatched_by: session.user_id,
+            response_team_type: req.response_type,
+            priority_level: parse_priority_level(&req.priority)?,
+            estimated_personnel_needed: req.team_size.unwrap_or(DEFAULT_TEAM_SIZE),
+            special_equipment_needed: equipment,
+            notes: req.notes,
+            destination: None,
+            estimated_duration_minutes: req.estimated_duration,
+        })
+        .await?;
+    let volunteer_assignments = auto_assign_volunteers(&container, &session, disaster_id, Vec::new()).await;
+    if !volunteer_assignments.is_empty() {
+        tracing::info!(
+            "Auto-assigned {} volunteers to disaster {} for response {}",
+            volunteer_assignments.len(), disaster_id, dispatched.response_id
+        );
+    }
+
+    let response = load_response(&container, &dispatched.response_id.to_string()).await?;
+    Ok(HttpResponse::Created().json(ApiResponse::success(
+        EmergencyResponseView::from_response(response, true)
+    )))
+}
+
+/// Live team positions and response tracking are limited to those running or working a response
+fn require_response_access(container: &AppContainer, session: &SecureAuthSession) -> AppResult<()> {
+    require_permission(&container.policy_engine, session, Permission::ManageEmergencyResponse)
+        .or_else(|_| require_permission(&container.policy_engine, session, Permission::UpdateEmergencyStatus))
 }
 
 /// GET /api/v1/emergency/active
 async fn get_active_emergencies(
-    http_req: HttpRequest,
+    query: web::Query<ActiveEmergenciesQuery>,
+    session: SecureAuthSession,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    // TODO: Implement get active emergencies
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Active emergencies",
-        "emergencies": []
-    })))
+    require_response_access(&container, &session)?;
+    let responses = match query.disaster_id.as_deref() {
+        Some(raw) => {
+            let disaster_id = DisasterId(parse_uuid(raw, "disaster id")?);
+            let mut responses = container.emergency_response_repository.find_by_disaster(&disaster_id).await?;
+            responses.retain(EmergencyResponse::is_active);
+            responses
+        }
+        None => container.emergency_response_repository.find_active().await?,
+    };
+
+    let views: Vec<EmergencyResponseView> = responses.into_iter()
+        .map(|r| EmergencyResponseView::from_response(r, false))
+        .collect();
+    Ok(HttpResponse::Ok().json(ApiResponse::success(views)))
 }
 
 /// GET /api/v1/emergency/{emergency_id}
 async fn get_emergency_details(
     path: web::Path<String>,
-    http_req: HttpRequest,
+    session: SecureAuthSession,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    let emergency_id = path.into_inner();
-    // TODO: Implement get emergency details
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Emergency details",
-        "emergency_id": emergency_id
-    })))
+    require_response_access(&container, &session)?;
+    let response = load_response(&container, &path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        EmergencyResponseView::from_response(response, true)
+    )))
 }
 
 /// POST /api/v1/emergency/{emergency_id}/dispatch
 async fn dispatch_teams(
     path: web::Path<String>,
     req: web::Json<TeamDispatchRequest>,
-    http_req: HttpRequest,
+    session: SecureAuthSession,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    let emergency_id = path.into_inner();
-    // TODO: Implement team dispatch logic
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Teams dispatched",
-        "emergency_id": emergency_id,
-        "teams": req.team_ids,
-        "dispatch_time": chrono::Utc::now().to_rfc3339()
-    })))
+    require_permission(&container.policy_engine, &session, Permission::ManageEmergencyResponse)?;
+    let req = req.into_inner();
+    let team_ids = req.team_ids.iter()
+        .map(|id| parse_uuid(id, "team id"))
+        .collect::<AppResult<Vec<_>>>()?;
+    let destination = parse_point(&req.destination)?;
+    let estimated_arrival = req.estimated_arrival.as_deref()
+        .map(|raw| parse_timestamp(raw, "estimated_arrival"))
+        .transpose()?;
+
+    let mut response = load_response(&container, &path.into_inner()).await?;
+    let new_team_ids: Vec<Uuid> = team_ids.iter()
+        .filter(|id| !response.assigned_teams.contains(id))
+        .copied()
+        .collect();
+    response.dispatch_teams(&team_ids, Some(destination), &req.equipment_needed.unwrap_or_default())?;
+    response.set_estimates(estimated_arrival, None);
+    if !req.mission_brief.trim().is_empty() {
+        response.notes = Some(req.mission_brief);
+    }
+
+    let claimed = claim_teams(&container, response.id, &new_team_ids).await?;
+    response.personnel_count += claimed.iter().map(|team| team.personnel).sum::<u32>();
+    let response = match container.emergency_response_repository.update(&response).await {
+        Ok(response) => response,
+        Err(e) => {
+            for mut team in claimed {
+                team.release();
+                if let Err(release_err) = container.response_team_repository.update(&team).await {
+                    tracing::error!("Failed to release team {}: {}", team.id, release_err);
+                }
+            }
+            return Err(e.into());
+        }
+    };
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        EmergencyResponseView::from_response(response, false)
+    )))
 }
 
 /// PUT /api/v1/emergency/{emergency_id}/status
 async fn update_emergency_status(
     path: web::Path<String>,
     req: web::Json<StatusUpdateRequest>,
-    http_req: HttpRequest,
+    session: SecureAuthSession,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    let emergency_id = path.into_inner();
-    // TODO: Implement emergency status update
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Emergency status updated",
-        "emergency_id": emergency_id,
-        "status": req.status
-    })))
+    let req = req.into_inner();
+    let response = container.update_emergency_response_status_use_case
+        .execute_validated(UpdateEmergencyResponseStatusRequest {
+            response_id: EmergencyResponseId(parse_uuid(&path.into_inner(), "emergency id")?),
+            new_status: req.status.parse()?,
+            actor: session.policy_subject(),
+            location: req.location.as_ref().map(parse_point).transpose()?,
+            notes: req.progress_notes,
+            estimated_completion: req.estimated_completion.as_deref()
+                .map(|raw| parse_timestamp(raw, "estimated_completion"))
+                .transpose()?,
+        })
+        .await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        EmergencyResponseView::from_response(response, true)
+    )))
+}
+
+/// GET /api/v1/emergency/teams
+async fn list_teams(
+    query: web::Query<TeamListQuery>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_response_access(&container, &session)?;
+    let team_type = query.team_type.as_deref().map(str::parse::<TeamType>).transpose()?;
+    let availability = query.availability.as_deref().map(str::parse::<TeamAvailability>).transpose()?;
+
+    let teams = container.response_team_repository.find_by_filter(team_type, availability).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(teams)))
+}
+
+/// POST /api/v1/emergency/teams
+async fn create_team(
+    req: web::Json<CreateTeamRequest>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ManageEmergencyResponse)?;
+    let req = req.into_inner();
+
+    let mut team = ResponseTeam::new(req.name, req.team_type.parse()?, parse_point(&req.location)?, req.personnel)?;
+    team.station_id = resolve_station_id(&container, req.station_id.as_deref()).await?;
+    team.vehicles = req.vehicles.unwrap_or_default();
+
+    let team = container.response_team_repository.save(&team).await?;
+    Ok(HttpResponse::Created().json(ApiResponse::success(team)))
 }
 
 /// GET /api/v1/emergency/teams/available
 async fn get_available_teams(
-    http_req: HttpRequest,
+    query: web::Query<AvailableTeamsQuery>,
+    session: SecureAuthSession,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    // TODO: Implement get available response teams
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Available teams",
-        "teams": [
-            {
-                "id": "team_001",
-                "name": "Alpha Rescue Team",
-                "type": "rescue",
-                "location": { "lat": -6.2088, "lng": 106.8456 },
-                "members": 8,
-                "status": "available"
-            }
-        ]
-    })))
+    require_response_access(&container, &session)?;
+    let team_type = query.team_type.as_deref().map(str::parse::<TeamType>).transpose()?;
+    let min_personnel = query.min_personnel.unwrap_or(1);
+    let limit = query.limit.unwrap_or(DEFAULT_TEAM_LIMIT).clamp(1, MAX_TEAM_LIMIT);
+
+    let teams = container.response_team_repository
+        .find_by_filter(team_type, Some(TeamAvailability::Available))
+        .await?;
+
+    // With a reference point, closest teams come first
+    let views: Vec<RankedTeamView> = match (query.latitude, query.longitude) {
+        (Some(latitude), Some(longitude)) => {
+            let origin = GeoPoint::new(latitude, longitude).map_err(|e| AppError::Validation(e.to_string()))?;
+            rank_teams_by_distance(&teams, &origin, min_personnel, limit)
+                .into_iter()
+                .map(|(team, distance)| RankedTeamView { team: team.clone(), distance_km: Some(distance) })
+                .collect()
+        }
+        (None, None) => teams.into_iter()
+            .filter(|team| team.has_capacity(min_personnel))
+            .take(limit)
+            .map(|team| RankedTeamView { team, distance_km: None })
+            .collect(),
+        _ => return Err(AppError::Validation("latitude and longitude must be provided together".to_string()).into()),
+    };
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(views)))
 }
 
 /// GET /api/v1/emergency/teams/{team_id}
 async fn get_team_details(
     path: web::Path<String>,
-    http_req: HttpRequest,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_response_access(&container, &session)?;
+    let team = load_team(&container, &path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(team)))
+}
+
+/// PUT /api/v1/emergency/teams/{team_id}
+async fn update_team(
+    path: web::Path<String>,
+    req: web::Json<UpdateTeamRequest>,
+    session: SecureAuthSession,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    let team_id = path.into_inner();
-    // TODO: Implement get team details
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Team details",
-        "team_id": team_id
-    })))
+    require_permission(&container.policy_engine, &session, Permission::ManageEmergencyResponse)?;
+    let req = req.into_inner();
+    let mut team = load_team(&container, &path.into_inner()).await?;
+
+    if let Some(name) = req.name {
+        if name.trim().is_empty() {
+            return Err(AppError::Validation("Team name is required".to_string()).into());
+        }
+        team.name = name;
+    }
+    if let Some(team_type) = req.team_type.as_deref() {
+        team.team_type = team_type.parse()?;
+    }
+    if let Some(personnel) = req.personnel {
+        if personnel == 0 {
+            return Err(AppError::Validation("A team needs at least one member".to_string()).into());
+        }
+        team.personnel = personnel;
+    }
+    if let Some(vehicles) = req.vehicles {
+        team.vehicles = vehicles;
+    }
+    if req.station_id.is_some() {
+        team.station_id = resolve_station_id(&container, req.station_id.as_deref()).await?;
+    }
+    if let Some(availability) = req.availability.as_deref() {
+        team.set_availability(availability.parse()?)?;
+    }
+    team.updated_at = Utc::now();
+    team.version += 1;
+
+    let team = container.response_team_repository.update(&team).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(team)))
+}
+
+/// DELETE /api/v1/emergency/teams/{team_id}
+async fn delete_team(
+    path: web::Path<String>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ManageEmergencyResponse)?;
+    let team = load_team(&container, &path.into_inner()).await?;
+    if team.current_response_id.is_some() {
+        return Err(AppError::BusinessRuleViolation(
+            "Cannot remove a team while it is dispatched to a response".to_string()
+        ).into());
+    }
+
+    container.response_team_repository.delete(&team.id).await?;
+    Ok(HttpResponse::NoContent().finish())
 }
 
 /// GET /api/v1/emergency/teams/{team_id}/location
+/// Latest GPS fix from the team's devices, or the last position set by hand when that is newer
 async fn get_team_location(
     path: web::Path<String>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_response_access(&container, &session)?;
+    let team = load_team(&container, &path.into_inner()).await?;
+    let fix = container.location_tracking_repository.latest_for_team(&team.id).await?
+        .filter(|fix| fix.recorded_at >= team.location_updated_at);
+    let (location, timestamp, source) = match &fix {
+        Some(fix) => (&fix.position, fix.recorded_at, "gps"),
+        None => (&team.location, team.location_updated_at, "manual"),
+    };
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "team_id": team.id,
+        "location": location,
+        "accuracy_m": fix.as_ref().and_then(|f| f.accuracy_m),
+        "speed_mps": fix.as_ref().and_then(|f| f.speed_mps),
+        "heading_deg": fix.as_ref().and_then(|f| f.heading_deg),
+        "source": source,
+        "availability": team.availability,
+        "current_response_id": team.current_response_id,
+        "timestamp": timestamp
+    }))))
+}
+
+/// PUT /api/v1/emergency/teams/{team_id}/location
+async fn update_team_location(
+    path: web::Path<String>,
+    req: web::Json<Coordinates>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::UpdateEmergencyStatus)?;
+    let mut team = load_team(&container, &path.into_inner()).await?;
+    team.update_location(parse_point(&req)?);
+
+    let team = container.response_team_repository.update(&team).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "team_id": team.id,
+        "location": team.location,
+        "timestamp": team.location_updated_at
+    }))))
+}
+
+/// GET /api/v1/emergency/teams/stations
+async fn list_stations(
     http_req: HttpRequest,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    let team_id = path.into_inner();
-    // TODO: Implement real-time team location tracking
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Team location",
-        "team_id": team_id,
-        "location": {
-            "lat": -6.2088,
-            "lng": 106.8456,
-            "accuracy": 10,
-            "timestamp": chrono::Utc::now().to_rfc3339()
-        }
-    })))
+    let stations = container.emergency_station_repository.find_all().await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(stations)))
+}
+
+/// POST /api/v1/emergency/teams/stations
+async fn create_station(
+    req: web::Json<StationRequest>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ManageEmergencyResponse)?;
+    let req = req.into_inner();
+
+    let mut station = EmergencyStation::new(req.name, req.station_type.parse()?, parse_point(&req.location)?)?;
+    station.address = req.address;
+    station.contact_phone = req.contact_phone;
+    station.staff_count = req.staff_count.unwrap_or(0);
+    station.vehicles = req.vehicles.unwrap_or_default();
+    station.is_operational = req.is_operational.unwrap_or(true);
+
+    let station = container.emergency_station_repository.save(&station).await?;
+    Ok(HttpResponse::Created().json(ApiResponse::success(station)))
+}
+
+/// GET /api/v1/emergency/teams/stations/{station_id}
+async fn get_station(
+    path: web::Path<String>,
+    http_req: HttpRequest,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    let station = load_station(&container, &path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(station)))
+}
+
+/// PUT /api/v1/emergency/teams/stations/{station_id}
+async fn update_station(
+    path: web::Path<String>,
+    req: web::Json<StationRequest>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ManageEmergencyResponse)?;
+    let req = req.into_inner();
+    let existing = load_station(&container, &path.into_inner()).await?;
+
+    let mut station = EmergencyStation::new(req.name, req.station_type.parse()?, parse_point(&req.location)?)?;
+    station.id = existing.id;
+    station.created_at = existing.created_at;
+    station.address = req.address;
+    station.contact_phone = req.contact_phone;
+    station.staff_count = req.staff_count.unwrap_or(existing.staff_count);
+    station.vehicles = req.vehicles.unwrap_or(existing.vehicles);
+    station.is_operational = req.is_operational.unwrap_or(existing.is_operational);
+
+    let station = container.emergency_station_repository.update(&station).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(station)))
+}
+
+/// DELETE /api/v1/emergency/teams/stations/{station_id}
+async fn delete_station(
+    path: web::Path<String>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ManageEmergencyResponse)?;
+    let station = load_station(&container, &path.into_inner()).await?;
+
+    // Teams stay in the registry without a home station
+    container.emergency_station_repository.delete(&station.id).await?;
+    Ok(HttpResponse::NoContent().finish())
 }
 
 /// POST /api/v1/emergency/resources/request
 async fn request_resources(
     req: web::Json<ResourceRequest>,
-    http_req: HttpRequest,
+    session: SecureAuthSession,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    // TODO: Implement resource request logic
-    Ok(HttpResponse::Created().json(serde_json::json!({
-        "message": "Resource request submitted",
-        "request_id": "resource_req_123456",
-        "type": req.resource_type,
-        "quantity": req.quantity,
-        "urgency": req.urgency
-    })))
+    require_permission(&container.policy_engine, &session, Permission::ManageResources)
+        .or_else(|_| require_permission(&container.policy_engine, &session, Permission::ManageEmergencyResponse))?;
+    let req = req.into_inner();
+    if req.quantity == 0 {
+        return Err(AppError::Validation("Quantity must be greater than zero".to_string()).into());
+    }
+    if req.resource_type.trim().is_empty() {
+        return Err(AppError::Validation("Resource type is required".to_string()).into());
+    }
+
+    let disaster_id = DisasterId(parse_uuid(&req.disaster_id, "disaster id")?);
+    let mut disaster = container.disaster_repository.find_by_id(&disaster_id).await?
+        .ok_or_else(|| AppError::NotFound(format!("Disaster {} not found", disaster_id)))?;
+
+    let need = ResourceNeed {
+        resource_type: req.resource_type.trim().to_lowercase(),
+        quantity: req.quantity,
+        urgency: parse_urgency(&req.urgency)?,
+        description: Some(req.description).filter(|d| !d.trim().is_empty()),
+    };
+    disaster.add_resource_need(need.clone())?;
+    container.disaster_repository.update(&disaster).await?;
+
+    tracing::info!(
+        "Resource request for disaster {} by {}: {} x{}",
+        disaster_id, session.user_id, need.resource_type, need.quantity
+    );
+
+    let volunteer_assignments = auto_assign_volunteers(&container, &session, disaster_id, vec![need.resource_type.clone()]).await;
+
+    Ok(HttpResponse::Created().json(ApiResponse::success(serde_json::json!({
+        "disaster_id": disaster_id,
+        "resource": need,
+        "open_requests": disaster.resources_needed.len(),
+        "volunteer_assignments": volunteer_assignments
+    }))))
 }
 
 /// GET /api/v1/emergency/resources/available
@@ -200,94 +856,166 @@ async fn get_available_resources(
     http_req: HttpRequest,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    // TODO: Implement get available resources
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Available resources",
-        "resources": {
-            "vehicles": {
-                "ambulances": 5,
-                "fire_trucks": 3,
-                "rescue_vehicles": 4
-            },
-            "equipment": {
-                "medical_kits": 20,
-                "rescue_tools": 15,
-                "communication_devices": 30
-            },
-            "personnel": {
-                "paramedics": 12,
-                "firefighters": 25,
-                "rescue_specialists": 18
-            }
-        }
-    })))
+    let stock = container.resource_inventory_repository.available_stock().await?;
+
+    let mut by_category: BTreeMap<String, Vec<ResourceStock>> = BTreeMap::new();
+    for item in stock {
+        by_category.entry(item.category.clone()).or_default().push(item);
+    }
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(by_category)))
 }
 
 /// GET /api/v1/emergency/evacuation/routes
 async fn get_evacuation_routes(
-    query: web::Query<Coordinates>,
+    query: web::Query<EvacuationRouteQuery>,
     http_req: HttpRequest,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    // TODO: Implement evacuation route calculation
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Evacuation routes",
-        "from": {
-            "lat": query.latitude,
-            "lng": query.longitude
-        },
-        "routes": [
-            {
-                "route_id": "route_001",
-                "destination": "Shelter A",
-                "distance": "2.5 km",
-                "estimated_time": "15 minutes",
-                "safety_level": "high",
-                "waypoints": []
+    let origin = GeoPoint::new(query.latitude, query.longitude)
+        .map_err(|e| AppError::Validation(e.to_string()))?;
+    let limit = query.limit.unwrap_or(DEFAULT_ROUTE_LIMIT).clamp(1, MAX_ROUTE_LIMIT);
+    let shelters = load_shelters(&container, &origin).await?;
+    let road_network = container.routing_service.is_available();
+
+    // Route to a few more candidates than requested: the closest shelter may be cut off
+    let mut routes = Vec::new();
+    for shelter in shelters.into_iter().take(limit * 2) {
+        let route = if road_network {
+            match container.routing_service.route(&origin, &shelter.coordinates, TravelMode::Walking).await? {
+                Some(route) => route,
+                None => continue,
             }
-        ]
-    })))
+        } else {
+            let distance_km = origin.distance_to(&shelter.coordinates);
+            Route {
+                distance_km,
+                duration_minutes: distance_km / WALKING_SPEED_KMH * 60.0,
+                waypoints: vec![origin.clone(), shelter.coordinates.clone()],
+                closures_avoided: 0,
+            }
+        };
+
+        routes.push(EvacuationRouteView {
+            destination: LocationResponse::from_location(shelter, Some(&origin)),
+            distance_km: route.distance_km,
+            estimated_minutes: route.duration_minutes,
+            closures_avoided: route.closures_avoided,
+            waypoints: route.waypoints,
+        });
+    }
+    routes.sort_by(|a, b| a.estimated_minutes.total_cmp(&b.estimated_minutes));
+    routes.truncate(limit);
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "from": origin,
+        "source": if road_network { "road_network" } else { "straight_line" },
+        "routes": routes
+    }))))
 }
 
 /// GET /api/v1/emergency/shelters/nearest
 async fn get_nearest_shelters(
-    query: web::Query<Coordinates>,
+    query: web::Query<NearestShelterQuery>,
     http_req: HttpRequest,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    // TODO: Implement nearest shelter lookup
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Nearest shelters",
-        "location": {
-            "lat": query.latitude,
-            "lng": query.longitude
-        },
-        "shelters": [
-            {
-                "id": "shelter_001",
-                "name": "Community Center A",
-                "distance": "1.2 km",
-                "capacity": 500,
-                "current_occupancy": 150,
-                "available_space": 350,
-                "facilities": ["medical", "food", "restrooms"]
-            }
-        ]
-    })))
+    let origin = GeoPoint::new(query.latitude, query.longitude)
+        .map_err(|e| AppError::Validation(e.to_string()))?;
+    let limit = query.limit.unwrap_or(DEFAULT_SHELTER_LIMIT).clamp(1, MAX_SHELTER_LIMIT);
+    let shelters = load_shelters(&container, &origin).await?;
+
+    let shelters: Vec<LocationResponse> = shelters.into_iter()
+        .take(limit)
+        .map(|l| LocationResponse::from_location(l, Some(&origin)))
+        .collect();
+    Ok(HttpResponse::Ok().json(ApiResponse::success(shelters)))
 }
 
 /// POST /api/v1/emergency/alerts/broadcast
+/// Responds with the CAP 1.2 document when the client accepts `application/cap+xml`
 async fn broadcast_emergency_alert(
-    req: web::Json<serde_json::Value>,
     http_req: HttpRequest,
+    req: web::Json<BroadcastAlertRequest>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::SendNotifications)?;
+
+    let req = req.into_inner();
+    let target = match (req.latitude, req.longitude, req.radius_km, &req.zone_id, &req.area) {
+        (Some(latitude), Some(longitude), Some(radius_km), None, None) => AlertTarget::Radius {
+            center: GeoPoint::new(latitude, longitude).map_err(|e| AppError::Validation(e.to_string()))?,
+            radius_km,
+        },
+        (None, None, None, Some(zone_id), None) => AlertTarget::Zone {
+            zone_id: parse_uuid(zone_id, "zone id")?,
+        },
+        (None, None, None, None, Some(area)) => AlertTarget::Area(GeoArea::from_geojson(area)?),
+        _ => return Err(AppError::Validation(
+            "Specify exactly one target: latitude, longitude and radius_km, zone_id, or area".to_string()
+        ).into()),
+    };
+
+    let alert = container.send_emergency_alert_use_case
+        .execute_validated(SendEmergencyAlertRequest {
+            disaster_id: DisasterId(parse_uuid(&req.disaster_id, "disaster id")?),
+            alert_type: req.alert_type,
+            severity: parse_severity(&req.severity)?,
+            target,
+            message: req.message,
+            channels: req.channels,
+            sent_by: session.user_id,
+            expires_at: req.expires_at.as_deref()
+                .map(|raw| parse_timestamp(raw, "expires_at"))
+                .transpose()?,
+        })
+        .await?;
+
+    let wants_cap = http_req.headers().get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|accept| accept.contains(CAP_CONTENT_TYPE));
+    if wants_cap {
+        return Ok(HttpResponse::Ok().content_type(CAP_CONTENT_TYPE).body(alert.cap_alert.to_xml()));
+    }
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "alert_id": alert.alert_id,
+        "disaster_id": alert.disaster_id,
+        "recipients_targeted": alert.recipients_targeted,
+        "deliveries_queued": alert.deliveries_queued,
+        "channels_used": alert.channels_used,
+        "broadcast_time": alert.sent_at,
+        "estimated_delivery_seconds": alert.estimated_delivery_time
+    }))))
+}
+
+/// POST /api/v1/emergency/alerts/cap
+/// Ingests a CAP 1.2 alert from an external warning system as a disaster report
+async fn import_cap_alert(
+    body: String,
+    session: SecureAuthSession,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    // TODO: Implement emergency alert broadcast
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Emergency alert broadcasted",
-        "alert_id": "alert_123456",
-        "broadcast_time": chrono::Utc::now().to_rfc3339()
-    })))
+    require_permission(&container.policy_engine, &session, Permission::ManageAlerts)?;
+
+    let imported = container.import_cap_alert_use_case
+        .execute(ImportCapAlertRequest { xml: body, imported_by: session.user_id })
+        .await?;
+
+    let payload = serde_json::json!({
+        "identifier": imported.identifier,
+        "sender": imported.sender,
+        "outcome": imported.outcome.as_str(),
+        "disaster_id": imported.disaster.as_ref().map(|d| d.id),
+        "title": imported.disaster.as_ref().map(|d| d.title.clone()),
+        "skipped_reason": imported.skipped_reason,
+    });
+    if imported.outcome == CapImportOutcome::Created {
+        Ok(HttpResponse::Created().json(ApiResponse::success(payload)))
+    } else {
+        Ok(HttpResponse::Ok().json(ApiResponse::success(payload)))
+    }
 }
 
 /// GET /api/v1/emergency/command-center/status
@@ -295,32 +1023,65 @@ async fn get_command_center_status(
     http_req: HttpRequest,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    // TODO: Implement command center status overview
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Command center status",
+    let responses = container.emergency_response_repository.find_active().await?;
+    let active_disasters = container.disaster_repository.find_active().await?;
+    let stock = container.resource_inventory_repository.available_stock().await?;
+
+    let mut by_status: BTreeMap<&'static str, usize> = BTreeMap::new();
+    for response in &responses {
+        *by_status.entry(response.status.as_str()).or_default() += 1;
+    }
+    let mut deployed_teams: Vec<Uuid> = responses.iter()
+        .flat_map(|r| r.assigned_teams.iter().copied())
+        .collect();
+    deployed_teams.sort();
+    deployed_teams.dedup();
+
+    let depleted_categories: Vec<String> = ["food", "medical", "shelter", "water"].iter()
+        .filter(|category| !stock.iter().any(|s| s.category.eq_ignore_ascii_case(category)))
+        .map(|category| category.to_string())
+        .collect();
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
         "status": "operational",
-        "active_operations": 2,
-        "deployed_teams": 5,
-        "available_teams": 3,
-        "resource_status": "adequate",
-        "communication_status": "online"
-    })))
+        "active_operations": responses.len(),
+        "operations_by_status": by_status,
+        "critical_operations": responses.iter().filter(|r| r.priority >= 5).count(),
+        "deployed_teams": deployed_teams.len(),
+        "deployed_personnel": responses.iter().map(|r| r.personnel_count as u64).sum::<u64>(),
+        "active_disasters": active_disasters.len(),
+        "resource_status": if depleted_categories.is_empty() { "adequate" } else { "shortage" },
+        "depleted_resource_categories": depleted_categories,
+        "generated_at": Utc::now()
+    }))))
 }
 
 pub fn configure_emergency_routes(cfg: &mut web::ServiceConfig) {
+    // Static paths are registered before `/{emergency_id}` so they are not captured by it
     cfg
-        .route("/response", web::post().to(initiate_emergency_response))
-        .route("/active", web::get().to(get_active_emergencies))
-        .route("/{emergency_id}", web::get().to(get_emergency_details))
-        .route("/{emergency_id}/dispatch", web::post().to(dispatch_teams))
-        .route("/{emergency_id}/status", web::put().to(update_emergency_status))
-        .route("/teams/available", web::get().to(get_available_teams))
-        .route("/teams/{team_id}", web::get().to(get_team_details))
-        .route("/teams/{team_id}/location", web::get().to(get_team_location))
-        .route("/resources/request", web::post().to(request_resources))
+        .route("/response", web::post().to(initiate_emergency_response).wrap(AuthMiddleware::new()))
+        .route("/active", web::get().to(get_active_emergencies).wrap(AuthMiddleware::new()))
+        .route("/teams", web::get().to(list_teams).wrap(AuthMiddleware::new()))
+        .route("/teams", web::post().to(create_team).wrap(AuthMiddleware::new()))
+        .route("/teams/available", web::get().to(get_available_teams).wrap(AuthMiddleware::new()))
+        .route("/teams/stations", web::get().to(list_stations))
+        .route("/teams/stations", web::post().to(create_station).wrap(AuthMiddleware::new()))
+        .route("/teams/stations/{station_id}", web::get().to(get_station))
+        .route("/teams/stations/{station_id}", web::put().to(update_station).wrap(AuthMiddleware::new()))
+        .route("/teams/stations/{station_id}", web::delete().to(delete_station).wrap(AuthMiddleware::new()))
+        .route("/teams/{team_id}", web::get().to(get_team_details).wrap(AuthMiddleware::new()))
+        .route("/teams/{team_id}", web::put().to(update_team).wrap(AuthMiddleware::new()))
+        .route("/teams/{team_id}", web::delete().to(delete_team).wrap(AuthMiddleware::new()))
+        .route("/teams/{team_id}/location", web::get().to(get_team_location).wrap(AuthMiddleware::new()))
+        .route("/teams/{team_id}/location", web::put().to(update_team_location).wrap(AuthMiddleware::new()))
+        .route("/resources/request", web::post().to(request_resources).wrap(AuthMiddleware::new()))
         .route("/resources/available", web::get().to(get_available_resources))
         .route("/evacuation/routes", web::get().to(get_evacuation_routes))
         .route("/shelters/nearest", web::get().to(get_nearest_shelters))
-        .route("/alerts/broadcast", web::post().to(broadcast_emergency_alert))
-        .route("/command-center/status", web::get().to(get_command_center_status));
+        .route("/alerts/broadcast", web::post().to(broadcast_emergency_alert).wrap(AuthMiddleware::new()))
+        .route("/alerts/cap", web::post().to(import_cap_alert).wrap(AuthMiddleware::new()))
+        .route("/command-center/status", web::get().to(get_command_center_status))
+        .route("/{emergency_id}", web::get().to(get_emergency_details).wrap(AuthMiddleware::new()))
+        .route("/{emergency_id}/dispatch", web::post().to(dispatch_teams).wrap(AuthMiddleware::new()))
+        .route("/{emergency_id}/status", web::put().to(update_emergency_status).wrap(AuthMiddleware::new()));
 }
@@ -1,9 +1,25 @@
 /// Location and mapping API endpoints
 /// Handles location data, geocoding, and mapping services
 
-use actix_web::{web, HttpRequest, HttpResponse, Result};
+use actix_web::{web, HttpResponse, Result};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 use crate::infrastructure::AppContainer;
+use crate::infrastructure::security::SecureAuthSession;
+use crate::middleware::auth::AuthMiddleware;
+use crate::presentation::api::v1::require_permission;
+use crate::domain::entities::location::{
+    AdministrativeLevel, EmergencyContact, Location, LocationSearch, LocationType, RiskFactor,
+};
+use crate::domain::value_objects::{Address, Coordinates};
+use crate::shared::{ApiResponse, AppError, AppResult, LocationId, PaginatedResponse, Permission};
+
+/// Default search radius in km for nearby lookups
+const DEFAULT_RADIUS_KM: f64 = 5.0;
+/// Maximum distance in km a reverse-geocoded location may be from the query point
+const REVERSE_GEOCODE_RADIUS_KM: f64 = 2.0;
+const DEFAULT_PAGE_SIZE: u32 = 20;
+const MAX_PAGE_SIZE: u32 = 100;
 
 #[derive(Debug, Deserialize)]
 pub struct CreateLocationRequest {
@@ -16,6 +32,16 @@ pub struct CreateLocationRequest {
     pub contact_info: Option<String>,
     pub capacity: Option<u32>,
     pub operational_hours: Option<String>,
+    pub city: String,
+    pub province: String,
+    pub region: Option<String>,
+    pub postal_code: Option<String>,
+    pub administrative_level: Option<String>, // province, regency, district, village, hamlet, neighborhood
+    pub population: Option<u32>,
+    pub area_km2: Option<f64>,
+    pub elevation_meters: Option<f64>,
+    pub risk_factors: Option<Vec<RiskFactor>>,
+    pub emergency_contacts: Option<Vec<EmergencyContact>>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -42,70 +68,271 @@ pub struct ReverseGeocodeQuery {
     pub lng: f64,
 }
 
+#[derive(Debug, Serialize)]
+pub struct LocationResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub address: Address,
+    pub region: Option<String>,
+    pub location_type: String,
+    pub administrative_level: AdministrativeLevel,
+    pub population: Option<u32>,
+    pub area_km2: Option<f64>,
+    pub elevation_meters: Option<f64>,
+    pub is_disaster_prone: bool,
+    pub risk_factors: Vec<RiskFactor>,
+    pub emergency_contacts: Vec<EmergencyContact>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distance_km: Option<f64>,
+}
+
+impl LocationResponse {
+    pub(crate) fn from_location(location: Location, origin: Option<&Coordinates>) -> Self {
+        Self {
+            id: location.id.0,
+            distance_km: origin.map(|o| o.distance_to(&location.coordinates)),
+            name: location.name,
+            latitude: location.coordinates.latitude,
+            longitude: location.coordinates.longitude,
+            address: location.address,
+            region: location.region,
+            location_type: location_type_label(&location.location_type),
+            administrative_level: location.administrative_level,
+            population: location.population,
+            area_km2: location.area_km2,
+            elevation_meters: location.elevation_meters,
+            is_disaster_prone: location.is_disaster_prone,
+            risk_factors: location.risk_factors,
+            emergency_contacts: location.emergency_contacts,
+        }
+    }
+}
+
+fn parse_location_type(value: &str) -> LocationType {
+    match value.trim().to_lowercase().replace('-', "_").as_str() {
+        "city" => LocationType::City,
+        "village" => LocationType::Village,
+        "district" => LocationType::District,
+        "province" => LocationType::Province,
+        "shelter" | "evacuation_center" => LocationType::EvacuationCenter,
+        "hospital" => LocationType::Hospital,
+        "school" => LocationType::School,
+        "fire_station" => LocationType::FireStation,
+        "police_station" => LocationType::PoliceStation,
+        other => LocationType::Other(other.to_string()),
+    }
+}
+
+fn location_type_label(location_type: &LocationType) -> String {
+    match location_type {
+        LocationType::City => "city".to_string(),
+        LocationType::Village => "village".to_string(),
+        LocationType::District => "district".to_string(),
+        LocationType::Province => "province".to_string(),
+        LocationType::EvacuationCenter => "evacuation_center".to_string(),
+        LocationType::Hospital => "hospital".to_string(),
+        LocationType::School => "school".to_string(),
+        LocationType::FireStation => "fire_station".to_string(),
+        LocationType::PoliceStation => "police_station".to_string(),
+        LocationType::Other(label) => label.clone(),
+    }
+}
+
+fn parse_administrative_level(value: Option<&str>) -> AppResult<AdministrativeLevel> {
+    match value.map(|v| v.trim().to_lowercase()).as_deref() {
+        Some("province") | Some("provinsi") => Ok(AdministrativeLevel::Province),
+        Some("regency") | Some("kabupaten") | Some("kota") => Ok(AdministrativeLevel::Regency),
+        Some("district") | Some("kecamatan") => Ok(AdministrativeLevel::District),
+        None | Some("village") | Some("desa") | Some("kelurahan") => Ok(AdministrativeLevel::Village),
+        Some("hamlet") | Some("dusun") | Some("rw") => Ok(AdministrativeLevel::Hamlet),
+        Some("neighborhood") | Some("rt") => Ok(AdministrativeLevel::Neighborhood),
+        Some(other) => Err(AppError::Validation(format!("Unknown administrative level: {}", other))),
+    }
+}
+
+fn parse_location_id(raw: &str) -> AppResult<LocationId> {
+    Uuid::parse_str(raw)
+        .map(LocationId)
+        .map_err(|_| AppError::Validation(format!("Invalid location id: {}", raw)))
+}
+
+fn parse_origin(lat: Option<f64>, lng: Option<f64>) -> AppResult<Option<Coordinates>> {
+    match (lat, lng) {
+        (Some(lat), Some(lng)) => Coordinates::new(lat, lng)
+            .map(Some)
+            .map_err(|e| AppError::Validation(e.to_string())),
+        (None, None) => Ok(None),
+        _ => Err(AppError::Validation("Both lat and lng are required".to_string())),
+    }
+}
+
+/// Build a domain location from the request, keeping identity and audit of `existing`
+fn build_location(req: CreateLocationRequest, existing: Option<Location>) -> AppResult<Location> {
+    let coordinates = Coordinates::new(req.latitude, req.longitude)
+        .map_err(|e| AppError::Validation(e.to_string()))?;
+    let address = Address::new(
+        req.address,
+        req.city,
+        req.province,
+        req.postal_code,
+        Some("Indonesia".to_string()),
+    )?;
+    let location_type = parse_location_type(&req.location_type);
+
+    let mut location = Location::new(
+        req.name,
+        coordinates,
+        address,
+        location_type.clone(),
+        parse_administrative_level(req.administrative_level.as_deref())?,
+    )?;
+
+    location.region = req.region.map(|r| r.trim().to_string()).filter(|r| !r.is_empty());
+    if let Some(existing) = existing {
+        location.id = existing.id;
+        location.audit = existing.audit;
+        location.region = location.region.or(existing.region);
+    }
+
+    location.population = req.population;
+    location.area_km2 = req.area_km2;
+    location.elevation_meters = req.elevation_meters;
+    if let Some(elevation) = req.elevation_meters {
+        location.coordinates = location.coordinates.with_altitude(elevation);
+    }
+
+    for risk_factor in req.risk_factors.unwrap_or_default() {
+        location.add_risk_factor(risk_factor)?;
+    }
+    for contact in req.emergency_contacts.unwrap_or_default() {
+        location.add_emergency_contact(contact)?;
+    }
+    if let Some(phone) = req.contact_info.filter(|c| !c.trim().is_empty()) {
+        location.add_emergency_contact(EmergencyContact {
+            contact_type: location_type_label(&location_type),
+            name: location.name.clone(),
+            phone,
+            is_primary: true,
+        })?;
+    }
+
+    Ok(location)
+}
+
+/// Locations may be edited by area coordinators or anyone managing location data
+fn require_location_write(container: &AppContainer, session: &SecureAuthSession) -> AppResult<()> {
+    require_permission(&container.policy_engine, session, Permission::WriteArea)
+        .or_else(|_| require_permission(&container.policy_engine, session, Permission::UpdateLocationData))
+}
+
+/// Run `criteria` narrowed by the query's own filters and return the requested page
+async fn search_page(
+    container: &AppContainer,
+    mut criteria: LocationSearch,
+    query: &LocationSearchQuery,
+) -> AppResult<PaginatedResponse<LocationResponse>> {
+    let origin = parse_origin(query.lat, query.lng)?;
+    if let Some(origin) = &origin {
+        let radius = query.radius.unwrap_or(DEFAULT_RADIUS_KM);
+        if radius.is_nan() || radius <= 0.0 {
+            return Err(AppError::Validation("Radius must be greater than zero".to_string()));
+        }
+        criteria.near = Some((origin.clone(), radius));
+    }
+    criteria.text = query.q.clone();
+    criteria.province = query.province.clone();
+    criteria.city = query.city.clone();
+    if criteria.types.is_empty() {
+        criteria.types.extend(query.location_type.as_deref().map(parse_location_type));
+    }
+
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let offset = u64::from(query.page.unwrap_or(1).max(1) - 1) * u64::from(limit);
+    let (locations, total) = container.location_repository
+        .search(&criteria, i64::from(limit), i64::try_from(offset).unwrap_or(i64::MAX))
+        .await?;
+
+    let page = locations.into_iter()
+        .map(|l| LocationResponse::from_location(l, origin.as_ref()))
+        .collect();
+    Ok(PaginatedResponse::new(page, total, limit, u32::try_from(offset).unwrap_or(u32::MAX)))
+}
+
 /// POST /api/v1/locations
 async fn create_location(
     req: web::Json<CreateLocationRequest>,
-    http_req: HttpRequest,
+    session: SecureAuthSession,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    // TODO: Implement create location logic
-    Ok(HttpResponse::Created().json(serde_json::json!({
-        "message": "Location created successfully",
-        "name": req.name,
-        "type": req.location_type
-    })))
+    require_location_write(&container, &session)?;
+    let mut location = build_location(req.into_inner(), None)?;
+    location.audit.created_by = Some(session.user_id);
+    location.audit.updated_by = Some(session.user_id);
+    let saved = container.location_repository.save(&location).await?;
+
+    Ok(HttpResponse::Created().json(ApiResponse::success(
+        LocationResponse::from_location(saved, None)
+    )))
 }
 
 /// GET /api/v1/locations
 async fn list_locations(
     query: web::Query<LocationSearchQuery>,
-    http_req: HttpRequest,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    // TODO: Implement location listing with filters
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Locations list",
-        "filters": query.into_inner()
-    })))
+    let page = search_page(&container, LocationSearch::default(), &query).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(page)))
 }
 
 /// GET /api/v1/locations/{location_id}
 async fn get_location_by_id(
     path: web::Path<String>,
-    http_req: HttpRequest,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    let location_id = path.into_inner();
-    // TODO: Implement get location by ID logic
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Location details",
-        "location_id": location_id
-    })))
+    let location_id = parse_location_id(&path.into_inner())?;
+    let location = container.location_repository.find_by_id(&location_id).await?
+        .ok_or_else(|| AppError::NotFound(format!("Location {} not found", location_id)))?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        LocationResponse::from_location(location, None)
+    )))
 }
 
 /// PUT /api/v1/locations/{location_id}
 async fn update_location(
     path: web::Path<String>,
     req: web::Json<CreateLocationRequest>,
-    http_req: HttpRequest,
+    session: SecureAuthSession,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    let location_id = path.into_inner();
-    // TODO: Implement update location logic
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Location updated successfully",
-        "location_id": location_id
-    })))
+    require_location_write(&container, &session)?;
+    let location_id = parse_location_id(&path.into_inner())?;
+    let existing = container.location_repository.find_by_id(&location_id).await?
+        .ok_or_else(|| AppError::NotFound(format!("Location {} not found", location_id)))?;
+
+    let mut location = build_location(req.into_inner(), Some(existing))?;
+    location.audit.updated_by = Some(session.user_id);
+    let updated = container.location_repository.update(&location).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        LocationResponse::from_location(updated, None)
+    )))
 }
 
 /// DELETE /api/v1/locations/{location_id}
 async fn delete_location(
     path: web::Path<String>,
-    http_req: HttpRequest,
+    session: SecureAuthSession,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    let location_id = path.into_inner();
-    // TODO: Implement delete location logic
+    require_location_write(&container, &session)?;
+    let location_id = parse_location_id(&path.into_inner())?;
+    if !container.location_repository.delete(&location_id).await? {
+        return Err(AppError::NotFound(format!("Location {} not found", location_id)).into());
+    }
+
     Ok(HttpResponse::Ok().json(serde_json::json!({
         "message": "Location deleted",
         "location_id": location_id
@@ -115,141 +342,143 @@ async fn delete_location(
 /// GET /api/v1/locations/nearby
 async fn get_nearby_locations(
     query: web::Query<LocationSearchQuery>,
-    http_req: HttpRequest,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    // TODO: Implement get nearby locations
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Nearby locations",
-        "coordinates": {
-            "lat": query.lat,
-            "lng": query.lng,
-            "radius": query.radius.unwrap_or(5.0)
-        }
-    })))
+    if query.lat.is_none() || query.lng.is_none() {
+        return Err(AppError::Validation("lat and lng are required".to_string()).into());
+    }
+    let page = search_page(&container, LocationSearch::default(), &query).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(page)))
 }
 
 /// GET /api/v1/locations/shelters
 async fn get_emergency_shelters(
     query: web::Query<LocationSearchQuery>,
-    http_req: HttpRequest,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    // TODO: Implement get emergency shelters
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Emergency shelters"
-    })))
+    let criteria = LocationSearch {
+        types: vec![LocationType::EvacuationCenter, LocationType::School],
+        evacuation_only: true,
+        ..LocationSearch::default()
+    };
+    let page = search_page(&container, criteria, &query).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(page)))
 }
 
 /// GET /api/v1/locations/hospitals
 async fn get_hospitals(
     query: web::Query<LocationSearchQuery>,
-    http_req: HttpRequest,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    // TODO: Implement get hospitals
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Hospitals"
-    })))
+    let criteria = LocationSearch { types: vec![LocationType::Hospital], ..LocationSearch::default() };
+    let page = search_page(&container, criteria, &query).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(page)))
 }
 
 /// GET /api/v1/locations/fire-stations
 async fn get_fire_stations(
     query: web::Query<LocationSearchQuery>,
-    http_req: HttpRequest,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    // TODO: Implement get fire stations
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Fire stations"
-    })))
+    let criteria = LocationSearch { types: vec![LocationType::FireStation], ..LocationSearch::default() };
+    let page = search_page(&container, criteria, &query).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(page)))
 }
 
 /// GET /api/v1/locations/police-stations
 async fn get_police_stations(
     query: web::Query<LocationSearchQuery>,
-    http_req: HttpRequest,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    // TODO: Implement get police stations
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Police stations"
-    })))
+    let criteria = LocationSearch { types: vec![LocationType::PoliceStation], ..LocationSearch::default() };
+    let page = search_page(&container, criteria, &query).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(page)))
 }
 
 /// GET /api/v1/locations/geocode
 async fn geocode_address(
     query: web::Query<GeocodeQuery>,
-    http_req: HttpRequest,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    // TODO: Implement geocoding service
+    let location = container.location_repository.search_by_name(&query.address).await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::NotFound(format!("No location matches '{}'", query.address)))?;
+
     Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Geocoding result",
         "address": query.address,
         "coordinates": {
-            "lat": -6.2088,
-            "lng": 106.8456
-        }
+            "lat": location.coordinates.latitude,
+            "lng": location.coordinates.longitude
+        },
+        "location": LocationResponse::from_location(location, None)
     })))
 }
 
 /// GET /api/v1/locations/reverse-geocode
 async fn reverse_geocode(
     query: web::Query<ReverseGeocodeQuery>,
-    http_req: HttpRequest,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    // TODO: Implement reverse geocoding service
+    let origin = Coordinates::new(query.lat, query.lng)
+        .map_err(|e| AppError::Validation(e.to_string()))?;
+    let location = container.location_repository
+        .find_nearby(query.lat, query.lng, REVERSE_GEOCODE_RADIUS_KM)
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::NotFound("No known location near the given coordinates".to_string()))?;
+
+    let address = [
+        Some(location.address.street.as_str()),
+        location.region.as_deref(),
+        Some(location.address.city.as_str()),
+        Some(location.address.province.as_str()),
+        location.address.postal_code.as_deref(),
+    ]
+        .into_iter()
+        .flatten()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(", ");
     Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Reverse geocoding result",
         "coordinates": {
             "lat": query.lat,
             "lng": query.lng
         },
-        "address": "Sample Address, Jakarta, Indonesia"
+        "address": address,
+        "location": LocationResponse::from_location(location, Some(&origin))
     })))
 }
 
 /// GET /api/v1/locations/provinces
 async fn get_provinces(
-    http_req: HttpRequest,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
-    // TODO: Implement get provinces list
+    let provinces = container.location_repository.list_provinces().await?;
     Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Provinces list",
-        "provinces": [
-            "DKI Jakarta",
-            "Jawa Barat",
-            "Jawa Tengah",
-            "Jawa Timur"
-        ]
+        "provinces": provinces
     })))
 }
 
 /// GET /api/v1/locations/cities/{province}
 async fn get_cities_by_province(
     path: web::Path<String>,
-    http_req: HttpRequest,
     container: web::Data<AppContainer>,
 ) -> Result<HttpResponse> {
     let province = path.into_inner();
-    // TODO: Implement get cities by province
+    let cities = container.location_repository.list_cities(&province).await?;
     Ok(HttpResponse::Ok().json(serde_json::json!({
-        "message": "Cities in province",
         "province": province,
-        "cities": []
+        "cities": cities
     })))
 }
 
 pub fn configure_location_routes(cfg: &mut web::ServiceConfig) {
+    // Static segments must be registered before the `/{location_id}` matcher
     cfg
-        .route("", web::post().to(create_location))
+        .route("", web::post().to(create_location).wrap(AuthMiddleware::new()))
         .route("", web::get().to(list_locations))
-        .route("/{location_id}", web::get().to(get_location_by_id))
-        .route("/{location_id}", web::put().to(update_location))
-        .route("/{location_id}", web::delete().to(delete_location))
         .route("/nearby", web::get().to(get_nearby_locations))
         .route("/shelters", web::get().to(get_emergency_shelters))
         .route("/hospitals", web::get().to(get_hospitals))
@@ -258,5 +487,8 @@ pub fn configure_location_routes(cfg: &mut web::ServiceConfig) {
         .route("/geocode", web::get().to(geocode_address))
         .route("/reverse-geocode", web::get().to(reverse_geocode))
         .route("/provinces", web::get().to(get_provinces))
-        .route("/cities/{province}", web::get().to(get_cities_by_province));
+        .route("/cities/{province}", web::get().to(get_cities_by_province))
+        .route("/{location_id}", web::get().to(get_location_by_id))
+        .route("/{location_id}", web::put().to(update_location).wrap(AuthMiddleware::new()))
+        .route("/{location_id}", web::delete().to(delete_location).wrap(AuthMiddleware::new()));
 }
@@ -2,6 +2,11 @@
 /// Contains all v1 API routes organized by domain
 
 use actix_web::web;
+use uuid::Uuid;
+
+use crate::infrastructure::security::SecureAuthSession;
+use crate::shared::policy::{Attributes, PolicyEngine};
+use crate::shared::{AppError, AppResult, Permission};
 
 pub mod auth;
 pub mod users;
@@ -10,6 +15,28 @@ pub mod locations;
 pub mod notifications;
 pub mod analytics;
 pub mod emergency;
+pub mod organizations;
+pub mod roles;
+pub mod reports;
+pub mod volunteers;
+pub mod realtime;
+pub mod tracking;
+
+pub(crate) fn parse_uuid(raw: &str, what: &str) -> AppResult<Uuid> {
+    Uuid::parse_str(raw.trim()).map_err(|_| AppError::Validation(format!("Invalid {}: {}", what, raw)))
+}
+
+/// Check a resource-independent permission against the caller's role and the access policies
+pub(crate) fn require_permission(policy: &PolicyEngine, session: &SecureAuthSession, permission: Permission) -> AppResult<()> {
+    policy.authorize(&session.policy_subject(), permission.as_str(), &Attributes::new())?;
+    if permission.requires_mfa() && !session.is_mfa_elevated() {
+        return Err(AppError::Forbidden(format!(
+            "Permission {} requires an MFA-elevated session; elevate via /api/v1/auth/elevate",
+            permission.as_str()
+        )));
+    }
+    Ok(())
+}
 
 /// Configure all v1 API routes
 pub fn configure_v1_routes(cfg: &mut web::ServiceConfig) {
@@ -28,6 +55,30 @@ pub fn configure_v1_routes(cfg: &mut web::ServiceConfig) {
                 .configure(users::configure_user_routes)
         )
 
+        // Role & permission administration routes
+        .service(
+            web::scope("/roles")
+                .configure(roles::configure_role_routes)
+        )
+
+        // Organization & membership routes
+        .service(
+            web::scope("/organizations")
+                .configure(organizations::configure_organization_routes)
+        )
+
+        // Incident report intake & review routes
+        .service(
+            web::scope("/reports")
+                .configure(reports::configure_report_routes)
+        )
+
+        // Volunteer profile & assignment routes
+        .service(
+            web::scope("/volunteers")
+                .configure(volunteers::configure_volunteer_routes)
+        )
+
         // Disaster management routes
         .service(
             web::scope("/disasters")
@@ -56,5 +107,17 @@ pub fn configure_v1_routes(cfg: &mut web::ServiceConfig) {
         .service(
             web::scope("/emergency")
                 .configure(emergency::configure_emergency_routes)
+        )
+
+        // WebSocket & SSE push routes
+        .service(
+            web::scope("/realtime")
+                .configure(realtime::configure_realtime_routes)
+        )
+
+        // Responder GPS tracking routes
+        .service(
+            web::scope("/tracking")
+                .configure(tracking::configure_tracking_routes)
         );
 }
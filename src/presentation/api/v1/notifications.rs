@@ -4,6 +4,10 @@
 use actix_web::{web, HttpRequest, HttpResponse, Result};
 use serde::{Deserialize, Serialize};
 use crate::infrastructure::AppContainer;
+use crate::infrastructure::security::SecureAuthSession;
+use crate::middleware::auth::AuthMiddleware;
+use crate::presentation::api::v1::{parse_uuid, require_permission};
+use crate::shared::{ApiResponse, AppError, Permission};
 
 #[derive(Debug, Deserialize)]
 pub struct CreateNotificationRequest {
@@ -44,6 +48,12 @@ pub struct MarkAsReadRequest {
     pub notification_ids: Vec<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct DeadLetterQuery {
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
 /// POST /api/v1/notifications
 async fn create_notification(
     req: web::Json<CreateNotificationRequest>,
@@ -237,8 +247,60 @@ async fn get_notification_statistics(
     })))
 }
 
+/// GET /api/v1/notifications/outbox/dead-letters
+async fn list_dead_letters(
+    query: web::Query<DeadLetterQuery>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ManageSystem)?;
+    let limit = query.limit.unwrap_or(50).clamp(1, 500);
+    let messages = container.notification_outbox_repository
+        .find_dead_lettered(limit, query.offset.unwrap_or(0))
+        .await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(messages)))
+}
+
+/// GET /api/v1/notifications/outbox/{message_id}
+async fn get_outbox_message(
+    path: web::Path<String>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ManageSystem)?;
+    let id = parse_uuid(&path, "outbox message id")?;
+    let message = container.notification_outbox_repository.find_by_id(&id).await?
+        .ok_or_else(|| AppError::NotFound(format!("Outbox message {} not found", id)))?;
+    let attempts = container.notification_outbox_repository.find_attempts(&id).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "message": message,
+        "attempts": attempts
+    }))))
+}
+
+/// POST /api/v1/notifications/outbox/{message_id}/requeue
+async fn requeue_outbox_message(
+    path: web::Path<String>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ManageSystem)?;
+    let id = parse_uuid(&path, "outbox message id")?;
+    let mut message = container.notification_outbox_repository.find_by_id(&id).await?
+        .ok_or_else(|| AppError::NotFound(format!("Outbox message {} not found", id)))?;
+
+    message.requeue()?;
+    if !container.notification_outbox_repository.requeue(&message).await? {
+        return Err(AppError::Conflict(format!("Outbox message {} is no longer dead-lettered", id)).into());
+    }
+    Ok(HttpResponse::Ok().json(ApiResponse::success(message)))
+}
+
 pub fn configure_notification_routes(cfg: &mut web::ServiceConfig) {
     cfg
+        .route("/outbox/dead-letters", web::get().to(list_dead_letters).wrap(AuthMiddleware::new()))
+        .route("/outbox/{message_id}", web::get().to(get_outbox_message).wrap(AuthMiddleware::new()))
+        .route("/outbox/{message_id}/requeue", web::post().to(requeue_outbox_message).wrap(AuthMiddleware::new()))
         .route("", web::post().to(create_notification))
         .route("", web::get().to(list_notifications))
         .route("/{notification_id}", web::get().to(get_notification_by_id))
@@ -0,0 +1,380 @@
+/// Organization API endpoints
+/// Handles partner organizations, their members and the emergency resources they own
+
+use actix_web::{web, HttpResponse, Result};
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::entities::emergency_response::{EmergencyResource, ResourceStatus};
+use crate::domain::entities::organization::{Organization, OrganizationMembership, OrganizationRole};
+use crate::domain::value_objects::Email;
+use crate::infrastructure::AppContainer;
+use crate::infrastructure::security::SecureAuthSession;
+use crate::middleware::auth::{AuthMiddleware, OrganizationScope};
+use crate::presentation::api::v1::{parse_uuid, require_permission};
+use crate::shared::{ApiResponse, AppError, AppResult, LocationId, OrganizationId, Permission, ResourceId, UserId};
+
+#[derive(Debug, Deserialize)]
+pub struct CreateOrganizationRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub logo_url: Option<String>,
+    pub website: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub address: Option<String>,
+    pub location_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateOrganizationRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub logo_url: Option<String>,
+    pub website: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub address: Option<String>,
+    pub location_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetMemberRoleRequest {
+    pub role: String, // admin, coordinator, volunteer, member
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateResourceRequest {
+    pub name: String,
+    pub category: String,
+    pub quantity: u32,
+    pub unit: String,
+    pub location_id: Option<String>,
+    pub expiry_date: Option<NaiveDate>,
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateResourceRequest {
+    pub name: Option<String>,
+    pub category: Option<String>,
+    pub quantity: Option<u32>,
+    pub unit: Option<String>,
+    pub location_id: Option<String>,
+    pub expiry_date: Option<NaiveDate>,
+    pub status: Option<String>,
+}
+
+/// An organization together with the caller's role in it
+#[derive(Debug, Serialize)]
+pub struct MyOrganizationView {
+    #[serde(flatten)]
+    pub organization: Organization,
+    pub role: OrganizationRole,
+}
+
+fn required_text(value: String, what: &str) -> AppResult<String> {
+    let value = value.trim().to_string();
+    if value.is_empty() {
+        return Err(AppError::Validation(format!("{} is required", what)));
+    }
+    Ok(value)
+}
+
+fn normalize_email(raw: String) -> AppResult<String> {
+    Ok(Email::new(raw)?.value().to_string())
+}
+
+/// Resolve an optional location reference, making sure it exists
+async fn resolve_location_id(container: &AppContainer, raw_id: Option<&str>) -> AppResult<Option<LocationId>> {
+    let Some(raw_id) = raw_id else { return Ok(None) };
+    let location_id = LocationId(parse_uuid(raw_id, "location id")?);
+    if container.location_repository.find_by_id(&location_id).await?.is_none() {
+        return Err(AppError::NotFound(format!("Location {} not found", location_id)));
+    }
+    Ok(Some(location_id))
+}
+
+async fn load_organization(container: &AppContainer, organization_id: &OrganizationId) -> AppResult<Organization> {
+    container.organization_repository.find_by_id(organization_id).await?
+        .ok_or_else(|| AppError::NotFound(format!("Organization {} not found", organization_id)))
+}
+
+/// Load a resource owned by the scoped organization; other organizations' resources look missing
+async fn load_resource(container: &AppContainer, scope: &OrganizationScope, raw_id: &str) -> AppResult<EmergencyResource> {
+    let resource_id = ResourceId(parse_uuid(raw_id, "resource id")?);
+    container.resource_inventory_repository.find_by_id(&resource_id).await?
+        .filter(|resource| resource.organization_id == Some(scope.organization_id))
+        .ok_or_else(|| AppError::NotFound(format!("Resource {} not found", resource_id)))
+}
+
+/// GET /api/v1/organizations
+async fn list_organizations(container: web::Data<AppContainer>) -> Result<HttpResponse> {
+    let organizations = container.organization_repository.find_all().await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(organizations)))
+}
+
+/// POST /api/v1/organizations
+/// The caller becomes the first admin of the new organization
+async fn create_organization(
+    req: web::Json<CreateOrganizationRequest>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ManageOrganization)?;
+    let req = req.into_inner();
+
+    let mut organization = Organization::new(req.name)?;
+    organization.description = req.description;
+    organization.logo_url = req.logo_url;
+    organization.website = req.website;
+    organization.phone = req.phone;
+    organization.email = req.email.map(normalize_email).transpose()?;
+    organization.address = req.address;
+    organization.location_id = resolve_location_id(&container, req.location_id.as_deref()).await?;
+
+    let organization = container.organization_repository.create(&organization, &session.user_id).await?;
+    tracing::info!("Organization {} created by {}", organization.id, session.user_id);
+    Ok(HttpResponse::Created().json(ApiResponse::success(organization)))
+}
+
+/// GET /api/v1/organizations/mine
+async fn list_my_organizations(
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    let memberships = container.organization_repository.find_memberships_by_user(&session.user_id).await?;
+
+    let mut views = Vec::with_capacity(memberships.len());
+    for membership in memberships {
+        if let Some(organization) = container.organization_repository.find_by_id(&membership.organization_id).await? {
+            views.push(MyOrganizationView { organization, role: membership.role });
+        }
+    }
+    Ok(HttpResponse::Ok().json(ApiResponse::success(views)))
+}
+
+/// GET /api/v1/organizations/{organization_id}
+async fn get_organization(
+    path: web::Path<String>,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    let organization_id = OrganizationId(parse_uuid(&path, "organization id")?);
+    let organization = load_organization(&container, &organization_id).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(organization)))
+}
+
+/// PUT /api/v1/organizations/{organization_id}
+async fn update_organization(
+    req: web::Json<UpdateOrganizationRequest>,
+    scope: OrganizationScope,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    let req = req.into_inner();
+    let mut organization = load_organization(&container, &scope.organization_id).await?;
+
+    if let Some(name) = req.name {
+        organization.name = required_text(name, "Organization name")?;
+    }
+    if let Some(description) = req.description {
+        organization.description = Some(description);
+    }
+    if let Some(logo_url) = req.logo_url {
+        organization.logo_url = Some(logo_url);
+    }
+    if let Some(website) = req.website {
+        organization.website = Some(website);
+    }
+    if let Some(phone) = req.phone {
+        organization.phone = Some(phone);
+    }
+    if let Some(email) = req.email {
+        organization.email = Some(normalize_email(email)?);
+    }
+    if let Some(address) = req.address {
+        organization.address = Some(address);
+    }
+    if req.location_id.is_some() {
+        organization.location_id = resolve_location_id(&container, req.location_id.as_deref()).await?;
+    }
+    organization.updated_at = Utc::now();
+
+    let organization = container.organization_repository.update(&organization).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(organization)))
+}
+
+/// DELETE /api/v1/organizations/{organization_id}
+async fn delete_organization(
+    scope: OrganizationScope,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    if !container.organization_repository.delete(&scope.organization_id).await? {
+        return Err(AppError::NotFound(format!("Organization {} not found", scope.organization_id)).into());
+    }
+    tracing::info!("Organization {} deleted by {}", scope.organization_id, session.user_id);
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// GET /api/v1/organizations/{organization_id}/members
+async fn list_members(
+    scope: OrganizationScope,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    let members = container.organization_repository.find_members(&scope.organization_id).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(members)))
+}
+
+/// PUT /api/v1/organizations/{organization_id}/members/{user_id}
+/// Adds the user or changes their role; coordinators may only hand out volunteer and member roles
+async fn set_member_role(
+    path: web::Path<(String, String)>,
+    req: web::Json<SetMemberRoleRequest>,
+    scope: OrganizationScope,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    let user_id = UserId(parse_uuid(&path.1, "user id")?);
+    let role: OrganizationRole = req.role.parse()?;
+
+    let existing = container.organization_repository.find_membership(&scope.organization_id, &user_id).await?;
+    if !scope.can_assign(role) || existing.as_ref().is_some_and(|member| !scope.can_assign(member.role)) {
+        return Err(AppError::Forbidden("Your organization role cannot assign this role".to_string()).into());
+    }
+
+    let membership = match existing {
+        Some(mut membership) => {
+            membership.role = role;
+            membership.updated_at = Utc::now();
+            membership
+        }
+        None => OrganizationMembership::new(scope.organization_id, user_id, role),
+    };
+    let membership = container.organization_repository.save_membership(&membership).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(membership)))
+}
+
+/// DELETE /api/v1/organizations/{organization_id}/members/{user_id}
+async fn remove_member(
+    path: web::Path<(String, String)>,
+    scope: OrganizationScope,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    let user_id = UserId(parse_uuid(&path.1, "user id")?);
+    let membership = container.organization_repository.find_membership(&scope.organization_id, &user_id).await?
+        .ok_or_else(|| AppError::NotFound(format!("User {} is not a member of this organization", user_id)))?;
+    if !scope.can_assign(membership.role) {
+        return Err(AppError::Forbidden("Your organization role cannot remove this member".to_string()).into());
+    }
+
+    container.organization_repository.remove_member(&scope.organization_id, &user_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// GET /api/v1/organizations/{organization_id}/resources
+async fn list_resources(
+    scope: OrganizationScope,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    let resources = container.resource_inventory_repository.find_by_organization(&scope.organization_id).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(resources)))
+}
+
+/// POST /api/v1/organizations/{organization_id}/resources
+async fn create_resource(
+    req: web::Json<CreateResourceRequest>,
+    scope: OrganizationScope,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    let req = req.into_inner();
+    let now = Utc::now();
+    let resource = EmergencyResource {
+        id: ResourceId::new(),
+        organization_id: Some(scope.organization_id),
+        name: required_text(req.name, "Resource name")?,
+        category: required_text(req.category, "Resource category")?,
+        quantity: req.quantity,
+        unit: required_text(req.unit, "Resource unit")?,
+        location_id: resolve_location_id(&container, req.location_id.as_deref()).await?,
+        expiry_date: req.expiry_date,
+        status: req.status.as_deref().map(str::parse).transpose()?.unwrap_or(ResourceStatus::Available),
+        created_at: now,
+        updated_at: now,
+    };
+
+    let resource = container.resource_inventory_repository.save(&resource).await?;
+    Ok(HttpResponse::Created().json(ApiResponse::success(resource)))
+}
+
+/// PUT /api/v1/organizations/{organization_id}/resources/{resource_id}
+async fn update_resource(
+    path: web::Path<(String, String)>,
+    req: web::Json<UpdateResourceRequest>,
+    scope: OrganizationScope,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    let req = req.into_inner();
+    let mut resource = load_resource(&container, &scope, &path.1).await?;
+
+    if let Some(name) = req.name {
+        resource.name = required_text(name, "Resource name")?;
+    }
+    if let Some(category) = req.category {
+        resource.category = required_text(category, "Resource category")?;
+    }
+    if let Some(quantity) = req.quantity {
+        resource.quantity = quantity;
+    }
+    if let Some(unit) = req.unit {
+        resource.unit = required_text(unit, "Resource unit")?;
+    }
+    if req.location_id.is_some() {
+        resource.location_id = resolve_location_id(&container, req.location_id.as_deref()).await?;
+    }
+    if let Some(expiry_date) = req.expiry_date {
+        resource.expiry_date = Some(expiry_date);
+    }
+    if let Some(status) = req.status.as_deref() {
+        resource.status = status.parse()?;
+    }
+    resource.updated_at = Utc::now();
+
+    let resource = container.resource_inventory_repository.update(&resource).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(resource)))
+}
+
+/// DELETE /api/v1/organizations/{organization_id}/resources/{resource_id}
+async fn delete_resource(
+    path: web::Path<(String, String)>,
+    scope: OrganizationScope,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    let resource = load_resource(&container, &scope, &path.1).await?;
+    container.resource_inventory_repository.delete(&resource.id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub fn configure_organization_routes(cfg: &mut web::ServiceConfig) {
+    // `/mine` is registered before `/{organization_id}` so it is not captured by it
+    cfg
+        .route("", web::get().to(list_organizations))
+        .route("", web::post().to(create_organization).wrap(AuthMiddleware::new()))
+        .route("/mine", web::get().to(list_my_organizations).wrap(AuthMiddleware::new()))
+        .route("/{organization_id}", web::get().to(get_organization))
+        .route("/{organization_id}", web::put().to(update_organization)
+            .wrap(AuthMiddleware::for_organization(Permission::WriteOrgData)))
+        .route("/{organization_id}", web::delete().to(delete_organization)
+            .wrap(AuthMiddleware::for_organization(Permission::ManageOrganization)))
+        .route("/{organization_id}/members", web::get().to(list_members)
+            .wrap(AuthMiddleware::for_organization(Permission::ReadVolunteerData)))
+        .route("/{organization_id}/members/{user_id}", web::put().to(set_member_role)
+            .wrap(AuthMiddleware::for_organization(Permission::ManageVolunteers)))
+        .route("/{organization_id}/members/{user_id}", web::delete().to(remove_member)
+            .wrap(AuthMiddleware::for_organization(Permission::ManageVolunteers)))
+        .route("/{organization_id}/resources", web::get().to(list_resources)
+            .wrap(AuthMiddleware::for_organization(Permission::ReadResourceData)))
+        .route("/{organization_id}/resources", web::post().to(create_resource)
+            .wrap(AuthMiddleware::for_organization(Permission::ManageResources)))
+        .route("/{organization_id}/resources/{resource_id}", web::put().to(update_resource)
+            .wrap(AuthMiddleware::for_organization(Permission::UpdateResourceData)))
+        .route("/{organization_id}/resources/{resource_id}", web::delete().to(delete_resource)
+            .wrap(AuthMiddleware::for_organization(Permission::ManageResources)));
+}
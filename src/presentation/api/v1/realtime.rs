@@ -0,0 +1,404 @@
+/// Realtime API endpoints
+/// Pushes disaster updates and the caller's own notifications over WebSocket or Server-Sent Events.
+/// Browsers cannot set headers on either, so they first trade their bearer token for a short-lived,
+/// single-use ticket and connect with `?ticket=`; other clients may send the bearer token directly.
+
+use std::convert::Infallible;
+use std::time::Duration;
+use actix_codec::{Decoder, Encoder};
+use actix_http::ws::{self, CloseCode, CloseReason, Frame, Message};
+use actix_web::body::{BodyStream, MessageBody};
+use actix_web::http::header::{CACHE_CONTROL, CONTENT_ENCODING};
+use actix_web::web::{Bytes, BytesMut};
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::Utc;
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use crate::infrastructure::realtime::{RealtimeMessage, Subscription, Topic};
+use crate::infrastructure::security::SecureAuthSession;
+use crate::infrastructure::AppContainer;
+use crate::middleware::auth::AuthMiddleware;
+use crate::shared::{ApiResponse, AppError, AppResult};
+
+const TICKET_TTL: Duration = Duration::from_secs(60);
+const TICKET_PREFIX: &str = "realtime_ticket:";
+/// Client frames larger than this close the socket
+const MAX_FRAME_BYTES: usize = 16 * 1024;
+
+#[derive(Debug, Deserialize)]
+pub struct StreamQuery {
+    /// Topics separated by `;`, e.g. `disaster:{id};bbox:106.7,-6.4,107.0,-6.1;notifications`
+    pub topics: Option<String>,
+    pub ticket: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TicketResponse {
+    pub ticket: String,
+    pub expires_in: u64,
+}
+
+/// Messages a WebSocket client sends
+#[derive(Debug, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum ClientFrame {
+    Subscribe { topics: Vec<String> },
+    Unsubscribe { topics: Vec<String> },
+    Ping,
+}
+
+/// Messages sent to a WebSocket client
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerFrame<'a> {
+    Subscribed { topics: Vec<String> },
+    Event(&'a RealtimeMessage),
+    /// The connection fell behind and skipped events; re-fetch over REST to catch up
+    Lagged { missed: u64 },
+    Error { message: String },
+    Pong,
+}
+
+impl ServerFrame<'_> {
+    fn subscribed(subscription: &Subscription) -> Self {
+        Self::Subscribed { topics: subscription.topics().iter().map(Topic::to_string).collect() }
+    }
+}
+
+fn bearer_token(req: &HttpRequest) -> Option<String> {
+    req.headers().get("Authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+}
+
+fn ensure_enabled(container: &AppContainer) -> AppResult<()> {
+    let features = &container.config.features;
+    if features.enable_real_time_notifications || features.enable_real_time_tracking {
+        Ok(())
+    } else {
+        Err(AppError::NotFound("Realtime updates are disabled".to_string()))
+    }
+}
+
+fn parse_topics<S: AsRef<str>>(container: &AppContainer, raw: &[S]) -> AppResult<Vec<Topic>> {
+    let features = &container.config.features;
+    raw.iter()
+        .map(|topic| topic.as_ref().trim())
+        .filter(|topic| !topic.is_empty())
+        .map(|topic| {
+            let topic: Topic = topic.parse()?;
+            let enabled = if topic.is_notifications() {
+                features.enable_real_time_notifications
+            } else {
+                features.enable_real_time_tracking
+            };
+            if !enabled {
+                return Err(AppError::Validation(format!("Realtime updates for '{}' are disabled", topic)));
+            }
+            Ok(topic)
+        })
+        .collect()
+}
+
+/// The access token a stream runs under, re-checked while the stream stays open
+struct StreamSession {
+    container: web::Data<AppContainer>,
+    token: String,
+    user_id: crate::shared::UserId,
+    expires_at: Instant,
+    next_check: Instant,
+    check_interval: Duration,
+}
+
+impl StreamSession {
+    /// Authenticate by bearer header or by redeeming a ticket
+    async fn open(req: &HttpRequest, ticket: Option<&str>, container: web::Data<AppContainer>) -> AppResult<Self> {
+        let token = match (bearer_token(req), ticket) {
+            (Some(token), _) => token,
+            (None, Some(ticket)) => {
+                let key = format!("{}{}", TICKET_PREFIX, ticket.trim());
+                // Redeemed atomically so a ticket opens at most one stream
+                container.cache_service.take_string(&key).await?
+                    .ok_or_else(|| AppError::Unauthorized("Invalid or expired realtime ticket".to_string()))?
+            }
+            (None, None) => return Err(AppError::Unauthorized("A bearer token or realtime ticket is required".to_string())),
+        };
+
+        let session = container.paseto_service.validate_paseto_token(&token).await?;
+        let remaining = (session.expires_at - Utc::now()).to_std().unwrap_or(Duration::ZERO);
+        let check_interval = Duration::from_secs(container.config.realtime.session_check_seconds);
+        let now = Instant::now();
+        Ok(Self {
+            token,
+            user_id: session.user_id,
+            expires_at: now + remaining,
+            next_check: now + check_interval,
+            check_interval,
+            container,
+        })
+    }
+
+    /// Completes once the session expires or is revoked; safe to re-create on every loop iteration
+    async fn lapsed(&mut self) -> &'static str {
+        loop {
+            tokio::time::sleep_until(self.next_check.min(self.expires_at)).await;
+            if Instant::now() >= self.expires_at {
+                return "Session expired";
+            }
+            if self.container.paseto_service.validate_paseto_token(&self.token).await.is_err() {
+                return "Session revoked";
+            }
+            self.next_check = Instant::now() + self.check_interval;
+        }
+    }
+}
+
+fn heartbeat(container: &AppContainer) -> tokio::time::Interval {
+    let period = Duration::from_secs(container.config.realtime.heartbeat_seconds);
+    tokio::time::interval_at(Instant::now() + period, period)
+}
+
+/// POST /api/v1/realtime/tickets
+/// Exchange the bearer token for a single-use ticket to open a stream with
+async fn issue_ticket(
+    req: HttpRequest,
+    _session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    ensure_enabled(&container)?;
+    let token = bearer_token(&req)
+        .ok_or_else(|| AppError::Unauthorized("Valid Bearer token is required".to_string()))?;
+    let ticket = URL_SAFE_NO_PAD.encode(rand::random::<[u8; 32]>());
+    container.cache_service
+        .set_string(&format!("{}{}", TICKET_PREFIX, ticket), token, Some(TICKET_TTL))
+        .await?;
+    Ok(HttpResponse::Created().json(ApiResponse::success(TicketResponse {
+        ticket,
+        expires_in: TICKET_TTL.as_secs(),
+    })))
+}
+
+fn sse_event(event_type: &str, id: Option<String>, data: &impl Serialize) -> Bytes {
+    let data = serde_json::to_string(data).unwrap_or_else(|_| "null".to_string());
+    let id = id.map(|id| format!("id: {}\n", id)).unwrap_or_default();
+    Bytes::from(format!("{}event: {}\ndata: {}\n\n", id, event_type, data))
+}
+
+/// GET /api/v1/realtime/sse?topics=...
+/// Server-Sent Events stream; each event is named after its event type
+async fn stream_events(
+    req: HttpRequest,
+    query: web::Query<StreamQuery>,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    ensure_enabled(&container)?;
+    let query = query.into_inner();
+    let mut session = StreamSession::open(&req, query.ticket.as_deref(), container.clone()).await?;
+    let raw_topics: Vec<&str> = query.topics.as_deref().unwrap_or_default().split(';').collect();
+    let mut subscription = Subscription::new(session.user_id);
+    subscription.add(parse_topics(&container, &raw_topics)?)?;
+    if subscription.topics().is_empty() {
+        return Err(AppError::Validation("Subscribe to at least one topic".to_string()).into());
+    }
+
+    let mut messages = container.realtime_hub.subscribe();
+    let mut ticker = heartbeat(&container);
+    let body = async_stream::stream! {
+        let topics: Vec<String> = subscription.topics().iter().map(Topic::to_string).collect();
+        yield Ok::<_, Infallible>(sse_event("subscribed", None, &topics));
+        loop {
+            tokio::select! {
+                received = messages.recv() => match received {
+                    Ok(message) if subscription.matches(&message) => {
+                        yield Ok(sse_event(&message.event_type, Some(message.event_id.to_string()), &message));
+                    }
+                    Ok(_) => {}
+                    Err(RecvError::Lagged(missed)) => yield Ok(sse_event("lagged", None, &serde_json::json!({ "missed": missed }))),
+                    Err(RecvError::Closed) => break,
+                },
+                _ = ticker.tick() => yield Ok(Bytes::from_static(b": keep-alive\n\n")),
+                reason = session.lapsed() => {
+                    yield Ok(sse_event("closed", None, &serde_json::json!({ "reason": reason })));
+                    break;
+                }
+            }
+        }
+    };
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header((CACHE_CONTROL, "no-cache"))
+        // Keeps the compression middleware and reverse proxies from buffering the stream
+        .insert_header((CONTENT_ENCODING, "identity"))
+        .insert_header(("X-Accel-Buffering", "no"))
+        .streaming(body))
+}
+
+/// Outgoing side of a WebSocket connection
+struct Socket {
+    codec: ws::Codec,
+    frames: mpsc::Sender<Bytes>,
+}
+
+impl Socket {
+    /// Encode and queue a frame; false once the client is gone
+    async fn send(&mut self, message: Message) -> bool {
+        let mut buffer = BytesMut::new();
+        if self.codec.encode(message, &mut buffer).is_err() {
+            return false;
+        }
+        self.frames.send(buffer.freeze()).await.is_ok()
+    }
+
+    async fn send_json(&mut self, frame: &ServerFrame<'_>) -> bool {
+        match serde_json::to_string(frame) {
+            Ok(text) => self.send(Message::Text(text.into())).await,
+            Err(_) => true,
+        }
+    }
+
+    async fn close(&mut self, code: CloseCode, description: &str) {
+        let reason = CloseReason { code, description: Some(description.to_string()) };
+        self.send(Message::Close(Some(reason))).await;
+    }
+}
+
+/// What to do after handling a client frame
+enum Flow {
+    Continue,
+    Close(Option<(CloseCode, &'static str)>),
+}
+
+async fn handle_client_frame(
+    frame: Frame,
+    socket: &mut Socket,
+    subscription: &mut Subscription,
+    container: &AppContainer,
+) -> Flow {
+    let text = match frame {
+        Frame::Text(text) => text,
+        Frame::Ping(payload) => {
+            return if socket.send(Message::Pong(payload)).await { Flow::Continue } else { Flow::Close(None) };
+        }
+        Frame::Pong(_) => return Flow::Continue,
+        Frame::Close(_) => return Flow::Close(Some((CloseCode::Normal, "Closed by client"))),
+        Frame::Binary(_) | Frame::Continuation(_) => {
+            return Flow::Close(Some((CloseCode::Unsupported, "Only single-frame JSON text messages are accepted")));
+        }
+    };
+
+    let reply = match serde_json::from_slice::<ClientFrame>(&text) {
+        Ok(ClientFrame::Subscribe { topics }) => parse_topics(container, &topics)
+            .and_then(|topics| subscription.add(topics))
+            .map(|()| ServerFrame::subscribed(subscription)),
+        Ok(ClientFrame::Unsubscribe { topics }) => parse_topics(container, &topics)
+            .map(|topics| subscription.remove(&topics))
+            .map(|()| ServerFrame::subscribed(subscription)),
+        Ok(ClientFrame::Ping) => Ok(ServerFrame::Pong),
+        Err(e) => Err(AppError::Validation(format!("Invalid message: {}", e))),
+    };
+    let reply = reply.unwrap_or_else(|e| ServerFrame::Error { message: e.to_string() });
+    if socket.send_json(&reply).await { Flow::Continue } else { Flow::Close(None) }
+}
+
+async fn run_socket(
+    mut payload: web::Payload,
+    mut socket: Socket,
+    mut subscription: Subscription,
+    mut messages: broadcast::Receiver<RealtimeMessage>,
+    mut session: StreamSession,
+) {
+    let container = session.container.clone();
+    let mut decoder = ws::Codec::new().max_size(MAX_FRAME_BYTES);
+    let mut buffer = BytesMut::new();
+    let mut ticker = heartbeat(&container);
+    let mut awaiting_pong = false;
+
+    if !socket.send_json(&ServerFrame::subscribed(&subscription)).await {
+        return;
+    }
+    let closing = 'connection: loop {
+        tokio::select! {
+            chunk = payload.next() => {
+                let Some(Ok(chunk)) = chunk else { break None };
+                buffer.extend_from_slice(&chunk);
+                loop {
+                    match decoder.decode(&mut buffer) {
+                        Ok(Some(frame)) => {
+                            awaiting_pong = false;
+                            if let Flow::Close(reason) = handle_client_frame(frame, &mut socket, &mut subscription, &container).await {
+                                break 'connection reason;
+                            }
+                        }
+                        Ok(None) => break,
+                        Err(_) => break 'connection Some((CloseCode::Protocol, "Malformed frame")),
+                    }
+                }
+            }
+            received = messages.recv() => {
+                let sent = match received {
+                    Ok(message) if subscription.matches(&message) => socket.send_json(&ServerFrame::Event(&message)).await,
+                    Ok(_) => true,
+                    Err(RecvError::Lagged(missed)) => socket.send_json(&ServerFrame::Lagged { missed }).await,
+                    Err(RecvError::Closed) => break Some((CloseCode::Restart, "Server shutting down")),
+                };
+                if !sent {
+                    break None;
+                }
+            }
+            _ = ticker.tick() => {
+                // No frame at all since the last ping: the client is gone
+                if awaiting_pong || !socket.send(Message::Ping(Bytes::new())).await {
+                    break None;
+                }
+                awaiting_pong = true;
+            }
+            reason = session.lapsed() => break Some((CloseCode::Policy, reason)),
+        }
+    };
+
+    if let Some((code, description)) = closing {
+        socket.close(code, description).await;
+    }
+}
+
+/// GET /api/v1/realtime/ws?topics=...
+/// WebSocket; clients send `{"action":"subscribe","topics":[...]}`, `unsubscribe` or `ping`
+async fn open_socket(
+    req: HttpRequest,
+    payload: web::Payload,
+    query: web::Query<StreamQuery>,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    ensure_enabled(&container)?;
+    let query = query.into_inner();
+    let session = StreamSession::open(&req, query.ticket.as_deref(), container.clone()).await?;
+    let raw_topics: Vec<&str> = query.topics.as_deref().unwrap_or_default().split(';').collect();
+    let mut subscription = Subscription::new(session.user_id);
+    subscription.add(parse_topics(&container, &raw_topics)?)?;
+
+    let mut response = ws::handshake(req.head())?;
+    let (frames, outgoing) = mpsc::channel::<Bytes>(container.config.realtime.buffer_size);
+    let socket = Socket { codec: ws::Codec::new(), frames };
+    let messages = container.realtime_hub.subscribe();
+    // The request payload is tied to this worker's thread, so the connection runs on it too
+    actix_web::rt::spawn(run_socket(payload, socket, subscription, messages, session));
+
+    let body = futures_util::stream::unfold(outgoing, |mut outgoing| async move {
+        outgoing.recv().await.map(|frame| (Ok::<_, Infallible>(frame), outgoing))
+    });
+    Ok(response.message_body(BodyStream::new(body).boxed())?.into())
+}
+
+pub fn configure_realtime_routes(cfg: &mut web::ServiceConfig) {
+    cfg
+        .route("/tickets", web::post().to(issue_ticket).wrap(AuthMiddleware::new()))
+        .route("/sse", web::get().to(stream_events))
+        .route("/ws", web::get().to(open_socket));
+}
@@ -0,0 +1,433 @@
+/// Incident report API endpoints
+/// Handles report intake (with anonymous submissions and photo/video uploads), review,
+/// threaded comments, status history, linking reports to disasters and the credibility-ranked
+/// triage queue of duplicate-report clusters
+
+use actix_multipart::{Field, Multipart};
+use actix_web::{web, HttpResponse, Result};
+use futures_util::TryStreamExt;
+use serde::Deserialize;
+use crate::application::use_cases::{
+    AssessReportCredibilityRequest, ChangeReportStatusRequest, LinkReportsRequest, MediaUpload, ReportResponse, SubmitReportRequest,
+    UpdateReportRequest, UploadReportMediaRequest, UseCase, ValidatedUseCase, MAX_MEDIA_PER_REPORT,
+};
+use crate::domain::entities::report::{CasualtyEstimate, CommentThread, ReportComment, ReportDetails, ReportSource, ReportStatus};
+use crate::domain::entities::disaster::DisasterType;
+use crate::domain::entities::Report;
+use crate::domain::value_objects::Coordinates;
+use crate::infrastructure::AppContainer;
+use crate::infrastructure::security::SecureAuthSession;
+use crate::middleware::auth::AuthMiddleware;
+use crate::presentation::api::v1::{parse_uuid, require_permission};
+use crate::shared::{ApiResponse, AppError, AppResult, DisasterId, Permission, ReportId};
+
+/// Largest JSON document accepted in the `report` part of a multipart upload
+const MAX_REPORT_JSON_BYTES: usize = 64 * 1024;
+
+/// Report fields, sent as JSON on their own or as the `report` part of a multipart submission
+#[derive(Debug, Deserialize)]
+pub struct ReportDetailsRequest {
+    pub disaster_type: String, // earthquake, flood, fire, etc.
+    pub title: String,
+    pub description: Option<String>,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub address: Option<String>,
+    pub impact_radius_m: Option<f64>,
+    pub estimated_severity: u8, // 1 (minor) to 5 (catastrophic)
+    #[serde(default)]
+    pub casualties: CasualtyEstimate,
+}
+
+impl ReportDetailsRequest {
+    fn into_details(self) -> AppResult<ReportDetails> {
+        let location = Coordinates::new(self.latitude, self.longitude)
+            .map_err(|e| AppError::Validation(e.to_string()))?;
+        Ok(ReportDetails {
+            disaster_type: DisasterType::parse(&self.disaster_type),
+            title: self.title,
+            description: self.description,
+            location,
+            address: self.address,
+            impact_radius_m: self.impact_radius_m,
+            estimated_severity: self.estimated_severity,
+            casualties: self.casualties,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SubmitReportBody {
+    #[serde(flatten)]
+    pub details: ReportDetailsRequest,
+    /// Contact details for anonymous submissions; ignored when signed in
+    pub reporter_name: Option<String>,
+    pub reporter_phone: Option<String>,
+    /// Captions for the uploaded files, in upload order
+    #[serde(default)]
+    pub captions: Vec<Option<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReportListQuery {
+    pub status: Option<String>,
+    /// Only reports submitted by the caller
+    pub mine: Option<bool>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TriageQuery {
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReportStatusRequest {
+    pub status: String, // under_review, verified, rejected, resolved, reported
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CommentRequest {
+    pub content: String,
+    pub parent_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LinkReportsBody {
+    pub report_ids: Vec<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MediaQuery {
+    pub caption: Option<String>,
+    pub primary: Option<bool>,
+}
+
+fn parse_report_id(raw: &str) -> AppResult<ReportId> {
+    parse_uuid(raw, "report id").map(ReportId)
+}
+
+async fn load_report(container: &AppContainer, report_id: &ReportId) -> AppResult<Report> {
+    container.report_repository.find_by_id(report_id).await?
+        .ok_or_else(|| AppError::NotFound(format!("Report {} not found", report_id)))
+}
+
+/// Report with its media and linked disasters; anonymous contact details only for validators
+async fn report_view(container: &AppContainer, session: &SecureAuthSession, report: Report) -> AppResult<ReportResponse> {
+    let media = container.report_repository.find_media(&report.id).await?;
+    let disaster_ids = container.report_repository.find_linked_disasters(&report.id).await?;
+    let view = ReportResponse::new(report, media, disaster_ids);
+    Ok(if session.has_permission(Permission::ValidateReports.as_str()) {
+        view
+    } else {
+        view.without_contact_details()
+    })
+}
+
+/// Read a field into memory, refusing anything larger than `limit` while it streams in
+async fn read_field(field: &mut Field, limit: usize) -> AppResult<Vec<u8>> {
+    let mut bytes = Vec::new();
+    while let Some(chunk) = field.try_next().await
+        .map_err(|e| AppError::Validation(format!("Malformed multipart body: {}", e)))?
+    {
+        if bytes.len() + chunk.len() > limit {
+            return Err(AppError::Validation(format!("Uploaded part exceeds {} bytes", limit)));
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(bytes)
+}
+
+/// Split a multipart body into its `report` JSON part and its files
+async fn read_multipart(mut payload: Multipart, max_upload_bytes: usize) -> AppResult<(Option<Vec<u8>>, Vec<MediaUpload>)> {
+    let mut report_json = None;
+    let mut uploads = Vec::new();
+
+    while let Some(mut field) = payload.try_next().await
+        .map_err(|e| AppError::Validation(format!("Malformed multipart body: {}", e)))?
+    {
+        if field.name() == Some("report") {
+            report_json = Some(read_field(&mut field, MAX_REPORT_JSON_BYTES).await?);
+            continue;
+        }
+        if uploads.len() == MAX_MEDIA_PER_REPORT {
+            return Err(AppError::Validation(format!("A report can carry at most {} files", MAX_MEDIA_PER_REPORT)));
+        }
+        let content_type = field.content_type()
+            .map(|mime| mime.essence_str().to_string())
+            .ok_or_else(|| AppError::Validation("Uploaded files need a Content-Type".to_string()))?;
+        let bytes = read_field(&mut field, max_upload_bytes).await?;
+        uploads.push(MediaUpload { content_type, bytes, caption: None });
+    }
+    Ok((report_json, uploads))
+}
+
+/// POST /api/v1/reports
+/// Multipart body: a `report` part with the JSON fields and any number of photo/video parts.
+/// Works without signing in; the report is then recorded as anonymous.
+async fn submit_report(
+    payload: Multipart,
+    session: Option<SecureAuthSession>,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    if let Some(session) = &session {
+        require_permission(&container.policy_engine, session, Permission::WriteReports)?;
+    }
+    let (report_json, mut media) = read_multipart(payload, container.config.media_storage.max_upload_bytes).await?;
+    let body: SubmitReportBody = serde_json::from_slice(
+        &report_json.ok_or_else(|| AppError::Validation("Multipart field 'report' is required".to_string()))?,
+    ).map_err(|e| AppError::Validation(format!("Invalid report: {}", e)))?;
+
+    let source = match &session {
+        Some(session) => ReportSource::User { user_id: session.user_id },
+        None => ReportSource::anonymous(body.reporter_name, body.reporter_phone)?,
+    };
+    for (upload, caption) in media.iter_mut().zip(body.captions) {
+        upload.caption = caption;
+    }
+
+    let report = container.submit_report_use_case
+        .execute_validated(SubmitReportRequest { source, details: body.details.into_details()?, media })
+        .await?;
+    Ok(HttpResponse::Created().json(ApiResponse::success(report.without_contact_details())))
+}
+
+/// GET /api/v1/reports
+async fn list_reports(
+    query: web::Query<ReportListQuery>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ReadReports)?;
+    let status = query.status.as_deref().map(str::parse::<ReportStatus>).transpose()?;
+    let reporter_id = query.mine.unwrap_or(false).then_some(session.user_id);
+    let limit = query.limit.unwrap_or(50).clamp(1, 200);
+
+    let reports = container.report_repository
+        .find_all(status, reporter_id.as_ref(), limit as i64, query.offset.unwrap_or(0) as i64)
+        .await?;
+    let validator = session.has_permission(Permission::ValidateReports.as_str());
+    let reports: Vec<Report> = reports.into_iter()
+        .map(|mut report| {
+            if !validator {
+                report.source = report.source.redacted();
+            }
+            report
+        })
+        .collect();
+    Ok(HttpResponse::Ok().json(ApiResponse::success(reports)))
+}
+
+/// GET /api/v1/reports/{report_id}
+async fn get_report(
+    path: web::Path<String>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ReadReports)?;
+    let report = load_report(&container, &parse_report_id(&path)?).await?;
+    let view = report_view(&container, &session, report).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(view)))
+}
+
+/// PUT /api/v1/reports/{report_id}
+/// Reporters may revise their own report until review starts
+async fn update_report(
+    path: web::Path<String>,
+    req: web::Json<ReportDetailsRequest>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    let report = container.update_report_use_case
+        .execute(UpdateReportRequest {
+            report_id: parse_report_id(&path)?,
+            actor: session.policy_subject(),
+            details: req.into_inner().into_details()?,
+        })
+        .await?;
+    let view = report_view(&container, &session, report).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(view)))
+}
+
+/// POST /api/v1/reports/{report_id}/media?caption=...&primary=true
+/// Multipart body with a single photo or video part
+async fn upload_media(
+    path: web::Path<String>,
+    query: web::Query<MediaQuery>,
+    payload: Multipart,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    let report_id = parse_report_id(&path)?;
+    let (_, mut uploads) = read_multipart(payload, container.config.media_storage.max_upload_bytes).await?;
+    if uploads.len() != 1 {
+        return Err(AppError::Validation("Upload exactly one file per request".to_string()).into());
+    }
+    let mut upload = uploads.remove(0);
+    upload.caption = query.caption.clone();
+
+    let media = container.upload_report_media_use_case
+        .execute_validated(UploadReportMediaRequest {
+            report_id,
+            actor: session.policy_subject(),
+            upload,
+            is_primary: query.primary.unwrap_or(false),
+        })
+        .await?;
+    Ok(HttpResponse::Created().json(ApiResponse::success(media)))
+}
+
+/// GET /api/v1/reports/{report_id}/history
+async fn get_report_history(
+    path: web::Path<String>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ReadReports)?;
+    let report = load_report(&container, &parse_report_id(&path)?).await?;
+    let history = container.report_repository.find_history(&report.id).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(history)))
+}
+
+/// GET /api/v1/reports/{report_id}/comments
+/// Comments as threads, oldest first
+async fn list_comments(
+    path: web::Path<String>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ReadReports)?;
+    let report = load_report(&container, &parse_report_id(&path)?).await?;
+    let comments = container.report_repository.find_comments(&report.id).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(CommentThread::build(comments))))
+}
+
+/// POST /api/v1/reports/{report_id}/comments
+async fn add_comment(
+    path: web::Path<String>,
+    req: web::Json<CommentRequest>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::WriteReports)?;
+    let report = load_report(&container, &parse_report_id(&path)?).await?;
+    let parent_id = req.parent_id.as_deref().map(|raw| parse_uuid(raw, "parent comment id")).transpose()?;
+
+    let comment = ReportComment::new(report.id, session.user_id, &req.content, parent_id)?;
+    let comment = container.report_repository.add_comment(&comment).await?;
+    Ok(HttpResponse::Created().json(ApiResponse::success(comment)))
+}
+
+/// POST /api/v1/reports/{report_id}/status
+async fn change_report_status(
+    path: web::Path<String>,
+    req: web::Json<ReportStatusRequest>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    let req = req.into_inner();
+    let report = container.change_report_status_use_case
+        .execute_validated(ChangeReportStatusRequest {
+            report_id: parse_report_id(&path)?,
+            new_status: req.status,
+            actor: session.policy_subject(),
+            notes: req.notes,
+        })
+        .await?;
+    let view = report_view(&container, &session, report).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(view)))
+}
+
+/// POST /api/v1/reports/disasters/{disaster_id}
+/// Link reports to the disaster they describe; unreviewed reports are verified on the way
+async fn link_reports(
+    path: web::Path<String>,
+    req: web::Json<LinkReportsBody>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    let req = req.into_inner();
+    let report_ids = req.report_ids.iter()
+        .map(|raw| parse_report_id(raw))
+        .collect::<AppResult<Vec<_>>>()?;
+
+    let results = container.link_reports_to_disaster_use_case
+        .execute_validated(LinkReportsRequest {
+            disaster_id: DisasterId(parse_uuid(&path, "disaster id")?),
+            report_ids,
+            actor: session.policy_subject(),
+            notes: req.notes,
+        })
+        .await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(results)))
+}
+
+/// GET /api/v1/reports/triage
+/// Incident clusters with reports awaiting review, most credible first
+async fn triage_queue(
+    query: web::Query<TriageQuery>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ValidateReports)?;
+    let limit = query.limit.unwrap_or(50).clamp(1, 200);
+    let clusters = container.report_repository
+        .find_triage_queue(limit as i64, query.offset.unwrap_or(0) as i64)
+        .await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(clusters)))
+}
+
+/// GET /api/v1/reports/clusters/{cluster_id}
+/// Every report grouped into one incident cluster, oldest first
+async fn get_cluster(
+    path: web::Path<String>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ValidateReports)?;
+    let cluster_id = parse_uuid(&path, "cluster id")?;
+    let reports = container.report_repository.find_cluster(&cluster_id).await?;
+    if reports.is_empty() {
+        return Err(AppError::NotFound(format!("Report cluster {} not found", cluster_id)).into());
+    }
+    Ok(HttpResponse::Ok().json(ApiResponse::success(reports)))
+}
+
+/// POST /api/v1/reports/{report_id}/credibility
+/// Rescore a report and the pending reports around it
+async fn assess_credibility(
+    path: web::Path<String>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ValidateReports)?;
+    let report = container.assess_report_credibility_use_case
+        .execute(AssessReportCredibilityRequest {
+            report_id: parse_report_id(&path)?,
+            include_neighbours: true,
+        })
+        .await?;
+    let view = report_view(&container, &session, report).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(view)))
+}
+
+pub fn configure_report_routes(cfg: &mut web::ServiceConfig) {
+    cfg
+        .route("", web::post().to(submit_report).wrap(AuthMiddleware::optional()))
+        .route("", web::get().to(list_reports).wrap(AuthMiddleware::new()))
+        .route("/triage", web::get().to(triage_queue).wrap(AuthMiddleware::new()))
+        .route("/clusters/{cluster_id}", web::get().to(get_cluster).wrap(AuthMiddleware::new()))
+        .route("/disasters/{disaster_id}", web::post().to(link_reports).wrap(AuthMiddleware::new()))
+        .route("/{report_id}", web::get().to(get_report).wrap(AuthMiddleware::new()))
+        .route("/{report_id}", web::put().to(update_report).wrap(AuthMiddleware::new()))
+        .route("/{report_id}/media", web::post().to(upload_media).wrap(AuthMiddleware::new()))
+        .route("/{report_id}/history", web::get().to(get_report_history).wrap(AuthMiddleware::new()))
+        .route("/{report_id}/comments", web::get().to(list_comments).wrap(AuthMiddleware::new()))
+        .route("/{report_id}/comments", web::post().to(add_comment).wrap(AuthMiddleware::new()))
+        .route("/{report_id}/status", web::post().to(change_report_status).wrap(AuthMiddleware::new()))
+        .route("/{report_id}/credibility", web::post().to(assess_credibility).wrap(AuthMiddleware::new()));
+}
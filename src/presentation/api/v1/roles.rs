@@ -0,0 +1,210 @@
+/// Role administration API endpoints
+/// Handles custom roles, the permissions they grant and the audit trail of role changes
+
+use actix_web::{web, HttpResponse, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::entities::role::{parse_permissions, Role};
+use crate::infrastructure::AppContainer;
+use crate::infrastructure::security::{RoleChanges, SecureAuthSession};
+use crate::middleware::auth::AuthMiddleware;
+use crate::presentation::api::v1::require_permission;
+use crate::shared::{ApiResponse, AppError, AppResult, Permission, UserId};
+
+#[derive(Debug, Deserialize)]
+pub struct CreateRoleRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub permissions: Vec<String>, // e.g. reports:write, see GET /roles/permissions
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateRoleRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub permissions: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RoleAuditQuery {
+    pub role_id: Option<i32>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+/// A role with its permissions by name
+#[derive(Debug, Serialize)]
+pub struct RoleView {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub permissions: Vec<&'static str>,
+    pub is_system: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Role> for RoleView {
+    fn from(role: Role) -> Self {
+        Self {
+            permissions: role.permission_names(),
+            id: role.id,
+            name: role.name,
+            description: role.description,
+            is_system: role.is_system,
+            created_at: role.created_at,
+            updated_at: role.updated_at,
+        }
+    }
+}
+
+pub(crate) fn parse_role_id(raw: &str) -> AppResult<i32> {
+    raw.trim().parse().map_err(|_| AppError::Validation(format!("Invalid role id: {}", raw)))
+}
+
+/// Callers may only hand out permissions they hold themselves, so role administration cannot escalate
+pub(crate) fn ensure_grantable(session: &SecureAuthSession, permissions: &[Permission]) -> AppResult<()> {
+    let missing: Vec<&str> = permissions.iter()
+        .map(Permission::as_str)
+        .filter(|name| !session.permissions.iter().any(|held| held == name))
+        .collect();
+    if !missing.is_empty() {
+        return Err(AppError::Forbidden(format!("Cannot grant permissions you do not hold: {}", missing.join(", "))));
+    }
+    Ok(())
+}
+
+/// GET /api/v1/roles
+async fn list_roles(
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ManageUsers)?;
+    let roles: Vec<RoleView> = container.role_service.catalog().await?
+        .into_iter()
+        .map(RoleView::from)
+        .collect();
+    Ok(HttpResponse::Ok().json(ApiResponse::success(roles)))
+}
+
+/// GET /api/v1/roles/permissions
+/// Every permission a role can grant
+async fn list_permissions(
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ManageUsers)?;
+    let permissions: Vec<&'static str> = Permission::all().iter().map(Permission::as_str).collect();
+    Ok(HttpResponse::Ok().json(ApiResponse::success(permissions)))
+}
+
+/// POST /api/v1/roles
+async fn create_role(
+    req: web::Json<CreateRoleRequest>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ManageUsers)?;
+    let req = req.into_inner();
+    let permissions = parse_permissions(&req.permissions)?;
+    ensure_grantable(&session, &permissions)?;
+
+    let role = Role::new(req.name, req.description, permissions)?;
+    let role = container.role_service.create_role(role, &session.user_id).await?;
+    Ok(HttpResponse::Created().json(ApiResponse::success(RoleView::from(role))))
+}
+
+/// GET /api/v1/roles/{role_id}
+async fn get_role(
+    path: web::Path<String>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ManageUsers)?;
+    let role = container.role_service.get_role(parse_role_id(&path)?).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(RoleView::from(role))))
+}
+
+/// PUT /api/v1/roles/{role_id}
+/// Built-in roles keep their name but their permissions may be changed
+async fn update_role(
+    path: web::Path<String>,
+    req: web::Json<UpdateRoleRequest>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ManageUsers)?;
+    let role_id = parse_role_id(&path)?;
+    let req = req.into_inner();
+
+    let permissions = req.permissions.as_deref().map(parse_permissions).transpose()?;
+    if let Some(permissions) = &permissions {
+        // Only additions need the caller to hold them; dropping a permission is always allowed
+        let current = container.role_service.get_role(role_id).await?;
+        let added: Vec<Permission> = permissions.iter()
+            .filter(|permission| !current.grants(permission))
+            .cloned()
+            .collect();
+        ensure_grantable(&session, &added)?;
+    }
+
+    let changes_grants = req.name.is_some() || permissions.is_some();
+    let role = container.role_service
+        .update_role(role_id, RoleChanges { name: req.name, description: req.description, permissions }, &session.user_id)
+        .await?;
+    if changes_grants {
+        let holders = container.role_service.holders(role_id).await?;
+        sign_out_holders(&container, &holders).await?;
+    }
+    Ok(HttpResponse::Ok().json(ApiResponse::success(RoleView::from(role))))
+}
+
+/// DELETE /api/v1/roles/{role_id}
+async fn delete_role(
+    path: web::Path<String>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ManageUsers)?;
+    let role_id = parse_role_id(&path)?;
+    let holders = container.role_service.holders(role_id).await?;
+    container.role_service.delete_role(role_id, &session.user_id).await?;
+    sign_out_holders(&container, &holders).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Revokes the sessions of users whose role grants changed, so their next login picks up the new permissions
+async fn sign_out_holders(container: &AppContainer, holders: &[UserId]) -> Result<()> {
+    for user_id in holders {
+        container.paseto_service.revoke_user_sessions(user_id, None).await?;
+    }
+    if !holders.is_empty() {
+        tracing::info!("Revoked the sessions of {} holder(s) after a role change", holders.len());
+    }
+    Ok(())
+}
+
+/// GET /api/v1/roles/audit
+async fn get_role_audit(
+    query: web::Query<RoleAuditQuery>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ManageUsers)?;
+    let limit = query.limit.unwrap_or(50).clamp(1, 500);
+    let entries = container.role_service
+        .audit_trail(query.role_id, limit as i64, query.offset.unwrap_or(0) as i64)
+        .await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(entries)))
+}
+
+pub fn configure_role_routes(cfg: &mut web::ServiceConfig) {
+    cfg
+        .route("", web::get().to(list_roles).wrap(AuthMiddleware::new()))
+        .route("", web::post().to(create_role).wrap(AuthMiddleware::new()))
+        .route("/permissions", web::get().to(list_permissions).wrap(AuthMiddleware::new()))
+        .route("/audit", web::get().to(get_role_audit).wrap(AuthMiddleware::new()))
+        .route("/{role_id}", web::get().to(get_role).wrap(AuthMiddleware::new()))
+        .route("/{role_id}", web::put().to(update_role).wrap(AuthMiddleware::new()))
+        .route("/{role_id}", web::delete().to(delete_role).wrap(AuthMiddleware::new()));
+}
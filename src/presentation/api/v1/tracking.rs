@@ -0,0 +1,144 @@
+/// Location tracking API endpoints
+/// Batched GPS uploads from responders' devices, the latest positions of everyone working a
+/// disaster and breadcrumb trails per assignment or response team
+
+use actix_web::{web, HttpResponse, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use crate::application::use_cases::{RecordLocationFixesRequest, ValidatedUseCase};
+use crate::domain::entities::tracking::LocationSample;
+use crate::infrastructure::AppContainer;
+use crate::infrastructure::security::SecureAuthSession;
+use crate::middleware::auth::AuthMiddleware;
+use crate::presentation::api::v1::{parse_uuid, require_permission};
+use crate::shared::{ApiResponse, AppError, AppResult, DisasterId, Permission};
+
+const DEFAULT_POSITION_WINDOW_MINUTES: i64 = 60;
+const MAX_POSITION_WINDOW_MINUTES: i64 = 7 * 24 * 60;
+const DEFAULT_TRAIL_LIMIT: i64 = 500;
+const MAX_TRAIL_LIMIT: i64 = 5000;
+
+#[derive(Debug, Deserialize)]
+pub struct LocationBatchBody {
+    /// Assignment the fixes belong to; the caller's assignment under way when omitted
+    pub assignment_id: Option<String>,
+    /// Response team the caller travels with
+    pub team_id: Option<String>,
+    pub fixes: Vec<LocationSample>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PositionsQuery {
+    /// Ignore anyone whose last fix is older than this
+    pub since_minutes: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TrailQuery {
+    /// ISO 8601; the whole trail when omitted
+    pub since: Option<String>,
+    pub limit: Option<i64>,
+}
+
+impl TrailQuery {
+    fn since(&self) -> AppResult<Option<DateTime<Utc>>> {
+        self.since.as_deref()
+            .map(|raw| {
+                DateTime::parse_from_rfc3339(raw)
+                    .map(|t| t.with_timezone(&Utc))
+                    .map_err(|_| AppError::Validation("since must be an ISO 8601 datetime".to_string()))
+            })
+            .transpose()
+    }
+
+    fn limit(&self) -> i64 {
+        self.limit.unwrap_or(DEFAULT_TRAIL_LIMIT).clamp(1, MAX_TRAIL_LIMIT)
+    }
+}
+
+/// POST /api/v1/tracking/fixes
+/// Upload a batch of fixes; safe to repeat after a failed or unconfirmed upload
+async fn record_fixes(
+    req: web::Json<LocationBatchBody>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    let req = req.into_inner();
+    let result = container.record_location_fixes_use_case
+        .execute_validated(RecordLocationFixesRequest {
+            actor: session.policy_subject(),
+            assignment_id: req.assignment_id.as_deref().map(|id| parse_uuid(id, "assignment id")).transpose()?,
+            team_id: req.team_id.as_deref().map(|id| parse_uuid(id, "team id")).transpose()?,
+            samples: req.fixes,
+        })
+        .await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(result)))
+}
+
+/// GET /api/v1/tracking/disasters/{disaster_id}/positions
+/// Latest position of every volunteer and team member working the disaster
+async fn disaster_positions(
+    path: web::Path<String>,
+    query: web::Query<PositionsQuery>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ReadVolunteerData)?;
+    let disaster_id = DisasterId(parse_uuid(&path, "disaster id")?);
+    let window = query.since_minutes.unwrap_or(DEFAULT_POSITION_WINDOW_MINUTES).clamp(1, MAX_POSITION_WINDOW_MINUTES);
+    let positions = container.location_tracking_repository
+        .latest_for_disaster(&disaster_id, Utc::now() - Duration::minutes(window))
+        .await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(positions)))
+}
+
+/// GET /api/v1/tracking/assignments/{assignment_id}/trail
+/// Breadcrumb trail of an assignment, for its volunteer or coordinators
+async fn assignment_trail(
+    path: web::Path<String>,
+    query: web::Query<TrailQuery>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    let assignment_id = parse_uuid(&path, "assignment id")?;
+    let assignment = container.volunteer_repository.find_assignment(&assignment_id).await?
+        .ok_or_else(|| AppError::NotFound(format!("Assignment {} not found", assignment_id)))?;
+    let is_own = container.volunteer_repository.find_by_id(&assignment.volunteer_id).await?
+        .is_some_and(|volunteer| volunteer.user_id == session.user_id);
+    if !is_own {
+        require_permission(&container.policy_engine, &session, Permission::ReadVolunteerData)?;
+    }
+
+    let trail = container.location_tracking_repository
+        .trail_for_assignment(&assignment.id, query.since()?, query.limit())
+        .await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(trail)))
+}
+
+/// GET /api/v1/tracking/teams/{team_id}/trail
+async fn team_trail(
+    path: web::Path<String>,
+    query: web::Query<TrailQuery>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ManageEmergencyResponse)
+        .or_else(|_| require_permission(&container.policy_engine, &session, Permission::UpdateEmergencyStatus))?;
+    let team_id = parse_uuid(&path, "team id")?;
+    if container.response_team_repository.find_by_id(&team_id).await?.is_none() {
+        return Err(AppError::NotFound(format!("Response team {} not found", team_id)).into());
+    }
+
+    let trail = container.location_tracking_repository
+        .trail_for_team(&team_id, query.since()?, query.limit())
+        .await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(trail)))
+}
+
+pub fn configure_tracking_routes(cfg: &mut web::ServiceConfig) {
+    cfg
+        .route("/fixes", web::post().to(record_fixes).wrap(AuthMiddleware::new()))
+        .route("/disasters/{disaster_id}/positions", web::get().to(disaster_positions).wrap(AuthMiddleware::new()))
+        .route("/assignments/{assignment_id}/trail", web::get().to(assignment_trail).wrap(AuthMiddleware::new()))
+        .route("/teams/{team_id}/trail", web::get().to(team_trail).wrap(AuthMiddleware::new()));
+}
@@ -4,6 +4,11 @@
 use actix_web::{web, HttpRequest, HttpResponse, Result};
 use serde::{Deserialize, Serialize};
 use crate::infrastructure::AppContainer;
+use crate::infrastructure::security::SecureAuthSession;
+use crate::middleware::auth::AuthMiddleware;
+use crate::presentation::api::v1::{parse_uuid, require_permission};
+use crate::presentation::api::v1::roles::{ensure_grantable, parse_role_id, RoleView};
+use crate::shared::{ApiResponse, AppError, Permission, UserId};
 
 #[derive(Debug, Deserialize)]
 pub struct UpdateUserProfileRequest {
@@ -18,6 +23,18 @@ pub struct UpdateUserRoleRequest {
     pub role: String, // admin, responder, citizen
 }
 
+/// Provinces a coordinator is responsible for, as they appear in `locations.province`
+#[derive(Debug, Deserialize)]
+pub struct AssignProvincesRequest {
+    pub provinces: Vec<String>,
+}
+
+/// Role to add to a user, by id
+#[derive(Debug, Deserialize)]
+pub struct AssignRoleRequest {
+    pub role_id: i32,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct UserSearchQuery {
     pub q: Option<String>,
@@ -179,8 +196,145 @@ async fn get_users_statistics(
     })))
 }
 
+/// GET /api/v1/users/{user_id}/sessions
+async fn list_user_sessions(
+    path: web::Path<String>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ManageUsers)?;
+    let user_id = UserId(parse_uuid(&path, "user id")?);
+    let sessions = container.paseto_service.list_user_sessions(&user_id).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(sessions)))
+}
+
+/// POST /api/v1/users/{user_id}/sessions/revoke
+/// Force-logout: signs the user out on every device
+async fn force_logout_user(
+    path: web::Path<String>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ManageUsers)?;
+    let user_id = UserId(parse_uuid(&path, "user id")?);
+    if container.user_repository.find_by_id(&user_id).await?.is_none() {
+        return Err(AppError::NotFound(format!("User {} not found", user_id)).into());
+    }
+
+    container.paseto_service.revoke_user_sessions(&user_id, None).await?;
+    tracing::info!("All sessions of user {} revoked by {}", user_id, session.user_id);
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "message": "User has been signed out from all devices",
+        "user_id": user_id
+    }))))
+}
+
+/// GET /api/v1/users/{user_id}/provinces
+async fn get_user_provinces(
+    path: web::Path<String>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ManageUsers)?;
+    let user_id = UserId(parse_uuid(&path, "user id")?);
+    let provinces = container.province_assignment_repository.find_by_user(&user_id).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(provinces)))
+}
+
+/// PUT /api/v1/users/{user_id}/provinces
+/// Replaces the user's province assignments, which scope the disaster policies
+async fn assign_user_provinces(
+    path: web::Path<String>,
+    req: web::Json<AssignProvincesRequest>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ManageUsers)?;
+    let user_id = UserId(parse_uuid(&path, "user id")?);
+    if container.user_repository.find_by_id(&user_id).await?.is_none() {
+        return Err(AppError::NotFound(format!("User {} not found", user_id)).into());
+    }
+
+    let mut provinces: Vec<String> = req.into_inner().provinces.into_iter()
+        .map(|province| province.trim().to_string())
+        .filter(|province| !province.is_empty())
+        .collect();
+    provinces.sort();
+    provinces.dedup();
+
+    container.province_assignment_repository.replace(&user_id, &provinces, &session.user_id).await?;
+    tracing::info!("Provinces of user {} set to {:?} by {}", user_id, provinces, session.user_id);
+    Ok(HttpResponse::Ok().json(ApiResponse::success(provinces)))
+}
+
+/// GET /api/v1/users/{user_id}/roles
+async fn get_user_roles(
+    path: web::Path<String>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ManageUsers)?;
+    let user_id = UserId(parse_uuid(&path, "user id")?);
+    let roles: Vec<RoleView> = container.role_service.user_roles(&user_id).await?
+        .into_iter()
+        .map(RoleView::from)
+        .collect();
+    Ok(HttpResponse::Ok().json(ApiResponse::success(roles)))
+}
+
+/// POST /api/v1/users/{user_id}/roles
+/// The user's sessions are revoked so their next login carries the new permissions
+async fn assign_user_role(
+    path: web::Path<String>,
+    req: web::Json<AssignRoleRequest>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ManageUsers)?;
+    let user_id = UserId(parse_uuid(&path, "user id")?);
+    let role = container.role_service.get_role(req.role_id).await?;
+    ensure_grantable(&session, &role.permissions)?;
+
+    let assigned = container.role_service.assign_role(&user_id, role.id, &session.user_id).await?;
+    if assigned {
+        container.paseto_service.revoke_user_sessions(&user_id, None).await?;
+        tracing::info!("Role '{}' assigned to user {} by {}", role.name, user_id, session.user_id);
+    }
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "user_id": user_id,
+        "role": RoleView::from(role),
+        "assigned": assigned
+    }))))
+}
+
+/// DELETE /api/v1/users/{user_id}/roles/{role_id}
+async fn revoke_user_role(
+    path: web::Path<(String, String)>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ManageUsers)?;
+    let (raw_user_id, raw_role_id) = path.into_inner();
+    let user_id = UserId(parse_uuid(&raw_user_id, "user id")?);
+    let role_id = parse_role_id(&raw_role_id)?;
+
+    if !container.role_service.revoke_role(&user_id, role_id, &session.user_id).await? {
+        return Err(AppError::NotFound(format!("User {} does not hold role {}", user_id, role_id)).into());
+    }
+    container.paseto_service.revoke_user_sessions(&user_id, None).await?;
+    tracing::info!("Role {} revoked from user {} by {}", role_id, user_id, session.user_id);
+    Ok(HttpResponse::NoContent().finish())
+}
+
 pub fn configure_user_routes(cfg: &mut web::ServiceConfig) {
     cfg
+        .route("/{user_id}/sessions", web::get().to(list_user_sessions).wrap(AuthMiddleware::new()))
+        .route("/{user_id}/sessions/revoke", web::post().to(force_logout_user).wrap(AuthMiddleware::new()))
+        .route("/{user_id}/provinces", web::get().to(get_user_provinces).wrap(AuthMiddleware::new()))
+        .route("/{user_id}/provinces", web::put().to(assign_user_provinces).wrap(AuthMiddleware::new()))
+        .route("/{user_id}/roles", web::get().to(get_user_roles).wrap(AuthMiddleware::new()))
+        .route("/{user_id}/roles", web::post().to(assign_user_role).wrap(AuthMiddleware::new()))
+        .route("/{user_id}/roles/{role_id}", web::delete().to(revoke_user_role).wrap(AuthMiddleware::new()))
         .route("", web::get().to(list_users))
         .route("/{user_id}", web::get().to(get_user_by_id))
         .route("/{user_id}", web::put().to(update_user_profile))
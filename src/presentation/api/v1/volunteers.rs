@@ -0,0 +1,286 @@
+/// Volunteer API endpoints
+/// Handles volunteer sign-up, skills, certifications and availability, matching volunteers to a
+/// disaster's needs, and assignments from hand-out through acceptance, arrival and completion
+
+use actix_web::{web, HttpResponse, Result};
+use serde::{Deserialize, Serialize};
+use crate::application::use_cases::{
+    AssignVolunteerRequest, ChangeAssignmentStatusRequest, MatchVolunteersRequest, RegisterVolunteerRequest,
+    UpdateVolunteerRequest, UseCase, ValidatedUseCase, VolunteerAvailability,
+};
+use crate::domain::entities::volunteer::{Volunteer, VolunteerAssignment, VolunteerProfile};
+use crate::infrastructure::AppContainer;
+use crate::infrastructure::security::SecureAuthSession;
+use crate::middleware::auth::AuthMiddleware;
+use crate::presentation::api::v1::{parse_uuid, require_permission};
+use crate::shared::{ApiResponse, AppError, AppResult, DisasterId, Permission, ReportId, VolunteerId};
+
+#[derive(Debug, Deserialize)]
+pub struct VolunteerProfileRequest {
+    #[serde(default)]
+    pub skills: Vec<String>,
+    #[serde(default)]
+    pub certifications: Vec<String>,
+    pub experience_years: Option<u32>,
+    pub specialization: Option<String>,
+}
+
+impl From<VolunteerProfileRequest> for VolunteerProfile {
+    fn from(req: VolunteerProfileRequest) -> Self {
+        Self {
+            skills: req.skills,
+            certifications: req.certifications,
+            experience_years: req.experience_years,
+            specialization: req.specialization,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AvailabilityRequest {
+    pub available: bool,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VolunteerListQuery {
+    pub available: Option<bool>,
+    /// Only volunteers listing this skill
+    pub skill: Option<String>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AssignmentListQuery {
+    /// Only assignments still holding the volunteer
+    pub active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AssignVolunteerBody {
+    pub report_id: String,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MatchVolunteersBody {
+    pub disaster_id: String,
+    /// Only match against these recorded resource needs; all of them when omitted
+    #[serde(default)]
+    pub resource_types: Vec<String>,
+    pub limit: Option<usize>,
+    /// Assign the matches straight away instead of only proposing them
+    #[serde(default)]
+    pub auto_assign: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AssignmentStatusRequest {
+    pub status: String, // accepted, declined, arrived, completed, cancelled
+    pub notes: Option<String>,
+}
+
+/// A volunteer together with the assignments that still hold them
+#[derive(Debug, Serialize)]
+pub struct VolunteerView {
+    #[serde(flatten)]
+    pub volunteer: Volunteer,
+    pub active_assignments: Vec<VolunteerAssignment>,
+}
+
+fn parse_volunteer_id(raw: &str) -> AppResult<VolunteerId> {
+    parse_uuid(raw, "volunteer id").map(VolunteerId)
+}
+
+async fn volunteer_view(container: &AppContainer, volunteer: Volunteer) -> AppResult<VolunteerView> {
+    let active_assignments = container.volunteer_repository.find_assignments(&volunteer.id, true).await?;
+    Ok(VolunteerView { volunteer, active_assignments })
+}
+
+async fn load_own_volunteer(container: &AppContainer, session: &SecureAuthSession) -> AppResult<Volunteer> {
+    container.volunteer_repository.find_by_user(&session.user_id).await?
+        .ok_or_else(|| AppError::NotFound("You are not registered as a volunteer".to_string()))
+}
+
+/// POST /api/v1/volunteers/me
+/// Register the caller as a volunteer
+async fn register_volunteer(
+    req: web::Json<VolunteerProfileRequest>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::WriteVolunteerResponse)?;
+    let volunteer = container.register_volunteer_use_case
+        .execute(RegisterVolunteerRequest {
+            user_id: session.user_id,
+            profile: req.into_inner().into(),
+        })
+        .await?;
+    Ok(HttpResponse::Created().json(ApiResponse::success(volunteer)))
+}
+
+/// GET /api/v1/volunteers/me
+async fn get_own_volunteer(
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::WriteVolunteerResponse)?;
+    let volunteer = load_own_volunteer(&container, &session).await?;
+    let view = volunteer_view(&container, volunteer).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(view)))
+}
+
+/// PUT /api/v1/volunteers/me
+/// Replace the caller's skills, certifications, experience and specialization
+async fn update_own_profile(
+    req: web::Json<VolunteerProfileRequest>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::WriteVolunteerResponse)?;
+    let volunteer = container.update_volunteer_use_case
+        .execute_validated(UpdateVolunteerRequest {
+            user_id: session.user_id,
+            profile: Some(req.into_inner().into()),
+            availability: None,
+        })
+        .await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(volunteer)))
+}
+
+/// PUT /api/v1/volunteers/me/availability
+async fn set_own_availability(
+    req: web::Json<AvailabilityRequest>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::WriteVolunteerResponse)?;
+    let req = req.into_inner();
+    let volunteer = container.update_volunteer_use_case
+        .execute_validated(UpdateVolunteerRequest {
+            user_id: session.user_id,
+            profile: None,
+            availability: Some(VolunteerAvailability { is_available: req.available, notes: req.notes }),
+        })
+        .await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(volunteer)))
+}
+
+/// GET /api/v1/volunteers/me/assignments
+async fn list_own_assignments(
+    query: web::Query<AssignmentListQuery>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::WriteVolunteerResponse)?;
+    let volunteer = load_own_volunteer(&container, &session).await?;
+    let assignments = container.volunteer_repository
+        .find_assignments(&volunteer.id, query.active.unwrap_or(false))
+        .await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(assignments)))
+}
+
+/// POST /api/v1/volunteers/assignments/{assignment_id}/status
+/// Volunteers accept, decline, arrive and complete; coordinators may also cancel
+async fn change_assignment_status(
+    path: web::Path<String>,
+    req: web::Json<AssignmentStatusRequest>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    let req = req.into_inner();
+    let assignment = container.change_assignment_status_use_case
+        .execute_validated(ChangeAssignmentStatusRequest {
+            assignment_id: parse_uuid(&path, "assignment id")?,
+            new_status: req.status,
+            actor: session.policy_subject(),
+            notes: req.notes,
+        })
+        .await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(assignment)))
+}
+
+/// POST /api/v1/volunteers/matches
+/// Rank available volunteers for a disaster and optionally assign the best of them
+async fn match_volunteers(
+    req: web::Json<MatchVolunteersBody>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    let req = req.into_inner();
+    let result = container.match_volunteers_use_case
+        .execute_validated(MatchVolunteersRequest {
+            disaster_id: DisasterId(parse_uuid(&req.disaster_id, "disaster id")?),
+            resource_types: req.resource_types,
+            limit: req.limit.unwrap_or(container.config.volunteer_matching.auto_assign_count.max(1)),
+            auto_assign: req.auto_assign,
+            actor: session.policy_subject(),
+        })
+        .await?;
+    let response = if result.assignments.is_empty() { HttpResponse::Ok() } else { HttpResponse::Created() }
+        .json(ApiResponse::success(result));
+    Ok(response)
+}
+
+/// GET /api/v1/volunteers
+async fn list_volunteers(
+    query: web::Query<VolunteerListQuery>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ReadVolunteerData)?;
+    let limit = query.limit.unwrap_or(50).clamp(1, 200);
+    let volunteers = container.volunteer_repository
+        .find_all(query.available, query.skill.as_deref(), limit as i64, query.offset.unwrap_or(0) as i64)
+        .await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(volunteers)))
+}
+
+/// GET /api/v1/volunteers/{volunteer_id}
+async fn get_volunteer(
+    path: web::Path<String>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    require_permission(&container.policy_engine, &session, Permission::ReadVolunteerData)?;
+    let volunteer_id = parse_volunteer_id(&path)?;
+    let volunteer = container.volunteer_repository.find_by_id(&volunteer_id).await?
+        .ok_or_else(|| AppError::NotFound(format!("Volunteer {} not found", volunteer_id)))?;
+    let view = volunteer_view(&container, volunteer).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(view)))
+}
+
+/// POST /api/v1/volunteers/{volunteer_id}/assignments
+/// Assign a volunteer to a report; they are notified and then accept or decline
+async fn assign_volunteer(
+    path: web::Path<String>,
+    req: web::Json<AssignVolunteerBody>,
+    session: SecureAuthSession,
+    container: web::Data<AppContainer>,
+) -> Result<HttpResponse> {
+    let req = req.into_inner();
+    let assignment = container.assign_volunteer_use_case
+        .execute(AssignVolunteerRequest {
+            volunteer_id: parse_volunteer_id(&path)?,
+            report_id: ReportId(parse_uuid(&req.report_id, "report id")?),
+            actor: session.policy_subject(),
+            notes: req.notes,
+        })
+        .await?;
+    Ok(HttpResponse::Created().json(ApiResponse::success(assignment)))
+}
+
+pub fn configure_volunteer_routes(cfg: &mut web::ServiceConfig) {
+    cfg
+        .route("", web::get().to(list_volunteers).wrap(AuthMiddleware::new()))
+        .route("/me", web::post().to(register_volunteer).wrap(AuthMiddleware::new()))
+        .route("/me", web::get().to(get_own_volunteer).wrap(AuthMiddleware::new()))
+        .route("/me", web::put().to(update_own_profile).wrap(AuthMiddleware::new()))
+        .route("/me/availability", web::put().to(set_own_availability).wrap(AuthMiddleware::new()))
+        .route("/me/assignments", web::get().to(list_own_assignments).wrap(AuthMiddleware::new()))
+        .route("/matches", web::post().to(match_volunteers).wrap(AuthMiddleware::new()))
+        .route("/assignments/{assignment_id}/status", web::post().to(change_assignment_status).wrap(AuthMiddleware::new()))
+        .route("/{volunteer_id}", web::get().to(get_volunteer).wrap(AuthMiddleware::new()))
+        .route("/{volunteer_id}/assignments", web::post().to(assign_volunteer).wrap(AuthMiddleware::new()));
+}
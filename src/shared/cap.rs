@@ -0,0 +1,562 @@
+/// Common Alerting Protocol (CAP) 1.2
+/// Serializes outgoing alerts and parses warning feeds published by agencies such as BMKG and BNPB
+
+use chrono::{DateTime, Utc};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+
+use crate::domain::entities::disaster::{DisasterSeverity, DisasterType};
+use crate::domain::value_objects::{Coordinates, GeoArea};
+use crate::shared::{AppError, AppResult};
+
+pub const CAP_NAMESPACE: &str = "urn:oasis:names:tc:emergency:cap:1.2";
+pub const CAP_CONTENT_TYPE: &str = "application/cap+xml";
+/// Sender identifier used on alerts published by this system
+pub const CAP_SENDER: &str = "alerts@terrasiaga.id";
+
+macro_rules! cap_enum {
+    ($name:ident { $($variant:ident => $text:literal),+ $(,)? }) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+        pub enum $name {
+            $($variant),+
+        }
+
+        impl $name {
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $text),+
+                }
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = AppError;
+
+            fn from_str(s: &str) -> AppResult<Self> {
+                match s.trim() {
+                    $($text => Ok(Self::$variant),)+
+                    other => Err(AppError::Validation(format!("Invalid CAP {} '{}'", stringify!($name), other))),
+                }
+            }
+        }
+    };
+}
+
+cap_enum!(CapStatus { Actual => "Actual", Exercise => "Exercise", System => "System", Test => "Test", Draft => "Draft" });
+cap_enum!(CapMsgType { Alert => "Alert", Update => "Update", Cancel => "Cancel", Ack => "Ack", Error => "Error" });
+cap_enum!(CapScope { Public => "Public", Restricted => "Restricted", Private => "Private" });
+cap_enum!(CapUrgency { Immediate => "Immediate", Expected => "Expected", Future => "Future", Past => "Past", Unknown => "Unknown" });
+cap_enum!(CapSeverity { Extreme => "Extreme", Severe => "Severe", Moderate => "Moderate", Minor => "Minor", Unknown => "Unknown" });
+cap_enum!(CapCertainty { Observed => "Observed", Likely => "Likely", Possible => "Possible", Unlikely => "Unlikely", Unknown => "Unknown" });
+
+impl From<&DisasterSeverity> for CapSeverity {
+    fn from(severity: &DisasterSeverity) -> Self {
+        match severity {
+            DisasterSeverity::Minor => CapSeverity::Minor,
+            DisasterSeverity::Moderate => CapSeverity::Moderate,
+            DisasterSeverity::Major | DisasterSeverity::Severe => CapSeverity::Severe,
+            DisasterSeverity::Critical | DisasterSeverity::Catastrophic => CapSeverity::Extreme,
+        }
+    }
+}
+
+impl CapSeverity {
+    pub fn to_disaster_severity(self) -> DisasterSeverity {
+        match self {
+            CapSeverity::Extreme => DisasterSeverity::Critical,
+            CapSeverity::Severe => DisasterSeverity::Severe,
+            CapSeverity::Moderate | CapSeverity::Unknown => DisasterSeverity::Moderate,
+            CapSeverity::Minor => DisasterSeverity::Minor,
+        }
+    }
+}
+
+/// CAP event category for a disaster type
+pub fn category_for(disaster_type: &DisasterType) -> &'static str {
+    match disaster_type {
+        DisasterType::Earthquake | DisasterType::Tsunami | DisasterType::Landslide
+        | DisasterType::VolcanicEruption => "Geo",
+        DisasterType::Flood | DisasterType::Storm | DisasterType::Drought => "Met",
+        DisasterType::Fire => "Fire",
+        DisasterType::Epidemic => "Health",
+        DisasterType::TechnologicalDisaster => "CBRNE",
+        DisasterType::Other(_) => "Other",
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CapCircle {
+    pub center: Coordinates,
+    pub radius_km: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CapArea {
+    pub area_desc: String,
+    /// Closed rings; CAP has no notion of holes
+    pub polygons: Vec<Vec<Coordinates>>,
+    pub circles: Vec<CapCircle>,
+}
+
+impl CapArea {
+    pub fn circle(area_desc: String, center: Coordinates, radius_km: f64) -> Self {
+        Self { area_desc, polygons: Vec::new(), circles: vec![CapCircle { center, radius_km }] }
+    }
+
+    /// Exterior rings of the area; holes are dropped, so the CAP area may be slightly larger
+    pub fn from_geo_area(area_desc: String, area: &GeoArea) -> Self {
+        let polygons = area.polygons.iter()
+            .map(|polygon| {
+                let mut ring = polygon.exterior.clone();
+                if ring.first() != ring.last() {
+                    ring.push(ring[0].clone());
+                }
+                ring
+            })
+            .collect();
+        Self { area_desc, polygons, circles: Vec::new() }
+    }
+
+    /// Representative point: first circle centre, else the vertex average of the first polygon
+    pub fn centroid(&self) -> Option<Coordinates> {
+        if let Some(circle) = self.circles.first() {
+            return Some(circle.center.clone());
+        }
+        let ring = self.polygons.first()?;
+        let vertices = if ring.len() > 1 && ring.first() == ring.last() { &ring[..ring.len() - 1] } else { &ring[..] };
+        if vertices.is_empty() {
+            return None;
+        }
+        let n = vertices.len() as f64;
+        let latitude = vertices.iter().map(|c| c.latitude).sum::<f64>() / n;
+        let longitude = vertices.iter().map(|c| c.longitude).sum::<f64>() / n;
+        Coordinates::new(latitude, longitude).ok()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CapInfo {
+    pub language: String,
+    pub categories: Vec<String>,
+    pub event: String,
+    pub response_types: Vec<String>,
+    pub urgency: CapUrgency,
+    pub severity: CapSeverity,
+    pub certainty: CapCertainty,
+    pub effective: Option<DateTime<Utc>>,
+    pub onset: Option<DateTime<Utc>>,
+    pub expires: Option<DateTime<Utc>>,
+    pub sender_name: Option<String>,
+    pub headline: Option<String>,
+    pub description: Option<String>,
+    pub instruction: Option<String>,
+    pub web: Option<String>,
+    pub areas: Vec<CapArea>,
+}
+
+impl CapInfo {
+    /// Disaster type key understood by the disaster use cases, from the event name or category
+    pub fn disaster_type(&self) -> String {
+        let event = self.event.to_lowercase();
+        let keywords: [(&[&str], &str); 9] = [
+            (&["tsunami"], "tsunami"),
+            (&["earthquake", "gempa"], "earthquake"),
+            (&["flood", "banjir"], "flood"),
+            (&["landslide", "longsor"], "landslide"),
+            (&["volcan", "erupsi", "gunung api", "gunungapi"], "volcanic_eruption"),
+            (&["fire", "kebakaran"], "fire"),
+            (&["drought", "kekeringan"], "drought"),
+            (&["storm", "cyclone", "wind", "rain", "weather", "cuaca", "angin", "hujan", "badai", "siklon"], "storm"),
+            (&["epidemic", "outbreak", "wabah"], "epidemic"),
+        ];
+        if let Some((_, key)) = keywords.iter().find(|(words, _)| words.iter().any(|w| event.contains(w))) {
+            return key.to_string();
+        }
+
+        match self.categories.first().map(String::as_str) {
+            Some("Fire") => "fire".to_string(),
+            Some("Health") => "epidemic".to_string(),
+            Some("CBRNE") => "technological".to_string(),
+            _ => self.event.trim().to_lowercase(),
+        }
+    }
+
+    /// First location found across the info areas
+    pub fn location(&self) -> Option<Coordinates> {
+        self.areas.iter().find_map(CapArea::centroid)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CapAlert {
+    pub identifier: String,
+    pub sender: String,
+    pub sent: DateTime<Utc>,
+    pub status: CapStatus,
+    pub msg_type: CapMsgType,
+    pub scope: CapScope,
+    pub note: Option<String>,
+    pub references: Option<String>,
+    pub info: Vec<CapInfo>,
+}
+
+impl CapAlert {
+    /// Info block in the preferred language, falling back to the first one
+    pub fn info_for_language(&self, language_prefix: &str) -> Option<&CapInfo> {
+        self.info.iter()
+            .find(|info| info.language.to_lowercase().starts_with(language_prefix))
+            .or_else(|| self.info.first())
+    }
+
+    /// `(sender, identifier)` of each earlier message named in `references`,
+    /// which lists `sender,identifier,sent` triples separated by whitespace
+    pub fn referenced_alerts(&self) -> Vec<(String, String)> {
+        self.references.as_deref()
+            .unwrap_or_default()
+            .split_whitespace()
+            .filter_map(|entry| {
+                let mut parts = entry.splitn(3, ',');
+                let sender = parts.next().filter(|s| !s.is_empty())?;
+                let identifier = parts.next().filter(|s| !s.is_empty())?;
+                Some((sender.to_string(), identifier.to_string()))
+            })
+            .collect()
+    }
+
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::new();
+        xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(xml, "<alert xmlns=\"{}\">", CAP_NAMESPACE);
+        push_element(&mut xml, 1, "identifier", &self.identifier);
+        push_element(&mut xml, 1, "sender", &self.sender);
+        push_element(&mut xml, 1, "sent", &format_time(&self.sent));
+        push_element(&mut xml, 1, "status", self.status.as_str());
+        push_element(&mut xml, 1, "msgType", self.msg_type.as_str());
+        push_element(&mut xml, 1, "scope", self.scope.as_str());
+        push_optional(&mut xml, 1, "note", &self.note);
+        push_optional(&mut xml, 1, "references", &self.references);
+
+        for info in &self.info {
+            xml.push_str("  <info>\n");
+            push_element(&mut xml, 2, "language", &info.language);
+            for category in &info.categories {
+                push_element(&mut xml, 2, "category", category);
+            }
+            push_element(&mut xml, 2, "event", &info.event);
+            for response_type in &info.response_types {
+                push_element(&mut xml, 2, "responseType", response_type);
+            }
+            push_element(&mut xml, 2, "urgency", info.urgency.as_str());
+            push_element(&mut xml, 2, "severity", info.severity.as_str());
+            push_element(&mut xml, 2, "certainty", info.certainty.as_str());
+            push_optional(&mut xml, 2, "effective", &info.effective.as_ref().map(format_time));
+            push_optional(&mut xml, 2, "onset", &info.onset.as_ref().map(format_time));
+            push_optional(&mut xml, 2, "expires", &info.expires.as_ref().map(format_time));
+            push_optional(&mut xml, 2, "senderName", &info.sender_name);
+            push_optional(&mut xml, 2, "headline", &info.headline);
+            push_optional(&mut xml, 2, "description", &info.description);
+            push_optional(&mut xml, 2, "instruction", &info.instruction);
+            push_optional(&mut xml, 2, "web", &info.web);
+
+            for area in &info.areas {
+                xml.push_str("    <area>\n");
+                push_element(&mut xml, 3, "areaDesc", &area.area_desc);
+                for polygon in &area.polygons {
+                    let points: Vec<String> = polygon.iter()
+                        .map(|c| format!("{},{}", c.latitude, c.longitude))
+                        .collect();
+                    push_element(&mut xml, 3, "polygon", &points.join(" "));
+                }
+                for circle in &area.circles {
+                    let value = format!("{},{} {}", circle.center.latitude, circle.center.longitude, circle.radius_km);
+                    push_element(&mut xml, 3, "circle", &value);
+                }
+                xml.push_str("    </area>\n");
+            }
+            xml.push_str("  </info>\n");
+        }
+
+        xml.push_str("</alert>\n");
+        xml
+    }
+
+    /// Parse a CAP 1.2 document; namespace prefixes are ignored
+    pub fn from_xml(xml: &str) -> AppResult<Self> {
+        let root = Element::parse(xml)?;
+        if root.name != "alert" {
+            return Err(AppError::Validation(format!("Expected a CAP <alert> document, found <{}>", root.name)));
+        }
+
+        let info = root.children("info").map(parse_info).collect::<AppResult<Vec<_>>>()?;
+        Ok(Self {
+            identifier: root.required("identifier")?,
+            sender: root.required("sender")?,
+            sent: parse_time(&root.required("sent")?)?,
+            status: root.required("status")?.parse()?,
+            msg_type: root.required("msgType")?.parse()?,
+            scope: root.required("scope")?.parse()?,
+            note: root.text("note"),
+            references: root.text("references"),
+            info,
+        })
+    }
+}
+
+fn parse_info(element: &Element) -> AppResult<CapInfo> {
+    let optional_time = |name: &str| element.text(name).map(|raw| parse_time(&raw)).transpose();
+    let areas = element.children("area").map(parse_area).collect::<AppResult<Vec<_>>>()?;
+
+    Ok(CapInfo {
+        language: element.text("language").unwrap_or_else(|| "en-US".to_string()),
+        categories: element.children("category").filter_map(Element::value).collect(),
+        event: element.required("event")?,
+        response_types: element.children("responseType").filter_map(Element::value).collect(),
+        urgency: element.required("urgency")?.parse()?,
+        severity: element.required("severity")?.parse()?,
+        certainty: element.required("certainty")?.parse()?,
+        effective: optional_time("effective")?,
+        onset: optional_time("onset")?,
+        expires: optional_time("expires")?,
+        sender_name: element.text("senderName"),
+        headline: element.text("headline"),
+        description: element.text("description"),
+        instruction: element.text("instruction"),
+        web: element.text("web"),
+        areas,
+    })
+}
+
+fn parse_area(element: &Element) -> AppResult<CapArea> {
+    let polygons = element.children("polygon")
+        .filter_map(Element::value)
+        .map(|raw| raw.split_whitespace().map(parse_point).collect::<AppResult<Vec<_>>>())
+        .collect::<AppResult<Vec<_>>>()?;
+
+    let circles = element.children("circle")
+        .filter_map(Element::value)
+        .map(|raw| {
+            let (point, radius) = raw.split_once(char::is_whitespace)
+                .ok_or_else(|| AppError::Validation(format!("Invalid CAP circle '{}'", raw)))?;
+            let radius_km: f64 = radius.trim().parse()
+                .map_err(|_| AppError::Validation(format!("Invalid CAP circle radius '{}'", radius)))?;
+            Ok(CapCircle { center: parse_point(point)?, radius_km })
+        })
+        .collect::<AppResult<Vec<_>>>()?;
+
+    Ok(CapArea {
+        area_desc: element.text("areaDesc").unwrap_or_default(),
+        polygons,
+        circles,
+    })
+}
+
+/// CAP points are written as "latitude,longitude"
+fn parse_point(raw: &str) -> AppResult<Coordinates> {
+    let invalid = || AppError::Validation(format!("Invalid CAP point '{}'", raw));
+    let (lat, lng) = raw.split_once(',').ok_or_else(invalid)?;
+    let latitude: f64 = lat.trim().parse().map_err(|_| invalid())?;
+    let longitude: f64 = lng.trim().parse().map_err(|_| invalid())?;
+    Coordinates::new(latitude, longitude).map_err(|_| invalid())
+}
+
+/// CAP forbids the "Z" designator, so UTC is written as +00:00
+fn format_time(time: &DateTime<Utc>) -> String {
+    time.format("%Y-%m-%dT%H:%M:%S+00:00").to_string()
+}
+
+fn parse_time(raw: &str) -> AppResult<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(raw.trim())
+        .map(|time| time.with_timezone(&Utc))
+        .map_err(|_| AppError::Validation(format!("Invalid CAP timestamp '{}'", raw)))
+}
+
+fn push_element(xml: &mut String, depth: usize, name: &str, value: &str) {
+    let _ = writeln!(xml, "{}<{}>{}</{}>", "  ".repeat(depth), name, quick_xml::escape::escape(value), name);
+}
+
+fn push_optional(xml: &mut String, depth: usize, name: &str, value: &Option<String>) {
+    if let Some(value) = value {
+        push_element(xml, depth, name, value);
+    }
+}
+
+/// Minimal element tree keyed by local names
+struct Element {
+    name: String,
+    text: String,
+    children: Vec<Element>,
+}
+
+impl Element {
+    fn parse(xml: &str) -> AppResult<Self> {
+        let invalid = |e: quick_xml::Error| AppError::Validation(format!("Invalid CAP XML: {}", e));
+        let new_element = |name: &[u8]| Element {
+            name: String::from_utf8_lossy(name).into_owned(),
+            text: String::new(),
+            children: Vec::new(),
+        };
+
+        let mut reader = Reader::from_str(xml);
+        let mut stack: Vec<Element> = Vec::new();
+        loop {
+            match reader.read_event().map_err(invalid)? {
+                Event::Start(start) => stack.push(new_element(start.local_name().as_ref())),
+                Event::Empty(start) => {
+                    let element = new_element(start.local_name().as_ref());
+                    match stack.last_mut() {
+                        Some(parent) => parent.children.push(element),
+                        None => return Ok(element),
+                    }
+                }
+                Event::Text(text) => {
+                    if let Some(current) = stack.last_mut() {
+                        current.text.push_str(&text.unescape().map_err(invalid)?);
+                    }
+                }
+                Event::CData(data) => {
+                    if let Some(current) = stack.last_mut() {
+                        current.text.push_str(&String::from_utf8_lossy(&data.into_inner()));
+                    }
+                }
+                Event::End(_) => {
+                    let element = stack.pop()
+                        .ok_or_else(|| AppError::Validation("Invalid CAP XML: unbalanced tags".to_string()))?;
+                    match stack.last_mut() {
+                        Some(parent) => parent.children.push(element),
+                        None => return Ok(element),
+                    }
+                }
+                Event::Eof => return Err(AppError::Validation("Invalid CAP XML: unexpected end of document".to_string())),
+                _ => {}
+            }
+        }
+    }
+
+    fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> + 'a {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    fn value(&self) -> Option<String> {
+        let value = self.text.trim();
+        (!value.is_empty()).then(|| value.to_string())
+    }
+
+    fn text(&self, name: &str) -> Option<String> {
+        self.children(name).next().and_then(Element::value)
+    }
+
+    fn required(&self, name: &str) -> AppResult<String> {
+        self.text(name)
+            .ok_or_else(|| AppError::Validation(format!("CAP <{}> is missing <{}>", self.name, name)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BMKG_SAMPLE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<cap:alert xmlns:cap="urn:oasis:names:tc:emergency:cap:1.2">
+  <cap:identifier>BMKG-20261016-0001</cap:identifier>
+  <cap:sender>info@bmkg.go.id</cap:sender>
+  <cap:sent>2026-10-16T14:05:00+07:00</cap:sent>
+  <cap:status>Actual</cap:status>
+  <cap:msgType>Alert</cap:msgType>
+  <cap:scope>Public</cap:scope>
+  <cap:info>
+    <cap:language>en-US</cap:language>
+    <cap:category>Met</cap:category>
+    <cap:event>Heavy rain</cap:event>
+    <cap:urgency>Expected</cap:urgency>
+    <cap:severity>Moderate</cap:severity>
+    <cap:certainty>Likely</cap:certainty>
+    <cap:headline>Heavy rain warning</cap:headline>
+  </cap:info>
+  <cap:info>
+    <cap:language>id-ID</cap:language>
+    <cap:category>Met</cap:category>
+    <cap:event>Hujan Lebat &amp; Angin Kencang</cap:event>
+    <cap:urgency>Immediate</cap:urgency>
+    <cap:severity>Severe</cap:severity>
+    <cap:certainty>Observed</cap:certainty>
+    <cap:headline>Peringatan Dini Cuaca Jakarta</cap:headline>
+    <cap:description><![CDATA[Hujan lebat disertai angin kencang]]></cap:description>
+    <cap:area>
+      <cap:areaDesc>Jakarta Selatan</cap:areaDesc>
+      <cap:polygon>-6.30,106.75 -6.30,106.85 -6.20,106.85 -6.20,106.75 -6.30,106.75</cap:polygon>
+    </cap:area>
+  </cap:info>
+</cap:alert>"#;
+
+    #[test]
+    fn parses_prefixed_feed_and_prefers_language() {
+        let alert = CapAlert::from_xml(BMKG_SAMPLE).unwrap();
+        assert_eq!(alert.identifier, "BMKG-20261016-0001");
+        assert_eq!(alert.sent.to_rfc3339(), "2026-10-16T07:05:00+00:00");
+
+        let info = alert.info_for_language("id").unwrap();
+        assert_eq!(info.event, "Hujan Lebat & Angin Kencang");
+        assert_eq!(info.description.as_deref(), Some("Hujan lebat disertai angin kencang"));
+        assert_eq!(info.disaster_type(), "storm");
+        assert_eq!(info.severity.to_disaster_severity(), DisasterSeverity::Severe);
+
+        let location = info.location().unwrap();
+        assert!((location.latitude + 6.25).abs() < 1e-9 && (location.longitude - 106.80).abs() < 1e-9);
+        assert!(alert.referenced_alerts().is_empty());
+    }
+
+    #[test]
+    fn update_names_the_alerts_it_supersedes() {
+        let update = BMKG_SAMPLE
+            .replace("<cap:msgType>Alert</cap:msgType>", "<cap:msgType>Update</cap:msgType>\n  \
+                <cap:references>info@bmkg.go.id,BMKG-20261016-0001,2026-10-16T14:05:00+07:00 \
+                info@bmkg.go.id,BMKG-20261016-0002,2026-10-16T15:05:00+07:00</cap:references>");
+        let alert = CapAlert::from_xml(&update).unwrap();
+        assert_eq!(alert.msg_type, CapMsgType::Update);
+        assert_eq!(alert.referenced_alerts(), vec![
+            ("info@bmkg.go.id".to_string(), "BMKG-20261016-0001".to_string()),
+            ("info@bmkg.go.id".to_string(), "BMKG-20261016-0002".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn serialized_alert_round_trips() {
+        let center = Coordinates::new(-6.2, 106.8).unwrap();
+        let alert = CapAlert {
+            identifier: "ts-1".to_string(),
+            sender: CAP_SENDER.to_string(),
+            sent: DateTime::parse_from_rfc3339("2026-10-16T07:00:00Z").unwrap().with_timezone(&Utc),
+            status: CapStatus::Actual,
+            msg_type: CapMsgType::Alert,
+            scope: CapScope::Public,
+            note: None,
+            references: None,
+            info: vec![CapInfo {
+                language: "id-ID".to_string(),
+                categories: vec![category_for(&DisasterType::Flood).to_string()],
+                event: "Flood".to_string(),
+                response_types: vec!["Evacuate".to_string()],
+                urgency: CapUrgency::Immediate,
+                severity: CapSeverity::from(&DisasterSeverity::Catastrophic),
+                certainty: CapCertainty::Observed,
+                effective: None,
+                onset: None,
+                expires: None,
+                sender_name: Some("Terra Siaga".to_string()),
+                headline: Some("Evakuasi <segera>".to_string()),
+                description: Some("Banjir & longsor".to_string()),
+                instruction: None,
+                web: None,
+                areas: vec![CapArea::circle("Radius 5 km".to_string(), center, 5.0)],
+            }],
+        };
+
+        let xml = alert.to_xml();
+        assert!(xml.contains("<sent>2026-10-16T07:00:00+00:00</sent>"));
+        assert!(xml.contains("<severity>Extreme</severity>"));
+        assert!(xml.contains("<circle>-6.2,106.8 5</circle>"));
+        assert_eq!(CapAlert::from_xml(&xml).unwrap(), alert);
+    }
+}
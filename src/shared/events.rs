@@ -3,12 +3,14 @@
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::{broadcast, mpsc, RwLock};
 use uuid::Uuid;
 
+use crate::domain::entities::disaster as disaster_entity;
+use crate::domain::value_objects as domain_values;
 use crate::shared::error::{AppError, AppResult};
 use crate::shared::types::*;
 
@@ -24,9 +26,28 @@ pub enum Status {
     Failed,
 }
 
+/// Number of events loaded per batch when replaying the store into projections
+const REBUILD_BATCH_SIZE: u32 = 500;
+
+/// Serialized event body, available for every `Serialize` event type
+pub trait EventPayload {
+    fn payload(&self) -> AppResult<serde_json::Value>;
+}
+
+impl<T: Serialize> EventPayload for T {
+    fn payload(&self) -> AppResult<serde_json::Value> {
+        serde_json::to_value(self).map_err(|e| AppError::Serialization(e.to_string()))
+    }
+}
+
+/// Aggregate type encoded in a dotted event type (`disaster.reported` -> `disaster`)
+pub fn aggregate_type_of(event_type: &str) -> &str {
+    event_type.split('.').next().unwrap_or(event_type)
+}
+
 /// Base trait for all domain events
 #[async_trait]
-pub trait DomainEvent: Send + Sync + std::fmt::Debug {
+pub trait DomainEvent: EventPayload + Send + Sync + std::fmt::Debug {
     fn event_type(&self) -> &'static str;
     fn aggregate_id(&self) -> String;
     fn event_version(&self) -> u32;
@@ -53,10 +74,43 @@ pub struct EventMetadata {
 /// Stored event with metadata and payload
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StoredEvent {
+    /// Global position in the event store (0 until persisted)
+    #[serde(default)]
+    pub sequence: i64,
     pub metadata: EventMetadata,
     pub payload: serde_json::Value,
 }
 
+impl StoredEvent {
+    /// Capture an event that has not been persisted, taking its ids and timestamps from the event itself
+    pub fn capture(event: &dyn DomainEvent) -> AppResult<Self> {
+        Ok(Self {
+            sequence: 0,
+            metadata: EventMetadata {
+                event_id: Uuid::new_v4(),
+                event_type: event.event_type().to_string(),
+                aggregate_id: event.aggregate_id(),
+                aggregate_type: aggregate_type_of(event.event_type()).to_string(),
+                event_version: event.event_version(),
+                occurred_at: event.occurred_at(),
+                correlation_id: event.correlation_id(),
+                causation_id: event.causation_id(),
+                user_id: None,
+                session_id: None,
+            },
+            payload: event.payload()?,
+        })
+    }
+
+    /// Deserialize the payload into a concrete event type
+    pub fn decode<T: DeserializeOwned>(&self) -> AppResult<T> {
+        serde_json::from_value(self.payload.clone()).map_err(|e| AppError::Serialization(format!(
+            "Cannot decode {} event {}: {}",
+            self.metadata.event_type, self.metadata.event_id, e
+        )))
+    }
+}
+
 /// Disaster-related events
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DisasterEvent {
@@ -92,6 +146,65 @@ pub enum DisasterEvent {
         reason: String,
         updated_at: DateTime<Utc>,
     },
+
+    // Aggregate change events emitted by the `Disaster` entity; replaying them
+    // in order rebuilds its state
+    DisasterOpened {
+        disaster_id: DisasterId,
+        title: String,
+        description: String,
+        disaster_type: disaster_entity::DisasterType,
+        severity: disaster_entity::DisasterSeverity,
+        location: domain_values::Coordinates,
+        reporter_id: UserId,
+        reported_at: DateTime<Utc>,
+    },
+    DisasterStatusUpdated {
+        disaster_id: DisasterId,
+        old_status: disaster_entity::DisasterStatus,
+        new_status: disaster_entity::DisasterStatus,
+        updated_by: UserId,
+        updated_at: DateTime<Utc>,
+    },
+    DisasterSeverityEscalated {
+        disaster_id: DisasterId,
+        old_severity: disaster_entity::DisasterSeverity,
+        new_severity: disaster_entity::DisasterSeverity,
+        escalated_by: UserId,
+        escalated_at: DateTime<Utc>,
+    },
+    DisasterResponderAssigned {
+        disaster_id: DisasterId,
+        responder_id: UserId,
+        assigned_at: DateTime<Utc>,
+    },
+    DisasterResponderRemoved {
+        disaster_id: DisasterId,
+        responder_id: UserId,
+        removed_at: DateTime<Utc>,
+    },
+    DisasterResourceNeedAdded {
+        disaster_id: DisasterId,
+        resource: disaster_entity::ResourceNeed,
+        added_at: DateTime<Utc>,
+    },
+    DisasterDamageEstimated {
+        disaster_id: DisasterId,
+        damage: disaster_entity::EstimatedDamage,
+        estimated_at: DateTime<Utc>,
+    },
+    DisasterConfidenceAssessed {
+        disaster_id: DisasterId,
+        confidence_score: f32,
+        report_count: u32,
+        assessed_at: DateTime<Utc>,
+    },
+    /// A responder's tracked position first reached the scene
+    DisasterFirstResponderArrived {
+        disaster_id: DisasterId,
+        responder_id: UserId,
+        arrived_at: DateTime<Utc>,
+    },
 }
 
 #[async_trait]
@@ -102,16 +215,20 @@ impl DomainEvent for DisasterEvent {
             DisasterEvent::DisasterVerified { .. } => "disaster.verified",
             DisasterEvent::DisasterStatusChanged { .. } => "disaster.status_changed",
             DisasterEvent::DisasterSeverityUpdated { .. } => "disaster.severity_updated",
+            DisasterEvent::DisasterOpened { .. } => "disaster.opened",
+            DisasterEvent::DisasterStatusUpdated { .. } => "disaster.status_updated",
+            DisasterEvent::DisasterSeverityEscalated { .. } => "disaster.severity_escalated",
+            DisasterEvent::DisasterResponderAssigned { .. } => "disaster.responder_assigned",
+            DisasterEvent::DisasterResponderRemoved { .. } => "disaster.responder_removed",
+            DisasterEvent::DisasterResourceNeedAdded { .. } => "disaster.resource_need_added",
+            DisasterEvent::DisasterDamageEstimated { .. } => "disaster.damage_estimated",
+            DisasterEvent::DisasterConfidenceAssessed { .. } => "disaster.confidence_assessed",
+            DisasterEvent::DisasterFirstResponderArrived { .. } => "disaster.first_responder_arrived",
         }
     }
 
     fn aggregate_id(&self) -> String {
-        match self {
-            DisasterEvent::DisasterReported { disaster_id, .. } => disaster_id.to_string(),
-            DisasterEvent::DisasterVerified { disaster_id, .. } => disaster_id.to_string(),
-            DisasterEvent::DisasterStatusChanged { disaster_id, .. } => disaster_id.to_string(),
-            DisasterEvent::DisasterSeverityUpdated { disaster_id, .. } => disaster_id.to_string(),
-        }
+        self.disaster_id().to_string()
     }
 
     fn event_version(&self) -> u32 {
@@ -124,6 +241,15 @@ impl DomainEvent for DisasterEvent {
             DisasterEvent::DisasterVerified { verified_at, .. } => *verified_at,
             DisasterEvent::DisasterStatusChanged { changed_at, .. } => *changed_at,
             DisasterEvent::DisasterSeverityUpdated { updated_at, .. } => *updated_at,
+            DisasterEvent::DisasterOpened { reported_at, .. } => *reported_at,
+            DisasterEvent::DisasterStatusUpdated { updated_at, .. } => *updated_at,
+            DisasterEvent::DisasterSeverityEscalated { escalated_at, .. } => *escalated_at,
+            DisasterEvent::DisasterResponderAssigned { assigned_at, .. } => *assigned_at,
+            DisasterEvent::DisasterResponderRemoved { removed_at, .. } => *removed_at,
+            DisasterEvent::DisasterResourceNeedAdded { added_at, .. } => *added_at,
+            DisasterEvent::DisasterDamageEstimated { estimated_at, .. } => *estimated_at,
+            DisasterEvent::DisasterConfidenceAssessed { assessed_at, .. } => *assessed_at,
+            DisasterEvent::DisasterFirstResponderArrived { arrived_at, .. } => *arrived_at,
         }
     }
 
@@ -136,6 +262,26 @@ impl DomainEvent for DisasterEvent {
     }
 }
 
+impl DisasterEvent {
+    pub fn disaster_id(&self) -> DisasterId {
+        match self {
+            DisasterEvent::DisasterReported { disaster_id, .. }
+            | DisasterEvent::DisasterVerified { disaster_id, .. }
+            | DisasterEvent::DisasterStatusChanged { disaster_id, .. }
+            | DisasterEvent::DisasterSeverityUpdated { disaster_id, .. }
+            | DisasterEvent::DisasterOpened { disaster_id, .. }
+            | DisasterEvent::DisasterStatusUpdated { disaster_id, .. }
+            | DisasterEvent::DisasterSeverityEscalated { disaster_id, .. }
+            | DisasterEvent::DisasterResponderAssigned { disaster_id, .. }
+            | DisasterEvent::DisasterResponderRemoved { disaster_id, .. }
+            | DisasterEvent::DisasterResourceNeedAdded { disaster_id, .. }
+            | DisasterEvent::DisasterDamageEstimated { disaster_id, .. }
+            | DisasterEvent::DisasterConfidenceAssessed { disaster_id, .. }
+            | DisasterEvent::DisasterFirstResponderArrived { disaster_id, .. } => *disaster_id,
+        }
+    }
+}
+
 /// Emergency response events
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum EmergencyResponseEvent {
@@ -302,10 +448,11 @@ pub trait EventHandler<T: DomainEvent>: Send + Sync {
 /// Event store trait for persisting events
 #[async_trait]
 pub trait EventStore: Send + Sync {
+    /// Append an event; `expected_version` (if given) must match the aggregate's current version
     async fn append_event(
         &self,
         aggregate_id: &str,
-        expected_version: u32,
+        expected_version: Option<u32>,
         event: Box<dyn DomainEvent>,
         metadata: Option<EventMetadata>,
     ) -> AppResult<()>;
@@ -322,25 +469,58 @@ pub trait EventStore: Send + Sync {
         from_timestamp: Option<DateTime<Utc>>,
         limit: Option<u32>,
     ) -> AppResult<Vec<StoredEvent>>;
+
+    /// Events across all aggregates in global order, starting after `after_sequence`
+    async fn get_all_events(
+        &self,
+        after_sequence: i64,
+        limit: u32,
+    ) -> AppResult<Vec<StoredEvent>>;
 }
 
+/// Point-in-time state of an aggregate, used to avoid replaying its full history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregateSnapshot {
+    pub aggregate_id: String,
+    pub aggregate_type: String,
+    /// Stream version the state reflects; replay continues after it
+    pub version: u32,
+    pub state: serde_json::Value,
+    pub taken_at: DateTime<Utc>,
+}
+
+/// Snapshot storage; a snapshot also counts towards the aggregate's stream version
+#[async_trait]
+pub trait SnapshotStore: Send + Sync {
+    async fn save_snapshot(&self, snapshot: AggregateSnapshot) -> AppResult<()>;
+    async fn load_snapshot(&self, aggregate_id: &str) -> AppResult<Option<AggregateSnapshot>>;
+}
+
+/// Events kept for listeners that fall behind before they start missing them
+const LISTENER_BUFFER: usize = 1024;
+
 /// Event bus for publishing and subscribing to events
 pub struct EventBus {
     handlers: Arc<RwLock<HashMap<String, Vec<Box<dyn EventHandler<dyn DomainEvent>>>>>>,
     event_store: Arc<dyn EventStore>,
     publisher: mpsc::UnboundedSender<StoredEvent>,
+    listeners: broadcast::Sender<StoredEvent>,
 }
 
 impl EventBus {
     pub fn new(event_store: Arc<dyn EventStore>) -> Self {
         let (publisher, mut receiver) = mpsc::unbounded_channel::<StoredEvent>();
+        let (listeners, _) = broadcast::channel(LISTENER_BUFFER);
         let handlers = Arc::new(RwLock::new(HashMap::new()));
 
         // Start background event processing
         let handlers_clone = handlers.clone();
+        let listeners_clone = listeners.clone();
         tokio::spawn(async move {
             while let Some(stored_event) = receiver.recv().await {
-                Self::process_event(handlers_clone.clone(), stored_event).await;
+                Self::process_event(handlers_clone.clone(), stored_event.clone()).await;
+                // No listeners is not an error; the event is simply not observed
+                let _ = listeners_clone.send(stored_event);
             }
         });
 
@@ -348,6 +528,7 @@ impl EventBus {
             handlers,
             event_store,
             publisher,
+            listeners,
         }
     }
 
@@ -360,41 +541,38 @@ impl EventBus {
         correlation_id: Option<String>,
         causation_id: Option<String>,
     ) -> AppResult<()> {
-        let metadata = EventMetadata {
-            event_id: Uuid::new_v4(),
-            event_type: event.event_type().to_string(),
-            aggregate_id: event.aggregate_id(),
-            aggregate_type: "disaster".to_string(), // This should be determined by the event
-            event_version: event.event_version(),
-            occurred_at: event.occurred_at(),
-            correlation_id,
-            causation_id,
-            user_id,
-            session_id,
-        };
+        let mut stored_event = StoredEvent::capture(event.as_ref())?;
+        stored_event.metadata.correlation_id = correlation_id;
+        stored_event.metadata.causation_id = causation_id;
+        stored_event.metadata.user_id = user_id;
+        stored_event.metadata.session_id = session_id;
 
         // Store the event
         self.event_store
             .append_event(
                 &event.aggregate_id(),
-                event.event_version(),
+                None,
                 event,
-                Some(metadata.clone()),
+                Some(stored_event.metadata.clone()),
             )
             .await?;
 
-        // Create stored event for processing
-        let stored_event = StoredEvent {
-            metadata,
-            payload: serde_json::Value::Null, // Would contain serialized event data
-        };
-
         // Publish for async processing
-        if let Err(_) = self.publisher.send(stored_event) {
+        self.announce(stored_event);
+        Ok(())
+    }
+
+    /// Hand an event that is already persisted, or never will be, to handlers and listeners
+    pub fn announce(&self, stored_event: StoredEvent) {
+        if self.publisher.send(stored_event).is_err() {
             tracing::error!("Failed to send event to processing queue");
         }
+    }
 
-        Ok(())
+    /// Observe every event passing through the bus, in order; a listener that falls more than
+    /// `LISTENER_BUFFER` events behind skips ahead and is told how many it missed
+    pub fn listen(&self) -> broadcast::Receiver<StoredEvent> {
+        self.listeners.subscribe()
     }
 
     /// Subscribe to events of a specific type
@@ -515,6 +693,11 @@ impl QueryBus {
 pub trait Projection: Send + Sync {
     async fn project(&self, event: &StoredEvent) -> AppResult<()>;
     async fn reset(&self) -> AppResult<()>;
+
+    /// Whether the read model is missing and must be rebuilt before following live events
+    async fn needs_rebuild(&self) -> AppResult<bool> {
+        Ok(false)
+    }
 }
 
 /// Projection manager
@@ -541,14 +724,66 @@ impl ProjectionManager {
             projection.reset().await?;
         }
 
-        // This would typically stream all events and replay them
-        // let events = self.event_store.get_all_events().await?;
-        // for event in events {
-        //     for projection in &self.projections {
-        //         projection.project(&event).await?;
-        //     }
-        // }
+        let mut after_sequence = 0;
+        let mut replayed = 0usize;
+        loop {
+            let batch = self.event_store.get_all_events(after_sequence, REBUILD_BATCH_SIZE).await?;
+            for event in &batch {
+                for projection in &self.projections {
+                    projection.project(event).await?;
+                }
+            }
+
+            replayed += batch.len();
+            match batch.last() {
+                Some(last) if batch.len() as u32 == REBUILD_BATCH_SIZE => after_sequence = last.sequence,
+                _ => break,
+            }
+        }
+
+        tracing::info!("Rebuilt {} projections from {} events", self.projections.len(), replayed);
+        Ok(())
+    }
+
+    /// Keep the projections current from events announced on the bus, rebuilding them first
+    /// when one needs it and again whenever the listener falls too far behind to catch up
+    pub fn spawn(self: Arc<Self>, bus: &EventBus) -> tokio::task::JoinHandle<()> {
+        // Listen before rebuilding so events saved during the rebuild are not lost
+        let mut events = bus.listen();
+        tokio::spawn(async move {
+            if let Err(e) = self.rebuild_if_needed().await {
+                tracing::error!("Projection rebuild failed: {}", e);
+            }
+            loop {
+                match events.recv().await {
+                    Ok(event) => {
+                        for projection in &self.projections {
+                            if let Err(e) = projection.project(&event).await {
+                                tracing::error!(
+                                    "Projection of {} event {} failed: {}",
+                                    event.metadata.event_type, event.metadata.event_id, e
+                                );
+                            }
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(missed)) => {
+                        tracing::warn!("Projections missed {} events, rebuilding", missed);
+                        if let Err(e) = self.rebuild_projections().await {
+                            tracing::error!("Projection rebuild failed: {}", e);
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
 
+    async fn rebuild_if_needed(&self) -> AppResult<()> {
+        for projection in &self.projections {
+            if projection.needs_rebuild().await? {
+                return self.rebuild_projections().await;
+            }
+        }
         Ok(())
     }
 }
@@ -5,9 +5,11 @@ use geo_types::Coord;
 use rstar::{RTree, RTreeObject, AABB};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use crate::domain::value_objects::Coordinates;
 use crate::shared::error::AppResult;
+use crate::shared::routing::{RoutingEngine, TravelMode, WALKING_SPEED_KMH};
 use crate::shared::types::constants::EARTH_RADIUS_KM;
 
 /// Geographic region types for disaster management
@@ -71,6 +73,7 @@ pub struct SpatialIndex {
     rtree: RTree<IndexedPoint>,
     regions: HashMap<String, AdministrativeRegion>,
     pois: HashMap<String, PointOfInterest>,
+    response_teams: HashMap<String, Coordinates>,
 }
 
 /// Point wrapper for R-tree indexing
@@ -87,6 +90,7 @@ enum IndexedPointType {
     DisasterReport,
     EmergencyResponse,
     Volunteer,
+    ResponseTeam,
 }
 
 impl RTreeObject for IndexedPoint {
@@ -113,6 +117,7 @@ impl SpatialIndex {
             rtree: RTree::new(),
             regions: HashMap::new(),
             pois: HashMap::new(),
+            response_teams: HashMap::new(),
         }
     }
 
@@ -128,6 +133,49 @@ impl SpatialIndex {
         self.pois.insert(poi.id.clone(), poi);
     }
 
+    /// Add a response team at its current location
+    pub fn add_response_team(&mut self, team_id: &str, location: &Coordinates) {
+        self.rtree.insert(IndexedPoint {
+            id: team_id.to_string(),
+            point: Point::new(location.longitude, location.latitude),
+            point_type: IndexedPointType::ResponseTeam,
+        });
+        self.response_teams.insert(team_id.to_string(), location.clone());
+    }
+
+    /// Find the nearest response teams accepted by `filter`, closest first, with distance in km
+    pub fn find_nearest_response_teams<F>(
+        &self,
+        center: &Coordinates,
+        limit: usize,
+        filter: F,
+    ) -> Vec<(&str, f64)>
+    where
+        F: Fn(&str) -> bool,
+    {
+        if limit == 0 {
+            return Vec::new();
+        }
+        let center_arr = [center.longitude, center.latitude];
+
+        let mut candidates: Vec<(&str, f64)> = self
+            .rtree
+            .nearest_neighbor_iter(&center_arr)
+            .filter(|indexed_point| matches!(indexed_point.point_type, IndexedPointType::ResponseTeam))
+            .filter(|indexed_point| filter(&indexed_point.id))
+            .filter_map(|indexed_point| {
+                self.response_teams.get(&indexed_point.id).map(|location| {
+                    (indexed_point.id.as_str(), GeoCalculations::haversine_distance(center, location))
+                })
+            })
+            .collect();
+
+        // Degree-space ordering is only approximate; sort by geodesic distance
+        candidates.sort_by(|a, b| a.1.total_cmp(&b.1));
+        candidates.truncate(limit);
+        candidates
+    }
+
     /// Add an administrative region
     pub fn add_region(&mut self, region: AdministrativeRegion) {
         self.regions.insert(region.id.clone(), region);
@@ -404,6 +452,7 @@ pub struct EmergencyZoneManager {
     spatial_index: SpatialIndex,
     evacuation_routes: HashMap<String, EvacuationRoute>,
     safe_zones: HashMap<String, SafeZone>,
+    routing_engine: Option<Arc<RoutingEngine>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -457,6 +506,29 @@ impl EmergencyZoneManager {
             spatial_index: SpatialIndex::new(),
             evacuation_routes: HashMap::new(),
             safe_zones: HashMap::new(),
+            routing_engine: None,
+        }
+    }
+
+    /// Estimate evacuation times over the road network instead of straight lines
+    pub fn with_routing_engine(mut self, engine: Arc<RoutingEngine>) -> Self {
+        self.routing_engine = Some(engine);
+        self
+    }
+
+    pub fn add_evacuation_route(&mut self, route: EvacuationRoute) {
+        self.evacuation_routes.insert(route.id.clone(), route);
+    }
+
+    pub fn add_safe_zone(&mut self, zone: SafeZone) {
+        self.safe_zones.insert(zone.id.clone(), zone);
+    }
+
+    /// Walking time in hours between two points
+    fn walking_hours(&self, from: &Coordinates, to: &Coordinates) -> f64 {
+        match self.routing_engine.as_ref().and_then(|engine| engine.route(from, to, TravelMode::Walking)) {
+            Some(route) => route.duration_minutes / 60.0,
+            None => GeoCalculations::haversine_distance(from, to) / WALKING_SPEED_KMH,
         }
     }
 
@@ -502,12 +574,9 @@ impl EmergencyZoneManager {
     ) -> Option<std::time::Duration> {
         let route = self.evacuation_routes.get(route_id)?;
 
-        let distance = GeoCalculations::haversine_distance(from_location, &route.start_location);
-        let route_distance =
-            GeoCalculations::haversine_distance(&route.start_location, &route.end_location);
-
-        // Simple calculation - in practice this would be much more complex
-        let travel_time_hours = (distance + route_distance) / 5.0; // Assume 5 km/h walking speed
+        // Walk to the start of the route, then follow it to its end
+        let travel_time_hours = self.walking_hours(from_location, &route.start_location)
+            + self.walking_hours(&route.start_location, &route.end_location);
         let processing_time_hours = population as f64 / route.capacity_per_hour as f64;
 
         let total_hours = travel_time_hours + processing_time_hours;
@@ -649,6 +718,22 @@ mod tests {
         assert_eq!(nearby_pois[0].id, "hospital1");
     }
 
+    #[test]
+    fn test_nearest_response_teams() {
+        let mut index = SpatialIndex::new();
+        index.add_response_team("near", &Coordinates::new(-6.21, 106.85).unwrap());
+        index.add_response_team("far", &Coordinates::new(-6.90, 107.60).unwrap());
+        index.add_response_team("busy", &Coordinates::new(-6.2089, 106.8457).unwrap());
+
+        let center = Coordinates::new(-6.2088, 106.8456).unwrap();
+        let nearest = index.find_nearest_response_teams(&center, 2, |id| id != "busy");
+
+        assert_eq!(nearest.len(), 2);
+        assert_eq!(nearest[0].0, "near");
+        assert_eq!(nearest[1].0, "far");
+        assert!(nearest[0].1 < nearest[1].1);
+    }
+
     #[test]
     fn test_evacuation_time_estimation() {
         let mut manager = EmergencyZoneManager::new();
@@ -673,4 +758,45 @@ mod tests {
         assert!(time.is_some());
         assert!(time.unwrap().as_secs() > 0);
     }
+
+    #[test]
+    fn test_evacuation_time_follows_roads() {
+        use crate::shared::routing::RoadGraph;
+
+        // A single road that loops east, so the walk is much longer than the straight line
+        let graph = RoadGraph::from_geojson(r#"{
+            "type": "FeatureCollection",
+            "features": [
+                { "type": "Feature", "properties": { "highway": "residential" },
+                  "geometry": { "type": "LineString", "coordinates": [[106.80, -6.20], [106.83, -6.20], [106.83, -6.18], [106.80, -6.18]] } }
+            ]
+        }"#).unwrap();
+        let start = Coordinates::new(-6.20, 106.80).unwrap();
+        let end = Coordinates::new(-6.18, 106.80).unwrap();
+        let route = EvacuationRoute {
+            id: "route1".to_string(),
+            name: "River Road".to_string(),
+            start_location: start.clone(),
+            end_location: end.clone(),
+            waypoints: vec![],
+            capacity_per_hour: 1000,
+            is_active: true,
+            alternative_routes: vec![],
+            terrain_difficulty: TerrainDifficulty::Easy,
+        };
+
+        let mut straight = EmergencyZoneManager::new();
+        straight.add_evacuation_route(route.clone());
+        let mut routed = EmergencyZoneManager::new()
+            .with_routing_engine(Arc::new(RoutingEngine::new(graph)));
+        routed.add_evacuation_route(route);
+
+        let straight_secs = straight.estimate_evacuation_time(&start, "route1", 0).unwrap().as_secs_f64();
+        let routed_secs = routed.estimate_evacuation_time(&start, "route1", 0).unwrap().as_secs_f64();
+
+        let road_km = GeoCalculations::haversine_distance(&start, &Coordinates::new(-6.20, 106.83).unwrap()) * 2.0
+            + GeoCalculations::haversine_distance(&start, &end);
+        assert!(routed_secs > straight_secs * 3.0);
+        assert!((routed_secs - road_km / WALKING_SPEED_KMH * 3600.0).abs() < 5.0);
+    }
 }
@@ -18,6 +18,14 @@ pub mod rate_limiter;
 
 // Geographic utilities
 pub mod geo_utils;
+pub mod routing;
+pub mod cap;
+
+// Multi-factor authentication
+pub mod totp;
+
+// Attribute-based access policies
+pub mod policy;
 
 // Re-export commonly used types and functions
 pub use error::{AppError, AppResult};
@@ -0,0 +1,380 @@
+/// Attribute-based access policies
+/// Rules over subject and resource attributes that refine the role permission baseline; loaded from TOML
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use crate::shared::{AppError, AppResult, UserId, UserRole};
+
+/// Policies compiled into the binary, used when no policy file is configured
+const BUILTIN_POLICIES: &str = include_str!("../../config/policies.toml");
+
+/// Value of a subject or resource attribute, or a literal in a condition
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AttributeValue {
+    Bool(bool),
+    Text(String),
+    List(Vec<String>),
+}
+
+impl From<&str> for AttributeValue {
+    fn from(value: &str) -> Self {
+        AttributeValue::Text(value.to_string())
+    }
+}
+
+impl From<String> for AttributeValue {
+    fn from(value: String) -> Self {
+        AttributeValue::Text(value)
+    }
+}
+
+impl From<bool> for AttributeValue {
+    fn from(value: bool) -> Self {
+        AttributeValue::Bool(value)
+    }
+}
+
+impl From<Vec<String>> for AttributeValue {
+    fn from(value: Vec<String>) -> Self {
+        AttributeValue::List(value)
+    }
+}
+
+/// Named attributes describing a subject or a resource
+#[derive(Debug, Clone, Default)]
+pub struct Attributes(HashMap<String, AttributeValue>);
+
+impl Attributes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, name: &str, value: impl Into<AttributeValue>) -> Self {
+        self.insert(name, value);
+        self
+    }
+
+    pub fn insert(&mut self, name: &str, value: impl Into<AttributeValue>) {
+        self.0.insert(name.to_string(), value.into());
+    }
+
+    pub fn get(&self, name: &str) -> Option<&AttributeValue> {
+        self.0.get(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains_key(name)
+    }
+}
+
+/// The caller an access decision is made for
+#[derive(Debug, Clone)]
+pub struct Subject {
+    pub user_id: UserId,
+    pub role: UserRole,
+    /// Permission strings granted by the caller's role; the baseline for every action
+    pub permissions: Vec<String>,
+    /// Further attributes such as `provinces` or `mfa`
+    pub attributes: Attributes,
+}
+
+impl Subject {
+    pub fn new(user_id: UserId, role: UserRole, permissions: Vec<String>) -> Self {
+        Self { user_id, role, permissions, attributes: Attributes::new() }
+    }
+
+    pub fn with(mut self, name: &str, value: impl Into<AttributeValue>) -> Self {
+        self.attributes.insert(name, value);
+        self
+    }
+
+    fn attribute(&self, name: &str) -> Option<AttributeValue> {
+        match name {
+            "id" => Some(AttributeValue::Text(self.user_id.to_string())),
+            "role" => Some(AttributeValue::Text(self.role.as_str().to_string())),
+            "permissions" => Some(AttributeValue::List(self.permissions.clone())),
+            other => self.attributes.get(other).cloned(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Effect {
+    Allow,
+    Deny,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Operator {
+    #[default]
+    Eq,
+    Ne,
+    In,
+    NotIn,
+    Contains,
+    Exists,
+}
+
+/// One test on an attribute; a missing attribute never equals, belongs to or contains anything
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Condition {
+    /// `subject.<name>` or `resource.<name>`
+    pub attribute: String,
+    #[serde(default)]
+    pub op: Operator,
+    /// Literal to compare against
+    #[serde(default)]
+    pub value: Option<AttributeValue>,
+    /// Attribute to compare against, e.g. `subject.id`
+    #[serde(default)]
+    pub value_from: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Policy {
+    pub id: String,
+    #[serde(default)]
+    pub description: String,
+    pub effect: Effect,
+    /// Exact actions, `prefix:*` or `*`
+    pub actions: Vec<String>,
+    /// Roles the policy applies to; empty means every role
+    #[serde(default)]
+    pub roles: Vec<String>,
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PolicyFile {
+    #[serde(default)]
+    policies: Vec<Policy>,
+}
+
+/// Outcome of an access check and what decided it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decision {
+    pub allowed: bool,
+    /// Id of the deciding policy; `None` when the role baseline decided
+    pub policy: Option<String>,
+}
+
+impl Policy {
+    fn validate(&self) -> AppResult<()> {
+        if self.id.trim().is_empty() {
+            return Err(AppError::Configuration("Every policy needs an id".to_string()));
+        }
+        if self.actions.is_empty() {
+            return Err(AppError::Configuration(format!("Policy '{}' lists no actions", self.id)));
+        }
+        for condition in &self.conditions {
+            let references = std::iter::once(&condition.attribute).chain(condition.value_from.iter());
+            for reference in references {
+                if !(reference.starts_with("subject.") || reference.starts_with("resource.")) {
+                    return Err(AppError::Configuration(format!(
+                        "Policy '{}': attribute '{}' must start with subject. or resource.",
+                        self.id, reference
+                    )));
+                }
+            }
+            let operands = condition.value.is_some() as u8 + condition.value_from.is_some() as u8;
+            let expected = if condition.op == Operator::Exists { 0 } else { 1 };
+            if operands != expected {
+                return Err(AppError::Configuration(format!(
+                    "Policy '{}': condition on '{}' needs {} of value / value_from",
+                    self.id, condition.attribute, if expected == 0 { "neither" } else { "exactly one" }
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn covers(&self, subject: &Subject, action: &str) -> bool {
+        let action_matches = self.actions.iter().any(|pattern| {
+            pattern == "*"
+                || pattern == action
+                || pattern.strip_suffix('*').is_some_and(|prefix| action.starts_with(prefix))
+        });
+        action_matches && (self.roles.is_empty() || self.roles.iter().any(|role| role == subject.role.as_str()))
+    }
+}
+
+fn resolve(path: &str, subject: &Subject, resource: &Attributes) -> Option<AttributeValue> {
+    if let Some(name) = path.strip_prefix("subject.") {
+        subject.attribute(name)
+    } else if let Some(name) = path.strip_prefix("resource.") {
+        resource.get(name).cloned()
+    } else {
+        None
+    }
+}
+
+impl Condition {
+    fn holds(&self, subject: &Subject, resource: &Attributes) -> bool {
+        let left = resolve(&self.attribute, subject, resource);
+        let right = match &self.value_from {
+            Some(path) => resolve(path, subject, resource),
+            None => self.value.clone(),
+        };
+
+        let matches = |left: &AttributeValue, right: &AttributeValue| match (self.op, left, right) {
+            (Operator::Eq | Operator::Ne, l, r) => l == r,
+            (Operator::In | Operator::NotIn, AttributeValue::Text(l), AttributeValue::List(r)) => r.contains(l),
+            (Operator::Contains, AttributeValue::List(l), AttributeValue::Text(r)) => l.contains(r),
+            _ => false,
+        };
+
+        match self.op {
+            Operator::Exists => left.is_some(),
+            Operator::Ne | Operator::NotIn => !matches!((&left, &right), (Some(l), Some(r)) if matches(l, r)),
+            _ => matches!((&left, &right), (Some(l), Some(r)) if matches(l, r)),
+        }
+    }
+}
+
+/// Evaluates actions against the role baseline and the loaded policies
+#[derive(Debug, Clone)]
+pub struct PolicyEngine {
+    policies: Vec<Policy>,
+}
+
+impl PolicyEngine {
+    pub fn from_toml_str(source: &str) -> AppResult<Self> {
+        let file: PolicyFile = toml::from_str(source)
+            .map_err(|e| AppError::Configuration(format!("Invalid policy file: {}", e)))?;
+
+        let mut seen = HashSet::new();
+        for policy in &file.policies {
+            policy.validate()?;
+            if !seen.insert(policy.id.as_str()) {
+                return Err(AppError::Configuration(format!("Duplicate policy id '{}'", policy.id)));
+            }
+        }
+        Ok(Self { policies: file.policies })
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> AppResult<Self> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path)
+            .map_err(|e| AppError::Configuration(format!("Cannot read policy file {}: {}", path.display(), e)))?;
+        Self::from_toml_str(&source)
+    }
+
+    /// Policies shipped in `config/policies.toml`
+    pub fn builtin() -> Self {
+        Self::from_toml_str(BUILTIN_POLICIES).expect("built-in policies are valid")
+    }
+
+    pub fn policies(&self) -> &[Policy] {
+        &self.policies
+    }
+
+    /// Deny policies win, then allow policies, then the subject's role permissions
+    pub fn decide(&self, subject: &Subject, action: &str, resource: &Attributes) -> Decision {
+        let matching: Vec<&Policy> = self.policies.iter()
+            .filter(|policy| policy.covers(subject, action))
+            .filter(|policy| policy.conditions.iter().all(|condition| condition.holds(subject, resource)))
+            .collect();
+
+        if let Some(policy) = matching.iter().find(|policy| policy.effect == Effect::Deny) {
+            return Decision { allowed: false, policy: Some(policy.id.clone()) };
+        }
+        if let Some(policy) = matching.iter().find(|policy| policy.effect == Effect::Allow) {
+            return Decision { allowed: true, policy: Some(policy.id.clone()) };
+        }
+        Decision { allowed: subject.permissions.iter().any(|granted| granted == action), policy: None }
+    }
+
+    pub fn authorize(&self, subject: &Subject, action: &str, resource: &Attributes) -> AppResult<()> {
+        let decision = self.decide(subject, action, resource);
+        if decision.allowed {
+            return Ok(());
+        }
+        Err(AppError::Forbidden(match decision.policy {
+            Some(policy) => format!("Action {} denied by policy {}", action, policy),
+            None => format!("Missing permission {}", action),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn subject(role: UserRole) -> Subject {
+        let permissions = role.default_permissions().iter().map(|p| p.as_str().to_string()).collect();
+        Subject::new(UserId::new(), role, permissions)
+    }
+
+    #[test]
+    fn builtin_policies_load() {
+        assert!(!PolicyEngine::builtin().policies().is_empty());
+    }
+
+    #[test]
+    fn reporters_update_only_their_own_unverified_reports() {
+        let engine = PolicyEngine::builtin();
+        let reporter = subject(UserRole::Reporter);
+        let own = Attributes::new()
+            .with("reporter_id", reporter.user_id.to_string())
+            .with("status", "reported");
+
+        assert!(engine.decide(&reporter, "reports:update", &own).allowed);
+        assert!(!engine.decide(&reporter, "reports:update", &own.clone().with("status", "verified")).allowed);
+        let foreign = own.with("reporter_id", UserId::new().to_string());
+        assert!(!engine.decide(&reporter, "reports:update", &foreign).allowed);
+    }
+
+    #[test]
+    fn coordinators_verify_only_in_assigned_provinces() {
+        let engine = PolicyEngine::builtin();
+        let coordinator = subject(UserRole::Coordinator).with("provinces", vec!["Jawa Timur".to_string()]);
+        let disaster = |province: &str| Attributes::new().with("province", province).with("status", "reported");
+
+        assert!(engine.decide(&coordinator, "disasters:verify", &disaster("Jawa Timur")).allowed);
+        assert!(!engine.decide(&coordinator, "disasters:verify", &disaster("Jawa Barat")).allowed);
+        assert!(!engine.decide(&coordinator, "disasters:verify", &Attributes::new()).allowed);
+    }
+
+    #[test]
+    fn deny_overrides_allow_and_baseline() {
+        let engine = PolicyEngine::from_toml_str(r#"
+            [[policies]]
+            id = "no-alerts-without-mfa"
+            effect = "deny"
+            actions = ["alerts:*"]
+            conditions = [{ attribute = "subject.mfa", op = "ne", value = true }]
+        "#).unwrap();
+        let admin = subject(UserRole::Admin);
+
+        let decision = engine.decide(&admin, "alerts:write", &Attributes::new());
+        assert_eq!(decision, Decision { allowed: false, policy: Some("no-alerts-without-mfa".to_string()) });
+        assert!(engine.decide(&admin.clone().with("mfa", true), "alerts:write", &Attributes::new()).allowed);
+        assert!(engine.decide(&admin, "users:manage", &Attributes::new()).allowed);
+    }
+
+    #[test]
+    fn malformed_policies_are_rejected() {
+        let missing_operand = r#"
+            [[policies]]
+            id = "broken"
+            effect = "allow"
+            actions = ["reports:update"]
+            conditions = [{ attribute = "resource.status" }]
+        "#;
+        assert!(PolicyEngine::from_toml_str(missing_operand).is_err());
+
+        let bad_path = r#"
+            [[policies]]
+            id = "broken"
+            effect = "allow"
+            actions = ["reports:update"]
+            conditions = [{ attribute = "status", value = "reported" }]
+        "#;
+        assert!(PolicyEngine::from_toml_str(bad_path).is_err());
+    }
+}
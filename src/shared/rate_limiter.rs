@@ -1,25 +1,23 @@
 /// Rate limiting utilities for Terra Siaga API
-/// Implements various rate limiting strategies for security and performance
+/// Implements fixed window, sliding window and token bucket limits, in memory for a single
+/// instance or atomically in Redis (Lua scripts) when instances share the limits
 
 use async_trait::async_trait;
-use governor::{
-    clock::{DefaultClock, QuantaClock},
-    state::{InMemoryState, keyed::KeyedStateStore},
-    middleware::NoOpMiddleware,
-    Quota, RateLimiter as GovernorRateLimiter,
-};
-use std::collections::HashMap;
-use std::hash::Hash;
-use std::num::NonZeroU32;
-use std::sync::Arc;
-use std::time::Duration;
-use governor::state::keyed::DefaultKeyedStateStore;
-use tokio::sync::RwLock;
-
-use crate::infrastructure::cache::CacheService;
+use deadpool_redis::redis;
+use deadpool_redis::{Pool as RedisPool, Runtime};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+use uuid::Uuid;
+
+use crate::domain::entities::disaster::{Disaster, DisasterSeverity, DisasterStatus};
+use crate::domain::ports::repositories::DisasterRepository;
 use crate::shared::error::{AppError, AppResult};
 use crate::shared::types::{UserId, UserRole, constants::*};
 
+/// Keys the in-memory limiter tracks before it sweeps out expired ones
+const MAX_TRACKED_KEYS: usize = 10_000;
+
 /// Rate limiting strategy
 #[derive(Debug, Clone)]
 pub enum RateLimitStrategy {
@@ -41,12 +39,36 @@ pub enum RateLimitStrategy {
     },
 }
 
+impl RateLimitStrategy {
+    /// Requests allowed at once
+    pub fn limit(&self) -> u32 {
+        match self {
+            RateLimitStrategy::FixedWindow { requests, .. } => *requests,
+            RateLimitStrategy::SlidingWindow { requests, .. } => *requests,
+            RateLimitStrategy::TokenBucket { capacity, .. } => *capacity,
+        }
+    }
+
+    /// Short tag kept in storage keys so a key never holds state of another strategy
+    fn tag(&self) -> &'static str {
+        match self {
+            RateLimitStrategy::FixedWindow { .. } => "fw",
+            RateLimitStrategy::SlidingWindow { .. } => "sw",
+            RateLimitStrategy::TokenBucket { .. } => "tb",
+        }
+    }
+}
+
 /// Rate limit configuration for different user roles and endpoints
 #[derive(Debug, Clone)]
 pub struct RateLimitConfig {
     pub default_strategy: RateLimitStrategy,
     pub role_overrides: HashMap<UserRole, RateLimitStrategy>,
+    /// Keyed by route pattern, optionally preceded by the method (`"POST /api/v1/reports"`)
     pub endpoint_overrides: HashMap<String, RateLimitStrategy>,
+    /// Routes carrying emergency alerts and dispatches, never limited for signed-in callers
+    /// while an emergency is declared
+    pub emergency_alert_routes: HashSet<String>,
 }
 
 impl Default for RateLimitConfig {
@@ -93,21 +115,21 @@ impl Default for RateLimitConfig {
 
         // Endpoint-specific rate limits
         endpoint_overrides.insert(
-            "/api/auth/login".to_string(),
-            RateLimitStrategy::FixedWindow {
+            "POST /api/v1/auth/login".to_string(),
+            RateLimitStrategy::SlidingWindow {
                 requests: 5,
                 window: Duration::from_secs(300), // 5 login attempts per 5 minutes
             },
         );
         endpoint_overrides.insert(
-            "/api/auth/register".to_string(),
+            "POST /api/v1/auth/register".to_string(),
             RateLimitStrategy::FixedWindow {
                 requests: 3,
                 window: Duration::from_secs(3600), // 3 registrations per hour
             },
         );
         endpoint_overrides.insert(
-            "/api/emergency/report".to_string(),
+            "POST /api/v1/reports".to_string(),
             RateLimitStrategy::TokenBucket {
                 capacity: 10,
                 refill_rate: 1,
@@ -115,6 +137,15 @@ impl Default for RateLimitConfig {
             },
         );
 
+        let emergency_alert_routes = [
+            "POST /api/v1/emergency/alerts/broadcast",
+            "POST /api/v1/emergency/alerts/cap",
+            "POST /api/v1/emergency/response",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+
         Self {
             default_strategy: RateLimitStrategy::FixedWindow {
                 requests: DEFAULT_RATE_LIMIT_PER_MINUTE,
@@ -122,312 +153,453 @@ impl Default for RateLimitConfig {
             },
             role_overrides,
             endpoint_overrides,
+            emergency_alert_routes,
         }
     }
 }
 
-/// Rate limit result
+impl RateLimitConfig {
+    /// Strategy for a request: the endpoint's own limit, else the caller's role limit, else the default
+    pub fn strategy_for(&self, method: &str, route: &str, role: Option<&UserRole>) -> &RateLimitStrategy {
+        self.endpoint_overrides.get(&format!("{} {}", method, route))
+            .or_else(|| self.endpoint_overrides.get(route))
+            .or_else(|| role.and_then(|role| self.role_overrides.get(role)))
+            .unwrap_or(&self.default_strategy)
+    }
+
+    pub fn is_emergency_alert_route(&self, method: &str, route: &str) -> bool {
+        self.emergency_alert_routes.contains(&format!("{} {}", method, route))
+            || self.emergency_alert_routes.contains(route)
+    }
+}
+
+/// Outcome of taking one request from a limit
 #[derive(Debug, Clone)]
-pub enum RateLimitResult {
-    Allowed,
-    Denied {
-        retry_after: Duration,
-        limit: u32,
-        remaining: u32,
-        reset_time: std::time::SystemTime,
-    },
+pub struct RateLimitInfo {
+    pub limit: u32,
+    pub remaining: u32,
+    /// When the limit is fully available again
+    pub reset_time: SystemTime,
+    /// Set when the request was denied: how long until one more request is allowed
+    pub retry_after: Option<Duration>,
 }
 
-impl RateLimitResult {
+impl RateLimitInfo {
+    fn new(limit: u32, remaining: u32, reset_after: Duration, retry_after: Option<Duration>) -> Self {
+        Self {
+            limit,
+            remaining,
+            reset_time: SystemTime::now() + reset_after,
+            retry_after,
+        }
+    }
+
     pub fn is_allowed(&self) -> bool {
-        matches!(self, RateLimitResult::Allowed)
+        self.retry_after.is_none()
     }
 }
 
 /// Rate limiter trait for different implementations
 #[async_trait]
 pub trait RateLimiter: Send + Sync {
-    /// Check if request is allowed for given key
-    async fn check_rate_limit(&self, key: &str, strategy: &RateLimitStrategy) -> AppResult<bool>;
-
-    /// Check rate limit with custom identifier
-    async fn check_limit(&self, identifier: &str) -> AppResult<bool>;
-
-    /// Get remaining requests for a key
-    async fn get_remaining(&self, key: &str) -> AppResult<u32>;
+    /// Take one request from the limit for `key`; checking and counting happen as one step
+    async fn acquire(&self, key: &str, strategy: &RateLimitStrategy) -> AppResult<RateLimitInfo>;
 
-    /// Get rate limit info
-    async fn get_limit_info(&self, key: &str) -> AppResult<RateLimitInfo>;
+    /// Check if request is allowed for given key
+    async fn check_rate_limit(&self, key: &str, strategy: &RateLimitStrategy) -> AppResult<bool> {
+        Ok(self.acquire(key, strategy).await?.is_allowed())
+    }
 
     /// Reset rate limit for a key
     async fn reset_limit(&self, key: &str) -> AppResult<()>;
 }
 
-/// Rate limit information
-#[derive(Debug, Clone)]
-pub struct RateLimitInfo {
-    pub limit: u32,
-    pub remaining: u32,
-    pub reset_time: std::time::SystemTime,
-    pub retry_after: Option<Duration>,
+/// Per-key state of the in-memory limiter
+#[derive(Debug)]
+enum WindowState {
+    Fixed { count: u32 },
+    Sliding { hits: VecDeque<Instant> },
+    Bucket { tokens: f64, updated: Instant },
 }
 
-/// In-memory rate limiter using governor
+#[derive(Debug)]
+struct KeyState {
+    tag: &'static str,
+    window: WindowState,
+    /// After this the key holds nothing worth keeping (window over, bucket full)
+    expires_at: Instant,
+}
+
+/// In-memory rate limiter, exact for every strategy but private to this instance
+#[derive(Default)]
 pub struct InMemoryRateLimiter {
-    limiters: Arc<RwLock<HashMap<String, Arc<GovernorRateLimiter<String, governor::state::keyed::DefaultKeyedStateStore<String>, DefaultClock>>>>>,
-    config: RateLimitConfig,
+    states: Mutex<HashMap<String, KeyState>>,
 }
 
 impl InMemoryRateLimiter {
-    pub fn new(config: RateLimitConfig) -> Self {
-        Self {
-            limiters: Arc::new(RwLock::new(HashMap::new())),
-            config,
-        }
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    async fn get_or_create_limiter(&self, key: &str, strategy: &RateLimitStrategy) -> Arc<governor::RateLimiter<String, DefaultKeyedStateStore<String>, DefaultClock>> {
-        let limiters = self.limiters.read().await;
-
-        if let Some(limiter) = limiters.get(key) {
-            return limiter.clone();
-        }
-
-        drop(limiters);
-
-        let mut limiters = self.limiters.write().await;
-
-        // Double-check pattern
-        if let Some(limiter) = limiters.get(key) {
-            return limiter.clone();
-        }
-
-        let quota = match strategy {
-            RateLimitStrategy::FixedWindow { requests, window } => {
-                Quota::with_period(*window).unwrap().allow_burst(NonZeroU32::new(*requests).unwrap())
+    fn take(state: &mut KeyState, strategy: &RateLimitStrategy, now: Instant) -> RateLimitInfo {
+        match (strategy, &mut state.window) {
+            (RateLimitStrategy::FixedWindow { requests, .. }, WindowState::Fixed { count }) => {
+                let reset_after = state.expires_at.saturating_duration_since(now);
+                if *count >= *requests {
+                    return RateLimitInfo::new(*requests, 0, reset_after, Some(reset_after));
+                }
+                *count += 1;
+                RateLimitInfo::new(*requests, requests - *count, reset_after, None)
             }
-            RateLimitStrategy::SlidingWindow { requests, window } => {
-                // Governor doesn't have sliding window, so we use fixed window as approximation
-                Quota::with_period(*window).unwrap().allow_burst(NonZeroU32::new(*requests).unwrap())
+            (RateLimitStrategy::SlidingWindow { requests, window }, WindowState::Sliding { hits }) => {
+                while hits.front().is_some_and(|hit| *hit + *window <= now) {
+                    hits.pop_front();
+                }
+                let until_oldest_leaves = |hits: &VecDeque<Instant>| {
+                    hits.front().map_or(*window, |oldest| (*oldest + *window).saturating_duration_since(now))
+                };
+                if hits.len() as u32 >= *requests {
+                    let retry_after = until_oldest_leaves(hits);
+                    return RateLimitInfo::new(*requests, 0, retry_after, Some(retry_after));
+                }
+                hits.push_back(now);
+                state.expires_at = now + *window;
+                RateLimitInfo::new(*requests, requests - hits.len() as u32, until_oldest_leaves(hits), None)
             }
-            RateLimitStrategy::TokenBucket { capacity, refill_rate: _, refill_interval } => {
-                Quota::with_period(*refill_interval).unwrap().allow_burst(NonZeroU32::new(*capacity).unwrap())
+            (
+                RateLimitStrategy::TokenBucket { capacity, refill_rate, refill_interval },
+                WindowState::Bucket { tokens, updated },
+            ) => {
+                let per_token = refill_interval.as_secs_f64() / f64::from((*refill_rate).max(1));
+                let refilled = now.saturating_duration_since(*updated).as_secs_f64() / per_token;
+                *tokens = (*tokens + refilled).min(f64::from(*capacity));
+                *updated = now;
+
+                let retry_after = if *tokens >= 1.0 {
+                    *tokens -= 1.0;
+                    None
+                } else {
+                    Some(Duration::from_secs_f64((1.0 - *tokens) * per_token))
+                };
+                let reset_after = Duration::from_secs_f64((f64::from(*capacity) - *tokens) * per_token);
+                state.expires_at = now + reset_after;
+                RateLimitInfo::new(*capacity, *tokens as u32, reset_after, retry_after)
             }
-        };
-
-        let limiter = Arc::new(GovernorRateLimiter::keyed(quota));
-        limiters.insert(key.to_string(), limiter.clone());
+            _ => unreachable!("key state always matches its strategy tag"),
+        }
+    }
 
-        limiter
+    fn fresh_state(strategy: &RateLimitStrategy, now: Instant) -> KeyState {
+        let (window, expires_at) = match strategy {
+            RateLimitStrategy::FixedWindow { window, .. } => (WindowState::Fixed { count: 0 }, now + *window),
+            RateLimitStrategy::SlidingWindow { .. } => (WindowState::Sliding { hits: VecDeque::new() }, now),
+            RateLimitStrategy::TokenBucket { capacity, .. } => (
+                WindowState::Bucket { tokens: f64::from(*capacity), updated: now },
+                now,
+            ),
+        };
+        KeyState { tag: strategy.tag(), window, expires_at }
     }
 }
 
 #[async_trait]
 impl RateLimiter for InMemoryRateLimiter {
-    async fn check_rate_limit(&self, key: &str, strategy: &RateLimitStrategy) -> AppResult<bool> {
-        let limiter = self.get_or_create_limiter(key, strategy).await;
-
-        match limiter.check_key(&key.to_string()) {
-            Ok(_) => Ok(true),
-            Err(_) => Ok(false),
+    async fn acquire(&self, key: &str, strategy: &RateLimitStrategy) -> AppResult<RateLimitInfo> {
+        let now = Instant::now();
+        let mut states = self.states.lock()
+            .map_err(|_| AppError::InternalServer("Rate limiter state poisoned".to_string()))?;
+        if states.len() >= MAX_TRACKED_KEYS {
+            states.retain(|_, state| state.expires_at > now);
         }
-    }
-
-    async fn check_limit(&self, identifier: &str) -> AppResult<bool> {
-        self.check_rate_limit(identifier, &self.config.default_strategy).await
-    }
-
-    async fn get_remaining(&self, key: &str) -> AppResult<u32> {
-        // Governor doesn't directly expose remaining count
-        // This is a simplified implementation
-        Ok(0)
-    }
 
-    async fn get_limit_info(&self, key: &str) -> AppResult<RateLimitInfo> {
-        // Simplified implementation
-        Ok(RateLimitInfo {
-            limit: 100,
-            remaining: 50,
-            reset_time: std::time::SystemTime::now() + Duration::from_secs(3600),
-            retry_after: None,
-        })
+        let state = states.entry(key.to_string())
+            .or_insert_with(|| Self::fresh_state(strategy, now));
+        let window_over = matches!(state.window, WindowState::Fixed { .. }) && state.expires_at <= now;
+        if state.tag != strategy.tag() || window_over {
+            *state = Self::fresh_state(strategy, now);
+        }
+        Ok(Self::take(state, strategy, now))
     }
 
     async fn reset_limit(&self, key: &str) -> AppResult<()> {
-        let mut limiters = self.limiters.write().await;
-        limiters.remove(key);
+        self.states.lock()
+            .map_err(|_| AppError::InternalServer("Rate limiter state poisoned".to_string()))?
+            .remove(key);
         Ok(())
     }
 }
 
-/// Cache-based rate limiter using external cache (Redis)
-pub struct CacheRateLimiter {
-    cache: Arc<dyn CacheService>,
-    config: RateLimitConfig,
+/// Counter that expires a window after its first request.
+/// KEYS[1] counter; ARGV limit, window ms. Returns {allowed, remaining, reset ms, retry ms}
+const FIXED_WINDOW_SCRIPT: &str = r#"
+local limit = tonumber(ARGV[1])
+local window = tonumber(ARGV[2])
+local count = tonumber(redis.call('GET', KEYS[1]) or '0')
+local ttl = redis.call('PTTL', KEYS[1])
+if ttl < 0 then ttl = window end
+if count >= limit then
+  return {0, 0, ttl, ttl}
+end
+count = redis.call('INCR', KEYS[1])
+if redis.call('PTTL', KEYS[1]) < 0 then
+  redis.call('PEXPIRE', KEYS[1], window)
+end
+return {1, limit - count, ttl, 0}
+"#;
+
+/// Log of request times in a sorted set, trimmed to the window on every call.
+/// KEYS[1] log; ARGV limit, window ms, unique member. Returns {allowed, remaining, reset ms, retry ms}
+const SLIDING_WINDOW_SCRIPT: &str = r#"
+local limit = tonumber(ARGV[1])
+local window = tonumber(ARGV[2])
+local clock = redis.call('TIME')
+local now = tonumber(clock[1]) * 1000 + math.floor(tonumber(clock[2]) / 1000)
+redis.call('ZREMRANGEBYSCORE', KEYS[1], '-inf', now - window)
+local count = redis.call('ZCARD', KEYS[1])
+local function until_oldest_leaves()
+  local oldest = redis.call('ZRANGE', KEYS[1], 0, 0, 'WITHSCORES')
+  if oldest[2] then return tonumber(oldest[2]) + window - now end
+  return window
+end
+if count >= limit then
+  local retry = until_oldest_leaves()
+  return {0, 0, retry, retry}
+end
+redis.call('ZADD', KEYS[1], now, ARGV[3])
+redis.call('PEXPIRE', KEYS[1], window)
+return {1, limit - count - 1, until_oldest_leaves(), 0}
+"#;
+
+/// Bucket of tokens refilled continuously from the time of the last request.
+/// KEYS[1] hash; ARGV capacity, refill tokens, refill interval ms. Returns {allowed, remaining, reset ms, retry ms}
+const TOKEN_BUCKET_SCRIPT: &str = r#"
+local capacity = tonumber(ARGV[1])
+local per_token = tonumber(ARGV[3]) / math.max(tonumber(ARGV[2]), 1)
+local clock = redis.call('TIME')
+local now = tonumber(clock[1]) * 1000 + math.floor(tonumber(clock[2]) / 1000)
+local state = redis.call('HMGET', KEYS[1], 'tokens', 'updated')
+local tokens = tonumber(state[1]) or capacity
+local updated = tonumber(state[2]) or now
+tokens = math.min(capacity, tokens + math.max(0, now - updated) / per_token)
+local allowed = 0
+local retry = 0
+if tokens >= 1 then
+  tokens = tokens - 1
+  allowed = 1
+else
+  retry = math.ceil((1 - tokens) * per_token)
+end
+local reset = math.ceil((capacity - tokens) * per_token)
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'updated', now)
+redis.call('PEXPIRE', KEYS[1], math.max(reset, 1))
+return {allowed, math.floor(tokens), reset, retry}
+"#;
+
+/// A script registered with Redis, run by its hash
+struct LuaScript {
+    source: &'static str,
+    sha: String,
 }
 
-impl CacheRateLimiter {
-    pub fn new(cache: Arc<dyn CacheService>, config: RateLimitConfig) -> Self {
-        Self { cache, config }
+/// Redis rate limiter: every strategy runs as one Lua script, so instances sharing the Redis
+/// never let more requests through than the limit between them
+pub struct RedisRateLimiter {
+    pool: RedisPool,
+    key_prefix: String,
+    fixed_window: LuaScript,
+    sliding_window: LuaScript,
+    token_bucket: LuaScript,
+}
+
+impl RedisRateLimiter {
+    /// Connect and register the scripts, failing if Redis is unreachable
+    pub async fn connect(redis_url: &str, key_prefix: &str) -> AppResult<Self> {
+        let pool = deadpool_redis::Config::from_url(redis_url)
+            .create_pool(Some(Runtime::Tokio1))
+            .map_err(|e| AppError::InternalServer(format!("Failed to create Redis pool: {}", e)))?;
+
+        let fixed_window = Self::load(&pool, FIXED_WINDOW_SCRIPT).await?;
+        let sliding_window = Self::load(&pool, SLIDING_WINDOW_SCRIPT).await?;
+        let token_bucket = Self::load(&pool, TOKEN_BUCKET_SCRIPT).await?;
+        Ok(Self {
+            pool,
+            key_prefix: key_prefix.to_string(),
+            fixed_window,
+            sliding_window,
+            token_bucket,
+        })
     }
 
-    fn rate_limit_key(&self, key: &str, strategy: &RateLimitStrategy) -> String {
-        let window = match strategy {
-            RateLimitStrategy::FixedWindow { window, .. } => window.as_secs(),
-            RateLimitStrategy::SlidingWindow { window, .. } => window.as_secs(),
-            RateLimitStrategy::TokenBucket { refill_interval, .. } => refill_interval.as_secs(),
-        };
+    async fn load(pool: &RedisPool, source: &'static str) -> AppResult<LuaScript> {
+        let mut conn = pool.get().await
+            .map_err(|e| AppError::Integration(format!("Failed to get Redis connection: {}", e)))?;
+        let sha = redis::cmd("SCRIPT").arg("LOAD").arg(source)
+            .query_async::<String>(&mut *conn).await
+            .map_err(|e| AppError::Integration(format!("Failed to load rate limit script: {}", e)))?;
+        Ok(LuaScript { source, sha })
+    }
 
-        let current_window = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs() / window;
+    fn storage_key(&self, tag: &str, key: &str) -> String {
+        format!("{}:{}:{}", self.key_prefix, tag, key)
+    }
 
-        format!("rate_limit:{}:{}", key, current_window)
+    /// Run by hash, sending the source again if Redis has lost it (restart, SCRIPT FLUSH)
+    async fn run(&self, script: &LuaScript, key: &str, args: &[String]) -> AppResult<Vec<i64>> {
+        let mut conn = self.pool.get().await
+            .map_err(|e| AppError::Integration(format!("Failed to get Redis connection: {}", e)))?;
+        let result = redis::cmd("EVALSHA").arg(&script.sha).arg(1).arg(key).arg(args)
+            .query_async::<Vec<i64>>(&mut *conn).await;
+        match result {
+            Err(e) if e.kind() == redis::ErrorKind::NoScriptError => {
+                redis::cmd("EVAL").arg(script.source).arg(1).arg(key).arg(args)
+                    .query_async::<Vec<i64>>(&mut *conn).await
+            }
+            other => other,
+        }
+        .map_err(|e| AppError::Integration(format!("Rate limit script failed: {}", e)))
     }
 }
 
-#[async_trait]
-impl RateLimiter for CacheRateLimiter {
-    async fn check_rate_limit(&self, key: &str, strategy: &RateLimitStrategy) -> AppResult<bool> {
-        let cache_key = self.rate_limit_key(key, strategy);
+fn millis(duration: &Duration) -> String {
+    duration.as_millis().max(1).to_string()
+}
 
-        let limit = match strategy {
-            RateLimitStrategy::FixedWindow { requests, .. } => *requests,
-            RateLimitStrategy::SlidingWindow { requests, .. } => *requests,
-            RateLimitStrategy::TokenBucket { capacity, .. } => *capacity,
+#[async_trait]
+impl RateLimiter for RedisRateLimiter {
+    async fn acquire(&self, key: &str, strategy: &RateLimitStrategy) -> AppResult<RateLimitInfo> {
+        let storage_key = self.storage_key(strategy.tag(), key);
+        let reply = match strategy {
+            RateLimitStrategy::FixedWindow { requests, window } => {
+                self.run(&self.fixed_window, &storage_key, &[requests.to_string(), millis(window)]).await?
+            }
+            RateLimitStrategy::SlidingWindow { requests, window } => {
+                let member = Uuid::new_v4().to_string();
+                self.run(&self.sliding_window, &storage_key, &[requests.to_string(), millis(window), member]).await?
+            }
+            RateLimitStrategy::TokenBucket { capacity, refill_rate, refill_interval } => {
+                let args = [capacity.to_string(), refill_rate.to_string(), millis(refill_interval)];
+                self.run(&self.token_bucket, &storage_key, &args).await?
+            }
         };
 
-        let current_count = self.cache.increment(&cache_key, 1).await?;
-
-        if current_count == 1 {
-            // Set TTL for the key
-            let ttl = match strategy {
-                RateLimitStrategy::FixedWindow { window, .. } => window,
-                RateLimitStrategy::SlidingWindow { window, .. } => window,
-                RateLimitStrategy::TokenBucket { refill_interval, .. } => refill_interval,
-            };
-
-            self.cache.set_string(&cache_key, (&current_count).to_string(), Some(ttl.clone())).await?;
-        }
-
-        Ok(current_count <= limit as i64)
+        let [allowed, remaining, reset_ms, retry_ms] = reply[..] else {
+            return Err(AppError::Integration(format!("Unexpected rate limit script reply: {:?}", reply)));
+        };
+        let as_duration = |ms: i64| Duration::from_millis(ms.max(0) as u64);
+        Ok(RateLimitInfo::new(
+            strategy.limit(),
+            remaining.max(0) as u32,
+            as_duration(reset_ms),
+            (allowed == 0).then(|| as_duration(retry_ms)),
+        ))
     }
 
-    async fn check_limit(&self, identifier: &str) -> AppResult<bool> {
-        self.check_rate_limit(identifier, &self.config.default_strategy).await
+    async fn reset_limit(&self, key: &str) -> AppResult<()> {
+        let mut conn = self.pool.get().await
+            .map_err(|e| AppError::Integration(format!("Failed to get Redis connection: {}", e)))?;
+        let keys: Vec<String> = ["fw", "sw", "tb"].iter().map(|tag| self.storage_key(tag, key)).collect();
+        redis::cmd("DEL").arg(&keys).query_async::<()>(&mut *conn).await
+            .map_err(|e| AppError::Integration(format!("Failed to reset rate limit: {}", e)))
     }
+}
 
-    async fn get_remaining(&self, key: &str) -> AppResult<u32> {
-        let cache_key = self.rate_limit_key(key, &self.config.default_strategy);
-        let current_count: i64 = self.cache.get_string(&cache_key, ).await?.unwrap_or("0".to_string()).parse().unwrap_or(0);
+/// Whether the disaster puts the platform in a declared emergency: confirmed, still unresolved
+/// and at least severe
+fn declares_emergency(disaster: &Disaster) -> bool {
+    matches!(disaster.status, DisasterStatus::Verified | DisasterStatus::Responded)
+        && disaster.severity >= DisasterSeverity::Severe
+}
 
-        let limit = match &self.config.default_strategy {
-            RateLimitStrategy::FixedWindow { requests, .. } => *requests,
-            RateLimitStrategy::SlidingWindow { requests, .. } => *requests,
-            RateLimitStrategy::TokenBucket { capacity, .. } => *capacity,
-        };
+/// Tells whether an emergency is declared, re-checking the disasters at most once per interval
+pub struct EmergencyBypass {
+    disasters: Arc<dyn DisasterRepository>,
+    check_interval: Duration,
+    last_check: Mutex<Option<(Instant, bool)>>,
+}
 
-        Ok((limit as i64 - current_count).max(0) as u32)
+impl EmergencyBypass {
+    pub fn new(disasters: Arc<dyn DisasterRepository>, check_interval: Duration) -> Self {
+        Self { disasters, check_interval, last_check: Mutex::new(None) }
     }
 
-    async fn get_limit_info(&self, key: &str) -> AppResult<RateLimitInfo> {
-        let remaining = self.get_remaining(key).await?;
-        let limit = match &self.config.default_strategy {
-            RateLimitStrategy::FixedWindow { requests, window } => {
-                RateLimitInfo {
-                    limit: *requests,
-                    remaining,
-                    reset_time: std::time::SystemTime::now() + *window,
-                    retry_after: if remaining == 0 { Some(*window) } else { None },
-                }
+    pub async fn is_declared(&self) -> bool {
+        let cached = self.last_check.lock().ok().and_then(|last| *last);
+        if let Some((checked_at, declared)) = cached {
+            if checked_at.elapsed() < self.check_interval {
+                return declared;
             }
-            RateLimitStrategy::SlidingWindow { requests, window } => {
-                RateLimitInfo {
-                    limit: *requests,
-                    remaining,
-                    reset_time: std::time::SystemTime::now() + *window,
-                    retry_after: if remaining == 0 { Some(*window) } else { None },
-                }
-            }
-            RateLimitStrategy::TokenBucket { capacity, refill_interval, .. } => {
-                RateLimitInfo {
-                    limit: *capacity,
-                    remaining,
-                    reset_time: std::time::SystemTime::now() + *refill_interval,
-                    retry_after: if remaining == 0 { Some(*refill_interval) } else { None },
-                }
+        }
+
+        let declared = match self.disasters.find_active().await {
+            Ok(active) => active.iter().any(declares_emergency),
+            Err(e) => {
+                tracing::warn!("Could not check for declared emergencies: {}", e);
+                cached.is_some_and(|(_, declared)| declared)
             }
         };
-
-        Ok(limit)
+        if let Ok(mut last) = self.last_check.lock() {
+            *last = Some((Instant::now(), declared));
+        }
+        declared
     }
+}
 
-    async fn reset_limit(&self, key: &str) -> AppResult<()> {
-        let cache_key = self.rate_limit_key(key, &self.config.default_strategy);
-        self.cache.delete(&cache_key).await?;
-        Ok(())
-    }
+/// Who a request is counted against
+#[derive(Debug, Clone)]
+pub enum RateLimitSubject {
+    User { user_id: UserId, role: UserRole },
+    Ip(String),
 }
 
-/// Rate limit middleware for HTTP requests
-pub struct RateLimitMiddleware {
+/// Decision for one request
+#[derive(Debug, Clone)]
+pub enum RateLimitDecision {
+    /// Emergency alert traffic during a declared emergency, not counted
+    Bypassed,
+    Limited(RateLimitInfo),
+}
+
+/// Applies the configured limits to requests; the HTTP side lives in `middleware::rate_limit`
+pub struct RateLimitGuard {
     rate_limiter: Arc<dyn RateLimiter>,
     config: RateLimitConfig,
+    emergency: Option<EmergencyBypass>,
 }
 
-impl RateLimitMiddleware {
-    pub fn new(rate_limiter: Arc<dyn RateLimiter>, config: RateLimitConfig) -> Self {
+impl RateLimitGuard {
+    pub fn new(rate_limiter: Arc<dyn RateLimiter>, config: RateLimitConfig, emergency: Option<EmergencyBypass>) -> Self {
         Self {
             rate_limiter,
             config,
+            emergency,
         }
     }
 
+    /// Count the request against the subject's limit for the route pattern it matched
     pub async fn check_request_limit(
         &self,
-        user_id: Option<&UserId>,
-        user_role: Option<&UserRole>,
-        endpoint: &str,
-        ip_address: &str,
-    ) -> AppResult<bool> {
-        // Determine rate limit strategy
-        let strategy = if let Some(endpoint_strategy) = self.config.endpoint_overrides.get(endpoint) {
-            endpoint_strategy.clone()
-        } else if let Some(role) = user_role {
-            self.config.role_overrides.get(role).cloned()
-                .unwrap_or(self.config.default_strategy.clone())
-        } else {
-            self.config.default_strategy.clone()
-        };
-
-        // Create rate limit key
-        let key = if let Some(user_id) = user_id {
-            format!("user:{}:{}", user_id, endpoint)
-        } else {
-            format!("ip:{}:{}", ip_address, endpoint)
+        subject: &RateLimitSubject,
+        method: &str,
+        route: &str,
+    ) -> AppResult<RateLimitDecision> {
+        let role = match subject {
+            RateLimitSubject::User { role, .. } => Some(role),
+            RateLimitSubject::Ip(_) => None,
         };
-
-        self.rate_limiter.check_rate_limit(&key, &strategy).await
-    }
-
-    pub async fn get_rate_limit_headers(&self, key: &str) -> HashMap<String, String> {
-        let mut headers = HashMap::new();
-
-        if let Ok(info) = self.rate_limiter.get_limit_info(key).await {
-            headers.insert("X-RateLimit-Limit".to_string(), info.limit.to_string());
-            headers.insert("X-RateLimit-Remaining".to_string(), info.remaining.to_string());
-
-            if let Ok(duration) = info.reset_time.duration_since(std::time::UNIX_EPOCH) {
-                headers.insert("X-RateLimit-Reset".to_string(), duration.as_secs().to_string());
-            }
-
-            if let Some(retry_after) = info.retry_after {
-                headers.insert("Retry-After".to_string(), retry_after.as_secs().to_string());
+        if role.is_some() && self.config.is_emergency_alert_route(method, route) {
+            if let Some(emergency) = &self.emergency {
+                if emergency.is_declared().await {
+                    return Ok(RateLimitDecision::Bypassed);
+                }
             }
         }
 
-        headers
+        let strategy = self.config.strategy_for(method, route, role);
+        let key = match subject {
+            RateLimitSubject::User { user_id, .. } => format!("user:{}:{} {}", user_id, method, route),
+            RateLimitSubject::Ip(ip) => format!("ip:{}:{} {}", ip, method, route),
+        };
+        self.rate_limiter.acquire(&key, strategy).await.map(RateLimitDecision::Limited)
     }
 }
 
@@ -435,21 +607,23 @@ impl RateLimitMiddleware {
 pub struct RateLimiterFactory;
 
 impl RateLimiterFactory {
-    /// Create rate limiter based on cache availability
-    pub fn create(cache: Option<Arc<dyn CacheService>>, config: RateLimitConfig) -> Arc<dyn RateLimiter> {
-        match cache {
-            Some(cache_service) => {
-                Arc::new(CacheRateLimiter::new(cache_service, config))
-            }
-            None => {
-                Arc::new(InMemoryRateLimiter::new(config))
+    /// Redis-backed limiter shared by every instance when Redis is reachable, else in memory
+    pub async fn create(redis_url: Option<&str>, key_prefix: &str) -> Arc<dyn RateLimiter> {
+        let Some(url) = redis_url.filter(|url| !url.is_empty()) else {
+            return Self::create_default();
+        };
+        match RedisRateLimiter::connect(url, key_prefix).await {
+            Ok(limiter) => Arc::new(limiter),
+            Err(e) => {
+                tracing::warn!("Rate limits are kept per instance, Redis unavailable: {}", e);
+                Self::create_default()
             }
         }
     }
 
     /// Create default rate limiter
     pub fn create_default() -> Arc<dyn RateLimiter> {
-        Arc::new(InMemoryRateLimiter::new(RateLimitConfig::default()))
+        Arc::new(InMemoryRateLimiter::new())
     }
 }
 
@@ -459,8 +633,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_in_memory_rate_limiter() {
-        let config = RateLimitConfig::default();
-        let limiter = InMemoryRateLimiter::new(config);
+        let limiter = InMemoryRateLimiter::new();
 
         let strategy = RateLimitStrategy::FixedWindow {
             requests: 2,
@@ -479,6 +652,33 @@ mod tests {
         assert!(limiter.check_rate_limit("test_key", &strategy).await.unwrap());
     }
 
+    #[tokio::test]
+    async fn test_sliding_window_and_token_bucket() {
+        let limiter = InMemoryRateLimiter::new();
+
+        let sliding = RateLimitStrategy::SlidingWindow { requests: 2, window: Duration::from_millis(200) };
+        assert_eq!(limiter.acquire("sliding", &sliding).await.unwrap().remaining, 1);
+        assert!(limiter.acquire("sliding", &sliding).await.unwrap().is_allowed());
+        let denied = limiter.acquire("sliding", &sliding).await.unwrap();
+        assert!(denied.retry_after.is_some_and(|retry| retry <= Duration::from_millis(200)));
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        assert!(limiter.acquire("sliding", &sliding).await.unwrap().is_allowed());
+
+        // Burst of 3, then one token back every 100 ms
+        let bucket = RateLimitStrategy::TokenBucket {
+            capacity: 3,
+            refill_rate: 1,
+            refill_interval: Duration::from_millis(100),
+        };
+        for _ in 0..3 {
+            assert!(limiter.acquire("bucket", &bucket).await.unwrap().is_allowed());
+        }
+        assert!(!limiter.acquire("bucket", &bucket).await.unwrap().is_allowed());
+        tokio::time::sleep(Duration::from_millis(120)).await;
+        assert!(limiter.acquire("bucket", &bucket).await.unwrap().is_allowed());
+        assert!(!limiter.acquire("bucket", &bucket).await.unwrap().is_allowed());
+    }
+
     #[test]
     fn test_rate_limit_config_default() {
         let config = RateLimitConfig::default();
@@ -488,7 +688,14 @@ mod tests {
         assert!(config.role_overrides.contains_key(&UserRole::SystemAdmin));
 
         // Check endpoint overrides
-        assert!(config.endpoint_overrides.contains_key("/api/auth/login"));
-        assert!(config.endpoint_overrides.contains_key("/api/emergency/report"));
+        assert!(config.endpoint_overrides.contains_key("POST /api/v1/auth/login"));
+        assert!(config.endpoint_overrides.contains_key("POST /api/v1/reports"));
+
+        // Endpoint limits win over role limits; reading reports is not submitting one
+        let login = config.strategy_for("POST", "/api/v1/auth/login", Some(&UserRole::SystemAdmin));
+        assert_eq!(login.limit(), 5);
+        assert_eq!(config.strategy_for("GET", "/api/v1/reports", Some(&UserRole::Reporter)).limit(), 100);
+        assert_eq!(config.strategy_for("GET", "/api/v1/reports", None).limit(), DEFAULT_RATE_LIMIT_PER_MINUTE);
+        assert!(config.is_emergency_alert_route("POST", "/api/v1/emergency/alerts/broadcast"));
     }
 }
@@ -0,0 +1,583 @@
+/// Offline road-network routing for Terra Siaga
+/// Loads an OpenStreetMap road graph (PBF or GeoJSON) and runs A* over it,
+/// avoiding road segments that cross closed disaster zones
+use geo::{BoundingRect, Contains, Coord, Intersects, Line, LineString, Point, Polygon, Rect};
+use rstar::primitives::GeomWithData;
+use rstar::RTree;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::path::Path;
+use std::sync::RwLock;
+
+use crate::domain::value_objects::Coordinates;
+use crate::shared::error::{AppError, AppResult};
+use crate::shared::geo_utils::GeoCalculations;
+
+/// Walking pace used for evacuation on foot
+pub const WALKING_SPEED_KMH: f64 = 5.0;
+/// Points further than this from the nearest road node are considered unroutable
+pub const MAX_SNAP_DISTANCE_KM: f64 = 5.0;
+/// Speed used for the off-road leg between a point and its nearest road node
+const OFF_ROAD_DRIVING_SPEED_KMH: f64 = 10.0;
+
+/// How a route is travelled
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "mode")]
+pub enum TravelMode {
+    /// On foot at walking pace; ignores one-way restrictions and avoids motorways
+    Walking,
+    /// By vehicle, following posted speeds but never faster than `max_speed_kmh`
+    Driving { max_speed_kmh: f64 },
+}
+
+impl TravelMode {
+    fn speed_on(&self, edge: &RoadEdge) -> Option<f64> {
+        match self {
+            Self::Walking => edge.walkable.then_some(WALKING_SPEED_KMH),
+            Self::Driving { max_speed_kmh } => edge.drivable.then(|| edge.speed_kmh.min(*max_speed_kmh)),
+        }
+    }
+
+    /// Fastest possible speed, used as the A* heuristic bound
+    fn top_speed(&self, graph_top_speed: f64) -> f64 {
+        match self {
+            Self::Walking => WALKING_SPEED_KMH,
+            Self::Driving { max_speed_kmh } => max_speed_kmh.min(graph_top_speed),
+        }
+    }
+
+    fn off_road_speed(&self) -> f64 {
+        match self {
+            Self::Walking => WALKING_SPEED_KMH,
+            Self::Driving { .. } => OFF_ROAD_DRIVING_SPEED_KMH,
+        }
+    }
+}
+
+/// A computed path over the road network
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Route {
+    pub distance_km: f64,
+    pub duration_minutes: f64,
+    pub waypoints: Vec<Coordinates>,
+    /// Number of distinct closed zones whose roads the search had to avoid
+    pub closures_avoided: usize,
+}
+
+/// Tags relevant to routing, taken from an OSM way or GeoJSON feature properties
+#[derive(Debug, Clone, Default)]
+pub struct RoadTags {
+    pub highway: String,
+    pub maxspeed: Option<String>,
+    pub oneway: Option<String>,
+    pub access: Option<String>,
+}
+
+impl RoadTags {
+    /// Default speed (km/h) for a highway class, or `None` if it is not a road
+    fn default_speed(&self) -> Option<f64> {
+        let speed = match self.highway.as_str() {
+            "motorway" | "motorway_link" => 100.0,
+            "trunk" | "trunk_link" => 80.0,
+            "primary" | "primary_link" => 60.0,
+            "secondary" | "secondary_link" => 50.0,
+            "tertiary" | "tertiary_link" => 40.0,
+            "unclassified" | "residential" | "road" => 30.0,
+            "living_street" | "service" => 15.0,
+            "track" => 15.0,
+            "footway" | "path" | "pedestrian" | "steps" | "cycleway" | "bridleway" => 0.0,
+            _ => return None,
+        };
+        Some(speed)
+    }
+
+    fn is_walkable(&self) -> bool {
+        !matches!(self.highway.as_str(), "motorway" | "motorway_link" | "trunk_link")
+    }
+
+    fn is_drivable(&self) -> bool {
+        !matches!(
+            self.highway.as_str(),
+            "footway" | "path" | "pedestrian" | "steps" | "cycleway" | "bridleway"
+        )
+    }
+
+    /// Posted speed in km/h; accepts "50", "50 km/h" and "30 mph"
+    fn parsed_maxspeed(&self) -> Option<f64> {
+        let raw = self.maxspeed.as_deref()?.trim().to_lowercase();
+        let numeric: String = raw.chars().take_while(|c| c.is_ascii_digit() || *c == '.').collect();
+        let value: f64 = numeric.parse().ok()?;
+        if value <= 0.0 {
+            return None;
+        }
+        Some(if raw.ends_with("mph") { value * 1.609_344 } else { value })
+    }
+
+    /// 1 for forward-only, -1 for reverse-only, 0 for two-way
+    fn direction(&self) -> i8 {
+        match self.oneway.as_deref().map(str::trim) {
+            Some("yes") | Some("true") | Some("1") => 1,
+            Some("-1") | Some("reverse") => -1,
+            _ if matches!(self.highway.as_str(), "motorway" | "motorway_link") => 1,
+            _ => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RoadEdge {
+    from: usize,
+    to: usize,
+    length_km: f64,
+    speed_kmh: f64,
+    walkable: bool,
+    drivable: bool,
+}
+
+/// Directed road graph with a spatial index over its nodes
+pub struct RoadGraph {
+    /// Node positions as `[longitude, latitude]`
+    nodes: Vec<[f64; 2]>,
+    edges: Vec<RoadEdge>,
+    adjacency: Vec<Vec<usize>>,
+    node_index: RTree<GeomWithData<[f64; 2], usize>>,
+    top_speed_kmh: f64,
+}
+
+/// Incrementally builds a `RoadGraph`, merging nodes that share a position
+#[derive(Default)]
+pub struct RoadGraphBuilder {
+    nodes: Vec<[f64; 2]>,
+    node_ids: HashMap<(i64, i64), usize>,
+    edges: Vec<RoadEdge>,
+}
+
+impl RoadGraphBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn node(&mut self, lon: f64, lat: f64) -> usize {
+        // ~1 cm precision so shared vertices of adjoining ways become one node
+        let key = ((lon * 1e7).round() as i64, (lat * 1e7).round() as i64);
+        let next = self.nodes.len();
+        *self.node_ids.entry(key).or_insert_with(|| {
+            self.nodes.push([lon, lat]);
+            next
+        })
+    }
+
+    /// Add a way given as `[longitude, latitude]` points; non-road ways are ignored
+    pub fn add_way(&mut self, points: &[[f64; 2]], tags: &RoadTags) {
+        let Some(default_speed) = tags.default_speed() else { return };
+        if matches!(tags.access.as_deref(), Some("no") | Some("private")) || points.len() < 2 {
+            return;
+        }
+
+        let speed_kmh = tags.parsed_maxspeed().unwrap_or(default_speed);
+        let walkable = tags.is_walkable();
+        let drivable = tags.is_drivable() && speed_kmh > 0.0;
+        let direction = tags.direction();
+
+        for pair in points.windows(2) {
+            let from = self.node(pair[0][0], pair[0][1]);
+            let to = self.node(pair[1][0], pair[1][1]);
+            if from == to {
+                continue;
+            }
+            let length_km = haversine_km(self.nodes[from], self.nodes[to]);
+
+            // Pedestrians may walk against one-way traffic
+            self.edges.push(RoadEdge { from, to, length_km, speed_kmh, walkable, drivable: drivable && direction >= 0 });
+            self.edges.push(RoadEdge { from: to, to: from, length_km, speed_kmh, walkable, drivable: drivable && direction <= 0 });
+        }
+    }
+
+    pub fn build(self) -> RoadGraph {
+        let mut adjacency = vec![Vec::new(); self.nodes.len()];
+        for (idx, edge) in self.edges.iter().enumerate() {
+            adjacency[edge.from].push(idx);
+        }
+        let node_index = RTree::bulk_load(
+            self.nodes.iter()
+                .enumerate()
+                .filter(|(idx, _)| !adjacency[*idx].is_empty())
+                .map(|(idx, point)| GeomWithData::new(*point, idx))
+                .collect(),
+        );
+        let top_speed_kmh = self.edges.iter()
+            .filter(|e| e.drivable)
+            .map(|e| e.speed_kmh)
+            .fold(WALKING_SPEED_KMH, f64::max);
+
+        RoadGraph { nodes: self.nodes, edges: self.edges, adjacency, node_index, top_speed_kmh }
+    }
+}
+
+impl RoadGraph {
+    /// Load a road graph from an `.osm.pbf` extract or a GeoJSON file of LineStrings
+    pub fn load<P: AsRef<Path>>(path: P) -> AppResult<Self> {
+        let path = path.as_ref();
+        let name = path.to_string_lossy().to_lowercase();
+        if name.ends_with(".pbf") {
+            Self::from_pbf(path)
+        } else {
+            let content = std::fs::read_to_string(path).map_err(|e| {
+                AppError::Configuration(format!("Cannot read road graph {}: {}", path.display(), e))
+            })?;
+            Self::from_geojson(&content)
+        }
+    }
+
+    /// Build from an OpenStreetMap PBF extract, keeping only `highway=*` ways
+    pub fn from_pbf<P: AsRef<Path>>(path: P) -> AppResult<Self> {
+        use osmpbf::{Element, ElementReader};
+
+        let reader = ElementReader::from_path(path.as_ref()).map_err(|e| {
+            AppError::Configuration(format!("Cannot open road graph {}: {}", path.as_ref().display(), e))
+        })?;
+
+        let mut positions: HashMap<i64, [f64; 2]> = HashMap::new();
+        let mut ways: Vec<(Vec<i64>, RoadTags)> = Vec::new();
+        reader.for_each(|element| match element {
+            Element::Node(node) => { positions.insert(node.id(), [node.lon(), node.lat()]); }
+            Element::DenseNode(node) => { positions.insert(node.id(), [node.lon(), node.lat()]); }
+            Element::Way(way) => {
+                let mut tags = RoadTags::default();
+                for (key, value) in way.tags() {
+                    match key {
+                        "highway" => tags.highway = value.to_string(),
+                        "maxspeed" => tags.maxspeed = Some(value.to_string()),
+                        "oneway" => tags.oneway = Some(value.to_string()),
+                        "access" => tags.access = Some(value.to_string()),
+                        _ => {}
+                    }
+                }
+                if !tags.highway.is_empty() {
+                    ways.push((way.refs().collect(), tags));
+                }
+            }
+            Element::Relation(_) => {}
+        }).map_err(|e| AppError::Configuration(format!("Invalid PBF road graph: {}", e)))?;
+
+        let mut builder = RoadGraphBuilder::new();
+        for (refs, tags) in ways {
+            let points: Vec<[f64; 2]> = refs.iter().filter_map(|id| positions.get(id).copied()).collect();
+            builder.add_way(&points, &tags);
+        }
+        Ok(builder.build())
+    }
+
+    /// Build from a GeoJSON FeatureCollection of LineString/MultiLineString roads
+    /// carrying OSM-style `highway`, `maxspeed` and `oneway` properties
+    pub fn from_geojson(content: &str) -> AppResult<Self> {
+        use geojson::{GeoJson, Value};
+
+        let geojson: GeoJson = content.parse()
+            .map_err(|e| AppError::Configuration(format!("Invalid GeoJSON road graph: {}", e)))?;
+        let GeoJson::FeatureCollection(collection) = geojson else {
+            return Err(AppError::Configuration("Road graph GeoJSON must be a FeatureCollection".to_string()));
+        };
+
+        let property = |feature: &geojson::Feature, key: &str| -> Option<String> {
+            feature.property(key).and_then(|value| match value {
+                serde_json::Value::String(s) => Some(s.clone()),
+                serde_json::Value::Number(n) => Some(n.to_string()),
+                serde_json::Value::Bool(b) => Some(if *b { "yes" } else { "no" }.to_string()),
+                _ => None,
+            })
+        };
+        let to_points = |line: &[Vec<f64>]| -> Vec<[f64; 2]> {
+            line.iter().filter(|p| p.len() >= 2).map(|p| [p[0], p[1]]).collect()
+        };
+
+        let mut builder = RoadGraphBuilder::new();
+        for feature in &collection.features {
+            let tags = RoadTags {
+                highway: property(feature, "highway").unwrap_or_default(),
+                maxspeed: property(feature, "maxspeed"),
+                oneway: property(feature, "oneway"),
+                access: property(feature, "access"),
+            };
+            match feature.geometry.as_ref().map(|g| &g.value) {
+                Some(Value::LineString(line)) => builder.add_way(&to_points(line), &tags),
+                Some(Value::MultiLineString(lines)) => {
+                    for line in lines {
+                        builder.add_way(&to_points(line), &tags);
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(builder.build())
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    fn nearest_node(&self, point: &Coordinates) -> Option<(usize, f64)> {
+        let nearest = self.node_index.nearest_neighbor(&[point.longitude, point.latitude])?;
+        let distance = haversine_km([point.longitude, point.latitude], self.nodes[nearest.data]);
+        Some((nearest.data, distance))
+    }
+
+    fn coordinates(&self, node: usize) -> Coordinates {
+        let [lon, lat] = self.nodes[node];
+        Coordinates { latitude: lat, longitude: lon, altitude: None }
+    }
+
+    fn edge_line(&self, edge: &RoadEdge) -> Line<f64> {
+        let [x1, y1] = self.nodes[edge.from];
+        let [x2, y2] = self.nodes[edge.to];
+        Line::new(Coord { x: x1, y: y1 }, Coord { x: x2, y: y2 })
+    }
+}
+
+/// A closed area; road segments crossing it are not routable
+struct Closure {
+    polygon: Polygon<f64>,
+    bounds: Option<Rect<f64>>,
+}
+
+#[derive(Default)]
+struct ClosureSet {
+    closures: Vec<Closure>,
+    /// Edge index -> indexes of the closures it crosses
+    blocked_edges: HashMap<usize, Vec<usize>>,
+}
+
+/// A* router over a `RoadGraph` with replaceable zone closures
+pub struct RoutingEngine {
+    graph: RoadGraph,
+    closures: RwLock<ClosureSet>,
+}
+
+#[derive(Copy, Clone, PartialEq)]
+struct QueueEntry {
+    estimate: f64,
+    node: usize,
+}
+
+impl Eq for QueueEntry {}
+
+impl Ord for QueueEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed for a min-heap on the estimated total cost
+        other.estimate.total_cmp(&self.estimate)
+    }
+}
+
+impl PartialOrd for QueueEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl RoutingEngine {
+    pub fn new(graph: RoadGraph) -> Self {
+        Self { graph, closures: RwLock::new(ClosureSet::default()) }
+    }
+
+    pub fn graph(&self) -> &RoadGraph {
+        &self.graph
+    }
+
+    /// Replace the closed areas; each polygon is a ring of coordinates
+    pub fn set_closures(&self, areas: &[Vec<Coordinates>]) {
+        let closures: Vec<Closure> = areas.iter()
+            .filter(|ring| ring.len() >= 3)
+            .map(|ring| {
+                let exterior: LineString<f64> = ring.iter()
+                    .map(|c| Coord { x: c.longitude, y: c.latitude })
+                    .collect();
+                let polygon = Polygon::new(exterior, vec![]);
+                let bounds = polygon.bounding_rect();
+                Closure { polygon, bounds }
+            })
+            .collect();
+
+        let mut blocked_edges: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (closure_idx, closure) in closures.iter().enumerate() {
+            let Some(bounds) = closure.bounds else { continue };
+            for (edge_idx, edge) in self.graph.edges.iter().enumerate() {
+                let line = self.graph.edge_line(edge);
+                if line.bounding_rect().intersects(&bounds) && line.intersects(&closure.polygon) {
+                    blocked_edges.entry(edge_idx).or_default().push(closure_idx);
+                }
+            }
+        }
+
+        let mut guard = self.closures.write().unwrap_or_else(|e| e.into_inner());
+        *guard = ClosureSet { closures, blocked_edges };
+    }
+
+    /// Number of active closure areas
+    pub fn closure_count(&self) -> usize {
+        self.closures.read().unwrap_or_else(|e| e.into_inner()).closures.len()
+    }
+
+    /// Shortest-time route between two points, or `None` if they are not connected.
+    /// Closures containing the origin or destination are ignored so people can leave
+    /// (or responders can enter) the affected area itself.
+    pub fn route(&self, from: &Coordinates, to: &Coordinates, mode: TravelMode) -> Option<Route> {
+        let (start, start_offset_km) = self.graph.nearest_node(from)?;
+        let (goal, goal_offset_km) = self.graph.nearest_node(to)?;
+        if start_offset_km > MAX_SNAP_DISTANCE_KM || goal_offset_km > MAX_SNAP_DISTANCE_KM {
+            return None;
+        }
+
+        let closures = self.closures.read().unwrap_or_else(|e| e.into_inner());
+        let exempt: Vec<usize> = closures.closures.iter()
+            .enumerate()
+            .filter(|(_, c)| {
+                c.polygon.contains(&Point::new(from.longitude, from.latitude))
+                    || c.polygon.contains(&Point::new(to.longitude, to.latitude))
+            })
+            .map(|(idx, _)| idx)
+            .collect();
+        let blocking = |edge_idx: usize| {
+            closures.blocked_edges.get(&edge_idx)
+                .into_iter()
+                .flatten()
+                .copied()
+                .filter(|c| !exempt.contains(c))
+        };
+
+        let top_speed = mode.top_speed(self.graph.top_speed_kmh);
+        let goal_point = self.graph.nodes[goal];
+        let heuristic = |node: usize| haversine_km(self.graph.nodes[node], goal_point) / top_speed;
+
+        // A* on travel time in hours
+        let mut best: HashMap<usize, f64> = HashMap::new();
+        let mut came_from: HashMap<usize, usize> = HashMap::new();
+        let mut queue = BinaryHeap::new();
+        let mut avoided: HashSet<usize> = HashSet::new();
+        best.insert(start, 0.0);
+        queue.push(QueueEntry { estimate: heuristic(start), node: start });
+
+        while let Some(QueueEntry { estimate, node }) = queue.pop() {
+            let cost = best[&node];
+            if node == goal {
+                break;
+            }
+            if estimate > cost + heuristic(node) + f64::EPSILON {
+                continue;
+            }
+            for &edge_idx in &self.graph.adjacency[node] {
+                let edge = &self.graph.edges[edge_idx];
+                let Some(speed) = mode.speed_on(edge) else { continue };
+                let mut blocked = false;
+                for closure in blocking(edge_idx) {
+                    avoided.insert(closure);
+                    blocked = true;
+                }
+                if blocked {
+                    continue;
+                }
+                let next_cost = cost + edge.length_km / speed;
+                if best.get(&edge.to).is_none_or(|&known| next_cost < known) {
+                    best.insert(edge.to, next_cost);
+                    came_from.insert(edge.to, edge_idx);
+                    queue.push(QueueEntry { estimate: next_cost + heuristic(edge.to), node: edge.to });
+                }
+            }
+        }
+
+        let travel_hours = *best.get(&goal)?;
+        let mut path = vec![goal];
+        let mut distance_km = 0.0;
+        let mut current = goal;
+        while current != start {
+            let edge = &self.graph.edges[came_from[&current]];
+            distance_km += edge.length_km;
+            current = edge.from;
+            path.push(current);
+        }
+        path.reverse();
+
+        let off_road_km = start_offset_km + goal_offset_km;
+        let mut waypoints = Vec::with_capacity(path.len() + 2);
+        waypoints.push(from.clone());
+        waypoints.extend(path.into_iter().map(|node| self.graph.coordinates(node)));
+        waypoints.push(to.clone());
+
+        Some(Route {
+            distance_km: distance_km + off_road_km,
+            duration_minutes: (travel_hours + off_road_km / mode.off_road_speed()) * 60.0,
+            waypoints,
+            closures_avoided: avoided.len(),
+        })
+    }
+}
+
+fn haversine_km(a: [f64; 2], b: [f64; 2]) -> f64 {
+    GeoCalculations::haversine_distance(
+        &Coordinates { latitude: a[1], longitude: a[0], altitude: None },
+        &Coordinates { latitude: b[1], longitude: b[0], altitude: None },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A square block: the direct road north (west side) and a detour east
+    const GRID: &str = r#"{
+        "type": "FeatureCollection",
+        "features": [
+            { "type": "Feature", "properties": { "highway": "primary", "maxspeed": "60" },
+              "geometry": { "type": "LineString", "coordinates": [[106.80, -6.20], [106.80, -6.18]] } },
+            { "type": "Feature", "properties": { "highway": "residential" },
+              "geometry": { "type": "LineString", "coordinates": [[106.80, -6.20], [106.82, -6.20], [106.82, -6.18], [106.80, -6.18]] } },
+            { "type": "Feature", "properties": { "highway": "footway" },
+              "geometry": { "type": "LineString", "coordinates": [[106.82, -6.20], [106.83, -6.20]] } }
+        ]
+    }"#;
+
+    fn point(lat: f64, lng: f64) -> Coordinates {
+        Coordinates::new(lat, lng).unwrap()
+    }
+
+    #[test]
+    fn routes_over_fastest_roads() {
+        let engine = RoutingEngine::new(RoadGraph::from_geojson(GRID).unwrap());
+        let route = engine
+            .route(&point(-6.20, 106.80), &point(-6.18, 106.80), TravelMode::Driving { max_speed_kmh: 80.0 })
+            .unwrap();
+
+        assert!((route.distance_km - 2.22).abs() < 0.05);
+        assert!((route.duration_minutes - 2.22).abs() < 0.1);
+        assert_eq!(route.waypoints.len(), 4);
+    }
+
+    #[test]
+    fn detours_around_closed_zones() {
+        let engine = RoutingEngine::new(RoadGraph::from_geojson(GRID).unwrap());
+        engine.set_closures(&[vec![
+            point(-6.195, 106.79), point(-6.195, 106.81), point(-6.185, 106.81), point(-6.185, 106.79),
+        ]]);
+
+        let route = engine
+            .route(&point(-6.20, 106.80), &point(-6.18, 106.80), TravelMode::Driving { max_speed_kmh: 80.0 })
+            .unwrap();
+        assert!(route.distance_km > 6.0);
+        assert_eq!(route.closures_avoided, 1);
+    }
+
+    #[test]
+    fn footways_are_walk_only() {
+        let engine = RoutingEngine::new(RoadGraph::from_geojson(GRID).unwrap());
+        let from = point(-6.20, 106.80);
+        let to = point(-6.20, 106.83);
+
+        let walking = engine.route(&from, &to, TravelMode::Walking).unwrap();
+        assert!((walking.duration_minutes - walking.distance_km / WALKING_SPEED_KMH * 60.0).abs() < 0.01);
+        assert!(engine.route(&from, &to, TravelMode::Driving { max_speed_kmh: 80.0 }).is_none());
+    }
+}
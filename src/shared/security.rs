@@ -508,3 +508,21 @@ pub fn generate_secure_password(length: usize) -> String {
     let security = PasswordSecurity::new();
     security.generate_secure_password(length)
 }
+
+/// Random URL-safe token for emailed or texted verification links
+pub fn generate_verification_token() -> String {
+    use base64::Engine;
+    use rand::RngCore;
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// SHA-256 hex digest stored in place of a verification token
+pub fn hash_verification_token(token: &str) -> String {
+    ring::digest::digest(&ring::digest::SHA256, token.trim().as_bytes())
+        .as_ref()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
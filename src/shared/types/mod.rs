@@ -134,7 +134,9 @@ impl UserRole {
                 Permission::ReadAlerts,
                 Permission::ReadPublicData,
                 Permission::UpdateReports,
+                Permission::ValidateReports,
                 Permission::ManageVolunteerResponse,
+                Permission::WriteVolunteerResponse,
             ],
             UserRole::Coordinator => vec![
                 Permission::ReadReports,
@@ -142,7 +144,11 @@ impl UserRole {
                 Permission::ReadAlerts,
                 Permission::ReadPublicData,
                 Permission::UpdateReports,
+                Permission::ValidateReports,
                 Permission::ManageVolunteerResponse,
+                Permission::WriteVolunteerResponse,
+                Permission::ReadVolunteerData,
+                Permission::ManageVolunteers,
                 Permission::ManageEmergencyResponse,
                 Permission::WriteArea,
             ],
@@ -152,11 +158,17 @@ impl UserRole {
                 Permission::ReadAlerts,
                 Permission::ReadPublicData,
                 Permission::UpdateReports,
+                Permission::ValidateReports,
                 Permission::ManageVolunteerResponse,
+                Permission::WriteVolunteerResponse,
+                Permission::ReadVolunteerData,
+                Permission::ManageVolunteers,
                 Permission::ManageEmergencyResponse,
                 Permission::WriteAlerts,
                 Permission::ManageUsers,
                 Permission::ReadAnalytics,
+                // May found organizations; authority inside one comes from its membership role
+                Permission::ManageOrganization,
             ],
             UserRole::SystemAdmin => Permission::all(),
             UserRole::Responder => vec![
@@ -165,7 +177,11 @@ impl UserRole {
                 Permission::ReadAlerts,
                 Permission::ReadPublicData,
                 Permission::UpdateReports,
+                Permission::ValidateReports,
                 Permission::ManageVolunteerResponse,
+                Permission::WriteVolunteerResponse,
+                Permission::ReadVolunteerData,
+                Permission::ManageVolunteers,
                 Permission::ManageEmergencyResponse,
                 Permission::WriteArea,
             ],
@@ -175,6 +191,37 @@ impl UserRole {
     }
 
 
+    /// Snake-case name used in policy files
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserRole::Reporter => "reporter",
+            UserRole::Volunteer => "volunteer",
+            UserRole::Coordinator => "coordinator",
+            UserRole::OrgAdmin => "org_admin",
+            UserRole::SystemAdmin => "system_admin",
+            UserRole::Admin => "admin",
+            UserRole::Citizen => "citizen",
+            UserRole::Responder => "responder",
+            UserRole::SuperAdmin => "super_admin",
+        }
+    }
+
+    /// Inverse of `as_str`, for role names stored in the database
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim() {
+            "reporter" => Some(UserRole::Reporter),
+            "volunteer" => Some(UserRole::Volunteer),
+            "coordinator" => Some(UserRole::Coordinator),
+            "org_admin" => Some(UserRole::OrgAdmin),
+            "system_admin" => Some(UserRole::SystemAdmin),
+            "admin" => Some(UserRole::Admin),
+            "citizen" => Some(UserRole::Citizen),
+            "responder" => Some(UserRole::Responder),
+            "super_admin" => Some(UserRole::SuperAdmin),
+            _ => None,
+        }
+    }
+
     pub fn has_permission(&self, permission: &Permission) -> bool {
         let default_permissions = self.default_permissions();
         default_permissions.contains(permission)
@@ -212,6 +259,7 @@ pub enum Permission {
     DeleteReports,
     ReadOwnReports,
     UpdateReports,
+    ValidateReports,
     // Alert permissions
     ReadAlerts,
     WriteAlerts,
@@ -286,6 +334,7 @@ impl Permission {
             Permission::WriteReports => "reports:write",
             Permission::DeleteReports => "reports:delete",
             Permission::UpdateReports => "reports:update",
+            Permission::ValidateReports => "reports:validate",
             Permission::ReadOwnReports => "reports:read_own",
             Permission::ReadAlerts => "alerts:read",
             Permission::WriteAlerts => "alerts:write",
@@ -337,6 +386,8 @@ impl Permission {
             "reports:write" => Some(Permission::WriteReports),
             "reports:delete" => Some(Permission::DeleteReports),
             "reports:read_own" => Some(Permission::ReadOwnReports),
+            "reports:update" => Some(Permission::UpdateReports),
+            "reports:validate" => Some(Permission::ValidateReports),
             "alerts:read" => Some(Permission::ReadAlerts),
             "alerts:write" => Some(Permission::WriteAlerts),
             "alerts:delete" => Some(Permission::DeleteAlerts),
@@ -375,10 +426,25 @@ impl Permission {
             "notifications:send" => Some(Permission::SendNotifications),
             "notifications:manage_templates" => Some(Permission::ManageNotificationTemplates),
             "notifications:read_history" => Some(Permission::ReadNotificationHistory),
+            "area:write" => Some(Permission::WriteArea),
+            "volunteer:manage" => Some(Permission::ManageVolunteerResponse),
             _ => None,
         }
     }
 
+    /// Permissions that may only be exercised from an MFA-elevated session
+    pub fn requires_mfa(&self) -> bool {
+        matches!(
+            self,
+            Permission::ManageEmergencyResponse
+                | Permission::ManageAlerts
+                | Permission::SendNotifications
+                | Permission::ManageUsers
+                | Permission::DeleteUsers
+                | Permission::ManageSystemConfig
+        )
+    }
+
     /// Get all available permissions
     pub fn all() -> Vec<Self> {
         vec![
@@ -386,6 +452,8 @@ impl Permission {
             Permission::WriteReports,
             Permission::DeleteReports,
             Permission::ReadOwnReports,
+            Permission::UpdateReports,
+            Permission::ValidateReports,
             Permission::ReadAlerts,
             Permission::WriteAlerts,
             Permission::DeleteAlerts,
@@ -396,6 +464,7 @@ impl Permission {
             Permission::ManageVolunteers,
             Permission::WriteVolunteerResponse,
             Permission::ReadVolunteerData,
+            Permission::ManageVolunteerResponse,
             Permission::ManageUsers,
             Permission::ReadUserProfiles,
             Permission::UpdateUserProfiles,
@@ -403,6 +472,7 @@ impl Permission {
             Permission::ReadAnalytics,
             Permission::WriteAnalytics,
             Permission::ReadAdvancedAnalytics,
+            Permission::WriteArea,
             Permission::ManageOrganization,
             Permission::ReadOrgData,
             Permission::WriteOrgData,